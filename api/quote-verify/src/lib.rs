@@ -0,0 +1,642 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    lib.rs
+
+Abstract:
+
+    Host-side verification of QUOTE_PCRS responses against the Caliptra
+    DICE certificate chain.
+
+--*/
+
+use caliptra_api::mailbox::{PcrValue, QuotePcrsResp};
+use caliptra_x509::{
+    FmcAliasCertTbs, FmcAliasCertTbsParams, LocalDevIdCertTbs, LocalDevIdCertTbsParams,
+    RtAliasCertTbs, RtAliasCertTbsParams,
+};
+use openssl::{
+    bn::{BigNum, BigNumContext},
+    ec::{EcGroup, EcKey, PointConversionForm},
+    ecdsa::EcdsaSig,
+    error::ErrorStack,
+    nid::Nid,
+    pkey::{PKey, Public},
+    sha::Sha384,
+    x509::{X509VerifyResult, X509},
+};
+use std::fmt;
+use x509_parser::prelude::{FromDer, X509Certificate};
+
+pub const PCR_COUNT: usize = 32;
+pub const NONCE_SIZE: usize = 32;
+
+/// Certificate in the chain a verification failure refers to
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CertKind {
+    LdevId,
+    FmcAlias,
+    RtAlias,
+}
+
+#[derive(Debug)]
+pub enum QuoteVerifyError {
+    /// The IDevID public key is not a valid P-384 point.
+    InvalidIdevIdKey,
+    /// The certificate is not valid DER.
+    CertParse { cert: CertKind },
+    /// The certificate was not built from the `caliptra-x509` template.
+    TemplateMismatch { cert: CertKind },
+    /// The certificate's issuer does not match the previous certificate.
+    IssuerMismatch { cert: CertKind },
+    /// The certificate signature does not verify with the issuer's key.
+    CertSignatureInvalid { cert: CertKind },
+    /// The quote does not carry the nonce the caller sent.
+    NonceMismatch,
+    /// The quote digest is not the SHA-384 of the PCRs and nonce.
+    DigestMismatch,
+    /// The quote signature does not verify with the PCR signing key.
+    QuoteSignatureInvalid,
+    /// OpenSSL failed while processing otherwise well-formed input.
+    Crypto(ErrorStack),
+}
+
+impl fmt::Display for QuoteVerifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::InvalidIdevIdKey => write!(f, "IDevID public key is not a valid P-384 point"),
+            Self::CertParse { cert } => write!(f, "{cert:?} certificate is not valid DER"),
+            Self::TemplateMismatch { cert } => {
+                write!(f, "{cert:?} certificate does not match its template")
+            }
+            Self::IssuerMismatch { cert } => {
+                write!(f, "{cert:?} certificate was not issued by its parent")
+            }
+            Self::CertSignatureInvalid { cert } => {
+                write!(f, "{cert:?} certificate signature is invalid")
+            }
+            Self::NonceMismatch => write!(f, "quote nonce does not match"),
+            Self::DigestMismatch => write!(f, "quote digest does not match the PCRs and nonce"),
+            Self::QuoteSignatureInvalid => write!(f, "quote signature is invalid"),
+            Self::Crypto(e) => write!(f, "crypto error: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for QuoteVerifyError {}
+
+impl From<ErrorStack> for QuoteVerifyError {
+    fn from(e: ErrorStack) -> Self {
+        Self::Crypto(e)
+    }
+}
+
+/// DER certificates returned by `GET_LDEV_CERT`, `GET_FMC_ALIAS_CERT` and
+/// `GET_RT_ALIAS_CERT`.
+#[derive(Clone, Copy, Debug)]
+pub struct CertChain<'a> {
+    pub ldevid: &'a [u8],
+    pub fmc_alias: &'a [u8],
+    pub rt_alias: &'a [u8],
+}
+
+/// A quote whose signature and certificate chain have been verified
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct VerifiedQuote {
+    pub pcrs: [PcrValue; PCR_COUNT],
+    pub reset_ctrs: [u32; PCR_COUNT],
+    pub nonce: [u8; NONCE_SIZE],
+    /// Uncompressed SEC1 public key that signed the quote
+    pub signing_key: Vec<u8>,
+}
+
+/// Fixed bytes of a `caliptra-x509` TBS template.
+///
+/// The generated templates do not expose their parameter offsets, so the
+/// parameter positions are found by building the template twice with
+/// parameters of all zeros and all ones.
+struct TemplateMask {
+    template: Vec<u8>,
+    is_param: Vec<bool>,
+}
+
+impl TemplateMask {
+    fn new(zeros: &[u8], ones: &[u8]) -> Self {
+        Self {
+            template: zeros.to_vec(),
+            is_param: zeros.iter().zip(ones).map(|(a, b)| a != b).collect(),
+        }
+    }
+
+    fn matches(&self, tbs: &[u8]) -> bool {
+        tbs.len() == self.template.len()
+            && tbs
+                .iter()
+                .zip(&self.template)
+                .zip(&self.is_param)
+                .all(|((a, b), is_param)| *is_param || a == b)
+    }
+}
+
+macro_rules! template_mask {
+    ($tbs:ident, $params:ident { $($field:ident: $len:ident),* $(,)? }) => {{
+        let zeros = $tbs::new(&$params {
+            $($field: &[0x00; $params::$len],)*
+        });
+        let ones = $tbs::new(&$params {
+            $($field: &[0xff; $params::$len],)*
+        });
+        TemplateMask::new(zeros.tbs(), ones.tbs())
+    }};
+}
+
+fn ldevid_template() -> TemplateMask {
+    template_mask!(
+        LocalDevIdCertTbs,
+        LocalDevIdCertTbsParams {
+            public_key: PUBLIC_KEY_LEN,
+            subject_sn: SUBJECT_SN_LEN,
+            issuer_sn: ISSUER_SN_LEN,
+            serial_number: SERIAL_NUMBER_LEN,
+            subject_key_id: SUBJECT_KEY_ID_LEN,
+            authority_key_id: AUTHORITY_KEY_ID_LEN,
+            ueid: UEID_LEN,
+            not_before: NOT_BEFORE_LEN,
+            not_after: NOT_AFTER_LEN,
+        }
+    )
+}
+
+fn fmc_alias_template() -> TemplateMask {
+    template_mask!(
+        FmcAliasCertTbs,
+        FmcAliasCertTbsParams {
+            public_key: PUBLIC_KEY_LEN,
+            subject_sn: SUBJECT_SN_LEN,
+            issuer_sn: ISSUER_SN_LEN,
+            tcb_info_device_info_hash: TCB_INFO_DEVICE_INFO_HASH_LEN,
+            tcb_info_fmc_tci: TCB_INFO_FMC_TCI_LEN,
+            serial_number: SERIAL_NUMBER_LEN,
+            subject_key_id: SUBJECT_KEY_ID_LEN,
+            authority_key_id: AUTHORITY_KEY_ID_LEN,
+            ueid: UEID_LEN,
+            not_before: NOT_BEFORE_LEN,
+            not_after: NOT_AFTER_LEN,
+            tcb_info_flags: TCB_INFO_FLAGS_LEN,
+            tcb_info_fmc_svn: TCB_INFO_FMC_SVN_LEN,
+            tcb_info_fmc_svn_fuses: TCB_INFO_FMC_SVN_FUSES_LEN,
+        }
+    )
+}
+
+fn rt_alias_template() -> TemplateMask {
+    template_mask!(
+        RtAliasCertTbs,
+        RtAliasCertTbsParams {
+            public_key: PUBLIC_KEY_LEN,
+            subject_sn: SUBJECT_SN_LEN,
+            issuer_sn: ISSUER_SN_LEN,
+            tcb_info_rt_tci: TCB_INFO_RT_TCI_LEN,
+            serial_number: SERIAL_NUMBER_LEN,
+            subject_key_id: SUBJECT_KEY_ID_LEN,
+            authority_key_id: AUTHORITY_KEY_ID_LEN,
+            ueid: UEID_LEN,
+            not_before: NOT_BEFORE_LEN,
+            not_after: NOT_AFTER_LEN,
+            tcb_info_rt_svn: TCB_INFO_RT_SVN_LEN,
+        }
+    )
+}
+
+/// Certificate chain verified up to the IDevID key
+pub struct VerifiedCertChain {
+    pub ldevid: X509,
+    pub fmc_alias: X509,
+    pub rt_alias: X509,
+}
+
+fn idevid_key(
+    idev_pub_x: &[u8; 48],
+    idev_pub_y: &[u8; 48],
+) -> Result<PKey<Public>, QuoteVerifyError> {
+    let group = EcGroup::from_curve_name(Nid::SECP384R1)?;
+    let x = BigNum::from_slice(idev_pub_x)?;
+    let y = BigNum::from_slice(idev_pub_y)?;
+    let key = EcKey::from_public_key_affine_coordinates(&group, &x, &y)
+        .map_err(|_| QuoteVerifyError::InvalidIdevIdKey)?;
+    key.check_key()
+        .map_err(|_| QuoteVerifyError::InvalidIdevIdKey)?;
+    Ok(PKey::from_ec_key(key)?)
+}
+
+fn verify_cert(
+    kind: CertKind,
+    der: &[u8],
+    template: &TemplateMask,
+    issuer: Option<&X509>,
+    issuer_key: &PKey<Public>,
+) -> Result<X509, QuoteVerifyError> {
+    let (rest, parsed) =
+        X509Certificate::from_der(der).map_err(|_| QuoteVerifyError::CertParse { cert: kind })?;
+    if !rest.is_empty() {
+        return Err(QuoteVerifyError::CertParse { cert: kind });
+    }
+    if !template.matches(parsed.tbs_certificate.as_ref()) {
+        return Err(QuoteVerifyError::TemplateMismatch { cert: kind });
+    }
+
+    let cert = X509::from_der(der).map_err(|_| QuoteVerifyError::CertParse { cert: kind })?;
+    if let Some(issuer) = issuer {
+        if issuer.issued(&cert) != X509VerifyResult::OK {
+            return Err(QuoteVerifyError::IssuerMismatch { cert: kind });
+        }
+    }
+    if !cert.verify(issuer_key)? {
+        return Err(QuoteVerifyError::CertSignatureInvalid { cert: kind });
+    }
+    Ok(cert)
+}
+
+/// Verify the LDevID, FMC Alias and RT Alias certificates up to the IDevID
+/// public key reported by `GET_IDEV_INFO`.
+///
+/// Every certificate must be signed by its parent and must be an instance of
+/// the `caliptra-x509` template the firmware builds it from.
+pub fn verify_cert_chain(
+    idev_pub_x: &[u8; 48],
+    idev_pub_y: &[u8; 48],
+    chain: &CertChain,
+) -> Result<VerifiedCertChain, QuoteVerifyError> {
+    let idevid_key = idevid_key(idev_pub_x, idev_pub_y)?;
+    let ldevid = verify_cert(
+        CertKind::LdevId,
+        chain.ldevid,
+        &ldevid_template(),
+        None,
+        &idevid_key,
+    )?;
+    let fmc_alias = verify_cert(
+        CertKind::FmcAlias,
+        chain.fmc_alias,
+        &fmc_alias_template(),
+        Some(&ldevid),
+        &ldevid.public_key()?,
+    )?;
+    let rt_alias = verify_cert(
+        CertKind::RtAlias,
+        chain.rt_alias,
+        &rt_alias_template(),
+        Some(&fmc_alias),
+        &fmc_alias.public_key()?,
+    )?;
+    Ok(VerifiedCertChain {
+        ldevid,
+        fmc_alias,
+        rt_alias,
+    })
+}
+
+/// SHA-384 digest the firmware signs for a quote
+pub fn quote_digest(pcrs: &[PcrValue; PCR_COUNT], nonce: &[u8; NONCE_SIZE]) -> [u8; 48] {
+    let mut sha = Sha384::new();
+    pcrs.iter().for_each(|pcr| sha.update(pcr));
+    sha.update(nonce);
+    sha.finish()
+}
+
+/// Verify a `QUOTE_PCRS` response.
+///
+/// The certificate chain is verified with [`verify_cert_chain`]. The quote
+/// must carry `nonce`, its digest must cover the reported PCRs and nonce, and
+/// its signature must verify over that digest.
+///
+/// The firmware signs quotes with the hardware PCR signing key, which is the
+/// FMC Alias key. The RT Alias certificate is verified as part of the chain
+/// but the quote signature is checked against the FMC Alias public key.
+pub fn verify_quote(
+    idev_pub_x: &[u8; 48],
+    idev_pub_y: &[u8; 48],
+    chain: &CertChain,
+    nonce: &[u8; NONCE_SIZE],
+    quote: &QuotePcrsResp,
+) -> Result<VerifiedQuote, QuoteVerifyError> {
+    let chain = verify_cert_chain(idev_pub_x, idev_pub_y, chain)?;
+
+    if &quote.nonce != nonce {
+        return Err(QuoteVerifyError::NonceMismatch);
+    }
+    let digest = quote_digest(&quote.pcrs, &quote.nonce);
+    if quote.digest != digest {
+        return Err(QuoteVerifyError::DigestMismatch);
+    }
+
+    let signing_key = chain.fmc_alias.public_key()?.ec_key()?;
+    let sig = EcdsaSig::from_private_components(
+        BigNum::from_slice(&quote.signature_r)?,
+        BigNum::from_slice(&quote.signature_s)?,
+    )?;
+    if !sig.verify(&digest, &signing_key)? {
+        return Err(QuoteVerifyError::QuoteSignatureInvalid);
+    }
+
+    let mut bn_ctx = BigNumContext::new()?;
+    let signing_key = signing_key.public_key().to_bytes(
+        signing_key.group(),
+        PointConversionForm::UNCOMPRESSED,
+        &mut bn_ctx,
+    )?;
+
+    Ok(VerifiedQuote {
+        pcrs: quote.pcrs,
+        reset_ctrs: quote.reset_ctrs,
+        nonce: quote.nonce,
+        signing_key,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use caliptra_x509::{Ecdsa384CertBuilder, Ecdsa384Signature, NotAfter, NotBefore};
+    use openssl::pkey::Private;
+    use zerocopy::FromZeros;
+
+    struct TestKey {
+        key: EcKey<Private>,
+        pub_key: [u8; 97],
+    }
+
+    impl TestKey {
+        fn new() -> Self {
+            let group = EcGroup::from_curve_name(Nid::SECP384R1).unwrap();
+            let key = EcKey::generate(&group).unwrap();
+            let mut bn_ctx = BigNumContext::new().unwrap();
+            let pub_key = key
+                .public_key()
+                .to_bytes(&group, PointConversionForm::UNCOMPRESSED, &mut bn_ctx)
+                .unwrap();
+            Self {
+                key,
+                pub_key: pub_key.try_into().unwrap(),
+            }
+        }
+
+        fn xy(&self) -> ([u8; 48], [u8; 48]) {
+            (
+                self.pub_key[1..49].try_into().unwrap(),
+                self.pub_key[49..].try_into().unwrap(),
+            )
+        }
+
+        fn sn(&self) -> [u8; 64] {
+            let mut sha = openssl::sha::Sha256::new();
+            sha.update(&self.pub_key);
+            hex::encode_upper(sha.finish())
+                .into_bytes()
+                .try_into()
+                .unwrap()
+        }
+
+        fn key_id(&self) -> [u8; 20] {
+            let mut sha = openssl::sha::Sha1::new();
+            sha.update(&self.pub_key);
+            sha.finish()
+        }
+
+        fn sign(&self, data: &[u8]) -> Ecdsa384Signature {
+            let mut sha = Sha384::new();
+            sha.update(data);
+            let sig = EcdsaSig::sign(&sha.finish(), &self.key).unwrap();
+            Ecdsa384Signature {
+                r: sig.r().to_vec_padded(48).unwrap().try_into().unwrap(),
+                s: sig.s().to_vec_padded(48).unwrap().try_into().unwrap(),
+            }
+        }
+
+        fn sign_cert(&self, tbs: &[u8]) -> Vec<u8> {
+            let sig = self.sign(tbs);
+            let builder = Ecdsa384CertBuilder::new(tbs, &sig).unwrap();
+            let mut buf = vec![0u8; builder.len()];
+            builder.build(&mut buf).unwrap();
+            buf
+        }
+    }
+
+    struct TestChain {
+        idevid: TestKey,
+        fmc_alias: TestKey,
+        ldevid_cert: Vec<u8>,
+        fmc_alias_cert: Vec<u8>,
+        rt_alias_cert: Vec<u8>,
+    }
+
+    impl TestChain {
+        fn new() -> Self {
+            let idevid = TestKey::new();
+            let ldevid = TestKey::new();
+            let fmc_alias = TestKey::new();
+            let rt_alias = TestKey::new();
+
+            let tbs = LocalDevIdCertTbs::new(&LocalDevIdCertTbsParams {
+                public_key: &ldevid.pub_key,
+                subject_sn: &ldevid.sn(),
+                issuer_sn: &idevid.sn(),
+                serial_number: &[0x11; 20],
+                subject_key_id: &ldevid.key_id(),
+                authority_key_id: &idevid.key_id(),
+                ueid: &[0xAB; 17],
+                not_before: &NotBefore::default().value,
+                not_after: &NotAfter::default().value,
+            });
+            let ldevid_cert = idevid.sign_cert(tbs.tbs());
+
+            let tbs = FmcAliasCertTbs::new(&FmcAliasCertTbsParams {
+                public_key: &fmc_alias.pub_key,
+                subject_sn: &fmc_alias.sn(),
+                issuer_sn: &ldevid.sn(),
+                tcb_info_device_info_hash: &[0xCD; 48],
+                tcb_info_fmc_tci: &[0xEF; 48],
+                serial_number: &[0x22; 20],
+                subject_key_id: &fmc_alias.key_id(),
+                authority_key_id: &ldevid.key_id(),
+                ueid: &[0xAB; 17],
+                not_before: &NotBefore::default().value,
+                not_after: &NotAfter::default().value,
+                tcb_info_flags: &[0xB0, 0xB1, 0xB2, 0xB3],
+                tcb_info_fmc_svn: &[0xB7],
+                tcb_info_fmc_svn_fuses: &[0xB8],
+            });
+            let fmc_alias_cert = ldevid.sign_cert(tbs.tbs());
+
+            let tbs = RtAliasCertTbs::new(&RtAliasCertTbsParams {
+                public_key: &rt_alias.pub_key,
+                subject_sn: &rt_alias.sn(),
+                issuer_sn: &fmc_alias.sn(),
+                tcb_info_rt_tci: &[0xCD; 48],
+                serial_number: &[0x33; 20],
+                subject_key_id: &rt_alias.key_id(),
+                authority_key_id: &fmc_alias.key_id(),
+                ueid: &[0xAB; 17],
+                not_before: &NotBefore::default().value,
+                not_after: &NotAfter::default().value,
+                tcb_info_rt_svn: &[0xB9],
+            });
+            let rt_alias_cert = fmc_alias.sign_cert(tbs.tbs());
+
+            Self {
+                idevid,
+                fmc_alias,
+                ldevid_cert,
+                fmc_alias_cert,
+                rt_alias_cert,
+            }
+        }
+
+        fn certs(&self) -> CertChain {
+            CertChain {
+                ldevid: &self.ldevid_cert,
+                fmc_alias: &self.fmc_alias_cert,
+                rt_alias: &self.rt_alias_cert,
+            }
+        }
+
+        fn quote(&self, nonce: &[u8; NONCE_SIZE]) -> QuotePcrsResp {
+            let mut quote = QuotePcrsResp::new_zeroed();
+            for (i, pcr) in quote.pcrs.iter_mut().enumerate() {
+                pcr.fill(i as u8);
+            }
+            quote.reset_ctrs[7] = 1;
+            quote.nonce = *nonce;
+            quote.digest = quote_digest(&quote.pcrs, nonce);
+            let sig = EcdsaSig::sign(&quote.digest, &self.fmc_alias.key).unwrap();
+            quote
+                .signature_r
+                .copy_from_slice(&sig.r().to_vec_padded(48).unwrap());
+            quote
+                .signature_s
+                .copy_from_slice(&sig.s().to_vec_padded(48).unwrap());
+            quote
+        }
+
+        fn verify(
+            &self,
+            nonce: &[u8; NONCE_SIZE],
+            quote: &QuotePcrsResp,
+        ) -> Result<VerifiedQuote, QuoteVerifyError> {
+            let (x, y) = self.idevid.xy();
+            verify_quote(&x, &y, &self.certs(), nonce, quote)
+        }
+    }
+
+    #[test]
+    fn test_verify_quote() {
+        let chain = TestChain::new();
+        let nonce = [0xf5; NONCE_SIZE];
+        let quote = chain.quote(&nonce);
+
+        let verified = chain.verify(&nonce, &quote).unwrap();
+        assert_eq!(verified.pcrs, quote.pcrs);
+        assert_eq!(verified.reset_ctrs[7], 1);
+        assert_eq!(verified.nonce, nonce);
+        assert_eq!(verified.signing_key, chain.fmc_alias.pub_key);
+    }
+
+    #[test]
+    fn test_nonce_mismatch() {
+        let chain = TestChain::new();
+        let quote = chain.quote(&[0xf5; NONCE_SIZE]);
+
+        assert!(matches!(
+            chain.verify(&[0; NONCE_SIZE], &quote),
+            Err(QuoteVerifyError::NonceMismatch)
+        ));
+    }
+
+    #[test]
+    fn test_tampered_pcr() {
+        let chain = TestChain::new();
+        let nonce = [0xf5; NONCE_SIZE];
+        let mut quote = chain.quote(&nonce);
+        quote.pcrs[4][0] ^= 1;
+
+        assert!(matches!(
+            chain.verify(&nonce, &quote),
+            Err(QuoteVerifyError::DigestMismatch)
+        ));
+
+        // Recomputing the digest does not help without the signing key.
+        quote.digest = quote_digest(&quote.pcrs, &nonce);
+        assert!(matches!(
+            chain.verify(&nonce, &quote),
+            Err(QuoteVerifyError::QuoteSignatureInvalid)
+        ));
+    }
+
+    #[test]
+    fn test_wrong_idevid_key() {
+        let chain = TestChain::new();
+        let nonce = [0xf5; NONCE_SIZE];
+        let quote = chain.quote(&nonce);
+        let (x, y) = TestKey::new().xy();
+
+        assert!(matches!(
+            verify_quote(&x, &y, &chain.certs(), &nonce, &quote),
+            Err(QuoteVerifyError::CertSignatureInvalid {
+                cert: CertKind::LdevId
+            })
+        ));
+        assert!(matches!(
+            verify_quote(&[0; 48], &[0; 48], &chain.certs(), &nonce, &quote),
+            Err(QuoteVerifyError::InvalidIdevIdKey)
+        ));
+    }
+
+    #[test]
+    fn test_chain_out_of_order() {
+        let chain = TestChain::new();
+        let (x, y) = chain.idevid.xy();
+
+        // The RT Alias certificate is not an instance of the FMC Alias template.
+        let certs = CertChain {
+            fmc_alias: &chain.rt_alias_cert,
+            ..chain.certs()
+        };
+        assert!(matches!(
+            verify_cert_chain(&x, &y, &certs),
+            Err(QuoteVerifyError::TemplateMismatch {
+                cert: CertKind::FmcAlias
+            })
+        ));
+
+        // An RT Alias certificate from another device is not issued by this
+        // FMC Alias certificate.
+        let other = TestChain::new();
+        let certs = CertChain {
+            rt_alias: &other.rt_alias_cert,
+            ..chain.certs()
+        };
+        assert!(matches!(
+            verify_cert_chain(&x, &y, &certs),
+            Err(QuoteVerifyError::IssuerMismatch {
+                cert: CertKind::RtAlias
+            })
+        ));
+    }
+
+    #[test]
+    fn test_template_mask() {
+        let chain = TestChain::new();
+        let (_, cert) = X509Certificate::from_der(&chain.ldevid_cert).unwrap();
+        let mut tbs = cert.tbs_certificate.as_ref().to_vec();
+        let template = ldevid_template();
+        assert!(template.matches(&tbs));
+
+        // Changing a fixed byte of the template, here the version, is rejected.
+        tbs[8] ^= 1;
+        assert!(!template.matches(&tbs));
+        assert!(!template.matches(&tbs[1..]));
+    }
+}
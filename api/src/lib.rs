@@ -43,4 +43,5 @@ pub enum CaliptraApiError {
     FusesAlreadyIniitalized,
     FuseDoneNotSet,
     StashMeasurementFailed,
+    TransferInvalidChunk,
 }
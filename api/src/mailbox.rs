@@ -30,8 +30,13 @@ impl CommandId {
     pub const INCREMENT_PCR_RESET_COUNTER: Self = Self(0x50435252); // "PCRR"
     pub const QUOTE_PCRS: Self = Self(0x50435251); // "PCRQ"
     pub const EXTEND_PCR: Self = Self(0x50435245); // "PCRE"
+    pub const GET_PCR_LOG: Self = Self(0x504C4F47); // "PLOG"
+    pub const GET_MEASUREMENT_LOG: Self = Self(0x4D4C4F47); // "MLOG"
     pub const ADD_SUBJECT_ALT_NAME: Self = Self(0x414C544E); // "ALTN"
     pub const CERTIFY_KEY_EXTENDED: Self = Self(0x434B4558); // "CKEX"
+    pub const GET_LDEV_MLDSA_CERT: Self = Self(0x4C444D43); // "LDMC"
+    pub const GET_FMC_ALIAS_MLDSA_CERT: Self = Self(0x43454D46); // "CEMF"
+    pub const GET_RT_ALIAS_MLDSA_CERT: Self = Self(0x43454D52); // "CEMR"
 
     /// FIPS module commands.
     /// The status command.
@@ -63,6 +68,29 @@ impl CommandId {
 
     // The revoke exported CDI handle command.
     pub const REVOKE_EXPORTED_CDI_HANDLE: Self = Self(0x5256_4348); // "RVCH"
+
+    // The derive key command.
+    pub const DERIVE_KEY: Self = Self(0x444B_4559); // "DKEY"
+
+    // The revoke derived key command.
+    pub const REVOKE_DERIVED_KEY: Self = Self(0x5256_444B); // "RVDK"
+
+    // The sign with DPE key command.
+    pub const SIGN_WITH_DPE_KEY: Self = Self(0x5357_444B); // "SWDK"
+
+    // The chunked transfer commands.
+    pub const TRANSFER_BEGIN: Self = Self(0x5842_474E); // "XBGN"
+    pub const TRANSFER_CONTINUE: Self = Self(0x5843_4E54); // "XCNT"
+    pub const TRANSFER_WRITE: Self = Self(0x5857_5254); // "XWRT"
+    pub const TRANSFER_END: Self = Self(0x5845_4E44); // "XEND"
+
+    // The background job commands.
+    pub const JOB_START: Self = Self(0x4A53_5452); // "JSTR"
+    pub const JOB_GET_STATUS: Self = Self(0x4A53_5453); // "JSTS"
+    pub const JOB_CANCEL: Self = Self(0x4A43_4E4C); // "JCNL"
+
+    // The get events command.
+    pub const GET_EVENTS: Self = Self(0x4745_5654); // "GEVT"
 }
 
 impl From<u32> for CommandId {
@@ -159,12 +187,25 @@ pub enum MailboxResp {
     GetTaggedTci(GetTaggedTciResp),
     GetRtAliasCert(GetRtAliasCertResp),
     QuotePcrs(QuotePcrsResp),
+    GetPcrLog(GetPcrLogResp),
+    GetMeasurementLog(GetMeasurementLogResp),
     CertifyKeyExtended(CertifyKeyExtendedResp),
     AuthorizeAndStash(AuthorizeAndStashResp),
     GetIdevCsr(GetIdevCsrResp),
     GetFmcAliasCsr(GetFmcAliasCsrResp),
     SignWithExportedEcdsa(SignWithExportedEcdsaResp),
     RevokeExportedCdiHandle(RevokeExportedCdiHandleResp),
+    DeriveKey(DeriveKeyResp),
+    RevokeDerivedKey(RevokeDerivedKeyResp),
+    SignWithDpeKey(SignWithDpeKeyResp),
+    GetLdevMldsaCert(GetLdevMldsaCertResp),
+    GetFmcAliasMldsaCert(GetFmcAliasMldsaCertResp),
+    GetRtAliasMldsaCert(GetRtAliasMldsaCertResp),
+    TransferBegin(TransferBeginResp),
+    TransferContinue(TransferContinueResp),
+    JobStart(JobStartResp),
+    JobGetStatus(JobGetStatusResp),
+    GetEvents(GetEventsResp),
 }
 
 impl MailboxResp {
@@ -183,12 +224,25 @@ impl MailboxResp {
             MailboxResp::GetFmcAliasCert(resp) => resp.as_bytes_partial(),
             MailboxResp::GetRtAliasCert(resp) => resp.as_bytes_partial(),
             MailboxResp::QuotePcrs(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetPcrLog(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetMeasurementLog(resp) => Ok(resp.as_bytes()),
             MailboxResp::CertifyKeyExtended(resp) => Ok(resp.as_bytes()),
             MailboxResp::AuthorizeAndStash(resp) => Ok(resp.as_bytes()),
             MailboxResp::GetIdevCsr(resp) => Ok(resp.as_bytes()),
             MailboxResp::GetFmcAliasCsr(resp) => Ok(resp.as_bytes()),
             MailboxResp::SignWithExportedEcdsa(resp) => Ok(resp.as_bytes()),
             MailboxResp::RevokeExportedCdiHandle(resp) => Ok(resp.as_bytes()),
+            MailboxResp::DeriveKey(resp) => Ok(resp.as_bytes()),
+            MailboxResp::RevokeDerivedKey(resp) => Ok(resp.as_bytes()),
+            MailboxResp::SignWithDpeKey(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetLdevMldsaCert(resp) => resp.as_bytes_partial(),
+            MailboxResp::GetFmcAliasMldsaCert(resp) => resp.as_bytes_partial(),
+            MailboxResp::GetRtAliasMldsaCert(resp) => resp.as_bytes_partial(),
+            MailboxResp::TransferBegin(resp) => Ok(resp.as_bytes()),
+            MailboxResp::TransferContinue(resp) => resp.as_bytes_partial(),
+            MailboxResp::JobStart(resp) => Ok(resp.as_bytes()),
+            MailboxResp::JobGetStatus(resp) => Ok(resp.as_bytes()),
+            MailboxResp::GetEvents(resp) => Ok(resp.as_bytes()),
         }
     }
 
@@ -207,12 +261,25 @@ impl MailboxResp {
             MailboxResp::GetFmcAliasCert(resp) => resp.as_bytes_partial_mut(),
             MailboxResp::GetRtAliasCert(resp) => resp.as_bytes_partial_mut(),
             MailboxResp::QuotePcrs(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetPcrLog(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetMeasurementLog(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::CertifyKeyExtended(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::AuthorizeAndStash(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::GetIdevCsr(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::GetFmcAliasCsr(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::SignWithExportedEcdsa(resp) => Ok(resp.as_mut_bytes()),
             MailboxResp::RevokeExportedCdiHandle(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::DeriveKey(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::RevokeDerivedKey(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::SignWithDpeKey(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetLdevMldsaCert(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::GetFmcAliasMldsaCert(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::GetRtAliasMldsaCert(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::TransferBegin(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::TransferContinue(resp) => resp.as_bytes_partial_mut(),
+            MailboxResp::JobStart(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::JobGetStatus(resp) => Ok(resp.as_mut_bytes()),
+            MailboxResp::GetEvents(resp) => Ok(resp.as_mut_bytes()),
         }
     }
 
@@ -267,12 +334,28 @@ pub enum MailboxReq {
     IncrementPcrResetCounter(IncrementPcrResetCounterReq),
     QuotePcrs(QuotePcrsReq),
     ExtendPcr(ExtendPcrReq),
+    GetPcrLog(GetPcrLogReq),
+    GetMeasurementLog(GetMeasurementLogReq),
     AddSubjectAltName(AddSubjectAltNameReq),
     CertifyKeyExtended(CertifyKeyExtendedReq),
     SetAuthManifest(SetAuthManifestReq),
     AuthorizeAndStash(AuthorizeAndStashReq),
     SignWithExportedEcdsa(SignWithExportedEcdsaReq),
     RevokeExportedCdiHandle(RevokeExportedCdiHandleReq),
+    DeriveKey(DeriveKeyReq),
+    RevokeDerivedKey(RevokeDerivedKeyReq),
+    SignWithDpeKey(SignWithDpeKeyReq),
+    GetLdevMldsaCert(GetLdevMldsaCertReq),
+    GetFmcAliasMldsaCert(GetFmcAliasMldsaCertReq),
+    GetRtAliasMldsaCert(GetRtAliasMldsaCertReq),
+    TransferBegin(TransferBeginReq),
+    TransferContinue(TransferContinueReq),
+    TransferWrite(TransferWriteReq),
+    TransferEnd(TransferEndReq),
+    JobStart(JobStartReq),
+    JobGetStatus(JobGetStatusReq),
+    JobCancel(JobCancelReq),
+    GetEvents(MailboxReqHeader),
 }
 
 impl MailboxReq {
@@ -294,12 +377,28 @@ impl MailboxReq {
             MailboxReq::IncrementPcrResetCounter(req) => Ok(req.as_bytes()),
             MailboxReq::QuotePcrs(req) => Ok(req.as_bytes()),
             MailboxReq::ExtendPcr(req) => Ok(req.as_bytes()),
+            MailboxReq::GetPcrLog(req) => Ok(req.as_bytes()),
+            MailboxReq::GetMeasurementLog(req) => Ok(req.as_bytes()),
             MailboxReq::AddSubjectAltName(req) => req.as_bytes_partial(),
             MailboxReq::CertifyKeyExtended(req) => Ok(req.as_bytes()),
             MailboxReq::SetAuthManifest(req) => Ok(req.as_bytes()),
             MailboxReq::AuthorizeAndStash(req) => Ok(req.as_bytes()),
             MailboxReq::SignWithExportedEcdsa(req) => Ok(req.as_bytes()),
             MailboxReq::RevokeExportedCdiHandle(req) => Ok(req.as_bytes()),
+            MailboxReq::DeriveKey(req) => Ok(req.as_bytes()),
+            MailboxReq::RevokeDerivedKey(req) => Ok(req.as_bytes()),
+            MailboxReq::SignWithDpeKey(req) => Ok(req.as_bytes()),
+            MailboxReq::GetLdevMldsaCert(req) => Ok(req.as_bytes()),
+            MailboxReq::GetFmcAliasMldsaCert(req) => Ok(req.as_bytes()),
+            MailboxReq::GetRtAliasMldsaCert(req) => Ok(req.as_bytes()),
+            MailboxReq::TransferBegin(req) => Ok(req.as_bytes()),
+            MailboxReq::TransferContinue(req) => Ok(req.as_bytes()),
+            MailboxReq::TransferWrite(req) => Ok(req.as_bytes()),
+            MailboxReq::TransferEnd(req) => Ok(req.as_bytes()),
+            MailboxReq::JobStart(req) => Ok(req.as_bytes()),
+            MailboxReq::JobGetStatus(req) => Ok(req.as_bytes()),
+            MailboxReq::JobCancel(req) => Ok(req.as_bytes()),
+            MailboxReq::GetEvents(req) => Ok(req.as_bytes()),
         }
     }
 
@@ -321,12 +420,28 @@ impl MailboxReq {
             MailboxReq::IncrementPcrResetCounter(req) => Ok(req.as_mut_bytes()),
             MailboxReq::QuotePcrs(req) => Ok(req.as_mut_bytes()),
             MailboxReq::ExtendPcr(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::GetPcrLog(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::GetMeasurementLog(req) => Ok(req.as_mut_bytes()),
             MailboxReq::AddSubjectAltName(req) => req.as_bytes_partial_mut(),
             MailboxReq::CertifyKeyExtended(req) => Ok(req.as_mut_bytes()),
             MailboxReq::SetAuthManifest(req) => Ok(req.as_mut_bytes()),
             MailboxReq::AuthorizeAndStash(req) => Ok(req.as_mut_bytes()),
             MailboxReq::SignWithExportedEcdsa(req) => Ok(req.as_mut_bytes()),
             MailboxReq::RevokeExportedCdiHandle(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::DeriveKey(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::RevokeDerivedKey(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::SignWithDpeKey(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::GetLdevMldsaCert(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::GetFmcAliasMldsaCert(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::GetRtAliasMldsaCert(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::TransferBegin(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::TransferContinue(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::TransferWrite(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::TransferEnd(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::JobStart(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::JobGetStatus(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::JobCancel(req) => Ok(req.as_mut_bytes()),
+            MailboxReq::GetEvents(req) => Ok(req.as_mut_bytes()),
         }
     }
 
@@ -348,12 +463,28 @@ impl MailboxReq {
             MailboxReq::IncrementPcrResetCounter(_) => CommandId::INCREMENT_PCR_RESET_COUNTER,
             MailboxReq::QuotePcrs(_) => CommandId::QUOTE_PCRS,
             MailboxReq::ExtendPcr(_) => CommandId::EXTEND_PCR,
+            MailboxReq::GetPcrLog(_) => CommandId::GET_PCR_LOG,
+            MailboxReq::GetMeasurementLog(_) => CommandId::GET_MEASUREMENT_LOG,
             MailboxReq::AddSubjectAltName(_) => CommandId::ADD_SUBJECT_ALT_NAME,
             MailboxReq::CertifyKeyExtended(_) => CommandId::CERTIFY_KEY_EXTENDED,
             MailboxReq::SetAuthManifest(_) => CommandId::SET_AUTH_MANIFEST,
             MailboxReq::AuthorizeAndStash(_) => CommandId::AUTHORIZE_AND_STASH,
             MailboxReq::SignWithExportedEcdsa(_) => CommandId::SIGN_WITH_EXPORTED_ECDSA,
             MailboxReq::RevokeExportedCdiHandle(_) => CommandId::REVOKE_EXPORTED_CDI_HANDLE,
+            MailboxReq::DeriveKey(_) => CommandId::DERIVE_KEY,
+            MailboxReq::RevokeDerivedKey(_) => CommandId::REVOKE_DERIVED_KEY,
+            MailboxReq::SignWithDpeKey(_) => CommandId::SIGN_WITH_DPE_KEY,
+            MailboxReq::GetLdevMldsaCert(_) => CommandId::GET_LDEV_MLDSA_CERT,
+            MailboxReq::GetFmcAliasMldsaCert(_) => CommandId::GET_FMC_ALIAS_MLDSA_CERT,
+            MailboxReq::GetRtAliasMldsaCert(_) => CommandId::GET_RT_ALIAS_MLDSA_CERT,
+            MailboxReq::TransferBegin(_) => CommandId::TRANSFER_BEGIN,
+            MailboxReq::TransferContinue(_) => CommandId::TRANSFER_CONTINUE,
+            MailboxReq::TransferWrite(_) => CommandId::TRANSFER_WRITE,
+            MailboxReq::TransferEnd(_) => CommandId::TRANSFER_END,
+            MailboxReq::JobStart(_) => CommandId::JOB_START,
+            MailboxReq::JobGetStatus(_) => CommandId::JOB_GET_STATUS,
+            MailboxReq::JobCancel(_) => CommandId::JOB_CANCEL,
+            MailboxReq::GetEvents(_) => CommandId::GET_EVENTS,
         }
     }
 
@@ -549,6 +680,117 @@ impl Default for GetRtAliasCertResp {
     }
 }
 
+// GET_LDEV_MLDSA_CERT
+#[repr(C)]
+#[derive(Default, Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetLdevMldsaCertReq {
+    pub header: MailboxReqHeader,
+}
+impl Request for GetLdevMldsaCertReq {
+    const ID: CommandId = CommandId::GET_LDEV_MLDSA_CERT;
+    type Resp = GetLdevMldsaCertResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetLdevMldsaCertResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; GetLdevMldsaCertResp::DATA_MAX_SIZE], // variable length
+}
+impl GetLdevMldsaCertResp {
+    pub const DATA_MAX_SIZE: usize = 8 * 1024;
+
+    pub fn data(&self) -> Option<&[u8]> {
+        self.data.get(..self.data_size as usize)
+    }
+}
+impl ResponseVarSize for GetLdevMldsaCertResp {}
+
+impl Default for GetLdevMldsaCertResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; GetLdevMldsaCertResp::DATA_MAX_SIZE],
+        }
+    }
+}
+
+// GET_FMC_ALIAS_MLDSA_CERT
+#[repr(C)]
+#[derive(Default, Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetFmcAliasMldsaCertReq {
+    pub header: MailboxReqHeader,
+}
+impl Request for GetFmcAliasMldsaCertReq {
+    const ID: CommandId = CommandId::GET_FMC_ALIAS_MLDSA_CERT;
+    type Resp = GetFmcAliasMldsaCertResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetFmcAliasMldsaCertResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; GetFmcAliasMldsaCertResp::DATA_MAX_SIZE], // variable length
+}
+impl GetFmcAliasMldsaCertResp {
+    pub const DATA_MAX_SIZE: usize = 8 * 1024;
+
+    pub fn data(&self) -> Option<&[u8]> {
+        self.data.get(..self.data_size as usize)
+    }
+}
+impl ResponseVarSize for GetFmcAliasMldsaCertResp {}
+
+impl Default for GetFmcAliasMldsaCertResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; GetFmcAliasMldsaCertResp::DATA_MAX_SIZE],
+        }
+    }
+}
+
+// GET_RT_ALIAS_MLDSA_CERT
+#[repr(C)]
+#[derive(Default, Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetRtAliasMldsaCertReq {
+    pub header: MailboxReqHeader,
+}
+impl Request for GetRtAliasMldsaCertReq {
+    const ID: CommandId = CommandId::GET_RT_ALIAS_MLDSA_CERT;
+    type Resp = GetRtAliasMldsaCertResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetRtAliasMldsaCertResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; GetRtAliasMldsaCertResp::DATA_MAX_SIZE], // variable length
+}
+impl GetRtAliasMldsaCertResp {
+    pub const DATA_MAX_SIZE: usize = 8 * 1024;
+
+    pub fn data(&self) -> Option<&[u8]> {
+        self.data.get(..self.data_size as usize)
+    }
+}
+impl ResponseVarSize for GetRtAliasMldsaCertResp {}
+
+impl Default for GetRtAliasMldsaCertResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; GetRtAliasMldsaCertResp::DATA_MAX_SIZE],
+        }
+    }
+}
+
 // ECDSA384_SIGNATURE_VERIFY
 #[repr(C)]
 #[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
@@ -965,6 +1207,102 @@ impl Request for QuotePcrsReq {
     type Resp = QuotePcrsResp;
 }
 
+/// Size of a serialized `PcrLogEntry` (see drivers/src/pcr_log.rs)
+pub const PCR_LOG_ENTRY_SIZE: usize = 56;
+
+/// Size of a serialized `MeasurementLogEntry` (see drivers/src/pcr_log.rs)
+pub const MEASUREMENT_LOG_ENTRY_SIZE: usize = 116;
+
+/// GET_PCR_LOG input arguments
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetPcrLogReq {
+    pub hdr: MailboxReqHeader,
+    /// Index of the first log entry to return
+    pub start_index: u32,
+}
+
+impl Request for GetPcrLogReq {
+    const ID: CommandId = CommandId::GET_PCR_LOG;
+    type Resp = GetPcrLogResp;
+}
+
+/// GET_PCR_LOG output
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetPcrLogResp {
+    pub hdr: MailboxRespHeader,
+    /// Total number of entries in the log
+    pub total_entries: u32,
+    /// Number of valid entries in `entries`
+    pub entry_count: u32,
+    pub entries: [[u8; PCR_LOG_ENTRY_SIZE]; GetPcrLogResp::MAX_ENTRIES],
+}
+impl GetPcrLogResp {
+    pub const MAX_ENTRIES: usize = 8;
+
+    pub fn entries(&self) -> Option<&[[u8; PCR_LOG_ENTRY_SIZE]]> {
+        self.entries.get(..self.entry_count as usize)
+    }
+}
+impl Response for GetPcrLogResp {}
+
+impl Default for GetPcrLogResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            total_entries: 0,
+            entry_count: 0,
+            entries: [[0u8; PCR_LOG_ENTRY_SIZE]; GetPcrLogResp::MAX_ENTRIES],
+        }
+    }
+}
+
+/// GET_MEASUREMENT_LOG input arguments
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetMeasurementLogReq {
+    pub hdr: MailboxReqHeader,
+    /// Index of the first log entry to return
+    pub start_index: u32,
+}
+
+impl Request for GetMeasurementLogReq {
+    const ID: CommandId = CommandId::GET_MEASUREMENT_LOG;
+    type Resp = GetMeasurementLogResp;
+}
+
+/// GET_MEASUREMENT_LOG output
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
+pub struct GetMeasurementLogResp {
+    pub hdr: MailboxRespHeader,
+    /// Total number of entries in the log
+    pub total_entries: u32,
+    /// Number of valid entries in `entries`
+    pub entry_count: u32,
+    pub entries: [[u8; MEASUREMENT_LOG_ENTRY_SIZE]; GetMeasurementLogResp::MAX_ENTRIES],
+}
+impl GetMeasurementLogResp {
+    pub const MAX_ENTRIES: usize = 4;
+
+    pub fn entries(&self) -> Option<&[[u8; MEASUREMENT_LOG_ENTRY_SIZE]]> {
+        self.entries.get(..self.entry_count as usize)
+    }
+}
+impl Response for GetMeasurementLogResp {}
+
+impl Default for GetMeasurementLogResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            total_entries: 0,
+            entry_count: 0,
+            entries: [[0u8; MEASUREMENT_LOG_ENTRY_SIZE]; GetMeasurementLogResp::MAX_ENTRIES],
+        }
+    }
+}
+
 // SET_AUTH_MANIFEST
 #[repr(C)]
 #[derive(Debug, IntoBytes, FromBytes, Immutable, KnownLayout, PartialEq, Eq)]
@@ -1164,6 +1502,537 @@ pub struct RevokeExportedCdiHandleResp {
     pub hdr: MailboxRespHeader,
 }
 
+// DERIVE_KEY
+bitflags::bitflags! {
+    pub struct DeriveKeyFlags : u32 {
+        /// Return the derived key instead of a handle to it
+        const EXPORT = 0x1;
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct DeriveKeyReq {
+    pub hdr: MailboxReqHeader,
+    pub exported_cdi_handle: [u8; Self::EXPORTED_CDI_MAX_SIZE],
+    pub flags: u32,
+    pub label_size: u32,
+    pub label: [u8; Self::MAX_LABEL_SIZE],
+    pub context_size: u32,
+    pub context: [u8; Self::MAX_CONTEXT_SIZE],
+}
+
+impl Default for DeriveKeyReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            exported_cdi_handle: [0u8; Self::EXPORTED_CDI_MAX_SIZE],
+            flags: 0,
+            label_size: 0,
+            label: [0u8; Self::MAX_LABEL_SIZE],
+            context_size: 0,
+            context: [0u8; Self::MAX_CONTEXT_SIZE],
+        }
+    }
+}
+
+impl DeriveKeyReq {
+    pub const EXPORTED_CDI_MAX_SIZE: usize = 32;
+    pub const MAX_LABEL_SIZE: usize = 48;
+    pub const MAX_CONTEXT_SIZE: usize = 64;
+}
+
+impl Request for DeriveKeyReq {
+    const ID: CommandId = CommandId::DERIVE_KEY;
+    type Resp = DeriveKeyResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct DeriveKeyResp {
+    pub hdr: MailboxRespHeader,
+    /// `DeriveKeyFlags::EXPORT` if `key` holds the derived key
+    pub flags: u32,
+    pub key: [u8; Self::KEY_SIZE],
+    pub key_handle: [u8; Self::KEY_HANDLE_SIZE],
+}
+
+impl Default for DeriveKeyResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            flags: 0,
+            key: [0u8; Self::KEY_SIZE],
+            key_handle: [0u8; Self::KEY_HANDLE_SIZE],
+        }
+    }
+}
+
+impl DeriveKeyResp {
+    pub const KEY_SIZE: usize = 48;
+    pub const KEY_HANDLE_SIZE: usize = 32;
+}
+
+impl Response for DeriveKeyResp {}
+
+// REVOKE_DERIVED_KEY
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct RevokeDerivedKeyReq {
+    pub hdr: MailboxReqHeader,
+    pub key_handle: [u8; DeriveKeyResp::KEY_HANDLE_SIZE],
+}
+
+impl Default for RevokeDerivedKeyReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            key_handle: [0u8; DeriveKeyResp::KEY_HANDLE_SIZE],
+        }
+    }
+}
+
+impl Request for RevokeDerivedKeyReq {
+    const ID: CommandId = CommandId::REVOKE_DERIVED_KEY;
+    type Resp = RevokeDerivedKeyResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct RevokeDerivedKeyResp {
+    pub hdr: MailboxRespHeader,
+}
+
+impl Response for RevokeDerivedKeyResp {}
+
+// SIGN_WITH_DPE_KEY
+bitflags::bitflags! {
+    pub struct SignWithDpeKeyFlags : u32 {
+        /// Sign with HMAC-SHA384 instead of ECDSA-P384
+        const HMAC = 0x1;
+    }
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct SignWithDpeKeyReq {
+    pub hdr: MailboxReqHeader,
+    /// Tag given to the DPE context by DPE_TAG_TCI
+    pub tag: u32,
+    pub flags: u32,
+    pub label: [u8; Self::LABEL_SIZE],
+    pub digest: [u8; Self::DIGEST_SIZE],
+}
+
+impl Default for SignWithDpeKeyReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            tag: 0,
+            flags: 0,
+            label: [0u8; Self::LABEL_SIZE],
+            digest: [0u8; Self::DIGEST_SIZE],
+        }
+    }
+}
+
+impl SignWithDpeKeyReq {
+    pub const LABEL_SIZE: usize = 48;
+    pub const DIGEST_SIZE: usize = 48;
+}
+
+impl Request for SignWithDpeKeyReq {
+    const ID: CommandId = CommandId::SIGN_WITH_DPE_KEY;
+    type Resp = SignWithDpeKeyResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct SignWithDpeKeyResp {
+    pub hdr: MailboxRespHeader,
+    /// Handle of the signing context after DPE rotated it
+    pub new_context_handle: [u8; Self::CONTEXT_HANDLE_SIZE],
+    /// Zero for HMAC
+    pub derived_pubkey_x: [u8; Self::ECC384_SCALAR_BYTE_SIZE],
+    /// Zero for HMAC
+    pub derived_pubkey_y: [u8; Self::ECC384_SCALAR_BYTE_SIZE],
+    /// ECDSA R, or the HMAC tag
+    pub signature_r: [u8; Self::ECC384_SCALAR_BYTE_SIZE],
+    /// ECDSA S, zero for HMAC
+    pub signature_s: [u8; Self::ECC384_SCALAR_BYTE_SIZE],
+    /// Zero for HMAC
+    pub cert_size: u32,
+    pub cert: [u8; Self::MAX_CERT_SIZE],
+}
+
+impl Default for SignWithDpeKeyResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            new_context_handle: [0u8; Self::CONTEXT_HANDLE_SIZE],
+            derived_pubkey_x: [0u8; Self::ECC384_SCALAR_BYTE_SIZE],
+            derived_pubkey_y: [0u8; Self::ECC384_SCALAR_BYTE_SIZE],
+            signature_r: [0u8; Self::ECC384_SCALAR_BYTE_SIZE],
+            signature_s: [0u8; Self::ECC384_SCALAR_BYTE_SIZE],
+            cert_size: 0,
+            cert: [0u8; Self::MAX_CERT_SIZE],
+        }
+    }
+}
+
+impl SignWithDpeKeyResp {
+    pub const CONTEXT_HANDLE_SIZE: usize = 16;
+    pub const ECC384_SCALAR_BYTE_SIZE: usize = 48;
+    pub const MAX_CERT_SIZE: usize = 6144;
+}
+
+impl Response for SignWithDpeKeyResp {}
+
+/// Data moved with the TRANSFER_* commands
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TransferSource {
+    Invalid = 0,
+    MeasurementLog,
+    PcrLog,
+    CertChain,
+    /// Request of another command, written by the caller with TRANSFER_WRITE
+    Caller,
+}
+
+impl From<u32> for TransferSource {
+    fn from(val: u32) -> Self {
+        match val {
+            1_u32 => TransferSource::MeasurementLog,
+            2_u32 => TransferSource::PcrLog,
+            3_u32 => TransferSource::CertChain,
+            4_u32 => TransferSource::Caller,
+            _ => TransferSource::Invalid,
+        }
+    }
+}
+
+// TRANSFER_BEGIN
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct TransferBeginReq {
+    pub hdr: MailboxReqHeader,
+    /// A `TransferSource`
+    pub source: u32,
+    /// Command run on the written data at TRANSFER_END, for
+    /// `TransferSource::Caller`
+    pub cmd: u32,
+    /// Size in bytes of the data to write, for `TransferSource::Caller`
+    pub total_size: u32,
+}
+
+impl Request for TransferBeginReq {
+    const ID: CommandId = CommandId::TRANSFER_BEGIN;
+    type Resp = TransferBeginResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct TransferBeginResp {
+    pub hdr: MailboxRespHeader,
+    pub transfer_id: u32,
+    /// Size in bytes of the data to transfer
+    pub total_size: u32,
+}
+
+impl Response for TransferBeginResp {}
+
+// TRANSFER_CONTINUE
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct TransferContinueReq {
+    pub hdr: MailboxReqHeader,
+    pub transfer_id: u32,
+    /// Offset in bytes of the chunk to return
+    pub offset: u32,
+}
+
+impl Request for TransferContinueReq {
+    const ID: CommandId = CommandId::TRANSFER_CONTINUE;
+    type Resp = TransferContinueResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct TransferContinueResp {
+    pub hdr: MailboxRespHeader,
+    pub data_size: u32,
+    pub data: [u8; Self::MAX_CHUNK_SIZE], // variable length
+}
+
+impl Default for TransferContinueResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            data_size: 0,
+            data: [0u8; Self::MAX_CHUNK_SIZE],
+        }
+    }
+}
+
+impl TransferContinueResp {
+    pub const MAX_CHUNK_SIZE: usize = 4096;
+}
+
+impl ResponseVarSize for TransferContinueResp {}
+
+// TRANSFER_WRITE
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct TransferWriteReq {
+    pub hdr: MailboxReqHeader,
+    pub transfer_id: u32,
+    /// Offset in bytes of the chunk, following the previous chunk
+    pub offset: u32,
+    pub data_size: u32,
+    pub data: [u8; Self::MAX_CHUNK_SIZE],
+}
+
+impl Default for TransferWriteReq {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxReqHeader::default(),
+            transfer_id: 0,
+            offset: 0,
+            data_size: 0,
+            data: [0u8; Self::MAX_CHUNK_SIZE],
+        }
+    }
+}
+
+impl TransferWriteReq {
+    pub const MAX_CHUNK_SIZE: usize = 4096;
+}
+
+impl Request for TransferWriteReq {
+    const ID: CommandId = CommandId::TRANSFER_WRITE;
+    type Resp = MailboxRespHeader;
+}
+
+// TRANSFER_END
+// Output args are those of the command run on the written data, if any
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct TransferEndReq {
+    pub hdr: MailboxReqHeader,
+    pub transfer_id: u32,
+}
+
+impl Request for TransferEndReq {
+    const ID: CommandId = CommandId::TRANSFER_END;
+    type Resp = MailboxRespHeader;
+}
+
+/// Operation run by a background job
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobKind {
+    Invalid = 0,
+    Kat,
+    SelfTest,
+    Sha384,
+    Mldsa87Verify,
+}
+
+impl From<u32> for JobKind {
+    fn from(val: u32) -> Self {
+        match val {
+            1_u32 => JobKind::Kat,
+            2_u32 => JobKind::SelfTest,
+            3_u32 => JobKind::Sha384,
+            4_u32 => JobKind::Mldsa87Verify,
+            _ => JobKind::Invalid,
+        }
+    }
+}
+
+/// Input of a `JobKind::Mldsa87Verify` job. The fields are in the byte
+/// order of FIPS 204.
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct Mldsa87VerifyJobInput {
+    pub pub_key: [u8; 2592],
+    /// The signature, padded with one zero byte
+    pub signature: [u8; 4628],
+    pub message: [u8; 64],
+}
+
+impl Default for Mldsa87VerifyJobInput {
+    fn default() -> Self {
+        Self {
+            pub_key: [0u8; 2592],
+            signature: [0u8; 4628],
+            message: [0u8; 64],
+        }
+    }
+}
+
+/// State of a background job
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum JobState {
+    Invalid = 0,
+    Running,
+    Done,
+    Cancelled,
+    Failed,
+}
+
+impl From<u32> for JobState {
+    fn from(val: u32) -> Self {
+        match val {
+            1_u32 => JobState::Running,
+            2_u32 => JobState::Done,
+            3_u32 => JobState::Cancelled,
+            4_u32 => JobState::Failed,
+            _ => JobState::Invalid,
+        }
+    }
+}
+
+// JOB_START
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct JobStartReq {
+    pub hdr: MailboxReqHeader,
+    /// A `JobKind`
+    pub kind: u32,
+}
+
+impl Request for JobStartReq {
+    const ID: CommandId = CommandId::JOB_START;
+    type Resp = JobStartResp;
+}
+
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct JobStartResp {
+    pub hdr: MailboxRespHeader,
+    pub job_id: u32,
+}
+
+impl Response for JobStartResp {}
+
+// JOB_GET_STATUS
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct JobGetStatusReq {
+    pub hdr: MailboxReqHeader,
+    pub job_id: u32,
+}
+
+impl Request for JobGetStatusReq {
+    const ID: CommandId = CommandId::JOB_GET_STATUS;
+    type Resp = JobGetStatusResp;
+}
+
+#[repr(C)]
+#[derive(Debug, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct JobGetStatusResp {
+    pub hdr: MailboxRespHeader,
+    /// A `JobState`
+    pub state: u32,
+    pub steps_done: u32,
+    pub total_steps: u32,
+    pub result_size: u32,
+    /// Output of a finished job, such as a digest
+    pub result: [u8; Self::MAX_RESULT_SIZE],
+}
+
+impl Default for JobGetStatusResp {
+    fn default() -> Self {
+        Self {
+            hdr: MailboxRespHeader::default(),
+            state: 0,
+            steps_done: 0,
+            total_steps: 0,
+            result_size: 0,
+            result: [0u8; Self::MAX_RESULT_SIZE],
+        }
+    }
+}
+
+impl JobGetStatusResp {
+    pub const MAX_RESULT_SIZE: usize = 64;
+}
+
+impl Response for JobGetStatusResp {}
+
+// JOB_CANCEL
+// No command-specific output args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct JobCancelReq {
+    pub hdr: MailboxReqHeader,
+    pub job_id: u32,
+}
+
+impl Request for JobCancelReq {
+    const ID: CommandId = CommandId::JOB_CANCEL;
+    type Resp = MailboxRespHeader;
+}
+
+/// Asynchronous event reported by the runtime
+#[repr(u32)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RuntimeEvent {
+    Invalid = 0,
+    MeasurementLogNearFull,
+    DpeContextThresholdReached,
+    AttestationDisabledWarmReset,
+}
+
+impl From<u32> for RuntimeEvent {
+    fn from(val: u32) -> Self {
+        match val {
+            1_u32 => RuntimeEvent::MeasurementLogNearFull,
+            2_u32 => RuntimeEvent::DpeContextThresholdReached,
+            3_u32 => RuntimeEvent::AttestationDisabledWarmReset,
+            _ => RuntimeEvent::Invalid,
+        }
+    }
+}
+
+#[repr(C)]
+#[derive(
+    Clone, Copy, Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq,
+)]
+pub struct RuntimeEventEntry {
+    /// A `RuntimeEvent`
+    pub event: u32,
+    /// Event-specific detail
+    pub data: u32,
+}
+
+// GET_EVENTS
+// No command-specific input args
+#[repr(C)]
+#[derive(Debug, Default, IntoBytes, FromBytes, KnownLayout, Immutable, PartialEq, Eq)]
+pub struct GetEventsResp {
+    pub hdr: MailboxRespHeader,
+    /// Number of valid entries in `events`
+    pub event_count: u32,
+    /// Number of events lost because the queue was full
+    pub dropped_count: u32,
+    pub events: [RuntimeEventEntry; GetEventsResp::MAX_EVENTS],
+}
+
+impl GetEventsResp {
+    pub const MAX_EVENTS: usize = 8;
+
+    pub fn events(&self) -> Option<&[RuntimeEventEntry]> {
+        self.events.get(..self.event_count as usize)
+    }
+}
+
+impl Response for GetEventsResp {}
+
 #[repr(u32)]
 #[derive(Debug, PartialEq, Eq)]
 pub enum ImageHashSource {
@@ -1212,6 +2081,10 @@ pub struct AuthorizeAndStashReq {
     pub svn: u32,
     pub flags: u32,
     pub source: u32,
+    pub image_size: u32,
+    pub load_address_lo: u32,
+    pub load_address_hi: u32,
+    pub version: u32,
 }
 impl Default for AuthorizeAndStashReq {
     fn default() -> Self {
@@ -1223,9 +2096,18 @@ impl Default for AuthorizeAndStashReq {
             svn: Default::default(),
             flags: AuthAndStashFlags::SKIP_STASH.bits(),
             source: ImageHashSource::InRequest as u32,
+            image_size: Default::default(),
+            load_address_lo: Default::default(),
+            load_address_hi: Default::default(),
+            version: Default::default(),
         }
     }
 }
+impl AuthorizeAndStashReq {
+    /// Size of a request without `image_size`, `load_address_lo`,
+    /// `load_address_hi` and `version`, as sent by clients predating them
+    pub const LEGACY_SIZE: usize = size_of::<Self>() - 4 * size_of::<u32>();
+}
 impl Request for AuthorizeAndStashReq {
     const ID: CommandId = CommandId::AUTHORIZE_AND_STASH;
     type Resp = StashMeasurementResp;
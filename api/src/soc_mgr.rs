@@ -3,8 +3,10 @@
 use crate::{
     calc_checksum,
     mailbox::{
-        mbox_read_response, mbox_write_fifo, MailboxReqHeader, MailboxRespHeader, Request,
-        Response, StashMeasurementReq,
+        mbox_read_response, mbox_write_fifo, CommandId, MailboxReqHeader, MailboxRespHeader,
+        Request, Response, StashMeasurementReq, TransferBeginReq, TransferBeginResp,
+        TransferContinueReq, TransferContinueResp, TransferEndReq, TransferSource,
+        TransferWriteReq,
     },
     CaliptraApiError,
 };
@@ -359,4 +361,100 @@ pub trait SocManager {
         }
         Err(CaliptraApiError::StashMeasurementFailed)
     }
+
+    /// Reads all of `source` into `buf` with the TRANSFER_BEGIN,
+    /// TRANSFER_CONTINUE and TRANSFER_END commands, and returns the number of
+    /// bytes read.
+    fn read_transfer(
+        &mut self,
+        source: TransferSource,
+        buf: &mut [u8],
+    ) -> Result<usize, CaliptraApiError> {
+        let mut resp_bytes = [0u8; mem::size_of::<TransferContinueResp>()];
+        let begin = self.mailbox_exec_req(
+            TransferBeginReq {
+                source: source as u32,
+                ..Default::default()
+            },
+            &mut resp_bytes,
+        )?;
+        let end = TransferEndReq {
+            transfer_id: begin.transfer_id,
+            ..Default::default()
+        };
+        let total_size = begin.total_size as usize;
+        if total_size > buf.len() {
+            self.mailbox_exec_req(end, &mut resp_bytes)?;
+            return Err(CaliptraApiError::ReadBuffTooSmall);
+        }
+
+        let mut offset = 0;
+        while offset < total_size {
+            let resp = self.mailbox_exec_req(
+                TransferContinueReq {
+                    transfer_id: begin.transfer_id,
+                    offset: offset as u32,
+                    ..Default::default()
+                },
+                &mut resp_bytes,
+            )?;
+            let chunk = resp
+                .data
+                .get(..resp.data_size as usize)
+                .filter(|chunk| !chunk.is_empty() && chunk.len() <= total_size - offset)
+                .ok_or(CaliptraApiError::TransferInvalidChunk)?;
+            buf[offset..][..chunk.len()].copy_from_slice(chunk);
+            offset += chunk.len();
+        }
+
+        self.mailbox_exec_req(end, &mut resp_bytes)?;
+        Ok(total_size)
+    }
+
+    /// Sends `data` as the request of `cmd` with the TRANSFER_BEGIN,
+    /// TRANSFER_WRITE and TRANSFER_END commands. `cmd` runs at TRANSFER_END,
+    /// and its response is returned as by `mailbox_exec`.
+    fn write_transfer<'r>(
+        &mut self,
+        cmd: CommandId,
+        data: &[u8],
+        resp_data: &'r mut [u8],
+    ) -> Result<Option<&'r [u8]>, CaliptraApiError> {
+        let mut resp_bytes = [0u8; mem::size_of::<TransferBeginResp>()];
+        let begin = self.mailbox_exec_req(
+            TransferBeginReq {
+                source: TransferSource::Caller as u32,
+                cmd: cmd.into(),
+                total_size: data.len() as u32,
+                ..Default::default()
+            },
+            &mut resp_bytes,
+        )?;
+
+        for (index, chunk) in data.chunks(TransferWriteReq::MAX_CHUNK_SIZE).enumerate() {
+            let mut req = TransferWriteReq {
+                transfer_id: begin.transfer_id,
+                offset: (index * TransferWriteReq::MAX_CHUNK_SIZE) as u32,
+                data_size: chunk.len() as u32,
+                ..Default::default()
+            };
+            req.data[..chunk.len()].copy_from_slice(chunk);
+            self.mailbox_exec_req(req, &mut resp_bytes)?;
+        }
+
+        let mut end = TransferEndReq {
+            transfer_id: begin.transfer_id,
+            ..Default::default()
+        };
+        end.hdr.chksum = calc_checksum(
+            CommandId::TRANSFER_END.into(),
+            &end.as_bytes()[mem::size_of::<MailboxReqHeader>()..],
+        );
+        SocManager::mailbox_exec(
+            self,
+            CommandId::TRANSFER_END.into(),
+            end.as_bytes(),
+            resp_data,
+        )
+    }
 }
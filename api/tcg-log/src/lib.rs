@@ -0,0 +1,429 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    lib.rs
+
+Abstract:
+
+    Renders the Caliptra PCR and measurement logs as a TCG PC Client
+    crypto-agile event log and replays them against a PCR quote.
+
+--*/
+
+use caliptra_api::mailbox::{QuotePcrsResp, MEASUREMENT_LOG_ENTRY_SIZE, PCR_LOG_ENTRY_SIZE};
+use sha2::{Digest, Sha384};
+use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
+
+pub const SHA384_DIGEST_SIZE: usize = 48;
+pub const PCR_COUNT: usize = 32;
+
+/// TPM_ALG_SHA384 from the TPM 2.0 Library, Part 2
+pub const TPM_ALG_SHA384: u16 = 0x000C;
+
+/// Event types from the TCG PC Client Platform Firmware Profile
+pub const EV_NO_ACTION: u32 = 0x0000_0003;
+pub const EV_EVENT_TAG: u32 = 0x0000_0006;
+
+/// Signature of the Spec ID event that starts a crypto-agile log
+pub const SPEC_ID_EVENT03_SIGNATURE: &[u8; 16] = b"Spec ID Event03\0";
+
+pub type PcrValue = [u8; SHA384_DIGEST_SIZE];
+
+/// Identifies a PCR log entry and the size of its data, as logged by
+/// Caliptra firmware
+#[repr(u16)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PcrLogEntryId {
+    Invalid = 0,
+    DeviceStatus = 1,     // data size = 11 bytes
+    VendorPubKeyHash = 2, // data size = 48 bytes
+    OwnerPubKeyHash = 3,  // data size = 48 bytes
+    FmcTci = 4,           // data size = 48 bytes
+    StashMeasurement = 5, // data size = 48 bytes
+    RtTci = 6,            // data size = 48 bytes
+    FwImageManifest = 7,  // data size = 48 bytes
+}
+
+impl From<u16> for PcrLogEntryId {
+    fn from(id: u16) -> PcrLogEntryId {
+        match id {
+            1 => PcrLogEntryId::DeviceStatus,
+            2 => PcrLogEntryId::VendorPubKeyHash,
+            3 => PcrLogEntryId::OwnerPubKeyHash,
+            4 => PcrLogEntryId::FmcTci,
+            5 => PcrLogEntryId::StashMeasurement,
+            6 => PcrLogEntryId::RtTci,
+            7 => PcrLogEntryId::FwImageManifest,
+            _ => PcrLogEntryId::Invalid,
+        }
+    }
+}
+
+/// PCR log entry as returned by `GET_PCR_LOG`
+#[repr(C)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout,
+)]
+pub struct PcrLogEntry {
+    pub id: u16,
+    pub reserved0: [u8; 2],
+    /// Bitmask of the PCRs the data was extended into
+    pub pcr_ids: u32,
+    pub pcr_data: [u32; 12],
+}
+
+impl PcrLogEntry {
+    /// The data extended into the PCRs
+    pub fn measured_data(&self) -> &[u8] {
+        let data_len = match PcrLogEntryId::from(self.id) {
+            PcrLogEntryId::Invalid => 0,
+            PcrLogEntryId::DeviceStatus => 11,
+            _ => SHA384_DIGEST_SIZE,
+        };
+
+        &self.pcr_data.as_bytes()[..data_len]
+    }
+}
+
+/// Measurement log entry as returned by `GET_MEASUREMENT_LOG`
+#[repr(C)]
+#[derive(
+    Clone, Copy, Debug, Default, PartialEq, Eq, FromBytes, IntoBytes, Immutable, KnownLayout,
+)]
+pub struct MeasurementLogEntry {
+    pub pcr_entry: PcrLogEntry,
+    pub metadata: [u8; 4],
+    pub context: [u32; 12],
+    pub svn: u32,
+    pub reserved0: [u8; 4],
+}
+
+const _: () = assert!(core::mem::size_of::<PcrLogEntry>() == PCR_LOG_ENTRY_SIZE);
+const _: () = assert!(core::mem::size_of::<MeasurementLogEntry>() == MEASUREMENT_LOG_ENTRY_SIZE);
+
+/// A single `TCG_PCR_EVENT2` record carrying a SHA-384 digest
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TcgPcrEvent2 {
+    pub pcr_index: u32,
+    pub event_type: u32,
+    pub digest: PcrValue,
+    pub event: Vec<u8>,
+}
+
+impl TcgPcrEvent2 {
+    /// Serializes the event in the `TCG_PCR_EVENT2` binary format.
+    pub fn write_to(&self, out: &mut Vec<u8>) {
+        out.extend_from_slice(&self.pcr_index.to_le_bytes());
+        out.extend_from_slice(&self.event_type.to_le_bytes());
+        // TPML_DIGEST_VALUES with a single SHA-384 digest
+        out.extend_from_slice(&1u32.to_le_bytes());
+        out.extend_from_slice(&TPM_ALG_SHA384.to_le_bytes());
+        out.extend_from_slice(&self.digest);
+        out.extend_from_slice(&(self.event.len() as u32).to_le_bytes());
+        out.extend_from_slice(&self.event);
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ReplayError {
+    /// A PCR value computed from the log does not match the quote.
+    PcrMismatch { pcr_index: u32 },
+}
+
+/// TCG crypto-agile event log built from the Caliptra logs.
+///
+/// Every Caliptra log entry becomes one `EV_EVENT_TAG` event per PCR it was
+/// extended into. The tagged event ID is the `PcrLogEntryId` of the entry and
+/// the tagged event data is the measured data. Entries with an invalid ID are
+/// skipped, as in [`replay`].
+///
+/// Caliptra extends the measured data itself into the PCR. For entries whose
+/// data is a SHA-384 digest this matches TPM extend semantics, and the event
+/// digest is the data. The 11-byte device status is not a digest. Its event
+/// digest is the SHA-384 of the data, so replaying the journey and current
+/// FMC PCRs needs [`replay`] rather than a generic TPM replay.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct TcgEventLog {
+    events: Vec<TcgPcrEvent2>,
+}
+
+impl TcgEventLog {
+    /// Builds the event log from PCR log entries returned by `GET_PCR_LOG`
+    /// and measurement log entries returned by `GET_MEASUREMENT_LOG`.
+    pub fn new(pcr_log: &[PcrLogEntry], measurement_log: &[MeasurementLogEntry]) -> Self {
+        let mut events = vec![];
+        for entry in pcr_log.iter().filter(|e| is_valid(e)) {
+            push_tagged_events(&mut events, entry, entry.measured_data());
+        }
+        for entry in measurement_log.iter().filter(|e| is_valid(&e.pcr_entry)) {
+            let mut data = entry.pcr_entry.measured_data().to_vec();
+            data.extend_from_slice(&entry.metadata);
+            data.extend_from_slice(entry.context.as_bytes());
+            data.extend_from_slice(&entry.svn.to_le_bytes());
+            push_tagged_events(&mut events, &entry.pcr_entry, &data);
+        }
+        Self { events }
+    }
+
+    pub fn events(&self) -> &[TcgPcrEvent2] {
+        &self.events
+    }
+
+    /// Serializes the log, starting with the `TCG_EfiSpecIDEvent` header
+    /// event in the SHA-1 `TCG_PCClientPCREvent` format.
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut spec_id = vec![];
+        spec_id.extend_from_slice(SPEC_ID_EVENT03_SIGNATURE);
+        spec_id.extend_from_slice(&0u32.to_le_bytes()); // platformClass
+        spec_id.push(0); // specVersionMinor
+        spec_id.push(2); // specVersionMajor
+        spec_id.push(0); // specErrata
+        spec_id.push(2); // uintnSize (UINT64)
+        spec_id.extend_from_slice(&1u32.to_le_bytes()); // numberOfAlgorithms
+        spec_id.extend_from_slice(&TPM_ALG_SHA384.to_le_bytes());
+        spec_id.extend_from_slice(&(SHA384_DIGEST_SIZE as u16).to_le_bytes());
+        spec_id.push(0); // vendorInfoSize
+
+        let mut out = vec![];
+        out.extend_from_slice(&0u32.to_le_bytes()); // PCRIndex
+        out.extend_from_slice(&EV_NO_ACTION.to_le_bytes());
+        out.extend_from_slice(&[0u8; 20]); // SHA-1 digest
+        out.extend_from_slice(&(spec_id.len() as u32).to_le_bytes());
+        out.extend_from_slice(&spec_id);
+
+        for event in &self.events {
+            event.write_to(&mut out);
+        }
+        out
+    }
+}
+
+fn is_valid(entry: &PcrLogEntry) -> bool {
+    PcrLogEntryId::from(entry.id) != PcrLogEntryId::Invalid
+}
+
+fn push_tagged_events(events: &mut Vec<TcgPcrEvent2>, entry: &PcrLogEntry, data: &[u8]) {
+    let measured = entry.measured_data();
+    let digest = match <PcrValue>::try_from(measured) {
+        Ok(digest) => digest,
+        Err(_) => Sha384::digest(measured).into(),
+    };
+
+    // TCG_PCClientTaggedEvent
+    let mut event = vec![];
+    event.extend_from_slice(&u32::from(entry.id).to_le_bytes());
+    event.extend_from_slice(&(data.len() as u32).to_le_bytes());
+    event.extend_from_slice(data);
+
+    for pcr_index in (0..PCR_COUNT as u32).filter(|i| entry.pcr_ids & (1 << i) != 0) {
+        events.push(TcgPcrEvent2 {
+            pcr_index,
+            event_type: EV_EVENT_TAG,
+            digest,
+            event: event.clone(),
+        });
+    }
+}
+
+/// Recomputes the PCR values extended by the logged entries, starting from
+/// all-zero PCRs as on a cold boot. Each entry is extended the way Caliptra
+/// extends it: `PCR = SHA384(PCR || measured data)`.
+///
+/// # Returns
+///
+/// * The PCR values and a bitmask of the PCRs referenced by the logs
+pub fn replay(
+    pcr_log: &[PcrLogEntry],
+    measurement_log: &[MeasurementLogEntry],
+) -> ([PcrValue; PCR_COUNT], u32) {
+    let mut pcrs = [[0u8; SHA384_DIGEST_SIZE]; PCR_COUNT];
+    let mut extended = 0u32;
+
+    let entries = pcr_log
+        .iter()
+        .chain(measurement_log.iter().map(|e| &e.pcr_entry))
+        .filter(|e| is_valid(e));
+    for entry in entries {
+        for (i, pcr) in pcrs.iter_mut().enumerate() {
+            if entry.pcr_ids & (1 << i) == 0 {
+                continue;
+            }
+            let mut hasher = Sha384::new();
+            hasher.update(*pcr);
+            hasher.update(entry.measured_data());
+            *pcr = hasher.finalize().into();
+        }
+        extended |= entry.pcr_ids;
+    }
+
+    (pcrs, extended)
+}
+
+/// Replays the logs and compares every PCR they reference with `quote`.
+///
+/// PCRs that are not referenced by the logs are not checked, since they may
+/// have been extended through `EXTEND_PCR` at runtime. The measurement log
+/// only covers measurements stashed with ROM, so PCR31 no longer matches once
+/// `STASH_MEASUREMENT` has been issued to runtime. The caller is responsible
+/// for verifying the quote signature.
+pub fn verify_quote(
+    pcr_log: &[PcrLogEntry],
+    measurement_log: &[MeasurementLogEntry],
+    quote: &QuotePcrsResp,
+) -> Result<(), ReplayError> {
+    let (pcrs, extended) = replay(pcr_log, measurement_log);
+    for (i, (replayed, quoted)) in pcrs.iter().zip(quote.pcrs.iter()).enumerate() {
+        if extended & (1 << i) != 0 && replayed != quoted {
+            return Err(ReplayError::PcrMismatch {
+                pcr_index: i as u32,
+            });
+        }
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use zerocopy::FromZeros;
+
+    fn entry(id: PcrLogEntryId, pcr_ids: u32, fill: u32) -> PcrLogEntry {
+        PcrLogEntry {
+            id: id as u16,
+            pcr_ids,
+            pcr_data: [fill; 12],
+            ..Default::default()
+        }
+    }
+
+    fn extend(pcr: &PcrValue, data: &[u8]) -> PcrValue {
+        let mut hasher = Sha384::new();
+        hasher.update(pcr);
+        hasher.update(data);
+        hasher.finalize().into()
+    }
+
+    #[test]
+    fn test_event_per_pcr() {
+        let log = [
+            entry(PcrLogEntryId::DeviceStatus, 0b11, 0x01020304),
+            entry(PcrLogEntryId::RtTci, 0b1100, 0xAABBCCDD),
+        ];
+        let tcg_log = TcgEventLog::new(&log, &[]);
+        let events = tcg_log.events();
+
+        assert_eq!(events.len(), 4);
+        assert_eq!(
+            events.iter().map(|e| e.pcr_index).collect::<Vec<_>>(),
+            [0, 1, 2, 3]
+        );
+        assert!(events.iter().all(|e| e.event_type == EV_EVENT_TAG));
+
+        // The 11-byte device status is hashed, digests are used as is.
+        let status: PcrValue = Sha384::digest(log[0].measured_data()).into();
+        assert_eq!(events[0].digest, status);
+        assert_eq!(&events[2].digest[..], log[1].measured_data());
+
+        // TCG_PCClientTaggedEvent
+        assert_eq!(
+            events[0].event[..4],
+            (PcrLogEntryId::DeviceStatus as u32).to_le_bytes()
+        );
+        assert_eq!(events[0].event[4..8], 11u32.to_le_bytes());
+        assert_eq!(&events[0].event[8..], log[0].measured_data());
+    }
+
+    #[test]
+    fn test_invalid_entries_skipped() {
+        let log = [
+            entry(PcrLogEntryId::Invalid, 0b1, 0x01020304),
+            entry(PcrLogEntryId::FmcTci, 0b1, 0x05060708),
+        ];
+        let events = TcgEventLog::new(&log, &[]).events().to_vec();
+
+        assert_eq!(events.len(), 1);
+        assert_eq!(&events[0].digest[..], log[1].measured_data());
+    }
+
+    #[test]
+    fn test_tpm_replay_matches_digest_entries() {
+        let log = [
+            entry(PcrLogEntryId::VendorPubKeyHash, 0b11, 0x01020304),
+            entry(PcrLogEntryId::FmcTci, 0b11, 0x05060708),
+        ];
+        let tcg_log = TcgEventLog::new(&log, &[]);
+
+        // TPM2_PCR_Extend semantics: PCR = SHA384(PCR || event digest)
+        let mut pcr = [0u8; 48];
+        for event in tcg_log.events().iter().filter(|e| e.pcr_index == 0) {
+            pcr = extend(&pcr, &event.digest);
+        }
+        let (pcrs, _) = replay(&log, &[]);
+        assert_eq!(pcr, pcrs[0]);
+    }
+
+    #[test]
+    fn test_to_bytes() {
+        let log = [entry(PcrLogEntryId::FmcTci, 0b1, 0x11111111)];
+        let bytes = TcgEventLog::new(&log, &[]).to_bytes();
+
+        // Spec ID event header
+        assert_eq!(bytes[..4], 0u32.to_le_bytes());
+        assert_eq!(bytes[4..8], EV_NO_ACTION.to_le_bytes());
+        assert_eq!(bytes[8..28], [0u8; 20]);
+        let spec_id_len = u32::from_le_bytes(bytes[28..32].try_into().unwrap()) as usize;
+        let spec_id = &bytes[32..32 + spec_id_len];
+        assert_eq!(&spec_id[..16], SPEC_ID_EVENT03_SIGNATURE);
+        assert_eq!(spec_id[24..28], 1u32.to_le_bytes());
+        assert_eq!(spec_id[28..30], TPM_ALG_SHA384.to_le_bytes());
+        assert_eq!(spec_id[30..32], 48u16.to_le_bytes());
+
+        // TCG_PCR_EVENT2
+        let event = &bytes[32 + spec_id_len..];
+        assert_eq!(event[..4], 0u32.to_le_bytes());
+        assert_eq!(event[4..8], EV_EVENT_TAG.to_le_bytes());
+        assert_eq!(event[8..12], 1u32.to_le_bytes());
+        assert_eq!(event[12..14], TPM_ALG_SHA384.to_le_bytes());
+        assert_eq!(&event[14..62], log[0].measured_data());
+        assert_eq!(event[62..66], (8u32 + 48).to_le_bytes());
+        assert_eq!(event.len(), 66 + 8 + 48);
+    }
+
+    #[test]
+    fn test_replay_and_verify_quote() {
+        let log = [
+            entry(PcrLogEntryId::DeviceStatus, 0b11, 0x01020304),
+            entry(PcrLogEntryId::FmcTci, 0b11, 0x05060708),
+        ];
+        let meas = MeasurementLogEntry {
+            pcr_entry: entry(PcrLogEntryId::StashMeasurement, 1 << 31, 0x0A0B0C0D),
+            ..Default::default()
+        };
+
+        let (pcrs, extended) = replay(&log, &[meas]);
+        assert_eq!(extended, 0b11 | (1 << 31));
+
+        let expected = extend(
+            &extend(&[0u8; 48], log[0].measured_data()),
+            log[1].measured_data(),
+        );
+        assert_eq!(pcrs[0], expected);
+        assert_eq!(pcrs[1], expected);
+        assert_eq!(pcrs[31], extend(&[0u8; 48], meas.pcr_entry.measured_data()));
+
+        let mut quote = QuotePcrsResp::new_zeroed();
+        quote.pcrs = pcrs;
+        // PCRs absent from the logs are ignored
+        quote.pcrs[5] = [0xFF; 48];
+        assert_eq!(verify_quote(&log, &[meas], &quote), Ok(()));
+
+        quote.pcrs[1][0] ^= 1;
+        assert_eq!(
+            verify_quote(&log, &[meas], &quote),
+            Err(ReplayError::PcrMismatch { pcr_index: 1 })
+        );
+    }
+}
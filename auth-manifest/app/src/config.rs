@@ -43,6 +43,10 @@ pub struct ImageMetadataConfigFromFile {
     source: u32,
     fw_id: u32,
     ignore_auth_check: bool,
+    image_size: Option<u32>,
+    load_address: Option<u64>,
+    version: Option<u32>,
+    svn: Option<u32>,
 }
 
 // Authorization Manifest configuration from TOML file
@@ -138,10 +142,21 @@ pub(crate) fn image_metadata_config_from_file(
         flags.set_ignore_auth_check(image.ignore_auth_check);
         flags.set_image_source(image.source);
 
+        flags.set_check_image_size(image.image_size.is_some());
+        flags.set_check_load_address(image.load_address.is_some());
+        flags.set_check_version(image.version.is_some());
+        flags.set_check_svn(image.svn.is_some());
+
+        let load_address = image.load_address.unwrap_or_default();
         let image_metadata = AuthManifestImageMetadata {
             fw_id: image.fw_id,
             flags: flags.0,
             digest: digest_vec.try_into().unwrap(),
+            image_size: image.image_size.unwrap_or_default(),
+            load_address_lo: load_address as u32,
+            load_address_hi: (load_address >> 32) as u32,
+            version: image.version.unwrap_or_default(),
+            svn: image.svn.unwrap_or_default(),
         };
 
         image_metadata_list.push(image_metadata);
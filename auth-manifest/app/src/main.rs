@@ -23,7 +23,6 @@ use clap::ArgMatches;
 use clap::{arg, value_parser, Command};
 use std::io::Write;
 use std::path::PathBuf;
-use zerocopy::IntoBytes;
 
 mod config;
 
@@ -36,6 +35,18 @@ fn main() {
                 .required(true)
                 .value_parser(value_parser!(u32)),
         )
+        .arg(
+            arg!(--"svn" <U32> "Image Metadata Collection Security Version Number")
+                .required(false)
+                .default_value("0")
+                .value_parser(value_parser!(u32)),
+        )
+        .arg(
+            arg!(--"collection-id" <U32> "Image Metadata Collection Identifier, shared by all chunks of the collection")
+                .required(false)
+                .default_value("0")
+                .value_parser(value_parser!(u32)),
+        )
         .arg(
             arg!(--"flags" <U32> "Manifest Flags")
                 .required(true)
@@ -52,7 +63,7 @@ fn main() {
                 .value_parser(value_parser!(PathBuf)),
         )
         .arg(
-            arg!(--"out" <FILE> "Output file")
+            arg!(--"out" <FILE> "Output file, suffixed with .N per chunk if multiple manifests are needed")
                 .required(true)
                 .value_parser(value_parser!(PathBuf)),
         )];
@@ -76,6 +87,14 @@ pub(crate) fn run_auth_man_cmd(args: &ArgMatches) -> anyhow::Result<()> {
         .get_one::<u32>("version")
         .with_context(|| "version arg not specified")?;
 
+    let svn: &u32 = args
+        .get_one::<u32>("svn")
+        .with_context(|| "svn arg not specified")?;
+
+    let collection_id: &u32 = args
+        .get_one::<u32>("collection-id")
+        .with_context(|| "collection-id arg not specified")?;
+
     let flags: AuthManifestFlags = AuthManifestFlags::from_bits_truncate(
         *args
             .get_one::<u32>("flags")
@@ -108,6 +127,8 @@ pub(crate) fn run_auth_man_cmd(args: &ArgMatches) -> anyhow::Result<()> {
     // Decode the configuration.
     let gen_config = AuthManifestGeneratorConfig {
         version: *version,
+        svn: *svn,
+        collection_id: *collection_id,
         flags,
         vendor_man_key_info: config::vendor_config_from_file(
             key_dir,
@@ -120,16 +141,28 @@ pub(crate) fn run_auth_man_cmd(args: &ArgMatches) -> anyhow::Result<()> {
     };
 
     let gen = AuthManifestGenerator::new(Crypto::default());
-    let manifest = gen.generate(&gen_config).unwrap();
-
-    let mut out_file = std::fs::OpenOptions::new()
-        .create(true)
-        .truncate(true)
-        .write(true)
-        .open(out_path)
-        .with_context(|| format!("Failed to create file {}", out_path.display()))?;
-
-    out_file.write_all(manifest.as_bytes())?;
+    let manifests = gen.generate_chunks(&gen_config)?;
+
+    // Image metadata lists too large for a single manifest are written as
+    // numbered chunks: <out>.0, <out>.1, ...
+    for (chunk_index, manifest) in manifests.iter().enumerate() {
+        let path = if manifests.len() == 1 {
+            out_path.clone()
+        } else {
+            let mut path = out_path.clone().into_os_string();
+            path.push(format!(".{chunk_index}"));
+            PathBuf::from(path)
+        };
+
+        let mut out_file = std::fs::OpenOptions::new()
+            .create(true)
+            .truncate(true)
+            .write(true)
+            .open(&path)
+            .with_context(|| format!("Failed to create file {}", path.display()))?;
+
+        out_file.write_all(manifest.as_bytes_partial())?;
+    }
 
     Ok(())
 }
@@ -29,25 +29,62 @@ impl<Crypto: ImageGeneratorCrypto> AuthManifestGenerator<Crypto> {
         Self { crypto }
     }
 
+    /// Generate an authorization manifest holding the whole image metadata list
     pub fn generate(
         &self,
         config: &AuthManifestGeneratorConfig,
+    ) -> anyhow::Result<AuthorizationManifest> {
+        self.generate_chunk(config, &config.image_metadata_list, 0, 1)
+    }
+
+    /// Generate authorization manifest chunks for an image metadata list of
+    /// any length. The chunks are to be sent to runtime in order.
+    pub fn generate_chunks(
+        &self,
+        config: &AuthManifestGeneratorConfig,
+    ) -> anyhow::Result<Vec<AuthorizationManifest>> {
+        if config.image_metadata_list.is_empty() {
+            return Ok(vec![self.generate_chunk(config, &[], 0, 1)?]);
+        }
+
+        let chunks = config
+            .image_metadata_list
+            .chunks(AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT);
+        let chunk_count = chunks.len() as u32;
+        chunks
+            .enumerate()
+            .map(|(chunk_index, slice)| {
+                self.generate_chunk(config, slice, chunk_index as u32, chunk_count)
+            })
+            .collect()
+    }
+
+    /// Generate the authorization manifest for one chunk of an image metadata list
+    pub fn generate_chunk(
+        &self,
+        config: &AuthManifestGeneratorConfig,
+        slice: &[AuthManifestImageMetadata],
+        chunk_index: u32,
+        chunk_count: u32,
     ) -> anyhow::Result<AuthorizationManifest> {
         let mut auth_manifest = AuthorizationManifest::default();
 
-        if config.image_metadata_list.len() > AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT {
+        if slice.len() > AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT {
             eprintln!(
-                "Unsupported image metadata count, only {} entries supported.",
+                "Unsupported image metadata count, only {} entries supported per chunk.",
                 AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT
             );
             return Err(anyhow::anyhow!("Error converting image metadata list"));
         }
 
         // Generate the Image Metadata List.
-        let slice = config.image_metadata_list.as_slice();
         auth_manifest.image_metadata_col.image_metadata_list[..slice.len()].copy_from_slice(slice);
 
-        auth_manifest.image_metadata_col.entry_count = config.image_metadata_list.len() as u32;
+        auth_manifest.image_metadata_col.entry_count = slice.len() as u32;
+        auth_manifest.image_metadata_col.svn = config.svn;
+        auth_manifest.image_metadata_col.collection_id = config.collection_id;
+        auth_manifest.image_metadata_col.chunk_index = chunk_index;
+        auth_manifest.image_metadata_col.chunk_count = chunk_count;
 
         // Generate the preamble.
         auth_manifest.preamble.marker = AUTH_MANIFEST_MARKER;
@@ -108,10 +145,10 @@ impl<Crypto: ImageGeneratorCrypto> AuthManifestGenerator<Crypto> {
             }
         }
 
-        // Hash the IMC.
+        // Hash the IMC. Only the used entries are signed.
         let digest = self
             .crypto
-            .sha384_digest(auth_manifest.image_metadata_col.as_bytes())?;
+            .sha384_digest(auth_manifest.image_metadata_col.as_bytes_partial())?;
 
         // Sign the IMC with the vendor manifest public keys if indicated in the flags.
         if config
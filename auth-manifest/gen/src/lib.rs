@@ -30,6 +30,11 @@ pub struct AuthManifestGeneratorKeyConfig {
 pub struct AuthManifestGeneratorConfig {
     pub version: u32,
 
+    pub svn: u32,
+
+    /// Identifier of the image metadata collection, shared by all its chunks
+    pub collection_id: u32,
+
     pub flags: AuthManifestFlags,
 
     pub vendor_fw_key_info: AuthManifestGeneratorKeyConfig,
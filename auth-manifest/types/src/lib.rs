@@ -16,14 +16,23 @@ Abstract:
 
 use bitfield::bitfield;
 use caliptra_image_types::*;
+use core::cmp::min;
 use core::default::Default;
+use core::mem::size_of;
 use core::ops::Range;
-use memoffset::span_of;
+use memoffset::{offset_of, span_of};
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 use zeroize::Zeroize;
 
-pub const AUTH_MANIFEST_MARKER: u32 = 0x4154_4D4E;
-pub const AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT: usize = 127;
+/// Marker of the authorization manifest format ("ATM2").
+///
+/// The first format ("ATMN", 0x4154_4D4E) signed the full image metadata
+/// list of 127 entries. This format signs only the used entries and binds each
+/// chunk to its collection, so manifests in the old format are rejected.
+pub const AUTH_MANIFEST_MARKER: u32 = 0x4154_4D32;
+// Maximum number of entries in a single manifest chunk, limited by the
+// SET_AUTH_MANIFEST mailbox size. Larger collections are split into chunks.
+pub const AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT: usize = 94;
 
 bitflags::bitflags! {
     #[derive(Default, Copy, Clone, Debug)]
@@ -134,6 +143,10 @@ bitfield! {
     pub struct ImageMetadataFlags(u32);
     pub image_source, set_image_source: 1, 0;
     pub ignore_auth_check, set_ignore_auth_check: 2;
+    pub check_image_size, set_check_image_size: 3;
+    pub check_load_address, set_check_load_address: 4;
+    pub check_version, set_check_version: 5;
+    pub check_svn, set_check_svn: 6;
 }
 
 /// Caliptra Authorization Manifest Image Metadata
@@ -143,9 +156,23 @@ bitfield! {
 pub struct AuthManifestImageMetadata {
     pub fw_id: u32,
 
-    pub flags: u32, // ImageMetadataFlags(image_source, ignore_auth_check)
+    pub flags: u32, // ImageMetadataFlags(image_source, ignore_auth_check, check_*)
 
     pub digest: [u8; 48],
+
+    /// Expected image size in bytes. Checked if `check_image_size` is set.
+    pub image_size: u32,
+
+    /// Expected load/staging address. Checked if `check_load_address` is set.
+    pub load_address_lo: u32,
+
+    pub load_address_hi: u32,
+
+    /// Expected component version. Checked if `check_version` is set.
+    pub version: u32,
+
+    /// Minimum component SVN. Checked if `check_svn` is set.
+    pub svn: u32,
 }
 
 impl Default for AuthManifestImageMetadata {
@@ -154,6 +181,11 @@ impl Default for AuthManifestImageMetadata {
             fw_id: u32::MAX,
             flags: 0,
             digest: [0; 48],
+            image_size: 0,
+            load_address_lo: 0,
+            load_address_hi: 0,
+            version: 0,
+            svn: 0,
         }
     }
 }
@@ -165,6 +197,22 @@ impl Default for AuthManifestImageMetadata {
 pub struct AuthManifestImageMetadataCollection {
     pub entry_count: u32,
 
+    /// Security version number of the collection. Runtime rejects a collection
+    /// whose SVN is lower than that of the last accepted collection. The SVN is
+    /// covered by the image metadata collection signatures.
+    pub svn: u32,
+
+    /// Identifier of the collection. All chunks of a collection share the
+    /// same identifier, SVN and chunk count.
+    pub collection_id: u32,
+
+    /// Index of this chunk of the collection. Chunk 0 replaces the image
+    /// metadata list held by runtime, later chunks are appended in order.
+    pub chunk_index: u32,
+
+    /// Number of chunks in the collection.
+    pub chunk_count: u32,
+
     pub image_metadata_list: [AuthManifestImageMetadata; AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT],
 }
 
@@ -172,12 +220,38 @@ impl Default for AuthManifestImageMetadataCollection {
     fn default() -> Self {
         AuthManifestImageMetadataCollection {
             entry_count: 0,
+            svn: 0,
+            collection_id: 0,
+            chunk_index: 0,
+            chunk_count: 1,
             image_metadata_list: [AuthManifestImageMetadata::default();
                 AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT],
         }
     }
 }
 
+impl AuthManifestImageMetadataCollection {
+    /// Returns the size of the collection header preceding the image metadata list.
+    pub fn header_size() -> usize {
+        offset_of!(AuthManifestImageMetadataCollection, image_metadata_list)
+    }
+
+    /// Returns the size of the collection including only the used entries.
+    pub fn size(&self) -> usize {
+        Self::header_size()
+            + min(
+                self.entry_count as usize,
+                AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT,
+            ) * size_of::<AuthManifestImageMetadata>()
+    }
+
+    /// Returns the collection bytes including only the used entries. This is
+    /// the signed and transmitted form of the collection.
+    pub fn as_bytes_partial(&self) -> &[u8] {
+        &self.as_bytes()[..self.size()]
+    }
+}
+
 /// Caliptra Image Authorization Manifest
 #[repr(C)]
 #[derive(IntoBytes, FromBytes, Immutable, KnownLayout, Clone, Copy, Debug, Zeroize, Default)]
@@ -187,3 +261,11 @@ pub struct AuthorizationManifest {
 
     pub image_metadata_col: AuthManifestImageMetadataCollection,
 }
+
+impl AuthorizationManifest {
+    /// Returns the manifest bytes including only the used image metadata
+    /// entries.
+    pub fn as_bytes_partial(&self) -> &[u8] {
+        &self.as_bytes()[..size_of::<AuthManifestPreamble>() + self.image_metadata_col.size()]
+    }
+}
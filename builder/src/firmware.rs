@@ -227,6 +227,11 @@ pub mod driver_tests {
         ..BASE_FWID
     };
 
+    pub const MLDSA87: FwId = FwId {
+        bin_name: "mldsa87",
+        ..BASE_FWID
+    };
+
     pub const PCRBANK: FwId = FwId {
         bin_name: "pcrbank",
         ..BASE_FWID
@@ -424,6 +429,7 @@ pub const REGISTERED_FW: &[&FwId] = &[
     &driver_tests::MAILBOX_DRIVER_SENDER,
     &driver_tests::MAILBOX_DRIVER_NEGATIVE_TESTS,
     &driver_tests::MBOX_SEND_TXN_DROP,
+    &driver_tests::MLDSA87,
     &driver_tests::PCRBANK,
     &driver_tests::SHA1,
     &driver_tests::SHA256,
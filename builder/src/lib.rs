@@ -202,6 +202,12 @@ pub fn build_firmware_elfs_uncached<'a>(
             }
             features_csv.push_str("hw-1.0");
         }
+        if cfg!(feature = "hw-2.x") {
+            if !features_csv.is_empty() {
+                features_csv.push(',');
+            }
+            features_csv.push_str("hw-2.x");
+        }
 
         cmd.arg("build")
             .arg("--quiet")
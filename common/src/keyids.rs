@@ -25,15 +25,25 @@ pub const KEY_ID_LDEVID_PRIV_KEY: KeyId = KeyId::KeyId5;
 pub const KEY_ID_ROM_FMC_CDI: KeyId = KeyId::KeyId6;
 #[cfg(feature = "rom")]
 pub const KEY_ID_FMC_PRIV_KEY: KeyId = KeyId::KeyId7;
+#[cfg(feature = "rom")]
+pub const KEY_ID_IDEVID_MLDSA_KEYPAIR_SEED: KeyId = KeyId::KeyId8;
+#[cfg(feature = "rom")]
+pub const KEY_ID_LDEVID_MLDSA_KEYPAIR_SEED: KeyId = KeyId::KeyId4;
+#[cfg(feature = "rom")]
+pub const KEY_ID_FMC_MLDSA_KEYPAIR_SEED: KeyId = KeyId::KeyId11;
 #[cfg(feature = "fmc")]
 pub const KEY_ID_RT_CDI: KeyId = KeyId::KeyId4;
 #[cfg(feature = "fmc")]
 pub const KEY_ID_RT_PRIV_KEY: KeyId = KeyId::KeyId5;
+#[cfg(feature = "fmc")]
+pub const KEY_ID_RT_MLDSA_KEYPAIR_SEED: KeyId = KeyId::KeyId12;
 #[cfg(feature = "runtime")]
 pub const KEY_ID_DPE_CDI: KeyId = KeyId::KeyId8;
 #[cfg(feature = "runtime")]
 pub const KEY_ID_DPE_PRIV_KEY: KeyId = KeyId::KeyId9;
 #[cfg(feature = "runtime")]
 pub const KEY_ID_EXPORTED_DPE_CDI: KeyId = KeyId::KeyId10;
+#[cfg(feature = "runtime")]
+pub const KEY_ID_DERIVED_KEY: KeyId = KeyId::KeyId13;
 
 pub const KEY_ID_TMP: KeyId = KeyId::KeyId3;
@@ -25,6 +25,7 @@ pub struct FirmwareImageVerificationEnv<'a, 'b> {
     pub sha384: &'a mut Sha384,
     pub soc_ifc: &'a mut SocIfc,
     pub ecc384: &'a mut Ecc384,
+    pub mldsa87: &'a mut Mldsa87,
     pub data_vault: &'a mut DataVault,
     pub pcr_bank: &'a mut PcrBank,
     pub image: &'b [u8],
@@ -78,6 +79,22 @@ impl<'a, 'b> ImageVerificationEnv for &mut FirmwareImageVerificationEnv<'a, 'b>
         Lms::default().verify_lms_signature_cfi(self.sha256, &message, pub_key, sig)
     }
 
+    fn mldsa87_verify(
+        &mut self,
+        digest: &ImageDigest,
+        pub_key: &ImageMldsaPubKey,
+        sig: &ImageMldsaSignature,
+    ) -> CaliptraResult<Mldsa87Result> {
+        let pub_key = Mldsa87PubKey::from(&pub_key.0);
+        let sig = Mldsa87Signature::from(&sig.0);
+
+        // The digest is zero-padded to the ML-DSA-87 message size.
+        let mut msg = Mldsa87Msg::default();
+        msg.0[..digest.len()].copy_from_slice(digest);
+
+        self.mldsa87.verify(&pub_key, &msg, &sig)
+    }
+
     /// Retrieve Vendor Public Key Digest
     fn vendor_pub_key_digest(&self) -> ImageDigest {
         self.soc_ifc.fuse_bank().vendor_pub_key_hash().into()
@@ -93,6 +110,11 @@ impl<'a, 'b> ImageVerificationEnv for &mut FirmwareImageVerificationEnv<'a, 'b>
         self.soc_ifc.fuse_bank().vendor_lms_pub_key_revocation()
     }
 
+    /// Retrieve Vendor ML-DSA Public Key Revocation Bitmask
+    fn vendor_mldsa_pub_key_revocation(&self) -> u32 {
+        self.soc_ifc.fuse_bank().vendor_mldsa_pub_key_revocation()
+    }
+
     /// Retrieve Owner Public Key Digest from fuses
     fn owner_pub_key_digest_fuses(&self) -> ImageDigest {
         self.soc_ifc.fuse_bank().owner_pub_key_hash().into()
@@ -118,6 +140,11 @@ impl<'a, 'b> ImageVerificationEnv for &mut FirmwareImageVerificationEnv<'a, 'b>
         self.data_vault.lms_vendor_pk_index()
     }
 
+    /// Get the vendor ML-DSA key index saved in data vault on cold boot
+    fn vendor_mldsa_pub_key_idx_dv(&self) -> u32 {
+        self.data_vault.mldsa_vendor_pk_index()
+    }
+
     /// Get the owner public key digest saved in the dv on cold boot
     fn owner_pub_key_digest_dv(&self) -> ImageDigest {
         self.data_vault.owner_pk_hash().into()
@@ -146,6 +173,12 @@ impl<'a, 'b> ImageVerificationEnv for &mut FirmwareImageVerificationEnv<'a, 'b>
         self.soc_ifc.fuse_bank().lms_verify() == RomVerifyConfig::EcdsaAndLms
     }
 
+    /// Only Caliptra 2.x hardware has the ML-DSA-87 engine and the DCCM
+    /// holding the ML-DSA-87 persistent data
+    fn mldsa_verify_enabled(&self) -> bool {
+        cfg!(feature = "hw-2.x")
+    }
+
     fn set_fw_extended_error(&mut self, err: u32) {
         self.soc_ifc.set_fw_extended_error(err);
     }
@@ -13,7 +13,9 @@ use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::{Path, PathBuf};
 
 mod disasm;
+mod profile;
 pub use disasm::invoke_objdump;
+pub use profile::{firmware_functions, write_folded_stacks, CPTRA_PROFILE_PATH};
 
 pub const CPTRA_COVERAGE_PATH: &str = "CPTRA_COVERAGE_PATH";
 
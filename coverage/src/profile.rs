@@ -0,0 +1,48 @@
+// Licensed under the Apache-2.0 license
+
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use caliptra_builder::{build_firmware_elf, FwId, SymbolType};
+use caliptra_emu_cpu::CycleProfile;
+
+/// Directory call-stack profiles are written to, if set
+pub const CPTRA_PROFILE_PATH: &str = "CPTRA_PROFILE_PATH";
+
+/// Name and address range of each function in the firmware `ids`.
+///
+/// Names are left mangled; pipe the output through `rustfilt` to demangle.
+pub fn firmware_functions(ids: &[&FwId<'static>]) -> anyhow::Result<Vec<(String, Range<u32>)>> {
+    let mut functions = vec![];
+    for id in ids {
+        let elf_bytes = build_firmware_elf(id)?;
+        let symbols = caliptra_builder::elf_symbols(&elf_bytes)?;
+        functions.extend(
+            symbols
+                .iter()
+                .filter(|sym| sym.ty == SymbolType::Func && sym.size > 0)
+                .map(|sym| {
+                    let start = sym.value as u32;
+                    (sym.name.to_string(), start..start + sym.size as u32)
+                }),
+        );
+    }
+    Ok(functions)
+}
+
+/// Write the call stacks of `profile` in the folded format read by
+/// `flamegraph.pl` and `inferno-flamegraph`, one `outer;...;inner cycles`
+/// line per stack.
+pub fn write_folded_stacks(
+    path: &Path,
+    profile: &CycleProfile,
+    functions: &[(String, Range<u32>)],
+) -> std::io::Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+    for (stack, cycles) in profile.folded_stacks(functions) {
+        writeln!(writer, "{stack} {cycles}")?;
+    }
+    writer.flush()
+}
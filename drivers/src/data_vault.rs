@@ -74,6 +74,7 @@ pub enum ColdResetEntry4 {
     FmcEntryPoint = 2,
     EccVendorPubKeyIndex = 3,
     LmsVendorPubKeyIndex = 4,
+    MldsaVendorPubKeyIndex = 5,
 }
 
 impl TryFrom<u8> for ColdResetEntry4 {
@@ -84,6 +85,7 @@ impl TryFrom<u8> for ColdResetEntry4 {
             2 => Ok(Self::FmcEntryPoint),
             3 => Ok(Self::EccVendorPubKeyIndex),
             4 => Ok(Self::LmsVendorPubKeyIndex),
+            5 => Ok(Self::MldsaVendorPubKeyIndex),
             _ => Err(()),
         }
     }
@@ -334,6 +336,15 @@ impl DataVault {
         self.read_cold_reset_entry4(ColdResetEntry4::LmsVendorPubKeyIndex)
     }
 
+    /// Get the ML-DSA vendor public key index used for image verification.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - Vendor public key index
+    pub fn mldsa_vendor_pk_index(&self) -> u32 {
+        self.read_cold_reset_entry4(ColdResetEntry4::MldsaVendorPubKeyIndex)
+    }
+
     /// Get the rom cold boot status.
     ///
     /// # Returns
@@ -15,6 +15,7 @@ Abstract:
 use crate::Array4x12;
 #[cfg(not(feature = "no-cfi"))]
 use caliptra_cfi_derive::Launder;
+use caliptra_image_types::VENDOR_MLDSA_KEY_COUNT;
 use caliptra_registers::soc_ifc::SocIfcReg;
 use zerocopy::IntoBytes;
 
@@ -223,6 +224,24 @@ impl FuseBank<'_> {
         soc_ifc_regs.fuse_lms_revocation().read()
     }
 
+    /// Get the ML-DSA vendor public key revocation mask.
+    ///
+    /// There is no dedicated ML-DSA revocation fuse yet, and the LMS
+    /// revocation fuse cannot be shared without letting one algorithm's
+    /// revocations affect the other. Until the fuse exists this fails closed:
+    /// every revocable key is reported as revoked, so only the last vendor
+    /// ML-DSA key (index `VENDOR_MLDSA_KEY_COUNT - 1`) is accepted.
+    ///
+    /// # Arguments
+    /// * None
+    ///
+    /// # Returns
+    ///     ML-DSA vendor public key revocation mask
+    ///
+    pub fn vendor_mldsa_pub_key_revocation(&self) -> u32 {
+        (1 << (VENDOR_MLDSA_KEY_COUNT - 1)) - 1
+    }
+
     /// Get the owner public key hash.
     ///
     /// # Arguments
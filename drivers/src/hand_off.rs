@@ -285,6 +285,12 @@ pub struct FirmwareHandoffTable {
     /// Address of RomInfo struct
     pub rom_info_addr: RomAddr<RomInfo>,
 
+    /// Index of FMC Alias ML-DSA-87 key pair seed in the Key Vault.
+    pub fmc_mldsa_keypair_seed_kv_hdl: HandOffDataHandle,
+
+    /// Index of RT Alias ML-DSA-87 key pair seed in the Key Vault.
+    pub rt_mldsa_keypair_seed_kv_hdl: HandOffDataHandle,
+
     /// RtAlias TBS Size.
     pub rtalias_tbs_size: u16,
 
@@ -298,10 +304,10 @@ pub struct FirmwareHandoffTable {
 
     /// Reserved for future use.
     #[cfg(any(feature = "fmc", feature = "runtime"))]
-    pub reserved: [u8; 1636],
+    pub reserved: [u8; 1628],
 
     #[cfg(not(any(feature = "fmc", feature = "runtime")))]
-    pub reserved: [u8; 1642],
+    pub reserved: [u8; 1634],
 }
 
 impl Default for FirmwareHandoffTable {
@@ -341,6 +347,8 @@ impl Default for FirmwareHandoffTable {
             ldevid_cert_sig_s_dv_hdl: FHT_INVALID_HANDLE,
             idev_dice_pub_key: Ecc384PubKey::default(),
             rom_info_addr: RomAddr::new(FHT_INVALID_ADDRESS),
+            fmc_mldsa_keypair_seed_kv_hdl: FHT_INVALID_HANDLE,
+            rt_mldsa_keypair_seed_kv_hdl: FHT_INVALID_HANDLE,
             rtalias_tbs_size: 0,
 
             #[cfg(any(feature = "fmc", feature = "runtime"))]
@@ -348,10 +356,10 @@ impl Default for FirmwareHandoffTable {
             #[cfg(any(feature = "fmc", feature = "runtime"))]
             rt_hash_chain_kv_hdl: HandOffDataHandle(0),
             #[cfg(any(feature = "fmc", feature = "runtime"))]
-            reserved: [0u8; 1636],
+            reserved: [0u8; 1628],
 
             #[cfg(not(any(feature = "fmc", feature = "runtime")))]
-            reserved: [0u8; 1642],
+            reserved: [0u8; 1634],
         }
     }
 }
@@ -417,6 +425,14 @@ pub fn print_fht(fht: &FirmwareHandoffTable) {
     crate::cprintln!("FmcAlias TBS Address: 0x{:08x}", fht.fmcalias_tbs_addr);
     crate::cprintln!("FmcAlias TBS Size: {} bytes", fht.fmcalias_tbs_size);
     crate::cprintln!("RtAlias TBS Size: {} bytes", fht.rtalias_tbs_size);
+    crate::cprintln!(
+        "FMC ML-DSA-87 Seed KV Handle: 0x{:08x}",
+        fht.fmc_mldsa_keypair_seed_kv_hdl.0
+    );
+    crate::cprintln!(
+        "RT ML-DSA-87 Seed KV Handle: 0x{:08x}",
+        fht.rt_mldsa_keypair_seed_kv_hdl.0
+    );
     crate::cprintln!("PCR log Address: 0x{:08x}", fht.pcr_log_addr);
     crate::cprintln!("PCR log Index: {}", fht.pcr_log_index);
     crate::cprintln!("Measurement log Address: {}", fht.meas_log_addr);
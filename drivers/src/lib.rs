@@ -37,6 +37,7 @@ mod kv_access;
 mod lms;
 mod mailbox;
 pub mod memory_layout;
+mod mldsa87;
 mod okref;
 mod pcr_bank;
 pub mod pcr_log;
@@ -82,24 +83,31 @@ pub use lms::{
     Sha256Digest, D_INTR, D_LEAF, D_MESG, D_PBLC,
 };
 pub use mailbox::{Mailbox, MailboxRecvTxn, MailboxSendTxn};
+pub use mldsa87::{
+    Mldsa87, Mldsa87Msg, Mldsa87PubKey, Mldsa87Result, Mldsa87Seed, Mldsa87SignRnd,
+    Mldsa87Signature,
+};
 pub use okref::okmutref;
 pub use okref::okref;
 pub use pcr_bank::{PcrBank, PcrId};
 pub use pcr_reset::PcrResetCounter;
 pub use persistent::fmc_alias_csr::FmcAliasCsr;
 #[cfg(feature = "runtime")]
-pub use persistent::AuthManifestImageMetadataList;
+pub use persistent::{
+    AuthManifestImageMetadataList, AuthManifestImageMetadataStore,
+    AUTH_MANIFEST_IMAGE_METADATA_STORE_MAX_COUNT, TRANSFER_STAGING_SIZE,
+};
 
 pub use persistent::{
-    FuseLogArray, IdevIdCsr, PcrLogArray, PersistentData, PersistentDataAccessor,
-    StashMeasurementArray, FUSE_LOG_MAX_COUNT, MAX_CSR_SIZE, MEASUREMENT_MAX_COUNT,
-    PCR_LOG_MAX_COUNT,
+    FuseLogArray, IdevIdCsr, Mldsa87CertData, PcrLogArray, PersistentData, PersistentDataAccessor,
+    PqcPersistentData, StashMeasurementArray, FUSE_LOG_MAX_COUNT, MAX_CSR_SIZE,
+    MEASUREMENT_MAX_COUNT, MLDSA87_TBS_MAX_SIZE, PCR_LOG_MAX_COUNT,
 };
 pub use pic::{IntSource, Pic};
 pub use sha1::{Sha1, Sha1Digest, Sha1DigestOp};
 pub use sha256::{Sha256, Sha256Alg, Sha256DigestOp};
 pub use sha2_512_384acc::{Sha2_512_384Acc, Sha2_512_384AccOp, ShaAccLockState};
-pub use sha384::{Sha384, Sha384Digest, Sha384DigestOp};
+pub use sha384::{Sha384, Sha384Digest, Sha384DigestOp, Sha384Resumable};
 pub use soc_ifc::{report_boot_status, Lifecycle, MfgFlags, ResetReason, SocIfc};
 pub use trng::Trng;
 
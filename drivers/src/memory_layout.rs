@@ -35,6 +35,10 @@ pub const ROM_ESTACK_ORG: u32 = 0x5001F800;
 pub const NSTACK_ORG: u32 = 0x5001FC00;
 pub const ROM_NSTACK_ORG: u32 = 0x5001FC00;
 
+// Caliptra 2.x DCCM is 256K. The upper half holds the ML-DSA-87 data and is
+// only accessed when the firmware is ML-DSA-87 signed.
+pub const PQC_DATA_ORG: u32 = 0x50020000;
+
 //
 // Memory Sizes In Bytes
 //
@@ -60,6 +64,7 @@ pub const ESTACK_SIZE: u32 = 1024;
 pub const ROM_ESTACK_SIZE: u32 = 1024;
 pub const NSTACK_SIZE: u32 = 1024;
 pub const ROM_NSTACK_SIZE: u32 = 1024;
+pub const PQC_DATA_SIZE: u32 = 128 * 1024;
 
 pub const ICCM_RANGE: core::ops::Range<u32> = core::ops::Range {
     start: ICCM_ORG,
@@ -89,3 +94,9 @@ fn mem_layout_test_stack() {
 fn mem_layout_test_estack() {
     assert_eq!((NSTACK_ORG - ESTACK_ORG), ESTACK_SIZE);
 }
+
+#[test]
+#[allow(clippy::assertions_on_constants)]
+fn mem_layout_test_pqc_data() {
+    assert_eq!(PQC_DATA_ORG, DCCM_ORG + DCCM_SIZE);
+}
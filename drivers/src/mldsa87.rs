@@ -0,0 +1,480 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    mldsa87.rs
+
+Abstract:
+
+    File contains API for ML-DSA-87 Cryptography operations
+
+--*/
+
+use crate::kv_access::{KvAccess, KvAccessErr};
+use crate::{
+    wait, Array4x16, Array4x8, Array4xN, CaliptraError, CaliptraResult, KeyReadArgs, Trng,
+};
+#[cfg(not(feature = "no-cfi"))]
+use caliptra_cfi_derive::cfi_impl_fn;
+use caliptra_registers::mldsa::{regs::CtrlWriteVal, MldsaReg, RegisterBlock};
+use zeroize::Zeroize;
+
+/// ML-DSA-87 Public Key
+pub type Mldsa87PubKey = Array4xN<648, 2592>;
+
+/// ML-DSA-87 Signature (4627 bytes, padded to a whole number of words)
+pub type Mldsa87Signature = Array4xN<1157, 4628>;
+
+/// ML-DSA-87 Message
+pub type Mldsa87Msg = Array4x16;
+
+/// ML-DSA-87 Signing Randomness
+pub type Mldsa87SignRnd = Array4x8;
+
+#[must_use]
+#[repr(u32)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mldsa87Result {
+    Success = 0xAAAAAAAA,
+    SigVerifyFailed = 0x55555555,
+}
+
+/// ML-DSA-87 message to sign or verify
+#[derive(Debug, Copy, Clone)]
+enum Mldsa87MsgInput<'a> {
+    /// Message held by the message registers
+    Fixed(&'a Mldsa87Msg),
+
+    /// Message of any length, streamed through the first message register
+    Stream(&'a [u8]),
+}
+
+/// ML-DSA-87 Seed
+#[derive(Debug, Copy, Clone)]
+pub enum Mldsa87Seed<'a> {
+    /// Array
+    Array4x8(&'a Array4x8),
+
+    /// Key Vault Key
+    Key(KeyReadArgs),
+}
+
+impl<'a> From<&'a Array4x8> for Mldsa87Seed<'a> {
+    /// Converts to this type from the input type.
+    fn from(value: &'a Array4x8) -> Self {
+        Self::Array4x8(value)
+    }
+}
+
+impl From<KeyReadArgs> for Mldsa87Seed<'_> {
+    /// Converts to this type from the input type.
+    fn from(value: KeyReadArgs) -> Self {
+        Self::Key(value)
+    }
+}
+
+/// ML-DSA-87 API
+///
+/// The private key never leaves the hardware: it is regenerated from the seed
+/// for every signing operation.
+pub struct Mldsa87 {
+    mldsa87: MldsaReg,
+}
+
+impl Mldsa87 {
+    pub fn new(mldsa87: MldsaReg) -> Self {
+        Self { mldsa87 }
+    }
+
+    /// Wait for the hardware to be ready for a new command.
+    fn wait_ready(regs: RegisterBlock<ureg::RealMmioMut>) {
+        wait::until(|| regs.status().read().ready());
+    }
+
+    /// Wait for the current command to complete.
+    fn wait_valid(regs: RegisterBlock<ureg::RealMmioMut>) {
+        wait::until(|| regs.status().read().valid());
+    }
+
+    /// Run `cmd` on the message and wait for it to complete.
+    fn execute_with_msg(
+        regs: RegisterBlock<ureg::RealMmioMut>,
+        msg: Mldsa87MsgInput,
+        cmd: impl FnOnce(CtrlWriteVal) -> CtrlWriteVal,
+    ) -> CaliptraResult<()> {
+        match msg {
+            Mldsa87MsgInput::Fixed(msg) => {
+                KvAccess::copy_from_arr(msg, regs.msg())?;
+                regs.ctrl().write(cmd);
+            }
+            Mldsa87MsgInput::Stream(msg) => {
+                regs.ctrl().write(|w| cmd(w).stream_msg(true));
+                wait::until(|| regs.status().read().msg_stream_ready());
+
+                // Whole words, then the remaining bytes with their byte
+                // enables, which ends the message
+                let (words, last) = msg.split_at(msg.len() & !3);
+                for word in words.chunks_exact(4) {
+                    let word = <[u8; 4]>::try_from(word).unwrap_or_default();
+                    regs.msg().at(0).write(|_| u32::from_le_bytes(word));
+                }
+                let mut word = [0u8; 4];
+                for (dst, src) in word.iter_mut().zip(last) {
+                    *dst = *src;
+                }
+                regs.msg_strobe().write(|_| (1 << last.len()) - 1);
+                regs.msg().at(0).write(|_| u32::from_le_bytes(word));
+            }
+        }
+
+        // Wait for command to complete
+        Self::wait_valid(regs);
+
+        Ok(())
+    }
+
+    /// Program a fresh IV for the masking countermeasures.
+    fn program_iv(regs: RegisterBlock<ureg::RealMmioMut>, trng: &mut Trng) -> CaliptraResult<()> {
+        let mut iv = [0u32; 16];
+        iv[..12].copy_from_slice(&trng.generate()?.0);
+        iv[12..].copy_from_slice(&trng.generate()?.0[..4]);
+        regs.iv().write(&iv);
+        iv.zeroize();
+        Ok(())
+    }
+
+    /// Load the seed and run key generation, leaving the key pair in the
+    /// hardware registers.
+    fn key_gen_internal(
+        regs: RegisterBlock<ureg::RealMmioMut>,
+        seed: &Mldsa87Seed,
+        trng: &mut Trng,
+    ) -> CaliptraResult<()> {
+        // Copy seed to the hardware
+        match seed {
+            Mldsa87Seed::Array4x8(arr) => KvAccess::copy_from_arr(arr, regs.seed())?,
+            Mldsa87Seed::Key(key) => {
+                KvAccess::copy_from_kv(*key, regs.kv_rd_seed_status(), regs.kv_rd_seed_ctrl())
+                    .map_err(|err| err.into_read_seed_err())?
+            }
+        }
+
+        Self::program_iv(regs, trng)?;
+
+        // Program the command register for key generation
+        regs.ctrl().write(|w| w.ctrl(|w| w.keygen()));
+
+        // Wait for command to complete
+        Self::wait_valid(regs);
+
+        Ok(())
+    }
+
+    /// Generate ML-DSA-87 Key Pair
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed for deterministic ML-DSA-87 Key Pair generation
+    /// * `trng` - TRNG driver instance
+    ///
+    /// # Returns
+    ///
+    /// * `Mldsa87PubKey` - Generated ML-DSA-87 Public Key
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn key_pair(
+        &mut self,
+        seed: &Mldsa87Seed,
+        trng: &mut Trng,
+    ) -> CaliptraResult<Mldsa87PubKey> {
+        self.key_pair_base(seed, trng, None)
+    }
+
+    /// Generate ML-DSA-87 Key Pair for FIPS KAT testing
+    /// ONLY to be used for KAT testing
+    ///
+    /// # Arguments
+    ///
+    /// * `trng` - TRNG driver instance
+    /// * `pct_sig` - Signature generated during the pairwise consistency test
+    ///
+    /// # Returns
+    ///
+    /// * `Mldsa87PubKey` - Generated ML-DSA-87 Public Key
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn key_pair_for_fips_kat(
+        &mut self,
+        trng: &mut Trng,
+        pct_sig: &mut Mldsa87Signature,
+    ) -> CaliptraResult<Mldsa87PubKey> {
+        let seed = Array4x8::new([0u32; 8]);
+        self.key_pair_base(&Mldsa87Seed::from(&seed), trng, Some(pct_sig))
+    }
+
+    /// Private base function to generate ML-DSA-87 Key Pair
+    /// pct_sig should only be provided in the KAT use case
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    fn key_pair_base(
+        &mut self,
+        seed: &Mldsa87Seed,
+        trng: &mut Trng,
+        pct_sig: Option<&mut Mldsa87Signature>,
+    ) -> CaliptraResult<Mldsa87PubKey> {
+        let mldsa = self.mldsa87.regs_mut();
+
+        // Wait for hardware ready
+        Self::wait_ready(mldsa);
+
+        Self::key_gen_internal(mldsa, seed, trng)?;
+
+        let pub_key = Mldsa87PubKey::read_from_reg(mldsa.pubkey());
+
+        self.zeroize_internal();
+
+        // Pairwise consistency check.
+        let msg = Mldsa87Msg::default();
+        let sign_rnd = Mldsa87SignRnd::default();
+        match self.sign(seed, &pub_key, &msg, &sign_rnd, trng) {
+            Ok(mut sig) => {
+                // Return the signature from this test if requested (only used for KAT)
+                if let Some(output_sig) = pct_sig {
+                    *output_sig = sig;
+                }
+                sig.zeroize();
+            }
+            Err(_) => {
+                // Remap error to a pairwise consistency check failure
+                return Err(CaliptraError::DRIVER_MLDSA87_KEYGEN_PAIRWISE_CONSISTENCY_FAILURE);
+            }
+        }
+
+        Ok(pub_key)
+    }
+
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    fn sign_internal(
+        &mut self,
+        seed: &Mldsa87Seed,
+        msg: Mldsa87MsgInput,
+        sign_rnd: &Mldsa87SignRnd,
+        trng: &mut Trng,
+    ) -> CaliptraResult<Mldsa87Signature> {
+        let mldsa = self.mldsa87.regs_mut();
+
+        // Wait for hardware ready
+        Self::wait_ready(mldsa);
+
+        // Regenerate the private key from the seed
+        Self::key_gen_internal(mldsa, seed, trng)?;
+
+        // Move the private key into the signing input one word at a time so
+        // it is never stored in memory.
+        let privkey_out = mldsa.privkey_out();
+        let privkey_in = mldsa.privkey_in();
+        for i in 0..1224 {
+            privkey_in.at(i).write(|_| privkey_out.at(i).read());
+        }
+
+        // Copy signing randomness
+        KvAccess::copy_from_arr(sign_rnd, mldsa.sign_rnd())?;
+
+        Self::program_iv(mldsa, trng)?;
+
+        Self::execute_with_msg(mldsa, msg, |w| w.ctrl(|w| w.signing()))?;
+
+        // Copy signature
+        let signature = Mldsa87Signature::read_from_reg(mldsa.signature());
+
+        self.zeroize_internal();
+
+        Ok(signature)
+    }
+
+    /// Sign the message with the key pair derived from the seed. To defend
+    /// against glitching attacks that could expose the private key, this
+    /// function also verifies the generated signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed the signing key pair is derived from
+    /// * `pub_key` - Public key to verify with
+    /// * `msg` - Message to sign
+    /// * `sign_rnd` - Signing randomness; all zeros selects deterministic signing
+    /// * `trng` - TRNG driver instance
+    ///
+    /// # Returns
+    ///
+    /// * `Mldsa87Signature` - Generated signature
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn sign(
+        &mut self,
+        seed: &Mldsa87Seed,
+        pub_key: &Mldsa87PubKey,
+        msg: &Mldsa87Msg,
+        sign_rnd: &Mldsa87SignRnd,
+        trng: &mut Trng,
+    ) -> CaliptraResult<Mldsa87Signature> {
+        self.sign_base(seed, pub_key, Mldsa87MsgInput::Fixed(msg), sign_rnd, trng)
+    }
+
+    /// Sign a message of any length with the key pair derived from the seed,
+    /// using pure ML-DSA-87. This function also verifies the generated
+    /// signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `seed` - Seed the signing key pair is derived from
+    /// * `pub_key` - Public key to verify with
+    /// * `msg` - Message to sign
+    /// * `sign_rnd` - Signing randomness; all zeros selects deterministic signing
+    /// * `trng` - TRNG driver instance
+    ///
+    /// # Returns
+    ///
+    /// * `Mldsa87Signature` - Generated signature
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn sign_var(
+        &mut self,
+        seed: &Mldsa87Seed,
+        pub_key: &Mldsa87PubKey,
+        msg: &[u8],
+        sign_rnd: &Mldsa87SignRnd,
+        trng: &mut Trng,
+    ) -> CaliptraResult<Mldsa87Signature> {
+        self.sign_base(seed, pub_key, Mldsa87MsgInput::Stream(msg), sign_rnd, trng)
+    }
+
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    fn sign_base(
+        &mut self,
+        seed: &Mldsa87Seed,
+        pub_key: &Mldsa87PubKey,
+        msg: Mldsa87MsgInput,
+        sign_rnd: &Mldsa87SignRnd,
+        trng: &mut Trng,
+    ) -> CaliptraResult<Mldsa87Signature> {
+        let sig = self.sign_internal(seed, msg, sign_rnd, trng)?;
+
+        // Verify the signature just created
+        let result = self.verify_base(pub_key, msg, &sig)?;
+        if result != Mldsa87Result::Success {
+            return Err(CaliptraError::DRIVER_MLDSA87_SIGN_VALIDATION_FAILED);
+        }
+        #[cfg(not(feature = "no-cfi"))]
+        caliptra_cfi_lib::cfi_assert_eq(result as u32, Mldsa87Result::Success as u32);
+
+        Ok(sig)
+    }
+
+    /// Verify signature with specified public key and message
+    ///
+    /// # Arguments
+    ///
+    /// * `pub_key` - Public key
+    /// * `msg` - Message to verify
+    /// * `signature` - Signature to verify
+    ///
+    /// # Result
+    ///
+    /// *  `Mldsa87Result` - Mldsa87Result::Success if the signature verification passed else an error code.
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn verify(
+        &mut self,
+        pub_key: &Mldsa87PubKey,
+        msg: &Mldsa87Msg,
+        signature: &Mldsa87Signature,
+    ) -> CaliptraResult<Mldsa87Result> {
+        self.verify_base(pub_key, Mldsa87MsgInput::Fixed(msg), signature)
+    }
+
+    /// Verify a pure ML-DSA-87 signature over a message of any length
+    ///
+    /// # Arguments
+    ///
+    /// * `pub_key` - Public key
+    /// * `msg` - Message to verify
+    /// * `signature` - Signature to verify
+    ///
+    /// # Result
+    ///
+    /// *  `Mldsa87Result` - Mldsa87Result::Success if the signature verification passed else an error code.
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn verify_var(
+        &mut self,
+        pub_key: &Mldsa87PubKey,
+        msg: &[u8],
+        signature: &Mldsa87Signature,
+    ) -> CaliptraResult<Mldsa87Result> {
+        self.verify_base(pub_key, Mldsa87MsgInput::Stream(msg), signature)
+    }
+
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    fn verify_base(
+        &mut self,
+        pub_key: &Mldsa87PubKey,
+        msg: Mldsa87MsgInput,
+        signature: &Mldsa87Signature,
+    ) -> CaliptraResult<Mldsa87Result> {
+        let mldsa = self.mldsa87.regs_mut();
+
+        // Wait for hardware ready
+        Self::wait_ready(mldsa);
+
+        // Copy public key and signature to registers
+        pub_key.write_to_reg(mldsa.pubkey());
+        signature.write_to_reg(mldsa.signature());
+
+        Self::execute_with_msg(mldsa, msg, |w| w.ctrl(|w| w.verifying()))?;
+
+        // Every word of the result must report success
+        let verify_res = Array4x16::read_from_reg(mldsa.verify_res());
+        let result = if verify_res.0.iter().all(|&word| word == 1) {
+            Mldsa87Result::Success
+        } else {
+            Mldsa87Result::SigVerifyFailed
+        };
+
+        self.zeroize_internal();
+
+        Ok(result)
+    }
+
+    /// Zeroize the hardware registers.
+    fn zeroize_internal(&mut self) {
+        self.mldsa87.regs_mut().ctrl().write(|w| w.zeroize(true));
+    }
+
+    /// Zeroize the hardware registers.
+    ///
+    /// This is useful to call from a fatal-error-handling routine.
+    ///
+    /// # Safety
+    ///
+    /// The caller must be certain that the results of any pending cryptographic
+    /// operations will not be used after this function is called.
+    ///
+    /// This function is safe to call from a trap handler.
+    pub unsafe fn zeroize() {
+        let mut mldsa87 = MldsaReg::new();
+        mldsa87.regs_mut().ctrl().write(|w| w.zeroize(true));
+    }
+}
+
+/// ML-DSA-87 key access error trait
+trait Mldsa87KeyAccessErr {
+    /// Convert to read seed operation error
+    fn into_read_seed_err(self) -> CaliptraError;
+}
+
+impl Mldsa87KeyAccessErr for KvAccessErr {
+    /// Convert to read seed operation error
+    fn into_read_seed_err(self) -> CaliptraError {
+        match self {
+            KvAccessErr::KeyRead => CaliptraError::DRIVER_MLDSA87_READ_SEED_KV_READ,
+            KvAccessErr::KeyWrite => CaliptraError::DRIVER_MLDSA87_READ_SEED_KV_WRITE,
+            KvAccessErr::Generic => CaliptraError::DRIVER_MLDSA87_READ_SEED_KV_UNKNOWN,
+        }
+    }
+}
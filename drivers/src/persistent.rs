@@ -3,14 +3,15 @@
 use core::{marker::PhantomData, mem::size_of, ptr::addr_of};
 
 #[cfg(feature = "runtime")]
-use caliptra_auth_man_types::{
-    AuthManifestImageMetadata, AuthManifestImageMetadataCollection,
-    AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT,
-};
+use caliptra_auth_man_types::AuthManifestImageMetadata;
 use caliptra_error::{CaliptraError, CaliptraResult};
-use caliptra_image_types::ImageManifest;
+#[cfg(feature = "runtime")]
+use caliptra_image_types::ImageDigest;
+use caliptra_image_types::{ImageManifest, ImageMldsaPreamble};
 #[cfg(feature = "runtime")]
 use dpe::{DpeInstance, U8Bool, MAX_HANDLES};
+#[cfg(feature = "runtime")]
+use zerocopy::{FromBytes, Immutable};
 use zerocopy::{IntoBytes, KnownLayout, TryFromBytes};
 use zeroize::Zeroize;
 
@@ -18,7 +19,7 @@ use crate::{
     fuse_log::FuseLogEntry,
     memory_layout,
     pcr_log::{MeasurementLogEntry, PcrLogEntry},
-    FirmwareHandoffTable,
+    FirmwareHandoffTable, Mldsa87Signature,
 };
 
 use crate::FmcAliasCsr;
@@ -38,14 +39,31 @@ pub const MEASUREMENT_LOG_SIZE: u32 = 1024;
 pub const FUSE_LOG_SIZE: u32 = 1024;
 pub const DPE_SIZE: u32 = 5 * 1024;
 pub const PCR_RESET_COUNTER_SIZE: u32 = 1024;
-pub const AUTH_MAN_IMAGE_METADATA_MAX_SIZE: u32 = 7 * 1024;
+pub const AUTH_MAN_IMAGE_METADATA_MAX_SIZE: u32 = 9 * 1024;
 pub const IDEVID_CSR_SIZE: u32 = 1024;
 pub const FMC_ALIAS_CSR_SIZE: u32 = 1024;
-pub const RESERVED_MEMORY_SIZE: u32 = 3 * 1024;
+pub const LDEVID_MLDSA_CERT_SIZE: u32 = 8 * 1024;
+pub const FMCALIAS_MLDSA_CERT_SIZE: u32 = 8 * 1024;
+pub const RTALIAS_MLDSA_CERT_SIZE: u32 = 8 * 1024;
+pub const AUTH_MAN_MIN_SVN_SIZE: u32 = 4;
+pub const RESERVED_MEMORY_SIZE: u32 = 1024 - AUTH_MAN_MIN_SVN_SIZE;
+pub const MLDSA_PREAMBLE1_SIZE: u32 = 16 * 1024;
+pub const PQC_TRANSFER_STAGING_SIZE: u32 = 20 * 1024;
+pub const PQC_RESERVED_MEMORY_SIZE: u32 = memory_layout::PQC_DATA_SIZE
+    - MLDSA_PREAMBLE1_SIZE
+    - PQC_TRANSFER_STAGING_SIZE
+    - LDEVID_MLDSA_CERT_SIZE
+    - FMCALIAS_MLDSA_CERT_SIZE
+    - RTALIAS_MLDSA_CERT_SIZE;
+
+pub const LDEVID_MLDSA_CERT_OFFSET: u32 = MLDSA_PREAMBLE1_SIZE + PQC_TRANSFER_STAGING_SIZE;
+pub const FMCALIAS_MLDSA_CERT_OFFSET: u32 = LDEVID_MLDSA_CERT_OFFSET + LDEVID_MLDSA_CERT_SIZE;
+pub const RTALIAS_MLDSA_CERT_OFFSET: u32 = FMCALIAS_MLDSA_CERT_OFFSET + FMCALIAS_MLDSA_CERT_SIZE;
 
 pub const PCR_LOG_MAX_COUNT: usize = 17;
 pub const FUSE_LOG_MAX_COUNT: usize = 62;
 pub const MEASUREMENT_MAX_COUNT: usize = 8;
+pub const MLDSA87_TBS_MAX_SIZE: usize = 3328;
 
 #[cfg(feature = "runtime")]
 const DPE_DCCM_STORAGE: usize = size_of::<DpeInstance>()
@@ -61,7 +79,52 @@ pub type FuseLogArray = [FuseLogEntry; FUSE_LOG_MAX_COUNT];
 pub type StashMeasurementArray = [MeasurementLogEntry; MEASUREMENT_MAX_COUNT];
 #[cfg(feature = "runtime")]
 pub type AuthManifestImageMetadataList =
-    [AuthManifestImageMetadata; AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT];
+    [AuthManifestImageMetadata; AUTH_MANIFEST_IMAGE_METADATA_STORE_MAX_COUNT];
+
+// Size of the buffer holding the data written by inbound transfers
+#[cfg(all(feature = "runtime", not(feature = "hw-2.x")))]
+pub const TRANSFER_STAGING_SIZE: usize = MAN2_SIZE as usize;
+#[cfg(all(feature = "runtime", feature = "hw-2.x"))]
+pub const TRANSFER_STAGING_SIZE: usize = PQC_TRANSFER_STAGING_SIZE as usize;
+
+// Number of image metadata entries runtime can hold across all authorization
+// manifest chunks. Bounded by the persistent data region, not the manifest format.
+#[cfg(feature = "runtime")]
+pub const AUTH_MANIFEST_IMAGE_METADATA_STORE_MAX_COUNT: usize =
+    (AUTH_MAN_IMAGE_METADATA_MAX_SIZE as usize - 5 * size_of::<u32>() - size_of::<ImageDigest>())
+        / size_of::<AuthManifestImageMetadata>();
+
+/// Image metadata list accumulated from the authorization manifest chunks,
+/// sorted by firmware ID.
+#[cfg(feature = "runtime")]
+#[repr(C)]
+#[derive(IntoBytes, FromBytes, Immutable, KnownLayout, Zeroize)]
+pub struct AuthManifestImageMetadataStore {
+    pub entry_count: u32,
+
+    pub svn: u32,
+
+    pub collection_id: u32,
+
+    /// Number of chunks of the collection, 0 if no collection is loaded
+    pub chunk_count: u32,
+
+    pub next_chunk_index: u32,
+
+    /// Digest of chunk 0 of the collection
+    pub first_chunk_digest: ImageDigest,
+
+    pub image_metadata_list: AuthManifestImageMetadataList,
+}
+
+#[cfg(feature = "runtime")]
+impl AuthManifestImageMetadataStore {
+    /// Returns the used entries of the image metadata list
+    pub fn entries(&self) -> &[AuthManifestImageMetadata] {
+        let count = core::cmp::min(self.entry_count as usize, self.image_metadata_list.len());
+        &self.image_metadata_list[..count]
+    }
+}
 
 const _: () = assert!(MAX_CSR_SIZE < IDEVID_CSR_SIZE as usize);
 const _: () = assert!(MAX_CSR_SIZE < FMC_ALIAS_CSR_SIZE as usize);
@@ -136,6 +199,35 @@ pub mod fmc_alias_csr {
     }
 }
 
+/// `To Be Signed` portion and signature of an ML-DSA-87 DICE certificate.
+#[derive(Clone, TryFromBytes, IntoBytes, KnownLayout, Zeroize)]
+#[repr(C)]
+pub struct Mldsa87CertData {
+    pub tbs_size: u32,
+    pub tbs: [u8; MLDSA87_TBS_MAX_SIZE],
+    pub sig: Mldsa87Signature,
+}
+
+impl Default for Mldsa87CertData {
+    fn default() -> Self {
+        Self {
+            tbs_size: 0,
+            tbs: [0; MLDSA87_TBS_MAX_SIZE],
+            sig: Mldsa87Signature::default(),
+        }
+    }
+}
+
+impl Mldsa87CertData {
+    /// Get the TBS buffer, or `None` if the certificate was not generated.
+    pub fn tbs(&self) -> Option<&[u8]> {
+        match self.tbs_size {
+            0 => None,
+            size => self.tbs.get(..size as usize),
+        }
+    }
+}
+
 impl Default for IdevIdCsr {
     fn default() -> Self {
         Self {
@@ -197,9 +289,12 @@ const _: () = assert!(size_of::<FuseLogArray>() <= FUSE_LOG_SIZE as usize);
 const _: () = assert!(size_of::<PcrResetCounter>() <= PCR_RESET_COUNTER_SIZE as usize);
 #[cfg(feature = "runtime")]
 const _: () = assert!(
-    size_of::<AuthManifestImageMetadataCollection>() <= AUTH_MAN_IMAGE_METADATA_MAX_SIZE as usize
+    size_of::<AuthManifestImageMetadataStore>() <= AUTH_MAN_IMAGE_METADATA_MAX_SIZE as usize
 );
 const _: () = assert!(size_of::<IdevIdCsr>() <= IDEVID_CSR_SIZE as usize);
+const _: () = assert!(size_of::<Mldsa87CertData>() <= LDEVID_MLDSA_CERT_SIZE as usize);
+const _: () = assert!(size_of::<Mldsa87CertData>() <= FMCALIAS_MLDSA_CERT_SIZE as usize);
+const _: () = assert!(size_of::<Mldsa87CertData>() <= RTALIAS_MLDSA_CERT_SIZE as usize);
 
 #[derive(TryFromBytes, IntoBytes, KnownLayout, Zeroize)]
 #[repr(C)]
@@ -207,9 +302,17 @@ pub struct PersistentData {
     pub manifest1: ImageManifest,
     reserved0: [u8; MAN1_SIZE as usize - size_of::<ImageManifest>()],
 
+    #[cfg(any(not(feature = "runtime"), feature = "hw-2.x"))]
     pub manifest2: ImageManifest,
+    #[cfg(any(not(feature = "runtime"), feature = "hw-2.x"))]
     reserved1: [u8; MAN2_SIZE as usize - size_of::<ImageManifest>()],
 
+    // ROM only uses the second manifest during update reset, and loads it
+    // from the mailbox first, so runtime stages inbound transfers there
+    // unless it has the larger staging buffer of the PQC persistent data.
+    #[cfg(all(feature = "runtime", not(feature = "hw-2.x")))]
+    pub transfer_staging: [u8; TRANSFER_STAGING_SIZE],
+
     pub fht: FirmwareHandoffTable,
     reserved2: [u8; FHT_SIZE as usize - size_of::<FirmwareHandoffTable>()],
 
@@ -249,10 +352,10 @@ pub struct PersistentData {
     pcr_reset: [u8; PCR_RESET_COUNTER_SIZE as usize],
 
     #[cfg(feature = "runtime")]
-    pub auth_manifest_image_metadata_col: AuthManifestImageMetadataCollection,
+    pub auth_manifest_image_metadata_col: AuthManifestImageMetadataStore,
     #[cfg(feature = "runtime")]
     reserved9: [u8; AUTH_MAN_IMAGE_METADATA_MAX_SIZE as usize
-        - size_of::<AuthManifestImageMetadataCollection>()],
+        - size_of::<AuthManifestImageMetadataStore>()],
 
     #[cfg(not(feature = "runtime"))]
     pub auth_manifest_image_metadata_col: [u8; AUTH_MAN_IMAGE_METADATA_MAX_SIZE as usize],
@@ -264,12 +367,108 @@ pub struct PersistentData {
 
     reserved11: [u8; FMC_ALIAS_CSR_SIZE as usize - size_of::<FmcAliasCsr>()],
 
+    // Minimum SVN of the next auth manifest image metadata collection.
+    pub auth_manifest_min_svn: u32,
+
     // Reserved memory for future objects.
     // New objects should always source memory from this range.
     // Taking memory from this reserve does NOT break hitless updates.
     pub reserved_memory: [u8; RESERVED_MEMORY_SIZE as usize],
 }
 
+const _: () = assert!(size_of::<PqcPersistentData>() == memory_layout::PQC_DATA_SIZE as usize);
+const _: () = assert!(size_of::<ImageMldsaPreamble>() <= MLDSA_PREAMBLE1_SIZE as usize);
+
+/// Persistent data of Caliptra 2.x firmware.
+///
+/// Lives in the upper half of the Caliptra 2.x DCCM, so it must only be
+/// accessed by `hw-2.x` builds.
+#[derive(TryFromBytes, IntoBytes, KnownLayout, Zeroize)]
+#[repr(C)]
+pub struct PqcPersistentData {
+    /// ML-DSA-87 preamble of the image described by `manifest1`
+    pub mldsa_preamble1: ImageMldsaPreamble,
+    reserved0: [u8; MLDSA_PREAMBLE1_SIZE as usize - size_of::<ImageMldsaPreamble>()],
+
+    #[cfg(feature = "runtime")]
+    pub transfer_staging: [u8; PQC_TRANSFER_STAGING_SIZE as usize],
+    #[cfg(not(feature = "runtime"))]
+    transfer_staging: [u8; PQC_TRANSFER_STAGING_SIZE as usize],
+
+    pub ldevid_mldsa_cert: Mldsa87CertData,
+    reserved1: [u8; LDEVID_MLDSA_CERT_SIZE as usize - size_of::<Mldsa87CertData>()],
+
+    pub fmcalias_mldsa_cert: Mldsa87CertData,
+    reserved2: [u8; FMCALIAS_MLDSA_CERT_SIZE as usize - size_of::<Mldsa87CertData>()],
+
+    pub rtalias_mldsa_cert: Mldsa87CertData,
+    reserved3: [u8; RTALIAS_MLDSA_CERT_SIZE as usize - size_of::<Mldsa87CertData>()],
+
+    // Reserved memory for future objects.
+    pub reserved_memory: [u8; PQC_RESERVED_MEMORY_SIZE as usize],
+}
+
+// The ML-DSA-87 certificates are read by every firmware stage, so pin their
+// offsets at compile time.
+const _: () = unsafe {
+    let p = core::mem::MaybeUninit::<PqcPersistentData>::uninit();
+    let base = p.as_ptr();
+    assert!(
+        addr_of!((*base).ldevid_mldsa_cert)
+            .cast::<u8>()
+            .offset_from(base.cast::<u8>())
+            == LDEVID_MLDSA_CERT_OFFSET as isize
+    );
+    assert!(
+        addr_of!((*base).fmcalias_mldsa_cert)
+            .cast::<u8>()
+            .offset_from(base.cast::<u8>())
+            == FMCALIAS_MLDSA_CERT_OFFSET as isize
+    );
+    assert!(
+        addr_of!((*base).rtalias_mldsa_cert)
+            .cast::<u8>()
+            .offset_from(base.cast::<u8>())
+            == RTALIAS_MLDSA_CERT_OFFSET as isize
+    );
+};
+
+impl PqcPersistentData {
+    pub fn assert_matches_layout() {
+        const P: *const PqcPersistentData = memory_layout::PQC_DATA_ORG as *const PqcPersistentData;
+        unsafe {
+            assert_eq!(
+                addr_of!((*P).mldsa_preamble1) as u32,
+                memory_layout::PQC_DATA_ORG
+            );
+            assert_eq!(
+                addr_of!((*P).transfer_staging) as u32,
+                memory_layout::PQC_DATA_ORG + MLDSA_PREAMBLE1_SIZE
+            );
+            assert_eq!(
+                addr_of!((*P).ldevid_mldsa_cert) as u32,
+                memory_layout::PQC_DATA_ORG + LDEVID_MLDSA_CERT_OFFSET
+            );
+            assert_eq!(
+                addr_of!((*P).fmcalias_mldsa_cert) as u32,
+                memory_layout::PQC_DATA_ORG + FMCALIAS_MLDSA_CERT_OFFSET
+            );
+            assert_eq!(
+                addr_of!((*P).rtalias_mldsa_cert) as u32,
+                memory_layout::PQC_DATA_ORG + RTALIAS_MLDSA_CERT_OFFSET
+            );
+            assert_eq!(
+                addr_of!((*P).reserved_memory) as u32,
+                memory_layout::PQC_DATA_ORG + RTALIAS_MLDSA_CERT_OFFSET + RTALIAS_MLDSA_CERT_SIZE
+            );
+            assert_eq!(
+                P.add(1) as u32,
+                memory_layout::PQC_DATA_ORG + memory_layout::PQC_DATA_SIZE
+            );
+        }
+    }
+}
+
 impl PersistentData {
     pub fn assert_matches_layout() {
         const P: *const PersistentData =
@@ -281,10 +480,16 @@ impl PersistentData {
                 memory_layout::PERSISTENT_DATA_ORG
             );
             persistent_data_offset += MAN1_SIZE;
+            #[cfg(any(not(feature = "runtime"), feature = "hw-2.x"))]
             assert_eq!(
                 addr_of!((*P).manifest2) as u32,
                 memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
             );
+            #[cfg(all(feature = "runtime", not(feature = "hw-2.x")))]
+            assert_eq!(
+                addr_of!((*P).transfer_staging) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
 
             persistent_data_offset += MAN2_SIZE;
             assert_eq!(
@@ -359,6 +564,12 @@ impl PersistentData {
             );
 
             persistent_data_offset += FMC_ALIAS_CSR_SIZE;
+            assert_eq!(
+                addr_of!((*P).auth_manifest_min_svn) as u32,
+                memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
+            );
+
+            persistent_data_offset += AUTH_MAN_MIN_SVN_SIZE;
             assert_eq!(
                 addr_of!((*P).reserved_memory) as u32,
                 memory_layout::PERSISTENT_DATA_ORG + persistent_data_offset
@@ -410,6 +621,81 @@ impl PersistentDataAccessor {
         // safety. Do not change this API without review by a Rust expert.
         unsafe { ref_mut_from_addr(memory_layout::PERSISTENT_DATA_ORG) }
     }
+
+    /// Returns the Caliptra 2.x persistent data
+    ///
+    /// # Safety
+    ///
+    /// Same rules as `get()`. Only Caliptra 2.x hardware has this memory; only
+    /// call this from `hw-2.x` builds or for ML-DSA-87 firmware, which only
+    /// verifies on Caliptra 2.x.
+    #[inline(always)]
+    pub fn pqc(&self) -> &PqcPersistentData {
+        // WARNING: The returned lifetime elided from `self` is critical for
+        // safety. Do not change this API without review by a Rust expert.
+        unsafe { ref_from_addr(memory_layout::PQC_DATA_ORG) }
+    }
+
+    /// Returns the Caliptra 2.x persistent data
+    ///
+    /// # Safety
+    ///
+    /// Same rules as `get_mut()`. Only Caliptra 2.x hardware has this memory;
+    /// only call this from `hw-2.x` builds or for ML-DSA-87 firmware, which
+    /// only verifies on Caliptra 2.x.
+    #[inline(always)]
+    pub fn pqc_mut(&mut self) -> &mut PqcPersistentData {
+        // WARNING: The returned lifetime elided from `self` is critical for
+        // safety. Do not change this API without review by a Rust expert.
+        unsafe { ref_mut_from_addr(memory_layout::PQC_DATA_ORG) }
+    }
+
+    /// Returns the persistent data and the Caliptra 2.x persistent data,
+    /// which do not overlap
+    ///
+    /// # Safety
+    ///
+    /// Same rules as `get_mut()` and `pqc_mut()`.
+    #[cfg(feature = "hw-2.x")]
+    #[inline(always)]
+    pub fn get_mut_and_pqc_mut(&mut self) -> (&mut PersistentData, &mut PqcPersistentData) {
+        // WARNING: The returned lifetimes elided from `self` are critical for
+        // safety. Do not change this API without review by a Rust expert.
+        unsafe {
+            (
+                ref_mut_from_addr(memory_layout::PERSISTENT_DATA_ORG),
+                ref_mut_from_addr(memory_layout::PQC_DATA_ORG),
+            )
+        }
+    }
+
+    /// Returns the buffer holding the data written by inbound transfers
+    #[cfg(all(feature = "runtime", not(feature = "hw-2.x")))]
+    #[inline(always)]
+    pub fn transfer_staging(&self) -> &[u8; TRANSFER_STAGING_SIZE] {
+        &self.get().transfer_staging
+    }
+
+    /// Returns the buffer holding the data written by inbound transfers
+    #[cfg(all(feature = "runtime", feature = "hw-2.x"))]
+    #[inline(always)]
+    pub fn transfer_staging(&self) -> &[u8; TRANSFER_STAGING_SIZE] {
+        &self.pqc().transfer_staging
+    }
+
+    /// Returns the buffer holding the data written by inbound transfers
+    #[cfg(all(feature = "runtime", not(feature = "hw-2.x")))]
+    #[inline(always)]
+    pub fn transfer_staging_mut(&mut self) -> &mut [u8; TRANSFER_STAGING_SIZE] {
+        &mut self.get_mut().transfer_staging
+    }
+
+    /// Returns the buffer holding the data written by inbound transfers
+    #[cfg(all(feature = "runtime", feature = "hw-2.x"))]
+    #[inline(always)]
+    pub fn transfer_staging_mut(&mut self) -> &mut [u8; TRANSFER_STAGING_SIZE] {
+        &mut self.pqc_mut().transfer_staging
+    }
 }
 
 #[inline(always)]
@@ -443,5 +729,6 @@ mod tests {
         // NOTE: It's not good enough to test this from the host; we also need
         // to call assert_matches_layout() in a risc-v test.
         PersistentData::assert_matches_layout();
+        PqcPersistentData::assert_matches_layout();
     }
 }
@@ -32,11 +32,18 @@ pub type Sha384Digest<'a> = &'a mut Array4x12;
 
 pub struct Sha384 {
     sha512: Sha512Reg,
+
+    /// Number of digests started on the engine, which tells a resumable
+    /// digest whether the engine still holds its intermediate state
+    generation: u32,
 }
 
 impl Sha384 {
     pub fn new(sha512: Sha512Reg) -> Self {
-        Self { sha512 }
+        Self {
+            sha512,
+            generation: 0,
+        }
     }
     /// Initialize multi step digest operation
     ///
@@ -120,6 +127,83 @@ impl Sha384 {
         Ok(digest)
     }
 
+    /// Whether `op` can be resumed, which is the case unless another digest
+    /// used the engine since `op` was last updated
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - Resumable digest operation
+    pub fn can_resume(&self, op: &Sha384Resumable) -> bool {
+        op.generation.is_none() || op.generation == Some(self.generation)
+    }
+
+    /// Update a resumable digest with whole blocks of data
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - Resumable digest operation
+    /// * `data` - Data to update the digest with, a multiple of the block size
+    pub fn resumable_update(
+        &mut self,
+        op: &mut Sha384Resumable,
+        data: &[u8],
+    ) -> CaliptraResult<()> {
+        if !self.can_resume(op) {
+            return Err(CaliptraError::DRIVER_SHA384_INVALID_STATE_ERR);
+        }
+        if data.len() % SHA384_BLOCK_BYTE_SIZE != 0 {
+            return Err(CaliptraError::DRIVER_SHA384_INVALID_SLICE);
+        }
+        if op.data_size + data.len() > SHA384_MAX_DATA_SIZE {
+            return Err(CaliptraError::DRIVER_SHA384_MAX_DATA_ERR);
+        }
+
+        for block in data.chunks_exact(SHA384_BLOCK_BYTE_SIZE) {
+            let block = <&[u8; SHA384_BLOCK_BYTE_SIZE]>::try_from(block)
+                .map_err(|_| CaliptraError::DRIVER_SHA384_INVALID_SLICE)?;
+            self.digest_block(block, op.generation.is_none(), false)?;
+            op.generation = Some(self.generation);
+        }
+        op.data_size += data.len();
+
+        Ok(())
+    }
+
+    /// Finalize a resumable digest with the rest of the data
+    ///
+    /// # Arguments
+    ///
+    /// * `op` - Resumable digest operation
+    /// * `data` - Rest of the data
+    ///
+    /// # Returns
+    ///
+    /// * `Array4x12` - Digest of all the data
+    pub fn resumable_finalize(
+        &mut self,
+        op: &mut Sha384Resumable,
+        data: &[u8],
+    ) -> CaliptraResult<Array4x12> {
+        // PANIC-FREE: Use data.get() instead of split_at() as the compiler
+        // cannot reason about the split point to optimize out the panic.
+        let split = data.len() - data.len() % SHA384_BLOCK_BYTE_SIZE;
+        let (Some(blocks), Some(rest)) = (data.get(..split), data.get(split..)) else {
+            return Err(CaliptraError::DRIVER_SHA384_INVALID_SLICE);
+        };
+        self.resumable_update(op, blocks)?;
+        if op.data_size + rest.len() > SHA384_MAX_DATA_SIZE {
+            return Err(CaliptraError::DRIVER_SHA384_MAX_DATA_ERR);
+        }
+
+        op.data_size += rest.len();
+        self.digest_partial_block(rest, op.generation.is_none(), op.data_size)?;
+        let digest = self.read_digest();
+        self.zeroize_internal();
+        *op = Sha384Resumable::default();
+
+        Ok(digest)
+    }
+
     /// Zeroize the hardware registers.
     fn zeroize_internal(&mut self) {
         self.sha512.regs_mut().ctrl().write(|w| w.zeroize(true));
@@ -169,6 +253,9 @@ impl Sha384 {
         // Wait for the registers to be ready
         wait::until(|| status_reg.read().ready());
 
+        // The engine no longer holds the state of a resumable digest
+        self.generation = self.generation.wrapping_add(1);
+
         // Write the nonce into the register
         reg.gen_pcr_hash_nonce().write(&nonce.into());
 
@@ -309,6 +396,10 @@ impl Sha384 {
     fn digest_op(&mut self, first: bool, last: bool) -> CaliptraResult<()> {
         const MODE_SHA384: u32 = 0b10;
 
+        if first {
+            self.generation = self.generation.wrapping_add(1);
+        }
+
         let sha = self.sha512.regs_mut();
 
         // Wait for the hardware to be ready
@@ -431,6 +522,26 @@ impl<'a> Sha384DigestOp<'a> {
     }
 }
 
+/// SHA-384 digest computed over several calls with other work in between,
+/// such as one call per step of a background job. Unlike `Sha384DigestOp` it
+/// does not borrow the engine, so the engine can be used meanwhile; the
+/// digest then has to start over, which `Sha384::can_resume` tells.
+#[derive(Debug, Default, Copy, Clone, Eq, PartialEq)]
+pub struct Sha384Resumable {
+    /// Engine generation after the last block, or None before the first block
+    generation: Option<u32>,
+
+    /// Data size
+    data_size: usize,
+}
+
+impl Sha384Resumable {
+    /// Size of the data digested so far
+    pub fn data_size(&self) -> usize {
+        self.data_size
+    }
+}
+
 /// SHA-384 key access error trait
 trait Sha384KeyAccessErr {
     /// Convert to read data operation error
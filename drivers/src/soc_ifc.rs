@@ -76,6 +76,12 @@ impl SocIfc {
         soc_ifc_regs.cptra_security_state().read().debug_locked()
     }
 
+    /// Retrieve the last non-fatal firmware error code
+    pub fn fw_error_non_fatal(&self) -> u32 {
+        let soc_ifc_regs = self.soc_ifc.regs();
+        soc_ifc_regs.cptra_fw_error_non_fatal().read()
+    }
+
     pub fn mbox_valid_pauser(&self) -> [u32; 5] {
         let soc_ifc_regs = self.soc_ifc.regs();
         soc_ifc_regs.cptra_mbox_valid_pauser().read()
@@ -0,0 +1,196 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    mldsa87_tests.rs
+
+Abstract:
+
+    File contains test cases for ML-DSA-87 API tests
+
+--*/
+
+#![no_std]
+#![no_main]
+
+use caliptra_cfi_lib::CfiCounter;
+use caliptra_drivers::{
+    Array4x12, Array4x8, Ecc384, Ecc384PrivKeyOut, Ecc384Scalar, Ecc384Seed, KeyId, KeyReadArgs,
+    KeyUsage, KeyWriteArgs, Mldsa87, Mldsa87Msg, Mldsa87Result, Mldsa87Seed, Mldsa87SignRnd, Trng,
+};
+use caliptra_kat::Mldsa87Kat;
+use caliptra_registers::csrng::CsrngReg;
+use caliptra_registers::ecc::EccReg;
+use caliptra_registers::entropy_src::EntropySrcReg;
+use caliptra_registers::mldsa::MldsaReg;
+use caliptra_registers::soc_ifc::SocIfcReg;
+use caliptra_registers::soc_ifc_trng::SocIfcTrngReg;
+use caliptra_test_harness::test_suite;
+
+const SEED: Array4x8 = Array4x8::new([
+    0x2d6b07e4, 0x0e1e52b1, 0x64a5a07b, 0x8f7bbbdc, 0x6e88d4b6, 0x5af0a1f4, 0x3cc5f3d9, 0x91ad2d4d,
+]);
+
+const MSG: Mldsa87Msg = Mldsa87Msg::new([
+    0xc8f518d4, 0xf3aa1bd4, 0x6ed56c1c, 0x3c9e16fb, 0x800af504, 0xdb988435, 0x48c5f623, 0xee115f73,
+    0xd4c62abc, 0x06d303b5, 0xd90d9a17, 0x5087290d, 0, 0, 0, 0,
+]);
+
+fn new_trng() -> Trng {
+    unsafe {
+        Trng::new(
+            CsrngReg::new(),
+            EntropySrcReg::new(),
+            SocIfcTrngReg::new(),
+            &SocIfcReg::new(),
+        )
+        .unwrap()
+    }
+}
+
+fn test_gen_key_pair() {
+    let mut mldsa87 = unsafe { Mldsa87::new(MldsaReg::new()) };
+    let mut trng = new_trng();
+
+    let pub_key = mldsa87
+        .key_pair(&Mldsa87Seed::from(&SEED), &mut trng)
+        .unwrap();
+
+    // Key generation is deterministic for a given seed.
+    let pub_key2 = mldsa87
+        .key_pair(&Mldsa87Seed::from(&SEED), &mut trng)
+        .unwrap();
+    assert_eq!(pub_key, pub_key2);
+
+    let other_seed = Array4x8::new([0u32; 8]);
+    let other_pub_key = mldsa87
+        .key_pair(&Mldsa87Seed::from(&other_seed), &mut trng)
+        .unwrap();
+    assert_ne!(pub_key, other_pub_key);
+}
+
+fn test_sign_and_verify() {
+    let mut mldsa87 = unsafe { Mldsa87::new(MldsaReg::new()) };
+    let mut trng = new_trng();
+
+    let seed = Mldsa87Seed::from(&SEED);
+    let pub_key = mldsa87.key_pair(&seed, &mut trng).unwrap();
+
+    let signature = mldsa87
+        .sign(&seed, &pub_key, &MSG, &Mldsa87SignRnd::default(), &mut trng)
+        .unwrap();
+
+    let result = mldsa87.verify(&pub_key, &MSG, &signature).unwrap();
+    assert_eq!(result, Mldsa87Result::Success);
+}
+
+fn test_verify_failure() {
+    let mut mldsa87 = unsafe { Mldsa87::new(MldsaReg::new()) };
+    let mut trng = new_trng();
+
+    let seed = Mldsa87Seed::from(&SEED);
+    let pub_key = mldsa87.key_pair(&seed, &mut trng).unwrap();
+
+    let mut signature = mldsa87
+        .sign(&seed, &pub_key, &MSG, &Mldsa87SignRnd::default(), &mut trng)
+        .unwrap();
+
+    // Wrong message
+    let msg = Mldsa87Msg::default();
+    let result = mldsa87.verify(&pub_key, &msg, &signature).unwrap();
+    assert_eq!(result, Mldsa87Result::SigVerifyFailed);
+
+    // Corrupted signature
+    signature.0[0] ^= 1;
+    let result = mldsa87.verify(&pub_key, &MSG, &signature).unwrap();
+    assert_eq!(result, Mldsa87Result::SigVerifyFailed);
+}
+
+fn test_sign_and_verify_var() {
+    let mut mldsa87 = unsafe { Mldsa87::new(MldsaReg::new()) };
+    let mut trng = new_trng();
+
+    let seed = Mldsa87Seed::from(&SEED);
+    let pub_key = mldsa87.key_pair(&seed, &mut trng).unwrap();
+
+    // Both a whole number of words and a partial last word
+    for msg in [&b"streamed message"[..], &b"streamed message!"[..]] {
+        let signature = mldsa87
+            .sign_var(&seed, &pub_key, msg, &Mldsa87SignRnd::default(), &mut trng)
+            .unwrap();
+        let result = mldsa87.verify_var(&pub_key, msg, &signature).unwrap();
+        assert_eq!(result, Mldsa87Result::Success);
+
+        // Truncated message
+        let result = mldsa87
+            .verify_var(&pub_key, &msg[..msg.len() - 1], &signature)
+            .unwrap();
+        assert_eq!(result, Mldsa87Result::SigVerifyFailed);
+    }
+}
+
+fn test_kv_seed() {
+    let mut ecc = unsafe { Ecc384::new(EccReg::new()) };
+    let mut mldsa87 = unsafe { Mldsa87::new(MldsaReg::new()) };
+    let mut trng = new_trng();
+
+    //
+    // Step 1: Generate an ECC key pair and store the private key in kv slot 3.
+    // Mark the key as ecc_key_gen_seed as it will be used as the ML-DSA-87 seed.
+    //
+    let key_out = KeyWriteArgs {
+        id: KeyId::KeyId3,
+        usage: KeyUsage::default()
+            .set_ecc_key_gen_seed_en()
+            .set_ecc_private_key_en(),
+    };
+    ecc.key_pair(
+        &Ecc384Seed::from(&Ecc384Scalar::from([0u8; 48])),
+        &Array4x12::default(),
+        &mut trng,
+        Ecc384PrivKeyOut::from(key_out),
+    )
+    .unwrap();
+
+    //
+    // Step 2: Generate an ML-DSA-87 key pair from the seed in kv slot 3.
+    //
+    let seed = Mldsa87Seed::from(KeyReadArgs::new(KeyId::KeyId3));
+    let pub_key = mldsa87.key_pair(&seed, &mut trng).unwrap();
+
+    //
+    // Step 3: Sign with the key pair derived from the kv seed and verify.
+    //
+    let signature = mldsa87
+        .sign(&seed, &pub_key, &MSG, &Mldsa87SignRnd::default(), &mut trng)
+        .unwrap();
+    let result = mldsa87.verify(&pub_key, &MSG, &signature).unwrap();
+    assert_eq!(result, Mldsa87Result::Success);
+}
+
+fn test_kat() {
+    let mut mldsa87 = unsafe { Mldsa87::new(MldsaReg::new()) };
+    let mut trng = new_trng();
+
+    // Init CFI
+    let mut entropy_gen = || trng.generate().map(|a| a.0);
+    CfiCounter::reset(&mut entropy_gen);
+
+    assert_eq!(
+        Mldsa87Kat::default()
+            .execute(&mut mldsa87, &mut trng)
+            .is_ok(),
+        true
+    );
+}
+
+test_suite! {
+    test_kat,
+    test_gen_key_pair,
+    test_sign_and_verify,
+    test_verify_failure,
+    test_sign_and_verify_var,
+    test_kv_seed,
+}
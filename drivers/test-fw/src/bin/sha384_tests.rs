@@ -16,7 +16,7 @@ Abstract:
 #![no_main]
 
 use caliptra_cfi_lib::CfiCounter;
-use caliptra_drivers::{Array4x12, PcrBank, PcrId, Sha384};
+use caliptra_drivers::{Array4x12, PcrBank, PcrId, Sha384, Sha384Resumable};
 use caliptra_kat::Sha384Kat;
 use caliptra_registers::{pv::PvReg, sha512::Sha512Reg};
 
@@ -382,6 +382,49 @@ fn test_pcr_hash_extend_limit() {
     assert!(result.is_err());
 }
 
+fn test_resumable() {
+    let mut sha384 = unsafe { Sha384::new(Sha512Reg::new()) };
+    let mut data = [0u8; 1000];
+    for (i, byte) in data.iter_mut().enumerate() {
+        *byte = i as u8;
+    }
+    let expected = sha384.digest(&data).unwrap();
+
+    let mut op = Sha384Resumable::default();
+    sha384.resumable_update(&mut op, &data[..256]).unwrap();
+    sha384.resumable_update(&mut op, &data[256..512]).unwrap();
+    assert_eq!(op.data_size(), 512);
+    let digest = sha384.resumable_finalize(&mut op, &data[512..]).unwrap();
+    assert_eq!(digest, expected);
+
+    // Only whole blocks are added before finalizing
+    let mut op = Sha384Resumable::default();
+    assert!(sha384.resumable_update(&mut op, &data[..100]).is_err());
+}
+
+fn test_resumable_interrupted() {
+    let mut sha384 = unsafe { Sha384::new(Sha512Reg::new()) };
+    let data = [0xa5u8; 384];
+    let expected = sha384.digest(&data).unwrap();
+
+    let mut op = Sha384Resumable::default();
+    assert!(sha384.can_resume(&op));
+    sha384.resumable_update(&mut op, &data[..128]).unwrap();
+    assert!(sha384.can_resume(&op));
+
+    // Another digest replaces the state held by the engine
+    sha384.digest("abc".as_bytes()).unwrap();
+    assert!(!sha384.can_resume(&op));
+    assert!(sha384.resumable_update(&mut op, &data[128..]).is_err());
+    assert!(sha384.resumable_finalize(&mut op, &data[128..]).is_err());
+
+    // Starting over gives the right digest
+    let mut op = Sha384Resumable::default();
+    sha384.resumable_update(&mut op, &data[..128]).unwrap();
+    let digest = sha384.resumable_finalize(&mut op, &data[128..]).unwrap();
+    assert_eq!(digest, expected);
+}
+
 fn test_kat() {
     // Init CFI
     CfiCounter::reset(&mut || Ok([0xDEADBEEFu32; 12]));
@@ -410,4 +453,6 @@ test_suite! {
     test_pcr_hash_extend_single_block_2,
     test_pcr_hash_extend_single_block_3,
     test_pcr_hash_extend_limit,
+    test_resumable,
+    test_resumable_interrupted,
 }
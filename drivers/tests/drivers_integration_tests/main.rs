@@ -720,6 +720,12 @@ fn test_uc_to_soc_error_state() {
     model.step_until(|m| m.soc_mbox().status().read().mbox_fsm_ps().mbox_idle());
 }
 
+#[test]
+#[cfg(feature = "hw-2.x")]
+fn test_mldsa87() {
+    run_driver_test(&firmware::driver_tests::MLDSA87);
+}
+
 #[test]
 fn test_pcrbank() {
     run_driver_test(&firmware::driver_tests::PCRBANK);
@@ -262,6 +262,34 @@ impl CaliptraError {
         CaliptraError::new_const(0x000b0040);
     pub const IMAGE_VERIFIER_ERR_DIGEST_OUT_OF_BOUNDS: CaliptraError =
         CaliptraError::new_const(0x000b0041);
+    pub const IMAGE_VERIFIER_ERR_PQC_KEY_TYPE_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000b0042);
+    pub const IMAGE_VERIFIER_ERR_PQC_KEY_TYPE_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000b0043);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_INDEX_OUT_OF_BOUNDS: CaliptraError =
+        CaliptraError::new_const(0x000b0044);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_REVOKED: CaliptraError =
+        CaliptraError::new_const(0x000b0045);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_DIGEST_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000b0046);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_INDEX_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000b0047);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_MLDSA_VERIFY_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x000b0048);
+    pub const IMAGE_VERIFIER_ERR_VENDOR_MLDSA_SIGNATURE_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000b0049);
+    pub const IMAGE_VERIFIER_ERR_OWNER_MLDSA_VERIFY_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x000b004a);
+    pub const IMAGE_VERIFIER_ERR_OWNER_MLDSA_SIGNATURE_INVALID: CaliptraError =
+        CaliptraError::new_const(0x000b004b);
+    pub const IMAGE_VERIFIER_ERR_UPDATE_RESET_VENDOR_MLDSA_PUB_KEY_IDX_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000b004c);
+    pub const IMAGE_VERIFIER_ERR_MLDSA_NOT_SUPPORTED: CaliptraError =
+        CaliptraError::new_const(0x000b004d);
+    pub const IMAGE_VERIFIER_ERR_MLDSA_PREAMBLE_MISSING: CaliptraError =
+        CaliptraError::new_const(0x000b004e);
+    pub const IMAGE_VERIFIER_ERR_OWNER_MLDSA_PUB_KEY_DIGEST_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000b004f);
 
     /// Driver Error: LMS
     pub const DRIVER_LMS_INVALID_LMS_ALGO_TYPE: CaliptraError =
@@ -467,6 +495,48 @@ impl CaliptraError {
     pub const RUNTIME_REVOKE_EXPORTED_CDI_HANDLE_NOT_FOUND: CaliptraError =
         CaliptraError::new_const(0x000E005A);
 
+    pub const RUNTIME_GET_LDEVID_MLDSA_CERT_FAILED: CaliptraError =
+        CaliptraError::new_const(0x000E005B);
+    pub const RUNTIME_GET_FMC_ALIAS_MLDSA_CERT_FAILED: CaliptraError =
+        CaliptraError::new_const(0x000E005C);
+    pub const RUNTIME_GET_RT_ALIAS_MLDSA_CERT_FAILED: CaliptraError =
+        CaliptraError::new_const(0x000E005D);
+    pub const RUNTIME_MLDSA_CERT_CHAIN_UNAVAILABLE: CaliptraError =
+        CaliptraError::new_const(0x000E005E);
+    pub const RUNTIME_LOG_INVALID_START_INDEX: CaliptraError = CaliptraError::new_const(0x000E005F);
+    pub const RUNTIME_LOG_INDEX_CORRUPTED: CaliptraError = CaliptraError::new_const(0x000E0060);
+    pub const RUNTIME_DPE_CONFIG_INVALID: CaliptraError = CaliptraError::new_const(0x000E0061);
+    pub const RUNTIME_DPE_COMMAND_NOT_ALLOWED: CaliptraError = CaliptraError::new_const(0x000E0062);
+    pub const RUNTIME_AUTH_MANIFEST_SVN_TOO_LOW: CaliptraError =
+        CaliptraError::new_const(0x000E0063);
+    pub const RUNTIME_AUTH_MANIFEST_CHUNK_OUT_OF_ORDER: CaliptraError =
+        CaliptraError::new_const(0x000E0064);
+    pub const RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_STORE_FULL: CaliptraError =
+        CaliptraError::new_const(0x000E0065);
+    pub const RUNTIME_DERIVE_KEY_INVALID_CDI_HANDLE: CaliptraError =
+        CaliptraError::new_const(0x000E0066);
+    pub const RUNTIME_DERIVE_KEY_EXPORT_NOT_ALLOWED: CaliptraError =
+        CaliptraError::new_const(0x000E0067);
+    pub const RUNTIME_DERIVE_KEY_FAILED: CaliptraError = CaliptraError::new_const(0x000E0068);
+    pub const RUNTIME_SIGN_WITH_DPE_KEY_FAILED: CaliptraError =
+        CaliptraError::new_const(0x000E0069);
+    pub const RUNTIME_TRANSFER_INVALID_SOURCE: CaliptraError = CaliptraError::new_const(0x000E006A);
+    pub const RUNTIME_TRANSFER_INVALID_ID: CaliptraError = CaliptraError::new_const(0x000E006B);
+    pub const RUNTIME_TRANSFER_INVALID_OFFSET: CaliptraError = CaliptraError::new_const(0x000E006C);
+    pub const RUNTIME_JOB_INVALID_KIND: CaliptraError = CaliptraError::new_const(0x000E006D);
+    pub const RUNTIME_JOB_INVALID_ID: CaliptraError = CaliptraError::new_const(0x000E006E);
+    pub const RUNTIME_JOB_IN_PROGRESS: CaliptraError = CaliptraError::new_const(0x000E006F);
+    pub const RUNTIME_EVENT_PENDING: CaliptraError = CaliptraError::new_const(0x000E0070);
+    pub const RUNTIME_AUTH_MANIFEST_COLLECTION_ALREADY_LOADED: CaliptraError =
+        CaliptraError::new_const(0x000E0071);
+    pub const RUNTIME_AUTH_MANIFEST_CHUNK_COLLECTION_MISMATCH: CaliptraError =
+        CaliptraError::new_const(0x000E0072);
+    pub const RUNTIME_TRANSFER_IN_PROGRESS: CaliptraError = CaliptraError::new_const(0x000E0073);
+    pub const RUNTIME_TRANSFER_INCOMPLETE: CaliptraError = CaliptraError::new_const(0x000E0074);
+    pub const RUNTIME_JOB_NOT_CANCELLABLE: CaliptraError = CaliptraError::new_const(0x000E0075);
+    pub const RUNTIME_REVOKE_DERIVED_KEY_HANDLE_NOT_FOUND: CaliptraError =
+        CaliptraError::new_const(0x000E0076);
+
     /// FMC Errors
     pub const FMC_GLOBAL_NMI: CaliptraError = CaliptraError::new_const(0x000F0001);
     pub const FMC_GLOBAL_EXCEPTION: CaliptraError = CaliptraError::new_const(0x000F0002);
@@ -482,6 +552,9 @@ impl CaliptraError {
     pub const FMC_HANDOFF_NOT_READY_FOR_RT: CaliptraError = CaliptraError::new_const(0x000F000C);
     pub const FMC_GLOBAL_WDT_EXPIRED: CaliptraError = CaliptraError::new_const(0x000F000D);
     pub const FMC_UNKNOWN_RESET: CaliptraError = CaliptraError::new_const(0x000F000E);
+    pub const FMC_RT_ALIAS_MLDSA_TBS_SIZE_EXCEEDED: CaliptraError =
+        CaliptraError::new_const(0x000F0014);
+    pub const FMC_MLDSA_KV_COLLISION: CaliptraError = CaliptraError::new_const(0x000F0015);
 
     /// FMC Alias CSR Errors
     pub const FMC_ALIAS_CSR_BUILDER_INIT_FAILURE: CaliptraError =
@@ -504,6 +577,18 @@ impl CaliptraError {
     pub const ADDRESS_MISALIGNED: CaliptraError = CaliptraError::new_const(0x00110000);
     pub const ADDRESS_NOT_IN_ROM: CaliptraError = CaliptraError::new_const(0x00110001);
 
+    /// Driver Error: ML-DSA-87
+    pub const DRIVER_MLDSA87_READ_SEED_KV_READ: CaliptraError =
+        CaliptraError::new_const(0x00120001);
+    pub const DRIVER_MLDSA87_READ_SEED_KV_WRITE: CaliptraError =
+        CaliptraError::new_const(0x00120002);
+    pub const DRIVER_MLDSA87_READ_SEED_KV_UNKNOWN: CaliptraError =
+        CaliptraError::new_const(0x00120003);
+    pub const DRIVER_MLDSA87_KEYGEN_PAIRWISE_CONSISTENCY_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x00120004);
+    pub const DRIVER_MLDSA87_SIGN_VALIDATION_FAILED: CaliptraError =
+        CaliptraError::new_const(0x00120005);
+
     /// Initial Device ID Errors
     pub const ROM_IDEVID_CSR_BUILDER_INIT_FAILURE: CaliptraError =
         CaliptraError::new_const(0x01000001);
@@ -606,6 +691,11 @@ impl CaliptraError {
 
     pub const ROM_GLOBAL_FIPS_HOOKS_ROM_EXIT: CaliptraError = CaliptraError::new_const(0x0105000F);
 
+    pub const ROM_GLOBAL_UNSUPPORTED_LDEVID_MLDSA_TBS_SIZE: CaliptraError =
+        CaliptraError::new_const(0x01050010);
+    pub const ROM_GLOBAL_UNSUPPORTED_FMCALIAS_MLDSA_TBS_SIZE: CaliptraError =
+        CaliptraError::new_const(0x01050011);
+
     /// ROM KAT Errors
     pub const KAT_SHA256_DIGEST_FAILURE: CaliptraError = CaliptraError::new_const(0x90010001);
     pub const KAT_SHA256_DIGEST_MISMATCH: CaliptraError = CaliptraError::new_const(0x90010002);
@@ -639,6 +729,11 @@ impl CaliptraError {
 
     pub const ROM_INTEGRITY_FAILURE: CaliptraError = CaliptraError::new_const(0x90080001);
 
+    pub const KAT_MLDSA87_KEY_PAIR_GENERATE_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x90090001);
+    pub const KAT_MLDSA87_SIGNATURE_VERIFY_FAILURE: CaliptraError =
+        CaliptraError::new_const(0x90090002);
+
     // TODO: What base value is right for this?
     // FIPS Hooks
     pub const FIPS_HOOKS_INJECTED_ERROR: CaliptraError = CaliptraError::new_const(0x90100000);
@@ -16,7 +16,7 @@ use caliptra_common::{crypto::Ecc384KeyPair, keyids::KEY_ID_TMP};
 use caliptra_drivers::{
     hmac384_kdf, okref, Array4x12, Array4x5, Array4x8, CaliptraResult, Ecc384PrivKeyIn,
     Ecc384PrivKeyOut, Ecc384PubKey, Ecc384Result, Ecc384Signature, KeyId, KeyReadArgs, KeyUsage,
-    KeyWriteArgs, Sha256Alg,
+    KeyWriteArgs, Mldsa87, Mldsa87PubKey, Mldsa87SignRnd, Mldsa87Signature, Sha256Alg, Trng,
 };
 
 pub trait Ecdsa384SignatureAdapter {
@@ -239,4 +239,53 @@ impl Crypto {
         digest.0.zeroize();
         result
     }
+
+    /// Generate ML-DSA-87 Key Pair
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - FMC Environment
+    /// * `seed` - Key slot to retrieve the key pair seed from
+    ///
+    /// # Returns
+    ///
+    /// * `Mldsa87PubKey` - Public key
+    #[inline(always)]
+    pub fn mldsa87_key_gen(env: &mut FmcEnv, seed: KeyId) -> CaliptraResult<Mldsa87PubKey> {
+        env.mldsa87
+            .key_pair(&KeyReadArgs::new(seed).into(), &mut env.trng)
+    }
+
+    /// Sign the data using the ML-DSA-87 key pair derived from `seed`.
+    /// Verify the signature using the ML-DSA-87 Public Key.
+    ///
+    /// The data is signed with pure ML-DSA-87, without pre-hashing.
+    ///
+    /// # Arguments
+    ///
+    /// * `mldsa87` - ML-DSA-87 driver
+    /// * `trng` - TRNG driver
+    /// * `seed` - Key slot to retrieve the key pair seed from
+    /// * `pub_key` - Public key of the key pair
+    /// * `data` - Data to sign
+    ///
+    /// # Returns
+    ///
+    /// * `Mldsa87Signature` - Signature
+    #[inline(always)]
+    pub fn mldsa87_sign_and_verify(
+        mldsa87: &mut Mldsa87,
+        trng: &mut Trng,
+        seed: KeyId,
+        pub_key: &Mldsa87PubKey,
+        data: &[u8],
+    ) -> CaliptraResult<Mldsa87Signature> {
+        mldsa87.sign_var(
+            &KeyReadArgs::new(seed).into(),
+            pub_key,
+            data,
+            &Mldsa87SignRnd::default(),
+            trng,
+        )
+    }
 }
@@ -24,13 +24,19 @@ use crate::FmcBootStatus;
 use crate::HandOff;
 use caliptra_common::cprintln;
 use caliptra_common::crypto::Ecc384KeyPair;
-use caliptra_common::keyids::{KEY_ID_RT_CDI, KEY_ID_RT_PRIV_KEY, KEY_ID_TMP};
+use caliptra_common::keyids::{
+    KEY_ID_RT_CDI, KEY_ID_RT_MLDSA_KEYPAIR_SEED, KEY_ID_RT_PRIV_KEY, KEY_ID_TMP,
+};
 use caliptra_common::HexBytes;
 use caliptra_drivers::{
     okref, report_boot_status, CaliptraError, CaliptraResult, Ecc384Result, KeyId, PersistentData,
     ResetReason,
 };
-use caliptra_x509::{NotAfter, NotBefore, RtAliasCertTbs, RtAliasCertTbsParams};
+use caliptra_x509::{
+    NotAfter, NotBefore, RtAliasCertTbs, RtAliasCertTbsMlDsa87, RtAliasCertTbsMlDsa87Params,
+    RtAliasCertTbsParams,
+};
+use zerocopy::IntoBytes;
 
 const SHA384_HASH_SIZE: usize = 48;
 
@@ -90,11 +96,39 @@ impl RtAliasLayer {
 
         // Generate Rt Alias Certificate
         Self::generate_cert_sig(env, input, &output, &nb.value, &nf.value)?;
+
+        // Generate the ML-DSA-87 Rt Alias Certificate if ROM generated the
+        // ML-DSA-87 certificate chain.
+        if let Some(auth_seed) = HandOff::fmc_mldsa_keypair_seed(env) {
+            if Self::kv_slot_collides(auth_seed) {
+                return Err(CaliptraError::FMC_MLDSA_KV_COLLISION);
+            }
+
+            Crypto::hmac384_kdf(
+                env,
+                KEY_ID_RT_CDI,
+                b"rt_alias_mldsa_keygen",
+                None,
+                KEY_ID_RT_MLDSA_KEYPAIR_SEED,
+            )?;
+            Self::generate_mldsa87_cert_sig(
+                env,
+                auth_seed,
+                KEY_ID_RT_MLDSA_KEYPAIR_SEED,
+                &nb.value,
+                &nf.value,
+            )?;
+            HandOff::set_rt_mldsa_keypair_seed(env, KEY_ID_RT_MLDSA_KEYPAIR_SEED);
+        }
+
         Ok(output)
     }
 
     fn kv_slot_collides(slot: KeyId) -> bool {
-        slot == KEY_ID_RT_CDI || slot == KEY_ID_RT_PRIV_KEY || slot == KEY_ID_TMP
+        slot == KEY_ID_RT_CDI
+            || slot == KEY_ID_RT_PRIV_KEY
+            || slot == KEY_ID_RT_MLDSA_KEYPAIR_SEED
+            || slot == KEY_ID_TMP
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
@@ -368,6 +402,89 @@ impl RtAliasLayer {
         Ok(())
     }
 
+    /// Generate Rt Alias ML-DSA-87 Certificate Signature
+    ///
+    /// # Arguments
+    ///
+    /// * `env`        - FMC Environment
+    /// * `auth_seed`  - Key slot holding the FMC Alias ML-DSA-87 key pair seed
+    /// * `seed`       - Key slot holding the Rt Alias ML-DSA-87 key pair seed
+    /// * `not_before` - Certificate validity start
+    /// * `not_after`  - Certificate validity end
+    #[inline(never)]
+    fn generate_mldsa87_cert_sig(
+        env: &mut FmcEnv,
+        auth_seed: KeyId,
+        seed: KeyId,
+        not_before: &[u8; RtAliasCertTbsMlDsa87Params::NOT_BEFORE_LEN],
+        not_after: &[u8; RtAliasCertTbsMlDsa87Params::NOT_AFTER_LEN],
+    ) -> CaliptraResult<()> {
+        let auth_pub_key = Crypto::mldsa87_key_gen(env, auth_seed);
+        let auth_pub_key = okref(&auth_pub_key)?;
+        let issuer_sn = X509::mldsa87_subj_sn(env, auth_pub_key)?;
+        let authority_key_id = X509::mldsa87_subj_key_id(env, auth_pub_key)?;
+
+        {
+            let pub_key = Crypto::mldsa87_key_gen(env, seed);
+            let pub_key = okref(&pub_key)?;
+
+            let rt_tci: [u8; 48] = HandOff::rt_tci(env).into();
+            let rt_svn = HandOff::rt_svn(env) as u8;
+
+            // Certificate `To Be Signed` Parameters
+            let params = RtAliasCertTbsMlDsa87Params {
+                ueid: &X509::ueid(env)?,
+                subject_sn: &X509::mldsa87_subj_sn(env, pub_key)?,
+                subject_key_id: &X509::mldsa87_subj_key_id(env, pub_key)?,
+                issuer_sn: &issuer_sn,
+                authority_key_id: &authority_key_id,
+                serial_number: &X509::mldsa87_cert_sn(env, pub_key)?,
+                public_key: pub_key.0.as_bytes().try_into().unwrap(),
+                not_before,
+                not_after,
+                tcb_info_rt_svn: &rt_svn.to_be_bytes(),
+                tcb_info_rt_tci: &rt_tci,
+            };
+
+            // Generate the `To Be Signed` portion of the certificate and
+            // copy it to DCCM.
+            let tbs = RtAliasCertTbsMlDsa87::new(&params);
+            let cert = &mut env.persistent_data.pqc_mut().rtalias_mldsa_cert;
+            let Some(dest) = cert.tbs.get_mut(..tbs.tbs().len()) else {
+                return Err(CaliptraError::FMC_RT_ALIAS_MLDSA_TBS_SIZE_EXCEEDED);
+            };
+            dest.copy_from_slice(tbs.tbs());
+            cert.tbs_size = tbs.tbs().len() as u32;
+        }
+
+        // Sign the `To Be Signed` portion with the FMC Alias ML-DSA-87 key
+        cprintln!(
+            "[art] Signing ML-DSA-87 Cert with AUTHORITY.KEYID = {}",
+            auth_seed as u8
+        );
+        let tbs = env
+            .persistent_data
+            .pqc()
+            .rtalias_mldsa_cert
+            .tbs()
+            .ok_or(CaliptraError::FMC_RT_ALIAS_MLDSA_TBS_SIZE_EXCEEDED)?;
+        let sig = Crypto::mldsa87_sign_and_verify(
+            &mut env.mldsa87,
+            &mut env.trng,
+            auth_seed,
+            auth_pub_key,
+            tbs,
+        );
+        let sig = okref(&sig)?;
+
+        // Block further usage of the FMC Alias ML-DSA-87 seed until the next boot.
+        env.key_vault.set_key_use_lock(auth_seed);
+
+        env.persistent_data.pqc_mut().rtalias_mldsa_cert.sig = *sig;
+
+        Ok(())
+    }
+
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     fn copy_tbs(tbs: &[u8], persistent_data: &mut PersistentData) -> CaliptraResult<()> {
         let Some(dest) = persistent_data.rtalias_tbs.get_mut(..tbs.len()) else {
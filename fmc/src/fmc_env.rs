@@ -16,13 +16,13 @@ Abstract:
 --*/
 
 use caliptra_drivers::{
-    CaliptraResult, DataVault, Ecc384, Hmac384, KeyVault, Mailbox, PcrBank, PersistentDataAccessor,
-    Sha1, Sha256, Sha2_512_384Acc, Sha384, SocIfc, Trng,
+    CaliptraResult, DataVault, Ecc384, Hmac384, KeyVault, Mailbox, Mldsa87, PcrBank,
+    PersistentDataAccessor, Sha1, Sha256, Sha2_512_384Acc, Sha384, SocIfc, Trng,
 };
 use caliptra_registers::{
     csrng::CsrngReg, dv::DvReg, ecc::EccReg, entropy_src::EntropySrcReg, hmac::HmacReg, kv::KvReg,
-    mbox::MboxCsr, pv::PvReg, sha256::Sha256Reg, sha512::Sha512Reg, sha512_acc::Sha512AccCsr,
-    soc_ifc::SocIfcReg, soc_ifc_trng::SocIfcTrngReg,
+    mbox::MboxCsr, mldsa::MldsaReg, pv::PvReg, sha256::Sha256Reg, sha512::Sha512Reg,
+    sha512_acc::Sha512AccCsr, soc_ifc::SocIfcReg, soc_ifc_trng::SocIfcTrngReg,
 };
 
 /// Hardware Context
@@ -45,6 +45,9 @@ pub struct FmcEnv {
     /// Ecc384 Engine
     pub ecc384: Ecc384,
 
+    /// ML-DSA-87 Engine
+    pub mldsa87: Mldsa87,
+
     /// Key Vault
     pub key_vault: KeyVault,
 
@@ -90,6 +93,7 @@ impl FmcEnv {
             sha2_512_384_acc: Sha2_512_384Acc::new(Sha512AccCsr::new()),
             hmac384: Hmac384::new(HmacReg::new()),
             ecc384: Ecc384::new(EccReg::new()),
+            mldsa87: Mldsa87::new(MldsaReg::new()),
             key_vault: KeyVault::new(KvReg::new()),
             data_vault: DataVault::new(DvReg::new()),
             soc_ifc: SocIfc::new(SocIfcReg::new()),
@@ -126,6 +126,31 @@ impl HandOff {
         }
     }
 
+    /// Retrieve FMC Alias ML-DSA-87 key pair seed
+    ///
+    /// # Returns
+    /// * `None` if ROM did not generate the ML-DSA-87 certificate chain
+    ///
+    pub fn fmc_mldsa_keypair_seed(env: &FmcEnv) -> Option<KeyId> {
+        let hdl = Self::fht(env).fmc_mldsa_keypair_seed_kv_hdl;
+        if !hdl.is_valid() {
+            return None;
+        }
+
+        let ds: DataStore = hdl.try_into().unwrap_or_else(|e: CaliptraError| {
+            cprintln!("[fht] Invalid FMC ML-DSA-87 seed KV handle");
+            handle_fatal_error(e.into())
+        });
+
+        match ds {
+            KeyVaultSlot(key_id) => Some(key_id),
+            _ => {
+                cprintln!("[fht] Invalid KeySlot DV Entry");
+                handle_fatal_error(CaliptraError::FMC_HANDOFF_INVALID_PARAM.into())
+            }
+        }
+    }
+
     /// Transfer control to the runtime firmware.
     pub fn to_rt(env: &FmcEnv) -> ! {
         // Function is defined in start.S
@@ -241,6 +266,12 @@ impl HandOff {
         Self::fht_mut(env).rtalias_tbs_size = rtalias_tbs_size as u16;
     }
 
+    /// Store the RT Alias ML-DSA-87 key pair seed handle
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn set_rt_mldsa_keypair_seed(env: &mut FmcEnv, kv_slot: KeyId) {
+        Self::fht_mut(env).rt_mldsa_keypair_seed_kv_hdl = Self::key_id_to_handle(kv_slot);
+    }
+
     /// Retrieve the entry point of the runtime firmware.
     fn rt_entry_point(env: &FmcEnv) -> u32 {
         let ds: DataStore = Self::fht(env)
@@ -95,11 +95,11 @@ fn test_fht_info() {
         CiRomVersion::Rom1_0 => assert_eq!(fht.fmcalias_tbs_size, 786),
         _ => assert_eq!(fht.fmcalias_tbs_size, 753),
     };
-    assert_eq!(fht.ldevid_tbs_addr, 0x50003C00);
-    assert_eq!(fht.fmcalias_tbs_addr, 0x50004000);
-    assert_eq!(fht.pcr_log_addr, 0x50004800);
-    assert_eq!(fht.meas_log_addr, 0x50004C00);
-    assert_eq!(fht.fuse_log_addr, 0x50005000);
+    assert_eq!(fht.ldevid_tbs_addr, 0x5000B400);
+    assert_eq!(fht.fmcalias_tbs_addr, 0x5000B800);
+    assert_eq!(fht.pcr_log_addr, 0x5000C000);
+    assert_eq!(fht.meas_log_addr, 0x5000C400);
+    assert_eq!(fht.fuse_log_addr, 0x5000C800);
 }
 
 #[test]
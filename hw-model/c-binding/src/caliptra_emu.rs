@@ -0,0 +1,169 @@
+// Licensed under the Apache-2.0 license
+
+//! Client for the control interface of a `caliptra-emu` process started with
+//! `--control-port` or `--control-socket`.
+
+use std::ffi::*;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::TcpStream;
+use std::os::unix::net::UnixStream;
+
+#[repr(C)]
+#[derive(Debug, Copy, Clone)]
+pub struct caliptra_emu {
+    _unused: [u8; 0],
+}
+
+pub const CALIPTRA_EMU_STATUS_OK: c_int = 0;
+pub const CALIPTRA_EMU_STATUS_ERROR: c_int = 1;
+
+struct EmuConnection {
+    reader: BufReader<Box<dyn Read>>,
+    writer: Box<dyn Write>,
+}
+
+impl EmuConnection {
+    /// Connect to `addr`, either `host:port` or `unix:<path>`
+    fn connect(addr: &str) -> io::Result<Self> {
+        let (reader, writer): (Box<dyn Read>, Box<dyn Write>) = match addr.strip_prefix("unix:") {
+            Some(path) => {
+                let stream = UnixStream::connect(path)?;
+                (Box::new(stream.try_clone()?), Box::new(stream))
+            }
+            None => {
+                let stream = TcpStream::connect(addr)?;
+                stream.set_nodelay(true)?;
+                (Box::new(stream.try_clone()?), Box::new(stream))
+            }
+        };
+        Ok(Self {
+            reader: BufReader::new(reader),
+            writer,
+        })
+    }
+
+    /// Send `request` and return the result following `OK`
+    fn request(&mut self, request: &str) -> Result<String, String> {
+        writeln!(self.writer, "{request}").map_err(|e| e.to_string())?;
+        let mut line = String::new();
+        match self.reader.read_line(&mut line) {
+            Ok(0) => return Err("connection closed".into()),
+            Ok(_) => (),
+            Err(e) => return Err(e.to_string()),
+        }
+        let line = line.trim_end();
+        match line.split_once(' ').unwrap_or((line, "")) {
+            ("OK", result) => Ok(result.to_string()),
+            ("ERR", e) => Err(e.to_string()),
+            _ => Err(format!("unexpected response {line:?}")),
+        }
+    }
+
+    /// Send `request`, printing any error, and return the C status
+    fn status(&mut self, request: &str) -> c_int {
+        match self.request(request) {
+            Ok(_) => CALIPTRA_EMU_STATUS_OK,
+            Err(e) => {
+                eprintln!("caliptra-emu: {request}: {e}");
+                CALIPTRA_EMU_STATUS_ERROR
+            }
+        }
+    }
+}
+
+/// # Safety
+#[no_mangle]
+pub unsafe extern "C" fn caliptra_emu_connect(
+    addr: *const c_char,
+    emu: *mut *mut caliptra_emu,
+) -> c_int {
+    // Parameter check
+    assert!(!addr.is_null() && !emu.is_null());
+    let Ok(addr) = CStr::from_ptr(addr).to_str() else {
+        return CALIPTRA_EMU_STATUS_ERROR;
+    };
+    match EmuConnection::connect(addr) {
+        Ok(conn) => {
+            *emu = Box::into_raw(Box::new(conn)) as *mut caliptra_emu;
+            CALIPTRA_EMU_STATUS_OK
+        }
+        Err(e) => {
+            eprintln!("caliptra-emu: unable to connect to {addr}: {e}");
+            CALIPTRA_EMU_STATUS_ERROR
+        }
+    }
+}
+
+/// # Safety
+#[no_mangle]
+pub unsafe extern "C" fn caliptra_emu_disconnect(emu: *mut caliptra_emu) {
+    // Parameter check
+    assert!(!emu.is_null());
+    drop(Box::from_raw(emu as *mut EmuConnection));
+}
+
+/// # Safety
+#[no_mangle]
+pub unsafe extern "C" fn caliptra_emu_apb_read_u32(
+    emu: *mut caliptra_emu,
+    addr: c_uint,
+    data: *mut c_uint,
+) -> c_int {
+    // Parameter check
+    assert!(!emu.is_null() && !data.is_null());
+    let request = format!("apb_read {addr:#x}");
+    let conn = &mut *(emu as *mut EmuConnection);
+    let result = conn.request(&request).and_then(|val| {
+        let hex = val.strip_prefix("0x").unwrap_or(&val);
+        u32::from_str_radix(hex, 16).map_err(|e| e.to_string())
+    });
+    match result {
+        Ok(val) => {
+            *data = val;
+            CALIPTRA_EMU_STATUS_OK
+        }
+        Err(e) => {
+            eprintln!("caliptra-emu: {request}: {e}");
+            CALIPTRA_EMU_STATUS_ERROR
+        }
+    }
+}
+
+/// # Safety
+#[no_mangle]
+pub unsafe extern "C" fn caliptra_emu_apb_write_u32(
+    emu: *mut caliptra_emu,
+    addr: c_uint,
+    data: c_uint,
+) -> c_int {
+    // Parameter check
+    assert!(!emu.is_null());
+    (*(emu as *mut EmuConnection)).status(&format!("apb_write {addr:#x} {data:#x}"))
+}
+
+/// # Safety
+#[no_mangle]
+pub unsafe extern "C" fn caliptra_emu_set_apb_pauser(
+    emu: *mut caliptra_emu,
+    pauser: c_uint,
+) -> c_int {
+    // Parameter check
+    assert!(!emu.is_null());
+    (*(emu as *mut EmuConnection)).status(&format!("set_apb_pauser {pauser:#x}"))
+}
+
+/// # Safety
+#[no_mangle]
+pub unsafe extern "C" fn caliptra_emu_cold_reset(emu: *mut caliptra_emu) -> c_int {
+    // Parameter check
+    assert!(!emu.is_null());
+    (*(emu as *mut EmuConnection)).status("cold_reset")
+}
+
+/// # Safety
+#[no_mangle]
+pub unsafe extern "C" fn caliptra_emu_warm_reset(emu: *mut caliptra_emu) -> c_int {
+    // Parameter check
+    assert!(!emu.is_null());
+    (*(emu as *mut EmuConnection)).status("warm_reset")
+}
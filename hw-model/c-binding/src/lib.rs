@@ -1,3 +1,4 @@
 // Licensed under the Apache-2.0 license
 
+pub mod caliptra_emu;
 pub mod caliptra_model;
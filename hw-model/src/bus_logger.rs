@@ -8,7 +8,7 @@ use std::{
     rc::Rc,
 };
 
-use caliptra_emu_bus::{Bus, BusError};
+use caliptra_emu_bus::{Bus, BusError, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter};
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
 
 #[derive(Clone)]
@@ -131,3 +131,12 @@ impl<TBus: Bus> Bus for BusLogger<TBus> {
         self.bus.update_reset();
     }
 }
+impl<TBus: Bus + Snapshot> Snapshot for BusLogger<TBus> {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.bus.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.bus.restore(r)
+    }
+}
@@ -34,6 +34,9 @@ pub mod mmio;
 mod model_emulated;
 
 mod bus_logger;
+mod lockstep;
+#[cfg(feature = "verilator")]
+mod model_lockstep;
 #[cfg(feature = "verilator")]
 mod model_verilated;
 
@@ -46,12 +49,20 @@ mod rv32_builder;
 pub use api::mailbox::mbox_write_fifo;
 pub use api_types::{DeviceLifecycle, Fuses, SecurityState, U4};
 pub use caliptra_emu_bus::BusMmio;
-pub use caliptra_emu_cpu::{CodeRange, ImageInfo, StackInfo, StackRange};
+pub use caliptra_emu_cpu::{
+    BusAccess, CodeRange, CycleProfile, Fault, FaultEffect, FaultInjector, FaultTrigger, ImageInfo,
+    InjectedFault, RetiredInstr, StackInfo, StackRange, TimingModel, WaitStates,
+};
+pub use caliptra_emu_periph::PeriphLatencies;
 use output::ExitStatus;
 pub use output::Output;
 
 pub use model_emulated::ModelEmulated;
 
+pub use lockstep::{Divergence, LockstepComparator, LockstepStream, Mismatch};
+
+#[cfg(feature = "verilator")]
+pub use model_lockstep::ModelLockstep;
 #[cfg(feature = "verilator")]
 pub use model_verilated::ModelVerilated;
 use ureg::MmioMut;
@@ -183,6 +194,14 @@ pub struct InitParams<'a> {
     // overflows.
     pub stack_info: Option<StackInfo>,
 
+    // Instruction costs and memory wait states used by the emulator to
+    // advance its clock. When None, every instruction takes one cycle.
+    pub timing_model: Option<TimingModel>,
+
+    // Time taken by the emulator's cryptographic peripheral operations. When
+    // None, the emulator defaults are used.
+    pub periph_latencies: Option<PeriphLatencies>,
+
     pub soc_user: MailboxRequester,
 }
 impl<'a> Default for InitParams<'a> {
@@ -219,6 +238,8 @@ impl<'a> Default for InitParams<'a> {
             random_sram_puf: true,
             trace_path: None,
             stack_info: None,
+            timing_model: None,
+            periph_latencies: None,
             soc_user: MailboxRequester::SocUser(1u32),
         }
     }
@@ -319,6 +340,7 @@ pub enum ModelError {
     FuseDoneNotSet,
     FusesAlreadyInitialized,
     StashMeasurementFailed,
+    TransferInvalidChunk,
 }
 
 impl From<CaliptraApiError> for ModelError {
@@ -366,6 +388,9 @@ impl From<CaliptraApiError> for ModelError {
                 ModelError::StashMeasurementFailed
             }
             caliptra_api::CaliptraApiError::UnableToSetPauser => ModelError::UnableToSetPauser,
+            caliptra_api::CaliptraApiError::TransferInvalidChunk => {
+                ModelError::TransferInvalidChunk
+            }
         }
     }
 }
@@ -451,6 +476,9 @@ impl Display for ModelError {
             ModelError::UnableToSetPauser => {
                 write!(f, "Valid PAUSER locked")
             }
+            ModelError::TransferInvalidChunk => {
+                write!(f, "Transfer returned an empty or oversized chunk")
+            }
         }
     }
 }
@@ -1368,6 +1396,47 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_emulator_snapshot_restore() {
+        use crate::ModelEmulated;
+        use caliptra_emu_bus::SnapshotError;
+
+        let message: [u8; 4] = [0x90, 0x5e, 0x1f, 0xad];
+
+        let rom =
+            caliptra_builder::build_firmware_rom(&firmware::hw_model_tests::MAILBOX_RESPONDER)
+                .unwrap();
+        let init_params = || InitParams {
+            rom: &rom,
+            ..Default::default()
+        };
+
+        let mut model = ModelEmulated::new(init_params(), BootParams::default()).unwrap();
+        let snapshot = model.save_snapshot();
+
+        // The restored model never runs the boot flow itself
+        let mut restored = ModelEmulated::new_unbooted(init_params()).unwrap();
+        restored.restore_snapshot(&snapshot).unwrap();
+        assert_eq!(
+            restored.mailbox_execute(0x1000_0000, &message),
+            Ok(Some(
+                [[0x00, 0x00, 0x00, 0x10].as_slice(), &message].concat()
+            )),
+        );
+
+        // The original model is unaffected by the restored one
+        assert_eq!(
+            model.mailbox_execute(0x1000_1000, &[]),
+            Ok(Some(vec![0x01, 0x23, 0x45, 0x67, 0x89, 0xab, 0xcd]))
+        );
+
+        let mut truncated = ModelEmulated::new_unbooted(init_params()).unwrap();
+        assert_eq!(
+            truncated.restore_snapshot(&snapshot[..snapshot.len() - 1]),
+            Err(SnapshotError::UnexpectedEnd)
+        );
+    }
+
     #[test]
     /// Test SocManager maiLbox API.
     fn test_negative_soc_mgr_mbox_users() {
@@ -0,0 +1,497 @@
+// Licensed under the Apache-2.0 license
+
+//! Comparison of the execution of the same firmware on two models.
+//!
+//! The comparator matches three independent event streams: retired program
+//! counters, general purpose register writes (per register) and peripheral
+//! bus accesses. The models are not cycle accurate with respect to each
+//! other, so firmware that polls a peripheral may spin a different number of
+//! times on each model. The comparator tolerates this:
+//!
+//! * A program counter that one model retires while the other has recently
+//!   retired it is treated as an extra loop iteration and skipped.
+//! * Consecutive identical register values and bus accesses are collapsed,
+//!   and values or bus reads that only one model produced are skipped when
+//!   the models agree again within a few values.
+//!
+//! Anything else is reported as a [`Divergence`].
+
+use std::collections::{HashMap, VecDeque};
+use std::fmt;
+
+use caliptra_emu_cpu::{BusAccess, RetiredInstr};
+use caliptra_emu_types::{RvAddr, RvData};
+
+/// Addresses of the peripherals reached over the microcontroller's AHB bus.
+/// ROM, ICCM, DCCM and PIC accesses do not leave the core on the RTL.
+const AHB_RANGE: std::ops::Range<RvAddr> = 0x1000_0000..0x4000_0000;
+
+/// Program counters remembered per model to detect extra loop iterations
+const PC_HISTORY_LEN: usize = 64;
+
+/// Values searched ahead to resynchronize a register or bus stream
+const LOOKAHEAD: usize = 16;
+
+/// Instructions one model may retire past the other when looping
+const MAX_PC_RESYNC: u64 = 100_000;
+
+/// Unmatched events one model may produce before the models are considered
+/// to have diverged
+const MAX_PENDING: usize = 4096;
+
+/// Event stream compared between the models
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum LockstepStream {
+    Pc,
+    XRegWrite,
+    BusAccess,
+}
+
+/// Difference found between the models
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum Mismatch {
+    /// The models retired different instructions
+    Pc { emulator: RvAddr, rtl: RvAddr },
+
+    /// The models wrote different values to register `reg`
+    XRegWrite {
+        reg: u32,
+        emulator: RvData,
+        rtl: RvData,
+    },
+
+    /// The models made different peripheral bus accesses
+    BusAccess { emulator: BusAccess, rtl: BusAccess },
+
+    /// One model produced events the other never matched
+    Unmatched {
+        stream: LockstepStream,
+        emulator_pending: usize,
+        rtl_pending: usize,
+    },
+}
+
+/// First difference found between the models
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Divergence {
+    pub mismatch: Mismatch,
+
+    /// Instructions retired by both models before the divergence
+    pub instr_count: u64,
+
+    /// Program counter of the last instruction retired by both models
+    pub last_pc: Option<RvAddr>,
+}
+
+impl fmt::Display for Divergence {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "diverged after {} instructions", self.instr_count)?;
+        if let Some(pc) = self.last_pc {
+            write!(f, " (last pc 0x{pc:08x})")?;
+        }
+        write!(f, ": ")?;
+        match &self.mismatch {
+            Mismatch::Pc { emulator, rtl } => {
+                write!(f, "emulator pc 0x{emulator:08x}, rtl pc 0x{rtl:08x}")
+            }
+            Mismatch::XRegWrite { reg, emulator, rtl } => write!(
+                f,
+                "x{reg} written with 0x{emulator:08x} by emulator, 0x{rtl:08x} by rtl"
+            ),
+            Mismatch::BusAccess { emulator, rtl } => {
+                write!(f, "emulator {emulator:x?}, rtl {rtl:x?}")
+            }
+            Mismatch::Unmatched {
+                stream,
+                emulator_pending,
+                rtl_pending,
+            } => write!(
+                f,
+                "{stream:?} events unmatched: {emulator_pending} from emulator, \
+                 {rtl_pending} from rtl"
+            ),
+        }
+    }
+}
+
+/// Values produced by each model that have not been matched yet
+struct ValueQueues<T> {
+    emulator: VecDeque<T>,
+    rtl: VecDeque<T>,
+    last_emulator: Option<T>,
+    last_rtl: Option<T>,
+}
+
+impl<T> Default for ValueQueues<T> {
+    fn default() -> Self {
+        Self {
+            emulator: VecDeque::new(),
+            rtl: VecDeque::new(),
+            last_emulator: None,
+            last_rtl: None,
+        }
+    }
+}
+
+impl<T: Copy + Eq> ValueQueues<T> {
+    fn push_emulator(&mut self, val: T) {
+        if self.last_emulator.replace(val) != Some(val) {
+            self.emulator.push_back(val);
+        }
+    }
+
+    fn push_rtl(&mut self, val: T) {
+        if self.last_rtl.replace(val) != Some(val) {
+            self.rtl.push_back(val);
+        }
+    }
+
+    /// Match queued values, skipping `skippable` values only one model
+    /// produced. Returns the first pair of values that cannot be matched.
+    fn compare(&mut self, skippable: impl Fn(&T) -> bool) -> Result<(), (T, T)> {
+        while let (Some(&emulator), Some(&rtl)) = (self.emulator.front(), self.rtl.front()) {
+            if emulator == rtl {
+                self.emulator.pop_front();
+                self.rtl.pop_front();
+            } else if let Some(n) = skip_to(&self.rtl, &emulator, &skippable) {
+                self.rtl.drain(..n);
+            } else if let Some(n) = skip_to(&self.emulator, &rtl, &skippable) {
+                self.emulator.drain(..n);
+            } else if self.emulator.len() < LOOKAHEAD || self.rtl.len() < LOOKAHEAD {
+                // The models may still agree once more values arrive
+                break;
+            } else {
+                return Err((emulator, rtl));
+            }
+        }
+        Ok(())
+    }
+
+    fn is_overflowing(&self) -> bool {
+        self.emulator.len() > MAX_PENDING || self.rtl.len() > MAX_PENDING
+    }
+}
+
+/// Number of leading values of `queue` to skip to reach `val`, if there are
+/// few enough and all are `skippable`
+fn skip_to<T: Eq>(queue: &VecDeque<T>, val: &T, skippable: &impl Fn(&T) -> bool) -> Option<usize> {
+    let n = queue.iter().take(LOOKAHEAD).position(|v| v == val)?;
+    queue.iter().take(n).all(skippable).then_some(n)
+}
+
+/// Program counters retired by one model
+#[derive(Default)]
+struct PcQueue {
+    pending: VecDeque<RvAddr>,
+
+    /// Most recently matched or skipped program counters
+    history: VecDeque<RvAddr>,
+}
+
+impl PcQueue {
+    fn pop(&mut self) -> Option<RvAddr> {
+        let pc = self.pending.pop_front()?;
+        if self.history.len() == PC_HISTORY_LEN {
+            self.history.pop_front();
+        }
+        self.history.push_back(pc);
+        Some(pc)
+    }
+}
+
+/// Compares the instructions retired by the emulator with the events seen on
+/// the RTL, in the order each model produced them
+#[derive(Default)]
+pub struct LockstepComparator {
+    emulator_pcs: PcQueue,
+    rtl_pcs: PcQueue,
+    pc_resyncs: u64,
+    xregs: HashMap<u32, ValueQueues<RvData>>,
+    bus: ValueQueues<BusAccess>,
+    instr_count: u64,
+    last_pc: Option<RvAddr>,
+    divergence: Option<Divergence>,
+}
+
+impl LockstepComparator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add an instruction retired by the emulator
+    pub fn push_emulator_instr(&mut self, instr: &RetiredInstr) {
+        self.emulator_pcs.pending.push_back(instr.pc);
+        for &(reg, val) in &instr.xreg_writes {
+            self.xregs.entry(reg).or_default().push_emulator(val);
+        }
+        for access in &instr.bus_accesses {
+            if AHB_RANGE.contains(&access.addr) {
+                self.bus.push_emulator(*access);
+            }
+        }
+    }
+
+    /// Add an instruction retired by the RTL
+    pub fn push_rtl_retire(&mut self, pc: RvAddr) {
+        self.rtl_pcs.pending.push_back(pc);
+    }
+
+    /// Add a register write made by the RTL
+    pub fn push_rtl_xreg_write(&mut self, reg: u32, val: RvData) {
+        self.xregs.entry(reg).or_default().push_rtl(val);
+    }
+
+    /// Add a bus access made by the RTL
+    pub fn push_rtl_bus_access(&mut self, access: BusAccess) {
+        if AHB_RANGE.contains(&access.addr) {
+            self.bus.push_rtl(access);
+        }
+    }
+
+    /// Returns true if the RTL has retired instructions the emulator has not
+    /// yet matched
+    pub fn emulator_behind(&self) -> bool {
+        self.divergence.is_none() && !self.rtl_pcs.pending.is_empty()
+    }
+
+    /// Match the events added so far, returning the first divergence found
+    /// by this or any earlier call
+    pub fn compare(&mut self) -> Option<&Divergence> {
+        if self.divergence.is_none() {
+            if let Err(mismatch) = self.compare_streams() {
+                self.divergence = Some(Divergence {
+                    mismatch,
+                    instr_count: self.instr_count,
+                    last_pc: self.last_pc,
+                });
+            }
+        }
+        self.divergence.as_ref()
+    }
+
+    /// The first divergence found
+    pub fn divergence(&self) -> Option<&Divergence> {
+        self.divergence.as_ref()
+    }
+
+    /// Instructions retired by both models
+    pub fn instr_count(&self) -> u64 {
+        self.instr_count
+    }
+
+    fn compare_streams(&mut self) -> Result<(), Mismatch> {
+        self.compare_pcs()?;
+        for (&reg, queues) in self.xregs.iter_mut() {
+            queues
+                .compare(|_| true)
+                .map_err(|(emulator, rtl)| Mismatch::XRegWrite { reg, emulator, rtl })?;
+            if queues.is_overflowing() {
+                return Err(unmatched(LockstepStream::XRegWrite, queues));
+            }
+        }
+        self.bus
+            .compare(|access| !access.write)
+            .map_err(|(emulator, rtl)| Mismatch::BusAccess { emulator, rtl })?;
+        if self.bus.is_overflowing() {
+            return Err(unmatched(LockstepStream::BusAccess, &self.bus));
+        }
+        Ok(())
+    }
+
+    fn compare_pcs(&mut self) -> Result<(), Mismatch> {
+        while let (Some(&emulator), Some(&rtl)) = (
+            self.emulator_pcs.pending.front(),
+            self.rtl_pcs.pending.front(),
+        ) {
+            if emulator == rtl {
+                self.emulator_pcs.pop();
+                self.rtl_pcs.pop();
+                self.instr_count += 1;
+                self.last_pc = Some(emulator);
+                self.pc_resyncs = 0;
+                continue;
+            }
+            if self.pc_resyncs >= MAX_PC_RESYNC {
+                return Err(Mismatch::Pc { emulator, rtl });
+            }
+            // One model is still in a loop the other has left
+            if self.emulator_pcs.history.contains(&rtl) {
+                self.rtl_pcs.pop();
+            } else if self.rtl_pcs.history.contains(&emulator) {
+                self.emulator_pcs.pop();
+            } else {
+                return Err(Mismatch::Pc { emulator, rtl });
+            }
+            self.pc_resyncs += 1;
+        }
+        let emulator_pending = self.emulator_pcs.pending.len();
+        let rtl_pending = self.rtl_pcs.pending.len();
+        if emulator_pending > MAX_PENDING || rtl_pending > MAX_PENDING {
+            return Err(Mismatch::Unmatched {
+                stream: LockstepStream::Pc,
+                emulator_pending,
+                rtl_pending,
+            });
+        }
+        Ok(())
+    }
+}
+
+fn unmatched<T>(stream: LockstepStream, queues: &ValueQueues<T>) -> Mismatch {
+    Mismatch::Unmatched {
+        stream,
+        emulator_pending: queues.emulator.len(),
+        rtl_pending: queues.rtl.len(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use caliptra_emu_types::RvSize;
+
+    fn instr(pc: RvAddr, xreg_writes: &[(u32, RvData)], bus: &[BusAccess]) -> RetiredInstr {
+        RetiredInstr {
+            pc,
+            instr: 0,
+            xreg_writes: xreg_writes.to_vec(),
+            bus_accesses: bus.to_vec(),
+        }
+    }
+
+    fn read(addr: RvAddr, val: RvData) -> BusAccess {
+        BusAccess {
+            write: false,
+            size: RvSize::Word,
+            addr,
+            val,
+        }
+    }
+
+    #[test]
+    fn test_matching_execution() {
+        let mut cmp = LockstepComparator::new();
+        cmp.push_emulator_instr(&instr(0x0, &[(1, 5)], &[]));
+        cmp.push_emulator_instr(&instr(0x4, &[], &[read(0x1000_0000, 7)]));
+        assert!(cmp.compare().is_none());
+        assert!(!cmp.emulator_behind());
+
+        // The RTL writes registers and accesses the bus before retiring
+        cmp.push_rtl_xreg_write(1, 5);
+        cmp.push_rtl_bus_access(read(0x1000_0000, 7));
+        cmp.push_rtl_retire(0x0);
+        cmp.push_rtl_retire(0x4);
+        cmp.push_rtl_retire(0x8);
+        assert!(cmp.compare().is_none());
+        assert!(cmp.emulator_behind());
+        assert_eq!(cmp.instr_count(), 2);
+    }
+
+    #[test]
+    fn test_polling_loop() {
+        // 0x10: lw x5, STATUS; 0x14: beqz x5, 0x10; 0x18: ...
+        let status = 0x1001_0000;
+        let mut cmp = LockstepComparator::new();
+        for (pc, writes, bus) in [
+            (0x10, vec![(5, 0)], vec![read(status, 0)]),
+            (0x14, vec![], vec![]),
+            (0x10, vec![(5, 1)], vec![read(status, 1)]),
+            (0x14, vec![], vec![]),
+            (0x18, vec![(6, 2)], vec![]),
+        ] {
+            cmp.push_emulator_instr(&instr(pc, &writes, &bus));
+        }
+
+        // The RTL peripheral takes longer to become ready
+        for _ in 0..3 {
+            cmp.push_rtl_xreg_write(5, 0);
+            cmp.push_rtl_bus_access(read(status, 0));
+            cmp.push_rtl_retire(0x10);
+            cmp.push_rtl_retire(0x14);
+        }
+        cmp.push_rtl_xreg_write(5, 1);
+        cmp.push_rtl_bus_access(read(status, 1));
+        cmp.push_rtl_retire(0x10);
+        cmp.push_rtl_retire(0x14);
+        cmp.push_rtl_xreg_write(6, 2);
+        cmp.push_rtl_retire(0x18);
+        assert_eq!(cmp.compare(), None);
+        assert!(!cmp.emulator_behind());
+    }
+
+    #[test]
+    fn test_divergence() {
+        let mut cmp = LockstepComparator::new();
+        cmp.push_emulator_instr(&instr(0x0, &[], &[]));
+        cmp.push_emulator_instr(&instr(0x4, &[], &[]));
+        cmp.push_rtl_retire(0x0);
+        cmp.push_rtl_retire(0x8);
+        let divergence = cmp.compare().unwrap().clone();
+        assert_eq!(
+            divergence,
+            Divergence {
+                mismatch: Mismatch::Pc {
+                    emulator: 0x4,
+                    rtl: 0x8
+                },
+                instr_count: 1,
+                last_pc: Some(0x0),
+            }
+        );
+        assert_eq!(
+            divergence.to_string(),
+            "diverged after 1 instructions (last pc 0x00000000): \
+             emulator pc 0x00000004, rtl pc 0x00000008"
+        );
+
+        // Only the first divergence is reported
+        cmp.push_emulator_instr(&instr(0xc, &[], &[]));
+        cmp.push_rtl_retire(0x10);
+        assert_eq!(cmp.compare(), Some(&divergence));
+    }
+
+    #[test]
+    fn test_register_divergence() {
+        let mut cmp = LockstepComparator::new();
+        for i in 0..LOOKAHEAD as u32 {
+            cmp.push_emulator_instr(&instr(i * 4, &[(3, i + 1)], &[]));
+            cmp.push_rtl_xreg_write(3, if i == 0 { 0xbad } else { i + 1 });
+            cmp.push_rtl_retire(i * 4);
+            if i + 1 < LOOKAHEAD as u32 {
+                assert_eq!(cmp.compare(), None);
+            }
+        }
+        assert_eq!(
+            cmp.compare().map(|d| &d.mismatch),
+            Some(&Mismatch::XRegWrite {
+                reg: 3,
+                emulator: 1,
+                rtl: 0xbad
+            })
+        );
+    }
+
+    #[test]
+    fn test_bus_writes_not_skipped() {
+        let write = |val| BusAccess {
+            write: true,
+            size: RvSize::Word,
+            addr: 0x1002_0008,
+            val,
+        };
+        let mut cmp = LockstepComparator::new();
+        for i in 0..LOOKAHEAD as u32 {
+            cmp.push_emulator_instr(&instr(0, &[], &[write(i + 1)]));
+            cmp.push_rtl_bus_access(write(i));
+        }
+        // DCCM accesses stay inside the core and are not compared
+        cmp.push_emulator_instr(&instr(0, &[], &[read(0x5000_0000, 0)]));
+        assert_eq!(
+            cmp.compare().map(|d| &d.mismatch),
+            Some(&Mismatch::BusAccess {
+                emulator: write(1),
+                rtl: write(0),
+            })
+        );
+    }
+}
@@ -3,15 +3,18 @@
 use std::cell::Cell;
 use std::collections::hash_map::DefaultHasher;
 use std::error::Error;
+use std::fs::File;
 use std::hash::Hasher;
-use std::io::Write;
-use std::path::PathBuf;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
 use std::rc::Rc;
 
-use caliptra_emu_bus::Clock;
+use caliptra_emu_bus::{Clock, EventTrace, TraceEventKind};
+use caliptra_emu_bus::{Snapshot, SnapshotError, SnapshotReader, SnapshotWriter};
 #[cfg(feature = "coverage")]
 use caliptra_emu_cpu::CoverageBitmaps;
-use caliptra_emu_cpu::{Cpu, InstrTracer};
+use caliptra_emu_cpu::CycleProfile;
+use caliptra_emu_cpu::{Cpu, FaultInjector, InstrTracer, RetiredInstr};
 use caliptra_emu_periph::ActionCb;
 use caliptra_emu_periph::MailboxExternal;
 use caliptra_emu_periph::ReadyForFwCb;
@@ -20,7 +23,8 @@ use caliptra_emu_periph::{
 };
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
 use caliptra_hw_model_types::ErrorInjectionMode;
-use caliptra_image_types::IMAGE_MANIFEST_BYTE_SIZE;
+use caliptra_image_types::{ImageManifest, IMAGE_MANIFEST_BYTE_SIZE};
+use zerocopy::FromBytes;
 
 use crate::bus_logger::BusLogger;
 use crate::bus_logger::LogFile;
@@ -41,6 +45,15 @@ impl<'a> Bus for EmulatedApbBus<'a> {
     fn read(&mut self, size: RvSize, addr: RvAddr) -> Result<RvData, caliptra_emu_bus::BusError> {
         let result = self.model.soc_to_caliptra_bus.read(size, addr);
         self.model.cpu.bus.log_read("SoC", size, addr, result);
+        if let (Some(event_trace), Ok(val)) = (&self.model.event_trace, result) {
+            event_trace.record(TraceEventKind::PeriphAccess {
+                bus: "SoC",
+                write: false,
+                size,
+                addr,
+                val,
+            });
+        }
         result
     }
     fn write(
@@ -51,6 +64,15 @@ impl<'a> Bus for EmulatedApbBus<'a> {
     ) -> Result<(), caliptra_emu_bus::BusError> {
         let result = self.model.soc_to_caliptra_bus.write(size, addr, val);
         self.model.cpu.bus.log_write("SoC", size, addr, val, result);
+        if let (Some(event_trace), Ok(())) = (&self.model.event_trace, result) {
+            event_trace.record(TraceEventKind::PeriphAccess {
+                bus: "SoC",
+                write: true,
+                size,
+                addr,
+                val,
+            });
+        }
         result
     }
 }
@@ -65,6 +87,9 @@ pub struct ModelEmulated {
     cpu_enabled: Rc<Cell<bool>>,
     trace_path: Option<PathBuf>,
 
+    // Peripheral events, when tracing to a .json or .vcd file
+    event_trace: Option<EventTrace>,
+
     // Keep this even when not including the coverage feature to keep the
     // interface consistent
     _rom_image_tag: u64,
@@ -72,9 +97,17 @@ pub struct ModelEmulated {
     trng_mode: TrngMode,
 }
 
-#[cfg(feature = "coverage")]
 impl Drop for ModelEmulated {
     fn drop(&mut self) {
+        self.finish_event_trace();
+        #[cfg(feature = "coverage")]
+        self.dump_coverage();
+    }
+}
+
+#[cfg(feature = "coverage")]
+impl ModelEmulated {
+    fn dump_coverage(&self) {
         let cov_path =
             std::env::var(caliptra_coverage::CPTRA_COVERAGE_PATH).unwrap_or_else(|_| "".into());
         if cov_path.is_empty() {
@@ -103,6 +136,135 @@ impl ModelEmulated {
     pub fn code_coverage_bitmap(&self) -> CoverageBitmaps {
         self.cpu.code_coverage.code_coverage_bitmap()
     }
+
+    /// Start profiling the cycles spent by each instruction, discarding any
+    /// previous profile
+    pub fn start_cycle_profile(&mut self) {
+        self.cpu.start_cycle_profile();
+    }
+
+    /// Start profiling the cycles spent in each call stack, discarding any
+    /// previous profile. Write the result with
+    /// `caliptra_coverage::write_folded_stacks`.
+    pub fn start_call_stack_profile(&mut self) {
+        self.cpu.start_call_stack_profile();
+    }
+
+    /// Stop profiling cycles, returning the profile if one was in progress
+    pub fn stop_cycle_profile(&mut self) -> Option<CycleProfile> {
+        self.cpu.stop_cycle_profile()
+    }
+
+    /// Number of CPU clock cycles since the model was created
+    pub fn cycle_count(&self) -> u64 {
+        self.cpu.clock.now()
+    }
+}
+
+impl ModelEmulated {
+    /// Stack high-water mark of each image in `InitParams::stack_info`, in
+    /// bytes. Returns `None` if no stack info was given.
+    pub fn max_stack_usage(&self) -> Option<Vec<u32>> {
+        self.cpu.max_stack_usage()
+    }
+
+    /// Save the state of the emulated machine, for example after boot so
+    /// later tests can skip it. The snapshot can only be restored into a model
+    /// built from the same emulator with the same `InitParams`; it does not
+    /// include the output log or tracing state.
+    pub fn save_snapshot(&self) -> Vec<u8> {
+        let mut w = SnapshotWriter::new();
+        self.ready_for_fw.get().save(&mut w);
+        self.cpu_enabled.get().save(&mut w);
+        self.iccm_image_tag.is_some().save(&mut w);
+        self.iccm_image_tag.unwrap_or_default().save(&mut w);
+        self.cpu.save(&mut w);
+        w.into_bytes()
+    }
+
+    /// Restore state saved with [`ModelEmulated::save_snapshot`]. If an error
+    /// is returned, the model is left in an undefined state and should be
+    /// discarded.
+    pub fn restore_snapshot(&mut self, snapshot: &[u8]) -> Result<(), SnapshotError> {
+        let mut r = SnapshotReader::new(snapshot);
+        self.ready_for_fw.set(r.read()?);
+        self.cpu_enabled.set(r.read()?);
+        let has_iccm_image_tag: bool = r.read()?;
+        let iccm_image_tag: u64 = r.read()?;
+        self.iccm_image_tag = has_iccm_image_tag.then_some(iccm_image_tag);
+        self.cpu.restore(&mut r)?;
+        r.finish()
+    }
+
+    /// Inject the faults of `injector` into the instructions the CPU executes
+    /// from now on, replacing any previous injector. Use
+    /// [`HwModel::new_unbooted`] to inject faults into the ROM.
+    pub fn install_fault_injector(&mut self, injector: FaultInjector) {
+        self.cpu.install_fault_injector(injector);
+    }
+
+    /// Stop injecting faults, returning the injector if one was installed
+    pub fn remove_fault_injector(&mut self) -> Option<FaultInjector> {
+        self.cpu.remove_fault_injector()
+    }
+
+    /// The installed fault injector, if any
+    pub fn fault_injector(&self) -> Option<&FaultInjector> {
+        self.cpu.fault_injector()
+    }
+
+    /// Enable or disable recording the register writes and bus accesses of
+    /// each instruction the CPU retires
+    pub fn trace_retired_instrs(&mut self, enable: bool) {
+        self.cpu.trace_retired_instrs(enable);
+    }
+
+    /// Takes the instruction retired by the last call to `step()`, if it
+    /// retired one while tracing was enabled
+    pub fn take_retired_instr(&mut self) -> Option<RetiredInstr> {
+        self.cpu.take_retired_instr()
+    }
+
+    fn start_event_trace(&mut self) {
+        let event_trace = EventTrace::new(&self.cpu.clock);
+        self.cpu.set_event_trace(Some(event_trace.clone()));
+        self.cpu.bus.bus.set_event_trace(Some(event_trace.clone()));
+        self.event_trace = Some(event_trace);
+    }
+
+    /// Stop recording events and write them to the trace path
+    fn finish_event_trace(&mut self) {
+        let Some(event_trace) = self.event_trace.take() else {
+            return;
+        };
+        self.cpu.set_event_trace(None);
+        self.cpu.bus.bus.set_event_trace(None);
+        let Some(trace_path) = &self.trace_path else {
+            return;
+        };
+        if let Err(e) = write_event_trace(&event_trace, trace_path) {
+            eprintln!("Unable to write event trace {trace_path:?}: {e}");
+        }
+    }
+}
+
+/// Whether tracing to `path` records peripheral events rather than the text
+/// log, based on its extension
+fn is_event_trace_path(path: &Path) -> bool {
+    matches!(
+        path.extension().and_then(|ext| ext.to_str()),
+        Some("json" | "vcd")
+    )
+}
+
+fn write_event_trace(event_trace: &EventTrace, path: &Path) -> std::io::Result<()> {
+    let mut w = BufWriter::new(File::create(path)?);
+    if path.extension().and_then(|ext| ext.to_str()) == Some("vcd") {
+        event_trace.write_vcd(&mut w)?;
+    } else {
+        event_trace.write_perfetto_json(&mut w)?;
+    }
+    w.flush()
 }
 
 fn hash_slice(slice: &[u8]) -> u64 {
@@ -167,6 +329,7 @@ impl HwModel for ModelEmulated {
 
             itrng_nibbles: Some(params.itrng_nibbles),
             etrng_responses: params.etrng_responses,
+            latencies: params.periph_latencies.unwrap_or_default(),
             ..CaliptraRootBusArgs::default()
         };
         let mut root_bus = CaliptraRootBus::new(&clock, bus_args);
@@ -195,6 +358,9 @@ impl HwModel for ModelEmulated {
             if let Some(stack_info) = params.stack_info {
                 cpu.with_stack_info(stack_info);
             }
+            if let Some(timing_model) = params.timing_model {
+                cpu.set_timing_model(timing_model);
+            }
             cpu
         };
 
@@ -210,6 +376,7 @@ impl HwModel for ModelEmulated {
             ready_for_fw,
             cpu_enabled,
             trace_path: trace_path_or_env(params.trace_path),
+            event_trace: None,
             _rom_image_tag: image_tag,
             iccm_image_tag: None,
             trng_mode,
@@ -249,19 +416,29 @@ impl HwModel for ModelEmulated {
     }
 
     fn cover_fw_mage(&mut self, fw_image: &[u8]) {
-        let iccm_image = &fw_image[IMAGE_MANIFEST_BYTE_SIZE..];
+        let mldsa_preamble_size = ImageManifest::read_from_prefix(fw_image)
+            .map(|(manifest, _)| manifest.mldsa_preamble_size())
+            .unwrap_or_default();
+        let iccm_image = &fw_image[IMAGE_MANIFEST_BYTE_SIZE + mldsa_preamble_size..];
         self.iccm_image_tag = Some(hash_slice(iccm_image));
     }
     fn tracing_hint(&mut self, enable: bool) {
-        if enable == self.trace_fn.is_some() {
+        if enable == (self.trace_fn.is_some() || self.event_trace.is_some()) {
             // No change
             return;
         }
         self.trace_fn = None;
         self.cpu.bus.log = None;
+        self.finish_event_trace();
         let Some(trace_path) = &self.trace_path else {
             return;
         };
+        if is_event_trace_path(trace_path) {
+            if enable {
+                self.start_event_trace();
+            }
+            return;
+        }
 
         let mut log = match LogFile::open(trace_path) {
             Ok(file) => file,
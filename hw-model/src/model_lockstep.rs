@@ -0,0 +1,296 @@
+// Licensed under the Apache-2.0 license
+
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io::Write;
+use std::sync::{Arc, Mutex};
+
+use caliptra_api::soc_mgr::SocManager;
+use caliptra_emu_bus::{Bus, BusError, BusMmio};
+use caliptra_emu_cpu::BusAccess;
+use caliptra_emu_types::{RvAddr, RvData, RvSize};
+use caliptra_hw_model_types::ErrorInjectionMode;
+use caliptra_verilated::{AhbTxnType, UcEvent};
+
+use crate::lockstep::{Divergence, LockstepComparator};
+use crate::{HwModel, InitParams, ModelEmulated, ModelVerilated, Output, TrngMode};
+
+// How many emulator steps may pass without retiring an instruction, for
+// example while taking a trap, before giving up until the next cycle
+const MAX_EMULATOR_IDLE_STEPS: u32 = 64;
+
+pub struct LockstepApbBus<'a> {
+    model: &'a mut ModelLockstep,
+}
+
+impl<'a> Bus for LockstepApbBus<'a> {
+    fn read(&mut self, size: RvSize, addr: RvAddr) -> Result<RvData, BusError> {
+        // Reads can have side effects (such as popping the mailbox FIFO), so
+        // the emulator must see them too.
+        let _ = self.model.emu.apb_bus().read(size, addr);
+        self.model.rtl.apb_bus().read(size, addr)
+    }
+
+    fn write(&mut self, size: RvSize, addr: RvAddr, val: RvData) -> Result<(), BusError> {
+        let _ = self.model.emu.apb_bus().write(size, addr, val);
+        self.model.rtl.apb_bus().write(size, addr, val)
+    }
+}
+
+/// Runs the same firmware on the emulator and the verilated model, comparing
+/// the instructions retired, the register writes and the peripheral bus
+/// accesses of both.
+///
+/// The SoC sees the verilated model: APB reads return its values, and APB
+/// accesses are mirrored to the emulator. The emulator is stepped as the
+/// verilated model retires instructions.
+pub struct ModelLockstep {
+    emu: ModelEmulated,
+    rtl: ModelVerilated,
+    comparator: LockstepComparator,
+    divergence_logged: bool,
+}
+
+impl ModelLockstep {
+    pub fn emulator(&mut self) -> &mut ModelEmulated {
+        &mut self.emu
+    }
+
+    pub fn verilated(&mut self) -> &mut ModelVerilated {
+        &mut self.rtl
+    }
+
+    /// The first divergence between the models, if any
+    pub fn divergence(&self) -> Option<&Divergence> {
+        self.comparator.divergence()
+    }
+
+    fn compare(&mut self) {
+        for event in self.rtl.v.take_uc_events() {
+            match event {
+                UcEvent::Retire {
+                    pc,
+                    exception: false,
+                    interrupt: false,
+                    ..
+                } => self.comparator.push_rtl_retire(pc),
+                // Trapped instructions do not retire
+                UcEvent::Retire { .. } => {}
+                UcEvent::XRegWrite { reg, val } => {
+                    self.comparator.push_rtl_xreg_write(reg.into(), val)
+                }
+                UcEvent::Ahb { ty, addr, data } => {
+                    for access in ahb_bus_accesses(ty, addr, data) {
+                        self.comparator.push_rtl_bus_access(access);
+                    }
+                }
+            }
+        }
+
+        let mut idle_steps = 0;
+        while self.comparator.emulator_behind() && idle_steps < MAX_EMULATOR_IDLE_STEPS {
+            self.emu.step();
+            match self.emu.take_retired_instr() {
+                Some(instr) => {
+                    self.comparator.push_emulator_instr(&instr);
+                    self.comparator.compare();
+                    idle_steps = 0;
+                }
+                None => idle_steps += 1,
+            }
+        }
+
+        if let Some(divergence) = self.comparator.compare() {
+            if !self.divergence_logged {
+                self.divergence_logged = true;
+                writeln!(self.rtl.output().logger(), "lockstep: {divergence}").ok();
+            }
+        }
+    }
+}
+
+/// Split an AHB transaction into the accesses the emulator would make
+fn ahb_bus_accesses(ty: AhbTxnType, addr: RvAddr, data: u64) -> Vec<BusAccess> {
+    let access = |size, addr, val| BusAccess {
+        write: ty.is_write(),
+        size,
+        addr,
+        val,
+    };
+    let word = data as u32;
+    let lane = |mask: u32| (word >> ((addr & 3) * 8)) & mask;
+    match ty {
+        AhbTxnType::ReadU8 | AhbTxnType::WriteU8 => vec![access(RvSize::Byte, addr, lane(0xff))],
+        AhbTxnType::ReadU16 | AhbTxnType::WriteU16 => {
+            vec![access(RvSize::HalfWord, addr, lane(0xffff))]
+        }
+        AhbTxnType::ReadU32 | AhbTxnType::WriteU32 => vec![access(RvSize::Word, addr, word)],
+        AhbTxnType::ReadU64 | AhbTxnType::WriteU64 => vec![
+            access(RvSize::Word, addr, word),
+            access(RvSize::Word, addr + 4, (data >> 32) as u32),
+        ],
+    }
+}
+
+/// Items of an iterator not yet seen by each of two consumers
+struct Tee<T> {
+    source: Box<dyn Iterator<Item = T> + Send>,
+    buffers: [VecDeque<T>; 2],
+}
+
+struct TeeIter<T> {
+    tee: Arc<Mutex<Tee<T>>>,
+    index: usize,
+}
+
+impl<T: Clone> Iterator for TeeIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let mut tee = self.tee.lock().unwrap();
+        if let Some(item) = tee.buffers[self.index].pop_front() {
+            return Some(item);
+        }
+        let item = tee.source.next()?;
+        tee.buffers[1 - self.index].push_back(item.clone());
+        Some(item)
+    }
+}
+
+type BoxedIter<T> = Box<dyn Iterator<Item = T> + Send>;
+
+/// Give both models the same TRNG values
+fn tee<T: Clone + Send + 'static>(source: BoxedIter<T>) -> (BoxedIter<T>, BoxedIter<T>) {
+    let tee = Arc::new(Mutex::new(Tee {
+        source,
+        buffers: Default::default(),
+    }));
+    (
+        Box::new(TeeIter {
+            tee: tee.clone(),
+            index: 0,
+        }),
+        Box::new(TeeIter { tee, index: 1 }),
+    )
+}
+
+impl SocManager for ModelLockstep {
+    type TMmio<'a> = BusMmio<LockstepApbBus<'a>>;
+
+    fn mmio_mut(&mut self) -> Self::TMmio<'_> {
+        BusMmio::new(self.apb_bus())
+    }
+
+    fn delay(&mut self) {
+        self.step();
+    }
+
+    const SOC_IFC_ADDR: u32 = 0x3003_0000;
+    const SOC_IFC_TRNG_ADDR: u32 = 0x3003_0000;
+    const SOC_SHA512_ACC_ADDR: u32 = 0x3002_1000;
+    const SOC_MBOX_ADDR: u32 = 0x3002_0000;
+
+    const MAX_WAIT_CYCLES: u32 = 20_000_000;
+}
+
+impl HwModel for ModelLockstep {
+    type TBus<'a> = LockstepApbBus<'a>;
+
+    fn new_unbooted(params: InitParams) -> Result<Self, Box<dyn Error>>
+    where
+        Self: Sized,
+    {
+        let (emu_itrng, rtl_itrng) = tee(params.itrng_nibbles);
+        let (emu_etrng, rtl_etrng) = tee(params.etrng_responses);
+
+        let mut emu = ModelEmulated::new_unbooted(InitParams {
+            rom: params.rom,
+            dccm: params.dccm,
+            iccm: params.iccm,
+            log_writer: Box::new(std::io::sink()),
+            security_state: params.security_state,
+            cptra_obf_key: params.cptra_obf_key,
+            itrng_nibbles: emu_itrng,
+            etrng_responses: emu_etrng,
+            trng_mode: params.trng_mode,
+            random_sram_puf: false,
+            trace_path: None,
+            stack_info: params.stack_info,
+            timing_model: params.timing_model,
+            periph_latencies: params.periph_latencies,
+            soc_user: params.soc_user,
+        })?;
+        // Leave CPTRA_TRACE_PATH to the verilated model
+        emu.tracing_hint(false);
+        emu.trace_retired_instrs(true);
+
+        let mut rtl = ModelVerilated::new_unbooted(InitParams {
+            itrng_nibbles: rtl_itrng,
+            etrng_responses: rtl_etrng,
+            stack_info: None,
+            timing_model: None,
+            ..params
+        })?;
+        rtl.v.trace_uc_events(true);
+
+        Ok(Self {
+            emu,
+            rtl,
+            comparator: LockstepComparator::new(),
+            divergence_logged: false,
+        })
+    }
+
+    fn type_name(&self) -> &'static str {
+        "ModelLockstep"
+    }
+
+    fn trng_mode(&self) -> TrngMode {
+        self.rtl.trng_mode()
+    }
+
+    fn apb_bus(&mut self) -> Self::TBus<'_> {
+        LockstepApbBus { model: self }
+    }
+
+    fn step(&mut self) {
+        self.rtl.step();
+        self.compare();
+    }
+
+    fn output(&mut self) -> &mut Output {
+        self.rtl.output()
+    }
+
+    fn warm_reset(&mut self) {
+        self.rtl.warm_reset();
+        self.emu.warm_reset();
+        self.rtl.v.take_uc_events();
+        self.emu.take_retired_instr();
+        if self.comparator.divergence().is_none() {
+            self.comparator = LockstepComparator::new();
+        }
+    }
+
+    fn ready_for_fw(&self) -> bool {
+        self.rtl.ready_for_fw()
+    }
+
+    fn cover_fw_mage(&mut self, image: &[u8]) {
+        self.emu.cover_fw_mage(image);
+    }
+
+    fn tracing_hint(&mut self, enable: bool) {
+        self.rtl.tracing_hint(enable);
+    }
+
+    fn ecc_error_injection(&mut self, mode: ErrorInjectionMode) {
+        self.emu.ecc_error_injection(mode);
+        self.rtl.ecc_error_injection(mode);
+    }
+
+    fn set_apb_pauser(&mut self, pauser: u32) {
+        self.emu.set_apb_pauser(pauser);
+        self.rtl.set_apb_pauser(pauser);
+    }
+}
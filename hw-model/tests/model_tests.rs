@@ -294,6 +294,28 @@ fn test_pcr_extend() {
     model.step_until_exit_success().unwrap();
 }
 
+#[test]
+#[cfg(feature = "verilator")]
+fn test_pcr_extend_lockstep() {
+    let elf =
+        caliptra_builder::build_firmware_elf(&firmware::hw_model_tests::TEST_PCR_EXTEND).unwrap();
+    let rom = caliptra_builder::elf2rom(&elf).unwrap();
+    let mut model = caliptra_hw_model::ModelLockstep::new(
+        InitParams {
+            rom: &rom,
+            random_sram_puf: false,
+            ..Default::default()
+        },
+        BootParams::default(),
+    )
+    .unwrap();
+
+    model.step_until_exit_success().unwrap();
+    if let Some(divergence) = model.divergence() {
+        panic!("{divergence}");
+    }
+}
+
 #[test]
 #[cfg(feature = "fpga_realtime")]
 fn test_mbox_pauser_sigbus() {
@@ -864,6 +864,7 @@ pub mod entropy_src;
 pub mod hmac;
 pub mod kv;
 pub mod mbox;
+pub mod mldsa;
 pub mod pv;
 pub mod sha256;
 pub mod sha512;
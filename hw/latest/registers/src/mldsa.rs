@@ -0,0 +1,765 @@
+// Licensed under the Apache-2.0 license.
+//
+// generated by caliptra_registers_generator with caliptra-rtl repo at 5f85fb4bc95b753a2f7d042db7dc2644ca1e8c49
+//
+#![allow(clippy::erasing_op)]
+#![allow(clippy::identity_op)]
+/// A zero-sized type that represents ownership of this
+/// peripheral, used to get access to a Register lock. Most
+/// programs create one of these in unsafe code near the top of
+/// main(), and pass it to the driver responsible for managing
+/// all access to the hardware.
+pub struct MldsaReg {
+    _priv: (),
+}
+impl MldsaReg {
+    pub const PTR: *mut u32 = 0x10030000 as *mut u32;
+    /// # Safety
+    ///
+    /// Caller must ensure that all concurrent use of this
+    /// peripheral in the firmware is done so in a compatible
+    /// way. The simplest way to enforce this is to only call
+    /// this function once.
+    #[inline(always)]
+    pub unsafe fn new() -> Self {
+        Self { _priv: () }
+    }
+    /// Returns a register block that can be used to read
+    /// registers from this peripheral, but cannot write.
+    #[inline(always)]
+    pub fn regs(&self) -> RegisterBlock<ureg::RealMmio> {
+        RegisterBlock {
+            ptr: Self::PTR,
+            mmio: core::default::Default::default(),
+        }
+    }
+    /// Return a register block that can be used to read and
+    /// write this peripheral's registers.
+    #[inline(always)]
+    pub fn regs_mut(&mut self) -> RegisterBlock<ureg::RealMmioMut> {
+        RegisterBlock {
+            ptr: Self::PTR,
+            mmio: core::default::Default::default(),
+        }
+    }
+}
+#[derive(Clone, Copy)]
+pub struct RegisterBlock<TMmio: ureg::Mmio + core::borrow::Borrow<TMmio>> {
+    ptr: *mut u32,
+    mmio: TMmio,
+}
+impl<TMmio: ureg::Mmio + core::default::Default> RegisterBlock<TMmio> {
+    /// # Safety
+    ///
+    /// The caller is responsible for ensuring that ptr is valid for
+    /// volatile reads and writes at any of the offsets in this register
+    /// block.
+    #[inline(always)]
+    pub unsafe fn new(ptr: *mut u32) -> Self {
+        Self {
+            ptr,
+            mmio: core::default::Default::default(),
+        }
+    }
+}
+impl<TMmio: ureg::Mmio> RegisterBlock<TMmio> {
+    /// # Safety
+    ///
+    /// The caller is responsible for ensuring that ptr is valid for
+    /// volatile reads and writes at any of the offsets in this register
+    /// block.
+    #[inline(always)]
+    pub unsafe fn new_with_mmio(ptr: *mut u32, mmio: TMmio) -> Self {
+        Self { ptr, mmio }
+    }
+    /// Two 32-bit read-only registers representing of the name
+    /// of ML-DSA-87 component.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn name(&self) -> ureg::Array<2, ureg::RegRef<crate::mldsa::meta::Name, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// Two 32-bit read-only registers representing of the version
+    /// of ML-DSA-87 component.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn version(&self) -> ureg::Array<2, ureg::RegRef<crate::mldsa::meta::Version, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(8 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// One 3-bit register including the following flags:
+    /// bits #1:0: Type of ML-DSA-87 operation:
+    ///    00: No operation
+    ///    01: Generate a key pair from SEED
+    ///    10: Sign MSG with PRIVKEY_IN
+    ///    11: Verify SIGNATURE over MSG with PUBKEY
+    /// bit #2: Zeroize all internal registers after the current operation
+    /// bit #6: Stream the message to sign or verify through MSG[0]
+    ///
+    /// Read value: [`mldsa::regs::CtrlReadVal`]; Write value: [`mldsa::regs::CtrlWriteVal`]
+    #[inline(always)]
+    pub fn ctrl(&self) -> ureg::RegRef<crate::mldsa::meta::Ctrl, &TMmio> {
+        unsafe {
+            ureg::RegRef::new_with_mmio(
+                self.ptr.wrapping_add(0x10 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// One 2-bit register including the following flags:
+    /// bit #0: READY : Indicates if the core is ready to take
+    ///                a control command and process the inputs.
+    /// bit #1: VALID : Indicates if the process is done and the
+    ///                results are valid.
+    /// bit #2: MSG_STREAM_READY : Indicates if the core is ready
+    ///                to take the streamed message.
+    ///
+    /// Read value: [`mldsa::regs::StatusReadVal`]; Write value: [`mldsa::regs::StatusWriteVal`]
+    #[inline(always)]
+    pub fn status(&self) -> ureg::RegRef<crate::mldsa::meta::Status, &TMmio> {
+        unsafe {
+            ureg::RegRef::new_with_mmio(
+                self.ptr.wrapping_add(0x18 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 16 32-bit registers storing the 512-bit IV used for masking
+    /// and other countermeasures.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn iv(&self) -> ureg::Array<16, ureg::RegRef<crate::mldsa::meta::Iv, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x80 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 8 32-bit registers storing the 256-bit seed for deterministic
+    /// key pair generation.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn seed(&self) -> ureg::Array<8, ureg::RegRef<crate::mldsa::meta::Seed, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x100 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 8 32-bit registers storing the 256-bit randomness used by
+    /// hedged signing. All zeros selects deterministic signing.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn sign_rnd(&self) -> ureg::Array<8, ureg::RegRef<crate::mldsa::meta::SignRnd, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x180 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 16 32-bit registers storing the 512-bit message to sign or
+    /// verify.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn msg(&self) -> ureg::Array<16, ureg::RegRef<crate::mldsa::meta::Msg, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x200 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 16 32-bit registers storing the result of the last
+    /// verification. Every word is 1 when the signature is valid.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn verify_res(
+        &self,
+    ) -> ureg::Array<16, ureg::RegRef<crate::mldsa::meta::VerifyRes, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x280 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// Byte enables of the last word of a streamed message. Writing
+    /// MSG[0] after this register ends the message.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn msg_strobe(&self) -> ureg::RegRef<crate::mldsa::meta::MsgStrobe, &TMmio> {
+        unsafe {
+            ureg::RegRef::new_with_mmio(
+                self.ptr.wrapping_add(0x2c0 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 1224 32-bit registers storing the 4896-byte private key
+    /// produced by key generation.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn privkey_out(
+        &self,
+    ) -> ureg::Array<1224, ureg::RegRef<crate::mldsa::meta::PrivkeyOut, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x300 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 1224 32-bit registers storing the 4896-byte private key
+    /// used for signing.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn privkey_in(
+        &self,
+    ) -> ureg::Array<1224, ureg::RegRef<crate::mldsa::meta::PrivkeyIn, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x1620 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 648 32-bit registers storing the 2592-byte public key. Written
+    /// by key generation and read by verification.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn pubkey(&self) -> ureg::Array<648, ureg::RegRef<crate::mldsa::meta::Pubkey, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x2940 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// 1157 32-bit registers storing the 4627-byte signature. The
+    /// final byte of the last word is padding.
+    ///
+    /// Read value: [`u32`]; Write value: [`u32`]
+    #[inline(always)]
+    pub fn signature(
+        &self,
+    ) -> ureg::Array<1157, ureg::RegRef<crate::mldsa::meta::Signature, &TMmio>> {
+        unsafe {
+            ureg::Array::new_with_mmio(
+                self.ptr.wrapping_add(0x3400 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// Controls the Key Vault read access for this engine
+    ///
+    /// Read value: [`regs::KvReadCtrlRegReadVal`]; Write value: [`regs::KvReadCtrlRegWriteVal`]
+    #[inline(always)]
+    pub fn kv_rd_seed_ctrl(&self) -> ureg::RegRef<crate::mldsa::meta::KvRdSeedCtrl, &TMmio> {
+        unsafe {
+            ureg::RegRef::new_with_mmio(
+                self.ptr.wrapping_add(0x5000 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+    /// Reports the Key Vault flow status for this engine
+    ///
+    /// Read value: [`regs::KvStatusRegReadVal`]; Write value: [`regs::KvStatusRegWriteVal`]
+    #[inline(always)]
+    pub fn kv_rd_seed_status(&self) -> ureg::RegRef<crate::mldsa::meta::KvRdSeedStatus, &TMmio> {
+        unsafe {
+            ureg::RegRef::new_with_mmio(
+                self.ptr.wrapping_add(0x5004 / core::mem::size_of::<u32>()),
+                core::borrow::Borrow::borrow(&self.mmio),
+            )
+        }
+    }
+}
+pub mod regs {
+    //! Types that represent the values held by registers.
+    #[derive(Clone, Copy)]
+    pub struct CtrlWriteVal(u32);
+    impl CtrlWriteVal {
+        /// Control command field
+        #[inline(always)]
+        pub fn ctrl(
+            self,
+            f: impl FnOnce(super::enums::selector::MldsaCtrlESelector) -> super::enums::MldsaCtrlE,
+        ) -> Self {
+            Self(
+                (self.0 & !(3 << 0))
+                    | (u32::from(f(super::enums::selector::MldsaCtrlESelector())) << 0),
+            )
+        }
+        /// Zeroize all internal registers
+        #[inline(always)]
+        pub fn zeroize(self, val: bool) -> Self {
+            Self((self.0 & !(1 << 2)) | (u32::from(val) << 2))
+        }
+        /// Stream the message through MSG[0]
+        #[inline(always)]
+        pub fn stream_msg(self, val: bool) -> Self {
+            Self((self.0 & !(1 << 6)) | (u32::from(val) << 6))
+        }
+    }
+    impl From<u32> for CtrlWriteVal {
+        #[inline(always)]
+        fn from(val: u32) -> Self {
+            Self(val)
+        }
+    }
+    impl From<CtrlWriteVal> for u32 {
+        #[inline(always)]
+        fn from(val: CtrlWriteVal) -> u32 {
+            val.0
+        }
+    }
+    #[derive(Clone, Copy)]
+    pub struct StatusReadVal(u32);
+    impl StatusReadVal {
+        /// Status ready bit
+        #[inline(always)]
+        pub fn ready(&self) -> bool {
+            ((self.0 >> 0) & 1) != 0
+        }
+        /// Status valid bit
+        #[inline(always)]
+        pub fn valid(&self) -> bool {
+            ((self.0 >> 1) & 1) != 0
+        }
+        /// Status message stream ready bit
+        #[inline(always)]
+        pub fn msg_stream_ready(&self) -> bool {
+            ((self.0 >> 2) & 1) != 0
+        }
+    }
+    impl From<u32> for StatusReadVal {
+        #[inline(always)]
+        fn from(val: u32) -> Self {
+            Self(val)
+        }
+    }
+    impl From<StatusReadVal> for u32 {
+        #[inline(always)]
+        fn from(val: StatusReadVal) -> u32 {
+            val.0
+        }
+    }
+}
+pub mod enums {
+    //! Enumerations used by some register fields.
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[repr(u32)]
+    pub enum KvErrorE {
+        Success = 0,
+        KvReadFail = 1,
+        KvWriteFail = 2,
+        Reserved3 = 3,
+        Reserved4 = 4,
+        Reserved5 = 5,
+        Reserved6 = 6,
+        Reserved7 = 7,
+        Reserved8 = 8,
+        Reserved9 = 9,
+        Reserved10 = 10,
+        Reserved11 = 11,
+        Reserved12 = 12,
+        Reserved13 = 13,
+        Reserved14 = 14,
+        Reserved15 = 15,
+        Reserved16 = 16,
+        Reserved17 = 17,
+        Reserved18 = 18,
+        Reserved19 = 19,
+        Reserved20 = 20,
+        Reserved21 = 21,
+        Reserved22 = 22,
+        Reserved23 = 23,
+        Reserved24 = 24,
+        Reserved25 = 25,
+        Reserved26 = 26,
+        Reserved27 = 27,
+        Reserved28 = 28,
+        Reserved29 = 29,
+        Reserved30 = 30,
+        Reserved31 = 31,
+        Reserved32 = 32,
+        Reserved33 = 33,
+        Reserved34 = 34,
+        Reserved35 = 35,
+        Reserved36 = 36,
+        Reserved37 = 37,
+        Reserved38 = 38,
+        Reserved39 = 39,
+        Reserved40 = 40,
+        Reserved41 = 41,
+        Reserved42 = 42,
+        Reserved43 = 43,
+        Reserved44 = 44,
+        Reserved45 = 45,
+        Reserved46 = 46,
+        Reserved47 = 47,
+        Reserved48 = 48,
+        Reserved49 = 49,
+        Reserved50 = 50,
+        Reserved51 = 51,
+        Reserved52 = 52,
+        Reserved53 = 53,
+        Reserved54 = 54,
+        Reserved55 = 55,
+        Reserved56 = 56,
+        Reserved57 = 57,
+        Reserved58 = 58,
+        Reserved59 = 59,
+        Reserved60 = 60,
+        Reserved61 = 61,
+        Reserved62 = 62,
+        Reserved63 = 63,
+        Reserved64 = 64,
+        Reserved65 = 65,
+        Reserved66 = 66,
+        Reserved67 = 67,
+        Reserved68 = 68,
+        Reserved69 = 69,
+        Reserved70 = 70,
+        Reserved71 = 71,
+        Reserved72 = 72,
+        Reserved73 = 73,
+        Reserved74 = 74,
+        Reserved75 = 75,
+        Reserved76 = 76,
+        Reserved77 = 77,
+        Reserved78 = 78,
+        Reserved79 = 79,
+        Reserved80 = 80,
+        Reserved81 = 81,
+        Reserved82 = 82,
+        Reserved83 = 83,
+        Reserved84 = 84,
+        Reserved85 = 85,
+        Reserved86 = 86,
+        Reserved87 = 87,
+        Reserved88 = 88,
+        Reserved89 = 89,
+        Reserved90 = 90,
+        Reserved91 = 91,
+        Reserved92 = 92,
+        Reserved93 = 93,
+        Reserved94 = 94,
+        Reserved95 = 95,
+        Reserved96 = 96,
+        Reserved97 = 97,
+        Reserved98 = 98,
+        Reserved99 = 99,
+        Reserved100 = 100,
+        Reserved101 = 101,
+        Reserved102 = 102,
+        Reserved103 = 103,
+        Reserved104 = 104,
+        Reserved105 = 105,
+        Reserved106 = 106,
+        Reserved107 = 107,
+        Reserved108 = 108,
+        Reserved109 = 109,
+        Reserved110 = 110,
+        Reserved111 = 111,
+        Reserved112 = 112,
+        Reserved113 = 113,
+        Reserved114 = 114,
+        Reserved115 = 115,
+        Reserved116 = 116,
+        Reserved117 = 117,
+        Reserved118 = 118,
+        Reserved119 = 119,
+        Reserved120 = 120,
+        Reserved121 = 121,
+        Reserved122 = 122,
+        Reserved123 = 123,
+        Reserved124 = 124,
+        Reserved125 = 125,
+        Reserved126 = 126,
+        Reserved127 = 127,
+        Reserved128 = 128,
+        Reserved129 = 129,
+        Reserved130 = 130,
+        Reserved131 = 131,
+        Reserved132 = 132,
+        Reserved133 = 133,
+        Reserved134 = 134,
+        Reserved135 = 135,
+        Reserved136 = 136,
+        Reserved137 = 137,
+        Reserved138 = 138,
+        Reserved139 = 139,
+        Reserved140 = 140,
+        Reserved141 = 141,
+        Reserved142 = 142,
+        Reserved143 = 143,
+        Reserved144 = 144,
+        Reserved145 = 145,
+        Reserved146 = 146,
+        Reserved147 = 147,
+        Reserved148 = 148,
+        Reserved149 = 149,
+        Reserved150 = 150,
+        Reserved151 = 151,
+        Reserved152 = 152,
+        Reserved153 = 153,
+        Reserved154 = 154,
+        Reserved155 = 155,
+        Reserved156 = 156,
+        Reserved157 = 157,
+        Reserved158 = 158,
+        Reserved159 = 159,
+        Reserved160 = 160,
+        Reserved161 = 161,
+        Reserved162 = 162,
+        Reserved163 = 163,
+        Reserved164 = 164,
+        Reserved165 = 165,
+        Reserved166 = 166,
+        Reserved167 = 167,
+        Reserved168 = 168,
+        Reserved169 = 169,
+        Reserved170 = 170,
+        Reserved171 = 171,
+        Reserved172 = 172,
+        Reserved173 = 173,
+        Reserved174 = 174,
+        Reserved175 = 175,
+        Reserved176 = 176,
+        Reserved177 = 177,
+        Reserved178 = 178,
+        Reserved179 = 179,
+        Reserved180 = 180,
+        Reserved181 = 181,
+        Reserved182 = 182,
+        Reserved183 = 183,
+        Reserved184 = 184,
+        Reserved185 = 185,
+        Reserved186 = 186,
+        Reserved187 = 187,
+        Reserved188 = 188,
+        Reserved189 = 189,
+        Reserved190 = 190,
+        Reserved191 = 191,
+        Reserved192 = 192,
+        Reserved193 = 193,
+        Reserved194 = 194,
+        Reserved195 = 195,
+        Reserved196 = 196,
+        Reserved197 = 197,
+        Reserved198 = 198,
+        Reserved199 = 199,
+        Reserved200 = 200,
+        Reserved201 = 201,
+        Reserved202 = 202,
+        Reserved203 = 203,
+        Reserved204 = 204,
+        Reserved205 = 205,
+        Reserved206 = 206,
+        Reserved207 = 207,
+        Reserved208 = 208,
+        Reserved209 = 209,
+        Reserved210 = 210,
+        Reserved211 = 211,
+        Reserved212 = 212,
+        Reserved213 = 213,
+        Reserved214 = 214,
+        Reserved215 = 215,
+        Reserved216 = 216,
+        Reserved217 = 217,
+        Reserved218 = 218,
+        Reserved219 = 219,
+        Reserved220 = 220,
+        Reserved221 = 221,
+        Reserved222 = 222,
+        Reserved223 = 223,
+        Reserved224 = 224,
+        Reserved225 = 225,
+        Reserved226 = 226,
+        Reserved227 = 227,
+        Reserved228 = 228,
+        Reserved229 = 229,
+        Reserved230 = 230,
+        Reserved231 = 231,
+        Reserved232 = 232,
+        Reserved233 = 233,
+        Reserved234 = 234,
+        Reserved235 = 235,
+        Reserved236 = 236,
+        Reserved237 = 237,
+        Reserved238 = 238,
+        Reserved239 = 239,
+        Reserved240 = 240,
+        Reserved241 = 241,
+        Reserved242 = 242,
+        Reserved243 = 243,
+        Reserved244 = 244,
+        Reserved245 = 245,
+        Reserved246 = 246,
+        Reserved247 = 247,
+        Reserved248 = 248,
+        Reserved249 = 249,
+        Reserved250 = 250,
+        Reserved251 = 251,
+        Reserved252 = 252,
+        Reserved253 = 253,
+        Reserved254 = 254,
+        Reserved255 = 255,
+    }
+    impl KvErrorE {
+        #[inline(always)]
+        pub fn success(&self) -> bool {
+            *self == Self::Success
+        }
+        #[inline(always)]
+        pub fn kv_read_fail(&self) -> bool {
+            *self == Self::KvReadFail
+        }
+        #[inline(always)]
+        pub fn kv_write_fail(&self) -> bool {
+            *self == Self::KvWriteFail
+        }
+    }
+    impl TryFrom<u32> for KvErrorE {
+        type Error = ();
+        #[inline(always)]
+        fn try_from(val: u32) -> Result<KvErrorE, ()> {
+            if val < 0x100 {
+                Ok(unsafe { core::mem::transmute(val) })
+            } else {
+                Err(())
+            }
+        }
+    }
+    impl From<KvErrorE> for u32 {
+        fn from(val: KvErrorE) -> Self {
+            val as u32
+        }
+    }
+    #[derive(Clone, Copy, Eq, PartialEq)]
+    #[repr(u32)]
+    pub enum MldsaCtrlE {
+        None = 0,
+        Keygen = 1,
+        Signing = 2,
+        Verifying = 3,
+    }
+    impl MldsaCtrlE {
+        #[inline(always)]
+        pub fn none(&self) -> bool {
+            *self == Self::None
+        }
+        #[inline(always)]
+        pub fn keygen(&self) -> bool {
+            *self == Self::Keygen
+        }
+        #[inline(always)]
+        pub fn signing(&self) -> bool {
+            *self == Self::Signing
+        }
+        #[inline(always)]
+        pub fn verifying(&self) -> bool {
+            *self == Self::Verifying
+        }
+    }
+    impl TryFrom<u32> for MldsaCtrlE {
+        type Error = ();
+        #[inline(always)]
+        fn try_from(val: u32) -> Result<MldsaCtrlE, ()> {
+            if val < 4 {
+                Ok(unsafe { core::mem::transmute(val) })
+            } else {
+                Err(())
+            }
+        }
+    }
+    impl From<MldsaCtrlE> for u32 {
+        fn from(val: MldsaCtrlE) -> Self {
+            val as u32
+        }
+    }
+    pub mod selector {
+        pub struct KvErrorESelector();
+        impl KvErrorESelector {
+            #[inline(always)]
+            pub fn success(&self) -> super::KvErrorE {
+                super::KvErrorE::Success
+            }
+            #[inline(always)]
+            pub fn kv_read_fail(&self) -> super::KvErrorE {
+                super::KvErrorE::KvReadFail
+            }
+            #[inline(always)]
+            pub fn kv_write_fail(&self) -> super::KvErrorE {
+                super::KvErrorE::KvWriteFail
+            }
+        }
+        pub struct MldsaCtrlESelector();
+        impl MldsaCtrlESelector {
+            #[inline(always)]
+            pub fn none(&self) -> super::MldsaCtrlE {
+                super::MldsaCtrlE::None
+            }
+            #[inline(always)]
+            pub fn keygen(&self) -> super::MldsaCtrlE {
+                super::MldsaCtrlE::Keygen
+            }
+            #[inline(always)]
+            pub fn signing(&self) -> super::MldsaCtrlE {
+                super::MldsaCtrlE::Signing
+            }
+            #[inline(always)]
+            pub fn verifying(&self) -> super::MldsaCtrlE {
+                super::MldsaCtrlE::Verifying
+            }
+        }
+    }
+}
+pub mod meta {
+    //! Additional metadata needed by ureg.
+    pub type Name = ureg::ReadOnlyReg32<u32>;
+    pub type Version = ureg::ReadOnlyReg32<u32>;
+    pub type Ctrl = ureg::WriteOnlyReg32<0, crate::mldsa::regs::CtrlWriteVal>;
+    pub type Status = ureg::ReadOnlyReg32<crate::mldsa::regs::StatusReadVal>;
+    pub type Iv = ureg::WriteOnlyReg32<0, u32>;
+    pub type Seed = ureg::WriteOnlyReg32<0, u32>;
+    pub type SignRnd = ureg::WriteOnlyReg32<0, u32>;
+    pub type Msg = ureg::WriteOnlyReg32<0, u32>;
+    pub type MsgStrobe = ureg::WriteOnlyReg32<0xf, u32>;
+    pub type VerifyRes = ureg::ReadOnlyReg32<u32>;
+    pub type PrivkeyOut = ureg::ReadOnlyReg32<u32>;
+    pub type PrivkeyIn = ureg::WriteOnlyReg32<0, u32>;
+    pub type Pubkey = ureg::ReadWriteReg32<0, u32, u32>;
+    pub type Signature = ureg::ReadWriteReg32<0, u32, u32>;
+    pub type KvRdSeedCtrl = ureg::ReadWriteReg32<
+        0,
+        crate::regs::KvReadCtrlRegReadVal,
+        crate::regs::KvReadCtrlRegWriteVal,
+    >;
+    pub type KvRdSeedStatus = ureg::ReadOnlyReg32<crate::regs::KvStatusRegReadVal>;
+}
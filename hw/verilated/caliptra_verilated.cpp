@@ -108,6 +108,24 @@ void caliptra_verilated_eval(struct caliptra_verilated* model,
   out->uc_hready = v->uc_hready;
   out->uc_hresp = v->uc_hresp;
 
+  out->uc_trace_valid = v->uc_trace_valid;
+  out->uc_trace_pc = v->uc_trace_pc;
+  out->uc_trace_insn = v->uc_trace_insn;
+  out->uc_trace_exception = v->uc_trace_exception;
+  out->uc_trace_interrupt = v->uc_trace_interrupt;
+
+  out->uc_wb_valid = v->uc_wb_valid;
+  out->uc_wb_dest = v->uc_wb_dest;
+  out->uc_wb_data = v->uc_wb_data;
+
+  out->uc_nb_load_wb_valid = v->uc_nb_load_wb_valid;
+  out->uc_nb_load_wb_dest = v->uc_nb_load_wb_dest;
+  out->uc_nb_load_wb_data = v->uc_nb_load_wb_data;
+
+  out->uc_div_wb_valid = v->uc_div_wb_valid;
+  out->uc_div_wb_dest = v->uc_div_wb_dest;
+  out->uc_div_wb_data = v->uc_div_wb_data;
+
   out->cptra_error_fatal = v->cptra_error_fatal;
 }
 
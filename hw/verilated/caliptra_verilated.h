@@ -65,6 +65,24 @@ struct caliptra_verilated_sig_out {
   bool uc_hready;
   bool uc_hresp;
 
+  bool uc_trace_valid;
+  uint32_t uc_trace_pc;
+  uint32_t uc_trace_insn;
+  bool uc_trace_exception;
+  bool uc_trace_interrupt;
+
+  bool uc_wb_valid;
+  uint8_t uc_wb_dest;
+  uint32_t uc_wb_data;
+
+  bool uc_nb_load_wb_valid;
+  uint8_t uc_nb_load_wb_dest;
+  uint32_t uc_nb_load_wb_data;
+
+  bool uc_div_wb_valid;
+  uint8_t uc_div_wb_dest;
+  uint32_t uc_div_wb_data;
+
   bool cptra_error_fatal;
 };
 
@@ -77,6 +77,24 @@ module caliptra_verilated (
     output bit uc_hready,
     output bit uc_hresp,
 
+    output bit uc_trace_valid,
+    output bit [31:0] uc_trace_pc,
+    output bit [31:0] uc_trace_insn,
+    output bit uc_trace_exception,
+    output bit uc_trace_interrupt,
+
+    output bit uc_wb_valid,
+    output bit [4:0] uc_wb_dest,
+    output bit [31:0] uc_wb_data,
+
+    output bit uc_nb_load_wb_valid,
+    output bit [4:0] uc_nb_load_wb_dest,
+    output bit [31:0] uc_nb_load_wb_data,
+
+    output bit uc_div_wb_valid,
+    output bit [4:0] uc_div_wb_dest,
+    output bit [31:0] uc_div_wb_data,
+
     output bit cptra_error_fatal
     );
 
@@ -195,6 +213,26 @@ assign uc_hrdata = caliptra_top_dut.rvtop.lsu_hrdata;
 assign uc_hready = caliptra_top_dut.rvtop.lsu_hready;
 assign uc_hresp = caliptra_top_dut.rvtop.lsu_hresp;
 
+// Instruction trace port
+assign uc_trace_valid = caliptra_top_dut.rvtop.trace_rv_i_valid_ip;
+assign uc_trace_pc = caliptra_top_dut.rvtop.trace_rv_i_address_ip;
+assign uc_trace_insn = caliptra_top_dut.rvtop.trace_rv_i_insn_ip;
+assign uc_trace_exception = caliptra_top_dut.rvtop.trace_rv_i_exception_ip;
+assign uc_trace_interrupt = caliptra_top_dut.rvtop.trace_rv_i_interrupt_ip;
+
+// Register file write ports
+assign uc_wb_valid = caliptra_top_dut.rvtop.veer.dec.dec_i0_wen_r;
+assign uc_wb_dest = caliptra_top_dut.rvtop.veer.dec.dec_i0_waddr_r;
+assign uc_wb_data = caliptra_top_dut.rvtop.veer.dec.dec_i0_wdata_r;
+
+assign uc_nb_load_wb_valid = caliptra_top_dut.rvtop.veer.dec.dec_nonblock_load_wen;
+assign uc_nb_load_wb_dest = caliptra_top_dut.rvtop.veer.dec.dec_nonblock_load_waddr;
+assign uc_nb_load_wb_data = caliptra_top_dut.rvtop.veer.dec.lsu_nonblock_load_data;
+
+assign uc_div_wb_valid = caliptra_top_dut.rvtop.veer.exu_div_wren;
+assign uc_div_wb_dest = caliptra_top_dut.rvtop.veer.dec.div_waddr_wb;
+assign uc_div_wb_data = caliptra_top_dut.rvtop.veer.exu_div_result;
+
 // Decode:
 //  [0] - Single bit, ICCM Error Injection
 //  [1] - Double bit, ICCM Error Injection
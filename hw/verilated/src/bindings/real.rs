@@ -55,6 +55,20 @@ pub struct caliptra_verilated_sig_out {
     pub uc_hrdata: u64,
     pub uc_hready: bool,
     pub uc_hresp: bool,
+    pub uc_trace_valid: bool,
+    pub uc_trace_pc: u32,
+    pub uc_trace_insn: u32,
+    pub uc_trace_exception: bool,
+    pub uc_trace_interrupt: bool,
+    pub uc_wb_valid: bool,
+    pub uc_wb_dest: u8,
+    pub uc_wb_data: u32,
+    pub uc_nb_load_wb_valid: bool,
+    pub uc_nb_load_wb_dest: u8,
+    pub uc_nb_load_wb_data: u32,
+    pub uc_div_wb_valid: bool,
+    pub uc_div_wb_dest: u8,
+    pub uc_div_wb_data: u32,
     pub cptra_error_fatal: bool,
 }
 #[repr(C)]
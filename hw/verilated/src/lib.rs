@@ -44,6 +44,29 @@ impl AhbTxnType {
     }
 }
 
+/// Microcontroller activity seen on the instruction trace port, the register
+/// file write ports and the LSU AHB bus
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+pub enum UcEvent {
+    /// An instruction retired, or trapped if `exception` or `interrupt` is set
+    Retire {
+        pc: u32,
+        insn: u32,
+        exception: bool,
+        interrupt: bool,
+    },
+
+    /// A general purpose register other than x0 was written
+    XRegWrite { reg: u8, val: u32 },
+
+    /// An AHB transaction completed; ROM accesses are not included
+    Ahb {
+        ty: AhbTxnType,
+        addr: u32,
+        data: u64,
+    },
+}
+
 pub type GenericOutputWiresChangedCallbackFn = dyn Fn(&CaliptraVerilated, u64);
 pub type AhbCallbackFn = dyn Fn(&CaliptraVerilated, AhbTxnType, u32, u64);
 
@@ -72,6 +95,7 @@ pub struct CaliptraVerilated {
     ahb_cb: Box<AhbCallbackFn>,
     total_cycles: u64,
     ahb_txn: Option<AhbPendingTxn>,
+    uc_events: Option<Vec<UcEvent>>,
 }
 
 impl CaliptraVerilated {
@@ -98,6 +122,7 @@ impl CaliptraVerilated {
                 ahb_cb,
                 total_cycles: 0,
                 ahb_txn: None,
+                uc_events: None,
             }
         }
     }
@@ -160,6 +185,20 @@ impl CaliptraVerilated {
         }
     }
 
+    /// Start or stop collecting microcontroller events. Collected events are
+    /// returned by `take_uc_events()`.
+    pub fn trace_uc_events(&mut self, enable: bool) {
+        self.uc_events = enable.then(Vec::new);
+    }
+
+    /// Returns the microcontroller events collected since the last call
+    pub fn take_uc_events(&mut self) -> Vec<UcEvent> {
+        self.uc_events
+            .as_mut()
+            .map(std::mem::take)
+            .unwrap_or_default()
+    }
+
     /// Evaluates the model into self.output, then copies all `self.input`
     /// signals into psuedo flip-flops that will be visible to always_ff blocks
     /// in subsequent evaluations. Typically `next_cycle_high` is used instead.
@@ -174,24 +213,47 @@ impl CaliptraVerilated {
         }
         if let Some(ahb_txn) = &self.ahb_txn {
             if self.output.uc_hready {
-                if ahb_txn.ty.is_write() {
-                    (self.ahb_cb)(
-                        self,
-                        ahb_txn.ty,
-                        ahb_txn.addr,
-                        ahb_txn.transform_data(self.output.uc_hwdata),
-                    );
+                let data = if ahb_txn.ty.is_write() {
+                    ahb_txn.transform_data(self.output.uc_hwdata)
                 } else {
-                    (self.ahb_cb)(
-                        self,
-                        ahb_txn.ty,
-                        ahb_txn.addr,
-                        ahb_txn.transform_data(self.output.uc_hrdata),
-                    );
+                    ahb_txn.transform_data(self.output.uc_hrdata)
+                };
+                (self.ahb_cb)(self, ahb_txn.ty, ahb_txn.addr, data);
+                if let Some(events) = &mut self.uc_events {
+                    events.push(UcEvent::Ahb {
+                        ty: ahb_txn.ty,
+                        addr: ahb_txn.addr,
+                        data,
+                    });
                 }
                 self.ahb_txn = None;
             }
         }
+        if let Some(events) = &mut self.uc_events {
+            let out = &self.output;
+            let xreg_writes = [
+                (out.uc_wb_valid, out.uc_wb_dest, out.uc_wb_data),
+                (
+                    out.uc_nb_load_wb_valid,
+                    out.uc_nb_load_wb_dest,
+                    out.uc_nb_load_wb_data,
+                ),
+                (out.uc_div_wb_valid, out.uc_div_wb_dest, out.uc_div_wb_data),
+            ];
+            for (valid, reg, val) in xreg_writes {
+                if valid && reg != 0 {
+                    events.push(UcEvent::XRegWrite { reg, val });
+                }
+            }
+            if out.uc_trace_valid {
+                events.push(UcEvent::Retire {
+                    pc: out.uc_trace_pc,
+                    insn: out.uc_trace_insn,
+                    exception: out.uc_trace_exception,
+                    interrupt: out.uc_trace_interrupt,
+                });
+            }
+        }
         match self.output.uc_htrans {
             0b00 => {}
             0b10 => {
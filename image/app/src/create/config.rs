@@ -13,7 +13,7 @@ Abstract:
 --*/
 
 use anyhow::Context;
-use caliptra_image_types::{VENDOR_ECC_KEY_COUNT, VENDOR_LMS_KEY_COUNT};
+use caliptra_image_types::{VENDOR_ECC_KEY_COUNT, VENDOR_LMS_KEY_COUNT, VENDOR_MLDSA_KEY_COUNT};
 use serde_derive::{Deserialize, Serialize};
 use std::path::PathBuf;
 
@@ -27,6 +27,10 @@ pub(crate) struct VendorKeyConfig {
     pub ecc_priv_keys: Option<[String; VENDOR_ECC_KEY_COUNT as usize]>,
 
     pub lms_priv_keys: Option<[String; VENDOR_LMS_KEY_COUNT as usize]>,
+
+    pub mldsa_pub_keys: Option<[String; VENDOR_MLDSA_KEY_COUNT as usize]>,
+
+    pub mldsa_priv_keys: Option<[String; VENDOR_MLDSA_KEY_COUNT as usize]>,
 }
 
 /// Owner Key Configuration
@@ -39,6 +43,31 @@ pub(crate) struct OwnerKeyConfig {
     pub lms_pub_key: String,
 
     pub lms_priv_key: Option<String>,
+
+    pub mldsa_pub_key: Option<String>,
+
+    pub mldsa_priv_key: Option<String>,
+
+    pub dpe: Option<DpeConfig>,
+}
+
+/// Owner DPE Configuration
+#[derive(Default, Serialize, Deserialize)]
+pub(crate) struct DpeConfig {
+    pub vendor_id: u32,
+
+    pub vendor_sku: u32,
+
+    pub pl0_context_limit: u32,
+
+    pub pl1_context_limit: u32,
+
+    pub support: u32,
+
+    pub allowed_commands: u32,
+
+    #[serde(default)]
+    pub export_derived_keys: bool,
 }
 
 //Key Configuration
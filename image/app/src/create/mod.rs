@@ -18,6 +18,8 @@ use anyhow::anyhow;
 use anyhow::Context;
 use caliptra_image_crypto::lms_priv_key_from_pem;
 use caliptra_image_crypto::lms_pub_key_from_pem;
+use caliptra_image_crypto::mldsa_priv_key_from_file;
+use caliptra_image_crypto::mldsa_pub_key_from_file;
 #[cfg(feature = "openssl")]
 use caliptra_image_crypto::OsslCrypto as Crypto;
 #[cfg(feature = "rustcrypto")]
@@ -118,6 +120,15 @@ pub(crate) fn run_cmd(args: &ArgMatches) -> anyhow::Result<()> {
         .get_one::<u32>("lms-pk-idx")
         .with_context(|| "lms-pk-idx arg not specified")?;
 
+    let mldsa_key_idx: u32 = args
+        .get_one::<u32>("mldsa-pk-idx")
+        .copied()
+        .unwrap_or(VENDOR_MLDSA_KEY_COUNT - 1);
+
+    let pqc_key_type = args.get_one::<u32>("pqc-key-type").copied().unwrap_or(0);
+    let pqc_key_type = ImagePqcKeyType::try_from(pqc_key_type)
+        .map_err(|_| anyhow!("Invalid pqc-key-type {pqc_key_type}"))?;
+
     let out_path: &PathBuf = args
         .get_one::<PathBuf>("out")
         .with_context(|| "out arg not specified")?;
@@ -176,6 +187,8 @@ pub(crate) fn run_cmd(args: &ArgMatches) -> anyhow::Result<()> {
             &config.vendor,
             *ecc_key_idx,
             *lms_key_idx,
+            mldsa_key_idx,
+            pqc_key_type,
             mfg_from_date,
             mfg_to_date,
         )?,
@@ -206,6 +219,8 @@ fn vendor_config(
     config: &VendorKeyConfig,
     ecc_key_idx: u32,
     lms_key_idx: u32,
+    mldsa_key_idx: u32,
+    pqc_key_type: ImagePqcKeyType,
     from_date: [u8; 15],
     to_date: [u8; 15],
 ) -> anyhow::Result<ImageGeneratorVendorConfig> {
@@ -232,6 +247,17 @@ fn vendor_config(
         gen_config.pub_keys.lms_pub_keys[i] = lms_pub_key_from_pem(&pub_key_path)?;
     }
 
+    if let Some(mldsa_pub_keys) = &config.mldsa_pub_keys {
+        for (i, key_file) in mldsa_pub_keys
+            .iter()
+            .enumerate()
+            .take(VENDOR_MLDSA_KEY_COUNT as usize)
+        {
+            let pub_key_path = path.join(key_file);
+            gen_config.mldsa_pub_keys[i] = mldsa_pub_key_from_file(&pub_key_path)?;
+        }
+    }
+
     let mut priv_keys = ImageVendorPrivKeys::default();
     if let Some(ecc_priv_keys) = &config.ecc_priv_keys {
         for (i, pem_file) in ecc_priv_keys
@@ -257,8 +283,22 @@ fn vendor_config(
         gen_config.priv_keys = Some(priv_keys);
     }
 
+    if let Some(mldsa_priv_keys) = &config.mldsa_priv_keys {
+        for (i, key_file) in mldsa_priv_keys
+            .iter()
+            .enumerate()
+            .take(VENDOR_MLDSA_KEY_COUNT as usize)
+        {
+            let priv_key_path = path.join(key_file);
+            priv_keys.mldsa_priv_keys[i] = mldsa_priv_key_from_file(&priv_key_path)?;
+        }
+        gen_config.priv_keys = Some(priv_keys);
+    }
+
     gen_config.ecc_key_idx = ecc_key_idx;
     gen_config.lms_key_idx = lms_key_idx;
+    gen_config.mldsa_key_idx = mldsa_key_idx;
+    gen_config.pqc_key_type = pqc_key_type;
     gen_config.not_before = from_date;
     gen_config.not_after = to_date;
 
@@ -284,6 +324,11 @@ fn owner_config(
         let pub_key_path = path.join(pem_file);
         gen_config.pub_keys.lms_pub_key = lms_pub_key_from_pem(&pub_key_path)?;
 
+        if let Some(key_file) = &config.mldsa_pub_key {
+            let pub_key_path = path.join(key_file);
+            gen_config.mldsa_pub_key = mldsa_pub_key_from_file(&pub_key_path)?;
+        }
+
         let mut priv_keys = ImageOwnerPrivKeys::default();
         if let Some(pem_file) = &config.ecc_priv_key {
             let pub_key_path = path.join(pem_file);
@@ -296,6 +341,27 @@ fn owner_config(
             priv_keys.lms_priv_key = lms_priv_key_from_pem(&priv_key_path)?;
             gen_config.priv_keys = Some(priv_keys);
         }
+
+        if let Some(key_file) = &config.mldsa_priv_key {
+            let priv_key_path = path.join(key_file);
+            priv_keys.mldsa_priv_key = mldsa_priv_key_from_file(&priv_key_path)?;
+            gen_config.priv_keys = Some(priv_keys);
+        }
+        if let Some(dpe) = &config.dpe {
+            let mut flags = ImageDpeConfig::ENABLED_FLAG;
+            if dpe.export_derived_keys {
+                flags |= ImageDpeConfig::EXPORT_DERIVED_KEYS_FLAG;
+            }
+            gen_config.dpe_config = ImageDpeConfig {
+                flags,
+                vendor_id: dpe.vendor_id,
+                vendor_sku: dpe.vendor_sku,
+                pl0_context_limit: dpe.pl0_context_limit,
+                pl1_context_limit: dpe.pl1_context_limit,
+                support: dpe.support,
+                allowed_commands: dpe.allowed_commands,
+            };
+        }
         gen_config.not_before = from_date;
         gen_config.not_after = to_date;
 
@@ -36,6 +36,16 @@ fn main() {
                 .required(false)
                 .value_parser(value_parser!(u32)),
         )
+        .arg(
+            arg!(--"mldsa-pk-idx" <U32> "Vendor ML-DSA Public Key Index (default: the last, unrevocable key)")
+                .required(false)
+                .value_parser(value_parser!(u32)),
+        )
+        .arg(
+            arg!(--"pqc-key-type" <U32> "Type of PQC signature: 0 = LMS (default), 1 = ML-DSA-87")
+                .required(false)
+                .value_parser(value_parser!(u32)),
+        )
         .arg(
             arg!(--"fmc" <FILE> "FMC ELF binary")
                 .required(true)
@@ -80,6 +80,50 @@ pub fn lms_priv_key_from_pem(path: &PathBuf) -> anyhow::Result<ImageLmsPrivKey>
         .map_err(|_| anyhow!("Error parsing LMS priv key"))
 }
 
+/// Read a raw ML-DSA-87 public key file
+pub fn mldsa_pub_key_from_file(path: &PathBuf) -> anyhow::Result<ImageMldsaPubKey> {
+    let key_bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read public key file {}", path.display()))?;
+
+    ImageMldsaPubKey::read_from_bytes(&key_bytes[..])
+        .map_err(|_| anyhow!("Error parsing ML-DSA public key"))
+}
+
+/// Read a raw ML-DSA-87 private key file
+pub fn mldsa_priv_key_from_file(path: &PathBuf) -> anyhow::Result<ImageMldsaPrivKey> {
+    let key_bytes = std::fs::read(path)
+        .with_context(|| format!("Failed to read private key file {}", path.display()))?;
+
+    ImageMldsaPrivKey::read_from_bytes(&key_bytes[..])
+        .map_err(|_| anyhow!("Error parsing ML-DSA priv key"))
+}
+
+/// Sign a digest with an ML-DSA-87 private key.
+///
+/// The message is the digest words serialized little-endian and zero-padded
+/// to `MLDSA87_MSG_BYTE_SIZE`, which is how the hardware consumes it.
+fn sign_with_mldsa_key(
+    digest: &ImageDigest,
+    priv_key: &ImageMldsaPrivKey,
+) -> anyhow::Result<ImageMldsaSignature> {
+    use fips204::ml_dsa_87::{PrivateKey, SK_LEN};
+    use fips204::traits::{SerDes, Signer};
+
+    let mut message = [0u8; MLDSA87_MSG_BYTE_SIZE];
+    message[..SHA384_DIGEST_BYTE_SIZE].copy_from_slice(digest.as_bytes());
+
+    let key_bytes: [u8; SK_LEN] = priv_key.as_bytes().try_into()?;
+    let priv_key =
+        PrivateKey::try_from_bytes(key_bytes).map_err(|e| anyhow!("Invalid ML-DSA key: {e}"))?;
+    let signature = priv_key
+        .try_sign(&message)
+        .map_err(|e| anyhow!("ML-DSA signing failed: {e}"))?;
+
+    let mut image_sig = ImageMldsaSignature::default();
+    image_sig.as_mut_bytes()[..signature.len()].copy_from_slice(&signature);
+    Ok(image_sig)
+}
+
 /// Convert the slice to hardware format
 fn to_hw_format<const NUM_WORDS: usize>(value: &[u8]) -> [u32; NUM_WORDS] {
     let mut result = [0u32; NUM_WORDS];
@@ -28,7 +28,10 @@ use {
     sha2::{Digest, Sha256, Sha384},
 };
 
-use crate::{from_hw_format, sign_with_lms_key, to_hw_format, Sha256Hasher, SUPPORTED_LMS_Q_VALUE};
+use crate::{
+    from_hw_format, sign_with_lms_key, sign_with_mldsa_key, to_hw_format, Sha256Hasher,
+    SUPPORTED_LMS_Q_VALUE,
+};
 
 #[derive(Default)]
 pub struct RustCrypto {}
@@ -93,6 +96,14 @@ impl ImageGeneratorCrypto for RustCrypto {
         sign_with_lms_key::<RustCryptoHasher>(priv_key, &message, &nonce, SUPPORTED_LMS_Q_VALUE)
     }
 
+    fn mldsa87_sign(
+        &self,
+        digest: &ImageDigest,
+        priv_key: &ImageMldsaPrivKey,
+    ) -> anyhow::Result<ImageMldsaSignature> {
+        sign_with_mldsa_key(digest, priv_key)
+    }
+
     fn ecc_pub_key_from_pem(path: &Path) -> anyhow::Result<ImageEccPubKey> {
         let key_bytes = std::fs::read(path)
             .with_context(|| format!("Failed to read public key PEM file {}", path.display()))?;
@@ -2,8 +2,9 @@
 
 use caliptra_image_gen::{ImageGeneratorOwnerConfig, ImageGeneratorVendorConfig};
 use caliptra_image_types::{
-    ImageEccPrivKey, ImageEccPubKey, ImageLmsPrivKey, ImageLmsPublicKey, ImageOwnerPrivKeys,
-    ImageOwnerPubKeys, ImageVendorPrivKeys, ImageVendorPubKeys, IMAGE_LMS_OTS_TYPE,
+    ImageDigest, ImageDpeConfig, ImageEccPrivKey, ImageEccPubKey, ImageLmsPrivKey,
+    ImageLmsPublicKey, ImageMldsaPrivKey, ImageMldsaPubKey, ImageOwnerPrivKeys, ImageOwnerPubKeys,
+    ImagePqcKeyType, ImageVendorPrivKeys, ImageVendorPubKeys, IMAGE_LMS_OTS_TYPE,
     IMAGE_LMS_TREE_TYPE,
 };
 use caliptra_lms_types::bytes_to_words_6;
@@ -237,6 +238,88 @@ pub const OWNER_ECC_KEY_PRIVATE: ImageEccPrivKey = [
     0x59fdf849, 0xe39f4256, 0x19342ed2, 0x81d28d3d, 0x45ab3219, 0x5174582c, 0xecb4e9df, 0x9cc2e991,
     0xb75f88fd, 0xfa4bc6a4, 0x6b88340f, 0x05dd8890,
 ];
+/// Converts a raw (FIPS 204 encoded) ML-DSA key into little-endian words.
+const fn mldsa_key_words<const N: usize>(bytes: &[u8]) -> [u32; N] {
+    let mut words = [0u32; N];
+    let mut i = 0;
+    while i < N {
+        words[i] = u32::from_le_bytes([
+            bytes[i * 4],
+            bytes[i * 4 + 1],
+            bytes[i * 4 + 2],
+            bytes[i * 4 + 3],
+        ]);
+        i += 1;
+    }
+    words
+}
+
+/// Generated with
+///
+/// ```text
+/// openssl genpkey -algorithm ML-DSA-87 -pkeyopt hexseed:<seed> -out key.pem
+/// openssl pkey -in key.pem -outform DER -pubout | tail -c 2592 > pub-key.bin
+/// openssl pkey -in key.pem -text -noout # priv key bytes
+/// ```
+pub const VENDOR_MLDSA_KEY_0_PUBLIC: ImageMldsaPubKey = ImageMldsaPubKey(mldsa_key_words(
+    include_bytes!("mldsa/vnd-mldsa-pub-key-0.bin"),
+));
+pub const VENDOR_MLDSA_KEY_0_PRIVATE: ImageMldsaPrivKey = ImageMldsaPrivKey(mldsa_key_words(
+    include_bytes!("mldsa/vnd-mldsa-priv-key-0.bin"),
+));
+pub const VENDOR_MLDSA_KEY_1_PUBLIC: ImageMldsaPubKey = ImageMldsaPubKey(mldsa_key_words(
+    include_bytes!("mldsa/vnd-mldsa-pub-key-1.bin"),
+));
+pub const VENDOR_MLDSA_KEY_1_PRIVATE: ImageMldsaPrivKey = ImageMldsaPrivKey(mldsa_key_words(
+    include_bytes!("mldsa/vnd-mldsa-priv-key-1.bin"),
+));
+pub const VENDOR_MLDSA_KEY_2_PUBLIC: ImageMldsaPubKey = ImageMldsaPubKey(mldsa_key_words(
+    include_bytes!("mldsa/vnd-mldsa-pub-key-2.bin"),
+));
+pub const VENDOR_MLDSA_KEY_2_PRIVATE: ImageMldsaPrivKey = ImageMldsaPrivKey(mldsa_key_words(
+    include_bytes!("mldsa/vnd-mldsa-priv-key-2.bin"),
+));
+pub const VENDOR_MLDSA_KEY_3_PUBLIC: ImageMldsaPubKey = ImageMldsaPubKey(mldsa_key_words(
+    include_bytes!("mldsa/vnd-mldsa-pub-key-3.bin"),
+));
+pub const VENDOR_MLDSA_KEY_3_PRIVATE: ImageMldsaPrivKey = ImageMldsaPrivKey(mldsa_key_words(
+    include_bytes!("mldsa/vnd-mldsa-priv-key-3.bin"),
+));
+pub const OWNER_MLDSA_KEY_PUBLIC: ImageMldsaPubKey = ImageMldsaPubKey(mldsa_key_words(
+    include_bytes!("mldsa/own-mldsa-pub-key.bin"),
+));
+pub const OWNER_MLDSA_KEY_PRIVATE: ImageMldsaPrivKey = ImageMldsaPrivKey(mldsa_key_words(
+    include_bytes!("mldsa/own-mldsa-priv-key.bin"),
+));
+
+pub const VENDOR_MLDSA_PUBLIC_KEYS: [ImageMldsaPubKey; 4] = [
+    VENDOR_MLDSA_KEY_0_PUBLIC,
+    VENDOR_MLDSA_KEY_1_PUBLIC,
+    VENDOR_MLDSA_KEY_2_PUBLIC,
+    VENDOR_MLDSA_KEY_3_PUBLIC,
+];
+
+/// SHA-384 digests of `VENDOR_MLDSA_PUBLIC_KEYS`, as carried in the vendor
+/// public keys of ML-DSA-87 images
+pub const VENDOR_MLDSA_PUB_KEY_DIGESTS: [ImageDigest; 4] = [
+    [
+        0xfb756c0f, 0x834f62b0, 0x682f23b4, 0xcf072a37, 0x4d2c1f84, 0x7cc5528c, 0xb3088ee8,
+        0x75c5883d, 0x064638ac, 0xd3338ae6, 0x37836d9e, 0xfa7b22fd,
+    ],
+    [
+        0xb75408ee, 0x48021c46, 0x53ece172, 0xee597d96, 0x013cdb48, 0x188fea19, 0x990b99b9,
+        0x6a63cd32, 0xd7d86baa, 0x91c8df56, 0x25b379c2, 0x4b2c513a,
+    ],
+    [
+        0x443ac37a, 0xe0444466, 0x3b9909d2, 0x7520138a, 0x1eb3df69, 0x6a3e1955, 0x4ba73214,
+        0x24e94cd2, 0x1add392d, 0x525c4df2, 0xf651295e, 0x366ae72a,
+    ],
+    [
+        0x844775bd, 0xa0b2c252, 0xf2eadba3, 0xd2b183bc, 0x9301c5d9, 0x556bee4a, 0x3522c81a,
+        0x1280778e, 0x9150f025, 0xc432dcc0, 0x4c4cf8a9, 0x5800cb77,
+    ],
+];
+
 pub const VENDOR_PUBLIC_KEYS: ImageVendorPubKeys = ImageVendorPubKeys {
     ecc_pub_keys: [
         VENDOR_ECC_KEY_0_PUBLIC,
@@ -325,17 +408,27 @@ pub const VENDOR_PRIVATE_KEYS: ImageVendorPrivKeys = ImageVendorPrivKeys {
         VENDOR_LMS_KEY_2_PRIVATE,
         VENDOR_LMS_KEY_3_PRIVATE,
     ],
+    mldsa_priv_keys: [
+        VENDOR_MLDSA_KEY_0_PRIVATE,
+        VENDOR_MLDSA_KEY_1_PRIVATE,
+        VENDOR_MLDSA_KEY_2_PRIVATE,
+        VENDOR_MLDSA_KEY_3_PRIVATE,
+    ],
 };
 
 pub const OWNER_PRIVATE_KEYS: ImageOwnerPrivKeys = ImageOwnerPrivKeys {
     ecc_priv_key: OWNER_ECC_KEY_PRIVATE,
     lms_priv_key: OWNER_LMS_KEY_PRIVATE,
+    mldsa_priv_key: OWNER_MLDSA_KEY_PRIVATE,
 };
 
 pub const VENDOR_CONFIG_KEY_0: ImageGeneratorVendorConfig = ImageGeneratorVendorConfig {
     pub_keys: VENDOR_PUBLIC_KEYS,
     ecc_key_idx: 0,
     lms_key_idx: 0,
+    mldsa_key_idx: 0,
+    mldsa_pub_keys: VENDOR_MLDSA_PUBLIC_KEYS,
+    pqc_key_type: ImagePqcKeyType::Lms,
     priv_keys: Some(VENDOR_PRIVATE_KEYS),
     not_before: [0u8; 15],
     not_after: [0u8; 15],
@@ -345,18 +438,21 @@ pub const VENDOR_CONFIG_KEY_0: ImageGeneratorVendorConfig = ImageGeneratorVendor
 pub const VENDOR_CONFIG_KEY_1: ImageGeneratorVendorConfig = ImageGeneratorVendorConfig {
     ecc_key_idx: 1,
     lms_key_idx: 1,
+    mldsa_key_idx: 1,
     ..VENDOR_CONFIG_KEY_0
 };
 
 pub const VENDOR_CONFIG_KEY_2: ImageGeneratorVendorConfig = ImageGeneratorVendorConfig {
     ecc_key_idx: 2,
     lms_key_idx: 2,
+    mldsa_key_idx: 2,
     ..VENDOR_CONFIG_KEY_0
 };
 
 pub const VENDOR_CONFIG_KEY_3: ImageGeneratorVendorConfig = ImageGeneratorVendorConfig {
     ecc_key_idx: 3,
     lms_key_idx: 3,
+    mldsa_key_idx: 3,
     ..VENDOR_CONFIG_KEY_0
 };
 
@@ -365,13 +461,24 @@ pub const OWNER_CONFIG: ImageGeneratorOwnerConfig = ImageGeneratorOwnerConfig {
         ecc_pub_key: OWNER_ECC_KEY_PUBLIC,
         lms_pub_key: OWNER_LMS_KEY_PUBLIC,
     },
+    mldsa_pub_key: OWNER_MLDSA_KEY_PUBLIC,
     priv_keys: Some(ImageOwnerPrivKeys {
         ecc_priv_key: OWNER_ECC_KEY_PRIVATE,
         lms_priv_key: OWNER_LMS_KEY_PRIVATE,
+        mldsa_priv_key: OWNER_MLDSA_KEY_PRIVATE,
     }),
     not_before: [0u8; 15],
     not_after: [0u8; 15],
     epoch: [0u8; 2],
+    dpe_config: ImageDpeConfig {
+        flags: 0,
+        vendor_id: 0,
+        vendor_sku: 0,
+        pl0_context_limit: 0,
+        pl1_context_limit: 0,
+        support: 0,
+        allowed_commands: 0,
+    },
 };
 
 #[test]
@@ -421,3 +528,17 @@ fn test_write_lms_keys() {
     file.write_all(OWNER_PUBLIC_KEYS.lms_pub_key.as_bytes())
         .unwrap();
 }
+
+#[test]
+fn test_vendor_mldsa_pub_key_digests() {
+    use caliptra_image_crypto::OsslCrypto;
+    use caliptra_image_gen::ImageGeneratorCrypto;
+
+    let crypto = OsslCrypto::default();
+    for (pub_key, digest) in VENDOR_MLDSA_PUBLIC_KEYS
+        .iter()
+        .zip(VENDOR_MLDSA_PUB_KEY_DIGESTS.iter())
+    {
+        assert_eq!(crypto.sha384_digest(pub_key.as_bytes()).unwrap(), *digest);
+    }
+}
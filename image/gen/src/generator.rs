@@ -45,8 +45,14 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
     where
         E: ImageGenratorExecutable,
     {
-        let image_size =
-            IMAGE_MANIFEST_BYTE_SIZE as u32 + config.fmc.size() + config.runtime.size();
+        let mldsa_preamble_size = match config.vendor_config.pqc_key_type {
+            ImagePqcKeyType::Lms => 0,
+            ImagePqcKeyType::Mldsa => IMAGE_MLDSA_PREAMBLE_BYTE_SIZE as u32,
+        };
+        let image_size = IMAGE_MANIFEST_BYTE_SIZE as u32
+            + mldsa_preamble_size
+            + config.fmc.size()
+            + config.runtime.size();
         if image_size > IMAGE_BYTE_SIZE as u32 {
             bail!(
                 "Image larger than {IMAGE_BYTE_SIZE} bytes; image size:{} bytes",
@@ -56,7 +62,7 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
 
         // Create FMC TOC & Content
         let id = ImageTocEntryId::Fmc;
-        let offset = IMAGE_MANIFEST_BYTE_SIZE as u32;
+        let offset = IMAGE_MANIFEST_BYTE_SIZE as u32 + mldsa_preamble_size;
         let (fmc_toc, fmc) = self.gen_image(&config.fmc, id, offset)?;
 
         // Create Runtime TOC & Content
@@ -78,6 +84,15 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
         let ecc_key_idx = config.vendor_config.ecc_key_idx;
         let lms_key_idx = config.vendor_config.lms_key_idx;
 
+        if config.vendor_config.pqc_key_type == ImagePqcKeyType::Mldsa
+            && config.vendor_config.mldsa_key_idx >= VENDOR_MLDSA_KEY_COUNT
+        {
+            bail!(
+                "ML-DSA key index {} out of range",
+                config.vendor_config.mldsa_key_idx
+            );
+        }
+
         // Create Header
         let toc_digest = self.toc_digest(&fmc_toc, &runtime_toc)?;
         let header = self.gen_header(config, ecc_key_idx, lms_key_idx, toc_digest)?;
@@ -92,6 +107,8 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             &header_digest_vendor,
             &header_digest_owner,
         )?;
+        let mldsa_preamble =
+            self.gen_mldsa_preamble(config, &header_digest_vendor, &header_digest_owner)?;
 
         // Create Manifest
         let manifest = ImageManifest {
@@ -106,6 +123,7 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
         // Create Image Bundle
         let image = ImageBundle {
             manifest,
+            mldsa_preamble,
             fmc,
             runtime,
         };
@@ -127,19 +145,22 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
     {
         let mut vendor_sigs = ImageSignatures::default();
         let mut owner_sigs = ImageSignatures::default();
+        let pqc_key_type = config.vendor_config.pqc_key_type;
 
-        if let Some(priv_keys) = config.vendor_config.priv_keys {
+        if let Some(priv_keys) = &config.vendor_config.priv_keys {
             let sig = self.crypto.ecdsa384_sign(
                 digest_vendor,
                 &priv_keys.ecc_priv_keys[ecc_vendor_key_idx as usize],
                 &config.vendor_config.pub_keys.ecc_pub_keys[ecc_vendor_key_idx as usize],
             )?;
             vendor_sigs.ecc_sig = sig;
-            let lms_sig = self.crypto.lms_sign(
-                digest_vendor,
-                &priv_keys.lms_priv_keys[lms_vendor_key_idx as usize],
-            )?;
-            vendor_sigs.lms_sig = lms_sig;
+            if pqc_key_type == ImagePqcKeyType::Lms {
+                let lms_sig = self.crypto.lms_sign(
+                    digest_vendor,
+                    &priv_keys.lms_priv_keys[lms_vendor_key_idx as usize],
+                )?;
+                vendor_sigs.lms_sig = lms_sig;
+            }
         }
 
         if let Some(owner_config) = &config.owner_config {
@@ -150,10 +171,12 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
                     &owner_config.pub_keys.ecc_pub_key,
                 )?;
                 owner_sigs.ecc_sig = sig;
-                let lms_sig = self
-                    .crypto
-                    .lms_sign(digest_owner, &priv_keys.lms_priv_key)?;
-                owner_sigs.lms_sig = lms_sig;
+                if pqc_key_type == ImagePqcKeyType::Lms {
+                    let lms_sig = self
+                        .crypto
+                        .lms_sign(digest_owner, &priv_keys.lms_priv_key)?;
+                    owner_sigs.lms_sig = lms_sig;
+                }
             }
         }
 
@@ -163,6 +186,7 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             vendor_lms_pub_key_idx: lms_vendor_key_idx,
             vendor_sigs,
             owner_sigs,
+            pqc_key_type: pqc_key_type.into(),
             ..Default::default()
         };
 
@@ -170,9 +194,68 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             preamble.owner_pub_keys = owner_config.pub_keys;
         }
 
+        // ML-DSA-87 images carry the ML-DSA-87 key digests in place of the
+        // LMS public keys
+        if pqc_key_type == ImagePqcKeyType::Mldsa {
+            let mut digests = [ImageDigest::default(); VENDOR_MLDSA_KEY_COUNT as usize];
+            for (digest, pub_key) in digests
+                .iter_mut()
+                .zip(config.vendor_config.mldsa_pub_keys.iter())
+            {
+                *digest = self.crypto.sha384_digest(pub_key.as_bytes())?;
+            }
+            preamble.vendor_pub_keys.set_mldsa_pub_key_digests(&digests);
+            preamble.vendor_mldsa_pub_key_idx = config.vendor_config.mldsa_key_idx;
+
+            if let Some(owner_config) = &config.owner_config {
+                let digest = self
+                    .crypto
+                    .sha384_digest(owner_config.mldsa_pub_key.as_bytes())?;
+                preamble.owner_pub_keys.set_mldsa_pub_key_digest(&digest);
+            }
+        }
+
         Ok(preamble)
     }
 
+    /// Create the ML-DSA-87 preamble of an ML-DSA-87 image
+    pub fn gen_mldsa_preamble<E>(
+        &self,
+        config: &ImageGeneratorConfig<E>,
+        digest_vendor: &ImageDigest,
+        digest_owner: &ImageDigest,
+    ) -> anyhow::Result<Option<ImageMldsaPreamble>>
+    where
+        E: ImageGenratorExecutable,
+    {
+        if config.vendor_config.pqc_key_type != ImagePqcKeyType::Mldsa {
+            return Ok(None);
+        }
+
+        let key_idx = config.vendor_config.mldsa_key_idx as usize;
+        let mut mldsa_preamble = ImageMldsaPreamble {
+            vendor_pub_key: config.vendor_config.mldsa_pub_keys[key_idx],
+            ..Default::default()
+        };
+
+        if let Some(priv_keys) = &config.vendor_config.priv_keys {
+            mldsa_preamble.vendor_sig = self
+                .crypto
+                .mldsa87_sign(digest_vendor, &priv_keys.mldsa_priv_keys[key_idx])?;
+        }
+
+        if let Some(owner_config) = &config.owner_config {
+            mldsa_preamble.owner_pub_key = owner_config.mldsa_pub_key;
+            if let Some(priv_keys) = &owner_config.priv_keys {
+                mldsa_preamble.owner_sig = self
+                    .crypto
+                    .mldsa87_sign(digest_owner, &priv_keys.mldsa_priv_key)?;
+            }
+        }
+
+        Ok(Some(mldsa_preamble))
+    }
+
     /// Generate header
     fn gen_header<E>(
         &self,
@@ -187,6 +270,8 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
         let mut header = ImageHeader {
             vendor_ecc_pub_key_idx: ecc_key_idx,
             vendor_lms_pub_key_idx: lms_key_idx,
+            pqc_key_type: config.vendor_config.pqc_key_type.into(),
+            vendor_mldsa_pub_key_idx: config.vendor_config.mldsa_key_idx,
             flags: Self::DEFAULT_FLAGS,
             toc_len: MAX_TOC_ENTRY_COUNT,
             toc_digest: digest,
@@ -205,6 +290,7 @@ impl<Crypto: ImageGeneratorCrypto> ImageGenerator<Crypto> {
             header.owner_data.owner_not_before = owner_config.not_before;
             header.owner_data.owner_not_after = owner_config.not_after;
             header.owner_data.epoch = owner_config.epoch;
+            header.owner_data.dpe_config = owner_config.dpe_config;
         }
 
         Ok(header)
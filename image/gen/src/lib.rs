@@ -83,6 +83,13 @@ pub trait ImageGeneratorCrypto {
         priv_key: &ImageLmsPrivKey,
     ) -> anyhow::Result<ImageLmsSignature>;
 
+    /// Calculate ML-DSA-87 Signature
+    fn mldsa87_sign(
+        &self,
+        digest: &ImageDigest,
+        priv_key: &ImageMldsaPrivKey,
+    ) -> anyhow::Result<ImageMldsaSignature>;
+
     /// Read ECC-384 Public Key from PEM file
     fn ecc_pub_key_from_pem(path: &Path) -> anyhow::Result<ImageEccPubKey>;
 
@@ -99,6 +106,18 @@ pub struct ImageGeneratorVendorConfig {
 
     pub lms_key_idx: u32,
 
+    pub mldsa_key_idx: u32,
+
+    /// Vendor ML-DSA-87 public keys. ML-DSA-87 images carry their digests in
+    /// place of the LMS keys in `pub_keys`, and the key at `mldsa_key_idx`
+    /// in full in the ML-DSA-87 preamble.
+    #[serde(skip)]
+    pub mldsa_pub_keys: [ImageMldsaPubKey; VENDOR_MLDSA_KEY_COUNT as usize],
+
+    /// PQC algorithm used alongside ECC-384
+    #[serde(default)]
+    pub pqc_key_type: ImagePqcKeyType,
+
     pub priv_keys: Option<ImageVendorPrivKeys>,
 
     pub not_before: [u8; 15],
@@ -113,6 +132,10 @@ pub struct ImageGeneratorVendorConfig {
 pub struct ImageGeneratorOwnerConfig {
     pub pub_keys: ImageOwnerPubKeys,
 
+    /// Owner ML-DSA-87 public key, used by ML-DSA-87 images only
+    #[serde(skip)]
+    pub mldsa_pub_key: ImageMldsaPubKey,
+
     pub priv_keys: Option<ImageOwnerPrivKeys>,
 
     pub not_before: [u8; 15],
@@ -120,6 +143,9 @@ pub struct ImageGeneratorOwnerConfig {
     pub not_after: [u8; 15],
 
     pub epoch: [u8; 2],
+
+    #[serde(default)]
+    pub dpe_config: ImageDpeConfig,
 }
 
 /// Image Generator Configuration
@@ -29,6 +29,9 @@ impl<W: Write> ImageBundleWriter<W> {
     /// Write Image Bundle
     pub fn write(&mut self, image: &ImageBundle) -> anyhow::Result<()> {
         self.writer.write_all(image.manifest.as_bytes())?;
+        if let Some(mldsa_preamble) = &image.mldsa_preamble {
+            self.writer.write_all(mldsa_preamble.as_bytes())?;
+        }
         self.writer.write_all(&image.fmc)?;
         self.writer.write_all(&image.runtime)?;
         Ok(())
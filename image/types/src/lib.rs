@@ -26,9 +26,16 @@ use serde_derive::Deserialize;
 use zerocopy::{FromBytes, Immutable, IntoBytes, KnownLayout};
 use zeroize::Zeroize;
 
-pub const MANIFEST_MARKER: u32 = 0x4E414D43;
+/// Marker of the current manifest format ("MAN2").
+///
+/// The format with the PQC key type and the owner DPE configuration is not
+/// layout compatible with the original "CMAN" (0x4E414D43) format, so the
+/// marker changed with it. ROM rejects images in the old format with
+/// `IMAGE_VERIFIER_ERR_MANIFEST_MARKER_MISMATCH`.
+pub const MANIFEST_MARKER: u32 = 0x324E414D;
 pub const VENDOR_ECC_KEY_COUNT: u32 = 4;
 pub const VENDOR_LMS_KEY_COUNT: u32 = 32;
+pub const VENDOR_MLDSA_KEY_COUNT: u32 = 4;
 pub const MAX_TOC_ENTRY_COUNT: u32 = 2;
 pub const IMAGE_REVISION_BYTE_SIZE: usize = 20;
 pub const ECC384_SCALAR_WORD_SIZE: usize = 12;
@@ -41,6 +48,15 @@ pub const SHA384_DIGEST_BYTE_SIZE: usize = 48;
 pub const IMAGE_LMS_OTS_P_PARAM: usize = 51;
 pub const IMAGE_LMS_KEY_HEIGHT: usize = 15;
 pub const IMAGE_BYTE_SIZE: usize = 128 * 1024;
+pub const MLDSA87_PUB_KEY_BYTE_SIZE: usize = 2592;
+pub const MLDSA87_PUB_KEY_WORD_SIZE: usize = 648;
+pub const MLDSA87_PRIV_KEY_BYTE_SIZE: usize = 4896;
+pub const MLDSA87_PRIV_KEY_WORD_SIZE: usize = 1224;
+// 4627 bytes rounded up to a whole number of words
+pub const MLDSA87_SIGNATURE_BYTE_SIZE: usize = 4628;
+pub const MLDSA87_SIGNATURE_WORD_SIZE: usize = 1157;
+pub const MLDSA87_MSG_BYTE_SIZE: usize = 64;
+pub const MLDSA87_MSG_WORD_SIZE: usize = 16;
 // LMS-SHA192-H15
 pub const IMAGE_LMS_TREE_TYPE: LmsAlgorithmType = LmsAlgorithmType::LmsSha256N24H15;
 // LMOTS-SHA192-W4
@@ -107,6 +123,82 @@ pub type ImageLmsSignature =
     LmsSignature<SHA192_DIGEST_WORD_SIZE, IMAGE_LMS_OTS_P_PARAM, IMAGE_LMS_KEY_HEIGHT>;
 pub type ImageLmOTSSignature = LmotsSignature<SHA192_DIGEST_WORD_SIZE, IMAGE_LMS_OTS_P_PARAM>;
 
+/// ML-DSA-87 public key. The little-endian byte representation of the
+/// words is the FIPS 204 encoding of the key.
+#[repr(C)]
+#[derive(
+    IntoBytes, FromBytes, Immutable, KnownLayout, Debug, Copy, Clone, Eq, PartialEq, Zeroize,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ImageMldsaPubKey(pub [u32; MLDSA87_PUB_KEY_WORD_SIZE]);
+
+impl Default for ImageMldsaPubKey {
+    fn default() -> Self {
+        Self([0; MLDSA87_PUB_KEY_WORD_SIZE])
+    }
+}
+
+/// ML-DSA-87 private key. The little-endian byte representation of the
+/// words is the FIPS 204 encoding of the key.
+#[repr(C)]
+#[derive(
+    IntoBytes, FromBytes, Immutable, KnownLayout, Debug, Copy, Clone, Eq, PartialEq, Zeroize,
+)]
+pub struct ImageMldsaPrivKey(pub [u32; MLDSA87_PRIV_KEY_WORD_SIZE]);
+
+impl Default for ImageMldsaPrivKey {
+    fn default() -> Self {
+        Self([0; MLDSA87_PRIV_KEY_WORD_SIZE])
+    }
+}
+
+/// ML-DSA-87 signature. The little-endian byte representation of the
+/// words is the FIPS 204 encoding of the signature followed by one byte of
+/// zero padding.
+#[repr(C)]
+#[derive(
+    IntoBytes, FromBytes, Immutable, KnownLayout, Debug, Copy, Clone, Eq, PartialEq, Zeroize,
+)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ImageMldsaSignature(pub [u32; MLDSA87_SIGNATURE_WORD_SIZE]);
+
+impl Default for ImageMldsaSignature {
+    fn default() -> Self {
+        Self([0; MLDSA87_SIGNATURE_WORD_SIZE])
+    }
+}
+
+/// Post-quantum algorithm used alongside ECC-384 to sign the firmware
+#[derive(Debug, Default, Clone, Copy, Eq, PartialEq)]
+#[cfg_attr(feature = "std", derive(Deserialize))]
+pub enum ImagePqcKeyType {
+    /// LMS-SHA192-H15
+    #[default]
+    Lms = 0,
+
+    /// ML-DSA-87
+    Mldsa = 1,
+}
+
+impl From<ImagePqcKeyType> for u32 {
+    /// Converts to this type from the input type.
+    fn from(value: ImagePqcKeyType) -> Self {
+        value as u32
+    }
+}
+
+impl TryFrom<u32> for ImagePqcKeyType {
+    type Error = ();
+
+    fn try_from(value: u32) -> Result<Self, Self::Error> {
+        match value {
+            0 => Ok(Self::Lms),
+            1 => Ok(Self::Mldsa),
+            _ => Err(()),
+        }
+    }
+}
+
 /// Caliptra Image Bundle
 #[cfg(feature = "std")]
 #[derive(Debug, Default)]
@@ -114,6 +206,9 @@ pub struct ImageBundle {
     /// Manifest
     pub manifest: ImageManifest,
 
+    /// ML-DSA-87 preamble, present only in ML-DSA-87 images
+    pub mldsa_preamble: Option<ImageMldsaPreamble>,
+
     /// FMC
     pub fmc: Vec<u8>,
 
@@ -127,6 +222,9 @@ impl ImageBundle {
         use std::io::ErrorKind;
         let mut result = vec![];
         result.extend_from_slice(self.manifest.as_bytes());
+        if let Some(mldsa_preamble) = &self.mldsa_preamble {
+            result.extend_from_slice(mldsa_preamble.as_bytes());
+        }
         if self.manifest.fmc.offset as usize != result.len() {
             return Err(std::io::Error::new(
                 ErrorKind::Other,
@@ -201,6 +299,22 @@ impl ImageManifest {
         span.start as u32 + offset..span.end as u32 + offset
     }
 
+    /// Returns `Range<u32>` containing the active vendor ML-DSA-87 public key
+    /// in the ML-DSA-87 preamble following the manifest
+    pub fn vendor_mldsa_pub_key_range() -> Range<u32> {
+        let offset = IMAGE_MANIFEST_BYTE_SIZE as u32;
+        let span = span_of!(ImageMldsaPreamble, vendor_pub_key);
+        span.start as u32 + offset..span.end as u32 + offset
+    }
+
+    /// Returns `Range<u32>` containing the owner ML-DSA-87 public key
+    /// in the ML-DSA-87 preamble following the manifest
+    pub fn owner_mldsa_pub_key_range() -> Range<u32> {
+        let offset = IMAGE_MANIFEST_BYTE_SIZE as u32;
+        let span = span_of!(ImageMldsaPreamble, owner_pub_key);
+        span.start as u32 + offset..span.end as u32 + offset
+    }
+
     /// Returns `Range<u32>` containing the owner public key
     pub fn owner_pub_key_range() -> Range<u32> {
         let offset = offset_of!(ImageManifest, preamble) as u32;
@@ -219,6 +333,28 @@ impl ImageManifest {
         let span = span_of!(ImageManifest, fmc..=runtime);
         span.start as u32..span.end as u32
     }
+
+    /// Returns the ML-DSA-87 preamble following the manifest in `image`
+    ///
+    /// Returns `None` for LMS images or if `image` is too short to hold it.
+    pub fn mldsa_preamble<'a>(&self, image: &'a [u8]) -> Option<&'a ImageMldsaPreamble> {
+        if self.preamble.pqc_key_type != u32::from(ImagePqcKeyType::Mldsa) {
+            return None;
+        }
+        let bytes = image.get(IMAGE_MANIFEST_BYTE_SIZE..)?;
+        ImageMldsaPreamble::ref_from_prefix(bytes)
+            .ok()
+            .map(|(preamble, _)| preamble)
+    }
+
+    /// Returns the size of the ML-DSA-87 preamble following the manifest
+    pub fn mldsa_preamble_size(&self) -> usize {
+        if self.preamble.pqc_key_type == u32::from(ImagePqcKeyType::Mldsa) {
+            IMAGE_MLDSA_PREAMBLE_BYTE_SIZE
+        } else {
+            0
+        }
+    }
 }
 
 #[repr(C)]
@@ -231,6 +367,41 @@ pub struct ImageVendorPubKeys {
     pub lms_pub_keys: [ImageLmsPublicKey; VENDOR_LMS_KEY_COUNT as usize],
 }
 
+const _: () = assert!(
+    VENDOR_MLDSA_KEY_COUNT as usize * SHA384_DIGEST_BYTE_SIZE
+        <= size_of::<[ImageLmsPublicKey; VENDOR_LMS_KEY_COUNT as usize]>()
+);
+
+impl ImageVendorPubKeys {
+    /// Returns the SHA-384 digest of the vendor ML-DSA-87 public key at `idx`.
+    ///
+    /// ML-DSA-87 images carry no LMS keys. Their `lms_pub_keys` slots hold the
+    /// digests of the vendor ML-DSA-87 public keys instead, so the digests are
+    /// covered by the vendor public key hash fuses.
+    pub fn mldsa_pub_key_digest(&self, idx: u32) -> Option<ImageDigest> {
+        if idx >= VENDOR_MLDSA_KEY_COUNT {
+            return None;
+        }
+        let start = idx as usize * SHA384_DIGEST_BYTE_SIZE;
+        let bytes = self
+            .lms_pub_keys
+            .as_bytes()
+            .get(start..start + SHA384_DIGEST_BYTE_SIZE)?;
+        ImageDigest::read_from_bytes(bytes).ok()
+    }
+
+    /// Replaces the LMS public keys with the vendor ML-DSA-87 public key digests
+    pub fn set_mldsa_pub_key_digests(
+        &mut self,
+        digests: &[ImageDigest; VENDOR_MLDSA_KEY_COUNT as usize],
+    ) {
+        let bytes = self.lms_pub_keys.as_mut_bytes();
+        bytes.fill(0);
+        let digests = digests.as_bytes();
+        bytes[..digests.len()].copy_from_slice(digests);
+    }
+}
+
 #[repr(C)]
 #[derive(IntoBytes, Immutable, KnownLayout, FromBytes, Default, Debug, Clone, Copy, Zeroize)]
 #[cfg_attr(feature = "std", derive(Deserialize))]
@@ -238,6 +409,8 @@ pub struct ImageVendorPrivKeys {
     pub ecc_priv_keys: [ImageEccPrivKey; VENDOR_ECC_KEY_COUNT as usize],
     #[zeroize(skip)]
     pub lms_priv_keys: [ImageLmsPrivKey; VENDOR_LMS_KEY_COUNT as usize],
+    #[cfg_attr(feature = "std", serde(skip))]
+    pub mldsa_priv_keys: [ImageMldsaPrivKey; VENDOR_MLDSA_KEY_COUNT as usize],
 }
 
 #[repr(C)]
@@ -250,6 +423,29 @@ pub struct ImageOwnerPubKeys {
     pub lms_pub_key: ImageLmsPublicKey,
 }
 
+const _: () = assert!(SHA384_DIGEST_BYTE_SIZE <= size_of::<ImageLmsPublicKey>());
+
+impl ImageOwnerPubKeys {
+    /// Returns the SHA-384 digest of the owner ML-DSA-87 public key.
+    ///
+    /// As with the vendor keys, ML-DSA-87 images store the digest in the
+    /// `lms_pub_key` slot so it is covered by the owner public key hash fuses.
+    pub fn mldsa_pub_key_digest(&self) -> ImageDigest {
+        let mut digest = ImageDigest::default();
+        digest
+            .as_mut_bytes()
+            .copy_from_slice(&self.lms_pub_key.as_bytes()[..SHA384_DIGEST_BYTE_SIZE]);
+        digest
+    }
+
+    /// Replaces the LMS public key with the owner ML-DSA-87 public key digest
+    pub fn set_mldsa_pub_key_digest(&mut self, digest: &ImageDigest) {
+        let bytes = self.lms_pub_key.as_mut_bytes();
+        bytes.fill(0);
+        bytes[..SHA384_DIGEST_BYTE_SIZE].copy_from_slice(digest.as_bytes());
+    }
+}
+
 #[repr(C)]
 #[derive(IntoBytes, Immutable, KnownLayout, FromBytes, Default, Debug, Clone, Copy, Zeroize)]
 #[cfg_attr(feature = "std", derive(Deserialize))]
@@ -257,6 +453,8 @@ pub struct ImageOwnerPrivKeys {
     pub ecc_priv_key: ImageEccPrivKey,
     #[zeroize(skip)]
     pub lms_priv_key: ImageLmsPrivKey,
+    #[cfg_attr(feature = "std", serde(skip))]
+    pub mldsa_priv_key: ImageMldsaPrivKey,
 }
 
 #[repr(C)]
@@ -291,9 +489,37 @@ pub struct ImagePreamble {
     /// Owner Signatures
     pub owner_sigs: ImageSignatures,
 
-    pub _rsvd: [u32; 2],
+    /// PQC key type (see `ImagePqcKeyType`)
+    pub pqc_key_type: u32,
+
+    /// Vendor ML-DSA-87 Public Key Index
+    pub vendor_mldsa_pub_key_idx: u32,
+}
+
+/// ML-DSA-87 keys and signatures of an ML-DSA-87 image.
+///
+/// Only images with `ImagePqcKeyType::Mldsa` carry this structure. It
+/// immediately follows the manifest in the image bundle, so LMS images keep
+/// the original bundle layout.
+#[repr(C)]
+#[derive(Clone, Copy, IntoBytes, Immutable, KnownLayout, FromBytes, Default, Debug, Zeroize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+pub struct ImageMldsaPreamble {
+    /// Active Vendor ML-DSA-87 Public Key
+    pub vendor_pub_key: ImageMldsaPubKey,
+
+    /// Vendor ML-DSA-87 Signature
+    pub vendor_sig: ImageMldsaSignature,
+
+    /// Owner ML-DSA-87 Public Key
+    pub owner_pub_key: ImageMldsaPubKey,
+
+    /// Owner ML-DSA-87 Signature
+    pub owner_sig: ImageMldsaSignature,
 }
 
+pub const IMAGE_MLDSA_PREAMBLE_BYTE_SIZE: usize = size_of::<ImageMldsaPreamble>();
+
 #[repr(C)]
 #[derive(IntoBytes, Clone, Copy, FromBytes, Immutable, KnownLayout, Default, Debug, Zeroize)]
 #[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
@@ -320,9 +546,67 @@ pub struct OwnerSignedData {
     /// Owner epoch, used to diversify stable SVN keys.
     pub epoch: [u8; 2],
 
+    /// DPE configuration enforced by runtime
+    pub dpe_config: ImageDpeConfig,
+
     pub reserved: [u8; 8],
 }
 
+/// Owner signed DPE configuration
+#[repr(C)]
+#[derive(IntoBytes, Clone, Copy, FromBytes, Immutable, KnownLayout, Default, Debug, Zeroize)]
+#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[cfg_attr(feature = "std", derive(Deserialize))]
+pub struct ImageDpeConfig {
+    /// Flags
+    /// Bit 0: Interpret the DPE configuration. If not set, runtime uses its defaults.
+    /// Bit 1: Allow DERIVE_KEY to return derived keys to the PL0 PAUSER.
+    pub flags: u32,
+
+    /// Vendor ID reported by the DPE GetProfile command
+    pub vendor_id: u32,
+
+    /// Vendor SKU reported by the DPE GetProfile command
+    pub vendor_sku: u32,
+
+    /// Maximum number of active DPE contexts in the PL0 PAUSER locality
+    pub pl0_context_limit: u32,
+
+    /// Maximum number of active DPE contexts in all other localities
+    pub pl1_context_limit: u32,
+
+    /// DPE support flags. Must be a subset of the flags runtime supports.
+    pub support: u32,
+
+    /// Bitmask of the DPE commands runtime accepts (see `ImageDpeConfig::CMD_*`)
+    pub allowed_commands: u32,
+}
+
+impl ImageDpeConfig {
+    pub const ENABLED_FLAG: u32 = 0b1;
+    pub const EXPORT_DERIVED_KEYS_FLAG: u32 = 0b10;
+
+    pub const CMD_GET_PROFILE: u32 = 1 << 0;
+    pub const CMD_INITIALIZE_CONTEXT: u32 = 1 << 1;
+    pub const CMD_DERIVE_CONTEXT: u32 = 1 << 2;
+    pub const CMD_CERTIFY_KEY: u32 = 1 << 3;
+    pub const CMD_SIGN: u32 = 1 << 4;
+    pub const CMD_ROTATE_CONTEXT_HANDLE: u32 = 1 << 5;
+    pub const CMD_DESTROY_CONTEXT: u32 = 1 << 6;
+    pub const CMD_GET_CERTIFICATE_CHAIN: u32 = 1 << 7;
+    pub const CMD_ALL: u32 = (1 << 8) - 1;
+
+    /// Returns true if the owner provided a DPE configuration
+    pub fn enabled(&self) -> bool {
+        self.flags & Self::ENABLED_FLAG != 0
+    }
+
+    /// Returns true if the owner allows derived keys to leave Caliptra
+    pub fn export_derived_keys(&self) -> bool {
+        self.flags & Self::EXPORT_DERIVED_KEYS_FLAG != 0
+    }
+}
+
 /// Caliptra Image header
 #[repr(C)]
 #[derive(IntoBytes, Clone, Copy, FromBytes, Immutable, KnownLayout, Default, Debug, Zeroize)]
@@ -337,6 +621,12 @@ pub struct ImageHeader {
     /// Vendor LMS Public Key Index
     pub vendor_lms_pub_key_idx: u32,
 
+    /// PQC key type (see `ImagePqcKeyType`)
+    pub pqc_key_type: u32,
+
+    /// Vendor ML-DSA-87 Public Key Index
+    pub vendor_mldsa_pub_key_idx: u32,
+
     /// Flags
     /// Bit 0: Interpret the pl0_pauser field. If not set, all PAUSERs are PL1.
     pub flags: u32,
@@ -25,9 +25,11 @@ struct TestEnv {
     fmc_digest: ImageDigest,
     verify_result: bool,
     verify_lms_result: bool,
+    verify_mldsa_result: bool,
     vendor_pub_key_digest: ImageDigest,
     vendor_ecc_pub_key_revocation: VendorPubKeyRevocation,
     vendor_lms_pub_key_revocation: u32,
+    vendor_mldsa_pub_key_revocation: u32,
     owner_pub_key_digest: ImageDigest,
     lifecycle: Lifecycle,
 }
@@ -41,9 +43,12 @@ impl Default for TestEnv {
             verify_result: true,
             // PATCHED
             verify_lms_result: true,
+            // PATCHED
+            verify_mldsa_result: true,
             vendor_pub_key_digest: ImageDigest::default(),
             vendor_ecc_pub_key_revocation: VendorPubKeyRevocation::default(),
             vendor_lms_pub_key_revocation: 0,
+            vendor_mldsa_pub_key_revocation: 0,
             owner_pub_key_digest: ImageDigest::default(),
             lifecycle: Lifecycle::Unprovisioned,
         }
@@ -81,6 +86,19 @@ impl ImageVerificationEnv for TestEnv {
         }
     }
 
+    fn mldsa87_verify(
+        &mut self,
+        _digest: &ImageDigest,
+        _pub_key: &ImageMldsaPubKey,
+        _sig: &ImageMldsaSignature,
+    ) -> CaliptraResult<Mldsa87Result> {
+        if self.verify_mldsa_result {
+            Ok(Mldsa87Result::Success)
+        } else {
+            Ok(Mldsa87Result::SigVerifyFailed)
+        }
+    }
+
     fn vendor_pub_key_digest(&self) -> ImageDigest {
         self.vendor_pub_key_digest
     }
@@ -93,6 +111,10 @@ impl ImageVerificationEnv for TestEnv {
         self.vendor_lms_pub_key_revocation
     }
 
+    fn vendor_mldsa_pub_key_revocation(&self) -> u32 {
+        self.vendor_mldsa_pub_key_revocation
+    }
+
     fn owner_pub_key_digest_fuses(&self) -> ImageDigest {
         self.owner_pub_key_digest
     }
@@ -113,6 +135,10 @@ impl ImageVerificationEnv for TestEnv {
         0
     }
 
+    fn vendor_mldsa_pub_key_idx_dv(&self) -> u32 {
+        0
+    }
+
     fn owner_pub_key_digest_dv(&self) -> ImageDigest {
         self.owner_pub_key_digest
     }
@@ -140,6 +166,10 @@ impl ImageVerificationEnv for TestEnv {
         true
     }
 
+    fn mldsa_verify_enabled(&self) -> bool {
+        true
+    }
+
     fn set_fw_extended_error(&mut self, _err: u32) {}
 }
 
@@ -149,7 +179,7 @@ pub fn harness_structured(reset_reason: ResetReason, manifest: ImageManifest) {
     let mut image_verifier = ImageVerifier::new(test_env);
 
     //println!("{:?}", fuzz_bundle);
-    let _result = image_verifier.verify(&manifest, IMAGE_BUNDLE_SIZE, reset_reason);
+    let _result = image_verifier.verify(&manifest, None, IMAGE_BUNDLE_SIZE, reset_reason);
     //println!("{:?}", _result);
 }
 
@@ -167,12 +197,22 @@ pub fn harness_unstructured(reset_reason: ResetReason, data: &[u8]) {
         typed_fuzz_manifest = &*(data.as_ptr() as *const ImageManifest);
     }
 
+    let typed_fuzz_mldsa_preamble: Option<&ImageMldsaPreamble> =
+        if data.len() >= size_of::<ImageManifest>() + size_of::<ImageMldsaPreamble>() {
+            unsafe {
+                Some(&*(data[size_of::<ImageManifest>()..].as_ptr() as *const ImageMldsaPreamble))
+            }
+        } else {
+            None
+        };
+
     let test_env = TestEnv::default();
     let mut image_verifier = ImageVerifier::new(test_env);
 
     //println!("{:?}", fuzz_bundle);
     let _result = image_verifier.verify(
         typed_fuzz_manifest,
+        typed_fuzz_mldsa_preamble,
         data.len().try_into().unwrap(),
         reset_reason,
     );
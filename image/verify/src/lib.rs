@@ -68,6 +68,12 @@ pub struct ImageVerificationLogInfo {
     /// Vendor LMS Public Key Revocation Fuse
     pub fuse_vendor_lms_pub_key_revocation: Option<u32>,
 
+    // ML-DSA Vendor Public Key Index
+    pub vendor_mldsa_pub_key_idx: Option<u32>,
+
+    /// Vendor ML-DSA Public Key Revocation Fuse
+    pub fuse_vendor_mldsa_pub_key_revocation: Option<u32>,
+
     /// First Mutable code's logging information
     pub fmc_log_info: ImageSvnLogInfo,
 
@@ -84,6 +90,12 @@ pub struct ImageVerificationInfo {
     /// Vendor LMS public key index
     pub vendor_lms_pub_key_idx: Option<u32>,
 
+    /// PQC algorithm that verified the image
+    pub pqc_key_type: ImagePqcKeyType,
+
+    /// Vendor ML-DSA public key index
+    pub vendor_mldsa_pub_key_idx: Option<u32>,
+
     /// Digest of owner public keys that verified the image
     pub owner_pub_keys_digest: ImageDigest,
 
@@ -121,6 +133,14 @@ pub trait ImageVerificationEnv {
         sig: &ImageLmsSignature,
     ) -> CaliptraResult<HashValue<SHA192_DIGEST_WORD_SIZE>>;
 
+    /// Perform ML-DSA-87 Verification
+    fn mldsa87_verify(
+        &mut self,
+        digest: &ImageDigest,
+        pub_key: &ImageMldsaPubKey,
+        sig: &ImageMldsaSignature,
+    ) -> CaliptraResult<Mldsa87Result>;
+
     /// Get Vendor Public Key Digest
     fn vendor_pub_key_digest(&self) -> ImageDigest;
 
@@ -130,6 +150,9 @@ pub trait ImageVerificationEnv {
     /// Get Vendor LMS Public Key Revocation list
     fn vendor_lms_pub_key_revocation(&self) -> u32;
 
+    /// Get Vendor ML-DSA Public Key Revocation list
+    fn vendor_mldsa_pub_key_revocation(&self) -> u32;
+
     /// Get Owner Public Key Digest from fuses
     fn owner_pub_key_digest_fuses(&self) -> ImageDigest;
 
@@ -145,6 +168,9 @@ pub trait ImageVerificationEnv {
     // Get the vendor LMS key index saved on cold boot in data vault
     fn vendor_lms_pub_key_idx_dv(&self) -> u32;
 
+    // Get the vendor ML-DSA key index saved on cold boot in data vault
+    fn vendor_mldsa_pub_key_idx_dv(&self) -> u32;
+
     // Get the owner key digest saved on cold boot in data vault
     fn owner_pub_key_digest_dv(&self) -> ImageDigest;
 
@@ -163,6 +189,9 @@ pub trait ImageVerificationEnv {
     // LMS Verification enabled
     fn lms_verify_enabled(&self) -> bool;
 
+    // ML-DSA-87 Verification backed by a driver
+    fn mldsa_verify_enabled(&self) -> bool;
+
     // Set the extended error code
     fn set_fw_extended_error(&mut self, err: u32);
 }
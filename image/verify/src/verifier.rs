@@ -37,6 +37,11 @@ struct HeaderInfo<'a> {
     vendor_lms_pub_key_revocation: Option<u32>,
     owner_ecc_info: (&'a ImageEccPubKey, &'a ImageEccSignature),
     owner_lms_info: Option<(&'a ImageLmsPublicKey, &'a ImageLmsSignature)>,
+    pqc_key_type: ImagePqcKeyType,
+    vendor_mldsa_pub_key_idx: Option<u32>,
+    vendor_mldsa_info: Option<(&'a ImageMldsaPubKey, &'a ImageMldsaSignature)>,
+    vendor_mldsa_pub_key_revocation: Option<u32>,
+    owner_mldsa_info: Option<(&'a ImageMldsaPubKey, &'a ImageMldsaSignature)>,
     owner_pub_keys_digest: ImageDigest,
     owner_pub_keys_digest_in_fuses: bool,
 }
@@ -45,6 +50,7 @@ struct HeaderInfo<'a> {
 struct TocInfo<'a> {
     len: u32,
     digest: &'a ImageDigest,
+    pqc_key_type: ImagePqcKeyType,
 }
 
 /// Image Info
@@ -73,9 +79,10 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
     ///
     /// # Arguments
     ///
-    /// * `manifest` - Image Manifest
-    /// * `image`    - Image to verify
-    /// * `reason`   - Reset Reason
+    /// * `manifest`       - Image Manifest
+    /// * `mldsa_preamble` - ML-DSA-87 preamble following the manifest, if any
+    /// * `image`          - Image to verify
+    /// * `reason`         - Reset Reason
     ///
     /// # Returns
     ///
@@ -85,6 +92,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
     pub fn verify(
         &mut self,
         manifest: &ImageManifest,
+        mldsa_preamble: Option<&ImageMldsaPreamble>,
         img_bundle_sz: u32,
         reason: ResetReason,
     ) -> CaliptraResult<ImageVerificationInfo> {
@@ -100,7 +108,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
 
         // Verify the preamble
         let preamble = &manifest.preamble;
-        let header_info = self.verify_preamble(preamble, reason);
+        let header_info = self.verify_preamble(preamble, mldsa_preamble, reason);
         let header_info = okref(&header_info)?;
 
         // Verify Header
@@ -121,6 +129,8 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         let info = ImageVerificationInfo {
             vendor_ecc_pub_key_idx: header_info.vendor_ecc_pub_key_idx,
             vendor_lms_pub_key_idx: header_info.vendor_lms_pub_key_idx,
+            pqc_key_type: header_info.pqc_key_type,
+            vendor_mldsa_pub_key_idx: header_info.vendor_mldsa_pub_key_idx,
             owner_pub_keys_digest: header_info.owner_pub_keys_digest,
             owner_pub_keys_digest_in_fuses: header_info.owner_pub_keys_digest_in_fuses,
             fmc: fmc_info,
@@ -132,6 +142,8 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
                 rt_log_info,
                 fuse_vendor_lms_pub_key_revocation: header_info.vendor_lms_pub_key_revocation,
                 vendor_lms_pub_key_idx: header_info.vendor_lms_pub_key_idx,
+                fuse_vendor_mldsa_pub_key_revocation: header_info.vendor_mldsa_pub_key_revocation,
+                vendor_mldsa_pub_key_idx: header_info.vendor_mldsa_pub_key_idx,
             },
         };
 
@@ -143,6 +155,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
     fn verify_preamble<'a>(
         &mut self,
         preamble: &'a ImagePreamble,
+        mldsa_preamble: Option<&'a ImageMldsaPreamble>,
         reason: ResetReason,
     ) -> CaliptraResult<HeaderInfo<'a>> {
         // Verify Vendor Public Key Digest
@@ -162,12 +175,44 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             &preamble.vendor_sigs.ecc_sig,
         );
 
+        // PQC algorithm selected by the manifest
+        let pqc_key_type = ImagePqcKeyType::try_from(preamble.pqc_key_type)
+            .map_err(|_| CaliptraError::IMAGE_VERIFIER_ERR_PQC_KEY_TYPE_INVALID)?;
+
+        // Verify ML-DSA Vendor Key Index and Key
+        let mut vendor_mldsa_pub_key_idx: Option<u32> = None;
+        let mut vendor_mldsa_info: Option<(&ImageMldsaPubKey, &'a ImageMldsaSignature)> = None;
+        let mut vendor_mldsa_pub_key_revocation: Option<u32> = None;
+        let mut owner_mldsa_info: Option<(&ImageMldsaPubKey, &'a ImageMldsaSignature)> = None;
+
+        if cfi_launder(pqc_key_type as u32) == ImagePqcKeyType::Mldsa as u32 {
+            if !cfi_launder(self.env.mldsa_verify_enabled()) {
+                Err(CaliptraError::IMAGE_VERIFIER_ERR_MLDSA_NOT_SUPPORTED)?;
+            } else {
+                cfi_assert!(self.env.mldsa_verify_enabled());
+            }
+            let Some(mldsa_preamble) = mldsa_preamble else {
+                return Err(CaliptraError::IMAGE_VERIFIER_ERR_MLDSA_PREAMBLE_MISSING);
+            };
+            let (idx, revocation) = self.verify_vendor_mldsa_pk_idx(preamble, reason)?;
+            self.verify_vendor_mldsa_pk(preamble, idx)?;
+            self.verify_owner_mldsa_pk(preamble)?;
+            vendor_mldsa_pub_key_idx = Some(idx);
+            vendor_mldsa_pub_key_revocation = Some(revocation);
+            vendor_mldsa_info = Some((&mldsa_preamble.vendor_pub_key, &mldsa_preamble.vendor_sig));
+            owner_mldsa_info = Some((&mldsa_preamble.owner_pub_key, &mldsa_preamble.owner_sig));
+        } else {
+            cfi_assert_eq(pqc_key_type as u32, ImagePqcKeyType::Lms as u32);
+        }
+
         // Verify LMS Vendor Key Index
         let mut vendor_lms_pub_key_idx: Option<u32> = None;
         let mut vendor_lms_info: Option<(&ImageLmsPublicKey, &'a ImageLmsSignature)> = None;
         let mut vendor_lms_pub_key_revocation: Option<u32> = None;
+        let lms_verify_enabled =
+            self.env.lms_verify_enabled() && pqc_key_type == ImagePqcKeyType::Lms;
 
-        if cfi_launder(self.env.lms_verify_enabled()) {
+        if cfi_launder(lms_verify_enabled) {
             (vendor_lms_pub_key_idx, vendor_lms_pub_key_revocation) =
                 self.verify_vendor_lms_pk_idx(preamble, reason)?;
 
@@ -178,7 +223,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
                 ));
             }
         } else {
-            cfi_assert!(!self.env.lms_verify_enabled());
+            cfi_assert!(!lms_verify_enabled);
         }
 
         // Owner Information
@@ -187,13 +232,13 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             &preamble.owner_sigs.ecc_sig,
         );
 
-        let owner_lms_info = if cfi_launder(self.env.lms_verify_enabled()) {
+        let owner_lms_info = if cfi_launder(lms_verify_enabled) {
             Some((
                 &preamble.owner_pub_keys.lms_pub_key,
                 &preamble.owner_sigs.lms_sig,
             ))
         } else {
-            cfi_assert!(!self.env.lms_verify_enabled());
+            cfi_assert!(!lms_verify_enabled);
             None
         };
 
@@ -208,6 +253,11 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             owner_ecc_info,
             vendor_ecc_pub_key_revocation,
             vendor_lms_pub_key_revocation,
+            pqc_key_type,
+            vendor_mldsa_pub_key_idx,
+            vendor_mldsa_info,
+            vendor_mldsa_pub_key_revocation,
+            owner_mldsa_info,
         };
 
         Ok(info)
@@ -302,6 +352,105 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         Ok((Some(key_idx), Some(revocation)))
     }
 
+    /// Verify Vendor ML-DSA Public Key Index
+    fn verify_vendor_mldsa_pk_idx(
+        &mut self,
+        preamble: &ImagePreamble,
+        reason: ResetReason,
+    ) -> CaliptraResult<(u32, u32)> {
+        const SECOND_LAST_KEY_IDX: u32 = VENDOR_MLDSA_KEY_COUNT - 2;
+        const LAST_KEY_IDX: u32 = SECOND_LAST_KEY_IDX + 1;
+
+        let key_idx = preamble.vendor_mldsa_pub_key_idx;
+        let revocation = self.env.vendor_mldsa_pub_key_revocation();
+
+        match key_idx {
+            0..=SECOND_LAST_KEY_IDX => {
+                cfi_assert_le(cfi_launder(key_idx), SECOND_LAST_KEY_IDX);
+                if (cfi_launder(revocation) & (0x01u32 << key_idx)) != 0 {
+                    Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_REVOKED)?;
+                } else {
+                    cfi_assert_eq(revocation & (0x01u32 << key_idx), 0);
+                }
+            }
+            LAST_KEY_IDX => {
+                cfi_assert_eq(cfi_launder(key_idx), LAST_KEY_IDX);
+                // The last key is never revoked
+            }
+            _ => Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_INDEX_OUT_OF_BOUNDS)?,
+        }
+
+        if cfi_launder(reason) == ResetReason::UpdateReset {
+            let expected = self.env.vendor_mldsa_pub_key_idx_dv();
+            if cfi_launder(expected) != key_idx {
+                Err(
+                    CaliptraError::IMAGE_VERIFIER_ERR_UPDATE_RESET_VENDOR_MLDSA_PUB_KEY_IDX_MISMATCH,
+                )?;
+            } else {
+                cfi_assert_eq(self.env.vendor_mldsa_pub_key_idx_dv(), key_idx);
+            }
+        } else {
+            cfi_assert_ne(reason, ResetReason::UpdateReset);
+        }
+
+        Ok((key_idx, revocation))
+    }
+
+    /// Verify the active vendor ML-DSA public key matches its digest in the
+    /// vendor public key list.
+    fn verify_vendor_mldsa_pk(
+        &mut self,
+        preamble: &ImagePreamble,
+        key_idx: u32,
+    ) -> CaliptraResult<()> {
+        let expected = &preamble
+            .vendor_pub_keys
+            .mldsa_pub_key_digest(key_idx)
+            .ok_or(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_INDEX_OUT_OF_BOUNDS)?;
+
+        let range = ImageManifest::vendor_mldsa_pub_key_range();
+
+        let actual = &self
+            .env
+            .sha384_digest(range.start, range.len() as u32)
+            .map_err(|err| {
+                self.env.set_fw_extended_error(err.into());
+                CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_PUB_KEY_DIGEST_FAILURE
+            })?;
+
+        if cfi_launder(expected) != actual {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_DIGEST_MISMATCH)?;
+        } else {
+            caliptra_cfi_lib::cfi_assert_eq_12_words(expected, actual);
+        }
+
+        Ok(())
+    }
+
+    /// Verify the owner ML-DSA public key matches its digest in the owner
+    /// public keys.
+    fn verify_owner_mldsa_pk(&mut self, preamble: &ImagePreamble) -> CaliptraResult<()> {
+        let expected = &preamble.owner_pub_keys.mldsa_pub_key_digest();
+
+        let range = ImageManifest::owner_mldsa_pub_key_range();
+
+        let actual = &self
+            .env
+            .sha384_digest(range.start, range.len() as u32)
+            .map_err(|err| {
+                self.env.set_fw_extended_error(err.into());
+                CaliptraError::IMAGE_VERIFIER_ERR_OWNER_PUB_KEY_DIGEST_FAILURE
+            })?;
+
+        if cfi_launder(expected) != actual {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_OWNER_MLDSA_PUB_KEY_DIGEST_MISMATCH)?;
+        } else {
+            caliptra_cfi_lib::cfi_assert_eq_12_words(expected, actual);
+        }
+
+        Ok(())
+    }
+
     /// Verify vendor public key digest
     fn verify_vendor_pk_digest(&mut self) -> Result<(), NonZeroU32> {
         // We skip vendor public key check in unprovisioned state
@@ -440,7 +589,20 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             })?;
 
         // Verify vendor signature
-        self.verify_vendor_sig(&digest_vendor, info.vendor_ecc_info, info.vendor_lms_info)?;
+        self.verify_vendor_sig(
+            &digest_vendor,
+            info.vendor_ecc_info,
+            info.vendor_lms_info,
+            info.vendor_mldsa_info,
+        )?;
+
+        // Verify the PQC key type used to verify header signature is encoded
+        // in the header
+        if cfi_launder(header.pqc_key_type) != info.pqc_key_type as u32 {
+            Err(CaliptraError::IMAGE_VERIFIER_ERR_PQC_KEY_TYPE_MISMATCH)?;
+        } else {
+            cfi_assert_eq(header.pqc_key_type, info.pqc_key_type as u32);
+        }
 
         // Verify the ECC public key index used to verify header signature is encoded
         // in the header
@@ -462,6 +624,18 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             cfi_assert!(info.vendor_lms_pub_key_idx.is_none());
         }
 
+        // Verify the ML-DSA public key index used to verify header signature is encoded
+        // in the header
+        if let Some(idx) = cfi_launder(info.vendor_mldsa_pub_key_idx) {
+            if cfi_launder(header.vendor_mldsa_pub_key_idx) != idx {
+                return Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_INDEX_MISMATCH);
+            } else {
+                cfi_assert_eq(header.vendor_mldsa_pub_key_idx, idx);
+            }
+        } else {
+            cfi_assert!(info.vendor_mldsa_pub_key_idx.is_none());
+        }
+
         // Verify owner ECC signature
         let (owner_ecc_pub_key, owner_ecc_sig) = info.owner_ecc_info;
         self.verify_owner_ecc_sig(&digest_owner, owner_ecc_pub_key, owner_ecc_sig)?;
@@ -477,9 +651,21 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             cfi_assert!(info.owner_lms_info.is_none());
         }
 
+        // Verify owner ML-DSA signature
+        if let Some((owner_mldsa_pub_key, owner_mldsa_sig)) = info.owner_mldsa_info {
+            self.verify_owner_mldsa_sig(
+                &digest_owner,
+                cfi_launder(owner_mldsa_pub_key),
+                cfi_launder(owner_mldsa_sig),
+            )?;
+        } else {
+            cfi_assert!(info.owner_mldsa_info.is_none());
+        }
+
         let verif_info = TocInfo {
             len: header.toc_len,
             digest: &header.toc_digest,
+            pqc_key_type: info.pqc_key_type,
         };
 
         Ok(verif_info)
@@ -532,6 +718,7 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         digest: &ImageDigest,
         ecc_info: (&ImageEccPubKey, &ImageEccSignature),
         lms_info: Option<(&ImageLmsPublicKey, &ImageLmsSignature)>,
+        mldsa_info: Option<(&ImageMldsaPubKey, &ImageMldsaSignature)>,
     ) -> CaliptraResult<()> {
         let (ecc_pub_key, ecc_sig) = ecc_info;
         if &ecc_pub_key.x == ZERO_DIGEST || &ecc_pub_key.y == ZERO_DIGEST {
@@ -571,6 +758,23 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
             )
         };
 
+        if let Some((mldsa_pub_key, mldsa_sig)) = mldsa_info {
+            let result = self
+                .env
+                .mldsa87_verify(digest, mldsa_pub_key, mldsa_sig)
+                .map_err(|err| {
+                    self.env.set_fw_extended_error(err.into());
+                    CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_VERIFY_FAILURE
+                })?;
+            if cfi_launder(result as u32) != Mldsa87Result::Success as u32 {
+                return Err(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_SIGNATURE_INVALID);
+            } else {
+                cfi_assert_eq(result as u32, Mldsa87Result::Success as u32);
+            }
+        } else {
+            cfi_assert!(mldsa_info.is_none());
+        }
+
         if cfi_launder(self.env.lms_verify_enabled()) {
             if let Some(info) = lms_info {
                 let (lms_pub_key, lms_sig) = info;
@@ -628,6 +832,30 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         Ok(())
     }
 
+    /// Verify owner ML-DSA Signature
+    fn verify_owner_mldsa_sig(
+        &mut self,
+        digest: &ImageDigest,
+        mldsa_pub_key: &ImageMldsaPubKey,
+        mldsa_sig: &ImageMldsaSignature,
+    ) -> CaliptraResult<()> {
+        let result = self
+            .env
+            .mldsa87_verify(digest, mldsa_pub_key, mldsa_sig)
+            .map_err(|err| {
+                self.env.set_fw_extended_error(err.into());
+                CaliptraError::IMAGE_VERIFIER_ERR_OWNER_MLDSA_VERIFY_FAILURE
+            })?;
+
+        if cfi_launder(result as u32) != Mldsa87Result::Success as u32 {
+            return Err(CaliptraError::IMAGE_VERIFIER_ERR_OWNER_MLDSA_SIGNATURE_INVALID);
+        } else {
+            cfi_assert_eq(result as u32, Mldsa87Result::Success as u32);
+        }
+
+        Ok(())
+    }
+
     /// Verify Table of Contents
     #[cfg_attr(all(not(test), not(feature = "no-cfi")), cfi_impl_fn)]
     fn verify_toc<'a>(
@@ -677,7 +905,12 @@ impl<Env: ImageVerificationEnv> ImageVerifier<Env> {
         }
 
         // Image length does not exceed the Image Bundle size
+        let mldsa_preamble_len = match verify_info.pqc_key_type {
+            ImagePqcKeyType::Mldsa => IMAGE_MLDSA_PREAMBLE_BYTE_SIZE as u64,
+            ImagePqcKeyType::Lms => 0,
+        };
         let img_len: u64 = manifest.size as u64
+            + mldsa_preamble_len
             + manifest.fmc.image_size() as u64
             + manifest.runtime.image_size() as u64;
 
@@ -1015,7 +1248,7 @@ mod tests {
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
 
-        let result = verifier.verify_preamble(&preamble, ResetReason::UpdateReset);
+        let result = verifier.verify_preamble(&preamble, None, ResetReason::UpdateReset);
         assert!(result.is_ok());
     }
 
@@ -1084,7 +1317,7 @@ mod tests {
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
 
-        let result = verifier.verify_preamble(&preamble, ResetReason::UpdateReset);
+        let result = verifier.verify_preamble(&preamble, None, ResetReason::UpdateReset);
         assert!(result.is_ok());
     }
 
@@ -1092,7 +1325,7 @@ mod tests {
     fn test_manifest_marker() {
         let manifest = ImageManifest::default();
         let mut verifier = ImageVerifier::new(TestEnv::default());
-        let result = verifier.verify(&manifest, manifest.size, ResetReason::ColdReset);
+        let result = verifier.verify(&manifest, None, manifest.size, ResetReason::ColdReset);
         assert!(result.is_err());
         assert_eq!(
             result.err(),
@@ -1108,7 +1341,7 @@ mod tests {
             ..Default::default()
         };
         let mut verifier = ImageVerifier::new(TestEnv::default());
-        let result = verifier.verify(&manifest, manifest.size, ResetReason::ColdReset);
+        let result = verifier.verify(&manifest, None, manifest.size, ResetReason::ColdReset);
         assert!(result.is_err());
         assert_eq!(
             result.err(),
@@ -1124,7 +1357,7 @@ mod tests {
             ..Default::default()
         };
         let mut verifier = ImageVerifier::new(test_env);
-        let result = verifier.verify_preamble(&preamble, ResetReason::ColdReset);
+        let result = verifier.verify_preamble(&preamble, None, ResetReason::ColdReset);
         assert!(result.is_err());
         assert_eq!(
             result.err(),
@@ -1144,7 +1377,7 @@ mod tests {
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
 
-        let result = verifier.verify_preamble(&preamble, ResetReason::ColdReset);
+        let result = verifier.verify_preamble(&preamble, None, ResetReason::ColdReset);
         assert!(result.is_ok());
     }
 
@@ -1158,7 +1391,7 @@ mod tests {
         };
         let mut verifier = ImageVerifier::new(test_env);
         let preamble = ImagePreamble::default();
-        let result = verifier.verify_preamble(&preamble, ResetReason::ColdReset);
+        let result = verifier.verify_preamble(&preamble, None, ResetReason::ColdReset);
         assert_eq!(
             result.err(),
             Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_PUB_KEY_DIGEST_MISMATCH)
@@ -1187,6 +1420,11 @@ mod tests {
             owner_pub_keys_digest_in_fuses: false,
             vendor_ecc_pub_key_revocation: Default::default(),
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let result = verifier.verify_header(&header, &header_info);
         assert_eq!(
@@ -1217,6 +1455,11 @@ mod tests {
             owner_pub_keys_digest_in_fuses: false,
             vendor_ecc_pub_key_revocation: Default::default(),
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let result = verifier.verify_header(&header, &header_info);
         assert_eq!(
@@ -1253,6 +1496,11 @@ mod tests {
             owner_pub_keys_digest_in_fuses: false,
             vendor_ecc_pub_key_revocation: Default::default(),
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let result = verifier.verify_header(&header, &header_info);
         assert_eq!(
@@ -1289,6 +1537,11 @@ mod tests {
             owner_pub_keys_digest: ImageDigest::default(),
             owner_pub_keys_digest_in_fuses: false,
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let result = verifier.verify_header(&header, &header_info);
         assert_eq!(
@@ -1323,6 +1576,11 @@ mod tests {
             owner_pub_keys_digest_in_fuses: false,
             vendor_ecc_pub_key_revocation: Default::default(),
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let result = verifier.verify_header(&header, &header_info);
         assert_eq!(
@@ -1357,6 +1615,11 @@ mod tests {
             owner_pub_keys_digest_in_fuses: false,
             vendor_ecc_pub_key_revocation: Default::default(),
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let result = verifier.verify_header(&header, &header_info);
         assert_eq!(
@@ -1391,6 +1654,11 @@ mod tests {
             owner_pub_keys_digest_in_fuses: false,
             vendor_ecc_pub_key_revocation: Default::default(),
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let result = verifier.verify_header(&header, &header_info);
         assert_eq!(
@@ -1424,6 +1692,11 @@ mod tests {
             owner_pub_keys_digest_in_fuses: false,
             vendor_ecc_pub_key_revocation: Default::default(),
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let result = verifier.verify_header(&header, &header_info);
         assert_eq!(
@@ -1460,12 +1733,260 @@ mod tests {
             owner_pub_keys_digest_in_fuses: false,
             vendor_ecc_pub_key_revocation: Default::default(),
             vendor_lms_pub_key_revocation: Default::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
+            vendor_mldsa_pub_key_idx: None,
+            vendor_mldsa_info: None,
+            vendor_mldsa_pub_key_revocation: None,
+            owner_mldsa_info: None,
         };
         let toc_info = verifier.verify_header(&header, &header_info).unwrap();
         assert_eq!(toc_info.len, 100);
         assert_eq!(toc_info.digest, &DUMMY_DATA);
     }
 
+    #[test]
+    fn test_preamble_invalid_pqc_key_type() {
+        let mut verifier = ImageVerifier::new(TestEnv::default());
+        let preamble = ImagePreamble {
+            pqc_key_type: 2,
+            ..Default::default()
+        };
+
+        let result = verifier.verify_preamble(&preamble, None, ResetReason::ColdReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_PQC_KEY_TYPE_INVALID)
+        );
+    }
+
+    #[test]
+    fn test_preamble_mldsa_not_supported() {
+        let test_env = TestEnv {
+            mldsa_verify_enabled: false,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let preamble = ImagePreamble {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            ..Default::default()
+        };
+
+        let result = verifier.verify_preamble(&preamble, None, ResetReason::ColdReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_MLDSA_NOT_SUPPORTED)
+        );
+    }
+
+    #[test]
+    fn test_vendor_mldsa_pk_idx_out_of_bounds() {
+        let mut verifier = ImageVerifier::new(TestEnv::default());
+        let preamble = ImagePreamble {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            vendor_mldsa_pub_key_idx: VENDOR_MLDSA_KEY_COUNT,
+            ..Default::default()
+        };
+
+        let result = verifier.verify_vendor_mldsa_pk_idx(&preamble, ResetReason::ColdReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_INDEX_OUT_OF_BOUNDS)
+        );
+    }
+
+    #[test]
+    fn test_vendor_mldsa_pk_idx_revoked() {
+        let test_env = TestEnv {
+            vendor_mldsa_pub_key_revocation: 0x2,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let preamble = ImagePreamble {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            vendor_mldsa_pub_key_idx: 1,
+            ..Default::default()
+        };
+
+        let result = verifier.verify_vendor_mldsa_pk_idx(&preamble, ResetReason::ColdReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_REVOKED)
+        );
+    }
+
+    #[test]
+    fn test_vendor_mldsa_pk_last_idx_never_revoked() {
+        let test_env = TestEnv {
+            vendor_mldsa_pub_key_revocation: 0xF,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let preamble = ImagePreamble {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            vendor_mldsa_pub_key_idx: VENDOR_MLDSA_KEY_COUNT - 1,
+            ..Default::default()
+        };
+
+        let result = verifier.verify_vendor_mldsa_pk_idx(&preamble, ResetReason::ColdReset);
+        assert_eq!(result.unwrap(), (VENDOR_MLDSA_KEY_COUNT - 1, 0xF));
+    }
+
+    #[test]
+    fn test_vendor_mldsa_pk_digest_mismatch() {
+        let test_env = TestEnv {
+            digest: DUMMY_DATA,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let preamble = ImagePreamble {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            ..Default::default()
+        };
+
+        let result = verifier.verify_vendor_mldsa_pk(&preamble, 0);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_DIGEST_MISMATCH)
+        );
+    }
+
+    #[test]
+    fn test_vendor_mldsa_pk_digest_in_lms_slots() {
+        let test_env = TestEnv {
+            digest: DUMMY_DATA,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let mut preamble = ImagePreamble {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            ..Default::default()
+        };
+        let mut digests = [ImageDigest::default(); VENDOR_MLDSA_KEY_COUNT as usize];
+        digests[2] = DUMMY_DATA;
+        preamble.vendor_pub_keys.set_mldsa_pub_key_digests(&digests);
+
+        assert!(verifier.verify_vendor_mldsa_pk(&preamble, 2).is_ok());
+        assert_eq!(
+            verifier.verify_vendor_mldsa_pk(&preamble, 1).err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_PUB_KEY_DIGEST_MISMATCH)
+        );
+    }
+
+    #[test]
+    fn test_owner_mldsa_pk_digest() {
+        let test_env = TestEnv {
+            digest: DUMMY_DATA,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let mut preamble = ImagePreamble {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            ..Default::default()
+        };
+
+        assert_eq!(
+            verifier.verify_owner_mldsa_pk(&preamble).err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_OWNER_MLDSA_PUB_KEY_DIGEST_MISMATCH)
+        );
+
+        preamble
+            .owner_pub_keys
+            .set_mldsa_pub_key_digest(&DUMMY_DATA);
+        assert!(verifier.verify_owner_mldsa_pk(&preamble).is_ok());
+    }
+
+    #[test]
+    fn test_preamble_mldsa_preamble_missing() {
+        let mut verifier = ImageVerifier::new(TestEnv::default());
+        let preamble = ImagePreamble {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            ..Default::default()
+        };
+
+        let result = verifier.verify_preamble(&preamble, None, ResetReason::ColdReset);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_MLDSA_PREAMBLE_MISSING)
+        );
+    }
+
+    #[test]
+    fn test_header_mldsa_success() {
+        let test_env = TestEnv {
+            verify_result: true,
+            verify_mldsa_result: true,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let header = ImageHeader {
+            toc_len: 100,
+            toc_digest: DUMMY_DATA,
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            vendor_mldsa_pub_key_idx: 1,
+            ..Default::default()
+        };
+        let mldsa_pubkey = ImageMldsaPubKey::default();
+        let mldsa_sig = ImageMldsaSignature::default();
+        let header_info: HeaderInfo = HeaderInfo {
+            vendor_ecc_pub_key_idx: 0,
+            vendor_lms_pub_key_idx: None,
+            vendor_ecc_info: (&VENDOR_ECC_PUBKEY, &VENDOR_ECC_SIG),
+            vendor_lms_info: None,
+            owner_ecc_info: (&OWNER_ECC_PUBKEY, &OWNER_ECC_SIG),
+            owner_lms_info: None,
+            owner_pub_keys_digest: ImageDigest::default(),
+            owner_pub_keys_digest_in_fuses: false,
+            vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_lms_pub_key_revocation: None,
+            pqc_key_type: ImagePqcKeyType::Mldsa,
+            vendor_mldsa_pub_key_idx: Some(1),
+            vendor_mldsa_info: Some((&mldsa_pubkey, &mldsa_sig)),
+            vendor_mldsa_pub_key_revocation: Some(0),
+            owner_mldsa_info: Some((&mldsa_pubkey, &mldsa_sig)),
+        };
+        let toc_info = verifier.verify_header(&header, &header_info).unwrap();
+        assert_eq!(toc_info.len, 100);
+        assert_eq!(toc_info.digest, &DUMMY_DATA);
+    }
+
+    #[test]
+    fn test_header_vendor_mldsa_signature_invalid() {
+        let test_env = TestEnv {
+            verify_result: true,
+            verify_mldsa_result: false,
+            ..Default::default()
+        };
+        let mut verifier = ImageVerifier::new(test_env);
+        let header = ImageHeader {
+            pqc_key_type: ImagePqcKeyType::Mldsa.into(),
+            ..Default::default()
+        };
+        let mldsa_pubkey = ImageMldsaPubKey::default();
+        let mldsa_sig = ImageMldsaSignature::default();
+        let header_info: HeaderInfo = HeaderInfo {
+            vendor_ecc_pub_key_idx: 0,
+            vendor_lms_pub_key_idx: None,
+            vendor_ecc_info: (&VENDOR_ECC_PUBKEY, &VENDOR_ECC_SIG),
+            vendor_lms_info: None,
+            owner_ecc_info: (&OWNER_ECC_PUBKEY, &OWNER_ECC_SIG),
+            owner_lms_info: None,
+            owner_pub_keys_digest: ImageDigest::default(),
+            owner_pub_keys_digest_in_fuses: false,
+            vendor_ecc_pub_key_revocation: Default::default(),
+            vendor_lms_pub_key_revocation: None,
+            pqc_key_type: ImagePqcKeyType::Mldsa,
+            vendor_mldsa_pub_key_idx: Some(0),
+            vendor_mldsa_info: Some((&mldsa_pubkey, &mldsa_sig)),
+            vendor_mldsa_pub_key_revocation: Some(0),
+            owner_mldsa_info: Some((&mldsa_pubkey, &mldsa_sig)),
+        };
+        let result = verifier.verify_header(&header, &header_info);
+        assert_eq!(
+            result.err(),
+            Some(CaliptraError::IMAGE_VERIFIER_ERR_VENDOR_MLDSA_SIGNATURE_INVALID)
+        );
+    }
+
     #[test]
     fn test_toc_incorrect_length() {
         let manifest = ImageManifest::default();
@@ -1474,6 +1995,7 @@ mod tests {
         let toc_info = TocInfo {
             len: MAX_TOC_ENTRY_COUNT / 2,
             digest: &ImageDigest::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
         };
         let result = verifier.verify_toc(&manifest, &toc_info, manifest.size);
         assert_eq!(
@@ -1490,6 +2012,7 @@ mod tests {
         let toc_info = TocInfo {
             len: MAX_TOC_ENTRY_COUNT,
             digest: &DUMMY_DATA,
+            pqc_key_type: ImagePqcKeyType::Lms,
         };
         let result = verifier.verify_toc(&manifest, &toc_info, manifest.size);
         assert_eq!(
@@ -1506,6 +2029,7 @@ mod tests {
         let toc_info = TocInfo {
             len: MAX_TOC_ENTRY_COUNT,
             digest: &ImageDigest::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
         };
 
         // Case 0:
@@ -1636,6 +2160,7 @@ mod tests {
         let toc_info = TocInfo {
             len: MAX_TOC_ENTRY_COUNT,
             digest: &ImageDigest::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
         };
 
         // FMC size == 0
@@ -1681,6 +2206,7 @@ mod tests {
         let toc_info = TocInfo {
             len: MAX_TOC_ENTRY_COUNT,
             digest: &ImageDigest::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
         };
 
         // [-FMC--]
@@ -1708,6 +2234,7 @@ mod tests {
         let toc_info = TocInfo {
             len: MAX_TOC_ENTRY_COUNT,
             digest: &ImageDigest::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
         };
 
         // [-FMC--]
@@ -1735,6 +2262,7 @@ mod tests {
         let toc_info = TocInfo {
             len: MAX_TOC_ENTRY_COUNT,
             digest: &ImageDigest::default(),
+            pqc_key_type: ImagePqcKeyType::Lms,
         };
 
         manifest.fmc.offset = 0;
@@ -1910,9 +2438,12 @@ mod tests {
         fmc_digest: ImageDigest,
         verify_result: bool,
         verify_lms_result: bool,
+        verify_mldsa_result: bool,
+        mldsa_verify_enabled: bool,
         vendor_pub_key_digest: ImageDigest,
         vendor_ecc_pub_key_revocation: VendorPubKeyRevocation,
         vendor_lms_pub_key_revocation: u32,
+        vendor_mldsa_pub_key_revocation: u32,
         owner_pub_key_digest: ImageDigest,
         lifecycle: Lifecycle,
     }
@@ -1924,9 +2455,12 @@ mod tests {
                 fmc_digest: ImageDigest::default(),
                 verify_result: false,
                 verify_lms_result: false,
+                verify_mldsa_result: false,
+                mldsa_verify_enabled: true,
                 vendor_pub_key_digest: ImageDigest::default(),
                 vendor_ecc_pub_key_revocation: VendorPubKeyRevocation::default(),
                 vendor_lms_pub_key_revocation: 0,
+                vendor_mldsa_pub_key_revocation: 0,
                 owner_pub_key_digest: ImageDigest::default(),
                 lifecycle: Lifecycle::Unprovisioned,
             }
@@ -1964,6 +2498,19 @@ mod tests {
             }
         }
 
+        fn mldsa87_verify(
+            &mut self,
+            _digest: &ImageDigest,
+            _pub_key: &ImageMldsaPubKey,
+            _sig: &ImageMldsaSignature,
+        ) -> CaliptraResult<Mldsa87Result> {
+            if self.verify_mldsa_result {
+                Ok(Mldsa87Result::Success)
+            } else {
+                Ok(Mldsa87Result::SigVerifyFailed)
+            }
+        }
+
         fn vendor_pub_key_digest(&self) -> ImageDigest {
             self.vendor_pub_key_digest
         }
@@ -1976,6 +2523,10 @@ mod tests {
             self.vendor_lms_pub_key_revocation
         }
 
+        fn vendor_mldsa_pub_key_revocation(&self) -> u32 {
+            self.vendor_mldsa_pub_key_revocation
+        }
+
         fn owner_pub_key_digest_fuses(&self) -> ImageDigest {
             self.owner_pub_key_digest
         }
@@ -1996,6 +2547,10 @@ mod tests {
             0
         }
 
+        fn vendor_mldsa_pub_key_idx_dv(&self) -> u32 {
+            0
+        }
+
         fn owner_pub_key_digest_dv(&self) -> ImageDigest {
             self.owner_pub_key_digest
         }
@@ -2023,6 +2578,10 @@ mod tests {
             true
         }
 
+        fn mldsa_verify_enabled(&self) -> bool {
+            self.mldsa_verify_enabled
+        }
+
         fn set_fw_extended_error(&mut self, _err: u32) {}
     }
 }
@@ -0,0 +1,64 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    mldsa87_kat.rs
+
+Abstract:
+
+    File contains the Known Answer Tests (KAT) for ML-DSA-87 cryptography operations.
+
+--*/
+
+use caliptra_drivers::{
+    CaliptraError, CaliptraResult, Mldsa87, Mldsa87Msg, Mldsa87Result, Mldsa87Signature, Trng,
+};
+
+#[derive(Default, Debug)]
+pub struct Mldsa87Kat {}
+
+impl Mldsa87Kat {
+    /// This function executes the Known Answer Tests (aka KAT) for ML-DSA-87.
+    ///
+    /// Test vector source:
+    /// Zeroed seed and message. The pairwise consistency signature must verify
+    /// and the same signature must be rejected for a different message.
+    ///
+    /// # Arguments
+    ///
+    /// * `mldsa87` - ML-DSA-87 Driver
+    /// * `trng` - TRNG Driver
+    ///
+    /// # Returns
+    ///
+    /// * `CaliptraResult` - Result denoting the KAT outcome.
+    pub fn execute(&self, mldsa87: &mut Mldsa87, trng: &mut Trng) -> CaliptraResult<()> {
+        self.kat_key_pair_gen_sign_and_verify(mldsa87, trng)
+    }
+
+    fn kat_key_pair_gen_sign_and_verify(
+        &self,
+        mldsa87: &mut Mldsa87,
+        trng: &mut Trng,
+    ) -> CaliptraResult<()> {
+        let mut pct_sig = Mldsa87Signature::default();
+
+        // NOTE: Signature verify step is performed in ML-DSA-87 driver sign function
+        let pub_key = mldsa87
+            .key_pair_for_fips_kat(trng, &mut pct_sig)
+            .map_err(|_| CaliptraError::KAT_MLDSA87_KEY_PAIR_GENERATE_FAILURE)?;
+
+        let mut msg = Mldsa87Msg::default();
+        msg.0[0] = 1;
+        let result = mldsa87
+            .verify(&pub_key, &msg, &pct_sig)
+            .map_err(|_| CaliptraError::KAT_MLDSA87_SIGNATURE_VERIFY_FAILURE)?;
+        if result != Mldsa87Result::SigVerifyFailed {
+            Err(CaliptraError::KAT_MLDSA87_SIGNATURE_VERIFY_FAILURE)?;
+        }
+
+        Ok(())
+    }
+}
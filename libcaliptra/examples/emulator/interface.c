@@ -0,0 +1,170 @@
+//Licensed under the Apache-2.0 license
+
+#include <assert.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdio.h>
+#include <stdlib.h>
+#include <string.h>
+#include <unistd.h>
+
+#include "caliptra_model.h"
+#include "test.h"
+
+#define CALIPTRA_STATUS_OK 0
+
+// Address of a running caliptra-emu control interface, either "host:port"
+// or "unix:<path>". When unset, the emulator runs in-process.
+#define CALIPTRA_EMU_ADDR_ENV "CALIPTRA_EMU_ADDR"
+
+// Time to let a remote emulator run between register polls
+#define CALIPTRA_EMU_WAIT_US 100
+
+// Implementation specifics
+
+struct caliptra_model *model = NULL;
+struct caliptra_emu *emu = NULL;
+
+static struct caliptra_model_init_params init_params;
+
+static const char *emu_addr(void)
+{
+    const char *addr = getenv(CALIPTRA_EMU_ADDR_ENV);
+
+    return (addr != NULL && addr[0] != '\0') ? addr : NULL;
+}
+
+// ONLY for testing. Not part of actual libcaliptra interface
+void testbench_reinit(void)
+{
+    if (model) {
+        caliptra_model_destroy(model);
+        model = NULL;
+    }
+
+    // The remote emulator outlives the test, so power cycle it instead
+    if (emu) {
+        caliptra_emu_cold_reset(emu);
+        caliptra_emu_set_apb_pauser(emu, init_params.soc_user);
+    }
+}
+
+void hwmod_init(struct caliptra_buffer rom, const test_info* info) {
+    assert(info != NULL);
+
+  // slice::from_raw_parts can panic when the pointer is NULL
+    uint8_t empty[0];
+    struct caliptra_model_init_params params = {
+        .rom = rom,
+        .dccm = {.data = empty, .len = 0},
+        .iccm = {.data = empty, .len = 0},
+        .security_state = CALIPTRA_SEC_STATE_DBG_LOCKED_MANUFACTURING,
+        .soc_user = info->apb_pauser,
+    };
+    init_params = params;
+
+    if (emu) {
+        caliptra_emu_set_apb_pauser(emu, init_params.soc_user);
+    }
+}
+
+static struct caliptra_emu* emu_get_or_connect(void)
+{
+    if (emu == NULL)
+    {
+        const char *addr = emu_addr();
+
+        if (addr == NULL) {
+            return NULL;
+        }
+
+        // The remote emulator loads its own ROM, so only the pauser is sent
+        if (caliptra_emu_connect(addr, &emu) != CALIPTRA_EMU_STATUS_OK ||
+            caliptra_emu_set_apb_pauser(emu, init_params.soc_user) != CALIPTRA_EMU_STATUS_OK) {
+            fprintf(stderr, "Unable to use caliptra-emu at %s\n", addr);
+            exit(EXIT_FAILURE);
+        }
+    }
+
+    return emu;
+}
+
+struct caliptra_model* hwmod_get_or_init(void)
+{
+    if (model == NULL)
+    {
+        int status = caliptra_model_init_default(init_params, &model);
+
+        if (status != CALIPTRA_STATUS_OK) {
+            return NULL;
+        }
+    }
+
+    return model;
+}
+
+// Memory
+
+/**
+ * caliptra_write_u32
+ *
+ * Writes a uint32_t value to the specified address.
+ *
+ * @param[in] address Memory address to write
+ * @param[in] data Data to write at address
+ *
+ * @return 0 if successful, other if error (TBD)
+ */
+int caliptra_write_u32(uint32_t address, uint32_t data)
+{
+    struct caliptra_emu *e = emu_get_or_connect();
+
+    if (e) {
+        return caliptra_emu_apb_write_u32(e, address, data);
+    }
+
+    struct caliptra_model *m = hwmod_get_or_init();
+
+    int result = caliptra_model_apb_write_u32(m, address, (int)data);
+
+    caliptra_model_step(m);
+
+    return result;
+}
+
+/**
+ * caliptra_read_u32
+ *
+ * Reads a uint32_t value from the specified address.
+ *
+ * @param[in] address Memory address to read
+ * @param[in] data Pointer to a uint32_t to store the data
+ *
+ * @return 0 if successful, other if error (TBD)
+ */
+int caliptra_read_u32(uint32_t address, uint32_t *data)
+{
+    struct caliptra_emu *e = emu_get_or_connect();
+
+    if (e) {
+        return caliptra_emu_apb_read_u32(e, address, (uint*)data);
+    }
+
+    return caliptra_model_apb_read_u32(hwmod_get_or_init(), address, (uint*)data);
+}
+
+/**
+ * caliptra_wait
+ *
+ * Pend the current operation.
+ */
+void caliptra_wait(void)
+{
+    // A remote emulator runs on its own between requests
+    if (emu_get_or_connect()) {
+        usleep(CALIPTRA_EMU_WAIT_US);
+        return;
+    }
+
+    caliptra_model_step(hwmod_get_or_init());
+}
@@ -13,6 +13,11 @@
 #define IMAGE_LMS_OTS_P_PARAM   51
 #define IMAGE_LMS_KEY_HEIGHT    15
 #define IMAGE_BYTE_SIZE         (128 * 1024)
+#define MLDSA87_PUB_KEY_WORD_SIZE   648
+#define MLDSA87_SIGNATURE_WORD_SIZE 1157
+
+// "MAN2": manifest format with ML-DSA-87 keys and the owner DPE configuration
+#define MANIFEST_MARKER 0x324E414D
 
 struct ecc_pub_key {
     uint32_t x[ECC384_SCALAR_WORD_SIZE];
@@ -44,53 +49,75 @@ struct image_lms_signature {
     uint32_t               tree_path[SHA192_DIGEST_WORD_SIZE][IMAGE_LMS_KEY_HEIGHT];
 };
 
+struct mldsa_pub_key {
+    uint32_t data[MLDSA87_PUB_KEY_WORD_SIZE];
+};
+
+struct image_mldsa_signature {
+    uint32_t data[MLDSA87_SIGNATURE_WORD_SIZE];
+};
+
 struct image_vendor_pubkeys {
     struct ecc_pub_key ecc_pub_keys[4];
     struct lms_pub_key lms_pub_keys[32];
+    uint32_t           mldsa_pub_key_digests[4][SHA384_DIGEST_WORD_SIZE];
 };
 
-struct image_vendor_signatures {
-    struct image_ecc_signature ecc_signature;
-    struct image_lms_signature lms_signature;
+struct image_signatures {
+    struct image_ecc_signature   ecc_signature;
+    struct image_lms_signature   lms_signature;
+    struct image_mldsa_signature mldsa_signature;
 };
 
 struct image_owner_pubkeys {
-    struct ecc_pub_key ecc_pub_key;
-    struct lms_pub_key lms_pub_key;
-};
-
-struct image_owner_signatures {
-    struct image_ecc_signature ecc_signature;
-    struct image_lms_signature lms_signature;
+    struct ecc_pub_key   ecc_pub_key;
+    struct lms_pub_key   lms_pub_key;
+    struct mldsa_pub_key mldsa_pub_key;
 };
 
 struct image_vendor_data {
     uint8_t not_before[15];
     uint8_t not_after[15];
-    uint8_t reserved[8];
+    uint8_t reserved[10];
+};
+
+struct image_dpe_config {
+    uint32_t flags;
+    uint32_t vendor_id;
+    uint32_t vendor_sku;
+    uint32_t pl0_context_limit;
+    uint32_t pl1_context_limit;
+    uint32_t support;
+    uint32_t allowed_commands;
 };
 
 struct image_owner_data {
-    uint8_t not_before[15];
-    uint8_t not_after[15];
-    uint8_t epoch[2];
-    uint8_t reserved[8];
+    uint8_t                 not_before[15];
+    uint8_t                 not_after[15];
+    uint8_t                 epoch[2];
+    struct image_dpe_config dpe_config;
+    uint8_t                 reserved[8];
 };
 
 struct caliptra_preamble {
-    struct image_vendor_pubkeys    vendor_pub_keys;
-    uint32_t                       vendor_ecc_key_index;
-    uint32_t                       vendor_lms_key_index;
-    struct image_vendor_signatures vendor_sigs;
-    struct image_owner_pubkeys     owner_pub_keys;
-    struct image_owner_signatures  owner_sigs;
-    uint32_t                       reserved[2];
+    struct image_vendor_pubkeys vendor_pub_keys;
+    uint32_t                    vendor_ecc_key_index;
+    uint32_t                    vendor_lms_key_index;
+    uint32_t                    pqc_key_type;
+    uint32_t                    vendor_mldsa_key_index;
+    struct mldsa_pub_key        vendor_mldsa_pub_key;
+    struct image_signatures     vendor_sigs;
+    struct image_owner_pubkeys  owner_pub_keys;
+    struct image_signatures     owner_sigs;
+    uint32_t                    reserved[2];
 };
 
 struct caliptra_header {
     uint64_t revision;
     uint32_t vendor_ecc_pub_key_id;
     uint32_t vendor_lms_pub_key_id;
+    uint32_t pqc_key_type;
+    uint32_t vendor_mldsa_pub_key_id;
     uint32_t flags;
     uint32_t toc_entry_count;
     uint32_t pl0_pauser;
@@ -113,7 +140,7 @@ struct caliptra_toc {
 };
 
 struct caliptra_image_manifest {
-    uint32_t       marker; // "CMAN"
+    uint32_t       marker; // MANIFEST_MARKER
     uint32_t       size;
     struct caliptra_preamble  preamble;
     struct caliptra_header    header;
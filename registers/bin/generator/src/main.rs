@@ -38,6 +38,7 @@ static CALIPTRA_RDL_FILES: &[&str] = &[
     "src/soc_ifc/rtl/soc_ifc_reg.rdl",
     "src/soc_ifc/rtl/sha512_acc_csr.rdl",
     "src/uart/data/uart.rdl",
+    "src/adams-bridge/src/mldsa_top/rtl/mldsa_reg.rdl",
     "src/integration/rtl/caliptra_reg.rdl",
 ];
 
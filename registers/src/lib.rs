@@ -7,3 +7,8 @@ pub use caliptra_registers_latest::*;
 
 #[cfg(feature = "hw-1.0")]
 pub use caliptra_registers_1_0::*;
+
+// Caliptra 1.0 has no ML-DSA-87 engine. Drivers shared with 2.x still build
+// against the 2.x registers, but firmware only accesses them on 2.x.
+#[cfg(feature = "hw-1.0")]
+pub use caliptra_registers_latest::mldsa;
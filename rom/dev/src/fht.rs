@@ -15,7 +15,7 @@ Abstract:
 use crate::{rom_env::RomEnv, CALIPTRA_ROM_INFO};
 use caliptra_cfi_derive::cfi_mod_fn;
 use caliptra_common::{
-    keyids::{KEY_ID_FMC_PRIV_KEY, KEY_ID_ROM_FMC_CDI},
+    keyids::{KEY_ID_FMC_MLDSA_KEYPAIR_SEED, KEY_ID_FMC_PRIV_KEY, KEY_ID_ROM_FMC_CDI},
     DataVaultRegister, FirmwareHandoffTable, HandOffDataHandle, Vault, FHT_INVALID_HANDLE,
     FHT_MARKER,
 };
@@ -38,6 +38,10 @@ impl FhtDataStore {
     pub const fn fmc_priv_key_store() -> HandOffDataHandle {
         HandOffDataHandle(((Vault::KeyVault as u32) << 12) | KEY_ID_FMC_PRIV_KEY as u32)
     }
+    /// The FMC ML-DSA-87 key pair seed is stored in a Key Vault slot.
+    pub const fn fmc_mldsa_keypair_seed_store() -> HandOffDataHandle {
+        HandOffDataHandle(((Vault::KeyVault as u32) << 12) | KEY_ID_FMC_MLDSA_KEYPAIR_SEED as u32)
+    }
     /// The FMC SVN is stored in a 32-bit DataVault sticky register.
     pub const fn fmc_svn_store() -> HandOffDataHandle {
         HandOffDataHandle(
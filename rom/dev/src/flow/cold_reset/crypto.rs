@@ -226,4 +226,53 @@ impl Crypto {
         digest.0.zeroize();
         result
     }
+
+    /// Generate ML-DSA-87 Key Pair
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - ROM Environment
+    /// * `seed` - Key slot to retrieve the key pair seed from
+    ///
+    /// # Returns
+    ///
+    /// * `Mldsa87PubKey` - Public key
+    #[inline(always)]
+    pub fn mldsa87_key_gen(env: &mut RomEnv, seed: KeyId) -> CaliptraResult<Mldsa87PubKey> {
+        env.mldsa87
+            .key_pair(&KeyReadArgs::new(seed).into(), &mut env.trng)
+    }
+
+    /// Sign the data using the ML-DSA-87 key pair derived from `seed`.
+    /// Verify the signature using the ML-DSA-87 Public Key.
+    ///
+    /// The data is signed with pure ML-DSA-87, without pre-hashing.
+    ///
+    /// # Arguments
+    ///
+    /// * `mldsa87` - ML-DSA-87 driver
+    /// * `trng` - TRNG driver
+    /// * `seed` - Key slot to retrieve the key pair seed from
+    /// * `pub_key` - Public key of the key pair
+    /// * `data` - Data to sign
+    ///
+    /// # Returns
+    ///
+    /// * `Mldsa87Signature` - Signature
+    #[inline(always)]
+    pub fn mldsa87_sign_and_verify(
+        mldsa87: &mut Mldsa87,
+        trng: &mut Trng,
+        seed: KeyId,
+        pub_key: &Mldsa87PubKey,
+        data: &[u8],
+    ) -> CaliptraResult<Mldsa87Signature> {
+        mldsa87.sign_var(
+            &KeyReadArgs::new(seed).into(),
+            pub_key,
+            data,
+            &Mldsa87SignRnd::default(),
+            trng,
+        )
+    }
 }
@@ -18,17 +18,26 @@ use super::dice::{DiceInput, DiceOutput};
 use super::fw_processor::FwProcInfo;
 use super::x509::X509;
 use crate::cprintln;
+use crate::fht::FhtDataStore;
 use crate::flow::cold_reset::{copy_tbs, TbsType};
 use crate::print::HexBytes;
 use crate::rom_env::RomEnv;
 use caliptra_cfi_derive::cfi_impl_fn;
 use caliptra_cfi_lib::{cfi_assert, cfi_assert_bool, cfi_launder};
 use caliptra_common::dice;
-use caliptra_common::keyids::{KEY_ID_FMC_PRIV_KEY, KEY_ID_ROM_FMC_CDI};
+use caliptra_common::keyids::{
+    KEY_ID_FMC_MLDSA_KEYPAIR_SEED, KEY_ID_FMC_PRIV_KEY, KEY_ID_LDEVID_MLDSA_KEYPAIR_SEED,
+    KEY_ID_ROM_FMC_CDI,
+};
 use caliptra_common::pcr::PCR_ID_FMC_CURRENT;
 use caliptra_common::RomBootStatus::*;
-use caliptra_drivers::{okmutref, report_boot_status, Array4x12, CaliptraResult, KeyId, Lifecycle};
-use caliptra_x509::{FmcAliasCertTbs, FmcAliasCertTbsParams};
+use caliptra_drivers::{
+    okmutref, okref, report_boot_status, Array4x12, CaliptraError, CaliptraResult, KeyId, Lifecycle,
+};
+use caliptra_x509::{
+    FmcAliasCertTbs, FmcAliasCertTbsMlDsa87, FmcAliasCertTbsMlDsa87Params, FmcAliasCertTbsParams,
+};
+use zerocopy::IntoBytes;
 use zeroize::Zeroize;
 
 #[derive(Default)]
@@ -83,6 +92,27 @@ impl FmcAliasLayer {
         output.zeroize();
         result?;
 
+        // Generate the ML-DSA-87 FMC Alias Certificate for ML-DSA signed images
+        if fw_proc_info.mldsa_cert_chain {
+            Crypto::hmac384_kdf(
+                env,
+                KEY_ID_ROM_FMC_CDI,
+                b"fmc_alias_mldsa_keygen",
+                None,
+                KEY_ID_FMC_MLDSA_KEYPAIR_SEED,
+            )?;
+            Self::generate_mldsa87_cert_sig(
+                env,
+                KEY_ID_LDEVID_MLDSA_KEYPAIR_SEED,
+                KEY_ID_FMC_MLDSA_KEYPAIR_SEED,
+                fw_proc_info,
+            )?;
+            env.persistent_data
+                .get_mut()
+                .fht
+                .fmc_mldsa_keypair_seed_kv_hdl = FhtDataStore::fmc_mldsa_keypair_seed_store();
+        }
+
         report_boot_status(FmcAliasDerivationComplete.into());
         cprintln!("[afmc] --");
 
@@ -156,22 +186,7 @@ impl FmcAliasLayer {
         let svn = env.data_vault.fmc_svn() as u8;
         let fuse_svn = fw_proc_info.fmc_effective_fuse_svn as u8;
 
-        let mut fuse_info_digest = Array4x12::default();
-        let mut hasher = env.sha384.digest_init()?;
-        hasher.update(&[
-            env.soc_ifc.lifecycle() as u8,
-            env.soc_ifc.debug_locked() as u8,
-            env.soc_ifc.fuse_bank().anti_rollback_disable() as u8,
-            env.data_vault.ecc_vendor_pk_index() as u8,
-            env.data_vault.lms_vendor_pk_index() as u8,
-            env.soc_ifc.fuse_bank().lms_verify() as u8,
-            fw_proc_info.owner_pub_keys_digest_in_fuses as u8,
-        ])?;
-        hasher.update(&<[u8; 48]>::from(
-            env.soc_ifc.fuse_bank().vendor_pub_key_hash(),
-        ))?;
-        hasher.update(&<[u8; 48]>::from(env.data_vault.owner_pk_hash()))?;
-        hasher.finalize(&mut fuse_info_digest)?;
+        let fuse_info_digest = Self::fuse_info_digest(env, fw_proc_info)?;
 
         // Certificate `To Be Signed` Parameters
         let params = FmcAliasCertTbsParams {
@@ -233,6 +248,121 @@ impl FmcAliasLayer {
         Ok(())
     }
 
+    /// Generate FMC Alias ML-DSA-87 Certificate Signature
+    ///
+    /// # Arguments
+    ///
+    /// * `env`          - ROM Environment
+    /// * `auth_seed`    - Key slot holding the authority ML-DSA-87 key pair seed
+    /// * `seed`         - Key slot holding the subject ML-DSA-87 key pair seed
+    /// * `fw_proc_info` - Firmware processing information
+    #[inline(never)]
+    fn generate_mldsa87_cert_sig(
+        env: &mut RomEnv,
+        auth_seed: KeyId,
+        seed: KeyId,
+        fw_proc_info: &FwProcInfo,
+    ) -> CaliptraResult<()> {
+        cprintln!("[afmc] ML-DSA-87 SUBJECT.KEYID = {}", seed as u8);
+        cprintln!("[afmc] ML-DSA-87 AUTHORITY.KEYID = {}", auth_seed as u8);
+
+        let auth_pub_key = Crypto::mldsa87_key_gen(env, auth_seed);
+        let auth_pub_key = okref(&auth_pub_key)?;
+        let issuer_sn = X509::mldsa87_subj_sn(env, auth_pub_key)?;
+        let authority_key_id = X509::mldsa87_subj_key_id(env, auth_pub_key)?;
+
+        {
+            let pub_key = Crypto::mldsa87_key_gen(env, seed);
+            let pub_key = okref(&pub_key)?;
+
+            let flags = Self::make_flags(env.soc_ifc.lifecycle(), env.soc_ifc.debug_locked());
+            let svn = env.data_vault.fmc_svn() as u8;
+            let fuse_svn = fw_proc_info.fmc_effective_fuse_svn as u8;
+            let fuse_info_digest = Self::fuse_info_digest(env, fw_proc_info)?;
+
+            // Certificate `To Be Signed` Parameters
+            let params = FmcAliasCertTbsMlDsa87Params {
+                ueid: &X509::ueid(env)?,
+                subject_sn: &X509::mldsa87_subj_sn(env, pub_key)?,
+                subject_key_id: &X509::mldsa87_subj_key_id(env, pub_key)?,
+                issuer_sn: &issuer_sn,
+                authority_key_id: &authority_key_id,
+                serial_number: &X509::mldsa87_cert_sn(env, pub_key)?,
+                public_key: pub_key.0.as_bytes().try_into().unwrap(),
+                tcb_info_fmc_tci: &(&env.data_vault.fmc_tci()).into(),
+                tcb_info_device_info_hash: &fuse_info_digest.into(),
+                tcb_info_flags: &flags,
+                tcb_info_fmc_svn: &svn.to_be_bytes(),
+                tcb_info_fmc_svn_fuses: &fuse_svn.to_be_bytes(),
+                not_before: &fw_proc_info.fmc_cert_valid_not_before.value,
+                not_after: &fw_proc_info.fmc_cert_valid_not_after.value,
+            };
+
+            // Generate the `To Be Signed` portion of the certificate and
+            // copy it to DCCM.
+            let tbs = FmcAliasCertTbsMlDsa87::new(&params);
+            copy_tbs(tbs.tbs(), TbsType::FmcaliasMldsaTbs, env)?;
+        }
+
+        // Sign the `To Be Signed` portion
+        cprintln!(
+            "[afmc] Signing ML-DSA-87 Cert w/ AUTHORITY.KEYID = {}",
+            auth_seed as u8
+        );
+        let tbs = env
+            .persistent_data
+            .pqc()
+            .fmcalias_mldsa_cert
+            .tbs()
+            .ok_or(CaliptraError::ROM_GLOBAL_UNSUPPORTED_FMCALIAS_MLDSA_TBS_SIZE)?;
+        let sig = Crypto::mldsa87_sign_and_verify(
+            &mut env.mldsa87,
+            &mut env.trng,
+            auth_seed,
+            auth_pub_key,
+            tbs,
+        );
+        let sig = okref(&sig)?;
+
+        // Clear the authority key pair seed
+        cprintln!(
+            "[afmc] Erase ML-DSA-87 AUTHORITY.KEYID = {}",
+            auth_seed as u8
+        );
+        env.key_vault.erase_key(auth_seed)?;
+
+        env.persistent_data.pqc_mut().fmcalias_mldsa_cert.sig = *sig;
+
+        Ok(())
+    }
+
+    /// Compute the digest of the fuse configuration reported in the
+    /// FMC Alias certificate TCB info.
+    ///
+    /// # Arguments
+    ///
+    /// * `env`          - ROM Environment
+    /// * `fw_proc_info` - Firmware processing information
+    fn fuse_info_digest(env: &mut RomEnv, fw_proc_info: &FwProcInfo) -> CaliptraResult<Array4x12> {
+        let mut fuse_info_digest = Array4x12::default();
+        let mut hasher = env.sha384.digest_init()?;
+        hasher.update(&[
+            env.soc_ifc.lifecycle() as u8,
+            env.soc_ifc.debug_locked() as u8,
+            env.soc_ifc.fuse_bank().anti_rollback_disable() as u8,
+            env.data_vault.ecc_vendor_pk_index() as u8,
+            env.data_vault.lms_vendor_pk_index() as u8,
+            env.soc_ifc.fuse_bank().lms_verify() as u8,
+            fw_proc_info.owner_pub_keys_digest_in_fuses as u8,
+        ])?;
+        hasher.update(&<[u8; 48]>::from(
+            env.soc_ifc.fuse_bank().vendor_pub_key_hash(),
+        ))?;
+        hasher.update(&<[u8; 48]>::from(env.data_vault.owner_pk_hash()))?;
+        hasher.finalize(&mut fuse_info_digest)?;
+        Ok(fuse_info_digest)
+    }
+
     /// Generate flags for DICE evidence
     ///
     /// # Arguments
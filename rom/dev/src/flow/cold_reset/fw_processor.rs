@@ -32,9 +32,9 @@ use caliptra_common::PcrLogEntryId;
 use caliptra_common::{FuseLogEntryId, RomBootStatus::*};
 use caliptra_drivers::pcr_log::MeasurementLogEntry;
 use caliptra_drivers::*;
-use caliptra_image_types::{ImageManifest, IMAGE_BYTE_SIZE};
+use caliptra_image_types::{ImageManifest, ImagePqcKeyType, IMAGE_BYTE_SIZE};
 use caliptra_image_verify::{ImageVerificationInfo, ImageVerificationLogInfo, ImageVerifier};
-use caliptra_kat::KatsEnv;
+use caliptra_kat::{KatsEnv, Mldsa87Kat};
 use caliptra_x509::{NotAfter, NotBefore};
 use core::mem::ManuallyDrop;
 use zerocopy::{FromBytes, IntoBytes};
@@ -51,6 +51,8 @@ pub struct FwProcInfo {
     pub fmc_effective_fuse_svn: u32,
 
     pub owner_pub_keys_digest_in_fuses: bool,
+
+    pub mldsa_cert_chain: bool,
 }
 
 pub struct FirmwareProcessor {}
@@ -105,11 +107,22 @@ impl FirmwareProcessor {
         let manifest = Self::load_manifest(&mut env.persistent_data, &mut txn);
         let manifest = okref(&manifest)?;
 
+        // The ML-DSA-87 engine is only used for ML-DSA signed images, so its
+        // KAT runs before first use rather than with the other KATs. Without
+        // the engine, verification rejects the image instead.
+        if cfg!(feature = "hw-2.x")
+            && manifest.preamble.pqc_key_type == ImagePqcKeyType::Mldsa as u32
+        {
+            cprintln!("[kat] ML-DSA-87");
+            Mldsa87Kat::default().execute(&mut env.mldsa87, &mut env.trng)?;
+        }
+
         let mut venv = FirmwareImageVerificationEnv {
             sha256: &mut env.sha256,
             sha384: &mut env.sha384,
             soc_ifc: &mut env.soc_ifc,
             ecc384: &mut env.ecc384,
+            mldsa87: &mut env.mldsa87,
             data_vault: &mut env.data_vault,
             pcr_bank: &mut env.pcr_bank,
             image: txn.raw_mailbox_contents(),
@@ -129,7 +142,7 @@ impl FirmwareProcessor {
         report_boot_status(FwProcessorExtendPcrComplete.into());
 
         // Load the image
-        Self::load_image(manifest, &mut txn)?;
+        Self::load_image(manifest, &mut txn, &mut env.persistent_data)?;
 
         // Complete the mailbox transaction indicating success.
         txn.complete(true)?;
@@ -149,6 +162,9 @@ impl FirmwareProcessor {
             fmc_cert_valid_not_after: nf,
             fmc_effective_fuse_svn: info.fmc.effective_fuse_svn,
             owner_pub_keys_digest_in_fuses: info.owner_pub_keys_digest_in_fuses,
+            // The ML-DSA-87 certificates live in the Caliptra 2.x DCCM.
+            mldsa_cert_chain: cfg!(feature = "hw-2.x")
+                && manifest.preamble.pqc_key_type == ImagePqcKeyType::Mldsa as u32,
         })
     }
 
@@ -375,6 +391,7 @@ impl FirmwareProcessor {
             soc_ifc: venv.soc_ifc,
             data_vault: venv.data_vault,
             ecc384: venv.ecc384,
+            mldsa87: venv.mldsa87,
             image: venv.image,
         };
 
@@ -384,8 +401,18 @@ impl FirmwareProcessor {
         CfiCounter::delay();
         CfiCounter::delay();
 
+        let mldsa_preamble = venv
+            .image
+            .get(..img_bundle_sz as usize)
+            .and_then(|image| manifest.mldsa_preamble(image));
+
         let mut verifier = ImageVerifier::new(venv);
-        let info = verifier.verify(manifest, img_bundle_sz, ResetReason::ColdReset)?;
+        let info = verifier.verify(
+            manifest,
+            mldsa_preamble,
+            img_bundle_sz,
+            ResetReason::ColdReset,
+        )?;
 
         cprintln!(
             "[fwproc] Img verified w/ Vendor ECC Key Idx {}",
@@ -486,6 +513,26 @@ impl FirmwareProcessor {
             )?;
         }
 
+        // Log VendorMldsaPubKeyIndex
+        if let Some(vendor_mldsa_pub_key_idx) = log_info.vendor_mldsa_pub_key_idx {
+            log_fuse_data(
+                log,
+                FuseLogEntryId::VendorMldsaPubKeyIndex,
+                vendor_mldsa_pub_key_idx.as_bytes(),
+            )?;
+        }
+
+        // Log VendorMldsaPubKeyRevocation
+        if let Some(fuse_vendor_mldsa_pub_key_revocation) =
+            log_info.fuse_vendor_mldsa_pub_key_revocation
+        {
+            log_fuse_data(
+                log,
+                FuseLogEntryId::VendorMldsaPubKeyRevocation,
+                fuse_vendor_mldsa_pub_key_revocation.as_bytes(),
+            )?;
+        }
+
         Ok(())
     }
 
@@ -493,13 +540,23 @@ impl FirmwareProcessor {
     ///
     /// # Arguments
     ///
-    /// * `env`      - ROM Environment
-    /// * `manifest` - Manifest
-    /// * `txn`      - Mailbox Receive Transaction
+    /// * `manifest`        - Manifest
+    /// * `txn`             - Mailbox Receive Transaction
+    /// * `persistent_data` - Persistent data
     // Inlined to reduce ROM size
     #[inline(always)]
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
-    fn load_image(manifest: &ImageManifest, txn: &mut MailboxRecvTxn) -> CaliptraResult<()> {
+    fn load_image(
+        manifest: &ImageManifest,
+        txn: &mut MailboxRecvTxn,
+        persistent_data: &mut PersistentDataAccessor,
+    ) -> CaliptraResult<()> {
+        // Keep the ML-DSA-87 preamble for the runtime FIPS self test
+        if manifest.preamble.pqc_key_type == ImagePqcKeyType::Mldsa as u32 {
+            cprintln!("[fwproc] Load ML-DSA-87 preamble");
+            txn.copy_request(persistent_data.pqc_mut().mldsa_preamble1.as_mut_bytes())?;
+        }
+
         cprintln!(
             "[fwproc] Load FMC at address 0x{:08x} len {}",
             manifest.fmc.load_addr,
@@ -565,6 +622,13 @@ impl FirmwareProcessor {
             info.vendor_lms_pub_key_idx.unwrap_or(u32::MAX),
         );
 
+        // If ML-DSA is not selected, write the max value to the data vault
+        // to indicate the index is invalid.
+        data_vault.write_cold_reset_entry4(
+            ColdResetEntry4::MldsaVendorPubKeyIndex,
+            info.vendor_mldsa_pub_key_idx.unwrap_or(u32::MAX),
+        );
+
         data_vault.write_warm_reset_entry48(WarmResetEntry48::RtTci, &info.runtime.digest.into());
 
         data_vault.write_warm_reset_entry4(WarmResetEntry4::RtSvn, info.runtime.svn);
@@ -21,7 +21,10 @@ use crate::print::HexBytes;
 use crate::rom_env::RomEnv;
 use caliptra_cfi_derive::cfi_impl_fn;
 use caliptra_cfi_lib::{cfi_assert, cfi_assert_bool, cfi_launder};
-use caliptra_common::keyids::{KEY_ID_FE, KEY_ID_IDEVID_PRIV_KEY, KEY_ID_ROM_FMC_CDI, KEY_ID_UDS};
+use caliptra_common::keyids::{
+    KEY_ID_FE, KEY_ID_IDEVID_MLDSA_KEYPAIR_SEED, KEY_ID_IDEVID_PRIV_KEY, KEY_ID_ROM_FMC_CDI,
+    KEY_ID_UDS,
+};
 use caliptra_common::RomBootStatus::*;
 use caliptra_drivers::MAX_CSR_SIZE;
 use caliptra_drivers::*;
@@ -72,6 +75,16 @@ impl InitDevIdLayer {
         // Derive DICE Key Pair from CDI
         let key_pair = Self::derive_key_pair(env, KEY_ID_ROM_FMC_CDI, KEY_ID_IDEVID_PRIV_KEY)?;
 
+        // Derive the ML-DSA-87 Key Pair Seed from CDI. The key pair itself is
+        // only generated once the firmware image is known to be ML-DSA signed.
+        Crypto::hmac384_kdf(
+            env,
+            KEY_ID_ROM_FMC_CDI,
+            b"idevid_mldsa_keygen",
+            None,
+            KEY_ID_IDEVID_MLDSA_KEYPAIR_SEED,
+        )?;
+
         // Generate the Subject Serial Number and Subject Key Identifier.
         // This information will be used by next DICE Layer while generating
         // certificates
@@ -22,10 +22,14 @@ use crate::print::HexBytes;
 use crate::rom_env::RomEnv;
 use caliptra_cfi_derive::cfi_impl_fn;
 use caliptra_cfi_lib::{cfi_assert, cfi_assert_bool, cfi_launder};
-use caliptra_common::keyids::{KEY_ID_FE, KEY_ID_LDEVID_PRIV_KEY, KEY_ID_ROM_FMC_CDI};
+use caliptra_common::keyids::{
+    KEY_ID_FE, KEY_ID_IDEVID_MLDSA_KEYPAIR_SEED, KEY_ID_LDEVID_MLDSA_KEYPAIR_SEED,
+    KEY_ID_LDEVID_PRIV_KEY, KEY_ID_ROM_FMC_CDI,
+};
 use caliptra_common::RomBootStatus::*;
 use caliptra_drivers::*;
 use caliptra_x509::*;
+use zerocopy::IntoBytes;
 use zeroize::Zeroize;
 
 /// Dice Local Device Identity (IDEVID) Layer
@@ -63,6 +67,16 @@ impl LocalDevIdLayer {
         // Derive DICE Key Pair from CDI
         let key_pair = Self::derive_key_pair(env, KEY_ID_ROM_FMC_CDI, KEY_ID_LDEVID_PRIV_KEY)?;
 
+        // Derive the ML-DSA-87 Key Pair Seed from CDI. The certificate is
+        // generated by `generate_mldsa87_cert` after firmware verification.
+        Crypto::hmac384_kdf(
+            env,
+            KEY_ID_ROM_FMC_CDI,
+            b"ldevid_mldsa_keygen",
+            None,
+            KEY_ID_LDEVID_MLDSA_KEYPAIR_SEED,
+        )?;
+
         // Generate the Subject Serial Number and Subject Key Identifier.
         //
         // This information will be used by the next DICE Layer while generating
@@ -208,4 +222,85 @@ impl LocalDevIdLayer {
         report_boot_status(LDevIdCertSigGenerationComplete.into());
         Ok(())
     }
+
+    /// Generate Local Device ID ML-DSA-87 Certificate
+    ///
+    /// The certificate is only generated for ML-DSA signed images, in which
+    /// case it is signed with the IDEVID ML-DSA-87 key. Otherwise the
+    /// ML-DSA-87 key pair seeds of both layers are erased.
+    ///
+    /// # Arguments
+    ///
+    /// * `env`              - ROM Environment
+    /// * `mldsa_cert_chain` - Generate the ML-DSA-87 certificate chain
+    #[inline(never)]
+    pub fn generate_mldsa87_cert(env: &mut RomEnv, mldsa_cert_chain: bool) -> CaliptraResult<()> {
+        let auth_seed = KEY_ID_IDEVID_MLDSA_KEYPAIR_SEED;
+        let seed = KEY_ID_LDEVID_MLDSA_KEYPAIR_SEED;
+
+        if !mldsa_cert_chain {
+            env.key_vault.erase_key(auth_seed)?;
+            env.key_vault.erase_key(seed)?;
+            return Ok(());
+        }
+
+        cprintln!("[ldev] ML-DSA-87 SUBJECT.KEYID = {}", seed as u8);
+        cprintln!("[ldev] ML-DSA-87 AUTHORITY.KEYID = {}", auth_seed as u8);
+
+        let auth_pub_key = Crypto::mldsa87_key_gen(env, auth_seed);
+        let auth_pub_key = okref(&auth_pub_key)?;
+        let issuer_sn = X509::mldsa87_subj_sn(env, auth_pub_key)?;
+        let authority_key_id = X509::mldsa87_subj_key_id(env, auth_pub_key)?;
+
+        {
+            let pub_key = Crypto::mldsa87_key_gen(env, seed);
+            let pub_key = okref(&pub_key)?;
+
+            // Certificate `To Be Signed` Parameters
+            let params = LocalDevIdCertTbsMlDsa87Params {
+                ueid: &X509::ueid(env)?,
+                subject_sn: &X509::mldsa87_subj_sn(env, pub_key)?,
+                subject_key_id: &X509::mldsa87_subj_key_id(env, pub_key)?,
+                issuer_sn: &issuer_sn,
+                authority_key_id: &authority_key_id,
+                serial_number: &X509::mldsa87_cert_sn(env, pub_key)?,
+                public_key: pub_key.0.as_bytes().try_into().unwrap(),
+                not_before: &NotBefore::default().value,
+                not_after: &NotAfter::default().value,
+            };
+
+            // Generate the `To Be Signed` portion of the certificate and
+            // copy it to DCCM.
+            let tbs = LocalDevIdCertTbsMlDsa87::new(&params);
+            copy_tbs(tbs.tbs(), TbsType::LdevidMldsaTbs, env)?;
+        }
+
+        // Sign the `To Be Signed` portion
+        cprintln!(
+            "[ldev] Signing ML-DSA-87 Cert w/ AUTHORITY.KEYID = {}",
+            auth_seed as u8
+        );
+        let tbs = env
+            .persistent_data
+            .pqc()
+            .ldevid_mldsa_cert
+            .tbs()
+            .ok_or(CaliptraError::ROM_GLOBAL_UNSUPPORTED_LDEVID_MLDSA_TBS_SIZE)?;
+        let sig = Crypto::mldsa87_sign_and_verify(
+            &mut env.mldsa87,
+            &mut env.trng,
+            auth_seed,
+            auth_pub_key,
+            tbs,
+        );
+        let sig = okref(&sig)?;
+
+        // Clear the authority key pair seed
+        env.key_vault.erase_key(auth_seed)?;
+
+        env.persistent_data.pqc_mut().ldevid_mldsa_cert.sig = *sig;
+
+        cprintln!("[ldev] ML-DSA-87 Cert generated");
+        Ok(())
+    }
 }
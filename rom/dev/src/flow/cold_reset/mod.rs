@@ -35,6 +35,8 @@ use zeroize::Zeroize;
 pub enum TbsType {
     LdevidTbs = 0,
     FmcaliasTbs = 1,
+    LdevidMldsaTbs = 2,
+    FmcaliasMldsaTbs = 3,
 }
 /// Cold Reset Flow
 pub struct ColdResetFlow {}
@@ -60,6 +62,10 @@ impl ColdResetFlow {
         // Initialize FHT
         fht::initialize_fht(env);
 
+        // The ROM startup code only clears the 1.x DCCM
+        #[cfg(feature = "hw-2.x")]
+        env.persistent_data.pqc_mut().zeroize();
+
         // Execute IDEVID layer
         let mut idevid_layer_output = InitDevIdLayer::derive(env)?;
         let ldevid_layer_input = dice_input_from_output(&idevid_layer_output);
@@ -73,6 +79,11 @@ impl ColdResetFlow {
         // Download and validate firmware.
         let mut fw_proc_info = FirmwareProcessor::process(env)?;
 
+        // Generate the LDEVID ML-DSA-87 certificate. The ML-DSA-87 engine is
+        // only used for ML-DSA signed images, so this has to wait until the
+        // firmware manifest has been verified.
+        LocalDevIdLayer::generate_mldsa87_cert(env, fw_proc_info.mldsa_cert_chain)?;
+
         // Execute FMCALIAS layer
         let result = FmcAliasLayer::derive(env, &fmc_layer_input, &fw_proc_info);
         ldevid_layer_output.zeroize();
@@ -106,9 +117,9 @@ impl ColdResetFlow {
 #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
 #[inline(never)]
 pub fn copy_tbs(tbs: &[u8], tbs_type: TbsType, env: &mut RomEnv) -> CaliptraResult<()> {
-    let mut persistent_data = env.persistent_data.get_mut();
     let dst = match tbs_type {
         TbsType::LdevidTbs => {
+            let persistent_data = env.persistent_data.get_mut();
             persistent_data.fht.ldevid_tbs_size = tbs.len() as u16;
             persistent_data
                 .ldevid_tbs
@@ -116,12 +127,27 @@ pub fn copy_tbs(tbs: &[u8], tbs_type: TbsType, env: &mut RomEnv) -> CaliptraResu
                 .ok_or(CaliptraError::ROM_GLOBAL_UNSUPPORTED_LDEVID_TBS_SIZE)?
         }
         TbsType::FmcaliasTbs => {
+            let persistent_data = env.persistent_data.get_mut();
             persistent_data.fht.fmcalias_tbs_size = tbs.len() as u16;
             persistent_data
                 .fmcalias_tbs
                 .get_mut(..tbs.len())
                 .ok_or(CaliptraError::ROM_GLOBAL_UNSUPPORTED_FMCALIAS_TBS_SIZE)?
         }
+        TbsType::LdevidMldsaTbs => {
+            let cert = &mut env.persistent_data.pqc_mut().ldevid_mldsa_cert;
+            cert.tbs_size = tbs.len() as u32;
+            cert.tbs
+                .get_mut(..tbs.len())
+                .ok_or(CaliptraError::ROM_GLOBAL_UNSUPPORTED_LDEVID_MLDSA_TBS_SIZE)?
+        }
+        TbsType::FmcaliasMldsaTbs => {
+            let cert = &mut env.persistent_data.pqc_mut().fmcalias_mldsa_cert;
+            cert.tbs_size = tbs.len() as u32;
+            cert.tbs
+                .get_mut(..tbs.len())
+                .ok_or(CaliptraError::ROM_GLOBAL_UNSUPPORTED_FMCALIAS_MLDSA_TBS_SIZE)?
+        }
     };
     dst.copy_from_slice(tbs);
     Ok(())
@@ -15,6 +15,7 @@ use super::crypto::Crypto;
 use crate::cprintln;
 use crate::rom_env::RomEnv;
 use caliptra_drivers::*;
+use zerocopy::IntoBytes;
 
 /// X509 API
 pub enum X509 {}
@@ -45,8 +46,25 @@ impl X509 {
     ///
     /// `[u8; 64]` - X509 Subject Identifier serial number
     pub fn subj_sn(env: &mut RomEnv, pub_key: &Ecc384PubKey) -> CaliptraResult<[u8; 64]> {
-        let data = pub_key.to_der();
-        let digest = Crypto::sha256_digest(env, &data);
+        Self::subj_sn_from_data(env, &pub_key.to_der())
+    }
+
+    /// Get X509 Subject Serial Number of an ML-DSA-87 key
+    ///
+    /// # Arguments
+    ///
+    /// * `env`     - ROM Environment
+    /// * `pub_key` - Public Key
+    ///
+    /// # Returns
+    ///
+    /// `[u8; 64]` - X509 Subject Identifier serial number
+    pub fn mldsa87_subj_sn(env: &mut RomEnv, pub_key: &Mldsa87PubKey) -> CaliptraResult<[u8; 64]> {
+        Self::subj_sn_from_data(env, pub_key.0.as_bytes())
+    }
+
+    fn subj_sn_from_data(env: &mut RomEnv, data: &[u8]) -> CaliptraResult<[u8; 64]> {
+        let digest = Crypto::sha256_digest(env, data);
         let digest = okref(&digest)?;
         Ok(Self::hex(&digest.into()))
     }
@@ -102,8 +120,28 @@ impl X509 {
     ///
     /// `[u8; 20]` - X509 Subject Key Identifier
     pub fn subj_key_id(env: &mut RomEnv, pub_key: &Ecc384PubKey) -> CaliptraResult<[u8; 20]> {
-        let data = pub_key.to_der();
-        let digest = Crypto::sha256_digest(env, &data);
+        Self::subj_key_id_from_data(env, &pub_key.to_der())
+    }
+
+    /// Get Cert Subject Key Identifier of an ML-DSA-87 key
+    ///
+    /// # Arguments
+    ///
+    /// * `env`     - ROM Environment
+    /// * `pub_key` - Public Key
+    ///
+    /// # Returns
+    ///
+    /// `[u8; 20]` - X509 Subject Key Identifier
+    pub fn mldsa87_subj_key_id(
+        env: &mut RomEnv,
+        pub_key: &Mldsa87PubKey,
+    ) -> CaliptraResult<[u8; 20]> {
+        Self::subj_key_id_from_data(env, pub_key.0.as_bytes())
+    }
+
+    fn subj_key_id_from_data(env: &mut RomEnv, data: &[u8]) -> CaliptraResult<[u8; 20]> {
+        let digest = Crypto::sha256_digest(env, data);
         let digest: [u8; 32] = okref(&digest)?.into();
         Ok(digest[..20].try_into().unwrap())
     }
@@ -119,8 +157,25 @@ impl X509 {
     ///
     /// `[u8; 20]` - X509 Serial Number
     pub fn cert_sn(env: &mut RomEnv, pub_key: &Ecc384PubKey) -> CaliptraResult<[u8; 20]> {
-        let data = pub_key.to_der();
-        let digest = Crypto::sha256_digest(env, &data);
+        Self::cert_sn_from_data(env, &pub_key.to_der())
+    }
+
+    /// Get Cert Serial Number of an ML-DSA-87 key
+    ///
+    /// # Arguments
+    ///
+    /// * `env`     - ROM Environment
+    /// * `pub_key` - Public Key
+    ///
+    /// # Returns
+    ///
+    /// `[u8; 20]` - X509 Serial Number
+    pub fn mldsa87_cert_sn(env: &mut RomEnv, pub_key: &Mldsa87PubKey) -> CaliptraResult<[u8; 20]> {
+        Self::cert_sn_from_data(env, pub_key.0.as_bytes())
+    }
+
+    fn cert_sn_from_data(env: &mut RomEnv, data: &[u8]) -> CaliptraResult<[u8; 20]> {
+        let digest = Crypto::sha256_digest(env, data);
         let mut digest: [u8; 32] = okref(&digest)?.into();
 
         // Ensure the encoded integer is positive, and that the first octet
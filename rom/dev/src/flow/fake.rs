@@ -226,6 +226,7 @@ pub(crate) struct FakeRomImageVerificationEnv<'a, 'b> {
     pub(crate) soc_ifc: &'a mut SocIfc,
     pub(crate) data_vault: &'a mut DataVault,
     pub(crate) ecc384: &'a mut Ecc384,
+    pub(crate) mldsa87: &'a mut Mldsa87,
     pub image: &'b [u8],
 }
 
@@ -287,6 +288,26 @@ impl<'a, 'b> ImageVerificationEnv for &mut FakeRomImageVerificationEnv<'a, 'b> {
         }
     }
 
+    fn mldsa87_verify(
+        &mut self,
+        digest: &ImageDigest,
+        pub_key: &ImageMldsaPubKey,
+        sig: &ImageMldsaSignature,
+    ) -> CaliptraResult<Mldsa87Result> {
+        if self.soc_ifc.verify_in_fake_mode() {
+            let pub_key = Mldsa87PubKey::from(&pub_key.0);
+            let sig = Mldsa87Signature::from(&sig.0);
+
+            let mut msg = Mldsa87Msg::default();
+            msg.0[..digest.len()].copy_from_slice(digest);
+
+            self.mldsa87.verify(&pub_key, &msg, &sig)
+        } else {
+            // Mock verify, just always return success
+            Ok(Mldsa87Result::Success)
+        }
+    }
+
     /// Retrieve Vendor Public Key Digest
     fn vendor_pub_key_digest(&self) -> ImageDigest {
         self.soc_ifc.fuse_bank().vendor_pub_key_hash().into()
@@ -302,6 +323,11 @@ impl<'a, 'b> ImageVerificationEnv for &mut FakeRomImageVerificationEnv<'a, 'b> {
         self.soc_ifc.fuse_bank().vendor_lms_pub_key_revocation()
     }
 
+    /// Retrieve Vendor ML-DSA Public Key Revocation Bitmask
+    fn vendor_mldsa_pub_key_revocation(&self) -> u32 {
+        self.soc_ifc.fuse_bank().vendor_mldsa_pub_key_revocation()
+    }
+
     /// Retrieve Owner Public Key Digest from fuses
     fn owner_pub_key_digest_fuses(&self) -> ImageDigest {
         self.soc_ifc.fuse_bank().owner_pub_key_hash().into()
@@ -327,6 +353,11 @@ impl<'a, 'b> ImageVerificationEnv for &mut FakeRomImageVerificationEnv<'a, 'b> {
         self.data_vault.lms_vendor_pk_index()
     }
 
+    /// Get the vendor ML-DSA key index saved in data vault on cold boot
+    fn vendor_mldsa_pub_key_idx_dv(&self) -> u32 {
+        self.data_vault.mldsa_vendor_pk_index()
+    }
+
     /// Get the owner public key digest saved in the dv on cold boot
     fn owner_pub_key_digest_dv(&self) -> ImageDigest {
         self.data_vault.owner_pk_hash().into()
@@ -355,6 +386,12 @@ impl<'a, 'b> ImageVerificationEnv for &mut FakeRomImageVerificationEnv<'a, 'b> {
         self.soc_ifc.fuse_bank().lms_verify() == RomVerifyConfig::EcdsaAndLms
     }
 
+    /// Only Caliptra 2.x hardware has the ML-DSA-87 engine and the DCCM
+    /// holding the ML-DSA-87 persistent data
+    fn mldsa_verify_enabled(&self) -> bool {
+        cfg!(feature = "hw-2.x")
+    }
+
     fn set_fw_extended_error(&mut self, err: u32) {
         self.soc_ifc.set_fw_extended_error(err);
     }
@@ -23,9 +23,9 @@ use caliptra_drivers::report_fw_error_non_fatal;
 use caliptra_drivers::{
     okref, report_boot_status, MailboxRecvTxn, ResetReason, WarmResetEntry4, WarmResetEntry48,
 };
-use caliptra_drivers::{DataVault, PersistentData};
+use caliptra_drivers::{DataVault, PersistentData, PersistentDataAccessor};
 use caliptra_error::{CaliptraError, CaliptraResult};
-use caliptra_image_types::ImageManifest;
+use caliptra_image_types::{ImageManifest, ImagePqcKeyType};
 use caliptra_image_verify::{ImageVerificationInfo, ImageVerifier};
 use zerocopy::IntoBytes;
 
@@ -70,6 +70,7 @@ impl UpdateResetFlow {
                 sha384: &mut env.sha384,
                 soc_ifc: &mut env.soc_ifc,
                 ecc384: &mut env.ecc384,
+                mldsa87: &mut env.mldsa87,
                 data_vault: &mut env.data_vault,
                 pcr_bank: &mut env.pcr_bank,
                 image: recv_txn.raw_mailbox_contents(),
@@ -94,6 +95,8 @@ impl UpdateResetFlow {
                 info.vendor_ecc_pub_key_idx
             );
 
+            Self::load_mldsa_preamble(&mut env.persistent_data, &mut recv_txn)?;
+
             let manifest = &env.persistent_data.get().manifest2;
             Self::load_image(manifest, &mut recv_txn)?;
             Ok(())
@@ -151,16 +154,44 @@ impl UpdateResetFlow {
             soc_ifc: env.soc_ifc,
             data_vault: env.data_vault,
             ecc384: env.ecc384,
+            mldsa87: env.mldsa87,
             image: env.image,
         };
 
+        let mldsa_preamble = env
+            .image
+            .get(..img_bundle_sz as usize)
+            .and_then(|image| manifest.mldsa_preamble(image));
+
         let mut verifier = ImageVerifier::new(env);
 
-        let info = verifier.verify(manifest, img_bundle_sz, ResetReason::UpdateReset)?;
+        let info = verifier.verify(
+            manifest,
+            mldsa_preamble,
+            img_bundle_sz,
+            ResetReason::UpdateReset,
+        )?;
 
         Ok(info)
     }
 
+    /// Load the ML-DSA-87 preamble following the manifest, kept for the
+    /// runtime FIPS self test
+    ///
+    /// # Arguments
+    ///
+    /// * `persistent_data` - Persistent data
+    /// * `txn`             - Mailbox Receive Transaction
+    fn load_mldsa_preamble(
+        persistent_data: &mut PersistentDataAccessor,
+        txn: &mut MailboxRecvTxn,
+    ) -> CaliptraResult<()> {
+        if persistent_data.get().manifest2.preamble.pqc_key_type == ImagePqcKeyType::Mldsa as u32 {
+            txn.copy_request(persistent_data.pqc_mut().mldsa_preamble1.as_mut_bytes())?;
+        }
+        Ok(())
+    }
+
     /// Load the image to ICCM & DCCM
     ///
     /// # Arguments
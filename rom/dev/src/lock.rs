@@ -78,6 +78,10 @@ fn lock_cold_reset_reg(env: &mut RomEnv) {
     env.data_vault
         .lock_cold_reset_entry4(ColdResetEntry4::LmsVendorPubKeyIndex);
 
+    // Lock the ML-DSA Vendor Public Key Index in data vault until next cold reset
+    env.data_vault
+        .lock_cold_reset_entry4(ColdResetEntry4::MldsaVendorPubKeyIndex);
+
     // Lock Cold Reset Status register in data vault until next cold reset
     env.data_vault
         .lock_cold_reset_entry4(ColdResetEntry4::RomColdBootStatus);
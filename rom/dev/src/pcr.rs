@@ -77,7 +77,7 @@ pub(crate) fn extend_pcrs(
         sha384: env.sha384,
     };
 
-    let device_status: [u8; 9] = [
+    let device_status: [u8; 11] = [
         env.soc_ifc.lifecycle() as u8,
         env.soc_ifc.debug_locked() as u8,
         env.soc_ifc.fuse_bank().anti_rollback_disable() as u8,
@@ -87,6 +87,8 @@ pub(crate) fn extend_pcrs(
         env.data_vault.lms_vendor_pk_index() as u8,
         env.soc_ifc.fuse_bank().lms_verify() as u8,
         info.owner_pub_keys_digest_in_fuses as u8,
+        info.pqc_key_type as u8,
+        env.data_vault.mldsa_vendor_pk_index() as u8,
     ];
 
     pcr.extend(&device_status, PcrLogEntryId::DeviceStatus)?;
@@ -21,7 +21,7 @@ use caliptra_hw_model::{BootParams, Fuses, HwModel, InitParams, ModelError, Secu
 use caliptra_image_crypto::OsslCrypto as Crypto;
 use caliptra_image_fake_keys::{OWNER_CONFIG, VENDOR_CONFIG_KEY_1};
 use caliptra_image_gen::ImageGenerator;
-use caliptra_image_types::IMAGE_BYTE_SIZE;
+use caliptra_image_types::{ImagePqcKeyType, IMAGE_BYTE_SIZE};
 use caliptra_test::swap_word_bytes;
 use openssl::hash::{Hasher, MessageDigest};
 use zerocopy::{FromBytes, IntoBytes, TryFromBytes};
@@ -203,6 +203,8 @@ fn test_pcr_log() {
             VENDOR_CONFIG_KEY_1.lms_key_idx as u8,
             RomVerifyConfig::EcdsaAndLms as u8,
             true as u8,
+            ImagePqcKeyType::Lms as u8,
+            u8::MAX,
         ],
     );
 
@@ -305,6 +307,8 @@ fn test_pcr_log_no_owner_key_digest_fuse() {
             VENDOR_CONFIG_KEY_1.lms_key_idx as u8,
             RomVerifyConfig::EcdsaAndLms as u8,
             false as u8,
+            ImagePqcKeyType::Lms as u8,
+            u8::MAX,
         ],
     );
 
@@ -398,6 +402,8 @@ fn test_pcr_log_fmc_fuse_svn() {
             u8::MAX,
             RomVerifyConfig::EcdsaOnly as u8,
             true as u8,
+            ImagePqcKeyType::Lms as u8,
+            u8::MAX,
         ],
     );
 }
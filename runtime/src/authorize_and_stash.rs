@@ -17,8 +17,7 @@ use core::mem::size_of;
 
 use crate::{dpe_crypto::DpeCrypto, CptraDpeTypes, DpePlatform, Drivers, StashMeasurementCmd};
 use caliptra_auth_man_types::{
-    AuthManifestImageMetadata, AuthManifestImageMetadataCollection, AuthManifestPreamble,
-    ImageMetadataFlags, AUTH_MANIFEST_MARKER,
+    AuthManifestImageMetadata, AuthManifestPreamble, ImageMetadataFlags, AUTH_MANIFEST_MARKER,
 };
 use caliptra_cfi_derive_git::cfi_impl_fn;
 use caliptra_cfi_lib_git::{cfi_assert, cfi_assert_eq, cfi_launder};
@@ -28,8 +27,8 @@ use caliptra_common::mailbox_api::{
 };
 use caliptra_drivers::{
     pcr_log::PCR_ID_STASH_MEASUREMENT, Array4x12, Array4xN, AuthManifestImageMetadataList,
-    CaliptraError, CaliptraResult, Ecc384, Ecc384PubKey, Ecc384Signature, HashValue, Lms,
-    PersistentData, RomVerifyConfig, Sha256, Sha384, SocIfc,
+    AuthManifestImageMetadataStore, CaliptraError, CaliptraResult, Ecc384, Ecc384PubKey,
+    Ecc384Signature, HashValue, Lms, PersistentData, RomVerifyConfig, Sha256, Sha384, SocIfc,
 };
 use caliptra_image_types::{
     ImageDigest, ImageEccPubKey, ImageEccSignature, ImageLmsPublicKey, ImageLmsSignature,
@@ -43,18 +42,23 @@ use dpe::{
     response::DpeErrorCode,
 };
 use memoffset::offset_of;
-use zerocopy::{FromBytes, IntoBytes};
+use zerocopy::{FromBytes, FromZeros, IntoBytes};
 
 pub const IMAGE_AUTHORIZED: u32 = 0xDEADC0DE; // Either FW ID and image digest matched or 'ignore_auth_check' is set for the FW ID.
 pub const IMAGE_NOT_AUTHORIZED: u32 = 0x21523F21; // FW ID not found in the image metadata entry collection.
 pub const IMAGE_HASH_MISMATCH: u32 = 0x8BFB95CB; // FW ID matched, but image digest mismatched.
+pub const IMAGE_SIZE_MISMATCH: u32 = 0x5A3C96E1; // FW ID and digest matched, but image size mismatched.
+pub const IMAGE_LOAD_ADDRESS_MISMATCH: u32 = 0xC3A5695A; // FW ID and digest matched, but load address mismatched.
+pub const IMAGE_VERSION_MISMATCH: u32 = 0x96E15A3C; // FW ID and digest matched, but component version mismatched.
+pub const IMAGE_SVN_TOO_LOW: u32 = 0x3C5AE196; // FW ID and digest matched, but component SVN is below the minimum.
 
 pub struct AuthorizeAndStashCmd;
 impl AuthorizeAndStashCmd {
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     #[inline(never)]
     pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
-        if let Ok(cmd) = AuthorizeAndStashReq::ref_from_bytes(cmd_args) {
+        if let Some((cmd, extended)) = Self::parse_request(cmd_args) {
+            let cmd = &cmd;
             if ImageHashSource::from(cmd.source) != ImageHashSource::InRequest {
                 Err(CaliptraError::RUNTIME_AUTH_AND_STASH_UNSUPPORTED_IMAGE_SOURCE)?;
             }
@@ -68,17 +72,18 @@ impl AuthorizeAndStashCmd {
             let auth_result = if let Some(metadata_entry) =
                 Self::find_metadata_entry(auth_manifest_image_metadata_col, cmd_fw_id)
             {
-                // If 'ignore_auth_check' is set, then skip the image digest comparison and authorize the image.
+                // If 'ignore_auth_check' is set, then skip the image digest comparison. The
+                // optional field checks still apply.
                 let flags = ImageMetadataFlags(metadata_entry.flags);
                 if flags.ignore_auth_check() {
                     cfi_assert!(cfi_launder(flags.ignore_auth_check()));
-                    IMAGE_AUTHORIZED
+                    Self::check_optional_fields(metadata_entry, flags, cmd, extended)
                 } else if cfi_launder(metadata_entry.digest) == cmd.measurement {
                     caliptra_cfi_lib_git::cfi_assert_eq_12_words(
                         &Array4x12::from(metadata_entry.digest).0,
                         &Array4x12::from(cmd.measurement).0,
                     );
-                    IMAGE_AUTHORIZED
+                    Self::check_optional_fields(metadata_entry, flags, cmd, extended)
                 } else {
                     IMAGE_HASH_MISMATCH
                 }
@@ -113,14 +118,83 @@ impl AuthorizeAndStashCmd {
         }
     }
 
-    /// Search for a metadata entry in the sorted `AuthManifestImageMetadataCollection` that matches the firmware ID.
+    /// Parse an AUTHORIZE_AND_STASH request in either the current or the
+    /// legacy format.
     ///
-    /// This function performs a binary search on the `image_metadata_list` of the provided `AuthManifestImageMetadataCollection`.
+    /// # Arguments
+    ///
+    /// * `cmd_args` - Request bytes.
+    ///
+    /// # Returns
+    ///
+    /// * `Option<(AuthorizeAndStashReq, bool)>` - The request, and whether it
+    ///   carries the image size, load address and version fields. The fields
+    ///   are zero in a legacy request.
+    fn parse_request(cmd_args: &[u8]) -> Option<(AuthorizeAndStashReq, bool)> {
+        if let Ok(cmd) = AuthorizeAndStashReq::read_from_bytes(cmd_args) {
+            return Some((cmd, true));
+        }
+        if cmd_args.len() != AuthorizeAndStashReq::LEGACY_SIZE {
+            return None;
+        }
+        let mut cmd = AuthorizeAndStashReq::new_zeroed();
+        cmd.as_mut_bytes()
+            .get_mut(..AuthorizeAndStashReq::LEGACY_SIZE)?
+            .copy_from_slice(cmd_args);
+        Some((cmd, false))
+    }
+
+    /// Check the optional image metadata fields enabled by the entry flags.
+    ///
+    /// # Arguments
+    ///
+    /// * `metadata_entry` - Image metadata entry matching the firmware ID.
+    /// * `flags` - Flags of the image metadata entry.
+    /// * `cmd` - AUTHORIZE_AND_STASH request.
+    /// * `extended` - Whether the request carries the image size, load address
+    ///   and version fields. A check on a field the request lacks fails.
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - `IMAGE_AUTHORIZED` if all enabled checks pass, otherwise the
+    ///   result code of the first failing check.
+    fn check_optional_fields(
+        metadata_entry: &AuthManifestImageMetadata,
+        flags: ImageMetadataFlags,
+        cmd: &AuthorizeAndStashReq,
+        extended: bool,
+    ) -> u32 {
+        if flags.check_image_size() && (!extended || metadata_entry.image_size != cmd.image_size) {
+            return IMAGE_SIZE_MISMATCH;
+        }
+
+        if flags.check_load_address()
+            && (!extended
+                || metadata_entry.load_address_lo != cmd.load_address_lo
+                || metadata_entry.load_address_hi != cmd.load_address_hi)
+        {
+            return IMAGE_LOAD_ADDRESS_MISMATCH;
+        }
+
+        if flags.check_version() && (!extended || metadata_entry.version != cmd.version) {
+            return IMAGE_VERSION_MISMATCH;
+        }
+
+        if flags.check_svn() && cmd.svn < metadata_entry.svn {
+            return IMAGE_SVN_TOO_LOW;
+        }
+
+        IMAGE_AUTHORIZED
+    }
+
+    /// Search for a metadata entry in the sorted `AuthManifestImageMetadataStore` that matches the firmware ID.
+    ///
+    /// This function performs a binary search on the used entries of the provided `AuthManifestImageMetadataStore`.
     /// It compares the firmware ID (`fw_id`) of each metadata entry with the provided `cmd_fw_id`.
     ///
     /// # Arguments
     ///
-    /// * `auth_manifest_image_metadata_col` - A reference to the `AuthManifestImageMetadataCollection` containing the metadata entries.
+    /// * `auth_manifest_image_metadata_col` - A reference to the `AuthManifestImageMetadataStore` containing the metadata entries.
     /// * `cmd_fw_id` - The firmware ID from the command to search for.
     ///
     /// # Returns
@@ -130,13 +204,13 @@ impl AuthorizeAndStashCmd {
     ///
     #[inline(never)]
     fn find_metadata_entry(
-        auth_manifest_image_metadata_col: &AuthManifestImageMetadataCollection,
+        auth_manifest_image_metadata_col: &AuthManifestImageMetadataStore,
         cmd_fw_id: u32,
     ) -> Option<&AuthManifestImageMetadata> {
-        auth_manifest_image_metadata_col
-            .image_metadata_list
+        let entries = auth_manifest_image_metadata_col.entries();
+        entries
             .binary_search_by(|metadata| metadata.fw_id.cmp(&cmd_fw_id))
             .ok()
-            .map(|index| &auth_manifest_image_metadata_col.image_metadata_list[index])
+            .map(|index| &entries[index])
     }
 }
@@ -44,6 +44,15 @@ impl CertifyKeyExtendedCmd {
             }
         }
 
+        let certify_key_cmd = CertifyKeyCmd::ref_from_bytes(&cmd.certify_key_req[..])
+            .map_err(|_| CaliptraError::RUNTIME_DPE_COMMAND_DESERIALIZATION_FAILED)?;
+        if !drivers
+            .dpe_config
+            .command_allowed(&Command::CertifyKey(certify_key_cmd))
+        {
+            return Err(CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED);
+        }
+
         let hashed_rt_pub_key = drivers.compute_rt_alias_sn()?;
         let key_id_rt_cdi = Drivers::get_key_id_rt_cdi(drivers)?;
         let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
@@ -80,12 +89,11 @@ impl CertifyKeyExtendedCmd {
                 &nf,
                 dmtf_device_info,
                 None,
+                &drivers.dpe_config,
             ),
         };
 
         let mut dpe = &mut pdata.dpe;
-        let certify_key_cmd = CertifyKeyCmd::ref_from_bytes(&cmd.certify_key_req[..])
-            .map_err(|_| CaliptraError::RUNTIME_DPE_COMMAND_DESERIALIZATION_FAILED)?;
         let locality = drivers.mbox.user();
         let resp = certify_key_cmd.execute(dpe, &mut env, locality);
 
@@ -0,0 +1,145 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    derive_key.rs
+
+Abstract:
+
+    File contains DERIVE_KEY and REVOKE_DERIVED_KEY mailbox commands.
+
+--*/
+
+use crate::{Drivers, Hmac, PauserPrivileges};
+
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_cfi_lib_git::{cfi_assert, cfi_launder};
+use caliptra_common::keyids::KEY_ID_DERIVED_KEY;
+use caliptra_common::mailbox_api::{
+    DeriveKeyFlags, DeriveKeyReq, DeriveKeyResp, MailboxResp, RevokeDerivedKeyReq,
+    RevokeDerivedKeyResp,
+};
+use caliptra_drivers::{hmac384_kdf, KeyId, KeyReadArgs, KeyUsage, KeyWriteArgs};
+use caliptra_error::{CaliptraError, CaliptraResult};
+use zerocopy::FromBytes;
+
+// Keeps keys derived for callers apart from the keys DPE derives from the
+// same CDI.
+const LABEL_PREFIX: &[u8] = b"DERIVE_KEY";
+
+pub struct DeriveKeyCmd;
+impl DeriveKeyCmd {
+    /// Find the CDI an exported CDI handle refers to
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `exported_cdi_handle` - A handle from DPE that is exchanged for a CDI
+    ///
+    /// # Returns
+    ///
+    /// * `KeyId` - Key vault slot holding the CDI
+    fn exported_cdi(
+        drivers: &Drivers,
+        exported_cdi_handle: &[u8; DeriveKeyReq::EXPORTED_CDI_MAX_SIZE],
+    ) -> CaliptraResult<KeyId> {
+        for slot in drivers.exported_cdi_slots.iter() {
+            match slot {
+                Some((cdi, handle)) if handle == exported_cdi_handle => return Ok(*cdi),
+                _ => (),
+            }
+        }
+        Err(CaliptraError::RUNTIME_DERIVE_KEY_INVALID_CDI_HANDLE)
+    }
+
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = DeriveKeyReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let flags = DeriveKeyFlags::from_bits(cmd.flags)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let label = cmd
+            .label
+            .get(..cmd.label_size as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let context = cmd
+            .context
+            .get(..cmd.context_size as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        let export = flags.contains(DeriveKeyFlags::EXPORT);
+        if export {
+            // Keys leave Caliptra only if the owner allows it, and only to PL0
+            if !drivers.dpe_config.export_derived_keys {
+                return Err(CaliptraError::RUNTIME_DERIVE_KEY_EXPORT_NOT_ALLOWED);
+            }
+            match drivers.caller_privilege_level() {
+                PauserPrivileges::PL0 => (),
+                PauserPrivileges::PL1 => {
+                    return Err(CaliptraError::RUNTIME_DERIVE_KEY_EXPORT_NOT_ALLOWED);
+                }
+            }
+        }
+
+        let cdi = Self::exported_cdi(drivers, &cmd.exported_cdi_handle)?;
+
+        let mut kdf_label = [0u8; LABEL_PREFIX.len() + DeriveKeyReq::MAX_LABEL_SIZE];
+        kdf_label[..LABEL_PREFIX.len()].copy_from_slice(LABEL_PREFIX);
+        kdf_label[LABEL_PREFIX.len()..][..label.len()].copy_from_slice(label);
+        let kdf_label = &kdf_label[..LABEL_PREFIX.len() + label.len()];
+
+        let mut resp = DeriveKeyResp::default();
+        if cfi_launder(export) {
+            cfi_assert!(export);
+            let key = Hmac::ecc384_kdf(drivers, cdi, kdf_label, context)
+                .map_err(|_| CaliptraError::RUNTIME_DERIVE_KEY_FAILED)?;
+            resp.key = key.into();
+            resp.flags = DeriveKeyFlags::EXPORT.bits();
+        } else {
+            cfi_assert!(!export);
+            // A new key replaces the one held for the previous handle.
+            drivers.derived_key_handle = None;
+            hmac384_kdf(
+                &mut drivers.hmac384,
+                KeyReadArgs::new(cdi).into(),
+                kdf_label,
+                Some(context),
+                &mut drivers.trng,
+                KeyWriteArgs::new(KEY_ID_DERIVED_KEY, KeyUsage::default().set_hmac_key_en()).into(),
+            )
+            .map_err(|_| CaliptraError::RUNTIME_DERIVE_KEY_FAILED)?;
+
+            let random = <[u8; 48]>::from(drivers.trng.generate()?);
+            resp.key_handle
+                .copy_from_slice(&random[..DeriveKeyResp::KEY_HANDLE_SIZE]);
+            drivers.derived_key_handle = Some(resp.key_handle);
+        }
+
+        Ok(MailboxResp::DeriveKey(resp))
+    }
+}
+
+pub struct RevokeDerivedKeyCmd;
+impl RevokeDerivedKeyCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = RevokeDerivedKeyReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        match drivers.derived_key_handle {
+            Some(handle) if handle == cmd.key_handle => {
+                cfi_assert!(handle == cmd.key_handle);
+                drivers.key_vault.erase_key(KEY_ID_DERIVED_KEY)?;
+                drivers.derived_key_handle = None;
+                Ok(MailboxResp::RevokeDerivedKey(
+                    RevokeDerivedKeyResp::default(),
+                ))
+            }
+            _ => Err(CaliptraError::RUNTIME_REVOKE_DERIVED_KEY_HANDLE_NOT_FOUND),
+        }
+    }
+}
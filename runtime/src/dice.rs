@@ -13,7 +13,8 @@ Abstract:
 --*/
 
 use caliptra_common::mailbox_api::{
-    GetFmcAliasCertResp, GetIdevCertReq, GetIdevCertResp, GetLdevCertResp, GetRtAliasCertResp,
+    GetFmcAliasCertResp, GetFmcAliasMldsaCertResp, GetIdevCertReq, GetIdevCertResp,
+    GetLdevCertResp, GetLdevMldsaCertResp, GetRtAliasCertResp, GetRtAliasMldsaCertResp,
     MailboxResp, MailboxRespHeader,
 };
 
@@ -21,9 +22,9 @@ use crate::Drivers;
 
 use caliptra_drivers::{
     hand_off::DataStore, CaliptraError, CaliptraResult, DataVault, Ecc384Scalar, Ecc384Signature,
-    PersistentData,
+    Mldsa87CertData, PersistentData, PqcPersistentData,
 };
-use caliptra_x509::{Ecdsa384CertBuilder, Ecdsa384Signature};
+use caliptra_x509::{Ecdsa384CertBuilder, Ecdsa384Signature, MlDsa87CertBuilder, MLDSA87_SIG_LEN};
 use zerocopy::IntoBytes;
 
 pub struct IDevIdCertCmd;
@@ -108,6 +109,53 @@ impl GetRtAliasCertCmd {
     }
 }
 
+pub struct GetLdevMldsaCertCmd;
+impl GetLdevMldsaCertCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        let mut resp = GetLdevMldsaCertResp::default();
+
+        resp.data_size = pqc_persistent_data(drivers)
+            .and_then(|pqc| mldsa87_cert_from_data(&pqc.ldevid_mldsa_cert, &mut resp.data))
+            .map_err(|e| mldsa87_cert_err(e, CaliptraError::RUNTIME_GET_LDEVID_MLDSA_CERT_FAILED))?
+            as u32;
+
+        Ok(MailboxResp::GetLdevMldsaCert(resp))
+    }
+}
+
+pub struct GetFmcAliasMldsaCertCmd;
+impl GetFmcAliasMldsaCertCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        let mut resp = GetFmcAliasMldsaCertResp::default();
+
+        resp.data_size = pqc_persistent_data(drivers)
+            .and_then(|pqc| mldsa87_cert_from_data(&pqc.fmcalias_mldsa_cert, &mut resp.data))
+            .map_err(|e| {
+                mldsa87_cert_err(e, CaliptraError::RUNTIME_GET_FMC_ALIAS_MLDSA_CERT_FAILED)
+            })? as u32;
+
+        Ok(MailboxResp::GetFmcAliasMldsaCert(resp))
+    }
+}
+
+pub struct GetRtAliasMldsaCertCmd;
+impl GetRtAliasMldsaCertCmd {
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        let mut resp = GetRtAliasMldsaCertResp::default();
+
+        resp.data_size = pqc_persistent_data(drivers)
+            .and_then(|pqc| mldsa87_cert_from_data(&pqc.rtalias_mldsa_cert, &mut resp.data))
+            .map_err(|e| {
+                mldsa87_cert_err(e, CaliptraError::RUNTIME_GET_RT_ALIAS_MLDSA_CERT_FAILED)
+            })? as u32;
+
+        Ok(MailboxResp::GetRtAliasMldsaCert(resp))
+    }
+}
+
 /// Retrieve the r portion of the LDevId cert signature
 ///
 /// # Arguments
@@ -367,3 +415,61 @@ fn cert_from_tbs_and_sig(
 
     Ok(size)
 }
+
+/// Create an ML-DSA-87 certificate from the tbs and signature stored in
+/// persistent data and write the output to `cert`
+///
+/// # Arguments
+///
+/// * `data` - ML-DSA-87 certificate data produced by ROM or FMC
+/// * `cert` - Buffer to copy the certificate to
+///
+/// # Returns
+///
+/// * `usize` - The number of bytes written to `cert`
+fn mldsa87_cert_from_data(data: &Mldsa87CertData, cert: &mut [u8]) -> CaliptraResult<usize> {
+    // The tbs is absent when the firmware image was not signed with ML-DSA-87.
+    let Some(tbs) = data.tbs() else {
+        return Err(CaliptraError::RUNTIME_MLDSA_CERT_CHAIN_UNAVAILABLE);
+    };
+
+    // The last byte of the final signature word is padding.
+    let Some(sig) = data
+        .sig
+        .0
+        .as_bytes()
+        .get(..MLDSA87_SIG_LEN)
+        .and_then(|sig| sig.try_into().ok())
+    else {
+        return Err(CaliptraError::RUNTIME_INTERNAL);
+    };
+    let Some(builder) = MlDsa87CertBuilder::new(tbs, sig) else {
+        return Err(CaliptraError::RUNTIME_INTERNAL);
+    };
+
+    let Some(size) = builder.build(cert) else {
+        return Err(CaliptraError::RUNTIME_INTERNAL);
+    };
+
+    Ok(size)
+}
+
+/// Returns the persistent data holding the ML-DSA-87 certificates, which only
+/// exists on Caliptra 2.x.
+fn pqc_persistent_data(drivers: &Drivers) -> CaliptraResult<&PqcPersistentData> {
+    if cfg!(feature = "hw-2.x") {
+        Ok(drivers.persistent_data.pqc())
+    } else {
+        Err(CaliptraError::RUNTIME_MLDSA_CERT_CHAIN_UNAVAILABLE)
+    }
+}
+
+/// Map a certificate construction failure to the command specific error,
+/// keeping the error reported when the ML-DSA-87 chain was never generated.
+fn mldsa87_cert_err(err: CaliptraError, cmd_err: CaliptraError) -> CaliptraError {
+    if err == CaliptraError::RUNTIME_MLDSA_CERT_CHAIN_UNAVAILABLE {
+        err
+    } else {
+        cmd_err
+    }
+}
@@ -0,0 +1,116 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    dpe_config.rs
+
+Abstract:
+
+    File contains the DPE configuration runtime enforces.
+
+--*/
+
+use crate::{
+    DPE_SUPPORT, PL0_DPE_ACTIVE_CONTEXT_THRESHOLD, PL1_DPE_ACTIVE_CONTEXT_THRESHOLD, VENDOR_ID,
+    VENDOR_SKU,
+};
+use caliptra_drivers::{CaliptraError, CaliptraResult};
+use caliptra_image_types::{ImageDpeConfig, ImageManifest};
+use dpe::{commands::Command, support::Support, MAX_HANDLES};
+
+/// DPE configuration from the owner signed portion of the firmware manifest,
+/// or the runtime defaults if the owner did not provide one.
+#[derive(Clone, Copy)]
+pub struct DpeConfig {
+    pub vendor_id: u32,
+    pub vendor_sku: u32,
+    pub pl0_context_threshold: usize,
+    pub pl1_context_threshold: usize,
+    pub support: Support,
+    pub allowed_commands: u32,
+    pub export_derived_keys: bool,
+}
+
+impl Default for DpeConfig {
+    fn default() -> Self {
+        Self {
+            vendor_id: VENDOR_ID,
+            vendor_sku: VENDOR_SKU,
+            pl0_context_threshold: PL0_DPE_ACTIVE_CONTEXT_THRESHOLD,
+            pl1_context_threshold: PL1_DPE_ACTIVE_CONTEXT_THRESHOLD,
+            support: DPE_SUPPORT,
+            allowed_commands: ImageDpeConfig::CMD_ALL,
+            export_derived_keys: false,
+        }
+    }
+}
+
+impl DpeConfig {
+    /// Validate and load the DPE configuration from the firmware manifest
+    ///
+    /// # Arguments
+    ///
+    /// * `manifest` - Firmware manifest
+    ///
+    /// # Returns
+    ///
+    /// * `DpeConfig` - Configuration to enforce
+    pub fn from_manifest(manifest: &ImageManifest) -> CaliptraResult<Self> {
+        let config = &manifest.header.owner_data.dpe_config;
+        if !config.enabled() {
+            return Ok(Self::default());
+        }
+
+        let err = CaliptraError::RUNTIME_DPE_CONFIG_INVALID;
+        let pl0_context_threshold = config.pl0_context_limit as usize;
+        let pl1_context_threshold = config.pl1_context_limit as usize;
+        if pl0_context_threshold == 0
+            || pl1_context_threshold == 0
+            || pl0_context_threshold + pl1_context_threshold > MAX_HANDLES
+        {
+            return Err(err);
+        }
+
+        // Runtime always auto-initializes DPE and cannot enable features it
+        // does not implement.
+        let support = Support::from_bits(config.support).ok_or(err)?;
+        if !DPE_SUPPORT.contains(support) || !support.contains(Support::AUTO_INIT) {
+            return Err(err);
+        }
+
+        // GetProfile is always allowed so callers can discover the
+        // configuration.
+        if config.allowed_commands & !ImageDpeConfig::CMD_ALL != 0
+            || config.allowed_commands & ImageDpeConfig::CMD_GET_PROFILE == 0
+        {
+            return Err(err);
+        }
+
+        Ok(Self {
+            vendor_id: config.vendor_id,
+            vendor_sku: config.vendor_sku,
+            pl0_context_threshold,
+            pl1_context_threshold,
+            support,
+            allowed_commands: config.allowed_commands,
+            export_derived_keys: config.export_derived_keys(),
+        })
+    }
+
+    /// Returns true if the configuration allows `command`
+    pub fn command_allowed(&self, command: &Command) -> bool {
+        let bit = match command {
+            Command::GetProfile => ImageDpeConfig::CMD_GET_PROFILE,
+            Command::InitCtx(_) => ImageDpeConfig::CMD_INITIALIZE_CONTEXT,
+            Command::DeriveContext(_) => ImageDpeConfig::CMD_DERIVE_CONTEXT,
+            Command::CertifyKey(_) => ImageDpeConfig::CMD_CERTIFY_KEY,
+            Command::Sign(_) => ImageDpeConfig::CMD_SIGN,
+            Command::RotateCtx(_) => ImageDpeConfig::CMD_ROTATE_CONTEXT_HANDLE,
+            Command::DestroyCtx(_) => ImageDpeConfig::CMD_DESTROY_CONTEXT,
+            Command::GetCertificateChain(_) => ImageDpeConfig::CMD_GET_CERTIFICATE_CHAIN,
+        };
+        self.allowed_commands & bit != 0
+    }
+}
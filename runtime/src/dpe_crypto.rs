@@ -20,9 +20,9 @@ use caliptra_common::keyids::{
     KEY_ID_DPE_CDI, KEY_ID_DPE_PRIV_KEY, KEY_ID_EXPORTED_DPE_CDI, KEY_ID_TMP,
 };
 use caliptra_drivers::{
-    cprintln, hmac384_kdf, Array4x12, Ecc384, Ecc384PrivKeyIn, Ecc384PubKey, Ecc384Scalar,
-    Ecc384Seed, Hmac384, Hmac384Data, Hmac384Key, Hmac384Tag, KeyId, KeyReadArgs, KeyUsage,
-    KeyVault, KeyWriteArgs, Sha384, Sha384DigestOp, Trng,
+    cprintln, hmac384_kdf, Array4x12, CaliptraResult, Ecc384, Ecc384PrivKeyIn, Ecc384PubKey,
+    Ecc384Scalar, Ecc384Seed, Hmac384, Hmac384Data, Hmac384Key, Hmac384Tag, KeyId, KeyReadArgs,
+    KeyUsage, KeyVault, KeyWriteArgs, Sha384, Sha384DigestOp, Trng,
 };
 use crypto::{AlgLen, Crypto, CryptoBuf, CryptoError, Digest, EcdsaPub, EcdsaSig, Hasher};
 use dpe::{
@@ -152,6 +152,53 @@ impl<'a> DpeCrypto<'a> {
         }
     }
 
+    /// HMAC `digest` with a key derived from `cdi`. The HMAC key is the hash
+    /// of the public key of an ECC keypair derived from `cdi`, `label` and
+    /// `info`, because the HMAC engine cannot return a tag computed with a key
+    /// from the key vault. Callers must not reveal that public key.
+    ///
+    /// # Arguments
+    ///
+    /// * `algs` - Algorithm size
+    /// * `cdi` - Key vault slot holding the CDI
+    /// * `label` - Label used to derive the keypair
+    /// * `info` - Info used to derive the keypair
+    /// * `digest` - Data to HMAC
+    pub fn hmac_sign_with_derived(
+        &mut self,
+        algs: AlgLen,
+        cdi: &<DpeCrypto<'a> as crypto::Crypto>::Cdi,
+        label: &[u8],
+        info: &[u8],
+        digest: &Digest,
+    ) -> Result<Array4x12, CryptoError> {
+        let (priv_key, pub_key) = self.derive_key_pair_inner(algs, cdi, label, info, KEY_ID_TMP)?;
+
+        let mut pub_key_digest = Array4x12::default();
+        // Done in a closure to ensure state is always cleaned up.
+        let hmac_result = || -> CaliptraResult<Array4x12> {
+            let mut hasher = self.sha384.digest_init()?;
+            hasher.update(pub_key.x.bytes())?;
+            hasher.update(pub_key.y.bytes())?;
+            hasher.finalize(&mut pub_key_digest)?;
+
+            let mut hmac_output = Array4x12::default();
+            self.hmac384.hmac(
+                &Hmac384Key::Array4x12(&pub_key_digest),
+                &Hmac384Data::Slice(digest.bytes()),
+                self.trng,
+                Hmac384Tag::Array4x12(&mut hmac_output),
+            )?;
+            Ok(hmac_output)
+        }();
+
+        unsafe { Sha384::zeroize() }
+        pub_key_digest.zeroize();
+        let _ = self.key_vault.erase_key(priv_key);
+
+        hmac_result.map_err(|e| CryptoError::CryptoLibError(u32::from(e)))
+    }
+
     pub fn get_cdi_from_exported_handle(
         &mut self,
         exported_cdi_handle: &[u8; MAX_EXPORTED_CDI_SIZE],
@@ -30,7 +30,7 @@ use platform::{
 };
 use zerocopy::IntoBytes;
 
-use crate::{subject_alt_name::AddSubjectAltNameCmd, MAX_CERT_CHAIN_SIZE};
+use crate::{subject_alt_name::AddSubjectAltNameCmd, DpeConfig, MAX_CERT_CHAIN_SIZE};
 
 pub struct DpePlatform<'a> {
     auto_init_locality: u32,
@@ -40,12 +40,14 @@ pub struct DpePlatform<'a> {
     not_after: &'a NotAfter,
     dmtf_device_info: Option<&'a [u8]>,
     ueid: Option<&'a [u8; 17]>,
+    dpe_config: &'a DpeConfig,
 }
 
 pub const VENDOR_ID: u32 = u32::from_be_bytes(*b"CTRA");
 pub const VENDOR_SKU: u32 = u32::from_be_bytes(*b"CTRA");
 
 impl<'a> DpePlatform<'a> {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         auto_init_locality: u32,
         hashed_rt_pub_key: &'a Digest,
@@ -54,6 +56,7 @@ impl<'a> DpePlatform<'a> {
         not_after: &'a NotAfter,
         dmtf_device_info: Option<&'a [u8]>,
         ueid: Option<&'a [u8; 17]>,
+        dpe_config: &'a DpeConfig,
     ) -> Self {
         Self {
             auto_init_locality,
@@ -63,6 +66,7 @@ impl<'a> DpePlatform<'a> {
             not_after,
             dmtf_device_info,
             ueid,
+            dpe_config,
         }
     }
 }
@@ -96,11 +100,11 @@ impl Platform for DpePlatform<'_> {
     }
 
     fn get_vendor_id(&mut self) -> Result<u32, PlatformError> {
-        Ok(VENDOR_ID)
+        Ok(self.dpe_config.vendor_id)
     }
 
     fn get_vendor_sku(&mut self) -> Result<u32, PlatformError> {
-        Ok(VENDOR_SKU)
+        Ok(self.dpe_config.vendor_sku)
     }
 
     fn get_auto_init_locality(&mut self) -> Result<u32, PlatformError> {
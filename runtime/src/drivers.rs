@@ -14,24 +14,24 @@ Abstract:
 
 #![cfg_attr(not(feature = "fip-self-test"), allow(unused))]
 
-#[cfg(feature = "fips_self_test")]
-pub use crate::fips::{fips_self_test_cmd, fips_self_test_cmd::SelfTestStatus};
-
 use crate::{
-    dice, CptraDpeTypes, DisableAttestationCmd, DpeCrypto, DpePlatform, Mailbox, DPE_SUPPORT,
-    MAX_CERT_CHAIN_SIZE, PL0_DPE_ACTIVE_CONTEXT_THRESHOLD, PL0_PAUSER_FLAG,
-    PL1_DPE_ACTIVE_CONTEXT_THRESHOLD,
+    dice, CptraDpeTypes, DisableAttestationCmd, DpeConfig, DpeCrypto, DpePlatform, Mailbox,
+    MAX_CERT_CHAIN_SIZE, PL0_PAUSER_FLAG,
 };
 
 use crate::dpe_crypto::{ExportedCdiHandles, EXPORTED_HANDLES_NUM};
+use crate::events::EventQueue;
+use crate::jobs::JobSlot;
+use crate::transfer::TransferState;
 use arrayvec::ArrayVec;
 use caliptra_cfi_derive_git::{cfi_impl_fn, cfi_mod_fn};
 use caliptra_cfi_lib_git::{cfi_assert, cfi_assert_eq, cfi_assert_eq_12_words, cfi_launder};
-use caliptra_common::mailbox_api::AddSubjectAltNameReq;
+use caliptra_common::mailbox_api::{AddSubjectAltNameReq, DeriveKeyResp};
 use caliptra_drivers::KeyId;
 use caliptra_drivers::{
     cprint, cprintln, pcr_log::RT_FW_JOURNEY_PCR, Array4x12, CaliptraError, CaliptraResult,
-    DataVault, Ecc384, KeyVault, Lms, PersistentDataAccessor, Pic, ResetReason, Sha1, SocIfc,
+    DataVault, Ecc384, KeyVault, Lms, Mldsa87, PersistentDataAccessor, Pic, ResetReason, Sha1,
+    SocIfc,
 };
 use caliptra_drivers::{
     hand_off::DataStore, Ecc384PubKey, Hmac384, PcrBank, PcrId, Sha256, Sha256Alg, Sha2_512_384Acc,
@@ -42,8 +42,8 @@ use caliptra_registers::el2_pic_ctrl::El2PicCtrl;
 use caliptra_registers::mbox::enums::MboxStatusE;
 use caliptra_registers::{
     csrng::CsrngReg, dv::DvReg, ecc::EccReg, entropy_src::EntropySrcReg, hmac::HmacReg, kv::KvReg,
-    mbox::MboxCsr, pv::PvReg, sha256::Sha256Reg, sha512::Sha512Reg, sha512_acc::Sha512AccCsr,
-    soc_ifc::SocIfcReg, soc_ifc_trng::SocIfcTrngReg,
+    mbox::MboxCsr, mldsa::MldsaReg, pv::PvReg, sha256::Sha256Reg, sha512::Sha512Reg,
+    sha512_acc::Sha512AccCsr, soc_ifc::SocIfcReg, soc_ifc_trng::SocIfcTrngReg,
 };
 use caliptra_x509::{NotAfter, NotBefore};
 use dpe::context::{Context, ContextState, ContextType};
@@ -55,7 +55,6 @@ use dpe::{
     commands::{CommandExecution, DeriveContextCmd, DeriveContextFlags},
     context::ContextHandle,
     dpe_instance::{DpeEnv, DpeInstance, DpeTypes},
-    support::Support,
     DPE_PROFILE,
 };
 
@@ -92,6 +91,9 @@ pub struct Drivers {
     /// Ecc384 Engine
     pub ecc384: Ecc384,
 
+    /// ML-DSA-87 Engine
+    pub mldsa87: Mldsa87,
+
     pub persistent_data: PersistentDataAccessor,
 
     pub lms: Lms,
@@ -104,13 +106,25 @@ pub struct Drivers {
 
     pub cert_chain: ArrayVec<u8, MAX_CERT_CHAIN_SIZE>,
 
-    #[cfg(feature = "fips_self_test")]
-    pub self_test_status: SelfTestStatus,
-
     pub is_shutdown: bool,
 
     pub dmtf_device_info: Option<ArrayVec<u8, { AddSubjectAltNameReq::MAX_DEVICE_INFO_LEN }>>,
     pub exported_cdi_slots: ExportedCdiHandles,
+
+    /// Handle of the key DERIVE_KEY left in the key vault, if any
+    pub derived_key_handle: Option<[u8; DeriveKeyResp::KEY_HANDLE_SIZE]>,
+
+    /// Transfer opened by TRANSFER_BEGIN, if any
+    pub transfer: TransferState,
+
+    /// Background job started by JOB_START or SELF_TEST_START, if any
+    pub jobs: JobSlot,
+
+    /// Events waiting to be read with GET_EVENTS
+    pub events: EventQueue,
+
+    /// DPE configuration from the firmware manifest
+    pub dpe_config: DpeConfig,
 }
 
 impl Drivers {
@@ -138,23 +152,28 @@ impl Drivers {
             sha2_512_384_acc: Sha2_512_384Acc::new(Sha512AccCsr::new()),
             hmac384: Hmac384::new(HmacReg::new()),
             ecc384: Ecc384::new(EccReg::new()),
+            mldsa87: Mldsa87::new(MldsaReg::new()),
             sha1: Sha1::default(),
             lms: Lms::default(),
             trng,
             persistent_data: PersistentDataAccessor::new(),
             pcr_bank: PcrBank::new(PvReg::new()),
             pic: Pic::new(El2PicCtrl::new()),
-            #[cfg(feature = "fips_self_test")]
-            self_test_status: SelfTestStatus::Idle,
             cert_chain: ArrayVec::new(),
             is_shutdown: false,
             dmtf_device_info: None,
             exported_cdi_slots: [None; EXPORTED_HANDLES_NUM],
+            derived_key_handle: None,
+            transfer: TransferState::default(),
+            jobs: JobSlot::default(),
+            events: EventQueue::default(),
+            dpe_config: DpeConfig::default(),
         })
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
     pub fn run_reset_flow(&mut self) -> CaliptraResult<()> {
+        self.dpe_config = DpeConfig::from_manifest(&self.persistent_data.get().manifest1)?;
         Self::create_cert_chain(self)?;
         if self.persistent_data.get().attestation_disabled.get() {
             DisableAttestationCmd::execute(self)
@@ -405,6 +424,7 @@ impl Drivers {
                 &nf,
                 None,
                 None,
+                &drivers.dpe_config,
             ),
         };
 
@@ -414,7 +434,7 @@ impl Drivers {
         );
         let mut dpe = DpeInstance::new_auto_init(
             &mut env,
-            DPE_SUPPORT,
+            drivers.dpe_config.support,
             u32::from_be_bytes(*b"RTJM"),
             rt_journey_measurement,
             DpeInstanceFlags::empty(),
@@ -456,6 +476,7 @@ impl Drivers {
                 pl0_pauser_locality,
                 privilege_level.clone(),
                 &dpe,
+                &drivers.dpe_config,
             )?;
 
             let measurement_data = measurement_log_entry.pcr_entry.measured_data();
@@ -545,13 +566,27 @@ impl Drivers {
             self.persistent_data.get().manifest1.header.pl0_pauser,
             self.caller_privilege_level(),
             &self.persistent_data.get().dpe,
+            &self.dpe_config,
+        )
+    }
+
+    /// Whether the number of non-inactive DPE contexts of `privilege_level`
+    /// has reached its active context threshold.
+    pub(crate) fn dpe_context_threshold_reached(&self, privilege_level: PauserPrivileges) -> bool {
+        Self::is_dpe_context_threshold_exceeded_helper(
+            self.persistent_data.get().manifest1.header.pl0_pauser,
+            privilege_level,
+            &self.persistent_data.get().dpe,
+            &self.dpe_config,
         )
+        .is_err()
     }
 
     fn is_dpe_context_threshold_exceeded_helper(
         pl0_pauser: u32,
         caller_privilege_level: PauserPrivileges,
         dpe: &DpeInstance,
+        dpe_config: &DpeConfig,
     ) -> CaliptraResult<()> {
         let used_pl0_dpe_context_count = dpe
             .count_contexts(|c: &Context| {
@@ -568,8 +603,8 @@ impl Drivers {
 
         match (
             caller_privilege_level,
-            used_pl1_dpe_context_count.cmp(&PL1_DPE_ACTIVE_CONTEXT_THRESHOLD),
-            used_pl0_dpe_context_count.cmp(&PL0_DPE_ACTIVE_CONTEXT_THRESHOLD),
+            used_pl1_dpe_context_count.cmp(&dpe_config.pl1_context_threshold),
+            used_pl0_dpe_context_count.cmp(&dpe_config.pl0_context_threshold),
         ) {
             (PauserPrivileges::PL1, Equal, _) => {
                 Err(CaliptraError::RUNTIME_PL1_USED_DPE_CONTEXT_THRESHOLD_REACHED)
@@ -0,0 +1,135 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    events.rs
+
+Abstract:
+
+    File contains the runtime event queue and the GET_EVENTS mailbox command.
+
+--*/
+
+use crate::{Drivers, PauserPrivileges};
+
+use arrayvec::ArrayVec;
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{GetEventsResp, MailboxResp, RuntimeEvent, RuntimeEventEntry};
+use caliptra_drivers::MEASUREMENT_MAX_COUNT;
+use caliptra_error::{CaliptraError, CaliptraResult};
+
+// Conditions that are reported once when they start to hold
+const MEASUREMENT_LOG_NEAR_FULL: u32 = 1 << 0;
+const PL0_DPE_CONTEXT_THRESHOLD: u32 = 1 << 1;
+const PL1_DPE_CONTEXT_THRESHOLD: u32 = 1 << 2;
+
+/// Events waiting to be read by the SoC
+#[derive(Default)]
+pub struct EventQueue {
+    events: ArrayVec<RuntimeEventEntry, { GetEventsResp::MAX_EVENTS }>,
+    /// Number of events lost because the queue was full
+    dropped_count: u32,
+    /// Conditions that have been reported and still hold
+    raised: u32,
+}
+
+impl EventQueue {
+    /// Queue an event
+    ///
+    /// # Arguments
+    ///
+    /// * `event` - Event to report
+    /// * `data` - Event-specific detail
+    pub(crate) fn push(&mut self, event: RuntimeEvent, data: u32) {
+        let entry = RuntimeEventEntry {
+            event: event as u32,
+            data,
+        };
+        if self.events.try_push(entry).is_err() {
+            self.dropped_count = self.dropped_count.saturating_add(1);
+        }
+    }
+
+    /// Queue `event` when the condition `mask` starts to hold
+    fn update(&mut self, mask: u32, holds: bool, event: RuntimeEvent, data: u32) {
+        if !holds {
+            self.raised &= !mask;
+        } else if self.raised & mask == 0 {
+            self.raised |= mask;
+            self.push(event, data);
+        }
+    }
+}
+
+/// Queue events for conditions that started to hold, and notify the SoC if
+/// events are waiting
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+pub(crate) fn check_events(drivers: &mut Drivers) {
+    let meas_log_index = drivers.persistent_data.get().fht.meas_log_index;
+    // Near full means at least three quarters full
+    let near_full = meas_log_index as usize * 4 >= MEASUREMENT_MAX_COUNT * 3;
+    drivers.events.update(
+        MEASUREMENT_LOG_NEAR_FULL,
+        near_full,
+        RuntimeEvent::MeasurementLogNearFull,
+        meas_log_index,
+    );
+
+    let pl0_reached = drivers.dpe_context_threshold_reached(PauserPrivileges::PL0);
+    drivers.events.update(
+        PL0_DPE_CONTEXT_THRESHOLD,
+        pl0_reached,
+        RuntimeEvent::DpeContextThresholdReached,
+        0,
+    );
+    let pl1_reached = drivers.dpe_context_threshold_reached(PauserPrivileges::PL1);
+    drivers.events.update(
+        PL1_DPE_CONTEXT_THRESHOLD,
+        pl1_reached,
+        RuntimeEvent::DpeContextThresholdReached,
+        1,
+    );
+
+    // Writing a non-fatal error code asserts the non-fatal error interrupt
+    // to the SoC. Leave an error reported by the last command in place; the
+    // interrupt is already asserted for it.
+    let error_reported = drivers.soc_ifc.fw_error_non_fatal() != 0;
+    if !drivers.events.events.is_empty() && !error_reported {
+        caliptra_drivers::report_fw_error_non_fatal(CaliptraError::RUNTIME_EVENT_PENDING.into());
+    }
+}
+
+pub struct GetEventsCmd;
+impl GetEventsCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers) -> CaliptraResult<MailboxResp> {
+        match drivers.caller_privilege_level() {
+            // Events are shared by all PAUSERs, so only PL0 may read them
+            PauserPrivileges::PL0 => (),
+            PauserPrivileges::PL1 => {
+                return Err(CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL);
+            }
+        }
+
+        let queue = &mut drivers.events;
+        let mut resp = GetEventsResp {
+            event_count: queue.events.len() as u32,
+            dropped_count: queue.dropped_count,
+            ..Default::default()
+        };
+        resp.events
+            .get_mut(..queue.events.len())
+            .ok_or(CaliptraError::RUNTIME_INTERNAL)?
+            .copy_from_slice(&queue.events);
+        queue.events.clear();
+        queue.dropped_count = 0;
+
+        Ok(MailboxResp::GetEvents(resp))
+    }
+}
@@ -22,6 +22,7 @@ use caliptra_drivers::KeyVault;
 use caliptra_drivers::Sha256;
 use caliptra_drivers::Sha2_512_384Acc;
 use caliptra_drivers::Sha384;
+use caliptra_image_types::ImagePqcKeyType;
 use caliptra_registers::mbox::enums::MboxStatusE;
 use zeroize::Zeroize;
 
@@ -57,18 +58,24 @@ impl FipsModule {
             )
         };
 
+        let mldsa = env.persistent_data.get().manifest1.preamble.pqc_key_type
+            == ImagePqcKeyType::Mldsa as u32;
         env.persistent_data.get_mut().zeroize();
+        if mldsa {
+            env.persistent_data.pqc_mut().zeroize();
+        }
     }
 }
 
 #[cfg(feature = "fips_self_test")]
 pub mod fips_self_test_cmd {
     use super::*;
+    use crate::jobs::{execute_kat_step, KAT_STEPS};
     use crate::RtBootStatus::{RtFipSelfTestComplete, RtFipSelfTestStarted};
     use caliptra_cfi_lib_git::cfi_assert_eq_8_words;
     use caliptra_common::HexBytes;
     use caliptra_common::{verifier::FirmwareImageVerificationEnv, FMC_SIZE, RUNTIME_SIZE};
-    use caliptra_drivers::{ResetReason, ShaAccLockState};
+    use caliptra_drivers::ResetReason;
     use caliptra_image_types::{ImageTocEntry, RomInfo};
     use caliptra_image_verify::ImageVerifier;
     use zerocopy::IntoBytes;
@@ -78,22 +85,27 @@ pub mod fips_self_test_cmd {
         let ptr = toc.load_addr as *mut u8;
         core::slice::from_raw_parts(ptr, toc.size as usize)
     }
-    pub enum SelfTestStatus {
-        Idle,
-        InProgress(fn(&mut Drivers) -> CaliptraResult<()>),
-        Done,
-    }
+
+    /// Number of steps in the self test: the KATs, the ROM integrity test
+    /// and the image verification.
+    pub(crate) const STEPS: u32 = KAT_STEPS + 2;
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
     fn copy_and_verify_image(env: &mut Drivers) -> CaliptraResult<()> {
+        let mldsa_preamble_size = env.persistent_data.get().manifest1.mldsa_preamble_size() as u32;
+        let img_bundle_sz = env.persistent_data.get().manifest1.size
+            + mldsa_preamble_size
+            + env.persistent_data.get().manifest1.fmc.size
+            + env.persistent_data.get().manifest1.runtime.size;
+
         env.mbox.write_cmd(0)?;
-        env.mbox.set_dlen(
-            env.persistent_data.get().manifest1.size
-                + env.persistent_data.get().manifest1.fmc.size
-                + env.persistent_data.get().manifest1.runtime.size,
-        );
+        env.mbox.set_dlen(img_bundle_sz);
         env.mbox
             .copy_bytes_to_mbox(env.persistent_data.get().manifest1.as_bytes())?;
+        if mldsa_preamble_size != 0 {
+            env.mbox
+                .copy_bytes_to_mbox(env.persistent_data.pqc().mldsa_preamble1.as_bytes())?;
+        }
 
         let fmc_toc = &env.persistent_data.get().manifest1.fmc;
         let rt_toc = &env.persistent_data.get().manifest1.runtime;
@@ -116,67 +128,50 @@ pub mod fips_self_test_cmd {
             sha384: &mut env.sha384,
             soc_ifc: &mut env.soc_ifc,
             ecc384: &mut env.ecc384,
+            mldsa87: &mut env.mldsa87,
             data_vault: &mut env.data_vault,
             pcr_bank: &mut env.pcr_bank,
             image: env.mbox.raw_mailbox_contents(),
         };
 
+        let manifest = &env.persistent_data.get().manifest1;
+        let mldsa_preamble = venv
+            .image
+            .get(..img_bundle_sz as usize)
+            .and_then(|image| manifest.mldsa_preamble(image));
+
         let mut verifier = ImageVerifier::new(&mut venv);
         let _info = verifier.verify(
-            &env.persistent_data.get().manifest1,
-            env.persistent_data.get().manifest1.size
-                + env.persistent_data.get().manifest1.fmc.size
-                + env.persistent_data.get().manifest1.runtime.size,
+            manifest,
+            mldsa_preamble,
+            img_bundle_sz,
             ResetReason::UpdateReset,
         )?;
         cprintln!("[rt] Verify complete");
         Ok(())
     }
 
+    /// Run one step of the self test
+    ///
+    /// # Arguments
+    ///
+    /// * `env` - Drivers
+    /// * `step` - Index of the step, below `STEPS`
     #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
-    pub(crate) fn execute(env: &mut Drivers) -> CaliptraResult<()> {
-        caliptra_drivers::report_boot_status(RtFipSelfTestStarted.into());
-        cprintln!("[rt] FIPS self test");
-        execute_kats(env)?;
-        rom_integrity_test(env)?;
-        copy_and_verify_image(env)?;
-        caliptra_drivers::report_boot_status(RtFipSelfTestComplete.into());
-        Ok(())
-    }
-
-    /// Execute KAT for cryptographic algorithms implemented in H/W.
-    fn execute_kats(env: &mut Drivers) -> CaliptraResult<()> {
-        let mut kats_env = caliptra_kat::KatsEnv {
-            // SHA1 Engine
-            sha1: &mut env.sha1,
-
-            // sha256
-            sha256: &mut env.sha256,
-
-            // SHA2-384 Engine
-            sha384: &mut env.sha384,
-
-            // SHA2-512/384 Accelerator
-            sha2_512_384_acc: &mut env.sha2_512_384_acc,
-
-            // Hmac384 Engine
-            hmac384: &mut env.hmac384,
-
-            /// Cryptographically Secure Random Number Generator
-            trng: &mut env.trng,
-
-            // LMS Engine
-            lms: &mut env.lms,
-
-            /// Ecc384 Engine
-            ecc384: &mut env.ecc384,
-
-            /// SHA Acc Lock State
-            sha_acc_lock_state: ShaAccLockState::NotAcquired,
-        };
-
-        caliptra_kat::execute_kat(&mut kats_env)?;
-        Ok(())
+    pub(crate) fn execute_step(env: &mut Drivers, step: u32) -> CaliptraResult<()> {
+        if step == 0 {
+            caliptra_drivers::report_boot_status(RtFipSelfTestStarted.into());
+            cprintln!("[rt] FIPS self test");
+        }
+        if step < KAT_STEPS {
+            execute_kat_step(env, step)
+        } else if step == KAT_STEPS {
+            rom_integrity_test(env)
+        } else {
+            copy_and_verify_image(env)?;
+            caliptra_drivers::report_boot_status(RtFipSelfTestComplete.into());
+            Ok(())
+        }
     }
 
     #[cfg_attr(not(feature = "no-cfi"), cfi_mod_fn)]
@@ -32,6 +32,7 @@ use crate::Drivers;
 /// * `drivers` - Drivers
 /// * `input` - KeyId containing the input data
 /// * `label` - Label for KDF
+/// * `context` - Context for KDF
 /// * `priv_key` - KeyId which the private key should be written to
 ///
 /// # Returns
@@ -42,13 +43,14 @@ fn ecc384_key_gen(
     drivers: &mut Drivers,
     input: KeyId,
     label: &[u8],
+    context: Option<&[u8]>,
     priv_key: KeyId,
 ) -> CaliptraResult<Ecc384KeyPair> {
     hmac384_kdf(
         &mut drivers.hmac384,
         KeyReadArgs::new(input).into(),
         label,
-        None,
+        context,
         &mut drivers.trng,
         KeyWriteArgs::new(
             KEY_ID_TMP,
@@ -129,7 +131,7 @@ impl Hmac {
         label: &[u8],
         data: &[u8],
     ) -> CaliptraResult<Array4x12> {
-        let keypair_result = ecc384_key_gen(drivers, input, label, KEY_ID_TMP);
+        let keypair_result = ecc384_key_gen(drivers, input, label, None, KEY_ID_TMP);
         if cfi_launder(keypair_result.is_ok()) {
             cfi_assert!(keypair_result.is_ok());
         } else {
@@ -166,4 +168,49 @@ impl Hmac {
 
         hmac_result
     }
+    /// Derive key material from a key in KV that may leave Caliptra. The KDF
+    /// output seeds an ECC keypair, and the digest of the public key
+    /// coordinates is returned. This is necessary because the hardware does
+    /// not expose the output of a KDF keyed from KV.
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `input` - KeyId containing the KDF key
+    /// * `label` - Label for KDF
+    /// * `context` - Context for KDF
+    ///
+    /// # Returns
+    ///
+    /// * `Array4x12` - Derived key material
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    pub fn ecc384_kdf(
+        drivers: &mut Drivers,
+        input: KeyId,
+        label: &[u8],
+        context: &[u8],
+    ) -> CaliptraResult<Array4x12> {
+        let keypair_result = ecc384_key_gen(drivers, input, label, Some(context), KEY_ID_TMP);
+        if cfi_launder(keypair_result.is_ok()) {
+            cfi_assert!(keypair_result.is_ok());
+        } else {
+            cfi_assert!(keypair_result.is_err());
+        }
+        let mut keypair = keypair_result?;
+
+        let mut output = Array4x12::default();
+        let digest_result = || -> CaliptraResult<()> {
+            let mut hasher = drivers.sha384.digest_init()?;
+            hasher.update(keypair.pub_key.x.as_bytes())?;
+            hasher.update(keypair.pub_key.y.as_bytes())?;
+            hasher.finalize(&mut output)
+        }();
+
+        // Clean up state.
+        unsafe { caliptra_drivers::Sha384::zeroize() }
+        keypair.pub_key.zeroize();
+        drivers.key_vault.erase_key(keypair.priv_key)?;
+
+        digest_result.map(|_| output)
+    }
 }
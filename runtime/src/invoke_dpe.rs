@@ -75,6 +75,7 @@ impl InvokeDpeCmd {
                     &nf,
                     None,
                     Some(ueid),
+                    &drivers.dpe_config,
                 ),
             };
 
@@ -86,6 +87,9 @@ impl InvokeDpeCmd {
             }
             let command = Command::deserialize(&cmd.data[..cmd.data_size as usize])
                 .map_err(|_| CaliptraError::RUNTIME_DPE_COMMAND_DESERIALIZATION_FAILED)?;
+            if !drivers.dpe_config.command_allowed(&command) {
+                return Err(CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED);
+            }
             let flags = pdata.manifest1.header.flags;
 
             let mut dpe = &mut pdata.dpe;
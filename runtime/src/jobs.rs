@@ -0,0 +1,450 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    jobs.rs
+
+Abstract:
+
+    File contains the JOB_START, JOB_GET_STATUS and JOB_CANCEL mailbox
+    commands, which run long operations in the background. Jobs that take
+    input are started by an inbound transfer of JOB_START, whose written
+    request is the job kind followed by the input.
+
+--*/
+
+#[cfg(feature = "fips_self_test")]
+use crate::fips::fips_self_test_cmd;
+use crate::Drivers;
+
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{
+    JobCancelReq, JobGetStatusReq, JobGetStatusResp, JobKind, JobStartReq, JobStartResp, JobState,
+    MailboxResp, Mldsa87VerifyJobInput,
+};
+use caliptra_drivers::{
+    Array4xN, Mldsa87Msg, Mldsa87PubKey, Mldsa87Result, Mldsa87Signature, Sha384Resumable,
+    ShaAccLockState,
+};
+use caliptra_error::{CaliptraError, CaliptraResult};
+use caliptra_image_types::ImagePqcKeyType;
+use caliptra_kat::{
+    Ecc384Kat, Hmac384KdfKat, LmsKat, Mldsa87Kat, Sha1Kat, Sha256Kat, Sha2_512_384AccKat, Sha384Kat,
+};
+use core::mem::size_of;
+use zerocopy::FromBytes;
+
+/// Number of steps in a KAT job, one per KAT
+pub(crate) const KAT_STEPS: u32 = 8;
+
+/// Offset of the job input in the transfer staging buffer, after the kind
+const JOB_INPUT_OFFSET: usize = size_of::<u32>();
+
+/// Input hashed by one step of a SHA-384 job, a multiple of the block size
+const SHA384_STEP_SIZE: u32 = 1024;
+
+type JobResult = [u8; JobGetStatusResp::MAX_RESULT_SIZE];
+
+#[derive(Clone, Copy)]
+struct Job {
+    id: u32,
+    kind: JobKind,
+    state: JobState,
+    steps_done: u32,
+    /// PAUSER of the caller that started the job
+    locality: u32,
+    /// Size of the input in the transfer staging buffer, for jobs that take
+    /// input
+    input_size: u32,
+    /// Digest of the input hashed so far by a SHA-384 job
+    sha384: Sha384Resumable,
+    result_size: u32,
+    result: JobResult,
+}
+
+impl Job {
+    fn total_steps(&self) -> u32 {
+        match self.kind {
+            JobKind::Kat => KAT_STEPS,
+            #[cfg(feature = "fips_self_test")]
+            JobKind::SelfTest => fips_self_test_cmd::STEPS,
+            JobKind::Sha384 => self.input_size.saturating_sub(1) / SHA384_STEP_SIZE + 1,
+            // The engine verifies the signature in one operation
+            JobKind::Mldsa87Verify => 1,
+            _ => 0,
+        }
+    }
+}
+
+/// Whether jobs of `kind` take input from an inbound transfer
+fn takes_input(kind: JobKind) -> bool {
+    matches!(kind, JobKind::Sha384 | JobKind::Mldsa87Verify)
+}
+
+/// State of the background jobs. There is room for one job, which stays
+/// until a new job replaces it so its status can still be read.
+#[derive(Default)]
+pub struct JobSlot {
+    last_id: u32,
+    job: Option<Job>,
+}
+
+impl JobSlot {
+    /// Whether a job is running
+    pub(crate) fn is_running(&self) -> bool {
+        matches!(self.job, Some(job) if job.state == JobState::Running)
+    }
+
+    /// Whether a running job still has to read its input from the transfer
+    /// staging buffer
+    pub(crate) fn uses_staging(&self) -> bool {
+        matches!(self.job, Some(job) if job.state == JobState::Running && takes_input(job.kind))
+    }
+
+    /// Start a job, replacing the finished one if any
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - Operation to run
+    /// * `locality` - PAUSER of the caller
+    /// * `input_size` - Size of the staged input, for jobs that take input
+    ///
+    /// # Returns
+    ///
+    /// * `u32` - ID of the new job
+    pub(crate) fn start(
+        &mut self,
+        kind: JobKind,
+        locality: u32,
+        input_size: u32,
+    ) -> CaliptraResult<u32> {
+        match kind {
+            JobKind::Kat | JobKind::Sha384 | JobKind::Mldsa87Verify => (),
+            #[cfg(feature = "fips_self_test")]
+            JobKind::SelfTest => (),
+            _ => return Err(CaliptraError::RUNTIME_JOB_INVALID_KIND),
+        }
+        if self.is_running() {
+            return Err(CaliptraError::RUNTIME_JOB_IN_PROGRESS);
+        }
+
+        let id = self.last_id.wrapping_add(1).max(1);
+        self.last_id = id;
+        self.job = Some(Job {
+            id,
+            kind,
+            state: JobState::Running,
+            steps_done: 0,
+            locality,
+            input_size,
+            sha384: Sha384Resumable::default(),
+            result_size: 0,
+            result: [0u8; JobGetStatusResp::MAX_RESULT_SIZE],
+        });
+        Ok(id)
+    }
+
+    /// Release the job if it is a finished job of `kind`
+    ///
+    /// # Returns
+    ///
+    /// * `bool` - Whether a job was released
+    pub(crate) fn take_done(&mut self, kind: JobKind) -> bool {
+        match self.job {
+            Some(job) if job.kind == kind && job.state == JobState::Done => {
+                self.job = None;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Find the job
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Job ID returned by JOB_START
+    /// * `locality` - PAUSER of the caller
+    fn get_mut(&mut self, id: u32, locality: u32) -> CaliptraResult<&mut Job> {
+        match self.job.as_mut() {
+            Some(job) if job.id == id && job.locality == locality => Ok(job),
+            _ => Err(CaliptraError::RUNTIME_JOB_INVALID_ID),
+        }
+    }
+}
+
+/// Run one KAT
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `step` - Index of the KAT, below `KAT_STEPS`
+pub(crate) fn execute_kat_step(drivers: &mut Drivers, step: u32) -> CaliptraResult<()> {
+    // The ML-DSA engine is only tested when the image is signed with it
+    let manifest = &drivers.persistent_data.get().manifest1;
+    let mldsa = manifest.preamble.pqc_key_type == ImagePqcKeyType::Mldsa as u32;
+    match step {
+        0 => Sha1Kat::default().execute(&mut drivers.sha1),
+        1 => Sha256Kat::default().execute(&mut drivers.sha256),
+        2 => Sha384Kat::default().execute(&mut drivers.sha384),
+        3 => Sha2_512_384AccKat::default()
+            .execute(&mut drivers.sha2_512_384_acc, ShaAccLockState::NotAcquired),
+        4 => Ecc384Kat::default().execute(&mut drivers.ecc384, &mut drivers.trng),
+        5 => Hmac384KdfKat::default().execute(&mut drivers.hmac384, &mut drivers.trng),
+        6 => LmsKat::default().execute(&mut drivers.sha256, &mut drivers.lms),
+        7 if mldsa => Mldsa87Kat::default().execute(&mut drivers.mldsa87, &mut drivers.trng),
+        _ => Ok(()),
+    }
+}
+
+/// Read an ML-DSA-87 operand given in the byte order of FIPS 204, which the
+/// engine takes as little-endian words
+fn mldsa87_operand<const W: usize, const B: usize>(bytes: &[u8; B]) -> Array4xN<W, B> {
+    let mut operand = Array4xN::<W, B>::default();
+    for (word, chunk) in operand.0.iter_mut().zip(bytes.chunks_exact(4)) {
+        *word = u32::from_le_bytes(chunk.try_into().unwrap_or_default());
+    }
+    operand
+}
+
+/// Hash the next part of the input of a SHA-384 job
+///
+/// The engine holds the digest between steps. If another command used the
+/// engine meanwhile, the digest starts over from the beginning of the input.
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `job` - Job to run, which keeps the digest between steps
+/// * `result` - Output of the job
+///
+/// # Returns
+///
+/// * `Option<usize>` - Size of the output once the input is hashed
+fn execute_sha384_step(
+    drivers: &mut Drivers,
+    job: &mut Job,
+    result: &mut JobResult,
+) -> CaliptraResult<Option<usize>> {
+    if !drivers.sha384.can_resume(&job.sha384) {
+        job.sha384 = Sha384Resumable::default();
+    }
+    let input = drivers
+        .persistent_data
+        .transfer_staging()
+        .get(JOB_INPUT_OFFSET..JOB_INPUT_OFFSET + job.input_size as usize)
+        .and_then(|input| input.get(job.sha384.data_size()..))
+        .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+    match input.get(..SHA384_STEP_SIZE as usize) {
+        Some(part) if part.len() < input.len() => {
+            drivers.sha384.resumable_update(&mut job.sha384, part)?;
+            Ok(None)
+        }
+        _ => {
+            let digest: [u8; 48] = drivers
+                .sha384
+                .resumable_finalize(&mut job.sha384, input)?
+                .into();
+            result[..digest.len()].copy_from_slice(&digest);
+            Ok(Some(digest.len()))
+        }
+    }
+}
+
+/// Run a job on the input staged by its transfer
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `job` - Job to run
+/// * `result` - Output of the job
+///
+/// # Returns
+///
+/// * `Option<usize>` - Size of the output, or None if the input was rejected
+fn execute_input_job(
+    drivers: &mut Drivers,
+    job: &Job,
+    result: &mut JobResult,
+) -> CaliptraResult<Option<usize>> {
+    let input = drivers
+        .persistent_data
+        .transfer_staging()
+        .get(JOB_INPUT_OFFSET..JOB_INPUT_OFFSET + job.input_size as usize)
+        .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+    match job.kind {
+        JobKind::Mldsa87Verify => {
+            let input = Mldsa87VerifyJobInput::ref_from_bytes(input)
+                .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+            let pub_key: Mldsa87PubKey = mldsa87_operand(&input.pub_key);
+            let signature: Mldsa87Signature = mldsa87_operand(&input.signature);
+            let message: Mldsa87Msg = mldsa87_operand(&input.message);
+            match drivers.mldsa87.verify(&pub_key, &message, &signature)? {
+                Mldsa87Result::Success => Ok(Some(0)),
+                Mldsa87Result::SigVerifyFailed => Ok(None),
+            }
+        }
+        _ => Err(CaliptraError::RUNTIME_JOB_INVALID_KIND),
+    }
+}
+
+/// Run the next step of the running job, if any
+///
+/// A failed KAT or self test step is a failed self test, so the caller
+/// treats it as fatal. Jobs that take input fail on their own instead.
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+pub(crate) fn execute_job_step(drivers: &mut Drivers) -> CaliptraResult<()> {
+    let job = match drivers.jobs.job {
+        Some(job) if job.state == JobState::Running => job,
+        _ => return Ok(()),
+    };
+    match job.kind {
+        JobKind::Kat => execute_kat_step(drivers, job.steps_done)?,
+        #[cfg(feature = "fips_self_test")]
+        JobKind::SelfTest => fips_self_test_cmd::execute_step(drivers, job.steps_done)?,
+        JobKind::Sha384 => {
+            let mut job = job;
+            let mut result = [0u8; JobGetStatusResp::MAX_RESULT_SIZE];
+            let output = execute_sha384_step(drivers, &mut job, &mut result);
+            match output {
+                Ok(Some(size)) => {
+                    job.steps_done = job.total_steps();
+                    job.result = result;
+                    job.result_size = size as u32;
+                    job.state = JobState::Done;
+                }
+                // Steps follow the hashed input, so starting over resets them
+                Ok(None) => job.steps_done = (job.sha384.data_size() as u32) / SHA384_STEP_SIZE,
+                Err(_) => job.state = JobState::Failed,
+            }
+            drivers.jobs.job = Some(job);
+            return Ok(());
+        }
+        JobKind::Mldsa87Verify => {
+            let mut result = [0u8; JobGetStatusResp::MAX_RESULT_SIZE];
+            let output = execute_input_job(drivers, &job, &mut result);
+            if let Some(job) = drivers.jobs.job.as_mut() {
+                job.steps_done += 1;
+                match output {
+                    Ok(Some(size)) => {
+                        job.result = result;
+                        job.result_size = size as u32;
+                        job.state = JobState::Done;
+                    }
+                    _ => job.state = JobState::Failed,
+                }
+            }
+            return Ok(());
+        }
+        _ => return Err(CaliptraError::RUNTIME_JOB_INVALID_KIND),
+    }
+
+    if let Some(job) = drivers.jobs.job.as_mut() {
+        job.steps_done += 1;
+        if job.steps_done >= job.total_steps() {
+            job.state = JobState::Done;
+        }
+    }
+    Ok(())
+}
+
+pub struct JobStartCmd;
+impl JobStartCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = JobStartReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        // The self test is started by SELF_TEST_START, and jobs that take
+        // input by a transfer
+        let kind = JobKind::from(cmd.kind);
+        if kind == JobKind::SelfTest || takes_input(kind) {
+            return Err(CaliptraError::RUNTIME_JOB_INVALID_KIND);
+        }
+        let job_id = drivers.jobs.start(kind, drivers.mbox.user(), 0)?;
+
+        Ok(MailboxResp::JobStart(JobStartResp {
+            job_id,
+            ..Default::default()
+        }))
+    }
+
+    /// Start a job on the request written by an inbound transfer: the job
+    /// kind followed by the input of the job
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `request_size` - Size of the request in the transfer staging buffer
+    pub(crate) fn execute_staged(
+        drivers: &mut Drivers,
+        request_size: usize,
+    ) -> CaliptraResult<MailboxResp> {
+        let request = drivers
+            .persistent_data
+            .transfer_staging()
+            .get(..request_size)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let (kind, input) = u32::read_from_prefix(request)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let kind = JobKind::from(kind);
+        if !takes_input(kind) {
+            return Err(CaliptraError::RUNTIME_JOB_INVALID_KIND);
+        }
+        let input_size = input.len() as u32;
+        let job_id = drivers.jobs.start(kind, drivers.mbox.user(), input_size)?;
+
+        Ok(MailboxResp::JobStart(JobStartResp {
+            job_id,
+            ..Default::default()
+        }))
+    }
+}
+
+pub struct JobGetStatusCmd;
+impl JobGetStatusCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = JobGetStatusReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let job = drivers.jobs.get_mut(cmd.job_id, drivers.mbox.user())?;
+
+        Ok(MailboxResp::JobGetStatus(JobGetStatusResp {
+            state: job.state as u32,
+            steps_done: job.steps_done,
+            total_steps: job.total_steps(),
+            result_size: job.result_size,
+            result: job.result,
+            ..Default::default()
+        }))
+    }
+}
+
+pub struct JobCancelCmd;
+impl JobCancelCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = JobCancelReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let job = drivers.jobs.get_mut(cmd.job_id, drivers.mbox.user())?;
+        // The self test runs to completion once started
+        if job.kind == JobKind::SelfTest {
+            return Err(CaliptraError::RUNTIME_JOB_NOT_CANCELLABLE);
+        }
+        // A finished job keeps its state
+        if job.state == JobState::Running {
+            job.state = JobState::Cancelled;
+        }
+
+        Ok(MailboxResp::default())
+    }
+}
@@ -16,11 +16,14 @@ Abstract:
 mod authorize_and_stash;
 mod capabilities;
 mod certify_key_extended;
+mod derive_key;
 pub mod dice;
 mod disable;
+mod dpe_config;
 mod dpe_crypto;
 mod dpe_platform;
 mod drivers;
+mod events;
 pub mod fips;
 mod get_fmc_alias_csr;
 mod get_idev_csr;
@@ -28,13 +31,16 @@ pub mod handoff;
 mod hmac;
 pub mod info;
 mod invoke_dpe;
+mod jobs;
 mod pcr;
 mod populate_idev;
 mod revoke_exported_cdi_handle;
 mod set_auth_manifest;
+mod sign_with_dpe_key;
 mod sign_with_exported_ecdsa;
 mod stash_measurement;
 mod subject_alt_name;
+mod transfer;
 mod update;
 mod verify;
 
@@ -49,19 +55,30 @@ use mailbox::Mailbox;
 
 use crate::capabilities::CapabilitiesCmd;
 pub use crate::certify_key_extended::CertifyKeyExtendedCmd;
+use crate::derive_key::{DeriveKeyCmd, RevokeDerivedKeyCmd};
+use crate::events::GetEventsCmd;
 pub use crate::hmac::Hmac;
+use crate::jobs::{JobCancelCmd, JobGetStatusCmd, JobStartCmd};
 use crate::revoke_exported_cdi_handle::RevokeExportedCdiHandleCmd;
+use crate::sign_with_dpe_key::SignWithDpeKeyCmd;
 use crate::sign_with_exported_ecdsa::SignWithExportedEcdsaCmd;
 pub use crate::subject_alt_name::AddSubjectAltNameCmd;
-pub use authorize_and_stash::{IMAGE_AUTHORIZED, IMAGE_HASH_MISMATCH, IMAGE_NOT_AUTHORIZED};
+use crate::transfer::{TransferBeginCmd, TransferContinueCmd, TransferEndCmd, TransferWriteCmd};
+pub use authorize_and_stash::{
+    IMAGE_AUTHORIZED, IMAGE_HASH_MISMATCH, IMAGE_LOAD_ADDRESS_MISMATCH, IMAGE_NOT_AUTHORIZED,
+    IMAGE_SIZE_MISMATCH, IMAGE_SVN_TOO_LOW, IMAGE_VERSION_MISMATCH,
+};
 pub use caliptra_common::fips::FipsVersionCmd;
-pub use dice::{GetFmcAliasCertCmd, GetLdevCertCmd, IDevIdCertCmd};
+pub use dice::{
+    GetFmcAliasCertCmd, GetFmcAliasMldsaCertCmd, GetLdevCertCmd, GetLdevMldsaCertCmd, IDevIdCertCmd,
+};
 pub use disable::DisableAttestationCmd;
+pub use dpe_config::DpeConfig;
 use dpe_crypto::DpeCrypto;
 pub use dpe_platform::{DpePlatform, VENDOR_ID, VENDOR_SKU};
-pub use fips::FipsShutdownCmd;
 #[cfg(feature = "fips_self_test")]
-pub use fips::{fips_self_test_cmd, fips_self_test_cmd::SelfTestStatus};
+pub use fips::fips_self_test_cmd;
+pub use fips::FipsShutdownCmd;
 pub use populate_idev::PopulateIDevIdCertCmd;
 
 pub use get_fmc_alias_csr::GetFmcAliasCsrCmd;
@@ -73,7 +90,9 @@ pub use set_auth_manifest::SetAuthManifestCmd;
 pub use stash_measurement::StashMeasurementCmd;
 pub use verify::{EcdsaVerifyCmd, LmsVerifyCmd};
 pub mod packet;
-use caliptra_common::mailbox_api::{CommandId, MailboxResp};
+#[cfg(feature = "fips_self_test")]
+use caliptra_common::mailbox_api::JobKind;
+use caliptra_common::mailbox_api::{CommandId, MailboxResp, RuntimeEvent};
 use packet::Packet;
 pub mod tagging;
 use tagging::{GetTaggedTciCmd, TagTciCmd};
@@ -92,8 +111,8 @@ use dpe::{
 pub use dpe::{context::ContextState, tci::TciMeasurement, DpeInstance, U8Bool, MAX_HANDLES};
 
 use crate::{
-    dice::GetRtAliasCertCmd,
-    pcr::{ExtendPcrCmd, GetPcrQuoteCmd},
+    dice::{GetRtAliasCertCmd, GetRtAliasMldsaCertCmd},
+    pcr::{ExtendPcrCmd, GetMeasurementLogCmd, GetPcrLogCmd, GetPcrQuoteCmd},
 };
 
 const RUNTIME_BOOT_STATUS_BASE: u32 = 0x600;
@@ -133,22 +152,21 @@ impl DpeTypes for CptraDpeTypes {
 
 /// Run pending jobs and enter low power mode.
 fn enter_idle(drivers: &mut Drivers) {
-    // Run pending jobs before entering low power mode.
-    #[cfg(feature = "fips_self_test")]
-    if let SelfTestStatus::InProgress(execute) = drivers.self_test_status {
+    // Run pending jobs before entering low power mode. Jobs run one step at
+    // a time so that mailbox commands are handled between steps.
+    if drivers.jobs.is_running() {
         let lock = drivers.mbox.lock();
         if lock == false {
-            let result = execute(drivers);
+            let result = jobs::execute_job_step(drivers);
             drivers.mbox.unlock();
-            match result {
-                Ok(_) => drivers.self_test_status = SelfTestStatus::Done,
-                Err(e) => caliptra_common::handle_fatal_error(e.into()),
+            if let Err(e) = result {
+                caliptra_common::handle_fatal_error(e.into());
             }
         } else {
             cfi_assert!(lock);
-            // Don't enter low power mode when in progress
-            return;
         }
+        // Don't enter low power mode when in progress
+        return;
     }
 
     #[cfg(feature = "riscv")]
@@ -202,31 +220,35 @@ fn handle_command(drivers: &mut Drivers) -> CaliptraResult<MboxStatusE> {
         CommandId::POPULATE_IDEV_CERT => PopulateIDevIdCertCmd::execute(drivers, cmd_bytes),
         CommandId::GET_FMC_ALIAS_CERT => GetFmcAliasCertCmd::execute(drivers),
         CommandId::GET_RT_ALIAS_CERT => GetRtAliasCertCmd::execute(drivers),
+        CommandId::GET_LDEV_MLDSA_CERT => GetLdevMldsaCertCmd::execute(drivers),
+        CommandId::GET_FMC_ALIAS_MLDSA_CERT => GetFmcAliasMldsaCertCmd::execute(drivers),
+        CommandId::GET_RT_ALIAS_MLDSA_CERT => GetRtAliasMldsaCertCmd::execute(drivers),
         CommandId::ADD_SUBJECT_ALT_NAME => AddSubjectAltNameCmd::execute(drivers, cmd_bytes),
         CommandId::CERTIFY_KEY_EXTENDED => CertifyKeyExtendedCmd::execute(drivers, cmd_bytes),
         CommandId::INCREMENT_PCR_RESET_COUNTER => {
             IncrementPcrResetCounterCmd::execute(drivers, cmd_bytes)
         }
         CommandId::QUOTE_PCRS => GetPcrQuoteCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_PCR_LOG => GetPcrLogCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_MEASUREMENT_LOG => GetMeasurementLogCmd::execute(drivers, cmd_bytes),
         CommandId::VERSION => {
             FipsVersionCmd::execute(&drivers.soc_ifc).map(MailboxResp::FipsVersion)
         }
         CommandId::CAPABILITIES => CapabilitiesCmd::execute(),
         #[cfg(feature = "fips_self_test")]
-        CommandId::SELF_TEST_START => match drivers.self_test_status {
-            SelfTestStatus::Idle => {
-                drivers.self_test_status = SelfTestStatus::InProgress(fips_self_test_cmd::execute);
-                Ok(MailboxResp::default())
+        CommandId::SELF_TEST_START => {
+            match drivers
+                .jobs
+                .start(JobKind::SelfTest, drivers.mbox.user(), 0)
+            {
+                Ok(_) => Ok(MailboxResp::default()),
+                Err(_) => Err(CaliptraError::RUNTIME_SELF_TEST_IN_PROGRESS),
             }
-            _ => Err(CaliptraError::RUNTIME_SELF_TEST_IN_PROGRESS),
-        },
+        }
         #[cfg(feature = "fips_self_test")]
-        CommandId::SELF_TEST_GET_RESULTS => match drivers.self_test_status {
-            SelfTestStatus::Done => {
-                drivers.self_test_status = SelfTestStatus::Idle;
-                Ok(MailboxResp::default())
-            }
-            _ => Err(CaliptraError::RUNTIME_SELF_TEST_NOT_STARTED),
+        CommandId::SELF_TEST_GET_RESULTS => match drivers.jobs.take_done(JobKind::SelfTest) {
+            true => Ok(MailboxResp::default()),
+            false => Err(CaliptraError::RUNTIME_SELF_TEST_NOT_STARTED),
         },
         CommandId::SHUTDOWN => FipsShutdownCmd::execute(drivers),
         CommandId::SET_AUTH_MANIFEST => SetAuthManifestCmd::execute(drivers, cmd_bytes),
@@ -239,6 +261,17 @@ fn handle_command(drivers: &mut Drivers) -> CaliptraResult<MboxStatusE> {
         CommandId::REVOKE_EXPORTED_CDI_HANDLE => {
             RevokeExportedCdiHandleCmd::execute(drivers, cmd_bytes)
         }
+        CommandId::DERIVE_KEY => DeriveKeyCmd::execute(drivers, cmd_bytes),
+        CommandId::REVOKE_DERIVED_KEY => RevokeDerivedKeyCmd::execute(drivers, cmd_bytes),
+        CommandId::SIGN_WITH_DPE_KEY => SignWithDpeKeyCmd::execute(drivers, cmd_bytes),
+        CommandId::TRANSFER_BEGIN => TransferBeginCmd::execute(drivers, cmd_bytes),
+        CommandId::TRANSFER_CONTINUE => TransferContinueCmd::execute(drivers, cmd_bytes),
+        CommandId::TRANSFER_WRITE => TransferWriteCmd::execute(drivers, cmd_bytes),
+        CommandId::TRANSFER_END => TransferEndCmd::execute(drivers, cmd_bytes),
+        CommandId::JOB_START => JobStartCmd::execute(drivers, cmd_bytes),
+        CommandId::JOB_GET_STATUS => JobGetStatusCmd::execute(drivers, cmd_bytes),
+        CommandId::JOB_CANCEL => JobCancelCmd::execute(drivers, cmd_bytes),
+        CommandId::GET_EVENTS => GetEventsCmd::execute(drivers),
         _ => Err(CaliptraError::RUNTIME_UNIMPLEMENTED_COMMAND),
     };
     let resp = okmutref(&mut resp)?;
@@ -287,6 +320,9 @@ pub fn handle_mailbox_commands(drivers: &mut Drivers) -> CaliptraResult<()> {
                     caliptra_drivers::report_fw_error_non_fatal(
                         CaliptraError::RUNTIME_CMD_BUSY_DURING_WARM_RESET.into(),
                     );
+                    drivers
+                        .events
+                        .push(RuntimeEvent::AttestationDisabledWarmReset, 0);
                 }
                 Err(e) => {
                     cprintln!("{}", e.0);
@@ -300,6 +336,7 @@ pub fn handle_mailbox_commands(drivers: &mut Drivers) -> CaliptraResult<()> {
     #[cfg(feature = "riscv")]
     setup_mailbox_wfi(drivers);
     caliptra_common::wdt::stop_wdt(&mut drivers.soc_ifc);
+    events::check_events(drivers);
     loop {
         if drivers.is_shutdown {
             return Err(CaliptraError::RUNTIME_SHUTDOWN);
@@ -350,6 +387,7 @@ pub fn handle_mailbox_commands(drivers: &mut Drivers) -> CaliptraResult<()> {
                 }
             }
             caliptra_common::wdt::stop_wdt(&mut drivers.soc_ifc);
+            events::check_events(drivers);
         } else {
             cfi_assert!(!cmd_ready);
         }
@@ -15,11 +15,20 @@ Abstract:
 use crate::Drivers;
 use caliptra_cfi_derive_git::cfi_impl_fn;
 use caliptra_common::mailbox_api::{
-    ExtendPcrReq, IncrementPcrResetCounterReq, MailboxResp, MailboxRespHeader, QuotePcrsReq,
-    QuotePcrsResp,
+    ExtendPcrReq, GetMeasurementLogReq, GetMeasurementLogResp, GetPcrLogReq, GetPcrLogResp,
+    IncrementPcrResetCounterReq, MailboxResp, MailboxRespHeader, QuotePcrsReq, QuotePcrsResp,
+    MEASUREMENT_LOG_ENTRY_SIZE, PCR_LOG_ENTRY_SIZE,
 };
-use caliptra_drivers::{hand_off::DataStore, CaliptraError, CaliptraResult, PcrBank, PcrId};
-use zerocopy::FromBytes;
+use caliptra_drivers::{
+    hand_off::DataStore,
+    pcr_log::{MeasurementLogEntry, PcrLogEntry},
+    CaliptraError, CaliptraResult, PcrBank, PcrId,
+};
+use core::mem::size_of;
+use zerocopy::{FromBytes, Immutable, IntoBytes};
+
+const _: () = assert!(size_of::<PcrLogEntry>() == PCR_LOG_ENTRY_SIZE);
+const _: () = assert!(size_of::<MeasurementLogEntry>() == MEASUREMENT_LOG_ENTRY_SIZE);
 
 pub struct IncrementPcrResetCounterCmd;
 impl IncrementPcrResetCounterCmd {
@@ -98,3 +107,71 @@ impl ExtendPcrCmd {
         Ok(MailboxResp::default())
     }
 }
+
+/// Copy the page of `log` starting at `start_index` into `dst`
+///
+/// # Returns
+///
+/// * `usize` - The number of entries copied to `dst`
+fn copy_log_page<T: IntoBytes + Immutable, const N: usize>(
+    log: &[T],
+    start_index: u32,
+    dst: &mut [[u8; N]],
+) -> CaliptraResult<usize> {
+    let Some(page) = log.get(start_index as usize..) else {
+        return Err(CaliptraError::RUNTIME_LOG_INVALID_START_INDEX);
+    };
+    let count = page.len().min(dst.len());
+    for (dst, entry) in dst.iter_mut().zip(&page[..count]) {
+        dst.copy_from_slice(entry.as_bytes());
+    }
+    Ok(count)
+}
+
+pub struct GetPcrLogCmd;
+impl GetPcrLogCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = GetPcrLogReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        let persistent_data = drivers.persistent_data.get();
+        let log = persistent_data
+            .pcr_log
+            .get(..persistent_data.fht.pcr_log_index as usize)
+            .ok_or(CaliptraError::RUNTIME_LOG_INDEX_CORRUPTED)?;
+
+        let mut resp = GetPcrLogResp {
+            total_entries: log.len() as u32,
+            ..Default::default()
+        };
+        resp.entry_count = copy_log_page(log, cmd.start_index, &mut resp.entries)? as u32;
+
+        Ok(MailboxResp::GetPcrLog(resp))
+    }
+}
+
+pub struct GetMeasurementLogCmd;
+impl GetMeasurementLogCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = GetMeasurementLogReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
+
+        let persistent_data = drivers.persistent_data.get();
+        let log = persistent_data
+            .measurement_log
+            .get(..persistent_data.fht.meas_log_index as usize)
+            .ok_or(CaliptraError::RUNTIME_LOG_INDEX_CORRUPTED)?;
+
+        let mut resp = GetMeasurementLogResp {
+            total_entries: log.len() as u32,
+            ..Default::default()
+        };
+        resp.entry_count = copy_log_page(log, cmd.start_index, &mut resp.entries)? as u32;
+
+        Ok(MailboxResp::GetMeasurementLog(resp))
+    }
+}
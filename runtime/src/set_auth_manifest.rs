@@ -19,7 +19,8 @@ use crate::verify;
 use crate::{dpe_crypto::DpeCrypto, CptraDpeTypes, DpePlatform, Drivers};
 use caliptra_auth_man_types::{
     AuthManifestFlags, AuthManifestImageMetadata, AuthManifestImageMetadataCollection,
-    AuthManifestPreamble, AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT, AUTH_MANIFEST_MARKER,
+    AuthManifestPreamble, AuthorizationManifest, AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT,
+    AUTH_MANIFEST_MARKER,
 };
 use caliptra_cfi_derive_git::cfi_impl_fn;
 use caliptra_cfi_lib_git::cfi_launder;
@@ -28,8 +29,8 @@ use caliptra_common::mailbox_api::{
 };
 use caliptra_drivers::{
     pcr_log::PCR_ID_STASH_MEASUREMENT, Array4x12, Array4xN, AuthManifestImageMetadataList,
-    CaliptraError, CaliptraResult, Ecc384, Ecc384PubKey, Ecc384Signature, HashValue, Lms,
-    PersistentData, RomVerifyConfig, Sha256, Sha384, SocIfc,
+    AuthManifestImageMetadataStore, CaliptraError, CaliptraResult, Ecc384, Ecc384PubKey,
+    Ecc384Signature, HashValue, Lms, PersistentData, RomVerifyConfig, Sha256, Sha384, SocIfc,
 };
 use caliptra_image_types::{
     ImageDigest, ImageEccPubKey, ImageEccSignature, ImageLmsPublicKey, ImageLmsSignature,
@@ -46,6 +47,9 @@ use memoffset::offset_of;
 use zerocopy::{FromBytes, IntoBytes};
 use zeroize::Zeroize;
 
+// A manifest with the maximum number of image metadata entries must fit in a request.
+const _: () = assert!(size_of::<AuthorizationManifest>() <= SetAuthManifestReq::MAX_MAN_SIZE);
+
 pub struct SetAuthManifestCmd;
 impl SetAuthManifestCmd {
     fn sha384_digest(
@@ -353,16 +357,19 @@ impl SetAuthManifestCmd {
         Ok(())
     }
 
+    #[allow(clippy::too_many_arguments)]
     fn process_image_metadata_col(
         cmd_buf: &[u8],
         auth_manifest_preamble: &AuthManifestPreamble,
-        metadata_persistent: &mut AuthManifestImageMetadataCollection,
+        metadata_persistent: &mut AuthManifestImageMetadataStore,
+        min_svn: &mut u32,
         sha384: &mut Sha384,
         ecc384: &mut Ecc384,
         sha256: &mut Sha256,
         soc_ifc: &SocIfc,
     ) -> CaliptraResult<()> {
-        if cmd_buf.len() < size_of::<u32>() {
+        let header_size = AuthManifestImageMetadataCollection::header_size();
+        if cmd_buf.len() < header_size {
             Err(CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_INVALID_SIZE)?;
         }
 
@@ -378,7 +385,7 @@ impl SetAuthManifestCmd {
 
         // Typecast the mailbox buffer to the image metadata collection.
         let metadata_mailbox =
-            unsafe { &mut *(buf.as_ptr() as *mut AuthManifestImageMetadataCollection) };
+            unsafe { &*(buf.as_ptr() as *const AuthManifestImageMetadataCollection) };
 
         if metadata_mailbox.entry_count == 0
             || metadata_mailbox.entry_count > AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT as u32
@@ -386,16 +393,16 @@ impl SetAuthManifestCmd {
             Err(CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_INVALID_ENTRY_COUNT)?;
         }
 
-        // Check if the buffer contains the entry count and all the image metadata entries specified by the entry count.
-        if buf.len()
-            < (size_of::<u32>()
-                + metadata_mailbox.entry_count as usize * size_of::<AuthManifestImageMetadata>())
-        {
+        // Check if the buffer contains the collection header and all the image metadata entries specified by the entry count.
+        // Only these bytes are signed; any trailing bytes are ignored.
+        let col_size = header_size
+            + metadata_mailbox.entry_count as usize * size_of::<AuthManifestImageMetadata>();
+        if buf.len() < col_size {
             Err(CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_INVALID_SIZE)?;
         }
 
         // Calculate the digest of the image metadata collection.
-        let digest_metadata_col = Self::sha384_digest(sha384, buf, 0, metadata_size as u32)?;
+        let digest_metadata_col = Self::sha384_digest(sha384, buf, 0, col_size as u32)?;
 
         Self::verify_vendor_image_metadata_col(
             auth_manifest_preamble,
@@ -415,17 +422,118 @@ impl SetAuthManifestCmd {
             soc_ifc,
         )?;
 
-        // Sort the image metadata list by firmware ID in place. Also check for duplicate firmware IDs.        let slice =
-        let slice =
-            &mut metadata_mailbox.image_metadata_list[..metadata_mailbox.entry_count as usize];
+        // Reject a rollback to a collection older than the last accepted one.
+        let svn = metadata_mailbox.svn;
+        if svn < *min_svn {
+            Err(CaliptraError::RUNTIME_AUTH_MANIFEST_SVN_TOO_LOW)?;
+        }
+
+        // Chunk 0 starts a new collection. Later chunks must follow the last
+        // accepted chunk of the same collection.
+        let collection_id = metadata_mailbox.collection_id;
+        let chunk_index = metadata_mailbox.chunk_index;
+        let chunk_count = metadata_mailbox.chunk_count;
+        if chunk_index >= chunk_count {
+            Err(CaliptraError::RUNTIME_AUTH_MANIFEST_CHUNK_OUT_OF_ORDER)?;
+        }
+        let same_collection = collection_id == metadata_persistent.collection_id
+            && chunk_count == metadata_persistent.chunk_count
+            && svn == metadata_persistent.svn;
+        if chunk_index == 0 {
+            // An equal SVN only allows reloading the collection accepted with
+            // it, so that another collection signed with the same SVN cannot
+            // be replayed over it.
+            if svn == *min_svn
+                && metadata_persistent.chunk_count != 0
+                && !(same_collection
+                    && metadata_persistent.first_chunk_digest == digest_metadata_col)
+            {
+                Err(CaliptraError::RUNTIME_AUTH_MANIFEST_SVN_TOO_LOW)?;
+            }
+
+            // Replaying chunk 0 would drop the later chunks of the collection.
+            if same_collection && metadata_persistent.next_chunk_index > 1 {
+                Err(CaliptraError::RUNTIME_AUTH_MANIFEST_COLLECTION_ALREADY_LOADED)?;
+            }
+        } else {
+            if metadata_persistent.chunk_count == 0
+                || chunk_index != metadata_persistent.next_chunk_index
+            {
+                Err(CaliptraError::RUNTIME_AUTH_MANIFEST_CHUNK_OUT_OF_ORDER)?;
+            }
+            if !same_collection {
+                Err(CaliptraError::RUNTIME_AUTH_MANIFEST_CHUNK_COLLECTION_MISMATCH)?;
+            }
+        }
+
+        let slice = &metadata_mailbox.image_metadata_list[..metadata_mailbox.entry_count as usize];
+
+        if chunk_index == 0 {
+            // Clear the previous image metadata collection.
+            metadata_persistent.zeroize();
+            metadata_persistent.svn = svn;
+            metadata_persistent.collection_id = collection_id;
+            metadata_persistent.chunk_count = chunk_count;
+            metadata_persistent.first_chunk_digest = digest_metadata_col;
+        }
+
+        Self::merge_image_metadata(metadata_persistent, slice)?;
+        metadata_persistent.next_chunk_index = chunk_index.saturating_add(1);
+        *min_svn = svn;
 
-        Self::sort_and_check_duplicate_fwid(slice)?;
+        Ok(())
+    }
 
-        // Clear the previous image metadata collection.
-        metadata_persistent.zeroize();
+    /// Merge an image metadata chunk into the sorted image metadata store.
+    ///
+    /// # Arguments
+    ///
+    /// * `store` - Image metadata store in persistent data.
+    /// * `entries` - Image metadata entries of the chunk, in any order.
+    ///
+    /// The chunk is sorted in the store, not in the mailbox. The store is left
+    /// unmodified on error.
+    fn merge_image_metadata(
+        store: &mut AuthManifestImageMetadataStore,
+        entries: &[AuthManifestImageMetadata],
+    ) -> CaliptraResult<()> {
+        let stored_count = store.entries().len();
+        let total_count = stored_count + entries.len();
+        if total_count > store.image_metadata_list.len() {
+            Err(CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_STORE_FULL)?;
+        }
 
-        // Copy the image metadata collection to the persistent data.
-        metadata_persistent.as_mut_bytes()[..buf.len()].copy_from_slice(buf);
+        for entry in entries {
+            if store
+                .entries()
+                .binary_search_by(|metadata| metadata.fw_id.cmp(&entry.fw_id))
+                .is_ok()
+            {
+                Err(
+                    CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_DUPLICATE_FIRMWARE_ID,
+                )?;
+            }
+        }
+
+        // Sort the chunk after the used entries. Also check for duplicate
+        // firmware IDs within the chunk.
+        let list = &mut store.image_metadata_list[..total_count];
+        list[stored_count..].copy_from_slice(entries);
+        if let Err(err) = Self::sort_and_check_duplicate_fwid(&mut list[stored_count..]) {
+            list[stored_count..].iter_mut().for_each(Zeroize::zeroize);
+            Err(err)?;
+        }
+
+        // Rotate each chunk entry into place so that no temporary buffer is
+        // needed.
+        let mut start = 0;
+        for j in stored_count..total_count {
+            let fw_id = list[j].fw_id;
+            let pos = start + list[start..j].partition_point(|metadata| metadata.fw_id < fw_id);
+            list[pos..=j].rotate_right(1);
+            start = pos + 1;
+        }
+        store.entry_count = total_count as u32;
 
         Ok(())
     }
@@ -486,6 +594,68 @@ impl SetAuthManifestCmd {
                 .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?
         };
 
+        let persistent_data = drivers.persistent_data.get_mut();
+        Self::set_auth_manifest(
+            manifest_buf,
+            &persistent_data.manifest1.preamble,
+            &mut persistent_data.auth_manifest_image_metadata_col,
+            &mut persistent_data.auth_manifest_min_svn,
+            &mut drivers.sha384,
+            &mut drivers.ecc384,
+            &mut drivers.sha256,
+            &drivers.soc_ifc,
+        )?;
+
+        Ok(MailboxResp::default())
+    }
+
+    /// Set the authorization manifest written by an inbound transfer
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `manifest_size` - Size of the manifest in the transfer staging buffer
+    pub(crate) fn execute_staged(
+        drivers: &mut Drivers,
+        manifest_size: usize,
+    ) -> CaliptraResult<MailboxResp> {
+        #[cfg(not(feature = "hw-2.x"))]
+        let persistent_data = drivers.persistent_data.get_mut();
+        #[cfg(not(feature = "hw-2.x"))]
+        let transfer_staging = &persistent_data.transfer_staging;
+        #[cfg(feature = "hw-2.x")]
+        let (persistent_data, pqc_persistent_data) = drivers.persistent_data.get_mut_and_pqc_mut();
+        #[cfg(feature = "hw-2.x")]
+        let transfer_staging = &pqc_persistent_data.transfer_staging;
+
+        let manifest_buf = transfer_staging
+            .get(..manifest_size)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        Self::set_auth_manifest(
+            manifest_buf,
+            &persistent_data.manifest1.preamble,
+            &mut persistent_data.auth_manifest_image_metadata_col,
+            &mut persistent_data.auth_manifest_min_svn,
+            &mut drivers.sha384,
+            &mut drivers.ecc384,
+            &mut drivers.sha256,
+            &drivers.soc_ifc,
+        )?;
+
+        Ok(MailboxResp::default())
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    fn set_auth_manifest(
+        manifest_buf: &[u8],
+        fw_preamble: &ImagePreamble,
+        metadata_persistent: &mut AuthManifestImageMetadataStore,
+        min_svn: &mut u32,
+        sha384: &mut Sha384,
+        ecc384: &mut Ecc384,
+        sha256: &mut Sha256,
+        soc_ifc: &SocIfc,
+    ) -> CaliptraResult<()> {
         let preamble_size = size_of::<AuthManifestPreamble>();
         let auth_manifest_preamble = {
             let err = CaliptraError::RUNTIME_AUTH_MANIFEST_PREAMBLE_SIZE_LT_MIN;
@@ -503,25 +673,24 @@ impl SetAuthManifestCmd {
             Err(CaliptraError::RUNTIME_AUTH_MANIFEST_PREAMBLE_SIZE_MISMATCH)?;
         }
 
-        let persistent_data = drivers.persistent_data.get_mut();
         // Verify the vendor signed data (vendor public keys + flags).
         Self::verify_vendor_signed_data(
             auth_manifest_preamble,
-            &persistent_data.manifest1.preamble,
-            &mut drivers.sha384,
-            &mut drivers.ecc384,
-            &mut drivers.sha256,
-            &drivers.soc_ifc,
+            fw_preamble,
+            sha384,
+            ecc384,
+            sha256,
+            soc_ifc,
         )?;
 
         // Verify the owner public keys.
         Self::verify_owner_pub_keys(
             auth_manifest_preamble,
-            &persistent_data.manifest1.preamble,
-            &mut drivers.sha384,
-            &mut drivers.ecc384,
-            &mut drivers.sha256,
-            &drivers.soc_ifc,
+            fw_preamble,
+            sha384,
+            ecc384,
+            sha256,
+            soc_ifc,
         )?;
 
         Self::process_image_metadata_col(
@@ -529,20 +698,20 @@ impl SetAuthManifestCmd {
                 .get(preamble_size..)
                 .ok_or(CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_INVALID_SIZE)?,
             auth_manifest_preamble,
-            &mut persistent_data.auth_manifest_image_metadata_col,
-            &mut drivers.sha384,
-            &mut drivers.ecc384,
-            &mut drivers.sha256,
-            &drivers.soc_ifc,
-        )?;
-
-        Ok(MailboxResp::default())
+            metadata_persistent,
+            min_svn,
+            sha384,
+            ecc384,
+            sha256,
+            soc_ifc,
+        )
     }
 }
 
 #[cfg(all(test))]
 mod tests {
     use super::*;
+    use zerocopy::FromZeros;
 
     fn is_sorted(slice: &[AuthManifestImageMetadata]) -> bool {
         for i in 0..slice.len() - 1 {
@@ -567,16 +736,19 @@ mod tests {
                 fw_id: 5,
                 flags: 0,
                 digest: [0u8; 48],
+                ..Default::default()
             },
             AuthManifestImageMetadata {
                 fw_id: 127,
                 flags: 0,
                 digest: [0u8; 48],
+                ..Default::default()
             },
             AuthManifestImageMetadata {
                 fw_id: 48,
                 flags: 0,
                 digest: [0u8; 48],
+                ..Default::default()
             },
         ];
         let resp = SetAuthManifestCmd::sort_and_check_duplicate_fwid(&mut list);
@@ -591,16 +763,19 @@ mod tests {
                 fw_id: 127,
                 flags: 0,
                 digest: [0u8; 48],
+                ..Default::default()
             },
             AuthManifestImageMetadata {
                 fw_id: 5,
                 flags: 0,
                 digest: [0u8; 48],
+                ..Default::default()
             },
             AuthManifestImageMetadata {
                 fw_id: 127,
                 flags: 0,
                 digest: [0u8; 48],
+                ..Default::default()
             },
         ];
         let resp = SetAuthManifestCmd::sort_and_check_duplicate_fwid(&mut list);
@@ -609,4 +784,71 @@ mod tests {
             CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_DUPLICATE_FIRMWARE_ID
         );
     }
+
+    fn metadata_list(fw_ids: &[u32]) -> [AuthManifestImageMetadata; 3] {
+        let mut list = [AuthManifestImageMetadata::default(); 3];
+        for (entry, fw_id) in list.iter_mut().zip(fw_ids) {
+            entry.fw_id = *fw_id;
+        }
+        list
+    }
+
+    #[test]
+    fn test_merge_image_metadata() {
+        let mut store = AuthManifestImageMetadataStore::new_zeroed();
+        let chunk0 = metadata_list(&[2, 8, 9]);
+        let chunk1 = metadata_list(&[10, 1, 5]);
+
+        SetAuthManifestCmd::merge_image_metadata(&mut store, &chunk0).unwrap();
+        SetAuthManifestCmd::merge_image_metadata(&mut store, &chunk1).unwrap();
+
+        assert_eq!(store.entry_count, 6);
+        assert!(is_sorted(store.entries()));
+        let fw_ids: [u32; 6] = core::array::from_fn(|i| store.entries()[i].fw_id);
+        assert_eq!(fw_ids, [1, 2, 5, 8, 9, 10]);
+    }
+
+    #[test]
+    fn test_merge_image_metadata_dupe() {
+        let mut store = AuthManifestImageMetadataStore::new_zeroed();
+        SetAuthManifestCmd::merge_image_metadata(&mut store, &metadata_list(&[2, 8, 9])).unwrap();
+
+        let resp =
+            SetAuthManifestCmd::merge_image_metadata(&mut store, &metadata_list(&[1, 8, 10]));
+        assert_eq!(
+            resp.unwrap_err(),
+            CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_DUPLICATE_FIRMWARE_ID
+        );
+
+        // The store is left unmodified.
+        assert_eq!(store.entry_count, 3);
+
+        let resp =
+            SetAuthManifestCmd::merge_image_metadata(&mut store, &metadata_list(&[1, 10, 1]));
+        assert_eq!(
+            resp.unwrap_err(),
+            CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_DUPLICATE_FIRMWARE_ID
+        );
+        assert_eq!(store.entry_count, 3);
+        let fw_ids: [u32; 3] = core::array::from_fn(|i| store.entries()[i].fw_id);
+        assert_eq!(fw_ids, [2, 8, 9]);
+    }
+
+    #[test]
+    fn test_merge_image_metadata_full() {
+        let mut store = AuthManifestImageMetadataStore::new_zeroed();
+        store.entry_count = store.image_metadata_list.len() as u32 - 2;
+        for (i, entry) in store.image_metadata_list.iter_mut().enumerate() {
+            entry.fw_id = i as u32;
+        }
+
+        let resp = SetAuthManifestCmd::merge_image_metadata(
+            &mut store,
+            &metadata_list(&[1000, 1001, 1002]),
+        );
+        assert_eq!(
+            resp.unwrap_err(),
+            CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_STORE_FULL
+        );
+    }
 }
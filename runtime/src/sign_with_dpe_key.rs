@@ -0,0 +1,295 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    sign_with_dpe_key.rs
+
+Abstract:
+
+    File contains SIGN_WITH_DPE_KEY mailbox command.
+
+--*/
+
+use crate::{
+    tagging::tagged_context_index, CptraDpeTypes, DpeCrypto, DpeEnv, DpePlatform, Drivers,
+    PauserPrivileges,
+};
+
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_cfi_lib_git::{cfi_assert, cfi_launder};
+use caliptra_common::mailbox_api::{
+    MailboxResp, SignWithDpeKeyFlags, SignWithDpeKeyReq, SignWithDpeKeyResp,
+};
+use caliptra_drivers::{Array4x12, PersistentData, Sha384, SocIfc};
+use caliptra_error::{CaliptraError, CaliptraResult};
+use crypto::{Crypto, Digest};
+use dpe::{
+    commands::{CertifyKeyCmd, CertifyKeyFlags, Command, CommandExecution, SignCmd, SignFlags},
+    context::{Context, ContextState},
+    response::{DpeErrorCode, Response},
+    DPE_PROFILE, MAX_HANDLES,
+};
+use zerocopy::FromBytes;
+
+// Keeps the HMAC key apart from the ECC key DPE derives for the same context.
+const HMAC_CDI_INFO: &[u8] = b"SIGN_WITH_DPE_KEY";
+const HMAC_KEY_INFO: &[u8] = b"HMAC";
+
+pub struct SignWithDpeKeyCmd;
+impl SignWithDpeKeyCmd {
+    /// Hash the TCIs of the context at `idx` and all of its ancestors
+    ///
+    /// # Arguments
+    ///
+    /// * `sha384` - SHA384 driver
+    /// * `persistent_data` - Persistent data holding DPE
+    /// * `idx` - Index of the context
+    ///
+    /// # Returns
+    ///
+    /// * `Array4x12` - Measurement of the context
+    fn measurement(
+        sha384: &mut Sha384,
+        persistent_data: &PersistentData,
+        idx: usize,
+    ) -> CaliptraResult<Array4x12> {
+        let contexts = &persistent_data.dpe.contexts;
+        let mut hasher = sha384.digest_init()?;
+        let mut idx = idx;
+        // Bounded so a corrupted parent chain cannot loop forever
+        for _ in 0..MAX_HANDLES {
+            let context = contexts
+                .get(idx)
+                .ok_or(CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED)?;
+            hasher.update(&context.tci.tci_cumulative.0)?;
+            hasher.update(&context.tci.tci_current.0)?;
+            if context.parent_idx == Context::ROOT_INDEX {
+                let mut measurement = Array4x12::default();
+                hasher.finalize(&mut measurement)?;
+                return Ok(measurement);
+            }
+            idx = context.parent_idx as usize;
+        }
+        Err(CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED)
+    }
+
+    /// Map a failed DPE command to a mailbox error
+    ///
+    /// # Arguments
+    ///
+    /// * `soc_ifc` - SOC interface
+    /// * `e` - Error returned by DPE
+    fn dpe_error(soc_ifc: &mut SocIfc, e: DpeErrorCode) -> CaliptraError {
+        // If there is extended error info, populate CPTRA_FW_EXTENDED_ERROR_INFO
+        if let Some(ext_err) = e.get_error_detail() {
+            soc_ifc.set_fw_extended_error(ext_err);
+        }
+        CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED
+    }
+
+    /// Sign `cmd.digest` with ECDSA using the key DPE derives for the context
+    /// at `idx`, and certify that key
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `cmd` - SIGN_WITH_DPE_KEY request
+    /// * `idx` - Index of the tagged context
+    /// * `resp` - Response to fill in
+    fn ecdsa_sign(
+        drivers: &mut Drivers,
+        cmd: &SignWithDpeKeyReq,
+        idx: usize,
+        resp: &mut SignWithDpeKeyResp,
+    ) -> CaliptraResult<()> {
+        let handle = drivers.persistent_data.get().dpe.contexts[idx].handle;
+        let certify_key_cmd = CertifyKeyCmd {
+            handle,
+            label: cmd.label,
+            flags: CertifyKeyFlags::empty(),
+            // PL1 cannot request X509
+            format: match drivers.caller_privilege_level() {
+                PauserPrivileges::PL0 => CertifyKeyCmd::FORMAT_X509,
+                PauserPrivileges::PL1 => CertifyKeyCmd::FORMAT_CSR,
+            },
+        };
+        let sign_cmd = SignCmd {
+            handle,
+            label: cmd.label,
+            flags: SignFlags::empty(),
+            digest: cmd.digest,
+        };
+        if !drivers
+            .dpe_config
+            .command_allowed(&Command::CertifyKey(&certify_key_cmd))
+            || !drivers
+                .dpe_config
+                .command_allowed(&Command::Sign(&sign_cmd))
+        {
+            return Err(CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED);
+        }
+
+        let hashed_rt_pub_key = drivers.compute_rt_alias_sn()?;
+        let key_id_rt_cdi = Drivers::get_key_id_rt_cdi(drivers)?;
+        let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
+        let pdata = drivers.persistent_data.get_mut();
+        let crypto = DpeCrypto::new(
+            &mut drivers.sha384,
+            &mut drivers.trng,
+            &mut drivers.ecc384,
+            &mut drivers.hmac384,
+            &mut drivers.key_vault,
+            &mut pdata.fht.rt_dice_pub_key,
+            key_id_rt_cdi,
+            key_id_rt_priv_key,
+            &mut drivers.exported_cdi_slots,
+        );
+        let pl0_pauser = pdata.manifest1.header.pl0_pauser;
+        let (nb, nf) = Drivers::get_cert_validity_info(&pdata.manifest1);
+        let ueid = &drivers.soc_ifc.fuse_bank().ueid();
+        let mut env = DpeEnv::<CptraDpeTypes> {
+            crypto,
+            platform: DpePlatform::new(
+                pl0_pauser,
+                &hashed_rt_pub_key,
+                &drivers.cert_chain,
+                &nb,
+                &nf,
+                None,
+                Some(ueid),
+                &drivers.dpe_config,
+            ),
+        };
+
+        let dpe = &mut pdata.dpe;
+        let locality = drivers.mbox.user();
+        let certify_key_resp = match certify_key_cmd.execute(dpe, &mut env, locality) {
+            Ok(Response::CertifyKey(certify_key_resp)) => certify_key_resp,
+            Ok(_) => return Err(CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED),
+            Err(e) => return Err(Self::dpe_error(&mut drivers.soc_ifc, e)),
+        };
+
+        // CertifyKey rotated the handle of the context
+        let sign_cmd = SignCmd {
+            handle: certify_key_resp.new_context_handle,
+            ..sign_cmd
+        };
+        let sign_resp = match sign_cmd.execute(dpe, &mut env, locality) {
+            Ok(Response::Sign(sign_resp)) => sign_resp,
+            Ok(_) => return Err(CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED),
+            Err(e) => return Err(Self::dpe_error(&mut drivers.soc_ifc, e)),
+        };
+
+        let cert_size = certify_key_resp.cert_size as usize;
+        let cert = certify_key_resp
+            .cert
+            .get(..cert_size)
+            .ok_or(CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED)?;
+        resp.cert
+            .get_mut(..cert_size)
+            .ok_or(CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED)?
+            .copy_from_slice(cert);
+        resp.cert_size = cert_size as u32;
+        resp.new_context_handle = sign_resp.new_context_handle.0;
+        resp.derived_pubkey_x = certify_key_resp.derived_pubkey_x;
+        resp.derived_pubkey_y = certify_key_resp.derived_pubkey_y;
+        resp.signature_r = sign_resp.sig_r;
+        resp.signature_s = sign_resp.sig_s;
+        Ok(())
+    }
+
+    /// HMAC `cmd.digest` with a key derived from the measurements of the
+    /// context at `idx`
+    ///
+    /// # Arguments
+    ///
+    /// * `drivers` - Drivers
+    /// * `cmd` - SIGN_WITH_DPE_KEY request
+    /// * `idx` - Index of the tagged context
+    /// * `resp` - Response to fill in
+    fn hmac_sign(
+        drivers: &mut Drivers,
+        cmd: &SignWithDpeKeyReq,
+        idx: usize,
+        resp: &mut SignWithDpeKeyResp,
+    ) -> CaliptraResult<()> {
+        let context = &drivers.persistent_data.get().dpe.contexts[idx];
+        // Callers may only use contexts in their own locality, as for DPE
+        // commands.
+        if context.state != ContextState::Active || context.locality != drivers.mbox.user() {
+            return Err(CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED);
+        }
+        let handle = context.handle;
+        let sign_cmd = SignCmd {
+            handle,
+            label: cmd.label,
+            flags: SignFlags::empty(),
+            digest: cmd.digest,
+        };
+        if !drivers
+            .dpe_config
+            .command_allowed(&Command::Sign(&sign_cmd))
+        {
+            return Err(CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED);
+        }
+        let measurement =
+            Self::measurement(&mut drivers.sha384, drivers.persistent_data.get(), idx)?;
+
+        let key_id_rt_cdi = Drivers::get_key_id_rt_cdi(drivers)?;
+        let key_id_rt_priv_key = Drivers::get_key_id_rt_priv_key(drivers)?;
+        let mut crypto = DpeCrypto::new(
+            &mut drivers.sha384,
+            &mut drivers.trng,
+            &mut drivers.ecc384,
+            &mut drivers.hmac384,
+            &mut drivers.key_vault,
+            &mut drivers.persistent_data.get_mut().fht.rt_dice_pub_key,
+            key_id_rt_cdi,
+            key_id_rt_priv_key,
+            &mut drivers.exported_cdi_slots,
+        );
+
+        let algs = DPE_PROFILE.alg_len();
+        let measurement = Digest::new(&<[u8; 48]>::from(measurement))
+            .map_err(|_| CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED)?;
+        let digest = Digest::new(&cmd.digest)
+            .map_err(|_| CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED)?;
+        let tag = crypto
+            .derive_cdi(algs, &measurement, HMAC_CDI_INFO)
+            .and_then(|cdi| {
+                crypto.hmac_sign_with_derived(algs, &cdi, &cmd.label, HMAC_KEY_INFO, &digest)
+            })
+            .map_err(|_| CaliptraError::RUNTIME_SIGN_WITH_DPE_KEY_FAILED)?;
+
+        // The HMAC key is never exposed, so there is no public key or
+        // certificate to return.
+        resp.new_context_handle = handle.0;
+        resp.signature_r = tag.into();
+        Ok(())
+    }
+
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = SignWithDpeKeyReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let flags = SignWithDpeKeyFlags::from_bits(cmd.flags)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+
+        let idx = tagged_context_index(drivers.persistent_data.get(), cmd.tag)?;
+
+        let mut resp = SignWithDpeKeyResp::default();
+        let hmac = flags.contains(SignWithDpeKeyFlags::HMAC);
+        if cfi_launder(hmac) {
+            cfi_assert!(hmac);
+            Self::hmac_sign(drivers, cmd, idx, &mut resp)?;
+        } else {
+            cfi_assert!(!hmac);
+            Self::ecdsa_sign(drivers, cmd, idx, &mut resp)?;
+        }
+
+        Ok(MailboxResp::SignWithDpeKey(resp))
+    }
+}
@@ -20,7 +20,7 @@ use caliptra_common::mailbox_api::{
 use caliptra_drivers::{pcr_log::PCR_ID_STASH_MEASUREMENT, CaliptraError, CaliptraResult};
 use crypto::{AlgLen, Crypto};
 use dpe::{
-    commands::{CommandExecution, DeriveContextCmd, DeriveContextFlags},
+    commands::{Command, CommandExecution, DeriveContextCmd, DeriveContextFlags},
     context::ContextHandle,
     dpe_instance::DpeEnv,
     response::DpeErrorCode,
@@ -45,6 +45,24 @@ impl StashMeasurementCmd {
                 }
             }
 
+            let locality = drivers.mbox.user();
+            let derive_context_cmd = DeriveContextCmd {
+                handle: ContextHandle::default(),
+                data: *measurement,
+                flags: DeriveContextFlags::MAKE_DEFAULT
+                    | DeriveContextFlags::CHANGE_LOCALITY
+                    | DeriveContextFlags::INPUT_ALLOW_CA
+                    | DeriveContextFlags::INPUT_ALLOW_X509,
+                tci_type: u32::from_ne_bytes(*metadata),
+                target_locality: locality,
+            };
+            if !drivers
+                .dpe_config
+                .command_allowed(&Command::DeriveContext(&derive_context_cmd))
+            {
+                return Err(CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED);
+            }
+
             // Check that adding this measurement to DPE doesn't cause
             // the PL0 context threshold to be exceeded.
             drivers.is_dpe_context_threshold_exceeded()?;
@@ -75,22 +93,12 @@ impl StashMeasurementCmd {
                     &nf,
                     None,
                     None,
+                    &drivers.dpe_config,
                 ),
             };
 
-            let locality = drivers.mbox.user();
-
-            let derive_context_resp = DeriveContextCmd {
-                handle: ContextHandle::default(),
-                data: *measurement,
-                flags: DeriveContextFlags::MAKE_DEFAULT
-                    | DeriveContextFlags::CHANGE_LOCALITY
-                    | DeriveContextFlags::INPUT_ALLOW_CA
-                    | DeriveContextFlags::INPUT_ALLOW_X509,
-                tci_type: u32::from_ne_bytes(*metadata),
-                target_locality: locality,
-            }
-            .execute(&mut pdata.dpe, &mut env, locality);
+            let derive_context_resp =
+                derive_context_cmd.execute(&mut pdata.dpe, &mut env, locality);
 
             match derive_context_resp {
                 Ok(_) => DpeErrorCode::NoError,
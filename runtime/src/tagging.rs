@@ -17,7 +17,7 @@ use caliptra_cfi_derive_git::cfi_impl_fn;
 use caliptra_common::mailbox_api::{
     GetTaggedTciReq, GetTaggedTciResp, MailboxResp, MailboxRespHeader, TagTciReq,
 };
-use caliptra_drivers::cprintln;
+use caliptra_drivers::{cprintln, PersistentData};
 use caliptra_error::{CaliptraError, CaliptraResult};
 use dpe::{
     context::{ContextHandle, ContextState},
@@ -67,6 +67,36 @@ impl TagTciCmd {
     }
 }
 
+/// Find the DPE context tagged with `tag`
+///
+/// # Arguments
+///
+/// * `persistent_data` - Persistent data holding DPE and the context tags
+/// * `tag` - Tag given to the context by DPE_TAG_TCI
+///
+/// # Returns
+///
+/// * `usize` - Index of the context in `persistent_data.dpe.contexts`
+pub(crate) fn tagged_context_index(
+    persistent_data: &PersistentData,
+    tag: u32,
+) -> CaliptraResult<usize> {
+    let context_has_tag = &persistent_data.context_has_tag;
+    let context_tags = &persistent_data.context_tags;
+    let idx = (0..MAX_HANDLES)
+        .find(|i| {
+            *i < context_has_tag.len()
+                && *i < context_tags.len()
+                && context_has_tag[*i].get()
+                && context_tags[*i] == tag
+        })
+        .ok_or(CaliptraError::RUNTIME_TAGGING_FAILURE)?;
+    if idx >= persistent_data.dpe.contexts.len() {
+        return Err(CaliptraError::RUNTIME_TAGGING_FAILURE);
+    }
+    Ok(idx)
+}
+
 pub struct GetTaggedTciCmd;
 impl GetTaggedTciCmd {
     #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
@@ -75,19 +105,7 @@ impl GetTaggedTciCmd {
         let cmd = GetTaggedTciReq::ref_from_bytes(cmd_args)
             .map_err(|_| CaliptraError::RUNTIME_INSUFFICIENT_MEMORY)?;
         let persistent_data = drivers.persistent_data.get();
-        let context_has_tag = &persistent_data.context_has_tag;
-        let context_tags = &persistent_data.context_tags;
-        let idx = (0..MAX_HANDLES)
-            .find(|i| {
-                *i < context_has_tag.len()
-                    && *i < context_tags.len()
-                    && context_has_tag[*i].get()
-                    && context_tags[*i] == cmd.tag
-            })
-            .ok_or(CaliptraError::RUNTIME_TAGGING_FAILURE)?;
-        if idx >= persistent_data.dpe.contexts.len() {
-            return Err(CaliptraError::RUNTIME_TAGGING_FAILURE);
-        }
+        let idx = tagged_context_index(persistent_data, cmd.tag)?;
         let context = persistent_data.dpe.contexts[idx];
 
         Ok(MailboxResp::GetTaggedTci(GetTaggedTciResp {
@@ -0,0 +1,258 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    transfer.rs
+
+Abstract:
+
+    File contains the TRANSFER_BEGIN, TRANSFER_CONTINUE, TRANSFER_WRITE and
+    TRANSFER_END mailbox commands, which move data too large for one
+    mailbox transaction in chunks.
+
+--*/
+
+use crate::jobs::JobStartCmd;
+use crate::{Drivers, SetAuthManifestCmd};
+
+use caliptra_cfi_derive_git::cfi_impl_fn;
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxResp, SetAuthManifestReq, TransferBeginReq, TransferBeginResp,
+    TransferContinueReq, TransferContinueResp, TransferEndReq, TransferSource, TransferWriteReq,
+};
+use caliptra_drivers::TRANSFER_STAGING_SIZE;
+use caliptra_error::{CaliptraError, CaliptraResult};
+use zerocopy::{FromBytes, IntoBytes};
+
+#[derive(Clone, Copy)]
+struct Transfer {
+    id: u32,
+    source: TransferSource,
+    /// Command run on the written data, for `TransferSource::Caller`
+    cmd: u32,
+    /// Size of the data when the transfer began. The logs only grow, so the
+    /// chunks all come from the same data.
+    total_size: u32,
+    /// Bytes written so far, for `TransferSource::Caller`
+    written: u32,
+    /// PAUSER of the caller that began the transfer
+    locality: u32,
+}
+
+/// State of the chunked transfers. At most one transfer is open, and only
+/// the caller that began it can replace it.
+#[derive(Default)]
+pub struct TransferState {
+    last_id: u32,
+    open: Option<Transfer>,
+}
+
+impl TransferState {
+    /// Find the open transfer
+    ///
+    /// # Arguments
+    ///
+    /// * `id` - Transfer ID returned by TRANSFER_BEGIN
+    /// * `locality` - PAUSER of the caller
+    fn get_mut(&mut self, id: u32, locality: u32) -> CaliptraResult<&mut Transfer> {
+        match self.open.as_mut() {
+            Some(transfer) if transfer.id == id && transfer.locality == locality => Ok(transfer),
+            _ => Err(CaliptraError::RUNTIME_TRANSFER_INVALID_ID),
+        }
+    }
+}
+
+/// Get the data read by a transfer
+///
+/// # Arguments
+///
+/// * `drivers` - Drivers
+/// * `source` - Data to read
+///
+/// # Returns
+///
+/// * `&[u8]` - The current contents of `source`
+fn source_bytes(drivers: &Drivers, source: TransferSource) -> CaliptraResult<&[u8]> {
+    let persistent_data = drivers.persistent_data.get();
+    match source {
+        TransferSource::MeasurementLog => persistent_data
+            .measurement_log
+            .get(..persistent_data.fht.meas_log_index as usize)
+            .map(|log| log.as_bytes())
+            .ok_or(CaliptraError::RUNTIME_LOG_INDEX_CORRUPTED),
+        TransferSource::PcrLog => persistent_data
+            .pcr_log
+            .get(..persistent_data.fht.pcr_log_index as usize)
+            .map(|log| log.as_bytes())
+            .ok_or(CaliptraError::RUNTIME_LOG_INDEX_CORRUPTED),
+        TransferSource::CertChain => Ok(drivers.cert_chain.as_slice()),
+        TransferSource::Caller | TransferSource::Invalid => {
+            Err(CaliptraError::RUNTIME_TRANSFER_INVALID_SOURCE)
+        }
+    }
+}
+
+/// Get the largest request a command accepts through a transfer
+///
+/// # Arguments
+///
+/// * `cmd` - Command run on the written data
+fn max_request_size(cmd: u32) -> CaliptraResult<usize> {
+    match CommandId::from(cmd) {
+        CommandId::SET_AUTH_MANIFEST => {
+            Ok(SetAuthManifestReq::MAX_MAN_SIZE.min(TRANSFER_STAGING_SIZE))
+        }
+        CommandId::JOB_START => Ok(TRANSFER_STAGING_SIZE),
+        _ => Err(CaliptraError::RUNTIME_TRANSFER_INVALID_SOURCE),
+    }
+}
+
+pub struct TransferBeginCmd;
+impl TransferBeginCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = TransferBeginReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let locality = drivers.mbox.user();
+
+        // Beginning a transfer ends the caller's open one, so a caller that
+        // lost track of its transfer is not locked out. Other callers cannot
+        // end it.
+        if matches!(drivers.transfer.open, Some(transfer) if transfer.locality != locality) {
+            return Err(CaliptraError::RUNTIME_TRANSFER_IN_PROGRESS);
+        }
+
+        let source = TransferSource::from(cmd.source);
+        let total_size = match source {
+            TransferSource::Caller => {
+                // A running job may still read its input from the staging
+                // buffer
+                if drivers.jobs.uses_staging() {
+                    return Err(CaliptraError::RUNTIME_JOB_IN_PROGRESS);
+                }
+                if cmd.total_size as usize > max_request_size(cmd.cmd)? {
+                    return Err(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS);
+                }
+                cmd.total_size
+            }
+            _ => source_bytes(drivers, source)?.len() as u32,
+        };
+
+        let id = drivers.transfer.last_id.wrapping_add(1).max(1);
+        drivers.transfer = TransferState {
+            last_id: id,
+            open: Some(Transfer {
+                id,
+                source,
+                cmd: cmd.cmd,
+                total_size,
+                written: 0,
+                locality,
+            }),
+        };
+
+        Ok(MailboxResp::TransferBegin(TransferBeginResp {
+            transfer_id: id,
+            total_size,
+            ..Default::default()
+        }))
+    }
+}
+
+pub struct TransferContinueCmd;
+impl TransferContinueCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = TransferContinueReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let transfer = *drivers
+            .transfer
+            .get_mut(cmd.transfer_id, drivers.mbox.user())?;
+
+        let remaining = source_bytes(drivers, transfer.source)?
+            .get(..transfer.total_size as usize)
+            .and_then(|data| data.get(cmd.offset as usize..))
+            .ok_or(CaliptraError::RUNTIME_TRANSFER_INVALID_OFFSET)?;
+        let chunk = &remaining[..remaining.len().min(TransferContinueResp::MAX_CHUNK_SIZE)];
+
+        let mut resp = TransferContinueResp {
+            data_size: chunk.len() as u32,
+            ..Default::default()
+        };
+        resp.data[..chunk.len()].copy_from_slice(chunk);
+
+        Ok(MailboxResp::TransferContinue(resp))
+    }
+}
+
+pub struct TransferWriteCmd;
+impl TransferWriteCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = TransferWriteReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let data = cmd
+            .data
+            .get(..cmd.data_size as usize)
+            .ok_or(CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let transfer = drivers
+            .transfer
+            .get_mut(cmd.transfer_id, drivers.mbox.user())?;
+        if transfer.source != TransferSource::Caller {
+            return Err(CaliptraError::RUNTIME_TRANSFER_INVALID_SOURCE);
+        }
+
+        // Chunks are written in order
+        let start = transfer.written as usize;
+        let end = start + data.len();
+        if cmd.offset != transfer.written || end > transfer.total_size as usize {
+            return Err(CaliptraError::RUNTIME_TRANSFER_INVALID_OFFSET);
+        }
+        drivers
+            .persistent_data
+            .transfer_staging_mut()
+            .get_mut(start..end)
+            .ok_or(CaliptraError::RUNTIME_TRANSFER_INVALID_OFFSET)?
+            .copy_from_slice(data);
+        transfer.written = end as u32;
+
+        Ok(MailboxResp::default())
+    }
+}
+
+pub struct TransferEndCmd;
+impl TransferEndCmd {
+    #[cfg_attr(not(feature = "no-cfi"), cfi_impl_fn)]
+    #[inline(never)]
+    pub(crate) fn execute(drivers: &mut Drivers, cmd_args: &[u8]) -> CaliptraResult<MailboxResp> {
+        let cmd = TransferEndReq::ref_from_bytes(cmd_args)
+            .map_err(|_| CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS)?;
+        let transfer = *drivers
+            .transfer
+            .get_mut(cmd.transfer_id, drivers.mbox.user())?;
+        if transfer.source != TransferSource::Caller {
+            drivers.transfer.open = None;
+            return Ok(MailboxResp::default());
+        }
+
+        // The transfer stays open until all the data is written
+        if transfer.written != transfer.total_size {
+            return Err(CaliptraError::RUNTIME_TRANSFER_INCOMPLETE);
+        }
+        drivers.transfer.open = None;
+
+        let request_size = transfer.total_size as usize;
+        match CommandId::from(transfer.cmd) {
+            CommandId::SET_AUTH_MANIFEST => {
+                SetAuthManifestCmd::execute_staged(drivers, request_size)
+            }
+            CommandId::JOB_START => JobStartCmd::execute_staged(drivers, request_size),
+            _ => Err(CaliptraError::RUNTIME_TRANSFER_INVALID_SOURCE),
+        }
+    }
+}
@@ -5,13 +5,16 @@ mod test_authorize_and_stash;
 mod test_boot;
 mod test_certify_key_extended;
 mod test_certs;
+mod test_derive_key;
 mod test_disable;
 mod test_ecdsa;
+mod test_events;
 mod test_fips;
 mod test_get_fmc_alias_csr;
 mod test_get_idev_csr;
 mod test_info;
 mod test_invoke_dpe;
+mod test_jobs;
 mod test_lms;
 mod test_mailbox;
 mod test_panic_missing;
@@ -20,8 +23,10 @@ mod test_pcr;
 mod test_populate_idev;
 mod test_revoke_exported_cdi_handle;
 mod test_set_auth_manifest;
+mod test_sign_with_dpe_key;
 mod test_sign_with_export_ecdsa;
 mod test_stash_measurement;
 mod test_tagging;
+mod test_transfer;
 mod test_update_reset;
 mod test_warm_reset;
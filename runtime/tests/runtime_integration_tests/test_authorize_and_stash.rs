@@ -1,7 +1,10 @@
 // Licensed under the Apache-2.0 license
 
 use crate::common::{run_rt_test, RuntimeTestArgs};
-use crate::test_set_auth_manifest::{create_auth_manifest, create_auth_manifest_with_metadata};
+use crate::test_set_auth_manifest::{
+    create_auth_manifest, create_auth_manifest_with_metadata,
+    create_auth_manifest_with_metadata_and_svn,
+};
 use crate::test_update_reset::update_fw;
 use caliptra_api::SocManager;
 use caliptra_auth_man_types::{
@@ -18,7 +21,10 @@ use caliptra_common::mailbox_api::{
 };
 use caliptra_hw_model::{DefaultHwModel, HwModel};
 use caliptra_runtime::RtBootStatus;
-use caliptra_runtime::{IMAGE_AUTHORIZED, IMAGE_HASH_MISMATCH, IMAGE_NOT_AUTHORIZED};
+use caliptra_runtime::{
+    IMAGE_AUTHORIZED, IMAGE_HASH_MISMATCH, IMAGE_LOAD_ADDRESS_MISMATCH, IMAGE_NOT_AUTHORIZED,
+    IMAGE_SIZE_MISMATCH, IMAGE_SVN_TOO_LOW, IMAGE_VERSION_MISMATCH,
+};
 use sha2::{Digest, Sha384};
 use zerocopy::{FromBytes, IntoBytes};
 
@@ -315,6 +321,7 @@ fn test_authorize_and_stash_fwid_0() {
         fw_id: 0,
         flags: flags.0,
         digest: IMAGE_DIGEST1,
+        ..Default::default()
     }];
     let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
     let mut model = set_auth_manifest(Some(auth_manifest));
@@ -353,6 +360,7 @@ fn test_authorize_and_stash_fwid_127() {
         fw_id: 127,
         flags: flags.0,
         digest: IMAGE_DIGEST1,
+        ..Default::default()
     }];
     let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
     let mut model = set_auth_manifest(Some(auth_manifest));
@@ -379,6 +387,190 @@ fn test_authorize_and_stash_fwid_127() {
     assert_eq!(authorize_and_stash_resp.auth_req_result, IMAGE_AUTHORIZED);
 }
 
+#[test]
+fn test_authorize_and_stash_optional_fields() {
+    let mut flags = ImageMetadataFlags(0);
+    flags.set_image_source(ImageHashSource::InRequest as u32);
+    flags.set_check_image_size(true);
+    flags.set_check_load_address(true);
+    flags.set_check_version(true);
+    flags.set_check_svn(true);
+
+    let image_metadata = vec![AuthManifestImageMetadata {
+        fw_id: 1,
+        flags: flags.0,
+        digest: IMAGE_DIGEST1,
+        image_size: 0x1000,
+        load_address_lo: 0x4000_0000,
+        load_address_hi: 0x1,
+        version: 2,
+        svn: 3,
+    }];
+    let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
+    let mut model = set_auth_manifest(Some(auth_manifest));
+
+    let valid_cmd = || AuthorizeAndStashReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        fw_id: FW_ID_1,
+        measurement: IMAGE_DIGEST1,
+        source: ImageHashSource::InRequest as u32,
+        image_size: 0x1000,
+        load_address_lo: 0x4000_0000,
+        load_address_hi: 0x1,
+        version: 2,
+        svn: 3,
+        ..Default::default()
+    };
+
+    let test_cases = [
+        (valid_cmd(), IMAGE_AUTHORIZED),
+        (
+            AuthorizeAndStashReq {
+                svn: 4,
+                ..valid_cmd()
+            },
+            IMAGE_AUTHORIZED,
+        ),
+        (
+            AuthorizeAndStashReq {
+                image_size: 0x1004,
+                ..valid_cmd()
+            },
+            IMAGE_SIZE_MISMATCH,
+        ),
+        (
+            AuthorizeAndStashReq {
+                load_address_hi: 0,
+                ..valid_cmd()
+            },
+            IMAGE_LOAD_ADDRESS_MISMATCH,
+        ),
+        (
+            AuthorizeAndStashReq {
+                version: 1,
+                ..valid_cmd()
+            },
+            IMAGE_VERSION_MISMATCH,
+        ),
+        (
+            AuthorizeAndStashReq {
+                svn: 2,
+                ..valid_cmd()
+            },
+            IMAGE_SVN_TOO_LOW,
+        ),
+    ];
+
+    for (cmd, expected_result) in test_cases {
+        let mut authorize_and_stash_cmd = MailboxReq::AuthorizeAndStash(cmd);
+        authorize_and_stash_cmd.populate_chksum().unwrap();
+
+        let resp = model
+            .mailbox_execute(
+                u32::from(CommandId::AUTHORIZE_AND_STASH),
+                authorize_and_stash_cmd.as_bytes().unwrap(),
+            )
+            .unwrap()
+            .expect("We should have received a response");
+
+        let authorize_and_stash_resp =
+            AuthorizeAndStashResp::read_from_bytes(resp.as_slice()).unwrap();
+        assert_eq!(authorize_and_stash_resp.auth_req_result, expected_result);
+    }
+}
+
+#[test]
+fn test_authorize_and_stash_optional_fields_skip_auth() {
+    let mut flags = ImageMetadataFlags(0);
+    flags.set_image_source(ImageHashSource::InRequest as u32);
+    flags.set_ignore_auth_check(true);
+    flags.set_check_svn(true);
+
+    let image_metadata = vec![AuthManifestImageMetadata {
+        fw_id: 1,
+        flags: flags.0,
+        svn: 3,
+        ..Default::default()
+    }];
+    let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
+    let mut model = set_auth_manifest(Some(auth_manifest));
+
+    // The digest is not checked, but the SVN still is
+    for (svn, expected_result) in [(3, IMAGE_AUTHORIZED), (2, IMAGE_SVN_TOO_LOW)] {
+        let mut authorize_and_stash_cmd = MailboxReq::AuthorizeAndStash(AuthorizeAndStashReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            fw_id: FW_ID_1,
+            measurement: IMAGE_DIGEST_BAD,
+            source: ImageHashSource::InRequest as u32,
+            svn,
+            ..Default::default()
+        });
+        authorize_and_stash_cmd.populate_chksum().unwrap();
+
+        let resp = model
+            .mailbox_execute(
+                u32::from(CommandId::AUTHORIZE_AND_STASH),
+                authorize_and_stash_cmd.as_bytes().unwrap(),
+            )
+            .unwrap()
+            .expect("We should have received a response");
+
+        let authorize_and_stash_resp =
+            AuthorizeAndStashResp::read_from_bytes(resp.as_slice()).unwrap();
+        assert_eq!(authorize_and_stash_resp.auth_req_result, expected_result);
+    }
+}
+
+#[test]
+fn test_authorize_and_stash_legacy_request() {
+    let mut flags = ImageMetadataFlags(0);
+    flags.set_image_source(ImageHashSource::InRequest as u32);
+    let mut size_flags = flags;
+    size_flags.set_check_image_size(true);
+
+    let image_metadata = vec![
+        AuthManifestImageMetadata {
+            fw_id: 1,
+            flags: flags.0,
+            digest: IMAGE_DIGEST1,
+            ..Default::default()
+        },
+        AuthManifestImageMetadata {
+            fw_id: 2,
+            flags: size_flags.0,
+            digest: IMAGE_DIGEST1,
+            ..Default::default()
+        },
+    ];
+    let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
+    let mut model = set_auth_manifest(Some(auth_manifest));
+
+    // A request without the image size, load address and version fields is
+    // accepted, but fails the checks that need them.
+    for (fw_id, expected_result) in [(FW_ID_1, IMAGE_AUTHORIZED), (FW_ID_2, IMAGE_SIZE_MISMATCH)] {
+        let cmd = AuthorizeAndStashReq {
+            fw_id,
+            measurement: IMAGE_DIGEST1,
+            ..Default::default()
+        };
+        let mut legacy_cmd = cmd.as_bytes()[..AuthorizeAndStashReq::LEGACY_SIZE].to_vec();
+        let chksum = caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::AUTHORIZE_AND_STASH),
+            &legacy_cmd[4..],
+        );
+        legacy_cmd[..4].copy_from_slice(&chksum.to_le_bytes());
+
+        let resp = model
+            .mailbox_execute(u32::from(CommandId::AUTHORIZE_AND_STASH), &legacy_cmd)
+            .unwrap()
+            .expect("We should have received a response");
+
+        let authorize_and_stash_resp =
+            AuthorizeAndStashResp::read_from_bytes(resp.as_slice()).unwrap();
+        assert_eq!(authorize_and_stash_resp.auth_req_result, expected_result);
+    }
+}
+
 #[test]
 fn test_authorize_and_stash_cmd_deny_second_bad_hash() {
     {
@@ -416,6 +608,7 @@ fn test_authorize_and_stash_cmd_deny_second_bad_hash() {
             fw_id: 1,
             flags: flags.0,
             digest: IMAGE_DIGEST_BAD,
+            ..Default::default()
         }];
         let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
         let mut model = set_auth_manifest(Some(auth_manifest));
@@ -459,6 +652,7 @@ fn test_authorize_and_stash_after_update_reset() {
         fw_id: 0,
         flags: flags.0,
         digest: IMAGE_DIGEST1,
+        ..Default::default()
     }];
     let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
     let mut model = set_auth_manifest(Some(auth_manifest));
@@ -522,6 +716,7 @@ fn test_authorize_and_stash_after_update_reset_unauthorized_fw_id() {
         fw_id: 0,
         flags: flags.0,
         digest: IMAGE_DIGEST1,
+        ..Default::default()
     }];
     let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
     let mut model = set_auth_manifest(Some(auth_manifest));
@@ -591,6 +786,7 @@ fn test_authorize_and_stash_after_update_reset_bad_hash() {
         fw_id: 0,
         flags: flags.0,
         digest: IMAGE_DIGEST1,
+        ..Default::default()
     }];
     let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
     let mut model = set_auth_manifest(Some(auth_manifest));
@@ -711,6 +907,7 @@ fn test_authorize_and_stash_after_update_reset_multiple_set_manifest() {
         fw_id: 0,
         flags: flags.0,
         digest: IMAGE_DIGEST1,
+        ..Default::default()
     }];
     let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
     let mut model = set_auth_manifest(Some(auth_manifest));
@@ -823,8 +1020,9 @@ fn test_authorize_and_stash_after_update_reset_multiple_set_manifest() {
         fw_id: 127,
         flags: flags.0,
         digest: IMAGE_DIGEST1,
+        ..Default::default()
     }];
-    let auth_manifest = create_auth_manifest_with_metadata(image_metadata);
+    let auth_manifest = create_auth_manifest_with_metadata_and_svn(image_metadata, 1);
 
     let buf = auth_manifest.as_bytes();
     let mut auth_manifest_slice = [0u8; SetAuthManifestReq::MAX_MAN_SIZE];
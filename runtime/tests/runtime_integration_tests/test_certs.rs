@@ -1,8 +1,8 @@
 // Licensed under the Apache-2.0 license
 
 use crate::common::{
-    execute_dpe_cmd, generate_test_x509_cert, get_fmc_alias_cert, get_rt_alias_cert, run_rt_test,
-    DpeResult, RuntimeTestArgs, TEST_LABEL,
+    assert_error, execute_dpe_cmd, generate_test_x509_cert, get_fmc_alias_cert, get_rt_alias_cert,
+    run_rt_test, DpeResult, RuntimeTestArgs, TEST_LABEL,
 };
 use caliptra_builder::firmware::{APP_WITH_UART, FMC_WITH_UART};
 use caliptra_builder::ImageOptions;
@@ -244,6 +244,95 @@ fn test_rt_alias_cert() {
     );
 }
 
+#[test]
+fn test_mldsa_cert_chain_unavailable_for_lms_image() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    for cmd in [
+        CommandId::GET_LDEV_MLDSA_CERT,
+        CommandId::GET_FMC_ALIAS_MLDSA_CERT,
+        CommandId::GET_RT_ALIAS_MLDSA_CERT,
+    ] {
+        let cmd = u32::from(cmd);
+        let payload = MailboxReqHeader {
+            chksum: caliptra_common::checksum::calc_checksum(cmd, &[]),
+        };
+        let resp = model.mailbox_execute(cmd, payload.as_bytes());
+        assert_error(
+            &mut model,
+            CaliptraError::RUNTIME_MLDSA_CERT_CHAIN_UNAVAILABLE,
+            resp.unwrap_err(),
+        );
+    }
+}
+
+/// Verify a pure ML-DSA-87 signature produced by Caliptra over `tbs`.
+#[cfg(feature = "hw-2.x")]
+fn mldsa87_verify(pub_key: &[u8], tbs: &[u8], sig: &[u8]) -> bool {
+    use fips204::ml_dsa_87::{PublicKey, PK_LEN, SIG_LEN};
+    use fips204::traits::{SerDes, Verifier};
+
+    let pub_key: [u8; PK_LEN] = pub_key.try_into().unwrap();
+    let pub_key = PublicKey::try_from_bytes(pub_key).unwrap();
+    pub_key.verify(tbs, sig[..SIG_LEN].try_into().unwrap())
+}
+
+#[test]
+#[cfg(feature = "hw-2.x")]
+fn test_mldsa_cert_chain() {
+    use caliptra_common::mailbox_api::{
+        GetFmcAliasMldsaCertReq, GetLdevMldsaCertReq, GetRtAliasMldsaCertReq,
+    };
+    use caliptra_image_types::{ImagePqcKeyType, VENDOR_MLDSA_KEY_COUNT};
+    use x509_parser::prelude::{FromDer, X509Certificate};
+
+    const MLDSA87_OID: &str = "2.16.840.1.101.3.4.3.19";
+
+    let mut opts = ImageOptions::default();
+    opts.vendor_config.pqc_key_type = ImagePqcKeyType::Mldsa;
+    // Without an ML-DSA revocation fuse only the last key is accepted
+    opts.vendor_config.mldsa_key_idx = VENDOR_MLDSA_KEY_COUNT - 1;
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(opts),
+        ..Default::default()
+    });
+
+    let ldev_resp = crate::common::get_certs::<GetLdevMldsaCertReq>(&mut model);
+    let fmc_resp = crate::common::get_certs::<GetFmcAliasMldsaCertReq>(&mut model);
+    let rt_resp = crate::common::get_certs::<GetRtAliasMldsaCertReq>(&mut model);
+
+    let (_, ldev_cert) = X509Certificate::from_der(ldev_resp.data().unwrap()).unwrap();
+    let (_, fmc_cert) = X509Certificate::from_der(fmc_resp.data().unwrap()).unwrap();
+    let (_, rt_cert) = X509Certificate::from_der(rt_resp.data().unwrap()).unwrap();
+
+    // Each certificate is issued by the previous layer's ML-DSA-87 key
+    for (issuer, cert) in [(&ldev_cert, &fmc_cert), (&fmc_cert, &rt_cert)] {
+        assert_eq!(
+            cert.signature_algorithm.algorithm.to_id_string(),
+            MLDSA87_OID
+        );
+        assert_eq!(cert.issuer().as_raw(), issuer.subject().as_raw());
+        assert!(mldsa87_verify(
+            &issuer.public_key().subject_public_key.data,
+            cert.tbs_certificate.as_ref(),
+            &cert.signature_value.data,
+        ));
+    }
+
+    // The ECC and ML-DSA-87 certificates share the same validity
+    let fmc_ecc_resp = get_fmc_alias_cert(&mut model);
+    let (_, fmc_ecc_cert) =
+        X509Certificate::from_der(&fmc_ecc_resp.data[..fmc_ecc_resp.data_size as usize]).unwrap();
+    assert_eq!(
+        fmc_ecc_cert.validity().not_before,
+        fmc_cert.validity().not_before
+    );
+    assert_eq!(
+        fmc_ecc_cert.validity().not_after,
+        fmc_cert.validity().not_after
+    );
+}
+
 #[test]
 fn test_dpe_leaf_cert() {
     let mut model = run_rt_test(RuntimeTestArgs::default());
@@ -0,0 +1,235 @@
+// Licensed under the Apache-2.0 license
+
+use caliptra_api::SocManager;
+use caliptra_common::mailbox_api::{
+    CommandId, DeriveKeyFlags, DeriveKeyReq, DeriveKeyResp, MailboxReq, MailboxReqHeader,
+    RevokeDerivedKeyReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use caliptra_image_types::ImageDpeConfig;
+use caliptra_runtime::{RtBootStatus, DPE_SUPPORT};
+use dpe::{
+    commands::{Command, DeriveContextCmd, DeriveContextFlags},
+    context::ContextHandle,
+    response::Response,
+    DPE_PROFILE,
+};
+use zerocopy::{FromBytes, IntoBytes};
+
+use crate::common::{assert_error, execute_dpe_cmd, run_rt_test, DpeResult, RuntimeTestArgs};
+use crate::test_invoke_dpe::image_options_with_dpe_config;
+
+// Runtime arguments for an image whose owner allows derived keys to be exported
+fn export_allowed_args() -> RuntimeTestArgs<'static> {
+    let dpe_config = ImageDpeConfig {
+        flags: ImageDpeConfig::ENABLED_FLAG | ImageDpeConfig::EXPORT_DERIVED_KEYS_FLAG,
+        pl0_context_limit: 16,
+        pl1_context_limit: 16,
+        support: DPE_SUPPORT.bits(),
+        allowed_commands: ImageDpeConfig::CMD_ALL,
+        ..Default::default()
+    };
+    RuntimeTestArgs {
+        test_image_options: Some(image_options_with_dpe_config(dpe_config)),
+        ..Default::default()
+    }
+}
+
+fn export_cdi(model: &mut DefaultHwModel) -> [u8; DeriveKeyReq::EXPORTED_CDI_MAX_SIZE] {
+    let derive_ctx_cmd = DeriveContextCmd {
+        handle: ContextHandle::default(),
+        data: [0; DPE_PROFILE.get_tci_size()],
+        flags: DeriveContextFlags::EXPORT_CDI | DeriveContextFlags::CREATE_CERTIFICATE,
+        tci_type: 0,
+        target_locality: 0,
+    };
+    let resp = execute_dpe_cmd(
+        model,
+        &mut Command::DeriveContext(&derive_ctx_cmd),
+        DpeResult::Success,
+    );
+    match resp {
+        Some(Response::DeriveContextExportedCdi(resp)) => resp.exported_cdi,
+        _ => panic!("expected derive context resp!"),
+    }
+}
+
+fn derive_key_req(
+    exported_cdi_handle: [u8; DeriveKeyReq::EXPORTED_CDI_MAX_SIZE],
+    flags: DeriveKeyFlags,
+    label: &[u8],
+    context: &[u8],
+) -> MailboxReq {
+    let mut req = DeriveKeyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        exported_cdi_handle,
+        flags: flags.bits(),
+        label_size: label.len() as u32,
+        context_size: context.len() as u32,
+        ..Default::default()
+    };
+    req.label[..label.len()].copy_from_slice(label);
+    req.context[..context.len()].copy_from_slice(context);
+    let mut cmd = MailboxReq::DeriveKey(req);
+    cmd.populate_chksum().unwrap();
+    cmd
+}
+
+fn derive_key(model: &mut DefaultHwModel, cmd: &MailboxReq) -> DeriveKeyResp {
+    let resp = model
+        .mailbox_execute(CommandId::DERIVE_KEY.into(), cmd.as_bytes().unwrap())
+        .unwrap()
+        .expect("We should have received a response");
+    DeriveKeyResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+fn revoke_derived_key(
+    model: &mut DefaultHwModel,
+    key_handle: [u8; DeriveKeyResp::KEY_HANDLE_SIZE],
+) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = MailboxReq::RevokeDerivedKey(RevokeDerivedKeyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        key_handle,
+    });
+    cmd.populate_chksum().unwrap();
+    model.mailbox_execute(
+        CommandId::REVOKE_DERIVED_KEY.into(),
+        cmd.as_bytes().unwrap(),
+    )
+}
+
+#[test]
+fn test_derive_key_export() {
+    let mut model = run_rt_test(export_allowed_args());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+    let exported_cdi = export_cdi(&mut model);
+
+    let cmd = derive_key_req(
+        exported_cdi,
+        DeriveKeyFlags::EXPORT,
+        b"storage",
+        b"volume 0",
+    );
+    let resp = derive_key(&mut model, &cmd);
+    assert_eq!(resp.flags, DeriveKeyFlags::EXPORT.bits());
+    assert_ne!(resp.key, [0u8; DeriveKeyResp::KEY_SIZE]);
+    assert_eq!(resp.key_handle, [0u8; DeriveKeyResp::KEY_HANDLE_SIZE]);
+
+    // The same inputs derive the same key
+    assert_eq!(derive_key(&mut model, &cmd).key, resp.key);
+
+    // Changing the label or the context derives a different key
+    let cmd = derive_key_req(
+        exported_cdi,
+        DeriveKeyFlags::EXPORT,
+        b"storage",
+        b"volume 1",
+    );
+    assert_ne!(derive_key(&mut model, &cmd).key, resp.key);
+    let cmd = derive_key_req(exported_cdi, DeriveKeyFlags::EXPORT, b"swap", b"volume 0");
+    assert_ne!(derive_key(&mut model, &cmd).key, resp.key);
+}
+
+#[test]
+fn test_derive_key_export_not_allowed_by_owner() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+    let exported_cdi = export_cdi(&mut model);
+
+    let cmd = derive_key_req(exported_cdi, DeriveKeyFlags::EXPORT, b"storage", &[]);
+    let resp = model
+        .mailbox_execute(CommandId::DERIVE_KEY.into(), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_DERIVE_KEY_EXPORT_NOT_ALLOWED,
+        resp,
+    );
+}
+
+#[test]
+fn test_derive_key_handle() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+    let exported_cdi = export_cdi(&mut model);
+
+    let cmd = derive_key_req(exported_cdi, DeriveKeyFlags::empty(), b"storage", &[]);
+    let resp = derive_key(&mut model, &cmd);
+    assert_eq!(resp.flags, 0);
+    assert_eq!(resp.key, [0u8; DeriveKeyResp::KEY_SIZE]);
+    assert_ne!(resp.key_handle, [0u8; DeriveKeyResp::KEY_HANDLE_SIZE]);
+
+    // Every derivation gets a new handle and invalidates the previous one
+    let key_handle = derive_key(&mut model, &cmd).key_handle;
+    assert_ne!(key_handle, resp.key_handle);
+    let err = revoke_derived_key(&mut model, resp.key_handle).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_REVOKE_DERIVED_KEY_HANDLE_NOT_FOUND,
+        err,
+    );
+
+    // A revoked handle cannot be revoked again
+    revoke_derived_key(&mut model, key_handle)
+        .unwrap()
+        .expect("We should have received a response");
+    let err = revoke_derived_key(&mut model, key_handle).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_REVOKE_DERIVED_KEY_HANDLE_NOT_FOUND,
+        err,
+    );
+}
+
+#[test]
+fn test_derive_key_invalid_cdi_handle() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+    let exported_cdi = export_cdi(&mut model);
+
+    let mut invalid_cdi = exported_cdi;
+    invalid_cdi[0] ^= 1;
+    let cmd = derive_key_req(invalid_cdi, DeriveKeyFlags::empty(), b"storage", &[]);
+    let resp = model
+        .mailbox_execute(CommandId::DERIVE_KEY.into(), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_DERIVE_KEY_INVALID_CDI_HANDLE,
+        resp,
+    );
+}
+
+#[test]
+fn test_derive_key_invalid_label_size() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+    let exported_cdi = export_cdi(&mut model);
+
+    let mut cmd = MailboxReq::DeriveKey(DeriveKeyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        exported_cdi_handle: exported_cdi,
+        label_size: DeriveKeyReq::MAX_LABEL_SIZE as u32 + 1,
+        ..Default::default()
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(CommandId::DERIVE_KEY.into(), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_MAILBOX_INVALID_PARAMS,
+        resp,
+    );
+}
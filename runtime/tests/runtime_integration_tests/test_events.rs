@@ -0,0 +1,101 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{execute_dpe_cmd, run_rt_test, DpeResult, RuntimeTestArgs};
+use caliptra_api::SocManager;
+use caliptra_common::mailbox_api::{
+    GetEventsResp, MailboxReq, MailboxReqHeader, RuntimeEvent, RuntimeEventEntry,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel};
+use caliptra_runtime::{RtBootStatus, PL0_DPE_ACTIVE_CONTEXT_THRESHOLD};
+use dpe::{
+    commands::{Command, DeriveContextCmd, DeriveContextFlags, RotateCtxCmd, RotateCtxFlags},
+    context::ContextHandle,
+    response::Response,
+};
+use zerocopy::FromBytes;
+
+fn get_events(model: &mut DefaultHwModel) -> GetEventsResp {
+    let mut cmd = MailboxReq::GetEvents(MailboxReqHeader { chksum: 0 });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(u32::from(cmd.cmd_code()), cmd.as_bytes().unwrap())
+        .unwrap()
+        .expect("We expected a response");
+    GetEventsResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+#[test]
+fn test_get_events_empty() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let resp = get_events(&mut model);
+    assert_eq!(resp.events(), Some(&[][..]));
+    assert_eq!(resp.dropped_count, 0);
+    assert_eq!(model.soc_ifc().cptra_fw_error_non_fatal().read(), 0);
+}
+
+#[test]
+fn test_dpe_context_threshold_event() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let rotate_ctx_cmd = RotateCtxCmd {
+        handle: ContextHandle::default(),
+        flags: RotateCtxFlags::empty(),
+    };
+    let Some(Response::RotateCtx(rotate_ctx_resp)) = execute_dpe_cmd(
+        &mut model,
+        &mut Command::RotateCtx(&rotate_ctx_cmd),
+        DpeResult::Success,
+    ) else {
+        panic!("Wrong response type!");
+    };
+    let mut handle = rotate_ctx_resp.handle;
+
+    // DPE measures the valid PAUSERs in PL0's locality at boot, so PL0 reaches
+    // its threshold one context early.
+    for _ in 0..PL0_DPE_ACTIVE_CONTEXT_THRESHOLD - 1 {
+        // No event before the threshold is reached
+        assert_eq!(model.soc_ifc().cptra_fw_error_non_fatal().read(), 0);
+
+        let derive_context_cmd = DeriveContextCmd {
+            handle,
+            data: [0u8; 48],
+            flags: DeriveContextFlags::RETAIN_PARENT_CONTEXT,
+            tci_type: 0,
+            target_locality: 0,
+        };
+        let Some(Response::DeriveContext(derive_context_resp)) = execute_dpe_cmd(
+            &mut model,
+            &mut Command::DeriveContext(&derive_context_cmd),
+            DpeResult::Success,
+        ) else {
+            panic!("Wrong response type!");
+        };
+        handle = derive_context_resp.handle;
+    }
+
+    // The SoC is interrupted, and the event is reported once
+    assert_eq!(
+        model.soc_ifc().cptra_fw_error_non_fatal().read(),
+        u32::from(CaliptraError::RUNTIME_EVENT_PENDING)
+    );
+    let resp = get_events(&mut model);
+    assert_eq!(
+        resp.events(),
+        Some(
+            &[RuntimeEventEntry {
+                event: RuntimeEvent::DpeContextThresholdReached as u32,
+                data: 0,
+            }][..]
+        )
+    );
+    assert_eq!(model.soc_ifc().cptra_fw_error_non_fatal().read(), 0);
+    assert_eq!(get_events(&mut model).events(), Some(&[][..]));
+}
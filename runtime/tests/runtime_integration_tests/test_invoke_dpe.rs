@@ -1,13 +1,22 @@
 // Licensed under the Apache-2.0 license.
 
 use crate::common::{
-    execute_dpe_cmd, get_rt_alias_cert, run_rt_test, DpeResult, RuntimeTestArgs, TEST_DIGEST,
-    TEST_LABEL,
+    assert_error, execute_dpe_cmd, get_rt_alias_cert, run_rt_test, DpeResult, RuntimeTestArgs,
+    TEST_DIGEST, TEST_LABEL,
 };
+use crate::test_authorize_and_stash::{FW_ID_1, IMAGE_DIGEST1};
+use crate::test_set_auth_manifest::create_auth_manifest;
 use caliptra_api::SocManager;
-use caliptra_common::mailbox_api::{InvokeDpeReq, MailboxReq, MailboxReqHeader};
+use caliptra_auth_man_types::AuthManifestFlags;
+use caliptra_builder::ImageOptions;
+use caliptra_common::mailbox_api::{
+    AuthorizeAndStashReq, CertifyKeyExtendedFlags, CertifyKeyExtendedReq, CommandId,
+    ImageHashSource, InvokeDpeReq, MailboxReq, MailboxReqHeader, SetAuthManifestReq,
+    StashMeasurementReq,
+};
 use caliptra_drivers::CaliptraError;
 use caliptra_hw_model::HwModel;
+use caliptra_image_types::ImageDpeConfig;
 use caliptra_runtime::{RtBootStatus, DPE_SUPPORT, VENDOR_ID, VENDOR_SKU};
 use cms::{
     cert::x509::der::{Decode, Encode},
@@ -21,7 +30,7 @@ use dpe::{
     },
     context::ContextHandle,
     response::{DpeErrorCode, Response},
-    DPE_PROFILE,
+    DPE_PROFILE, MAX_HANDLES,
 };
 use openssl::{
     bn::BigNum,
@@ -32,6 +41,7 @@ use openssl::{
 };
 use sha2::{Digest, Sha384};
 use x509_parser::{nom::Parser, prelude::*};
+use zerocopy::IntoBytes;
 
 #[test]
 fn test_invoke_dpe_get_profile_cmd() {
@@ -51,6 +61,186 @@ fn test_invoke_dpe_get_profile_cmd() {
     assert_eq!(profile.flags, DPE_SUPPORT.bits());
 }
 
+pub(crate) fn image_options_with_dpe_config(dpe_config: ImageDpeConfig) -> ImageOptions {
+    let mut opts = ImageOptions::default();
+    opts.vendor_config.pl0_pauser = Some(0x1);
+    let mut owner_config = opts.owner_config.unwrap();
+    owner_config.dpe_config = dpe_config;
+    opts.owner_config = Some(owner_config);
+    opts
+}
+
+#[test]
+fn test_invoke_dpe_owner_dpe_config() {
+    let dpe_config = ImageDpeConfig {
+        flags: ImageDpeConfig::ENABLED_FLAG,
+        vendor_id: u32::from_be_bytes(*b"SOC1"),
+        vendor_sku: u32::from_be_bytes(*b"SKU2"),
+        pl0_context_limit: 8,
+        pl1_context_limit: 8,
+        support: DPE_SUPPORT.bits(),
+        allowed_commands: ImageDpeConfig::CMD_ALL & !ImageDpeConfig::CMD_SIGN,
+    };
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(image_options_with_dpe_config(dpe_config)),
+        ..Default::default()
+    });
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let resp = execute_dpe_cmd(&mut model, &mut Command::GetProfile, DpeResult::Success);
+    let Some(Response::GetProfile(profile)) = resp else {
+        panic!("Wrong response type!");
+    };
+    assert_eq!(profile.vendor_id, dpe_config.vendor_id);
+    assert_eq!(profile.vendor_sku, dpe_config.vendor_sku);
+    assert_eq!(profile.flags, DPE_SUPPORT.bits());
+
+    let sign_cmd = SignCmd {
+        handle: ContextHandle::default(),
+        label: TEST_LABEL,
+        flags: SignFlags::empty(),
+        digest: TEST_DIGEST,
+    };
+    execute_dpe_cmd(
+        &mut model,
+        &mut Command::Sign(&sign_cmd),
+        DpeResult::MboxCmdFailure(CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED),
+    );
+}
+
+#[test]
+fn test_dpe_config_applies_to_mailbox_dpe_commands() {
+    let dpe_config = ImageDpeConfig {
+        flags: ImageDpeConfig::ENABLED_FLAG,
+        pl0_context_limit: 8,
+        pl1_context_limit: 8,
+        support: DPE_SUPPORT.bits(),
+        allowed_commands: ImageDpeConfig::CMD_ALL
+            & !ImageDpeConfig::CMD_DERIVE_CONTEXT
+            & !ImageDpeConfig::CMD_CERTIFY_KEY,
+        ..Default::default()
+    };
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(image_options_with_dpe_config(dpe_config)),
+        ..Default::default()
+    });
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    // STASH_MEASUREMENT derives a DPE context
+    let mut cmd = MailboxReq::StashMeasurement(StashMeasurementReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        metadata: [0u8; 4],
+        measurement: [1u8; 48],
+        context: [0u8; 48],
+        svn: 0,
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::STASH_MEASUREMENT),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED,
+        resp,
+    );
+
+    // CERTIFY_KEY_EXTENDED wraps CertifyKey
+    let certify_key_cmd = CertifyKeyCmd {
+        handle: ContextHandle::default(),
+        label: TEST_LABEL,
+        flags: CertifyKeyFlags::empty(),
+        format: CertifyKeyCmd::FORMAT_X509,
+    };
+    let mut cmd = MailboxReq::CertifyKeyExtended(CertifyKeyExtendedReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        certify_key_req: certify_key_cmd.as_bytes().try_into().unwrap(),
+        flags: CertifyKeyExtendedFlags::empty(),
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::CERTIFY_KEY_EXTENDED),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED,
+        resp,
+    );
+
+    // AUTHORIZE_AND_STASH derives a DPE context for an authorized image
+    let auth_manifest = create_auth_manifest(AuthManifestFlags::VENDOR_SIGNATURE_REQUIRED);
+    let buf = auth_manifest.as_bytes();
+    let mut manifest = [0u8; SetAuthManifestReq::MAX_MAN_SIZE];
+    manifest[..buf.len()].copy_from_slice(buf);
+    let mut cmd = MailboxReq::SetAuthManifest(SetAuthManifestReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        manifest_size: buf.len() as u32,
+        manifest,
+    });
+    cmd.populate_chksum().unwrap();
+    model
+        .mailbox_execute(
+            u32::from(CommandId::SET_AUTH_MANIFEST),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap()
+        .expect("We should have received a response");
+
+    let mut cmd = MailboxReq::AuthorizeAndStash(AuthorizeAndStashReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        fw_id: FW_ID_1,
+        measurement: IMAGE_DIGEST1,
+        source: ImageHashSource::InRequest as u32,
+        flags: 0, // Don't skip stash
+        ..Default::default()
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::AUTHORIZE_AND_STASH),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_DPE_COMMAND_NOT_ALLOWED,
+        resp,
+    );
+}
+
+#[test]
+fn test_invoke_dpe_invalid_dpe_config() {
+    let dpe_config = ImageDpeConfig {
+        flags: ImageDpeConfig::ENABLED_FLAG,
+        pl0_context_limit: MAX_HANDLES as u32,
+        pl1_context_limit: 1,
+        support: DPE_SUPPORT.bits(),
+        allowed_commands: ImageDpeConfig::CMD_ALL,
+        ..Default::default()
+    };
+    let mut model = run_rt_test(RuntimeTestArgs {
+        test_image_options: Some(image_options_with_dpe_config(dpe_config)),
+        ..Default::default()
+    });
+
+    model.step_until(|m| m.soc_ifc().cptra_fw_error_fatal().read() != 0);
+    assert_eq!(
+        model.soc_ifc().cptra_fw_error_fatal().read(),
+        u32::from(CaliptraError::RUNTIME_DPE_CONFIG_INVALID)
+    );
+}
+
 #[test]
 fn test_invoke_dpe_size_too_big() {
     // Test with data_size too big.
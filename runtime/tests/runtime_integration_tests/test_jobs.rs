@@ -0,0 +1,204 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs};
+use caliptra_api::SocManager;
+use caliptra_common::mailbox_api::{
+    CommandId, JobCancelReq, JobGetStatusReq, JobGetStatusResp, JobKind, JobStartReq, JobStartResp,
+    JobState, MailboxReq, MailboxReqHeader,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
+use caliptra_runtime::RtBootStatus;
+use sha2::{Digest, Sha384};
+use zerocopy::FromBytes;
+
+fn boot() -> DefaultHwModel {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+    model
+}
+
+/// Execute `cmd`, retrying while a job step holds the mailbox
+fn execute(model: &mut DefaultHwModel, cmd: MailboxReq) -> Result<Option<Vec<u8>>, ModelError> {
+    let mut cmd = cmd;
+    cmd.populate_chksum().unwrap();
+    loop {
+        match model.mailbox_execute(u32::from(cmd.cmd_code()), cmd.as_bytes().unwrap()) {
+            Err(ModelError::UnableToLockMailbox) => model.step(),
+            result => return result,
+        }
+    }
+}
+
+fn start(model: &mut DefaultHwModel, kind: u32) -> Result<u32, ModelError> {
+    let cmd = MailboxReq::JobStart(JobStartReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        kind,
+    });
+    let resp = execute(model, cmd)?.expect("We expected a response");
+    Ok(JobStartResp::read_from_bytes(resp.as_slice())
+        .unwrap()
+        .job_id)
+}
+
+/// Start a job of `kind` on `input` with an inbound transfer
+fn start_with_input(model: &mut DefaultHwModel, kind: JobKind, input: &[u8]) -> u32 {
+    let mut request = (kind as u32).to_le_bytes().to_vec();
+    request.extend_from_slice(input);
+    let mut resp_bytes = [0u8; core::mem::size_of::<JobStartResp>()];
+    let resp = model
+        .write_transfer(CommandId::JOB_START, &request, &mut resp_bytes)
+        .unwrap()
+        .expect("We expected a response");
+    JobStartResp::read_from_bytes(resp).unwrap().job_id
+}
+
+/// Poll the job until it is no longer running
+fn wait(model: &mut DefaultHwModel, job_id: u32) -> JobGetStatusResp {
+    loop {
+        let status = get_status(model, job_id).unwrap();
+        if JobState::from(status.state) != JobState::Running {
+            return status;
+        }
+        for _ in 0..10000 {
+            model.step();
+        }
+    }
+}
+
+fn get_status(model: &mut DefaultHwModel, job_id: u32) -> Result<JobGetStatusResp, ModelError> {
+    let cmd = MailboxReq::JobGetStatus(JobGetStatusReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        job_id,
+    });
+    let resp = execute(model, cmd)?.expect("We expected a response");
+    Ok(JobGetStatusResp::read_from_bytes(resp.as_slice()).unwrap())
+}
+
+fn cancel(model: &mut DefaultHwModel, job_id: u32) -> Result<(), ModelError> {
+    let cmd = MailboxReq::JobCancel(JobCancelReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        job_id,
+    });
+    execute(model, cmd).map(|_| ())
+}
+
+#[test]
+fn test_job_kat() {
+    let mut model = boot();
+    let job_id = start(&mut model, JobKind::Kat as u32).unwrap();
+
+    // The job runs while the mailbox is free, so it can be polled
+    let status = loop {
+        let status = get_status(&mut model, job_id).unwrap();
+        if JobState::from(status.state) != JobState::Running {
+            break status;
+        }
+        assert!(status.steps_done < status.total_steps);
+        for _ in 0..10000 {
+            model.step();
+        }
+    };
+    assert_eq!(JobState::from(status.state), JobState::Done);
+    assert_eq!(status.steps_done, status.total_steps);
+    assert!(status.total_steps > 0);
+}
+
+#[test]
+fn test_job_cancel() {
+    let mut model = boot();
+    let job_id = start(&mut model, JobKind::Kat as u32).unwrap();
+
+    cancel(&mut model, job_id).unwrap();
+    let status = get_status(&mut model, job_id).unwrap();
+    assert_eq!(JobState::from(status.state), JobState::Cancelled);
+    assert!(status.steps_done < status.total_steps);
+
+    // The cancelled job stays put until another one replaces it
+    for _ in 0..10000 {
+        model.step();
+    }
+    let status = get_status(&mut model, job_id).unwrap();
+    assert_eq!(JobState::from(status.state), JobState::Cancelled);
+
+    let next_id = start(&mut model, JobKind::Kat as u32).unwrap();
+    assert_ne!(next_id, job_id);
+    let resp = get_status(&mut model, job_id).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_INVALID_ID, resp);
+}
+
+#[test]
+fn test_job_in_progress() {
+    let mut model = boot();
+    start(&mut model, JobKind::Kat as u32).unwrap();
+
+    let resp = start(&mut model, JobKind::Kat as u32).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_IN_PROGRESS, resp);
+}
+
+#[test]
+fn test_job_invalid_kind() {
+    let mut model = boot();
+
+    let resp = start(&mut model, JobKind::Invalid as u32).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_INVALID_KIND, resp);
+}
+
+#[test]
+fn test_job_self_test_kind() {
+    let mut model = boot();
+
+    // The self test is only started by SELF_TEST_START
+    let resp = start(&mut model, JobKind::SelfTest as u32).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_INVALID_KIND, resp);
+}
+
+#[test]
+fn test_job_sha384() {
+    let mut model = boot();
+    let data: Vec<u8> = (0..10000u32).map(|i| i as u8).collect();
+
+    let job_id = start_with_input(&mut model, JobKind::Sha384, &data);
+    let status = get_status(&mut model, job_id).unwrap();
+    assert_eq!(status.total_steps, 10);
+    let status = wait(&mut model, job_id);
+    assert_eq!(JobState::from(status.state), JobState::Done);
+    assert_eq!(status.steps_done, status.total_steps);
+    assert_eq!(
+        &status.result[..status.result_size as usize],
+        Sha384::digest(&data).as_slice()
+    );
+
+    // Jobs that take input are not started by JOB_START
+    let resp = start(&mut model, JobKind::Sha384 as u32).unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_JOB_INVALID_KIND, resp);
+}
+
+#[test]
+#[cfg(feature = "hw-2.x")]
+fn test_job_mldsa87_verify() {
+    use caliptra_common::mailbox_api::Mldsa87VerifyJobInput;
+    use fips204::ml_dsa_87::try_keygen;
+    use fips204::traits::{SerDes, Signer};
+    use zerocopy::IntoBytes;
+
+    let mut model = boot();
+    let (pub_key, priv_key) = try_keygen().unwrap();
+    let mut input = Mldsa87VerifyJobInput::default();
+    input.message[..5].copy_from_slice(b"hello");
+    let signature = priv_key.try_sign(&input.message).unwrap();
+    input.pub_key.copy_from_slice(&pub_key.into_bytes());
+    input.signature[..signature.len()].copy_from_slice(&signature);
+
+    let job_id = start_with_input(&mut model, JobKind::Mldsa87Verify, input.as_bytes());
+    let status = wait(&mut model, job_id);
+    assert_eq!(JobState::from(status.state), JobState::Done);
+
+    // A signature over another message fails the job
+    input.message[0] ^= 1;
+    let job_id = start_with_input(&mut model, JobKind::Mldsa87Verify, input.as_bytes());
+    let status = wait(&mut model, job_id);
+    assert_eq!(JobState::from(status.state), JobState::Failed);
+}
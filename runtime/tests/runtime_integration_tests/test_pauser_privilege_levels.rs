@@ -10,16 +10,17 @@ use caliptra_builder::{
     ImageOptions,
 };
 use caliptra_common::mailbox_api::{
-    CertifyKeyExtendedFlags, CertifyKeyExtendedReq, CommandId, MailboxReq, MailboxReqHeader,
-    PopulateIdevCertReq, StashMeasurementReq,
+    CertifyKeyExtendedFlags, CertifyKeyExtendedReq, CommandId, DeriveKeyFlags, DeriveKeyReq,
+    MailboxReq, MailboxReqHeader, PopulateIdevCertReq, StashMeasurementReq,
 };
 use caliptra_error::CaliptraError;
 use caliptra_hw_model::{BootParams, Fuses, HwModel, InitParams, SecurityState};
 use caliptra_image_crypto::OsslCrypto as Crypto;
 use caliptra_image_elf::ElfExecutable;
 use caliptra_image_gen::{ImageGenerator, ImageGeneratorConfig};
+use caliptra_image_types::ImageDpeConfig;
 use caliptra_runtime::{
-    RtBootStatus, PL0_DPE_ACTIVE_CONTEXT_THRESHOLD, PL1_DPE_ACTIVE_CONTEXT_THRESHOLD,
+    RtBootStatus, DPE_SUPPORT, PL0_DPE_ACTIVE_CONTEXT_THRESHOLD, PL1_DPE_ACTIVE_CONTEXT_THRESHOLD,
 };
 
 use dpe::{
@@ -426,6 +427,76 @@ fn test_revoke_export_cdi_handle_cannot_be_called_from_pl1() {
     );
 }
 
+#[test]
+fn test_get_events_cannot_be_called_from_pl1() {
+    let mut image_opts = ImageOptions::default();
+    image_opts.vendor_config.pl0_pauser = None;
+
+    let args = RuntimeTestArgs {
+        test_image_options: Some(image_opts),
+        ..Default::default()
+    };
+    let mut model = run_rt_test(args);
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let mut cmd = MailboxReq::GetEvents(MailboxReqHeader::default());
+    cmd.populate_chksum().unwrap();
+
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_EVENTS), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_INCORRECT_PAUSER_PRIVILEGE_LEVEL,
+        resp,
+    );
+}
+
+#[test]
+fn test_derive_key_export_cannot_be_called_from_pl1() {
+    let mut image_opts = ImageOptions::default();
+    image_opts.vendor_config.pl0_pauser = None;
+    // The owner allows export, but not to PL1
+    let mut owner_config = image_opts.owner_config.unwrap();
+    owner_config.dpe_config = ImageDpeConfig {
+        flags: ImageDpeConfig::ENABLED_FLAG | ImageDpeConfig::EXPORT_DERIVED_KEYS_FLAG,
+        pl0_context_limit: PL0_DPE_ACTIVE_CONTEXT_THRESHOLD as u32,
+        pl1_context_limit: PL1_DPE_ACTIVE_CONTEXT_THRESHOLD as u32,
+        support: DPE_SUPPORT.bits(),
+        allowed_commands: ImageDpeConfig::CMD_ALL,
+        ..Default::default()
+    };
+    image_opts.owner_config = Some(owner_config);
+
+    let args = RuntimeTestArgs {
+        test_image_options: Some(image_opts),
+        ..Default::default()
+    };
+    let mut model = run_rt_test(args);
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let mut cmd = MailboxReq::DeriveKey(DeriveKeyReq {
+        flags: DeriveKeyFlags::EXPORT.bits(),
+        ..Default::default()
+    });
+    cmd.populate_chksum().unwrap();
+
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::DERIVE_KEY), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_DERIVE_KEY_EXPORT_NOT_ALLOWED,
+        resp,
+    );
+}
+
 #[test]
 fn test_export_cdi_cannot_be_called_from_pl1() {
     let mut image_opts = ImageOptions::default();
@@ -4,10 +4,11 @@ use crate::common::{get_fmc_alias_cert, run_rt_test, RuntimeTestArgs};
 use caliptra_api::SocManager;
 
 use caliptra_common::mailbox_api::{
-    CommandId, ExtendPcrReq, IncrementPcrResetCounterReq, MailboxReq, MailboxReqHeader,
-    QuotePcrsReq, QuotePcrsResp,
+    CommandId, ExtendPcrReq, GetMeasurementLogReq, GetMeasurementLogResp, GetPcrLogReq,
+    GetPcrLogResp, IncrementPcrResetCounterReq, MailboxReq, MailboxReqHeader, QuotePcrsReq,
+    QuotePcrsResp,
 };
-use caliptra_drivers::PcrId;
+use caliptra_drivers::{pcr_log::PcrLogEntry, PcrId};
 use caliptra_error::CaliptraError;
 use caliptra_hw_model::{DefaultHwModel, HwModel, ModelError};
 use openssl::{
@@ -194,3 +195,99 @@ fn test_extend_pcr_cmd_reserved_range() {
         );
     }
 }
+
+fn get_pcr_log_page(model: &mut DefaultHwModel, start_index: u32) -> GetPcrLogResp {
+    let mut cmd = MailboxReq::GetPcrLog(GetPcrLogReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        start_index,
+    });
+    cmd.populate_chksum().unwrap();
+
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::GET_PCR_LOG), cmd.as_bytes().unwrap())
+        .unwrap()
+        .unwrap();
+    GetPcrLogResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+#[test]
+fn test_get_pcr_log_replay() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    // Read the full log one page at a time
+    let mut entries = vec![];
+    loop {
+        let resp = get_pcr_log_page(&mut model, entries.len() as u32);
+        let page = resp.entries().unwrap();
+        for entry in page {
+            entries.push(PcrLogEntry::read_from_bytes(entry).unwrap());
+        }
+        if page.is_empty() || entries.len() == resp.total_entries as usize {
+            break;
+        }
+    }
+    assert!(!entries.is_empty());
+
+    // Replaying the log must reproduce the PCRs extended during boot
+    let pcrs = get_model_pcrs(&mut model);
+    for pcr_id in [PcrId::PcrId0, PcrId::PcrId1, PcrId::PcrId2, PcrId::PcrId3] {
+        let mut pcr = [0u8; 48];
+        for entry in entries
+            .iter()
+            .filter(|e| e.pcr_ids & (1 << pcr_id as u8) != 0)
+        {
+            let mut hasher = Hasher::new(MessageDigest::sha384()).unwrap();
+            hasher.update(&pcr).unwrap();
+            hasher.update(entry.measured_data()).unwrap();
+            pcr.copy_from_slice(&hasher.finish().unwrap());
+        }
+        assert_eq!(pcr, pcrs[pcr_id as usize]);
+    }
+}
+
+#[test]
+fn test_get_pcr_log_invalid_start_index() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let total_entries = get_pcr_log_page(&mut model, 0).total_entries;
+
+    // Reading at the end of the log returns an empty page
+    let resp = get_pcr_log_page(&mut model, total_entries);
+    assert_eq!(resp.entry_count, 0);
+
+    let mut cmd = MailboxReq::GetPcrLog(GetPcrLogReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        start_index: total_entries + 1,
+    });
+    cmd.populate_chksum().unwrap();
+    assert_eq!(
+        model.mailbox_execute(u32::from(CommandId::GET_PCR_LOG), cmd.as_bytes().unwrap()),
+        Err(ModelError::MailboxCmdFailed(u32::from(
+            CaliptraError::RUNTIME_LOG_INVALID_START_INDEX
+        )))
+    );
+}
+
+#[test]
+fn test_get_measurement_log_empty() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+
+    let mut cmd = MailboxReq::GetMeasurementLog(GetMeasurementLogReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        start_index: 0,
+    });
+    cmd.populate_chksum().unwrap();
+
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::GET_MEASUREMENT_LOG),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap()
+        .unwrap();
+    let resp = GetMeasurementLogResp::read_from_bytes(resp.as_slice()).unwrap();
+
+    // No measurements are stashed with ROM in the default boot flow
+    assert_eq!(resp.total_entries, 0);
+    assert_eq!(resp.entry_count, 0);
+}
@@ -12,15 +12,25 @@ use caliptra_auth_man_types::{
     AuthManifestFlags, AuthManifestImageMetadata, AuthManifestPrivKeys, AuthManifestPubKeys,
     AuthorizationManifest, ImageMetadataFlags, AUTH_MANIFEST_IMAGE_METADATA_MAX_COUNT,
 };
-use caliptra_common::mailbox_api::{CommandId, MailboxReq, MailboxReqHeader, SetAuthManifestReq};
+use caliptra_common::mailbox_api::{
+    AuthorizeAndStashReq, AuthorizeAndStashResp, CommandId, MailboxReq, MailboxReqHeader,
+    SetAuthManifestReq,
+};
 use caliptra_error::CaliptraError;
-use caliptra_hw_model::HwModel;
+use caliptra_hw_model::{DefaultHwModel, HwModel};
 use caliptra_image_crypto::OsslCrypto as Crypto;
 use caliptra_image_fake_keys::*;
-use caliptra_runtime::RtBootStatus;
-use zerocopy::IntoBytes;
+use caliptra_runtime::{RtBootStatus, IMAGE_AUTHORIZED, IMAGE_NOT_AUTHORIZED};
+use zerocopy::{FromBytes, IntoBytes};
 
 pub fn create_auth_manifest(manifest_flags: AuthManifestFlags) -> AuthorizationManifest {
+    create_auth_manifest_with_svn(manifest_flags, 0)
+}
+
+fn create_auth_manifest_with_svn(
+    manifest_flags: AuthManifestFlags,
+    svn: u32,
+) -> AuthorizationManifest {
     let vendor_fw_key_info: AuthManifestGeneratorKeyConfig = AuthManifestGeneratorKeyConfig {
         pub_keys: AuthManifestPubKeys {
             ecc_pub_key: VENDOR_ECC_KEY_0_PUBLIC,
@@ -88,11 +98,13 @@ pub fn create_auth_manifest(manifest_flags: AuthManifestFlags) -> AuthorizationM
             fw_id: 1,
             flags: flags1.0,
             digest: IMAGE_DIGEST1,
+            ..Default::default()
         },
         AuthManifestImageMetadata {
             fw_id: 2,
             flags: flags2.0,
             digest: image_digest2,
+            ..Default::default()
         },
     ];
 
@@ -103,6 +115,8 @@ pub fn create_auth_manifest(manifest_flags: AuthManifestFlags) -> AuthorizationM
         owner_man_key_info,
         image_metadata_list,
         version: 1,
+        svn,
+        collection_id: 0,
         flags: manifest_flags,
     };
 
@@ -112,6 +126,23 @@ pub fn create_auth_manifest(manifest_flags: AuthManifestFlags) -> AuthorizationM
 
 pub fn create_auth_manifest_with_metadata(
     image_metadata_list: Vec<AuthManifestImageMetadata>,
+) -> AuthorizationManifest {
+    create_auth_manifest_with_metadata_and_svn(image_metadata_list, 0)
+}
+
+pub fn create_auth_manifest_with_metadata_and_svn(
+    image_metadata_list: Vec<AuthManifestImageMetadata>,
+    svn: u32,
+) -> AuthorizationManifest {
+    create_auth_manifest_chunk(image_metadata_list, svn, 0, 0, 1)
+}
+
+fn create_auth_manifest_chunk(
+    image_metadata_list: Vec<AuthManifestImageMetadata>,
+    svn: u32,
+    collection_id: u32,
+    chunk_index: u32,
+    chunk_count: u32,
 ) -> AuthorizationManifest {
     let vendor_fw_key_info: AuthManifestGeneratorKeyConfig = AuthManifestGeneratorKeyConfig {
         pub_keys: AuthManifestPubKeys {
@@ -166,14 +197,28 @@ pub fn create_auth_manifest_with_metadata(
         owner_man_key_info,
         image_metadata_list,
         version: 1,
+        svn,
+        collection_id,
         flags: AuthManifestFlags::VENDOR_SIGNATURE_REQUIRED,
     };
 
     let gen = AuthManifestGenerator::new(Crypto::default());
-    gen.generate(&gen_config).unwrap()
+    gen.generate_chunk(
+        &gen_config,
+        &gen_config.image_metadata_list,
+        chunk_index,
+        chunk_count,
+    )
+    .unwrap()
 }
 
 fn create_auth_manifest_of_metadata_size(metadata_size: usize) -> AuthorizationManifest {
+    let gen_config = auth_manifest_config_of_metadata_size(metadata_size);
+    let gen = AuthManifestGenerator::new(Crypto::default());
+    gen.generate(&gen_config).unwrap()
+}
+
+fn auth_manifest_config_of_metadata_size(metadata_size: usize) -> AuthManifestGeneratorConfig {
     let vendor_fw_key_info: AuthManifestGeneratorKeyConfig = AuthManifestGeneratorKeyConfig {
         pub_keys: AuthManifestPubKeys {
             ecc_pub_key: VENDOR_ECC_KEY_0_PUBLIC,
@@ -233,21 +278,21 @@ fn create_auth_manifest_of_metadata_size(metadata_size: usize) -> AuthorizationM
             fw_id: id as u32,
             flags: flags.0,
             digest,
+            ..Default::default()
         })
     }
 
-    let gen_config: AuthManifestGeneratorConfig = AuthManifestGeneratorConfig {
+    AuthManifestGeneratorConfig {
         vendor_fw_key_info,
         vendor_man_key_info,
         owner_fw_key_info,
         owner_man_key_info,
         image_metadata_list,
         version: 1,
+        svn: 0,
+        collection_id: 0,
         flags: AuthManifestFlags::VENDOR_SIGNATURE_REQUIRED,
-    };
-
-    let gen = AuthManifestGenerator::new(Crypto::default());
-    gen.generate(&gen_config).unwrap()
+    }
 }
 
 #[test]
@@ -328,6 +373,332 @@ fn test_set_auth_manifest_cmd_invalid_len() {
     );
 }
 
+#[test]
+fn test_set_auth_manifest_svn_rollback() {
+    let mut model = run_rt_test_lms(RuntimeTestArgs::default(), true);
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let set_manifest = |model: &mut DefaultHwModel, svn: u32| {
+        let auth_manifest =
+            create_auth_manifest_with_svn(AuthManifestFlags::VENDOR_SIGNATURE_REQUIRED, svn);
+        let buf = auth_manifest.as_bytes();
+        let mut auth_manifest_slice = [0u8; SetAuthManifestReq::MAX_MAN_SIZE];
+        auth_manifest_slice[..buf.len()].copy_from_slice(buf);
+
+        let mut set_auth_manifest_cmd = MailboxReq::SetAuthManifest(SetAuthManifestReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            manifest_size: buf.len() as u32,
+            manifest: auth_manifest_slice,
+        });
+        set_auth_manifest_cmd.populate_chksum().unwrap();
+
+        model.mailbox_execute(
+            u32::from(CommandId::SET_AUTH_MANIFEST),
+            set_auth_manifest_cmd.as_bytes().unwrap(),
+        )
+    };
+
+    set_manifest(&mut model, 2)
+        .unwrap()
+        .expect("We should have received a response");
+
+    // A validly signed manifest with the same SVN is accepted.
+    set_manifest(&mut model, 2)
+        .unwrap()
+        .expect("We should have received a response");
+
+    // An older manifest is rejected.
+    let resp = set_manifest(&mut model, 1).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_AUTH_MANIFEST_SVN_TOO_LOW,
+        resp,
+    );
+
+    set_manifest(&mut model, 3)
+        .unwrap()
+        .expect("We should have received a response");
+}
+
+#[test]
+fn test_set_auth_manifest_same_svn_replay() {
+    let mut model = run_rt_test_lms(RuntimeTestArgs::default(), true);
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let set_manifest = |model: &mut DefaultHwModel, manifest: AuthorizationManifest| {
+        let buf = manifest.as_bytes_partial();
+        let mut auth_manifest_slice = [0u8; SetAuthManifestReq::MAX_MAN_SIZE];
+        auth_manifest_slice[..buf.len()].copy_from_slice(buf);
+
+        let mut set_auth_manifest_cmd = MailboxReq::SetAuthManifest(SetAuthManifestReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            manifest_size: buf.len() as u32,
+            manifest: auth_manifest_slice,
+        });
+        set_auth_manifest_cmd.populate_chksum().unwrap();
+
+        model.mailbox_execute(
+            u32::from(CommandId::SET_AUTH_MANIFEST),
+            set_auth_manifest_cmd.as_bytes().unwrap(),
+        )
+    };
+
+    let mut flags = ImageMetadataFlags(0);
+    flags.set_image_source(ImageHashSource::InRequest as u32);
+    let collection = |fw_id: u32, collection_id: u32| {
+        let image_metadata_list = vec![AuthManifestImageMetadata {
+            fw_id,
+            flags: flags.0,
+            digest: IMAGE_DIGEST1,
+            ..Default::default()
+        }];
+        create_auth_manifest_chunk(image_metadata_list, 2, collection_id, 0, 1)
+    };
+
+    set_manifest(&mut model, collection(1, 0))
+        .unwrap()
+        .expect("We should have received a response");
+
+    // The accepted collection can be sent again.
+    set_manifest(&mut model, collection(1, 0))
+        .unwrap()
+        .expect("We should have received a response");
+
+    // Another collection signed with the same SVN is rejected, whether or not
+    // it reuses the collection ID.
+    for collection_id in [0, 1] {
+        let resp = set_manifest(&mut model, collection(2, collection_id)).unwrap_err();
+        assert_error(
+            &mut model,
+            CaliptraError::RUNTIME_AUTH_MANIFEST_SVN_TOO_LOW,
+            resp,
+        );
+    }
+
+    for (fw_id, expected) in [(1u32, IMAGE_AUTHORIZED), (2, IMAGE_NOT_AUTHORIZED)] {
+        let mut authorize_and_stash_cmd = MailboxReq::AuthorizeAndStash(AuthorizeAndStashReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            fw_id: fw_id.to_le_bytes(),
+            measurement: IMAGE_DIGEST1,
+            source: ImageHashSource::InRequest as u32,
+            flags: 1, // Skip stash
+            ..Default::default()
+        });
+        authorize_and_stash_cmd.populate_chksum().unwrap();
+
+        let resp = model
+            .mailbox_execute(
+                u32::from(CommandId::AUTHORIZE_AND_STASH),
+                authorize_and_stash_cmd.as_bytes().unwrap(),
+            )
+            .unwrap()
+            .expect("We should have received a response");
+
+        let authorize_and_stash_resp =
+            AuthorizeAndStashResp::read_from_bytes(resp.as_slice()).unwrap();
+        assert_eq!(authorize_and_stash_resp.auth_req_result, expected);
+    }
+}
+
+#[test]
+fn test_set_auth_manifest_chunks() {
+    let mut model = run_rt_test_lms(RuntimeTestArgs::default(), true);
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let mut flags = ImageMetadataFlags(0);
+    flags.set_image_source(ImageHashSource::InRequest as u32);
+    let collection_chunk =
+        |fw_ids: &[u32], collection_id: u32, chunk_index: u32, chunk_count: u32| {
+            let image_metadata_list = fw_ids
+                .iter()
+                .map(|fw_id| AuthManifestImageMetadata {
+                    fw_id: *fw_id,
+                    flags: flags.0,
+                    digest: IMAGE_DIGEST1,
+                    ..Default::default()
+                })
+                .collect();
+            create_auth_manifest_chunk(
+                image_metadata_list,
+                0,
+                collection_id,
+                chunk_index,
+                chunk_count,
+            )
+        };
+    // Chunks of collection 1, which has three chunks
+    let chunk = |fw_ids: &[u32], chunk_index: u32| collection_chunk(fw_ids, 1, chunk_index, 3);
+
+    let set_manifest = |model: &mut DefaultHwModel, manifest: AuthorizationManifest| {
+        let buf = manifest.as_bytes_partial();
+        let mut auth_manifest_slice = [0u8; SetAuthManifestReq::MAX_MAN_SIZE];
+        auth_manifest_slice[..buf.len()].copy_from_slice(buf);
+
+        let mut set_auth_manifest_cmd = MailboxReq::SetAuthManifest(SetAuthManifestReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            manifest_size: buf.len() as u32,
+            manifest: auth_manifest_slice,
+        });
+        set_auth_manifest_cmd.populate_chksum().unwrap();
+
+        model.mailbox_execute(
+            u32::from(CommandId::SET_AUTH_MANIFEST),
+            set_auth_manifest_cmd.as_bytes().unwrap(),
+        )
+    };
+
+    // A chunk cannot be appended before chunk 0.
+    let resp = set_manifest(&mut model, chunk(&[2], 1)).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_AUTH_MANIFEST_CHUNK_OUT_OF_ORDER,
+        resp,
+    );
+
+    set_manifest(&mut model, chunk(&[1, 3], 0))
+        .unwrap()
+        .expect("We should have received a response");
+    set_manifest(&mut model, chunk(&[4, 2], 1))
+        .unwrap()
+        .expect("We should have received a response");
+
+    // Chunks are appended in order only.
+    let resp = set_manifest(&mut model, chunk(&[5], 1)).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_AUTH_MANIFEST_CHUNK_OUT_OF_ORDER,
+        resp,
+    );
+
+    // Firmware IDs must be unique across chunks.
+    let resp = set_manifest(&mut model, chunk(&[3], 2)).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_AUTH_MANIFEST_IMAGE_METADATA_LIST_DUPLICATE_FIRMWARE_ID,
+        resp,
+    );
+
+    // Replaying chunk 0 cannot drop the chunks loaded after it.
+    let resp = set_manifest(&mut model, chunk(&[1, 3], 0)).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_AUTH_MANIFEST_COLLECTION_ALREADY_LOADED,
+        resp,
+    );
+
+    // Chunks of another collection cannot be spliced in.
+    let resp = set_manifest(&mut model, collection_chunk(&[5], 2, 2, 3)).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_AUTH_MANIFEST_CHUNK_COLLECTION_MISMATCH,
+        resp,
+    );
+    let resp = set_manifest(&mut model, collection_chunk(&[5], 1, 2, 4)).unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_AUTH_MANIFEST_CHUNK_COLLECTION_MISMATCH,
+        resp,
+    );
+
+    for fw_id in 1..=4u32 {
+        let mut authorize_and_stash_cmd = MailboxReq::AuthorizeAndStash(AuthorizeAndStashReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            fw_id: fw_id.to_le_bytes(),
+            measurement: IMAGE_DIGEST1,
+            source: ImageHashSource::InRequest as u32,
+            ..Default::default()
+        });
+        authorize_and_stash_cmd.populate_chksum().unwrap();
+
+        let resp = model
+            .mailbox_execute(
+                u32::from(CommandId::AUTHORIZE_AND_STASH),
+                authorize_and_stash_cmd.as_bytes().unwrap(),
+            )
+            .unwrap()
+            .expect("We should have received a response");
+
+        let authorize_and_stash_resp =
+            AuthorizeAndStashResp::read_from_bytes(resp.as_slice()).unwrap();
+        assert_eq!(authorize_and_stash_resp.auth_req_result, IMAGE_AUTHORIZED);
+    }
+}
+
+#[test]
+fn test_set_auth_manifest_chunks_above_max_count() {
+    let mut model = run_rt_test_lms(RuntimeTestArgs::default(), true);
+
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    // More entries than fit in a single manifest, sent in three chunks.
+    let entry_count = 110;
+    let gen_config = auth_manifest_config_of_metadata_size(entry_count);
+    let gen = AuthManifestGenerator::new(Crypto::default());
+    let chunks = gen_config.image_metadata_list.chunks(40);
+    let chunk_count = chunks.len() as u32;
+    for (chunk_index, slice) in chunks.enumerate() {
+        let manifest = gen
+            .generate_chunk(&gen_config, slice, chunk_index as u32, chunk_count)
+            .unwrap();
+        let buf = manifest.as_bytes_partial();
+        let mut auth_manifest_slice = [0u8; SetAuthManifestReq::MAX_MAN_SIZE];
+        auth_manifest_slice[..buf.len()].copy_from_slice(buf);
+
+        let mut set_auth_manifest_cmd = MailboxReq::SetAuthManifest(SetAuthManifestReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            manifest_size: buf.len() as u32,
+            manifest: auth_manifest_slice,
+        });
+        set_auth_manifest_cmd.populate_chksum().unwrap();
+
+        model
+            .mailbox_execute(
+                u32::from(CommandId::SET_AUTH_MANIFEST),
+                set_auth_manifest_cmd.as_bytes().unwrap(),
+            )
+            .unwrap()
+            .expect("We should have received a response");
+    }
+
+    for (fw_id, expected) in [
+        (0, IMAGE_AUTHORIZED),
+        (70, IMAGE_AUTHORIZED),
+        (entry_count as u32 - 1, IMAGE_AUTHORIZED),
+        (entry_count as u32, IMAGE_NOT_AUTHORIZED),
+    ] {
+        let mut authorize_and_stash_cmd = MailboxReq::AuthorizeAndStash(AuthorizeAndStashReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            fw_id: fw_id.to_le_bytes(),
+            source: ImageHashSource::InRequest as u32,
+            ..Default::default()
+        });
+        authorize_and_stash_cmd.populate_chksum().unwrap();
+
+        let resp = model
+            .mailbox_execute(
+                u32::from(CommandId::AUTHORIZE_AND_STASH),
+                authorize_and_stash_cmd.as_bytes().unwrap(),
+            )
+            .unwrap()
+            .expect("We should have received a response");
+
+        let authorize_and_stash_resp =
+            AuthorizeAndStashResp::read_from_bytes(resp.as_slice()).unwrap();
+        assert_eq!(authorize_and_stash_resp.auth_req_result, expected);
+    }
+}
+
 fn set_manifest_command_execute(
     manifest: AuthorizationManifest,
     lms_verify: bool,
@@ -398,6 +769,7 @@ fn test_set_auth_manifest_cmd_max_plus_one_metadata_entry_limit() {
             fw_id: 127,
             flags: flags.0,
             digest: IMAGE_DIGEST1,
+            ..Default::default()
         };
     }
 
@@ -0,0 +1,136 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{assert_error, run_rt_test, RuntimeTestArgs, TEST_DIGEST, TEST_LABEL};
+use caliptra_api::SocManager;
+use caliptra_common::mailbox_api::{
+    CommandId, MailboxReq, MailboxReqHeader, SignWithDpeKeyFlags, SignWithDpeKeyReq,
+    SignWithDpeKeyResp, TagTciReq,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel};
+use caliptra_runtime::RtBootStatus;
+use openssl::{
+    bn::BigNum,
+    ec::{EcGroup, EcKey},
+    ecdsa::EcdsaSig,
+    nid::Nid,
+    pkey::PKey,
+    x509::X509,
+};
+use zerocopy::FromBytes;
+
+const TAG: u32 = 1;
+const DEFAULT_HANDLE: [u8; 16] = [0u8; 16];
+
+fn tag_default_context(model: &mut DefaultHwModel) {
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+    let mut cmd = MailboxReq::TagTci(TagTciReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        handle: DEFAULT_HANDLE,
+        tag: TAG,
+    });
+    cmd.populate_chksum().unwrap();
+    model
+        .mailbox_execute(u32::from(CommandId::DPE_TAG_TCI), cmd.as_bytes().unwrap())
+        .unwrap()
+        .expect("We expected a response");
+}
+
+fn sign_with_dpe_key_req(tag: u32, flags: SignWithDpeKeyFlags, label: [u8; 48]) -> MailboxReq {
+    let mut cmd = MailboxReq::SignWithDpeKey(SignWithDpeKeyReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        tag,
+        flags: flags.bits(),
+        label,
+        digest: TEST_DIGEST,
+    });
+    cmd.populate_chksum().unwrap();
+    cmd
+}
+
+fn sign_with_dpe_key(model: &mut DefaultHwModel, cmd: &MailboxReq) -> SignWithDpeKeyResp {
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::SIGN_WITH_DPE_KEY),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap()
+        .expect("We expected a response");
+    SignWithDpeKeyResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+#[test]
+fn test_sign_with_dpe_key_ecdsa() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    tag_default_context(&mut model);
+
+    let cmd = sign_with_dpe_key_req(TAG, SignWithDpeKeyFlags::empty(), TEST_LABEL);
+    let resp = sign_with_dpe_key(&mut model, &cmd);
+    assert_eq!(resp.new_context_handle, DEFAULT_HANDLE);
+
+    let sig = EcdsaSig::from_private_components(
+        BigNum::from_slice(&resp.signature_r).unwrap(),
+        BigNum::from_slice(&resp.signature_s).unwrap(),
+    )
+    .unwrap();
+    let ecc_pub_key = EcKey::from_public_key_affine_coordinates(
+        &EcGroup::from_curve_name(Nid::SECP384R1).unwrap(),
+        &BigNum::from_slice(&resp.derived_pubkey_x).unwrap(),
+        &BigNum::from_slice(&resp.derived_pubkey_y).unwrap(),
+    )
+    .unwrap();
+    assert!(sig.verify(&TEST_DIGEST, &ecc_pub_key).unwrap());
+
+    // The certificate is for the signing key
+    let cert = X509::from_der(&resp.cert[..resp.cert_size as usize]).unwrap();
+    assert!(cert
+        .public_key()
+        .unwrap()
+        .public_eq(&PKey::from_ec_key(ecc_pub_key).unwrap()));
+}
+
+#[test]
+fn test_sign_with_dpe_key_hmac() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    tag_default_context(&mut model);
+
+    let cmd = sign_with_dpe_key_req(TAG, SignWithDpeKeyFlags::HMAC, TEST_LABEL);
+    let resp = sign_with_dpe_key(&mut model, &cmd);
+    assert_ne!(resp.signature_r, [0u8; 48]);
+    assert_eq!(resp.signature_s, [0u8; 48]);
+    assert_eq!(resp.derived_pubkey_x, [0u8; 48]);
+    assert_eq!(resp.derived_pubkey_y, [0u8; 48]);
+    assert_eq!(resp.cert_size, 0);
+
+    // The same context and label give the same tag
+    assert_eq!(
+        sign_with_dpe_key(&mut model, &cmd).signature_r,
+        resp.signature_r
+    );
+
+    // A different label gives a different tag
+    let mut label = TEST_LABEL;
+    label[0] ^= 1;
+    let cmd = sign_with_dpe_key_req(TAG, SignWithDpeKeyFlags::HMAC, label);
+    assert_ne!(
+        sign_with_dpe_key(&mut model, &cmd).signature_r,
+        resp.signature_r
+    );
+}
+
+#[test]
+fn test_sign_with_dpe_key_unknown_tag() {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    tag_default_context(&mut model);
+
+    let cmd = sign_with_dpe_key_req(TAG + 1, SignWithDpeKeyFlags::empty(), TEST_LABEL);
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::SIGN_WITH_DPE_KEY),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_TAGGING_FAILURE, resp);
+}
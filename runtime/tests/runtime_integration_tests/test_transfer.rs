@@ -0,0 +1,363 @@
+// Licensed under the Apache-2.0 license
+
+use crate::common::{
+    assert_error, get_rt_alias_cert, run_rt_test, run_rt_test_lms, RuntimeTestArgs,
+};
+use crate::test_authorize_and_stash::IMAGE_DIGEST1;
+use crate::test_set_auth_manifest::create_auth_manifest;
+use caliptra_api::{mailbox::ImageHashSource, SocManager};
+use caliptra_auth_man_types::AuthManifestFlags;
+use caliptra_common::mailbox_api::{
+    AuthorizeAndStashReq, AuthorizeAndStashResp, CommandId, GetPcrLogReq, GetPcrLogResp,
+    MailboxReq, MailboxReqHeader, TransferBeginReq, TransferBeginResp, TransferContinueReq,
+    TransferContinueResp, TransferEndReq, TransferSource, TransferWriteReq, PCR_LOG_ENTRY_SIZE,
+};
+use caliptra_error::CaliptraError;
+use caliptra_hw_model::{DefaultHwModel, HwModel};
+use caliptra_runtime::{RtBootStatus, IMAGE_AUTHORIZED};
+use zerocopy::{FromBytes, IntoBytes};
+
+fn boot() -> DefaultHwModel {
+    let mut model = run_rt_test(RuntimeTestArgs::default());
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+    model
+}
+
+fn begin(model: &mut DefaultHwModel, source: TransferSource) -> TransferBeginResp {
+    let mut cmd = MailboxReq::TransferBegin(TransferBeginReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        source: source as u32,
+        ..Default::default()
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_BEGIN),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap()
+        .expect("We expected a response");
+    TransferBeginResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+fn begin_write(model: &mut DefaultHwModel, cmd: CommandId, total_size: u32) -> TransferBeginResp {
+    let mut cmd = MailboxReq::TransferBegin(TransferBeginReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        source: TransferSource::Caller as u32,
+        cmd: cmd.into(),
+        total_size,
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_BEGIN),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap()
+        .expect("We expected a response");
+    TransferBeginResp::read_from_bytes(resp.as_slice()).unwrap()
+}
+
+fn write_req(transfer_id: u32, offset: u32, data: &[u8]) -> MailboxReq {
+    let mut req = TransferWriteReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        transfer_id,
+        offset,
+        data_size: data.len() as u32,
+        ..Default::default()
+    };
+    req.data[..data.len()].copy_from_slice(data);
+    let mut cmd = MailboxReq::TransferWrite(req);
+    cmd.populate_chksum().unwrap();
+    cmd
+}
+
+fn end_req(transfer_id: u32) -> MailboxReq {
+    let mut cmd = MailboxReq::TransferEnd(TransferEndReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        transfer_id,
+    });
+    cmd.populate_chksum().unwrap();
+    cmd
+}
+
+fn continue_req(transfer_id: u32, offset: u32) -> MailboxReq {
+    let mut cmd = MailboxReq::TransferContinue(TransferContinueReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        transfer_id,
+        offset,
+    });
+    cmd.populate_chksum().unwrap();
+    cmd
+}
+
+#[test]
+fn test_transfer_pcr_log() {
+    let mut model = boot();
+
+    // The transfer returns the same entries as paging through GET_PCR_LOG
+    let mut expected = vec![];
+    loop {
+        let mut cmd = MailboxReq::GetPcrLog(GetPcrLogReq {
+            hdr: MailboxReqHeader { chksum: 0 },
+            start_index: (expected.len() / PCR_LOG_ENTRY_SIZE) as u32,
+        });
+        cmd.populate_chksum().unwrap();
+        let resp = model
+            .mailbox_execute(u32::from(CommandId::GET_PCR_LOG), cmd.as_bytes().unwrap())
+            .unwrap()
+            .unwrap();
+        let resp = GetPcrLogResp::read_from_bytes(resp.as_slice()).unwrap();
+        let page = resp.entries().unwrap();
+        expected.extend_from_slice(&page.concat());
+        if page.is_empty() || expected.len() / PCR_LOG_ENTRY_SIZE == resp.total_entries as usize {
+            break;
+        }
+    }
+    assert!(!expected.is_empty());
+
+    let mut buf = vec![0u8; expected.len()];
+    let size = model
+        .read_transfer(TransferSource::PcrLog, &mut buf)
+        .unwrap();
+    assert_eq!(size, expected.len());
+    assert_eq!(buf, expected);
+}
+
+#[test]
+fn test_transfer_cert_chain() {
+    let mut model = boot();
+
+    let mut buf = vec![0u8; 4096];
+    let size = model
+        .read_transfer(TransferSource::CertChain, &mut buf)
+        .unwrap();
+
+    // The RT alias certificate ends the chain
+    let rt_resp = get_rt_alias_cert(&mut model);
+    let rt_cert = &rt_resp.data[..rt_resp.data_size as usize];
+    assert!(buf[..size].ends_with(rt_cert));
+
+    // A chunk starts at the requested offset
+    let begin = begin(&mut model, TransferSource::CertChain);
+    assert_eq!(begin.total_size as usize, size);
+    let cmd = continue_req(begin.transfer_id, 100);
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_CONTINUE),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap()
+        .unwrap();
+    let mut chunk = TransferContinueResp::default();
+    chunk.as_mut_bytes()[..resp.len()].copy_from_slice(&resp);
+    assert_eq!(&chunk.data[..chunk.data_size as usize], &buf[100..size]);
+}
+
+#[test]
+fn test_transfer_invalid_source() {
+    let mut model = boot();
+
+    let mut cmd = MailboxReq::TransferBegin(TransferBeginReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        source: TransferSource::Invalid as u32,
+        ..Default::default()
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_BEGIN),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_TRANSFER_INVALID_SOURCE,
+        resp,
+    );
+}
+
+#[test]
+fn test_transfer_invalid_offset() {
+    let mut model = boot();
+    let begin = begin(&mut model, TransferSource::CertChain);
+
+    let cmd = continue_req(begin.transfer_id, begin.total_size + 1);
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_CONTINUE),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_TRANSFER_INVALID_OFFSET,
+        resp,
+    );
+}
+
+#[test]
+fn test_transfer_ended() {
+    let mut model = boot();
+    let first = begin(&mut model, TransferSource::CertChain);
+
+    // Beginning a transfer ends the open one
+    let second = begin(&mut model, TransferSource::PcrLog);
+    assert_ne!(first.transfer_id, second.transfer_id);
+    let cmd = continue_req(first.transfer_id, 0);
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_CONTINUE),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_TRANSFER_INVALID_ID, resp);
+
+    let cmd = end_req(second.transfer_id);
+    model
+        .mailbox_execute(u32::from(CommandId::TRANSFER_END), cmd.as_bytes().unwrap())
+        .unwrap();
+
+    let cmd = continue_req(second.transfer_id, 0);
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_CONTINUE),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_TRANSFER_INVALID_ID, resp);
+}
+
+#[test]
+fn test_transfer_other_locality() {
+    let mut model = boot();
+    model.set_apb_pauser(0x01);
+    let first = begin(&mut model, TransferSource::CertChain);
+
+    // Another PAUSER cannot end the open transfer by beginning one
+    model.set_apb_pauser(0x02);
+    let mut cmd = MailboxReq::TransferBegin(TransferBeginReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        source: TransferSource::PcrLog as u32,
+        ..Default::default()
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_BEGIN),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_TRANSFER_IN_PROGRESS,
+        resp,
+    );
+
+    // The owner can still read from it
+    model.set_apb_pauser(0x01);
+    let cmd = continue_req(first.transfer_id, 0);
+    model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_CONTINUE),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap()
+        .unwrap();
+    let cmd = end_req(first.transfer_id);
+    model
+        .mailbox_execute(u32::from(CommandId::TRANSFER_END), cmd.as_bytes().unwrap())
+        .unwrap();
+
+    // Once it is ended, another PAUSER can begin a transfer
+    model.set_apb_pauser(0x02);
+    begin(&mut model, TransferSource::PcrLog);
+}
+
+#[test]
+fn test_transfer_set_auth_manifest() {
+    let mut model = run_rt_test_lms(RuntimeTestArgs::default(), true);
+    model.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtReadyForCommands)
+    });
+
+    let auth_manifest = create_auth_manifest(AuthManifestFlags::VENDOR_SIGNATURE_REQUIRED);
+    model
+        .write_transfer(
+            CommandId::SET_AUTH_MANIFEST,
+            auth_manifest.as_bytes(),
+            &mut [0u8; 8],
+        )
+        .unwrap();
+
+    // The manifest written through the transfer authorizes its images
+    let mut cmd = MailboxReq::AuthorizeAndStash(AuthorizeAndStashReq {
+        hdr: MailboxReqHeader { chksum: 0 },
+        fw_id: 1u32.to_le_bytes(),
+        measurement: IMAGE_DIGEST1,
+        source: ImageHashSource::InRequest as u32,
+        ..Default::default()
+    });
+    cmd.populate_chksum().unwrap();
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::AUTHORIZE_AND_STASH),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap()
+        .expect("We should have received a response");
+    let resp = AuthorizeAndStashResp::read_from_bytes(resp.as_slice()).unwrap();
+    assert_eq!(resp.auth_req_result, IMAGE_AUTHORIZED);
+}
+
+#[test]
+fn test_transfer_write_incomplete() {
+    let mut model = boot();
+    let begin = begin_write(&mut model, CommandId::SET_AUTH_MANIFEST, 100);
+    let data = [0u8; 100];
+
+    let cmd = write_req(begin.transfer_id, 0, &data[..50]);
+    model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_WRITE),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap();
+
+    // Chunks are written in order
+    let cmd = write_req(begin.transfer_id, 60, &data[60..]);
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_WRITE),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_TRANSFER_INVALID_OFFSET,
+        resp,
+    );
+
+    // A chunk cannot go past the size given at TRANSFER_BEGIN
+    let cmd = write_req(begin.transfer_id, 50, &[0u8; 51]);
+    let resp = model
+        .mailbox_execute(
+            u32::from(CommandId::TRANSFER_WRITE),
+            cmd.as_bytes().unwrap(),
+        )
+        .unwrap_err();
+    assert_error(
+        &mut model,
+        CaliptraError::RUNTIME_TRANSFER_INVALID_OFFSET,
+        resp,
+    );
+
+    // The command does not run on part of a request
+    let cmd = end_req(begin.transfer_id);
+    let resp = model
+        .mailbox_execute(u32::from(CommandId::TRANSFER_END), cmd.as_bytes().unwrap())
+        .unwrap_err();
+    assert_error(&mut model, CaliptraError::RUNTIME_TRANSFER_INCOMPLETE, resp);
+}
@@ -0,0 +1,456 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    control.rs
+
+Abstract:
+
+    File contains the socket control interface that lets a host drive the
+    emulator as if it were the SoC.
+
+--*/
+
+use caliptra_emu_bus::{Bus, Snapshot, SnapshotReader, SnapshotWriter};
+use caliptra_emu_cpu::{Cpu, StepAction};
+use caliptra_emu_periph::{CaliptraRootBus, MailboxExternal, MailboxRequester, SocToCaliptraBus};
+use caliptra_emu_types::RvSize;
+use caliptra_hw_model::BusMmio;
+use caliptra_hw_model_types::{EtrngResponse, RandomEtrngResponses, RandomNibbles};
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
+use std::io::{self, ErrorKind, Read, Write};
+use std::net::{TcpListener, TcpStream};
+use std::ops::RangeInclusive;
+use std::os::unix::net::{UnixListener, UnixStream};
+use std::path::Path;
+use std::rc::Rc;
+use std::thread;
+use std::time::Duration;
+
+/// APB PAUSER of requests until the host changes it
+const DEFAULT_APB_PAUSER: u32 = 1;
+
+const SOC_IFC_ORG: u32 = 0x3003_0000;
+
+/// Fuse registers in the SoC interface
+const FUSE_ADDRS: RangeInclusive<u32> = 0x3003_0200..=0x3003_0340;
+
+/// Number of instructions executed between polls of the control socket
+const POLL_INTERVAL: usize = 10_000;
+
+/// Number of cycles to wait for a mailbox command to complete
+const MBOX_TIMEOUT_CYCLES: u32 = 40_000_000;
+
+/// Longest request line accepted, enough for a hex encoded mailbox request
+const MAX_LINE_LEN: usize = 1024 * 1024;
+
+trait ControlStream: Read + Write {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()>;
+}
+
+impl ControlStream for TcpStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        TcpStream::set_nonblocking(self, nonblocking)
+    }
+}
+
+impl ControlStream for UnixStream {
+    fn set_nonblocking(&self, nonblocking: bool) -> io::Result<()> {
+        UnixStream::set_nonblocking(self, nonblocking)
+    }
+}
+
+enum ControlListener {
+    Tcp(TcpListener),
+    Unix(UnixListener),
+}
+
+impl ControlListener {
+    fn accept(&self) -> io::Result<Box<dyn ControlStream>> {
+        let stream: Box<dyn ControlStream> = match self {
+            Self::Tcp(listener) => Box::new(listener.accept()?.0),
+            Self::Unix(listener) => Box::new(listener.accept()?.0),
+        };
+        stream.set_nonblocking(true)?;
+        Ok(stream)
+    }
+}
+
+struct Connection {
+    stream: Box<dyn ControlStream>,
+    buf: Vec<u8>,
+}
+
+/// Entropy source that returns values injected over the control interface,
+/// falling back to `fallback` once they are used up.
+struct InjectedEntropy<T, I> {
+    queue: Rc<RefCell<VecDeque<T>>>,
+    fallback: I,
+}
+
+impl<T, I: Iterator<Item = T>> Iterator for InjectedEntropy<T, I> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<T> {
+        let injected = self.queue.borrow_mut().pop_front();
+        injected.or_else(|| self.fallback.next())
+    }
+}
+
+/// Line-based control interface to a running emulator.
+///
+/// Each request is a single line containing a command and its arguments
+/// separated by spaces. Numbers are decimal, or hexadecimal with a `0x`
+/// prefix; byte buffers are hex strings. Each request is answered by a
+/// single line starting with `OK` followed by any results, or `ERR`
+/// followed by a description of the failure.
+///
+/// | Command                          | Response              |
+/// |----------------------------------|-----------------------|
+/// | `apb_read <addr>`                | `OK <value>`          |
+/// | `apb_write <addr> <value>`       | `OK`                  |
+/// | `set_apb_pauser <value>`         | `OK`                  |
+/// | `fuse_write <addr> <value>`      | `OK`                  |
+/// | `fuse_done`                      | `OK`                  |
+/// | `mbox_execute <cmd> [<data>]`    | `OK [<response>]`     |
+/// | `warm_reset`                     | `OK`                  |
+/// | `cold_reset`                     | `OK`                  |
+/// | `itrng <nibbles>`                | `OK`                  |
+/// | `etrng <48 bytes>`               | `OK`                  |
+///
+/// The CPU does not start executing until the host writes
+/// `CPTRA_BOOTFSM_GO`, so fuses can be programmed first.
+pub struct ControlServer {
+    listener: ControlListener,
+    conn: Option<Connection>,
+    cpu_enabled: Rc<Cell<bool>>,
+    itrng_nibbles: Rc<RefCell<VecDeque<u8>>>,
+    etrng_responses: Rc<RefCell<VecDeque<EtrngResponse>>>,
+    power_on_state: Vec<u8>,
+    apb_pauser: u32,
+}
+
+impl ControlServer {
+    /// Listen for a control connection on localhost TCP `port`
+    pub fn bind_tcp(port: u16) -> io::Result<Self> {
+        let listener = TcpListener::bind(("localhost", port))?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(ControlListener::Tcp(listener)))
+    }
+
+    /// Listen for a control connection on the Unix socket at `path`
+    pub fn bind_unix(path: &Path) -> io::Result<Self> {
+        let listener = UnixListener::bind(path)?;
+        listener.set_nonblocking(true)?;
+        Ok(Self::new(ControlListener::Unix(listener)))
+    }
+
+    fn new(listener: ControlListener) -> Self {
+        Self {
+            listener,
+            conn: None,
+            cpu_enabled: Rc::new(Cell::new(false)),
+            itrng_nibbles: Default::default(),
+            etrng_responses: Default::default(),
+            power_on_state: Vec::new(),
+            apb_pauser: DEFAULT_APB_PAUSER,
+        }
+    }
+
+    /// Flag set when the host writes `CPTRA_BOOTFSM_GO`
+    pub fn cpu_enabled(&self) -> Rc<Cell<bool>> {
+        self.cpu_enabled.clone()
+    }
+
+    /// ITRNG nibbles for `CaliptraRootBusArgs`
+    pub fn itrng_nibbles(&self) -> Box<dyn Iterator<Item = u8>> {
+        Box::new(InjectedEntropy {
+            queue: self.itrng_nibbles.clone(),
+            fallback: RandomNibbles::new_from_thread_rng(),
+        })
+    }
+
+    /// ETRNG responses for `CaliptraRootBusArgs`
+    pub fn etrng_responses(&self) -> Box<dyn Iterator<Item = EtrngResponse>> {
+        Box::new(InjectedEntropy {
+            queue: self.etrng_responses.clone(),
+            fallback: RandomEtrngResponses::new_from_stdrng(),
+        })
+    }
+
+    /// Run `cpu`, serving control requests, until the CPU stops
+    pub fn run(mut self, mut cpu: Cpu<CaliptraRootBus>) {
+        // Cold reset restores the machine to its state at power on.
+        let mut w = SnapshotWriter::new();
+        cpu.save(&mut w);
+        self.power_on_state = w.into_bytes();
+
+        loop {
+            self.poll(&mut cpu);
+            if !self.cpu_enabled.get() {
+                thread::sleep(Duration::from_millis(1));
+                continue;
+            }
+            for _ in 0..POLL_INTERVAL {
+                if cpu.step(None) != StepAction::Continue {
+                    return;
+                }
+            }
+        }
+    }
+
+    fn poll(&mut self, cpu: &mut Cpu<CaliptraRootBus>) {
+        if self.conn.is_none() {
+            match self.listener.accept() {
+                Ok(stream) => {
+                    eprintln!("Control client connected");
+                    self.conn = Some(Connection {
+                        stream,
+                        buf: Vec::new(),
+                    });
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => return,
+                Err(e) => {
+                    eprintln!("Control accept failed: {e}");
+                    return;
+                }
+            }
+        }
+        let Some(conn) = &mut self.conn else {
+            return;
+        };
+
+        let mut chunk = [0u8; 4096];
+        let disconnected = loop {
+            match conn.stream.read(&mut chunk) {
+                Ok(0) => break true,
+                Ok(n) => {
+                    conn.buf.extend_from_slice(&chunk[..n]);
+                    if conn.buf.len() > MAX_LINE_LEN {
+                        break false;
+                    }
+                }
+                Err(e) if e.kind() == ErrorKind::WouldBlock => break false,
+                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                Err(_) => break true,
+            }
+        };
+        let mut lines = Vec::new();
+        while let Some(end) = conn.buf.iter().position(|&b| b == b'\n') {
+            let line: Vec<u8> = conn.buf.drain(..=end).collect();
+            lines.push(String::from_utf8_lossy(&line).trim().to_string());
+        }
+        // Drop a client that sends an overlong line instead of buffering it
+        let disconnected = disconnected || conn.buf.len() > MAX_LINE_LEN;
+
+        let mut responses = String::new();
+        for line in lines.iter().filter(|line| !line.is_empty()) {
+            let response = match self.handle(cpu, line) {
+                Ok(result) if result.is_empty() => "OK".to_string(),
+                Ok(result) => format!("OK {result}"),
+                Err(e) => format!("ERR {e}"),
+            };
+            responses.push_str(&response);
+            responses.push('\n');
+        }
+
+        let Some(conn) = &mut self.conn else {
+            return;
+        };
+        if !responses.is_empty() {
+            let written = conn
+                .stream
+                .set_nonblocking(false)
+                .and_then(|_| conn.stream.write_all(responses.as_bytes()))
+                .and_then(|_| conn.stream.set_nonblocking(true));
+            if written.is_err() {
+                self.conn = None;
+                return;
+            }
+        }
+        if disconnected {
+            eprintln!("Control client disconnected");
+            self.conn = None;
+        }
+    }
+
+    fn handle(&mut self, cpu: &mut Cpu<CaliptraRootBus>, line: &str) -> Result<String, String> {
+        let mut args = line.split_whitespace();
+        let cmd = args.next().unwrap_or_default();
+        let args: Vec<&str> = args.collect();
+        match (cmd, args.as_slice()) {
+            ("apb_read", [addr]) => {
+                let val = self
+                    .soc_bus(cpu)
+                    .read(RvSize::Word, parse_u32(addr)?)
+                    .map_err(|e| format!("bus error {e:?}"))?;
+                Ok(format!("{val:#010x}"))
+            }
+            ("apb_write", [addr, val]) => {
+                self.apb_write(cpu, parse_u32(addr)?, parse_u32(val)?)?;
+                Ok(String::new())
+            }
+            ("set_apb_pauser", [pauser]) => {
+                self.apb_pauser = parse_u32(pauser)?;
+                Ok(String::new())
+            }
+            ("fuse_write", [addr, val]) => {
+                let addr = parse_u32(addr)?;
+                if !FUSE_ADDRS.contains(&addr) {
+                    return Err(format!("{addr:#x} is not a fuse register"));
+                }
+                self.apb_write(cpu, addr, parse_u32(val)?)
+                    .map_err(|_| "fuses are locked")?;
+                Ok(String::new())
+            }
+            ("fuse_done", []) => {
+                self.soc_ifc(cpu)
+                    .cptra_fuse_wr_done()
+                    .write(|w| w.done(true));
+                Ok(String::new())
+            }
+            ("mbox_execute", [cmd]) => self.mbox_execute(cpu, parse_u32(cmd)?, &[]),
+            ("mbox_execute", [cmd, data]) => {
+                let data = hex::decode(data).map_err(|e| e.to_string())?;
+                self.mbox_execute(cpu, parse_u32(cmd)?, &data)
+            }
+            ("warm_reset", []) => {
+                cpu.warm_reset();
+                cpu.step(None);
+                Ok(String::new())
+            }
+            ("cold_reset", []) => {
+                let mut r = SnapshotReader::new(&self.power_on_state);
+                cpu.restore(&mut r)
+                    .and_then(|_| r.finish())
+                    .map_err(|e| e.to_string())?;
+                self.cpu_enabled.set(false);
+                Ok(String::new())
+            }
+            ("itrng", [nibbles]) => {
+                let nibbles = nibbles
+                    .chars()
+                    .map(|c| c.to_digit(16).map(|n| n as u8))
+                    .collect::<Option<Vec<u8>>>()
+                    .ok_or("nibbles must be hex digits")?;
+                self.itrng_nibbles.borrow_mut().extend(nibbles);
+                Ok(String::new())
+            }
+            ("etrng", [data]) => {
+                let data = hex::decode(data).map_err(|e| e.to_string())?;
+                let mut response = EtrngResponse {
+                    delay: 0,
+                    data: [0; 12],
+                };
+                if data.len() != response.data.len() * 4 {
+                    return Err("etrng response must be 48 bytes".into());
+                }
+                for (word, bytes) in response.data.iter_mut().zip(data.chunks_exact(4)) {
+                    *word = u32::from_be_bytes(bytes.try_into().unwrap());
+                }
+                self.etrng_responses.borrow_mut().push_back(response);
+                Ok(String::new())
+            }
+            _ => Err(format!("invalid command {line:?}")),
+        }
+    }
+
+    fn mbox_execute(
+        &mut self,
+        cpu: &mut Cpu<CaliptraRootBus>,
+        cmd: u32,
+        data: &[u8],
+    ) -> Result<String, String> {
+        if !self.cpu_enabled.get() {
+            return Err("CPU is not running".into());
+        }
+        let mbox = self.soc_bus(cpu).mailbox.regs();
+
+        // Read a 0 to get the lock
+        if mbox.lock().read().lock() {
+            return Err("unable to lock mailbox".into());
+        }
+        mbox.cmd().write(|_| cmd);
+        caliptra_hw_model::mbox_write_fifo(&mbox, data).map_err(|e| format!("{e:?}"))?;
+        mbox.execute().write(|w| w.execute(true));
+
+        // Clearing execute releases the lock, whether or not the command
+        // completed.
+        let result = self.mbox_wait_response(cpu, &mbox);
+        mbox.execute().write(|w| w.execute(false));
+        result
+    }
+
+    fn mbox_wait_response(
+        &self,
+        cpu: &mut Cpu<CaliptraRootBus>,
+        mbox: &caliptra_registers::mbox::RegisterBlock<BusMmio<MailboxExternal>>,
+    ) -> Result<String, String> {
+        let mut cycles = 0;
+        while mbox.status().read().status().cmd_busy() {
+            if cpu.step(None) != StepAction::Continue {
+                return Err("CPU stopped".into());
+            }
+            cycles += 1;
+            if cycles == MBOX_TIMEOUT_CYCLES {
+                return Err("mailbox timeout".into());
+            }
+        }
+
+        let status = mbox.status().read().status();
+        if status.cmd_failure() {
+            let soc_ifc = self.soc_ifc(cpu);
+            let error = match soc_ifc.cptra_fw_error_fatal().read() {
+                0 => soc_ifc.cptra_fw_error_non_fatal().read(),
+                fatal => fatal,
+            };
+            return Err(format!("mailbox command failed {error:#010x}"));
+        }
+
+        let mut response = Vec::new();
+        if status.data_ready() {
+            let dlen = mbox.dlen().read() as usize;
+            while response.len() < dlen {
+                response.extend_from_slice(&mbox.dataout().read().to_le_bytes());
+            }
+            response.truncate(dlen);
+        } else if !status.cmd_complete() {
+            return Err(format!("unknown mailbox status {:#x}", status as u32));
+        }
+        Ok(hex::encode(response))
+    }
+
+    fn soc_bus(&self, cpu: &Cpu<CaliptraRootBus>) -> SocToCaliptraBus {
+        cpu.bus
+            .soc_to_caliptra_bus(MailboxRequester::from(self.apb_pauser))
+    }
+
+    fn soc_ifc(
+        &self,
+        cpu: &Cpu<CaliptraRootBus>,
+    ) -> caliptra_registers::soc_ifc::RegisterBlock<BusMmio<SocToCaliptraBus>> {
+        unsafe {
+            caliptra_registers::soc_ifc::RegisterBlock::new_with_mmio(
+                SOC_IFC_ORG as *mut u32,
+                BusMmio::new(self.soc_bus(cpu)),
+            )
+        }
+    }
+
+    fn apb_write(&self, cpu: &Cpu<CaliptraRootBus>, addr: u32, val: u32) -> Result<(), String> {
+        self.soc_bus(cpu)
+            .write(RvSize::Word, addr, val)
+            .map_err(|e| format!("bus error {e:?}"))
+    }
+}
+
+fn parse_u32(s: &str) -> Result<u32, String> {
+    match s.strip_prefix("0x") {
+        Some(hex) => u32::from_str_radix(hex, 16),
+        None => s.parse(),
+    }
+    .map_err(|_| format!("invalid number {s:?}"))
+}
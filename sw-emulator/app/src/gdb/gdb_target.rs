@@ -14,13 +14,14 @@ Abstract:
 
 use caliptra_emu_cpu::xreg_file::XReg;
 use caliptra_emu_cpu::StepAction;
-use caliptra_emu_cpu::{Cpu, WatchPtrKind};
+use caliptra_emu_cpu::{Cpu, ExecRecorder, WatchPtrKind};
 use caliptra_emu_periph::CaliptraRootBus;
 use caliptra_emu_types::RvSize;
 use gdbstub::arch::SingleStepGdbBehavior;
 use gdbstub::common::Signal;
 use gdbstub::stub::SingleThreadStopReason;
 use gdbstub::target;
+use gdbstub::target::ext::base::reverse_exec::{ReplayLogPosition, ReverseCont, ReverseStep};
 use gdbstub::target::ext::base::singlethread::{SingleThreadBase, SingleThreadResume};
 use gdbstub::target::ext::base::BaseOps;
 use gdbstub::target::ext::breakpoints::WatchKind;
@@ -31,8 +32,17 @@ use gdbstub_arch;
 pub enum ExecMode {
     Step,
     Continue,
+    ReverseStep,
+    ReverseContinue,
 }
 
+// Memory regions that are restored when stepping backwards. Writes to any
+// other address are treated as MMIO and are not undone.
+const MBOX_SRAM_ORG: u32 = 0x3000_0000;
+const MBOX_SRAM_SIZE: u32 = 128 * 1024;
+const ICCM_ORG: u32 = 0x4000_0000;
+const DCCM_ORG: u32 = 0x5000_0000;
+
 pub struct GdbTarget {
     cpu: Cpu<CaliptraRootBus>,
     exec_mode: ExecMode,
@@ -40,8 +50,17 @@ pub struct GdbTarget {
 }
 
 impl GdbTarget {
-    // Create new instance of GdbTarget
-    pub fn new(cpu: Cpu<CaliptraRootBus>) -> Self {
+    // Create new instance of GdbTarget, recording up to `record_depth`
+    // instructions for reverse execution
+    pub fn new(mut cpu: Cpu<CaliptraRootBus>, record_depth: usize) -> Self {
+        cpu.start_recording(ExecRecorder::new(
+            record_depth,
+            vec![
+                MBOX_SRAM_ORG..MBOX_SRAM_ORG + MBOX_SRAM_SIZE,
+                ICCM_ORG..ICCM_ORG + CaliptraRootBus::ICCM_SIZE as u32,
+                DCCM_ORG..DCCM_ORG + CaliptraRootBus::DCCM_SIZE as u32,
+            ],
+        ));
         Self {
             cpu,
             exec_mode: ExecMode::Continue,
@@ -58,24 +77,49 @@ impl GdbTarget {
                         return SingleThreadStopReason::SwBreak(());
                     }
                 }
-                StepAction::Break => {
-                    let watch = self.cpu.get_watchptr_hit().unwrap();
-                    return SingleThreadStopReason::Watch {
-                        tid: (),
-                        kind: if watch.kind == WatchPtrKind::Write {
-                            WatchKind::Write
-                        } else {
-                            WatchKind::Read
-                        },
-                        addr: watch.addr,
-                    };
-                }
+                StepAction::Break => return self.watch_stop_reason(),
                 _ => break,
             }
         }
         SingleThreadStopReason::Exited(0)
     }
 
+    // Reverse Conditional Run (Private function)
+    fn reverse_cond_run(&mut self) -> SingleThreadStopReason<u32> {
+        loop {
+            match self.cpu.reverse_step() {
+                Some(StepAction::Break) => return self.watch_stop_reason(),
+                Some(_) => {
+                    if self.breakpoints.contains(&self.cpu.read_pc()) {
+                        return SingleThreadStopReason::SwBreak(());
+                    }
+                }
+                None => return Self::replay_log_begin(),
+            }
+        }
+    }
+
+    fn watch_stop_reason(&self) -> SingleThreadStopReason<u32> {
+        let watch = self.cpu.get_watchptr_hit().unwrap();
+        SingleThreadStopReason::Watch {
+            tid: (),
+            kind: if watch.kind == WatchPtrKind::Write {
+                WatchKind::Write
+            } else {
+                WatchKind::Read
+            },
+            addr: watch.addr,
+        }
+    }
+
+    // Reported when there are no more recorded instructions to undo
+    fn replay_log_begin() -> SingleThreadStopReason<u32> {
+        SingleThreadStopReason::ReplayLog {
+            tid: None,
+            pos: ReplayLogPosition::Begin,
+        }
+    }
+
     // run the gdb target
     pub fn run(&mut self) -> SingleThreadStopReason<u32> {
         match self.exec_mode {
@@ -84,6 +128,11 @@ impl GdbTarget {
                 SingleThreadStopReason::DoneStep
             }
             ExecMode::Continue => self.cond_run(),
+            ExecMode::ReverseStep => match self.cpu.reverse_step() {
+                Some(_) => SingleThreadStopReason::DoneStep,
+                None => Self::replay_log_begin(),
+            },
+            ExecMode::ReverseContinue => self.reverse_cond_run(),
         }
     }
 }
@@ -194,6 +243,36 @@ impl SingleThreadResume for GdbTarget {
     ) -> Option<target::ext::base::singlethread::SingleThreadSingleStepOps<'_, Self>> {
         Some(self)
     }
+
+    #[inline(always)]
+    fn support_reverse_step(
+        &mut self,
+    ) -> Option<target::ext::base::reverse_exec::ReverseStepOps<'_, (), Self>> {
+        Some(self)
+    }
+
+    #[inline(always)]
+    fn support_reverse_cont(
+        &mut self,
+    ) -> Option<target::ext::base::reverse_exec::ReverseContOps<'_, (), Self>> {
+        Some(self)
+    }
+}
+
+impl ReverseStep<()> for GdbTarget {
+    fn reverse_step(&mut self, _tid: ()) -> Result<(), Self::Error> {
+        self.exec_mode = ExecMode::ReverseStep;
+
+        Ok(())
+    }
+}
+
+impl ReverseCont<()> for GdbTarget {
+    fn reverse_cont(&mut self) -> Result<(), Self::Error> {
+        self.exec_mode = ExecMode::ReverseContinue;
+
+        Ok(())
+    }
 }
 
 impl target::ext::breakpoints::Breakpoints for GdbTarget {
@@ -13,15 +13,16 @@ Abstract:
 --*/
 
 use caliptra_api_types::{DeviceLifecycle, SecurityState};
-use caliptra_emu_bus::Clock;
+use caliptra_emu_bus::{Clock, EventTrace};
 use caliptra_emu_cpu::{Cpu, RvInstr, StepAction};
 use caliptra_emu_periph::soc_reg::DebugManufService;
 use caliptra_emu_periph::{
-    CaliptraRootBus, CaliptraRootBusArgs, DownloadIdevidCsrCb, MailboxInternal, MailboxRequester,
-    ReadyForFwCb, TbServicesCb, UploadUpdateFwCb,
+    ActionCb, CaliptraRootBus, CaliptraRootBusArgs, DownloadIdevidCsrCb, MailboxInternal,
+    MailboxRequester, ReadyForFwCb, TbServicesCb, UploadUpdateFwCb,
 };
 use caliptra_hw_model::BusMmio;
 use clap::{arg, value_parser, ArgAction};
+use std::cell::Cell;
 use std::fs::File;
 use std::io;
 use std::io::{Read, Write};
@@ -30,7 +31,10 @@ use std::process::exit;
 use std::rc::Rc;
 use tock_registers::interfaces::{ReadWriteable, Readable, Writeable};
 use tock_registers::registers::InMemoryRegister;
+mod control;
 mod gdb;
+mod timing;
+use crate::control::ControlServer;
 use crate::gdb::gdb_target::GdbTarget;
 use gdb::gdb_state;
 
@@ -45,7 +49,11 @@ const FW_WRITE_TICKS: u64 = 1000;
 const EXPECTED_CALIPTRA_BOOT_TIME_IN_CYCLES: u64 = 20_000_000; // 20 million cycles
 
 // CPU Main Loop (free_run no GDB)
-fn free_run(mut cpu: Cpu<CaliptraRootBus>, trace_path: Option<PathBuf>) {
+fn free_run(
+    cpu: &mut Cpu<CaliptraRootBus>,
+    trace_path: Option<PathBuf>,
+    exit_code: &Cell<Option<i32>>,
+) {
     if let Some(path) = trace_path {
         let mut f = File::create(path).unwrap();
         let trace_fn: &mut dyn FnMut(u32, RvInstr) = &mut |pc, instr| {
@@ -61,9 +69,9 @@ fn free_run(mut cpu: Cpu<CaliptraRootBus>, trace_path: Option<PathBuf>) {
         };
 
         // Need to have the loop in the same scope as trace_fn to prevent borrowing rules violation
-        while let StepAction::Continue = cpu.step(Some(trace_fn)) {}
+        while exit_code.get().is_none() && cpu.step(Some(trace_fn)) == StepAction::Continue {}
     } else {
-        while let StepAction::Continue = cpu.step(None) {}
+        while exit_code.get().is_none() && cpu.step(None) == StepAction::Continue {}
     };
 }
 
@@ -86,6 +94,24 @@ fn main() -> io::Result<()> {
             arg!(--"gdb-port" <VALUE> "Gdb Debugger")
                 .required(false)
         )
+        .arg(
+            arg!(--"gdb-record-depth" <COUNT> "Number of instructions recorded for GDB reverse execution")
+                .required(false)
+                .value_parser(value_parser!(usize))
+                .default_value("1000000")
+        )
+        .arg(
+            arg!(--"control-port" <PORT> "Serve the control interface on a localhost TCP port")
+                .required(false)
+                .value_parser(value_parser!(u16))
+                .conflicts_with_all(&["gdb-port", "trace-instr"])
+        )
+        .arg(
+            arg!(--"control-socket" <PATH> "Serve the control interface on a Unix socket")
+                .required(false)
+                .value_parser(value_parser!(PathBuf))
+                .conflicts_with_all(&["gdb-port", "trace-instr", "control-port"])
+        )
         .arg(
             arg!(--"firmware" <FILE> "Current Firmware image file")
                 .required(false)
@@ -101,6 +127,30 @@ fn main() -> io::Result<()> {
                 .required(false)
                 .action(ArgAction::SetTrue)
         )
+        .arg(
+            arg!(--"timing-config" <FILE> "TOML file with instruction costs, memory wait states and peripheral latencies")
+                .required(false)
+                .value_parser(value_parser!(PathBuf))
+        )
+        .arg(
+            arg!(--"cycle-profile" ... "Write the cycles spent in each function to a file in log-dir")
+                .required(false)
+                .action(ArgAction::SetTrue)
+                .conflicts_with_all(&["gdb-port", "control-port", "control-socket"])
+        )
+        .arg(
+            arg!(--"profile-elf" <FILE> "ELF file used to name the functions in the cycle profile; may be repeated")
+                .required(false)
+                .value_parser(value_parser!(PathBuf))
+                .action(ArgAction::Append)
+                .requires("cycle-profile")
+        )
+        .arg(
+            arg!(--"trace-events" <FORMAT> "Write peripheral accesses, mailbox states, interrupts and key vault writes to a file in log-dir [json, vcd]")
+                .required(false)
+                .value_parser(value_parser!(String))
+                .conflicts_with_all(&["gdb-port", "control-port", "control-socket"])
+        )
         .arg(
             arg!(--"ueid" <U128> "128-bit Unique Endpoint Id")
                 .required(false)
@@ -176,6 +226,27 @@ fn main() -> io::Result<()> {
         }
     };
     let args_device_lifecycle = args.get_one::<String>("device-lifecycle").unwrap();
+    let cycle_profile = args.get_flag("cycle-profile");
+    let trace_events = match args.get_one::<String>("trace-events").map(String::as_str) {
+        None => None,
+        Some("json") => Some("caliptra_events.json"),
+        Some("vcd") => Some("caliptra_events.vcd"),
+        Some(other) => {
+            println!("Unknown event trace format {:?}", other);
+            exit(-1);
+        }
+    };
+
+    let (timing_model, periph_latencies) = match args.get_one::<PathBuf>("timing-config") {
+        Some(path) => match timing::load_timing_config(path) {
+            Ok(config) => config,
+            Err(e) => {
+                println!("Unable to load timing config {:?}: {}", path, e);
+                exit(-1);
+            }
+        },
+        None => Default::default(),
+    };
 
     if !Path::new(&args_rom).exists() {
         println!("ROM File {:?} does not exist", args_rom);
@@ -231,6 +302,16 @@ fn main() -> io::Result<()> {
 
     let log_dir = Rc::new(args_log_dir.to_path_buf());
 
+    let control = if let Some(port) = args.get_one::<u16>("control-port") {
+        Some(ControlServer::bind_tcp(*port)?)
+    } else if let Some(path) = args.get_one::<PathBuf>("control-socket") {
+        Some(ControlServer::bind_unix(path)?)
+    } else {
+        None
+    };
+    // A control client uploads firmware itself unless one was given
+    let auto_upload_fw = control.is_none() || args_current_fw.is_some();
+
     let clock = Clock::new();
 
     let req_idevid_csr = args.get_flag("req-idevid-csr");
@@ -249,15 +330,30 @@ fn main() -> io::Result<()> {
         },
     );
 
-    let bus_args = CaliptraRootBusArgs {
+    // The cycle profile and event trace are written when the main loop stops,
+    // so exit from there
+    let exit_code = Rc::new(Cell::new(None));
+    let exit_code_cb = exit_code.clone();
+    let request_exit = move |code| {
+        if cycle_profile || trace_events.is_some() {
+            exit_code_cb.set(Some(code));
+        } else {
+            exit(code);
+        }
+    };
+
+    let mut bus_args = CaliptraRootBusArgs {
         rom: rom_buffer,
         log_dir: args_log_dir.clone(),
         tb_services_cb: TbServicesCb::new(move |val| match val {
-            0x01 => exit(0xFF),
-            0xFF => exit(0x00),
+            0x01 => request_exit(0xFF),
+            0xFF => request_exit(0x00),
             _ => print!("{}", val as char),
         }),
         ready_for_fw_cb: ReadyForFwCb::new(move |args| {
+            if !auto_upload_fw {
+                return;
+            }
             let firmware_buffer = current_fw_buf.clone();
             args.schedule_later(FW_WRITE_TICKS, move |mailbox: &mut MailboxInternal| {
                 upload_fw_to_mailbox(mailbox, firmware_buffer);
@@ -276,10 +372,19 @@ fn main() -> io::Result<()> {
                 download_idev_id_csr(mailbox, log_dir.clone(), cptra_dbg_manuf_service_reg);
             },
         ),
+        latencies: periph_latencies,
         ..Default::default()
     };
+    if let Some(control) = &control {
+        let cpu_enabled = control.cpu_enabled();
+        bus_args.bootfsm_go_cb = ActionCb::new(move || cpu_enabled.set(true));
+        bus_args.itrng_nibbles = Some(control.itrng_nibbles());
+        bus_args.etrng_responses = control.etrng_responses();
+    }
 
-    let root_bus = CaliptraRootBus::new(&clock, bus_args);
+    let mut root_bus = CaliptraRootBus::new(&clock, bus_args);
+    let event_trace = trace_events.map(|_| EventTrace::new(&clock));
+    root_bus.set_event_trace(event_trace.clone());
     let soc_ifc = unsafe {
         caliptra_registers::soc_ifc::RegisterBlock::new_with_mmio(
             0x3003_0000 as *mut u32,
@@ -369,17 +474,27 @@ fn main() -> io::Result<()> {
             .write(|_| (*wdt_timeout >> 32) as u32);
     }
 
-    let cpu = Cpu::new(root_bus, clock);
+    let mut cpu = Cpu::new(root_bus, clock);
+    cpu.set_timing_model(timing_model);
+    if cycle_profile {
+        cpu.start_cycle_profile();
+    }
+    cpu.set_event_trace(event_trace.clone());
 
     // Check if Optional GDB Port is passed
-    match args.get_one::<String>("gdb-port") {
-        Some(port) => {
+    match (args.get_one::<String>("gdb-port"), control) {
+        (Some(port), _) => {
             // Create GDB Target Instance
-            let mut gdb_target = GdbTarget::new(cpu);
+            let record_depth = args.get_one::<usize>("gdb-record-depth").unwrap();
+            let mut gdb_target = GdbTarget::new(cpu, *record_depth);
 
             // Execute CPU through GDB State Machine
             gdb_state::wait_for_gdb_run(&mut gdb_target, port.parse().unwrap());
         }
+        (None, Some(control)) => {
+            // Run until the CPU stops, serving control requests
+            control.run(cpu);
+        }
         _ => {
             let instr_trace = if args.get_flag("trace-instr") {
                 let mut path = args_log_dir.clone();
@@ -390,7 +505,35 @@ fn main() -> io::Result<()> {
             };
 
             // If no GDB Port is passed, Free Run
-            free_run(cpu, instr_trace);
+            free_run(&mut cpu, instr_trace, &exit_code);
+
+            if let Some(profile) = cpu.stop_cycle_profile() {
+                let mut functions = vec![];
+                for path in args
+                    .get_many::<PathBuf>("profile-elf")
+                    .into_iter()
+                    .flatten()
+                {
+                    functions.extend(timing::elf_functions(path)?);
+                }
+                let mut path = args_log_dir.clone();
+                path.push("caliptra_cycle_profile.txt");
+                timing::write_cycle_profile(&path, &profile, &functions)?;
+            }
+            if let (Some(event_trace), Some(file_name)) = (&event_trace, trace_events) {
+                let mut path = args_log_dir.clone();
+                path.push(file_name);
+                let mut w = io::BufWriter::new(File::create(&path)?);
+                if file_name.ends_with(".vcd") {
+                    event_trace.write_vcd(&mut w)?;
+                } else {
+                    event_trace.write_perfetto_json(&mut w)?;
+                }
+                w.flush()?;
+            }
+            if let Some(code) = exit_code.get() {
+                exit(code);
+            }
         }
     }
 
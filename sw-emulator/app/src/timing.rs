@@ -0,0 +1,205 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    timing.rs
+
+Abstract:
+
+    File contains the timing configuration file parser and the cycle profile
+    report writer.
+
+--*/
+
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::ops::Range;
+use std::path::Path;
+
+use caliptra_emu_cpu::{CycleProfile, TimingModel, WaitStates};
+use caliptra_emu_periph::PeriphLatencies;
+use caliptra_emu_types::RvAddr;
+use elf::endian::LittleEndian;
+use elf::ElfBytes;
+use serde_derive::Deserialize;
+
+/// Instruction costs in CPU clock cycles
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct InstrConfig {
+    alu: Option<u64>,
+    mul: Option<u64>,
+    div: Option<u64>,
+    load: Option<u64>,
+    store: Option<u64>,
+    branch: Option<u64>,
+    branch_taken: Option<u64>,
+    jump: Option<u64>,
+    csr: Option<u64>,
+    fence: Option<u64>,
+    mret: Option<u64>,
+    system: Option<u64>,
+    trap: Option<u64>,
+}
+
+/// Memory wait states for an address range
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct WaitStatesConfig {
+    start: RvAddr,
+    end: RvAddr,
+    #[serde(default)]
+    read: u64,
+    #[serde(default)]
+    write: u64,
+}
+
+/// Peripheral operation latencies in CPU clock cycles
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct LatencyConfig {
+    doe_op: Option<u64>,
+    ecc384_op: Option<u64>,
+    hmac_init: Option<u64>,
+    hmac_update: Option<u64>,
+    sha512_init: Option<u64>,
+    sha512_update: Option<u64>,
+    sha256_init: Option<u64>,
+    sha256_update: Option<u64>,
+    sha512_acc_op: Option<u64>,
+    ml_dsa87_op: Option<u64>,
+    key_rw: Option<u64>,
+}
+
+/// Timing Configuration. Values that are not set keep their defaults.
+#[derive(Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct TimingConfig {
+    #[serde(default)]
+    instr: InstrConfig,
+
+    #[serde(default)]
+    wait_states: Vec<WaitStatesConfig>,
+
+    #[serde(default)]
+    latencies: LatencyConfig,
+}
+
+/// Replace each listed field of `dest` that is set in `src`
+macro_rules! override_fields {
+    ($dest:expr, $src:expr, $($field:ident),*) => {
+        $(
+            if let Some(val) = $src.$field {
+                $dest.$field = val;
+            }
+        )*
+    };
+}
+
+/// Load the timing model and peripheral latencies from a TOML file
+pub(crate) fn load_timing_config(path: &Path) -> io::Result<(TimingModel, PeriphLatencies)> {
+    let config_str = std::fs::read_to_string(path)?;
+    let config: TimingConfig = toml::from_str(&config_str)
+        .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e.to_string()))?;
+
+    let mut timing = TimingModel::default();
+    override_fields!(
+        timing,
+        config.instr,
+        alu,
+        mul,
+        div,
+        load,
+        store,
+        branch,
+        branch_taken,
+        jump,
+        csr,
+        fence,
+        mret,
+        system,
+        trap
+    );
+    timing.wait_states = config
+        .wait_states
+        .iter()
+        .map(|w| WaitStates {
+            range: w.start..w.end,
+            read: w.read,
+            write: w.write,
+        })
+        .collect();
+
+    let mut latencies = PeriphLatencies::default();
+    override_fields!(
+        latencies,
+        config.latencies,
+        doe_op,
+        ecc384_op,
+        hmac_init,
+        hmac_update,
+        sha512_init,
+        sha512_update,
+        sha256_init,
+        sha256_update,
+        sha512_acc_op,
+        ml_dsa87_op,
+        key_rw
+    );
+    Ok((timing, latencies))
+}
+
+/// Read the name and address range of each function in an ELF file
+pub(crate) fn elf_functions(path: &Path) -> io::Result<Vec<(String, Range<RvAddr>)>> {
+    let other_err = |e: elf::ParseError| io::Error::new(io::ErrorKind::InvalidData, e);
+    let elf_bytes = std::fs::read(path)?;
+    let elf = ElfBytes::<LittleEndian>::minimal_parse(&elf_bytes).map_err(other_err)?;
+    let Some((symbols, strings)) = elf.symbol_table().map_err(other_err)? else {
+        return Ok(vec![]);
+    };
+    let mut result = vec![];
+    for sym in symbols.iter() {
+        if sym.st_symtype() != elf::abi::STT_FUNC || sym.st_size == 0 {
+            continue;
+        }
+        let name = strings.get(sym.st_name as usize).map_err(other_err)?;
+        let start = sym.st_value as RvAddr;
+        result.push((name.to_string(), start..start + sym.st_size as RvAddr));
+    }
+    Ok(result)
+}
+
+/// Write the cycles spent in each function, or at each program counter when
+/// no functions are known, most expensive first
+pub(crate) fn write_cycle_profile(
+    path: &Path,
+    profile: &CycleProfile,
+    functions: &[(String, Range<RvAddr>)],
+) -> io::Result<()> {
+    let mut f = BufWriter::new(File::create(path)?);
+    writeln!(
+        f,
+        "# total cycles: {}, idle cycles: {}",
+        profile.total_cycles(),
+        profile.idle_cycles()
+    )?;
+    writeln!(f, "# cycles count name")?;
+    if functions.is_empty() {
+        let mut pcs: Vec<_> = profile.pcs().iter().collect();
+        pcs.sort_by(|a, b| b.1.cycles.cmp(&a.1.cycles).then(a.0.cmp(b.0)));
+        for (pc, pc_cycles) in pcs {
+            writeln!(f, "{} {} 0x{:08x}", pc_cycles.cycles, pc_cycles.count, pc)?;
+        }
+    } else {
+        for function in profile.by_function(functions) {
+            writeln!(
+                f,
+                "{} {} {}",
+                function.cycles, function.count, function.name
+            )?;
+        }
+    }
+    f.flush()
+}
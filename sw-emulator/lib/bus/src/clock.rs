@@ -19,7 +19,7 @@ use std::{
     rc::Rc,
 };
 
-use crate::Bus;
+use crate::{Bus, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter};
 
 /// Peripherals that want to use timer-based deferred execution will typically
 /// store a clone of Timer inside themselves, and use it to schedule future
@@ -366,6 +366,133 @@ impl ClockImpl {
     }
 }
 
+impl Snapshot for TimerAction {
+    fn save(&self, w: &mut SnapshotWriter) {
+        match *self {
+            TimerAction::Poll => 0u8.save(w),
+            TimerAction::WarmReset => 1u8.save(w),
+            TimerAction::UpdateReset => 2u8.save(w),
+            TimerAction::Nmi { mcause } => {
+                3u8.save(w);
+                mcause.save(w);
+            }
+            TimerAction::SetNmiVec { addr } => {
+                4u8.save(w);
+                addr.save(w);
+            }
+            TimerAction::ExtInt { irq, can_wake } => {
+                5u8.save(w);
+                irq.save(w);
+                can_wake.save(w);
+            }
+            TimerAction::SetExtIntVec { addr } => {
+                6u8.save(w);
+                addr.save(w);
+            }
+            TimerAction::SetGlobalIntEn { en } => {
+                7u8.save(w);
+                en.save(w);
+            }
+            TimerAction::SetExtIntEn { en } => {
+                8u8.save(w);
+                en.save(w);
+            }
+            TimerAction::Halt => 9u8.save(w),
+        }
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        *self = match r.read::<u8>()? {
+            0 => TimerAction::Poll,
+            1 => TimerAction::WarmReset,
+            2 => TimerAction::UpdateReset,
+            3 => TimerAction::Nmi { mcause: r.read()? },
+            4 => TimerAction::SetNmiVec { addr: r.read()? },
+            5 => TimerAction::ExtInt {
+                irq: r.read()?,
+                can_wake: r.read()?,
+            },
+            6 => TimerAction::SetExtIntVec { addr: r.read()? },
+            7 => TimerAction::SetGlobalIntEn { en: r.read()? },
+            8 => TimerAction::SetExtIntEn { en: r.read()? },
+            9 => TimerAction::Halt,
+            _ => return Err(SnapshotError::InvalidValue),
+        };
+        Ok(())
+    }
+}
+
+impl ActionHandleImpl {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.time.save(w);
+        self.id.id.save(w);
+        self.action.save(w);
+    }
+
+    fn restore(clock: &Rc<ClockImpl>, r: &mut SnapshotReader) -> Result<Self, SnapshotError> {
+        let time = r.read()?;
+        let id = r.read()?;
+        let mut action = TimerAction::Poll;
+        action.restore(r)?;
+        Ok(Self {
+            time,
+            id: TimerActionId {
+                timer_ptr: Rc::as_ptr(clock),
+                id,
+            },
+            action,
+        })
+    }
+}
+
+/// Saves the current time and all scheduled timer actions. Restoring the clock
+/// must happen before restoring any peripheral holding an [`ActionHandle`].
+impl Snapshot for Clock {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.clock.now.get().save(w);
+        self.clock.next_action_id.get().save(w);
+        let actions = self.clock.action_handles.borrow();
+        actions.len().save(w);
+        for action in actions.iter() {
+            action.save(w);
+        }
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.clock.now.set(r.read()?);
+        self.clock.next_action_id.set(r.read()?);
+        let mut actions = self.clock.action_handles.borrow_mut();
+        actions.clear();
+        for _ in 0..r.read::<usize>()? {
+            actions.insert(ActionHandleImpl::restore(&self.clock, r)?);
+        }
+        self.clock.recompute_next_action_time(&actions);
+        r.timer = Some(self.timer());
+        Ok(())
+    }
+}
+
+impl Snapshot for Option<ActionHandle> {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.is_some().save(w);
+        if let Some(action) = self {
+            action.0.save(w);
+        }
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        *self = if r.read::<bool>()? {
+            let Some(timer) = r.timer.clone() else {
+                return Err(SnapshotError::Mismatch);
+            };
+            Some(ActionHandleImpl::restore(&timer.clock, r)?.into())
+        } else {
+            None
+        };
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use crate::testing::FakeBus;
@@ -533,4 +660,39 @@ mod tests {
 
         clock1.timer().cancel(clock0_action0);
     }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let clock = Clock::new();
+        let timer = clock.timer();
+        clock.increment(100);
+        let action0 = Some(timer.schedule_poll_in(25));
+        let _action1 = timer.schedule_action_in(40, TimerAction::Nmi { mcause: 3 });
+
+        let mut w = SnapshotWriter::new();
+        clock.save(&mut w);
+        action0.save(&mut w);
+        let bytes = w.into_bytes();
+
+        let mut restored_clock = Clock::new();
+        let restored_timer = restored_clock.timer();
+        let mut restored_action0 = None;
+        let mut r = SnapshotReader::new(&bytes);
+        restored_clock.restore(&mut r).unwrap();
+        restored_action0.restore(&mut r).unwrap();
+        r.finish().unwrap();
+
+        assert_eq!(restored_clock.now(), 100);
+        assert!(restored_clock.increment(24).is_empty());
+        assert!(!restored_timer.fired(&mut restored_action0));
+        assert_eq!(
+            restored_clock.increment(1),
+            HashSet::from([TimerAction::Poll])
+        );
+        assert!(restored_timer.fired(&mut restored_action0));
+        assert_eq!(
+            restored_clock.increment(15),
+            HashSet::from([TimerAction::Nmi { mcause: 3 }])
+        );
+    }
 }
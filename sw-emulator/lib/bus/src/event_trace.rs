@@ -0,0 +1,336 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    event_trace.rs
+
+Abstract:
+
+    File contains the event trace used to export peripheral activity to
+    timeline viewers, as Perfetto (Chrome trace) JSON or VCD.
+
+--*/
+
+use std::cell::RefCell;
+use std::collections::BTreeMap;
+use std::io::{self, Write};
+use std::rc::Rc;
+
+use caliptra_emu_types::{RvAddr, RvData, RvSize};
+
+use crate::{Clock, Timer};
+
+/// Something that happened in the emulated machine
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub enum TraceEventKind {
+    /// A peripheral register was accessed by `bus` ("UC" or "SoC")
+    PeriphAccess {
+        bus: &'static str,
+        write: bool,
+        size: RvSize,
+        addr: RvAddr,
+        val: RvData,
+    },
+
+    /// The mailbox state machine entered a new state. `state` uses the
+    /// encoding of MBOX_STATUS.mbox_fsm_ps.
+    MailboxState { state: u32, name: &'static str },
+
+    /// The CPU took external interrupt `irq`
+    ExtInterrupt { irq: u8 },
+
+    /// The CPU took a non-maskable interrupt
+    Nmi { mcause: u32 },
+
+    /// A cryptographic peripheral wrote a key to key vault slot `key_id`
+    KeyVaultWrite { key_id: u32, usage: u32 },
+}
+
+/// Event and the clock cycle it happened in
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TraceEvent {
+    pub time: u64,
+    pub kind: TraceEventKind,
+}
+
+/// Collects trace events. Clones share the same events, so every component
+/// that reports events holds its own handle.
+#[derive(Clone)]
+pub struct EventTrace {
+    timer: Timer,
+    events: Rc<RefCell<Vec<TraceEvent>>>,
+}
+
+// Perfetto track of each kind of event
+const TRACKS: [(u32, &str); 4] = [
+    (1, "peripherals"),
+    (2, "mailbox"),
+    (3, "interrupts"),
+    (4, "key vault"),
+];
+
+// VCD signals as (identifier, width, name)
+const VCD_SIGNALS: [(&str, u32, &str); 11] = [
+    ("!", 1, "periph_rd"),
+    ("\"", 1, "periph_wr"),
+    ("#", 1, "periph_soc"),
+    ("$", 32, "periph_addr"),
+    ("%", 32, "periph_data"),
+    ("&", 3, "mbox_fsm_ps"),
+    ("'", 1, "ext_int"),
+    ("(", 8, "ext_int_irq"),
+    (")", 1, "nmi"),
+    ("*", 1, "kv_wr"),
+    ("+", 5, "kv_key_id"),
+];
+
+impl EventTrace {
+    pub fn new(clock: &Clock) -> Self {
+        Self {
+            timer: clock.timer(),
+            events: Default::default(),
+        }
+    }
+
+    /// Record an event at the current clock cycle
+    pub fn record(&self, kind: TraceEventKind) {
+        self.events.borrow_mut().push(TraceEvent {
+            time: self.timer.now(),
+            kind,
+        });
+    }
+
+    /// Events recorded so far, in order
+    pub fn events(&self) -> Vec<TraceEvent> {
+        self.events.borrow().clone()
+    }
+
+    /// Write the events as Chrome trace JSON, which Perfetto and
+    /// chrome://tracing can open. Each clock cycle is shown as one
+    /// microsecond.
+    pub fn write_perfetto_json(&self, w: &mut impl Write) -> io::Result<()> {
+        let events = self.events.borrow();
+        writeln!(w, "{{\"traceEvents\":[")?;
+        for (i, (tid, name)) in TRACKS.iter().enumerate() {
+            let sep = if i == 0 { "" } else { "," };
+            writeln!(
+                w,
+                "{sep}{{\"ph\":\"M\",\"pid\":1,\"tid\":{tid},\"name\":\"thread_name\",\
+                 \"args\":{{\"name\":\"{name}\"}}}}"
+            )?;
+        }
+        let end = events.last().map_or(0, |e| e.time);
+        let mut mailbox: Option<(u64, &str)> = None;
+        for event in events.iter() {
+            let ts = event.time;
+            match &event.kind {
+                TraceEventKind::PeriphAccess {
+                    bus,
+                    write,
+                    size,
+                    addr,
+                    val,
+                } => {
+                    let op = if *write { "write" } else { "read" };
+                    writeln!(
+                        w,
+                        ",{{\"ph\":\"i\",\"s\":\"t\",\"pid\":1,\"tid\":1,\"ts\":{ts},\
+                         \"name\":\"{bus} {op} 0x{addr:08x}\",\
+                         \"args\":{{\"size\":{},\"val\":\"0x{val:08x}\"}}}}",
+                        usize::from(*size)
+                    )?;
+                }
+                TraceEventKind::MailboxState { name, .. } => {
+                    if let Some((start, prev)) = mailbox.replace((ts, *name)) {
+                        write_slice(w, 2, prev, start, ts)?;
+                    }
+                }
+                TraceEventKind::ExtInterrupt { irq } => writeln!(
+                    w,
+                    ",{{\"ph\":\"i\",\"s\":\"t\",\"pid\":1,\"tid\":3,\"ts\":{ts},\
+                     \"name\":\"irq {irq}\"}}"
+                )?,
+                TraceEventKind::Nmi { mcause } => writeln!(
+                    w,
+                    ",{{\"ph\":\"i\",\"s\":\"t\",\"pid\":1,\"tid\":3,\"ts\":{ts},\
+                     \"name\":\"nmi\",\"args\":{{\"mcause\":\"0x{mcause:08x}\"}}}}"
+                )?,
+                TraceEventKind::KeyVaultWrite { key_id, usage } => writeln!(
+                    w,
+                    ",{{\"ph\":\"i\",\"s\":\"t\",\"pid\":1,\"tid\":4,\"ts\":{ts},\
+                     \"name\":\"key {key_id}\",\"args\":{{\"usage\":\"0x{usage:x}\"}}}}"
+                )?,
+            }
+        }
+        if let Some((start, name)) = mailbox {
+            write_slice(w, 2, name, start, end)?;
+        }
+        writeln!(w, "]}}")
+    }
+
+    /// Write the events as a VCD waveform, one nanosecond per clock cycle.
+    /// Accesses, interrupts and key writes are one cycle pulses; the address,
+    /// data and id signals hold their last value.
+    pub fn write_vcd(&self, w: &mut impl Write) -> io::Result<()> {
+        writeln!(w, "$timescale 1ns $end")?;
+        writeln!(w, "$scope module caliptra_emu $end")?;
+        for (id, width, name) in VCD_SIGNALS {
+            writeln!(w, "$var wire {width} {id} {name} $end")?;
+        }
+        writeln!(w, "$upscope $end")?;
+        writeln!(w, "$enddefinitions $end")?;
+        writeln!(w, "$dumpvars")?;
+        for (id, width, _) in VCD_SIGNALS {
+            writeln!(w, "{}", vcd_value(id, width, 0))?;
+        }
+        writeln!(w, "$end")?;
+
+        let mut changes = VcdChanges::new();
+        for event in self.events.borrow().iter() {
+            let time = event.time;
+            match event.kind {
+                TraceEventKind::PeriphAccess {
+                    bus,
+                    write,
+                    addr,
+                    val,
+                    ..
+                } => {
+                    let strobe = if write { "\"" } else { "!" };
+                    let soc = u32::from(bus != "UC");
+                    pulse(
+                        &mut changes,
+                        time,
+                        strobe,
+                        &[("#", soc), ("$", addr), ("%", val)],
+                    );
+                }
+                TraceEventKind::MailboxState { state, .. } => {
+                    changes.entry(time).or_default().insert("&", state);
+                }
+                TraceEventKind::ExtInterrupt { irq } => {
+                    pulse(&mut changes, time, "'", &[("(", irq.into())]);
+                }
+                TraceEventKind::Nmi { .. } => pulse(&mut changes, time, ")", &[]),
+                TraceEventKind::KeyVaultWrite { key_id, .. } => {
+                    pulse(&mut changes, time, "*", &[("+", key_id)]);
+                }
+            }
+        }
+
+        for (time, values) in changes {
+            writeln!(w, "#{time}")?;
+            for (id, val) in values {
+                let width = VCD_SIGNALS.iter().find(|s| s.0 == id).map_or(1, |s| s.1);
+                writeln!(w, "{}", vcd_value(id, width, val))?;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Signal values by cycle. Later changes to a signal in the same cycle
+// replace earlier ones.
+type VcdChanges = BTreeMap<u64, BTreeMap<&'static str, u32>>;
+
+// Raise `id` for one cycle, setting `values` along with it
+fn pulse(changes: &mut VcdChanges, time: u64, id: &'static str, values: &[(&'static str, u32)]) {
+    changes.entry(time + 1).or_default().insert(id, 0);
+    let at = changes.entry(time).or_default();
+    at.insert(id, 1);
+    for &(id, val) in values {
+        at.insert(id, val);
+    }
+}
+
+fn write_slice(w: &mut impl Write, tid: u32, name: &str, start: u64, end: u64) -> io::Result<()> {
+    writeln!(
+        w,
+        ",{{\"ph\":\"X\",\"pid\":1,\"tid\":{tid},\"ts\":{start},\"dur\":{},\
+         \"name\":\"{name}\"}}",
+        end - start
+    )
+}
+
+fn vcd_value(id: &str, width: u32, val: u32) -> String {
+    if width == 1 {
+        format!("{val}{id}")
+    } else {
+        format!("b{val:b} {id}")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_trace() -> (Clock, EventTrace) {
+        let clock = Clock::new();
+        let trace = EventTrace::new(&clock);
+        trace.record(TraceEventKind::MailboxState {
+            state: 0,
+            name: "idle",
+        });
+        clock.increment(2);
+        trace.record(TraceEventKind::PeriphAccess {
+            bus: "SoC",
+            write: true,
+            size: RvSize::Word,
+            addr: 0x3002_0008,
+            val: 0x4657_4d42,
+        });
+        trace.record(TraceEventKind::MailboxState {
+            state: 1,
+            name: "rdy_for_cmd",
+        });
+        clock.increment(1);
+        trace.record(TraceEventKind::ExtInterrupt { irq: 20 });
+        (clock, trace)
+    }
+
+    #[test]
+    fn test_perfetto_json() {
+        let (_clock, trace) = test_trace();
+        assert_eq!(trace.events().len(), 4);
+        let mut out = vec![];
+        trace.write_perfetto_json(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        assert!(out.contains(
+            "{\"ph\":\"i\",\"s\":\"t\",\"pid\":1,\"tid\":1,\"ts\":2,\
+             \"name\":\"SoC write 0x30020008\",\"args\":{\"size\":4,\"val\":\"0x46574d42\"}}"
+        ));
+        assert!(
+            out.contains("{\"ph\":\"X\",\"pid\":1,\"tid\":2,\"ts\":0,\"dur\":2,\"name\":\"idle\"}")
+        );
+        assert!(out.contains(
+            "{\"ph\":\"X\",\"pid\":1,\"tid\":2,\"ts\":2,\"dur\":1,\"name\":\"rdy_for_cmd\"}"
+        ));
+        assert!(out.contains("\"ts\":3,\"name\":\"irq 20\"}"));
+        assert!(out.ends_with("]}\n"));
+    }
+
+    #[test]
+    fn test_vcd() {
+        let (_clock, trace) = test_trace();
+        let mut out = vec![];
+        trace.write_vcd(&mut out).unwrap();
+        let out = String::from_utf8(out).unwrap();
+        let body = out.split("$enddefinitions $end\n").nth(1).unwrap();
+        assert!(body.ends_with(
+            "#2\n\
+             1\"\n\
+             1#\n\
+             b110000000000100000000000001000 $\n\
+             b1000110010101110100110101000010 %\n\
+             b1 &\n\
+             #3\n\
+             0\"\n\
+             1'\n\
+             b10100 (\n\
+             #4\n\
+             0'\n"
+        ));
+    }
+}
@@ -13,16 +13,19 @@ Abstract:
 --*/
 mod clock;
 mod dynamic_bus;
+mod event_trace;
 mod mem;
 mod mmio;
 mod ram;
 mod register;
 mod register_array;
 mod rom;
+mod snapshot;
 pub mod testing;
 
 pub use crate::clock::{ActionHandle, Clock, Timer, TimerAction};
 pub use crate::dynamic_bus::DynamicBus;
+pub use crate::event_trace::{EventTrace, TraceEvent, TraceEventKind};
 pub use crate::mmio::BusMmio;
 pub use crate::ram::Ram;
 pub use crate::register::{
@@ -31,4 +34,5 @@ pub use crate::register::{
 };
 pub use crate::register_array::{ReadWriteRegisterArray, RegisterArray};
 pub use crate::rom::Rom;
+pub use crate::snapshot::{Snapshot, SnapshotError, SnapshotReader, SnapshotWriter};
 pub use caliptra_emu_types::bus::{Bus, BusError};
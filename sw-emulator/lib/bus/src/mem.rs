@@ -13,7 +13,7 @@ Abstract:
 
 --*/
 
-use crate::BusError;
+use crate::{BusError, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter};
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
 
 /// Memory Exception
@@ -386,6 +386,16 @@ impl Mem {
     }
 }
 
+impl Snapshot for Mem {
+    fn save(&self, w: &mut SnapshotWriter) {
+        w.write_buf(&self.data);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        r.read_buf(&mut self.data)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -12,7 +12,7 @@ Abstract:
 
 --*/
 
-use crate::{mem::Mem, Bus, BusError};
+use crate::{mem::Mem, Bus, BusError, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter};
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
 
 /// Read Only Memory Device
@@ -98,6 +98,16 @@ impl Bus for Ram {
     }
 }
 
+impl Snapshot for Ram {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.data.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.data.restore(r)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,7 +13,7 @@ Abstract:
 --*/
 
 use crate::mem::Mem;
-use crate::{Bus, BusError};
+use crate::{Bus, BusError, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter};
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
 use tock_registers::interfaces::{Readable, Writeable};
 use tock_registers::registers::InMemoryRegister;
@@ -475,6 +475,43 @@ impl<const N: usize> Default for WriteOnlyMemory<N> {
     }
 }
 
+macro_rules! impl_snapshot_for_register {
+    ($($t:ident),*) => {
+        $(
+            impl<T: UIntLike + Snapshot + Default, R: RegisterLongName> Snapshot for $t<T, R> {
+                fn save(&self, w: &mut SnapshotWriter) {
+                    self.reg.get().save(w);
+                }
+
+                fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+                    self.reg.set(r.read()?);
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_snapshot_for_register!(ReadWriteRegister, ReadOnlyRegister, WriteOnlyRegister);
+
+macro_rules! impl_snapshot_for_memory {
+    ($($t:ident),*) => {
+        $(
+            impl<const N: usize> Snapshot for $t<N> {
+                fn save(&self, w: &mut SnapshotWriter) {
+                    self.data.save(w);
+                }
+
+                fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+                    self.data.restore(r)
+                }
+            }
+        )*
+    };
+}
+
+impl_snapshot_for_memory!(ReadWriteMemory, ReadOnlyMemory, WriteOnlyMemory);
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -8,7 +8,7 @@ use std::{
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
 use tock_registers::{LocalRegisterCopy, RegisterLongName, UIntLike};
 
-use crate::{Bus, BusError, Register};
+use crate::{Bus, BusError, Register, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter};
 
 pub trait RegisterArray {
     const ITEM_SIZE: usize;
@@ -101,6 +101,21 @@ impl<T: UIntLike + Into<RvData> + TryFrom<RvData>, const SIZE: usize, R: Registe
     }
 }
 
+impl<
+        T: UIntLike + Into<RvData> + TryFrom<RvData> + Snapshot + Default,
+        const SIZE: usize,
+        R: RegisterLongName,
+    > Snapshot for ReadWriteRegisterArray<T, SIZE, R>
+{
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.regs.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.regs.restore(r)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use tock_registers::register_bitfields;
@@ -0,0 +1,324 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    snapshot.rs
+
+Abstract:
+
+    File contains the Snapshot trait and the byte stream types used to save
+    and restore emulator machine state.
+
+--*/
+
+use std::fmt;
+
+use tock_registers::{LocalRegisterCopy, RegisterLongName, UIntLike};
+
+use crate::Timer;
+
+/// Snapshot Error
+#[derive(Debug, Copy, Clone, Eq, PartialEq)]
+pub enum SnapshotError {
+    /// The snapshot ended before all state was restored
+    UnexpectedEnd,
+
+    /// The snapshot contained data after all state was restored
+    TrailingData,
+
+    /// The snapshot does not match the layout of the machine being restored
+    /// (for example a memory of a different size)
+    Mismatch,
+
+    /// The snapshot contains a value that is not valid for its type
+    InvalidValue,
+}
+
+impl fmt::Display for SnapshotError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::UnexpectedEnd => write!(f, "snapshot ended unexpectedly"),
+            Self::TrailingData => write!(f, "snapshot contains trailing data"),
+            Self::Mismatch => write!(f, "snapshot does not match the machine layout"),
+            Self::InvalidValue => write!(f, "snapshot contains an invalid value"),
+        }
+    }
+}
+
+impl std::error::Error for SnapshotError {}
+
+/// Serializes machine state into a byte stream.
+///
+/// The stream is not self-describing; it must be restored by a machine with
+/// the same configuration that produced it, visiting state in the same order.
+#[derive(Default)]
+pub struct SnapshotWriter {
+    data: Vec<u8>,
+}
+
+impl SnapshotWriter {
+    /// Create an empty snapshot writer
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Append raw bytes to the stream
+    pub fn write_bytes(&mut self, bytes: &[u8]) {
+        self.data.extend_from_slice(bytes);
+    }
+
+    /// Append a length-prefixed byte buffer to the stream
+    pub fn write_buf(&mut self, bytes: &[u8]) {
+        (bytes.len() as u64).save(self);
+        self.write_bytes(bytes);
+    }
+
+    /// Returns the serialized snapshot
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.data
+    }
+}
+
+/// Deserializes machine state from a byte stream produced by
+/// [`SnapshotWriter`].
+pub struct SnapshotReader<'a> {
+    data: &'a [u8],
+
+    /// Timer of the clock restored from this snapshot. Set when the clock is
+    /// restored, and used to rebind timer actions held by peripherals.
+    pub(crate) timer: Option<Timer>,
+}
+
+impl<'a> SnapshotReader<'a> {
+    /// Create a snapshot reader over `data`
+    pub fn new(data: &'a [u8]) -> Self {
+        Self { data, timer: None }
+    }
+
+    /// Read `len` raw bytes from the stream
+    pub fn read_bytes(&mut self, len: usize) -> Result<&'a [u8], SnapshotError> {
+        if len > self.data.len() {
+            return Err(SnapshotError::UnexpectedEnd);
+        }
+        let (result, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(result)
+    }
+
+    /// Read a length-prefixed byte buffer into `dest`, which must be exactly
+    /// the saved length.
+    pub fn read_buf(&mut self, dest: &mut [u8]) -> Result<(), SnapshotError> {
+        let len = self.read::<u64>()?;
+        if len != dest.len() as u64 {
+            return Err(SnapshotError::Mismatch);
+        }
+        dest.copy_from_slice(self.read_bytes(dest.len())?);
+        Ok(())
+    }
+
+    /// Read a value of type `T` from the stream
+    pub fn read<T: Snapshot + Default>(&mut self) -> Result<T, SnapshotError> {
+        let mut val = T::default();
+        val.restore(self)?;
+        Ok(val)
+    }
+
+    /// Returns an error if any data has not been consumed
+    pub fn finish(self) -> Result<(), SnapshotError> {
+        if self.data.is_empty() {
+            Ok(())
+        } else {
+            Err(SnapshotError::TrailingData)
+        }
+    }
+}
+
+/// State that can be saved to and restored from a snapshot.
+///
+/// Implementations must save and restore the same fields in the same order.
+/// Configuration that is fixed when the machine is constructed (ROM contents,
+/// callbacks, IRQ wiring) is not part of the snapshot; restore into a machine
+/// constructed with the same parameters as the one that was saved.
+pub trait Snapshot {
+    /// Append the state of `self` to `w`
+    fn save(&self, w: &mut SnapshotWriter);
+
+    /// Replace the state of `self` with state read from `r`
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError>;
+}
+
+macro_rules! impl_snapshot_for_int {
+    ($($t:ty),*) => {
+        $(
+            impl Snapshot for $t {
+                fn save(&self, w: &mut SnapshotWriter) {
+                    w.write_bytes(&self.to_le_bytes());
+                }
+
+                fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+                    let bytes = r.read_bytes(std::mem::size_of::<$t>())?;
+                    *self = <$t>::from_le_bytes(bytes.try_into().unwrap());
+                    Ok(())
+                }
+            }
+        )*
+    };
+}
+
+impl_snapshot_for_int!(u8, u16, u32, u64);
+
+impl Snapshot for usize {
+    fn save(&self, w: &mut SnapshotWriter) {
+        (*self as u64).save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        *self = usize::try_from(r.read::<u64>()?).map_err(|_| SnapshotError::InvalidValue)?;
+        Ok(())
+    }
+}
+
+impl Snapshot for bool {
+    fn save(&self, w: &mut SnapshotWriter) {
+        u8::from(*self).save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        *self = match r.read::<u8>()? {
+            0 => false,
+            1 => true,
+            _ => return Err(SnapshotError::InvalidValue),
+        };
+        Ok(())
+    }
+}
+
+impl<T: Snapshot, const N: usize> Snapshot for [T; N] {
+    fn save(&self, w: &mut SnapshotWriter) {
+        for item in self.iter() {
+            item.save(w);
+        }
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        for item in self.iter_mut() {
+            item.restore(r)?;
+        }
+        Ok(())
+    }
+}
+
+impl<T: Snapshot + Default> Snapshot for Vec<T> {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.len().save(w);
+        for item in self.iter() {
+            item.save(w);
+        }
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        let len = r.read::<usize>()?;
+        self.clear();
+        for _ in 0..len {
+            self.push(r.read()?);
+        }
+        Ok(())
+    }
+}
+
+impl<T: UIntLike + Snapshot + Default, R: RegisterLongName> Snapshot for LocalRegisterCopy<T, R> {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.get().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.set(r.read()?);
+        Ok(())
+    }
+}
+
+/// Implement [`Snapshot`] for a type by saving and restoring the listed
+/// fields in order.
+///
+/// # Example
+///
+/// ```
+/// use caliptra_emu_bus::{impl_snapshot, ReadWriteRegister};
+/// struct MyPeriph {
+///     control: ReadWriteRegister<u32>,
+///     data: [u32; 4],
+/// }
+/// impl_snapshot!(MyPeriph { control, data });
+/// ```
+#[macro_export]
+macro_rules! impl_snapshot {
+    ($t:ty { $($field:ident),* $(,)? }) => {
+        impl $crate::Snapshot for $t {
+            fn save(&self, w: &mut $crate::SnapshotWriter) {
+                $($crate::Snapshot::save(&self.$field, w);)*
+            }
+
+            fn restore(
+                &mut self,
+                r: &mut $crate::SnapshotReader,
+            ) -> Result<(), $crate::SnapshotError> {
+                $($crate::Snapshot::restore(&mut self.$field, r)?;)*
+                Ok(())
+            }
+        }
+    };
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[derive(Default)]
+    struct Foo {
+        a: u32,
+        b: bool,
+        c: [u16; 3],
+        d: Vec<u8>,
+    }
+    impl_snapshot!(Foo { a, b, c, d });
+
+    #[test]
+    fn test_save_restore() {
+        let foo = Foo {
+            a: 0x1234_5678,
+            b: true,
+            c: [1, 2, 3],
+            d: vec![4, 5],
+        };
+        let mut w = SnapshotWriter::new();
+        foo.save(&mut w);
+        let bytes = w.into_bytes();
+
+        let mut restored = Foo::default();
+        let mut r = SnapshotReader::new(&bytes);
+        restored.restore(&mut r).unwrap();
+        r.finish().unwrap();
+        assert_eq!(restored.a, 0x1234_5678);
+        assert!(restored.b);
+        assert_eq!(restored.c, [1, 2, 3]);
+        assert_eq!(restored.d, vec![4, 5]);
+
+        let mut r = SnapshotReader::new(&bytes[..bytes.len() - 1]);
+        assert_eq!(
+            Foo::default().restore(&mut r),
+            Err(SnapshotError::UnexpectedEnd)
+        );
+
+        let mut r = SnapshotReader::new(&bytes);
+        r.read::<u32>().unwrap();
+        assert_eq!(r.finish(), Err(SnapshotError::TrailingData));
+    }
+
+    #[test]
+    fn test_restore_invalid_bool() {
+        let mut r = SnapshotReader::new(&[2]);
+        assert_eq!(r.read::<bool>(), Err(SnapshotError::InvalidValue));
+    }
+}
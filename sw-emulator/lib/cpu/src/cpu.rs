@@ -13,11 +13,19 @@ Abstract:
 --*/
 
 use crate::csr_file::{Csr, CsrFile};
+use crate::fault::FaultInjector;
 use crate::instr::Instr;
-use crate::types::{RvInstr, RvMEIHAP, RvMStatus};
+use crate::pmp::PmpAccess;
+use crate::recorder::{CoreState, ExecRecorder, MemWrite, StepRecord};
+use crate::retired::{BusAccess, RetiredInstr};
+use crate::timing::{CycleProfile, HpmEvent, InstrClass, TimingModel};
+use crate::types::{RvInstr, RvMEIHAP, RvMStatus, RvPrivMode};
 use crate::xreg_file::{XReg, XRegFile};
 use bit_vec::BitVec;
-use caliptra_emu_bus::{Bus, BusError, Clock, TimerAction};
+use caliptra_emu_bus::{
+    Bus, BusError, Clock, EventTrace, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter,
+    TimerAction, TraceEventKind,
+};
 use caliptra_emu_types::{RvAddr, RvData, RvException, RvSize};
 
 pub type InstrTracer<'a> = dyn FnMut(u32, RvInstr) + 'a;
@@ -50,6 +58,7 @@ impl CodeRange {
 pub struct ImageInfo {
     stack_range: StackRange,
     code_range: CodeRange,
+    max_stack_usage: u32,
 }
 
 impl ImageInfo {
@@ -57,6 +66,7 @@ impl ImageInfo {
         Self {
             stack_range,
             code_range,
+            max_stack_usage: 0,
         }
     }
 
@@ -73,8 +83,13 @@ impl ImageInfo {
     /// to the overflow amount.
     ///
     /// Returns `None` if no overflow has occurred.
-    fn check_overflow(&self, sp: u32) -> Option<u32> {
-        let stack_end = self.stack_range.1;
+    fn check_overflow(&mut self, sp: u32) -> Option<u32> {
+        let StackRange(stack_start, stack_end) = self.stack_range;
+
+        // Track the high-water mark
+        if sp <= stack_start {
+            self.max_stack_usage = self.max_stack_usage.max(stack_start - sp);
+        }
 
         // Stack grows to a lower address
         if sp < stack_end {
@@ -119,6 +134,15 @@ impl StackInfo {
         }
     }
 
+    /// Fetch the stack high-water mark of each image, in bytes, in the order
+    /// the images were described.
+    pub fn max_stack_usage(&self) -> Vec<u32> {
+        self.images
+            .iter()
+            .map(|image| image.max_stack_usage)
+            .collect()
+    }
+
     /// Checks if the stack will overflow when pushed to `stack_address`.
     ///
     /// Returns `Some(u32)` if the stack will overflow and by how much, `None` if it will not overflow.
@@ -128,7 +152,7 @@ impl StackInfo {
             return None;
         }
 
-        for image in self.images.iter() {
+        for image in self.images.iter_mut() {
             if image.contains_pc(pc) {
                 if let Some(overflow_amount) = image.check_overflow(stack_address) {
                     self.max_stack_overflow = self.max_stack_overflow.max(overflow_amount);
@@ -161,6 +185,9 @@ const ROM_SIZE: usize = 48 * 1024;
 const ROM_ORG: usize = 0x00000000;
 const ROM_UPPER: usize = ROM_ORG + ROM_SIZE - 1;
 
+// Peripherals sit between the ROM and the ICCM
+const PERIPH_ADDR_RANGE: std::ops::Range<RvAddr> = 0x1000_0000..0x4000_0000;
+
 impl CodeCoverage {
     pub fn new(rom_capacity_in_bytes: usize, iccm_capacity_in_bytes: usize) -> Self {
         Self {
@@ -267,6 +294,9 @@ pub struct Cpu<TBus: Bus> {
     /// Halted state
     halted: bool,
 
+    /// Privilege mode
+    pub(crate) priv_mode: RvPrivMode,
+
     // The bus the CPU uses to talk to memory and peripherals.
     pub bus: TBus,
 
@@ -280,11 +310,44 @@ pub struct Cpu<TBus: Bus> {
 
     pub code_coverage: CodeCoverage,
     stack_info: Option<StackInfo>,
+
+    // Records executed instructions so they can be undone
+    recorder: Option<ExecRecorder>,
+
+    /// Instruction costs and memory wait states
+    pub(crate) timing: TimingModel,
+
+    /// Cycles still owed by the previous instruction; charged to the clock
+    /// at the start of the next step.
+    stall_cycles: u64,
+
+    /// Memory wait states accumulated by the instruction being executed
+    pub(crate) mem_wait_cycles: u64,
+
+    // Cycles charged to each executed instruction
+    cycle_profile: Option<CycleProfile>,
+
+    // Faults to inject into executing instructions
+    pub(crate) fault_injector: Option<FaultInjector>,
+
+    // Whether to trace the side effects of retired instructions
+    pub(crate) trace_retired: bool,
+
+    // Instruction retired by the last step, when tracing
+    pub(crate) retired: Option<RetiredInstr>,
+
+    // Peripheral accesses and interrupts are recorded here, if set
+    event_trace: Option<EventTrace>,
 }
 
 impl<TBus: Bus> Drop for Cpu<TBus> {
     fn drop(&mut self) {
         if let Some(stack_info) = &self.stack_info {
+            for (image, max_usage) in stack_info.max_stack_usage().iter().enumerate() {
+                if *max_usage != 0 {
+                    eprintln!("[EMU] Image {image} stack high-water mark: {max_usage} bytes");
+                }
+            }
             if stack_info.has_overflowed {
                 panic!(
                     "[EMU] Fatal: Caliptra's stack overflowed by {} bytes!",
@@ -328,10 +391,20 @@ impl<TBus: Bus> Cpu<TBus> {
             global_int_en: false,
             ext_int_en: false,
             halted: false,
+            priv_mode: RvPrivMode::Machine,
             // TODO: Pass in code_coverage from the outside (as caliptra-emu-cpu
             // isn't supposed to know anything about the caliptra memory map)
             code_coverage: CodeCoverage::new(ROM_SIZE, ICCM_SIZE),
             stack_info: None,
+            recorder: None,
+            timing: TimingModel::default(),
+            stall_cycles: 0,
+            mem_wait_cycles: 0,
+            cycle_profile: None,
+            fault_injector: None,
+            trace_retired: false,
+            retired: None,
+            event_trace: None,
         }
     }
 
@@ -339,6 +412,12 @@ impl<TBus: Bus> Cpu<TBus> {
         self.stack_info = Some(stack_info);
     }
 
+    /// Stack high-water mark of each image described by the `StackInfo`, in
+    /// bytes. Returns `None` if no `StackInfo` was set.
+    pub fn max_stack_usage(&self) -> Option<Vec<u32>> {
+        self.stack_info.as_ref().map(StackInfo::max_stack_usage)
+    }
+
     /// Read the RISCV CPU Program counter
     ///
     ///  # Return
@@ -359,6 +438,10 @@ impl<TBus: Bus> Cpu<TBus> {
 
     fn reset_pc(&mut self) {
         self.pc = 0;
+        self.priv_mode = RvPrivMode::Machine;
+        if let Some(profile) = &mut self.cycle_profile {
+            profile.reset_call_stack();
+        }
     }
 
     /// Returns the next program counter after the current instruction is finished executing.
@@ -406,7 +489,17 @@ impl<TBus: Bus> Cpu<TBus> {
         if reg == XReg::X2 {
             self.check_stack(val);
         }
+        let old_val = self.xregs.read(reg)?;
         self.xregs.write(reg, val)?;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.record_xreg(reg, old_val, self.xregs.read(reg)?);
+        }
+        match &mut self.retired {
+            Some(retired) if self.is_execute_instr && reg != XReg::X0 => {
+                retired.xreg_writes.push((reg.into(), val));
+            }
+            _ => {}
+        }
         Ok(())
     }
 
@@ -450,7 +543,21 @@ impl<TBus: Bus> Cpu<TBus> {
     ///
     /// * `RvException` - Exception with cause `RvExceptionCause::IllegalRegister`
     pub fn write_csr(&mut self, csr: RvAddr, val: RvData) -> Result<(), RvException> {
-        self.csrs.write(csr, val)
+        match &mut self.recorder {
+            Some(recorder) if recorder.is_active() => {
+                // Halting through MPMC can also set MSTATUS.MIE
+                let old_val = self.csrs.read(csr)?;
+                let old_mstatus = self.csrs.read(Csr::MSTATUS)?;
+                self.csrs.write(csr, val)?;
+                recorder.record_csr(csr, old_val, self.csrs.read(csr)?);
+                let new_mstatus = self.csrs.read(Csr::MSTATUS)?;
+                if csr != Csr::MSTATUS && new_mstatus != old_mstatus {
+                    recorder.record_csr(Csr::MSTATUS, old_mstatus, new_mstatus);
+                }
+                Ok(())
+            }
+            _ => self.csrs.write(csr, val),
+        }
     }
 
     /// Read from bus
@@ -473,11 +580,18 @@ impl<TBus: Bus> Cpu<TBus> {
                     kind: WatchPtrKind::Read,
                 }),
                 false => None,
+            };
+            if !self.pmp_allows(PmpAccess::Read, size, addr) {
+                return Err(RvException::load_access_fault(addr));
             }
         }
+        self.mem_wait_cycles += self.timing.read_wait_states(addr);
 
         match self.bus.read(size, addr) {
-            Ok(val) => Ok(val),
+            Ok(val) => {
+                self.trace_bus_access(false, size, addr, val);
+                Ok(val)
+            }
             Err(exception) => match exception {
                 BusError::InstrAccessFault => Err(RvException::instr_access_fault(addr)),
                 BusError::LoadAccessFault => Err(RvException::load_access_fault(addr)),
@@ -514,10 +628,32 @@ impl<TBus: Bus> Cpu<TBus> {
                     kind: WatchPtrKind::Write,
                 }),
                 false => None,
+            };
+            if !self.pmp_allows(PmpAccess::Write, size, addr) {
+                return Err(RvException::store_access_fault(addr));
             }
         }
+        self.mem_wait_cycles += self.timing.write_wait_states(addr);
+        // Only memory can be read back without side effects
+        let old_val = match &self.recorder {
+            Some(recorder) if recorder.is_active() && recorder.is_mem(size, addr) => {
+                self.bus.read(size, addr).ok()
+            }
+            _ => None,
+        };
         match self.bus.write(size, addr, val) {
-            Ok(val) => Ok(val),
+            Ok(()) => {
+                self.trace_bus_access(true, size, addr, val);
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.record_mem(MemWrite {
+                        addr,
+                        size,
+                        old_val,
+                        new_val: val,
+                    });
+                }
+                Ok(())
+            }
             Err(exception) => match exception {
                 BusError::InstrAccessFault => Err(RvException::instr_access_fault(addr)),
                 BusError::LoadAccessFault => Err(RvException::load_access_fault(addr)),
@@ -528,6 +664,37 @@ impl<TBus: Bus> Cpu<TBus> {
         }
     }
 
+    /// Add a data access made by the executing instruction to its trace
+    fn trace_bus_access(&mut self, write: bool, size: RvSize, addr: RvAddr, val: RvData) {
+        if !self.is_execute_instr {
+            return;
+        }
+        let mask = match size {
+            RvSize::Byte => 0xff,
+            RvSize::HalfWord => 0xffff,
+            _ => 0xffff_ffff,
+        };
+        if let Some(event_trace) = &self.event_trace {
+            if PERIPH_ADDR_RANGE.contains(&addr) {
+                event_trace.record(TraceEventKind::PeriphAccess {
+                    bus: "UC",
+                    write,
+                    size,
+                    addr,
+                    val: val & mask,
+                });
+            }
+        }
+        if let Some(retired) = &mut self.retired {
+            retired.bus_accesses.push(BusAccess {
+                write,
+                size,
+                addr,
+                val: val & mask,
+            });
+        }
+    }
+
     /// Read instruction
     ///
     /// # Arguments
@@ -542,6 +709,9 @@ impl<TBus: Bus> Cpu<TBus> {
     pub fn read_instr(&mut self, size: RvSize, addr: RvAddr) -> Result<RvData, RvException> {
         match size {
             RvSize::Byte => Err(RvException::instr_access_fault(addr)),
+            _ if self.is_execute_instr && !self.pmp_allows(PmpAccess::Exec, size, addr) => {
+                Err(RvException::instr_access_fault(addr))
+            }
             _ => match self.bus.read(size, addr) {
                 Ok(val) => Ok(val),
                 Err(exception) => match exception {
@@ -555,6 +725,24 @@ impl<TBus: Bus> Cpu<TBus> {
         }
     }
 
+    /// Whether PMP allows the executing instruction to access `size` bytes at
+    /// `addr`. With `mstatus.MPRV` set, loads and stores are checked in the
+    /// privilege mode held in `mstatus.MPP`.
+    fn pmp_allows(&self, access: PmpAccess, size: RvSize, addr: RvAddr) -> bool {
+        // Cannot panic; mstatus is a valid CSR
+        let status = RvMStatus(self.read_csr(Csr::MSTATUS).unwrap());
+        let mode = match access {
+            PmpAccess::Read | PmpAccess::Write if status.mprv() != 0 => status.mpp().into(),
+            _ => self.priv_mode,
+        };
+        self.csrs.pmp_allows(mode, access, addr, size)
+    }
+
+    /// Privilege mode the CPU is executing in
+    pub fn priv_mode(&self) -> RvPrivMode {
+        self.priv_mode
+    }
+
     pub fn warm_reset(&mut self) {
         self.clock
             .timer()
@@ -562,10 +750,33 @@ impl<TBus: Bus> Cpu<TBus> {
     }
 
     /// Step a single instruction
+    ///
+    /// If the CPU has been stepped backwards with [`Cpu::reverse_step`], the
+    /// next recorded step is replayed instead of executed.
     pub fn step(&mut self, instr_tracer: Option<&mut InstrTracer>) -> StepAction {
+        if let Some(record) = self.recorder.as_mut().and_then(|r| r.replay()) {
+            return self.replay_step(&record);
+        }
+
+        let before = self.core_state();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.begin_step(before);
+        }
+        let action = self.exec_step(instr_tracer);
+        let after = self.core_state();
+        if let Some(recorder) = &mut self.recorder {
+            recorder.end_step(after);
+        }
+        action
+    }
+
+    fn exec_step(&mut self, instr_tracer: Option<&mut InstrTracer>) -> StepAction {
+        self.retired = None;
+        let cycles = 1 + std::mem::take(&mut self.stall_cycles);
+        self.csrs.count_event(HpmEvent::CyclesActive, cycles);
         let fired_action_types = self
             .clock
-            .increment_and_process_timer_actions(1, &mut self.bus);
+            .increment_and_process_timer_actions(cycles, &mut self.bus);
         for action_type in fired_action_types.iter() {
             match action_type {
                 TimerAction::WarmReset => {
@@ -580,12 +791,17 @@ impl<TBus: Bus> Cpu<TBus> {
                 }
                 TimerAction::Nmi { mcause } => {
                     self.halted = false;
+                    self.charge_cycles(self.pc, self.timing.trap);
                     return self.handle_nmi(*mcause, 0);
                 }
                 TimerAction::SetNmiVec { addr } => self.nmivec = *addr,
                 TimerAction::ExtInt { irq, can_wake } => {
-                    if self.global_int_en && self.ext_int_en && (!self.halted || *can_wake) {
+                    // Machine interrupts are always enabled in user mode
+                    let int_en = self.global_int_en || self.priv_mode == RvPrivMode::User;
+                    if int_en && self.ext_int_en && (!self.halted || *can_wake) {
                         self.halted = false;
+                        self.csrs.count_event(HpmEvent::Interrupt, 1);
+                        self.charge_cycles(self.pc, self.timing.trap);
                         return self.handle_external_int(*irq);
                     }
                 }
@@ -600,12 +816,56 @@ impl<TBus: Bus> Cpu<TBus> {
         // We are in a halted state. Don't continue executing but poll the bus for interrupts
         if self.halted {
             self.set_next_pc(self.pc);
+            if let Some(profile) = &mut self.cycle_profile {
+                profile.record_idle(cycles);
+            }
             return StepAction::Continue;
         }
 
         match self.exec_instr(instr_tracer) {
             Ok(result) => result,
-            Err(exception) => self.handle_exception(exception),
+            Err(exception) => {
+                self.is_execute_instr = false;
+                self.retired = None;
+                self.csrs.count_event(HpmEvent::Exception, 1);
+                self.charge_cycles(self.pc, self.timing.trap + self.mem_wait_cycles);
+                self.handle_exception(exception)
+            }
+        }
+    }
+
+    /// Count an instruction that executed without an exception
+    pub(crate) fn retire_instr(&mut self, pc: RvAddr, instr: u32, len: u32) {
+        let class = InstrClass::decode(instr);
+        let taken = class == InstrClass::Branch && self.pc != pc.wrapping_add(len);
+        self.csrs.count_event(HpmEvent::InstrCommitted, 1);
+        self.csrs.count_event(
+            match len {
+                2 => HpmEvent::InstrCommitted16,
+                _ => HpmEvent::InstrCommitted32,
+            },
+            1,
+        );
+        if let Some(event) = HpmEvent::for_class(class) {
+            self.csrs.count_event(event, 1);
+        }
+        if taken {
+            self.csrs.count_event(HpmEvent::BranchTaken, 1);
+        }
+        let cycles = self.timing.instr_cycles(class, taken) + self.mem_wait_cycles;
+        self.charge_cycles(pc, cycles);
+        if let Some(profile) = &mut self.cycle_profile {
+            profile.record_control_flow(pc, instr, class);
+        }
+    }
+
+    /// Charge `cycles` to the instruction at `pc`. The first cycle of every
+    /// step is charged when the clock advances; the rest delay the next step.
+    fn charge_cycles(&mut self, pc: RvAddr, cycles: u64) {
+        let cycles = cycles.max(1);
+        self.stall_cycles = cycles - 1;
+        if let Some(profile) = &mut self.cycle_profile {
+            profile.record(pc, cycles);
         }
     }
 
@@ -626,6 +886,9 @@ impl<TBus: Bus> Cpu<TBus> {
 
     /// Handle non-maskable interrupt (VeeR-specific)
     fn handle_nmi(&mut self, cause: u32, info: u32) -> StepAction {
+        if let Some(event_trace) = &self.event_trace {
+            event_trace.record(TraceEventKind::Nmi { mcause: cause });
+        }
         let ret = self.handle_trap(self.read_pc(), cause, info, self.nmivec);
         match ret {
             Ok(_) => StepAction::Continue,
@@ -652,11 +915,16 @@ impl<TBus: Bus> Cpu<TBus> {
         let mut status = RvMStatus(self.read_csr(Csr::MSTATUS)?);
         status.set_mpie(status.mie());
         status.set_mie(0);
+        status.set_mpp(self.priv_mode as u32);
         self.write_csr(Csr::MSTATUS, status.0)?;
+        self.priv_mode = RvPrivMode::Machine;
         // Don't rely on write_csr to disable global interrupts as the scheduled action could be
         // after a next interrupt
         self.global_int_en = false;
 
+        if let Some(profile) = &mut self.cycle_profile {
+            profile.record_trap(pc);
+        }
         self.write_pc(next_pc);
         println!(
             "handle_trap: cause={:x}, mtval={:x}, next_pc={:x}",
@@ -672,6 +940,9 @@ impl<TBus: Bus> Cpu<TBus> {
         const DCCM_ORG: u32 = 0x5000_0000;
         const DCCM_SIZE: u32 = 128 * 1024;
 
+        if let Some(event_trace) = &self.event_trace {
+            event_trace.record(TraceEventKind::ExtInterrupt { irq });
+        }
         let vec_table = self.ext_int_vec;
         if vec_table < DCCM_ORG || vec_table + MAX_IRQ * REDIRECT_ENTRY_SIZE > DCCM_ORG + DCCM_SIZE
         {
@@ -717,12 +988,240 @@ impl<TBus: Bus> Cpu<TBus> {
     pub fn get_watchptr_hit(&self) -> Option<&WatchPtrHit> {
         self.watch_ptr_cfg.hit.as_ref()
     }
+
+    /// Set the instruction costs and memory wait states used to advance the
+    /// clock
+    pub fn set_timing_model(&mut self, timing: TimingModel) {
+        self.timing = timing;
+    }
+
+    /// Returns the current timing model
+    pub fn timing_model(&self) -> &TimingModel {
+        &self.timing
+    }
+
+    /// Start profiling the cycles spent by each instruction, discarding any
+    /// previous profile
+    pub fn start_cycle_profile(&mut self) {
+        self.cycle_profile = Some(CycleProfile::new());
+    }
+
+    /// Stop profiling cycles
+    ///
+    /// # Return
+    ///
+    /// * `Option<CycleProfile>` - The profile, if one was in progress
+    pub fn stop_cycle_profile(&mut self) -> Option<CycleProfile> {
+        self.cycle_profile.take()
+    }
+
+    /// Start profiling the cycles spent in each call stack as well as by each
+    /// instruction, discarding any previous profile
+    pub fn start_call_stack_profile(&mut self) {
+        self.cycle_profile = Some(CycleProfile::with_call_stacks());
+    }
+
+    /// Returns the current cycle profile, if any
+    pub fn cycle_profile(&self) -> Option<&CycleProfile> {
+        self.cycle_profile.as_ref()
+    }
+
+    /// Inject the faults of `injector` into instructions as they execute,
+    /// replacing any previous injector
+    pub fn install_fault_injector(&mut self, injector: FaultInjector) {
+        self.fault_injector = Some(injector);
+    }
+
+    /// Stop injecting faults
+    ///
+    /// # Return
+    ///
+    /// * `Option<FaultInjector>` - The injector, if one was installed
+    pub fn remove_fault_injector(&mut self) -> Option<FaultInjector> {
+        self.fault_injector.take()
+    }
+
+    /// Returns the installed fault injector, if any
+    pub fn fault_injector(&self) -> Option<&FaultInjector> {
+        self.fault_injector.as_ref()
+    }
+
+    /// Enable or disable tracing the side effects of each retired instruction
+    pub fn trace_retired_instrs(&mut self, enable: bool) {
+        self.trace_retired = enable;
+        self.retired = None;
+    }
+
+    /// Takes the instruction retired by the last step. Returns `None` if the
+    /// step did not retire an instruction, it was already taken, or tracing is
+    /// disabled.
+    pub fn take_retired_instr(&mut self) -> Option<RetiredInstr> {
+        self.retired.take()
+    }
+
+    /// Record the peripheral accesses made by instructions, and the
+    /// interrupts taken, to `event_trace`. `None` stops recording.
+    pub fn set_event_trace(&mut self, event_trace: Option<EventTrace>) {
+        self.event_trace = event_trace;
+    }
+
+    /// Start recording executed instructions, replacing any previous recording
+    pub fn start_recording(&mut self, recorder: ExecRecorder) {
+        self.recorder = Some(recorder);
+    }
+
+    /// Stop recording executed instructions
+    ///
+    /// # Return
+    ///
+    /// * `Option<ExecRecorder>` - The recording, if one was in progress
+    pub fn stop_recording(&mut self) -> Option<ExecRecorder> {
+        self.recorder.take()
+    }
+
+    /// Returns the current recording, if any
+    pub fn recorder(&self) -> Option<&ExecRecorder> {
+        self.recorder.as_ref()
+    }
+
+    /// Undo the most recently executed or replayed step
+    ///
+    /// Register and memory state is restored to what it was before the step.
+    /// Peripheral state and the clock are left unchanged.
+    ///
+    ///  # Return
+    ///
+    ///  * `Option<StepAction>` - `None` if there is no recorded step to undo,
+    ///                           `StepAction::Break` if the step wrote to a
+    ///                           watched address
+    pub fn reverse_step(&mut self) -> Option<StepAction> {
+        let record = self.recorder.as_mut()?.rewind()?;
+        self.watch_ptr_cfg.hit = None;
+        for write in record.mem_writes().iter().rev() {
+            if let Some(old_val) = write.old_val {
+                // Ignore failures; the write succeeded when it was recorded.
+                let _ = self.bus.write(write.size, write.addr, old_val);
+            }
+            self.check_replayed_write(write.addr);
+        }
+        for write in record.csr_writes().iter().rev() {
+            self.csrs.set_val(write.csr, write.old_val);
+        }
+        for write in record.xreg_writes().iter().rev() {
+            let _ = self.xregs.write(write.reg, write.old_val);
+        }
+        self.set_core_state(record.before);
+        Some(self.replayed_step_action())
+    }
+
+    /// Re-apply a recorded step. Writes to peripherals are not repeated, as
+    /// peripherals already hold the state after the most recent step.
+    fn replay_step(&mut self, record: &StepRecord) -> StepAction {
+        self.watch_ptr_cfg.hit = None;
+        for write in record.xreg_writes() {
+            let _ = self.xregs.write(write.reg, write.new_val);
+        }
+        for write in record.csr_writes() {
+            self.csrs.set_val(write.csr, write.new_val);
+        }
+        for write in record.mem_writes() {
+            if !write.is_mmio() {
+                let _ = self.bus.write(write.size, write.addr, write.new_val);
+            }
+            self.check_replayed_write(write.addr);
+        }
+        self.set_core_state(record.after);
+        self.replayed_step_action()
+    }
+
+    fn check_replayed_write(&mut self, addr: RvAddr) {
+        if self.watch_ptr_cfg.hit.is_none() && self.watch_ptr_cfg.write.contains(&addr) {
+            self.watch_ptr_cfg.hit = Some(WatchPtrHit {
+                addr,
+                kind: WatchPtrKind::Write,
+            });
+        }
+    }
+
+    fn replayed_step_action(&self) -> StepAction {
+        match self.get_watchptr_hit() {
+            Some(_hit) => StepAction::Break,
+            None => StepAction::Continue,
+        }
+    }
+
+    fn core_state(&self) -> CoreState {
+        CoreState {
+            pc: self.pc,
+            next_pc: self.next_pc,
+            nmivec: self.nmivec,
+            ext_int_vec: self.ext_int_vec,
+            global_int_en: self.global_int_en,
+            ext_int_en: self.ext_int_en,
+            halted: self.halted,
+            priv_mode: self.priv_mode,
+        }
+    }
+
+    fn set_core_state(&mut self, state: CoreState) {
+        self.pc = state.pc;
+        self.next_pc = state.next_pc;
+        self.nmivec = state.nmivec;
+        self.ext_int_vec = state.ext_int_vec;
+        self.global_int_en = state.global_int_en;
+        self.ext_int_en = state.ext_int_en;
+        self.halted = state.halted;
+        self.priv_mode = state.priv_mode;
+    }
+}
+
+/// Saves the clock, the architectural CPU state and the bus. Debugging state
+/// (watchpoints, code coverage, stack tracking, execution recording) is not
+/// part of the snapshot; restoring discards any recorded steps.
+impl<TBus: Bus + Snapshot> Snapshot for Cpu<TBus> {
+    fn save(&self, w: &mut SnapshotWriter) {
+        // The clock must come first so timer actions held by the bus can be
+        // rebound on restore.
+        self.clock.save(w);
+        self.xregs.save(w);
+        self.csrs.save(w);
+        self.pc.save(w);
+        self.next_pc.save(w);
+        self.nmivec.save(w);
+        self.ext_int_vec.save(w);
+        self.global_int_en.save(w);
+        self.ext_int_en.save(w);
+        self.halted.save(w);
+        (self.priv_mode as u32).save(w);
+        self.stall_cycles.save(w);
+        self.bus.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.clock.restore(r)?;
+        self.xregs.restore(r)?;
+        self.csrs.restore(r)?;
+        self.pc.restore(r)?;
+        self.next_pc.restore(r)?;
+        self.nmivec.restore(r)?;
+        self.ext_int_vec.restore(r)?;
+        self.global_int_en.restore(r)?;
+        self.ext_int_en.restore(r)?;
+        self.halted.restore(r)?;
+        self.priv_mode = r.read::<u32>()?.into();
+        self.stall_cycles.restore(r)?;
+        self.bus.restore(r)?;
+        if let Some(recorder) = &mut self.recorder {
+            recorder.clear();
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
-    use caliptra_emu_bus::{testing::FakeBus, DynamicBus, Rom, Timer};
+    use caliptra_emu_bus::{testing::FakeBus, DynamicBus, Ram, Rom, Timer};
 
     #[test]
     fn test_new() {
@@ -746,6 +1245,22 @@ mod tests {
         }
     }
 
+    #[test]
+    #[cfg(not(feature = "sw_emu_stack_check_disable"))]
+    fn test_max_stack_usage() {
+        let mut cpu = Cpu::new(DynamicBus::new(), Clock::new());
+        assert_eq!(cpu.max_stack_usage(), None);
+
+        cpu.with_stack_info(StackInfo::new(vec![ImageInfo::new(
+            StackRange::new(0x5000_1000, 0x5000_0000),
+            CodeRange::new(0, 0x100),
+        )]));
+        for sp in [0x5000_1000, 0x5000_0c00, 0x5000_0f00] {
+            cpu.write_xreg(XReg::X2, sp).unwrap();
+        }
+        assert_eq!(cpu.max_stack_usage(), Some(vec![0x400]));
+    }
+
     #[test]
     fn test_bus_poll() {
         const RV32_NO_OP: u32 = 0x00000013;
@@ -812,4 +1327,258 @@ mod tests {
         // Check for expected values
         assert_eq!(count_executed(&coverage), 8);
     }
+
+    #[test]
+    fn test_timing_model() {
+        let program: [u32; 4] = [
+            0x0000_1137, // lui x2, 0x1
+            0x0001_2083, // lw x1, 0(x2)
+            0x0210_81b3, // mul x3, x1, x1
+            0xb000_2273, // csrr x4, mcycle
+        ];
+        let mut bus = DynamicBus::new();
+        let rom = Rom::new(program.iter().copied().flat_map(u32::to_le_bytes).collect());
+        bus.attach_dev("ROM", 0..=0x3ff, Box::new(rom)).unwrap();
+        bus.attach_dev("RAM", 0x1000..=0x1fff, Box::new(Ram::new(vec![0; 0x1000])))
+            .unwrap();
+
+        let mut cpu = Cpu::new(bus, Clock::new());
+        cpu.set_timing_model(TimingModel {
+            load: 2,
+            mul: 3,
+            wait_states: vec![crate::WaitStates {
+                range: 0x1000..0x2000,
+                read: 2,
+                write: 0,
+            }],
+            ..Default::default()
+        });
+        cpu.start_cycle_profile();
+        for _ in 0..program.len() {
+            assert_eq!(cpu.step(None), StepAction::Continue);
+        }
+
+        // The load (2 + 2 wait states) and the multiply delay later steps
+        assert_eq!(cpu.clock.now(), 9);
+        assert_eq!(cpu.read_xreg(XReg::X4).unwrap(), 9);
+        assert_eq!(cpu.read_csr(Csr::MINSTRET).unwrap(), 4);
+
+        let profile = cpu.stop_cycle_profile().unwrap();
+        assert_eq!(profile.pcs()[&4].cycles, 4);
+        assert_eq!(profile.pcs()[&8].cycles, 3);
+        assert_eq!(profile.total_cycles(), 9);
+    }
+
+    #[test]
+    fn test_fault_injection() {
+        use crate::{Fault, FaultEffect, FaultTrigger};
+
+        let program: [u32; 4] = [
+            0x0050_0093, // addi x1, x0, 5
+            0x0000_0463, // beq x0, x0, 8
+            0x0010_0113, // addi x2, x0, 1
+            0x0070_0193, // addi x3, x0, 7
+        ];
+        let mut bus = DynamicBus::new();
+        let rom = Rom::new(program.iter().copied().flat_map(u32::to_le_bytes).collect());
+        bus.attach_dev("ROM", 0..=0x3ff, Box::new(rom)).unwrap();
+
+        let mut cpu = Cpu::new(bus, Clock::new());
+        cpu.install_fault_injector(FaultInjector::new(vec![
+            Fault {
+                trigger: FaultTrigger::Pc { pc: 0, hit: 0 },
+                effect: FaultEffect::SkipInstr,
+            },
+            Fault {
+                trigger: FaultTrigger::Pc { pc: 4, hit: 0 },
+                effect: FaultEffect::InvertBranch,
+            },
+            Fault {
+                trigger: FaultTrigger::InstrCount(2),
+                effect: FaultEffect::FlipRegBits {
+                    reg: XReg::X1,
+                    mask: 0x100,
+                },
+            },
+        ]));
+        for _ in 0..program.len() {
+            assert_eq!(cpu.step(None), StepAction::Continue);
+        }
+
+        // The skipped addi leaves x1 clear and the inverted branch falls
+        // through to set x2
+        assert_eq!(cpu.read_xreg(XReg::X1).unwrap(), 0x100);
+        assert_eq!(cpu.read_xreg(XReg::X2).unwrap(), 1);
+        assert_eq!(cpu.read_xreg(XReg::X3).unwrap(), 7);
+        assert_eq!(cpu.read_csr(Csr::MINSTRET).unwrap(), 4);
+
+        let injector = cpu.remove_fault_injector().unwrap();
+        assert!(injector.pending().is_empty());
+        let pcs: Vec<_> = injector.injected().iter().map(|f| f.pc).collect();
+        assert_eq!(pcs, vec![0, 4, 8]);
+    }
+
+    #[test]
+    fn test_retired_instr_trace() {
+        let program: [u32; 5] = [
+            0x0000_1137, // lui x2, 0x1
+            0x0050_0093, // addi x1, x0, 5
+            0x0011_2023, // sw x1, 0(x2)
+            0x0001_2183, // lw x3, 0(x2)
+            0x0000_0013, // nop
+        ];
+        let mut bus = DynamicBus::new();
+        let rom = Rom::new(program.iter().copied().flat_map(u32::to_le_bytes).collect());
+        bus.attach_dev("ROM", 0..=0x3ff, Box::new(rom)).unwrap();
+        bus.attach_dev("RAM", 0x1000..=0x1fff, Box::new(Ram::new(vec![0; 0x1000])))
+            .unwrap();
+
+        let mut cpu = Cpu::new(bus, Clock::new());
+        cpu.trace_retired_instrs(true);
+        let mut retired = vec![];
+        for _ in 0..program.len() {
+            assert_eq!(cpu.step(None), StepAction::Continue);
+            retired.push(cpu.take_retired_instr().unwrap());
+        }
+
+        assert_eq!(
+            retired.iter().map(|r| r.pc).collect::<Vec<_>>(),
+            vec![0, 4, 8, 12, 16]
+        );
+        assert_eq!(retired[1].instr, program[1]);
+        assert_eq!(retired[1].xreg_writes, vec![(1, 5)]);
+        assert_eq!(
+            retired[2].bus_accesses,
+            vec![BusAccess {
+                write: true,
+                size: RvSize::Word,
+                addr: 0x1000,
+                val: 5,
+            }]
+        );
+        assert!(retired[2].xreg_writes.is_empty());
+        assert_eq!(retired[3].xreg_writes, vec![(3, 5)]);
+        assert!(!retired[3].bus_accesses[0].write);
+        // Writes to x0 are discarded
+        assert_eq!(
+            retired[4],
+            RetiredInstr {
+                pc: 16,
+                instr: program[4],
+                ..Default::default()
+            }
+        );
+
+        assert!(cpu.take_retired_instr().is_none());
+        cpu.trace_retired_instrs(false);
+        cpu.step(None);
+        assert!(cpu.take_retired_instr().is_none());
+    }
+
+    #[test]
+    fn test_event_trace() {
+        let program: [u32; 4] = [
+            0x1000_0137, // lui x2, 0x10000
+            0x0050_0093, // addi x1, x0, 5
+            0x0011_2023, // sw x1, 0(x2)
+            0x0000_2183, // lw x3, 0(x0)
+        ];
+        let mut bus = DynamicBus::new();
+        let rom = Rom::new(program.iter().copied().flat_map(u32::to_le_bytes).collect());
+        bus.attach_dev("ROM", 0..=0x3ff, Box::new(rom)).unwrap();
+        let periph = Ram::new(vec![0; 0x1000]);
+        bus.attach_dev("PERIPH", 0x1000_0000..=0x1000_0fff, Box::new(periph))
+            .unwrap();
+
+        let clock = Clock::new();
+        let event_trace = EventTrace::new(&clock);
+        let mut cpu = Cpu::new(bus, clock);
+        cpu.set_event_trace(Some(event_trace.clone()));
+        for _ in 0..program.len() {
+            assert_eq!(cpu.step(None), StepAction::Continue);
+        }
+
+        // The ROM read is not a peripheral access
+        let events = event_trace.events();
+        assert_eq!(events.len(), 1);
+        assert_eq!(
+            events[0].kind,
+            TraceEventKind::PeriphAccess {
+                bus: "UC",
+                write: true,
+                size: RvSize::Word,
+                addr: 0x1000_0000,
+                val: 5,
+            }
+        );
+    }
+
+    #[test]
+    fn test_reverse_step() {
+        let program: [u32; 6] = [
+            0x0000_1137, // lui x2, 0x1
+            0x0000_21b7, // lui x3, 0x2
+            0x0050_0093, // addi x1, x0, 5
+            0x0011_2023, // sw x1, 0(x2)
+            0x0011_a023, // sw x1, 0(x3)
+            0x0070_0093, // addi x1, x0, 7
+        ];
+        let mut bus = DynamicBus::new();
+        let rom = Rom::new(program.iter().copied().flat_map(u32::to_le_bytes).collect());
+        bus.attach_dev("ROM", 0..=0x3ff, Box::new(rom)).unwrap();
+        bus.attach_dev("RAM", 0x1000..=0x1fff, Box::new(Ram::new(vec![0; 0x1000])))
+            .unwrap();
+        let fake_bus = FakeBus::new();
+        let fake_bus_log = fake_bus.log.clone();
+        bus.attach_dev("FAKE", 0x2000..=0x3000, Box::new(fake_bus))
+            .unwrap();
+
+        let mut cpu = Cpu::new(bus, Clock::new());
+        assert_eq!(cpu.reverse_step(), None);
+        cpu.start_recording(ExecRecorder::new(16, vec![0x1000..0x2000]));
+        for _ in 0..program.len() {
+            assert_eq!(cpu.step(None), StepAction::Continue);
+        }
+        assert_eq!(fake_bus_log.take(), "write(RvSize::Word, 0x0, 0x5)\n");
+
+        let steps: Vec<_> = cpu.recorder().unwrap().steps().collect();
+        assert_eq!(steps.len(), program.len());
+        assert_eq!(steps[3].pc(), 12);
+        assert_eq!(
+            steps[3].mem_writes(),
+            &[MemWrite {
+                addr: 0x1000,
+                size: RvSize::Word,
+                old_val: Some(0),
+                new_val: 5,
+            }]
+        );
+        assert!(steps[4].mem_writes()[0].is_mmio());
+
+        // Undo the register write, the MMIO write and the memory write
+        for _ in 0..3 {
+            assert_eq!(cpu.reverse_step(), Some(StepAction::Continue));
+        }
+        assert_eq!(cpu.read_pc(), 12);
+        assert_eq!(cpu.read_xreg(XReg::X1).unwrap(), 5);
+        assert_eq!(cpu.read_bus(RvSize::Word, 0x1000).unwrap(), 0);
+
+        // Stepping forward replays the log without touching peripherals
+        cpu.add_watchptr(0x1000, 4, WatchPtrKind::Write);
+        assert_eq!(cpu.step(None), StepAction::Break);
+        assert_eq!(cpu.read_bus(RvSize::Word, 0x1000).unwrap(), 5);
+        assert_eq!(cpu.step(None), StepAction::Continue);
+        assert_eq!(cpu.step(None), StepAction::Continue);
+        assert_eq!(cpu.read_pc(), 24);
+        assert_eq!(cpu.read_xreg(XReg::X1).unwrap(), 7);
+        assert!(!cpu.recorder().unwrap().is_replaying());
+        assert_eq!(fake_bus_log.take(), "");
+
+        for _ in 0..program.len() {
+            assert!(cpu.reverse_step().is_some());
+        }
+        assert_eq!(cpu.reverse_step(), None);
+        assert_eq!(cpu.read_pc(), 0);
+        assert_eq!(cpu.read_xreg(XReg::X2).unwrap(), 0);
+    }
 }
@@ -12,8 +12,11 @@ Abstract:
 
 --*/
 
-use crate::types::{RvMIE, RvMPMC, RvMStatus};
-use caliptra_emu_bus::{Clock, Timer, TimerAction};
+use crate::timing::HpmEvent;
+use crate::types::{RvMIE, RvMPMC, RvMStatus, RvPrivMode};
+use caliptra_emu_bus::{
+    Clock, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter, Timer, TimerAction,
+};
 use caliptra_emu_types::{RvAddr, RvData, RvException};
 
 /// Configuration & Status Register
@@ -66,6 +69,15 @@ impl Csr {
     /// Interrupt Pending CSR
     pub const MIP: RvAddr = 0x344;
 
+    /// First PMP Configuration CSR; pmpcfg1-3 follow
+    pub const PMPCFG0: RvAddr = 0x3A0;
+
+    /// First PMP Address CSR; pmpaddr1-15 follow
+    pub const PMPADDR0: RvAddr = 0x3B0;
+
+    /// Number of PMP entries
+    pub const PMP_COUNT: RvAddr = 16;
+
     /// Power management const CSR
     pub const MPMC: RvAddr = 0x7C6;
 
@@ -75,12 +87,24 @@ impl Csr {
     /// Instruction Retired Low Counter CSR
     pub const MINSTRET: RvAddr = 0xB02;
 
+    /// First Performance Monitoring Low Counter CSR; counters 4-6 follow
+    pub const MHPMCOUNTER3: RvAddr = 0xB03;
+
     /// Cycle High Counter CSR
     pub const MCYCLEH: RvAddr = 0xB80;
 
     /// Instruction Retired High Counter CSR
     pub const MINSTRETH: RvAddr = 0xB82;
 
+    /// First Performance Monitoring High Counter CSR; counters 4-6 follow
+    pub const MHPMCOUNTER3H: RvAddr = 0xB83;
+
+    /// First Performance Monitoring Event Selector CSR; selectors 4-6 follow
+    pub const MHPMEVENT3: RvAddr = 0x323;
+
+    /// Number of Performance Monitoring Counters
+    pub const MHPMCOUNTER_COUNT: RvAddr = 4;
+
     /// External Interrupt Vector Table CSR
     pub const MEIVT: RvAddr = 0xBC8;
 
@@ -124,12 +148,12 @@ impl CsrFile {
 
     /// Reset the CSR file
     fn reset(&mut self) {
-        self.csrs[Csr::MISA as usize] = Csr::new(0x4000_1104, 0);
+        self.csrs[Csr::MISA as usize] = Csr::new(0x4010_1104, 0);
         self.csrs[Csr::MVENDORID as usize] = Csr::new(0x0000_0045, 0);
         self.csrs[Csr::MARCHID as usize] = Csr::new(0x0000_0010, 0);
         self.csrs[Csr::MIMPIID as usize] = Csr::new(0x0000_0004, 0);
         self.csrs[Csr::MHARTID as usize] = Csr::new(0x0000_0000, 0);
-        self.csrs[Csr::MSTATUS as usize] = Csr::new(0x1800_0000, 0x0000_0088);
+        self.csrs[Csr::MSTATUS as usize] = Csr::new(0x1800_1800, 0x0002_1888);
         self.csrs[Csr::MIE as usize] = Csr::new(0x0000_0000, 0x7000_0888);
         self.csrs[Csr::MTVEC as usize] = Csr::new(0x0000_0000, 0xFFFF_FFFF);
         self.csrs[Csr::MCOUNTINHIBIT as usize] = Csr::new(0x0000_0000, 0x0000_007D);
@@ -143,6 +167,17 @@ impl CsrFile {
         self.csrs[Csr::MCYCLEH as usize] = Csr::new(0x0000_0000, 0xFFFF_FFFF);
         self.csrs[Csr::MINSTRET as usize] = Csr::new(0x0000_0000, 0xFFFF_FFFF);
         self.csrs[Csr::MINSTRETH as usize] = Csr::new(0x0000_0000, 0xFFFF_FFFF);
+        for i in 0..Csr::MHPMCOUNTER_COUNT {
+            self.csrs[(Csr::MHPMCOUNTER3 + i) as usize] = Csr::new(0x0000_0000, 0xFFFF_FFFF);
+            self.csrs[(Csr::MHPMCOUNTER3H + i) as usize] = Csr::new(0x0000_0000, 0xFFFF_FFFF);
+            self.csrs[(Csr::MHPMEVENT3 + i) as usize] = Csr::new(0x0000_0000, 0x0000_03FF);
+        }
+        for i in 0..Csr::PMP_COUNT / 4 {
+            self.csrs[(Csr::PMPCFG0 + i) as usize] = Csr::new(0x0000_0000, 0x9F9F_9F9F);
+        }
+        for i in 0..Csr::PMP_COUNT {
+            self.csrs[(Csr::PMPADDR0 + i) as usize] = Csr::new(0x0000_0000, 0xFFFF_FFFF);
+        }
         self.csrs[Csr::MEIVT as usize] = Csr::new(0x0000_0000, 0xFFFF_FC00);
         self.csrs[Csr::MEIHAP as usize] = Csr::new(0x0000_0000, 0xFFFF_FFFC);
     }
@@ -184,15 +219,20 @@ impl CsrFile {
         const CSR_MAX: usize = CsrFile::CSR_COUNT - 1;
         match addr {
             0..=CSR_MAX => {
+                let locked = self.pmp_locked_mask(addr as RvAddr);
                 let csr = &mut self.csrs[addr];
-                csr.val = (csr.val & !csr.mask) | (val & csr.mask);
+                let mask = csr.mask & !locked;
+                csr.val = (csr.val & !mask) | (val & mask);
 
                 if addr == Csr::MEIVT as usize {
                     self.timer
                         .schedule_action_in(0, TimerAction::SetExtIntVec { addr: csr.val });
                 }
                 if addr == Csr::MSTATUS as usize {
-                    let mstatus = RvMStatus(csr.val);
+                    let mut mstatus = RvMStatus(csr.val);
+                    // MPP only holds the implemented privilege modes
+                    mstatus.set_mpp(RvPrivMode::from(mstatus.mpp()) as u32);
+                    csr.val = mstatus.0;
                     self.timer.schedule_action_in(
                         0,
                         TimerAction::SetGlobalIntEn {
@@ -230,6 +270,60 @@ impl CsrFile {
             _ => Err(RvException::illegal_register()),
         }
     }
+
+    /// Set the value of a configuration status register, bypassing the write
+    /// mask and write side effects. Used to undo and replay recorded writes.
+    pub(crate) fn set_val(&mut self, addr: RvAddr, val: RvData) {
+        if let Some(csr) = self.csrs.get_mut(addr as usize) {
+            csr.val = val;
+        }
+    }
+
+    /// Count `delta` occurrences of `event` in the performance counters that
+    /// track it and are not inhibited by `mcountinhibit`
+    pub(crate) fn count_event(&mut self, event: HpmEvent, delta: u64) {
+        let inhibit = self.csrs[Csr::MCOUNTINHIBIT as usize].val;
+        match event {
+            HpmEvent::CyclesActive if inhibit & (1 << 0) == 0 => {
+                self.increment_counter(Csr::MCYCLE, Csr::MCYCLEH, delta)
+            }
+            HpmEvent::InstrCommitted if inhibit & (1 << 2) == 0 => {
+                self.increment_counter(Csr::MINSTRET, Csr::MINSTRETH, delta)
+            }
+            _ => {}
+        }
+        for i in 0..Csr::MHPMCOUNTER_COUNT {
+            let selected = self.csrs[(Csr::MHPMEVENT3 + i) as usize].val == event as u32;
+            if selected && inhibit & (1 << (i + 3)) == 0 {
+                self.increment_counter(Csr::MHPMCOUNTER3 + i, Csr::MHPMCOUNTER3H + i, delta);
+            }
+        }
+    }
+
+    /// Add `delta` to the 64-bit counter held in the `lo` and `hi` CSRs
+    fn increment_counter(&mut self, lo: RvAddr, hi: RvAddr, delta: u64) {
+        let val =
+            (u64::from(self.csrs[hi as usize].val) << 32) | u64::from(self.csrs[lo as usize].val);
+        let val = val.wrapping_add(delta);
+        self.csrs[lo as usize].val = val as RvData;
+        self.csrs[hi as usize].val = (val >> 32) as RvData;
+    }
+}
+
+/// Only the CSR values are saved; write masks are fixed at reset.
+impl Snapshot for CsrFile {
+    fn save(&self, w: &mut SnapshotWriter) {
+        for csr in self.csrs.iter() {
+            csr.val.save(w);
+        }
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        for csr in self.csrs.iter_mut() {
+            csr.val.restore(r)?;
+        }
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -242,9 +336,9 @@ mod tests {
         let clock = Clock::new();
         let mut csrs = CsrFile::new(&clock);
 
-        assert_eq!(csrs.read(Csr::MISA).ok(), Some(0x4000_1104));
+        assert_eq!(csrs.read(Csr::MISA).ok(), Some(0x4010_1104));
         assert_eq!(csrs.write(Csr::MISA, u32::MAX).ok(), Some(()));
-        assert_eq!(csrs.read(Csr::MISA).ok(), Some(0x4000_1104));
+        assert_eq!(csrs.read(Csr::MISA).ok(), Some(0x4010_1104));
     }
 
     #[test]
@@ -261,12 +355,42 @@ mod tests {
         let clock = Clock::new();
         let mut csrs = CsrFile::new(&clock);
 
-        assert_eq!(csrs.read(Csr::MSTATUS).ok(), Some(0x1800_0000));
+        assert_eq!(csrs.read(Csr::MSTATUS).ok(), Some(0x1800_1800));
         assert_eq!(csrs.write(Csr::MSTATUS, u32::MAX).ok(), Some(()));
-        assert_eq!(csrs.read(Csr::MSTATUS).ok(), Some(0x1800_0088));
+        assert_eq!(csrs.read(Csr::MSTATUS).ok(), Some(0x1802_1888));
+        // MPP only holds user or machine mode
+        assert_eq!(csrs.write(Csr::MSTATUS, 0x0000_0800).ok(), Some(()));
+        assert_eq!(csrs.read(Csr::MSTATUS).ok(), Some(0x1800_1800));
+        assert_eq!(csrs.write(Csr::MSTATUS, 0x0000_0000).ok(), Some(()));
+        assert_eq!(csrs.read(Csr::MSTATUS).ok(), Some(0x1800_0000));
 
         assert_eq!(csrs.read(Csr::MCOUNTINHIBIT).ok(), Some(0x0000_0000));
         assert_eq!(csrs.write(Csr::MCOUNTINHIBIT, u32::MAX).ok(), Some(()));
         assert_eq!(csrs.read(Csr::MCOUNTINHIBIT).ok(), Some(0x0000_007D));
     }
+
+    #[test]
+    fn test_performance_counters() {
+        let clock = Clock::new();
+        let mut csrs = CsrFile::new(&clock);
+        csrs.write(Csr::MCYCLE, 0xFFFF_FFFF).unwrap();
+        csrs.write(Csr::MHPMEVENT3 + 1, HpmEvent::LoadCommitted as u32)
+            .unwrap();
+
+        csrs.count_event(HpmEvent::CyclesActive, 2);
+        csrs.count_event(HpmEvent::InstrCommitted, 1);
+        csrs.count_event(HpmEvent::LoadCommitted, 1);
+        assert_eq!(csrs.read(Csr::MCYCLE).ok(), Some(1));
+        assert_eq!(csrs.read(Csr::MCYCLEH).ok(), Some(1));
+        assert_eq!(csrs.read(Csr::MINSTRET).ok(), Some(1));
+        assert_eq!(csrs.read(Csr::MHPMCOUNTER3).ok(), Some(0));
+        assert_eq!(csrs.read(Csr::MHPMCOUNTER3 + 1).ok(), Some(1));
+
+        // Inhibit mcycle and mhpmcounter4
+        csrs.write(Csr::MCOUNTINHIBIT, 0b1_0001).unwrap();
+        csrs.count_event(HpmEvent::CyclesActive, 2);
+        csrs.count_event(HpmEvent::LoadCommitted, 1);
+        assert_eq!(csrs.read(Csr::MCYCLE).ok(), Some(1));
+        assert_eq!(csrs.read(Csr::MHPMCOUNTER3 + 1).ok(), Some(1));
+    }
 }
@@ -0,0 +1,210 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    fault.rs
+
+Abstract:
+
+    File contains the fault injector used to simulate glitch attacks on
+    executing firmware.
+
+--*/
+
+use std::collections::HashMap;
+use std::fmt;
+
+use crate::xreg_file::XReg;
+use caliptra_emu_types::RvAddr;
+
+/// Instruction a fault is injected into
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FaultTrigger {
+    /// The `n`th instruction (counting from zero) executed after the injector
+    /// was installed
+    InstrCount(u64),
+
+    /// The `hit`th execution (counting from zero) of the instruction at `pc`
+    Pc { pc: RvAddr, hit: u32 },
+}
+
+impl FaultTrigger {
+    /// Program counter the trigger watches, if any
+    fn pc(&self) -> Option<RvAddr> {
+        match self {
+            Self::InstrCount(_) => None,
+            Self::Pc { pc, .. } => Some(*pc),
+        }
+    }
+}
+
+/// Effect of an injected fault
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub enum FaultEffect {
+    /// Skip the instruction; it retires as a `nop`
+    SkipInstr,
+
+    /// XOR register `reg` with `mask` before the instruction executes
+    FlipRegBits { reg: XReg, mask: u32 },
+
+    /// XOR the value written by a load instruction with `mask`. Has no effect
+    /// on other instructions.
+    CorruptLoad { mask: u32 },
+
+    /// Take a conditional branch that would have fallen through, or fall
+    /// through a branch that would have been taken. Has no effect on other
+    /// instructions.
+    InvertBranch,
+}
+
+/// A fault to inject
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct Fault {
+    pub trigger: FaultTrigger,
+    pub effect: FaultEffect,
+}
+
+impl fmt::Display for Fault {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.trigger {
+            FaultTrigger::InstrCount(n) => write!(f, "instr #{n}: ")?,
+            FaultTrigger::Pc { pc, hit } => write!(f, "pc 0x{pc:08x} hit #{hit}: ")?,
+        }
+        match self.effect {
+            FaultEffect::SkipInstr => write!(f, "skip"),
+            FaultEffect::FlipRegBits { reg, mask } => {
+                write!(f, "flip x{} bits 0x{mask:08x}", u32::from(reg))
+            }
+            FaultEffect::CorruptLoad { mask } => write!(f, "corrupt load 0x{mask:08x}"),
+            FaultEffect::InvertBranch => write!(f, "invert branch"),
+        }
+    }
+}
+
+/// Fault that has been injected
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct InjectedFault {
+    pub fault: Fault,
+
+    /// Program counter of the faulted instruction
+    pub pc: RvAddr,
+
+    /// Number of instructions executed after the injector was installed and
+    /// before the faulted instruction
+    pub instr_count: u64,
+}
+
+/// Injects faults into instructions as the CPU executes them
+#[derive(Clone, Default)]
+pub struct FaultInjector {
+    /// Faults that have not been injected yet
+    pending: Vec<Fault>,
+
+    /// Faults that have been injected
+    injected: Vec<InjectedFault>,
+
+    /// Instructions executed so far
+    instr_count: u64,
+
+    /// Executions of each program counter with a pending `FaultTrigger::Pc`
+    pc_hits: HashMap<RvAddr, u32>,
+}
+
+impl FaultInjector {
+    /// Create a fault injector that injects each of `faults` once
+    pub fn new(faults: Vec<Fault>) -> Self {
+        Self {
+            pending: faults,
+            ..Default::default()
+        }
+    }
+
+    /// Faults that have not been injected yet
+    pub fn pending(&self) -> &[Fault] {
+        &self.pending
+    }
+
+    /// Faults that have been injected, in order
+    pub fn injected(&self) -> &[InjectedFault] {
+        &self.injected
+    }
+
+    /// Instructions executed since the injector was installed
+    pub fn instr_count(&self) -> u64 {
+        self.instr_count
+    }
+
+    /// Returns the effects to apply to the instruction about to execute at
+    /// `pc`
+    pub(crate) fn next_instr(&mut self, pc: RvAddr) -> Vec<FaultEffect> {
+        let instr_count = self.instr_count;
+        self.instr_count += 1;
+        if self.pending.is_empty() {
+            return vec![];
+        }
+
+        let watched = self.pending.iter().any(|f| f.trigger.pc() == Some(pc));
+        let pc_hit = watched.then(|| {
+            let hits = self.pc_hits.entry(pc).or_default();
+            *hits += 1;
+            *hits - 1
+        });
+
+        let mut effects = vec![];
+        let injected = &mut self.injected;
+        self.pending.retain(|fault| {
+            let fires = match fault.trigger {
+                FaultTrigger::InstrCount(n) => n == instr_count,
+                FaultTrigger::Pc { pc: fault_pc, hit } => fault_pc == pc && pc_hit == Some(hit),
+            };
+            if fires {
+                effects.push(fault.effect);
+                injected.push(InjectedFault {
+                    fault: *fault,
+                    pc,
+                    instr_count,
+                });
+            }
+            !fires
+        });
+        effects
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_triggers() {
+        let mut injector = FaultInjector::new(vec![
+            Fault {
+                trigger: FaultTrigger::InstrCount(1),
+                effect: FaultEffect::SkipInstr,
+            },
+            Fault {
+                trigger: FaultTrigger::Pc { pc: 0x10, hit: 1 },
+                effect: FaultEffect::InvertBranch,
+            },
+        ]);
+        assert!(injector.next_instr(0x10).is_empty());
+        assert!(injector.next_instr(0x14) == vec![FaultEffect::SkipInstr]);
+        assert!(injector.next_instr(0x18).is_empty());
+        assert!(injector.next_instr(0x10) == vec![FaultEffect::InvertBranch]);
+        assert!(injector.next_instr(0x10).is_empty());
+
+        assert!(injector.pending().is_empty());
+        let injected: Vec<_> = injector
+            .injected()
+            .iter()
+            .map(|f| (f.pc, f.instr_count))
+            .collect();
+        assert_eq!(injected, vec![(0x14, 1), (0x10, 3)]);
+        assert_eq!(
+            injector.injected()[1].fault.to_string(),
+            "pc 0x00000010 hit #1: invert branch"
+        );
+    }
+}
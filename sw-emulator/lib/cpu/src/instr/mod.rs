@@ -31,10 +31,15 @@ mod test_encoder;
 mod test_macros;
 
 use crate::cpu::{Cpu, InstrTracer, StepAction};
-use crate::types::{RvInstr, RvInstr32, RvInstr32Opcode};
+use crate::fault::FaultEffect;
+use crate::retired::RetiredInstr;
+use crate::types::{RvInstr, RvInstr32, RvInstr32B, RvInstr32I, RvInstr32Opcode};
 use caliptra_emu_bus::Bus;
 use caliptra_emu_types::{RvException, RvSize};
 
+/// `addi x0, x0, 0`, retired in place of skipped instructions
+const NOP: u32 = 0x0000_0013;
+
 /// Instruction
 pub enum Instr {
     Compressed(u16),
@@ -59,21 +64,58 @@ impl<TBus: Bus> Cpu<TBus> {
         self.is_execute_instr = true;
         self.watch_ptr_cfg.hit = None;
 
+        let pc = self.read_pc();
+        self.mem_wait_cycles = self.timing.read_wait_states(pc);
         let instr = self.fetch()?;
         // Code coverage here.
-        self.code_coverage.log_execution(self.read_pc(), &instr);
+        self.code_coverage.log_execution(pc, &instr);
+
+        let faults = match &mut self.fault_injector {
+            Some(injector) => injector.next_instr(pc),
+            None => vec![],
+        };
+        for fault in faults.iter() {
+            if let FaultEffect::FlipRegBits { reg, mask } = *fault {
+                self.write_xreg(reg, self.read_xreg(reg)? ^ mask)?;
+            }
+        }
 
-        match instr {
+        if self.trace_retired {
+            let fetched = match instr {
+                Instr::Compressed(instr) => u32::from(instr),
+                Instr::General(instr) => instr,
+            };
+            self.retired = Some(RetiredInstr {
+                pc,
+                instr: fetched,
+                ..Default::default()
+            });
+        }
+
+        let (instr, len) = match instr {
+            _ if faults.contains(&FaultEffect::SkipInstr) => {
+                let len = match instr {
+                    Instr::Compressed(_) => 2,
+                    Instr::General(_) => 4,
+                };
+                self.set_next_pc(pc.wrapping_add(len));
+                (NOP, len)
+            }
             Instr::Compressed(instr) => {
-                self.set_next_pc(self.read_pc().wrapping_add(2));
-                self.exec_instr16(instr, instr_tracer)?;
+                self.set_next_pc(pc.wrapping_add(2));
+                (self.exec_instr16(instr, instr_tracer)?, 2)
             }
             Instr::General(instr) => {
-                self.set_next_pc(self.read_pc().wrapping_add(4));
+                self.set_next_pc(pc.wrapping_add(4));
                 self.exec_instr32(instr, instr_tracer)?;
+                (instr, 4)
             }
+        };
+        for fault in faults.iter() {
+            self.apply_fault_result(*fault, pc, instr, len)?;
         }
         self.write_pc(self.next_pc());
+        self.retire_instr(pc, instr, len);
 
         self.is_execute_instr = false;
 
@@ -83,6 +125,40 @@ impl<TBus: Bus> Cpu<TBus> {
         }
     }
 
+    /// Apply the part of fault `fault` that changes the result of the
+    /// instruction `instr` that just executed at `pc`
+    ///
+    /// # Error
+    ///
+    /// * `RvException` - Exception encountered writing the corrupted result
+    fn apply_fault_result(
+        &mut self,
+        fault: FaultEffect,
+        pc: u32,
+        instr: u32,
+        len: u32,
+    ) -> Result<(), RvException> {
+        match fault {
+            FaultEffect::CorruptLoad { mask }
+                if RvInstr32(instr).opcode() == RvInstr32Opcode::Load =>
+            {
+                let rd = RvInstr32I(instr).rd();
+                self.write_xreg(rd, self.read_xreg(rd)? ^ mask)?;
+            }
+            FaultEffect::InvertBranch if RvInstr32(instr).opcode() == RvInstr32Opcode::Branch => {
+                let fall_through = pc.wrapping_add(len);
+                let target = pc.wrapping_add(RvInstr32B(instr).imm());
+                if self.next_pc() == fall_through {
+                    self.set_next_pc(target);
+                } else {
+                    self.set_next_pc(fall_through);
+                }
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
     /// Fetch an instruction from current program counter
     ///
     /// # Error
@@ -102,6 +178,10 @@ impl<TBus: Bus> Cpu<TBus> {
     /// Execute a single 16-bit instruction `instr`, tracing instructions to
     /// `instr_tracer` if it exists.
     ///
+    /// # Return
+    ///
+    /// * `u32` - The equivalent 32-bit instruction
+    ///
     /// # Error
     ///
     /// * `RvException` - Exception encountered during instruction execution
@@ -109,11 +189,13 @@ impl<TBus: Bus> Cpu<TBus> {
         &mut self,
         instr: u16,
         instr_tracer: Option<&mut InstrTracer>,
-    ) -> Result<(), RvException> {
+    ) -> Result<u32, RvException> {
         if let Some(instr_tracer) = instr_tracer {
             instr_tracer(self.read_pc(), RvInstr::Instr16(instr))
         }
-        self.exec_instr32(compression::decompress_instr(instr)?, None)
+        let instr = compression::decompress_instr(instr)?;
+        self.exec_instr32(instr, None)?;
+        Ok(instr)
     }
 
     /// Execute single 32-bit instruction
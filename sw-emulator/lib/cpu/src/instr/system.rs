@@ -15,7 +15,7 @@ Abstract:
 use crate::cpu::Cpu;
 use crate::csr_file::Csr;
 use crate::types::{
-    RvInstr32I, RvInstr32Opcode, RvInstr32SystemFunct3, RvInstr32SystemImm, RvMStatus,
+    RvInstr32I, RvInstr32Opcode, RvInstr32SystemFunct3, RvInstr32SystemImm, RvMStatus, RvPrivMode,
 };
 use caliptra_emu_bus::Bus;
 use caliptra_emu_types::{RvData, RvException};
@@ -36,16 +36,32 @@ impl<TBus: Bus> Cpu<TBus> {
         assert_eq!(instr.opcode(), RvInstr32Opcode::System);
 
         let imm = instr.uimm();
+        let funct3: RvInstr32SystemFunct3 = instr.funct3().into();
 
-        match instr.funct3().into() {
+        // Bits 9:8 of a CSR address hold the lowest privilege mode that can
+        // access it
+        if funct3 != RvInstr32SystemFunct3::Priv && (imm >> 8) & 0b11 > self.priv_mode as u32 {
+            return Err(RvException::illegal_instr(instr.0));
+        }
+
+        match funct3 {
             RvInstr32SystemFunct3::Priv => match imm.into() {
-                RvInstr32SystemImm::Ecall => Err(RvException::environment_call()),
+                RvInstr32SystemImm::Ecall => match self.priv_mode {
+                    RvPrivMode::User => Err(RvException::user_environment_call()),
+                    RvPrivMode::Machine => Err(RvException::environment_call()),
+                },
                 RvInstr32SystemImm::Ebreak => Err(RvException::breakpoint(self.read_pc())),
-                RvInstr32SystemImm::Mret => {
+                RvInstr32SystemImm::Mret if self.priv_mode == RvPrivMode::Machine => {
                     let mut status = RvMStatus(self.read_csr(Csr::MSTATUS)?);
+                    let mode = RvPrivMode::from(status.mpp());
                     status.set_mie(status.mpie());
                     status.set_mpie(1);
+                    status.set_mpp(RvPrivMode::User as u32);
+                    if mode != RvPrivMode::Machine {
+                        status.set_mprv(0);
+                    }
                     self.write_csr(Csr::MSTATUS, status.0)?;
+                    self.priv_mode = mode;
                     self.set_next_pc(self.read_csr(Csr::MEPC)?);
                     Ok(())
                 }
@@ -94,14 +110,19 @@ impl<TBus: Bus> Cpu<TBus> {
 
 #[cfg(test)]
 mod tests {
+    use crate::cpu::StepAction;
     use crate::csr_file::Csr;
     use crate::instr::test_encoder::tests::{
-        csrrc, csrrci, csrrs, csrrsi, csrrw, csrrwi, ebreak, ecall,
+        csrrc, csrrci, csrrs, csrrsi, csrrw, csrrwi, ebreak, ecall, mret, sw,
     };
+    use crate::types::{RvMStatus, RvPrivMode};
     use crate::xreg_file::XReg;
     use crate::{isa_test, isa_test_cpu, text};
     use caliptra_emu_types::RvException;
 
+    // PMP configuration of a read/execute TOR entry
+    const PMP_TOR_RX: u32 = 0b0_1101;
+
     #[test]
     fn test_ecall() {
         let mut cpu = isa_test_cpu!(0x0000 => text![ecall();], 0x1000 => vec![0]);
@@ -111,6 +132,75 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_user_mode() {
+        let mut cpu = isa_test_cpu!(
+            0x0000 => text![mret(); sw(XReg::X1, 0, XReg::X2); ecall();],
+            0x1000 => vec![0; 4]
+        );
+        // User mode may only execute the text
+        cpu.write_csr(Csr::PMPADDR0, 0x1000 >> 2).unwrap();
+        cpu.write_csr(Csr::PMPCFG0, PMP_TOR_RX).unwrap();
+        cpu.write_csr(Csr::MTVEC, 0x100).unwrap();
+        cpu.write_xreg(XReg::X2, 0x1000).unwrap();
+
+        // Return to user mode
+        cpu.write_csr(Csr::MSTATUS, 0).unwrap();
+        cpu.write_csr(Csr::MEPC, 4).unwrap();
+        assert_eq!(cpu.step(None), StepAction::Continue);
+        assert_eq!(cpu.priv_mode(), RvPrivMode::User);
+        assert_eq!(cpu.read_pc(), 4);
+
+        // The store is denied and traps to machine mode
+        assert_eq!(cpu.step(None), StepAction::Continue);
+        assert_eq!(cpu.priv_mode(), RvPrivMode::Machine);
+        assert_eq!(cpu.read_pc(), 0x100);
+        assert_eq!(cpu.read_csr(Csr::MCAUSE).unwrap(), 7);
+        assert_eq!(cpu.read_csr(Csr::MTVAL).unwrap(), 0x1000);
+        assert_eq!(cpu.read_csr(Csr::MEPC).unwrap(), 4);
+        let status = RvMStatus(cpu.read_csr(Csr::MSTATUS).unwrap());
+        assert_eq!(status.mpp(), RvPrivMode::User as u32);
+
+        // mret set MPP to user mode, so it can be repeated
+        cpu.write_pc(0);
+        cpu.write_csr(Csr::MEPC, 8).unwrap();
+        assert_eq!(cpu.step(None), StepAction::Continue);
+        assert_eq!(cpu.priv_mode(), RvPrivMode::User);
+        assert_eq!(cpu.step(None), StepAction::Continue);
+        assert_eq!(cpu.priv_mode(), RvPrivMode::Machine);
+        assert_eq!(cpu.read_csr(Csr::MCAUSE).unwrap(), 8);
+        assert_eq!(cpu.read_csr(Csr::MEPC).unwrap(), 8);
+    }
+
+    #[test]
+    fn test_user_mode_privileged_instrs() {
+        let text = text![csrrs(XReg::X1, XReg::X0, Csr::MSTATUS); mret();];
+        let mut cpu = isa_test_cpu!(0x0000 => text, 0x1000 => vec![0]);
+        cpu.write_csr(Csr::PMPADDR0, 0x1000 >> 2).unwrap();
+        cpu.write_csr(Csr::PMPCFG0, PMP_TOR_RX).unwrap();
+        cpu.priv_mode = RvPrivMode::User;
+        assert_eq!(
+            cpu.exec_instr(None).err(),
+            Some(RvException::illegal_instr(csrrs(
+                XReg::X1,
+                XReg::X0,
+                Csr::MSTATUS
+            )))
+        );
+        cpu.write_pc(4);
+        assert_eq!(
+            cpu.exec_instr(None).err(),
+            Some(RvException::illegal_instr(mret()))
+        );
+
+        // Without a PMP entry user mode cannot fetch
+        cpu.write_csr(Csr::PMPCFG0, 0).unwrap();
+        assert_eq!(
+            cpu.exec_instr(None).err(),
+            Some(RvException::instr_access_fault(4))
+        );
+    }
+
     #[test]
     fn test_ebreak() {
         let mut cpu = isa_test_cpu!(0x0000 => text![ebreak();], 0x1000 => vec![0]);
@@ -133,7 +223,7 @@ mod tests {
                 XReg::X2 = u32::MAX;
             },
             {
-                XReg::X1 = 0x4000_1104;
+                XReg::X1 = 0x4010_1104;
                 XReg::X3 = 0x0000_0000;
                 XReg::X5 = u32::MAX;
             }
@@ -171,9 +261,9 @@ mod tests {
                 XReg::X2 = 0x0000_0088;
             },
             {
-                XReg::X1 = 0x1800_0000;
-                XReg::X3 = 0x1800_0088;
-                XReg::X5 = 0x1800_0088;
+                XReg::X1 = 0x1800_1800;
+                XReg::X3 = 0x1800_1888;
+                XReg::X5 = 0x1800_1888;
             }
         );
     }
@@ -192,10 +282,10 @@ mod tests {
                 XReg::X2 = 0x0000_0088;
             },
             {
-                XReg::X1 = 0x1800_0000;
-                XReg::X3 = 0x1800_0088;
-                XReg::X5 = 0x1800_0088;
-                XReg::X7 = 0x1800_0000;
+                XReg::X1 = 0x1800_1800;
+                XReg::X3 = 0x1800_1888;
+                XReg::X5 = 0x1800_1888;
+                XReg::X7 = 0x1800_1800;
             }
         );
     }
@@ -14,8 +14,13 @@ Abstract:
 
 pub mod cpu;
 mod csr_file;
+mod fault;
 mod instr;
 mod pic;
+mod pmp;
+mod recorder;
+mod retired;
+mod timing;
 mod types;
 pub mod xreg_file;
 
@@ -24,5 +29,9 @@ pub use cpu::WatchPtrHit;
 pub use cpu::WatchPtrKind;
 pub use cpu::{CodeRange, CoverageBitmaps, Cpu, ImageInfo, InstrTracer, StackInfo, StackRange};
 pub use csr_file::CsrFile;
+pub use fault::{Fault, FaultEffect, FaultInjector, FaultTrigger, InjectedFault};
 pub use pic::{IntSource, Irq, Pic, PicMmioRegisters};
-pub use types::RvInstr;
+pub use recorder::{CsrWrite, ExecRecorder, MemWrite, StepRecord, XRegWrite};
+pub use retired::{BusAccess, RetiredInstr};
+pub use timing::{CycleProfile, FunctionCycles, InstrClass, PcCycles, TimingModel, WaitStates};
+pub use types::{RvInstr, RvPrivMode};
@@ -4,7 +4,8 @@ use std::cell::{Cell, RefCell};
 use std::rc::Rc;
 
 use caliptra_emu_bus::{
-    Bus, BusError, Clock, ReadWriteRegister, ReadWriteRegisterArray, Register, Timer, TimerAction,
+    Bus, BusError, Clock, ReadWriteRegister, ReadWriteRegisterArray, Register, Snapshot,
+    SnapshotError, SnapshotReader, SnapshotWriter, Timer, TimerAction,
 };
 use caliptra_emu_derive::Bus;
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
@@ -451,6 +452,47 @@ impl PicImpl {
     }
 }
 
+impl Snapshot for Bits32 {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.bits.get().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.bits.set(r.read()?);
+        Ok(())
+    }
+}
+
+/// Saves the PIC registers and interrupt line state; the priority order and
+/// pending bits are recomputed from them on restore.
+impl Snapshot for PicMmioRegisters {
+    fn save(&self, w: &mut SnapshotWriter) {
+        let regs = self.pic.regs.borrow();
+        regs.meipl.save(w);
+        regs.meip.save(w);
+        regs.meie.save(w);
+        regs.mpiccfg.save(w);
+        regs.meigwctrl.save(w);
+        self.pic.irq_levels.save(w);
+        self.pic.gw_pending_ff.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        {
+            let mut regs = self.pic.regs.borrow_mut();
+            regs.meipl.restore(r)?;
+            regs.meip.restore(r)?;
+            regs.meie.restore(r)?;
+            regs.mpiccfg.restore(r)?;
+            regs.meigwctrl.restore(r)?;
+        }
+        self.pic.irq_levels.bits.set(r.read()?);
+        self.pic.gw_pending_ff.bits.set(r.read()?);
+        self.pic.refresh_order();
+        Ok(())
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -0,0 +1,202 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    pmp.rs
+
+Abstract:
+
+    File contains the RISC-V Physical Memory Protection checks.
+
+--*/
+
+use crate::csr_file::{Csr, CsrFile};
+use crate::types::{RvPmpCfg, RvPrivMode};
+use caliptra_emu_types::{RvAddr, RvSize};
+
+/// Kind of memory access checked against the PMP entries
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum PmpAccess {
+    Exec,
+    Read,
+    Write,
+}
+
+// PMP address matching modes
+const PMP_A_OFF: u8 = 0;
+const PMP_A_TOR: u8 = 1;
+const PMP_A_NA4: u8 = 2;
+
+impl CsrFile {
+    /// Configuration of PMP entry `index`
+    fn pmp_cfg(&self, index: RvAddr) -> RvPmpCfg {
+        // Cannot panic; the PMP CSRs are valid CSRs
+        let cfg = self.read(Csr::PMPCFG0 + index / 4).unwrap();
+        RvPmpCfg((cfg >> (8 * (index % 4))) as u8)
+    }
+
+    /// Byte address held in the pmpaddr CSR of entry `index`
+    fn pmp_addr(&self, index: RvAddr) -> u64 {
+        // Cannot panic; the PMP CSRs are valid CSRs
+        u64::from(self.read(Csr::PMPADDR0 + index).unwrap()) << 2
+    }
+
+    /// Bits of the CSR at `addr` that writes must leave unchanged because
+    /// they belong to a locked PMP entry. A locked TOR entry also locks the
+    /// address of the entry below it.
+    pub(crate) fn pmp_locked_mask(&self, addr: RvAddr) -> u32 {
+        let cfgs = Csr::PMPCFG0..Csr::PMPCFG0 + Csr::PMP_COUNT / 4;
+        let addrs = Csr::PMPADDR0..Csr::PMPADDR0 + Csr::PMP_COUNT;
+        if cfgs.contains(&addr) {
+            let first = (addr - Csr::PMPCFG0) * 4;
+            (0..4)
+                .filter(|i| self.pmp_cfg(first + i).l() != 0)
+                .fold(0, |mask, i| mask | (0xff << (8 * i)))
+        } else if addrs.contains(&addr) {
+            let index = addr - Csr::PMPADDR0;
+            let locked = self.pmp_cfg(index).l() != 0;
+            let next_locked_tor = index + 1 < Csr::PMP_COUNT && {
+                let next = self.pmp_cfg(index + 1);
+                next.l() != 0 && next.a() == PMP_A_TOR
+            };
+            if locked || next_locked_tor {
+                0xFFFF_FFFF
+            } else {
+                0
+            }
+        } else {
+            0
+        }
+    }
+
+    /// Whether the PMP entries allow an `access` of `size` bytes at `addr`
+    /// made in privilege `mode`. The lowest numbered entry matching any of
+    /// the bytes decides; an access only partly inside it fails. Machine mode
+    /// may access anything no locked entry matches; user mode only what an
+    /// entry grants.
+    pub(crate) fn pmp_allows(
+        &self,
+        mode: RvPrivMode,
+        access: PmpAccess,
+        addr: RvAddr,
+        size: RvSize,
+    ) -> bool {
+        let start = u64::from(addr);
+        let end = start + usize::from(size) as u64;
+        let mut prev_addr = 0;
+        for index in 0..Csr::PMP_COUNT {
+            let cfg = self.pmp_cfg(index);
+            let pmp_addr = self.pmp_addr(index);
+            let region = match cfg.a() {
+                PMP_A_OFF => None,
+                PMP_A_TOR => Some(prev_addr..pmp_addr),
+                PMP_A_NA4 => Some(pmp_addr..pmp_addr + 4),
+                _ => {
+                    // NAPOT: the trailing ones of pmpaddr encode the size
+                    let ones = (pmp_addr >> 2).trailing_ones();
+                    let size = 1u64 << (ones + 3);
+                    let base = pmp_addr & !(size - 1);
+                    Some(base..base + size)
+                }
+            };
+            prev_addr = pmp_addr;
+            let Some(region) = region else {
+                continue;
+            };
+            if end <= region.start || start >= region.end {
+                continue;
+            }
+            if start < region.start || end > region.end {
+                return false;
+            }
+            if mode == RvPrivMode::Machine && cfg.l() == 0 {
+                return true;
+            }
+            return match access {
+                PmpAccess::Exec => cfg.x() != 0,
+                PmpAccess::Read => cfg.r() != 0,
+                PmpAccess::Write => cfg.w() != 0,
+            };
+        }
+        mode == RvPrivMode::Machine
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use caliptra_emu_bus::Clock;
+
+    const R: u32 = 0b001;
+    const W: u32 = 0b010;
+    const X: u32 = 0b100;
+    const TOR: u32 = 1 << 3;
+    const NA4: u32 = 2 << 3;
+    const NAPOT: u32 = 3 << 3;
+    const L: u32 = 1 << 7;
+
+    #[test]
+    fn test_pmp_regions() {
+        let clock = Clock::new();
+        let mut csrs = CsrFile::new(&clock);
+        let user = RvPrivMode::User;
+        let machine = RvPrivMode::Machine;
+
+        // Without entries user mode has no access
+        assert!(!csrs.pmp_allows(user, PmpAccess::Read, 0x1000, RvSize::Word));
+        assert!(csrs.pmp_allows(machine, PmpAccess::Write, 0x1000, RvSize::Word));
+
+        // Entry 0: 0x0000..0x1000 read/execute (TOR)
+        // Entry 1: 0x5000_0000..0x5000_1000 read/write (NAPOT)
+        // Entry 2: 0x2000..0x2004 read (NA4)
+        csrs.write(Csr::PMPADDR0, 0x1000 >> 2).unwrap();
+        csrs.write(Csr::PMPADDR0 + 1, (0x5000_0000 >> 2) | 0x1ff)
+            .unwrap();
+        csrs.write(Csr::PMPADDR0 + 2, 0x2000 >> 2).unwrap();
+        csrs.write(
+            Csr::PMPCFG0,
+            (TOR | R | X) | ((NAPOT | R | W) << 8) | ((NA4 | R) << 16),
+        )
+        .unwrap();
+
+        assert!(csrs.pmp_allows(user, PmpAccess::Exec, 0xffc, RvSize::Word));
+        assert!(!csrs.pmp_allows(user, PmpAccess::Write, 0x0, RvSize::Word));
+        // Straddles the end of entry 0
+        assert!(!csrs.pmp_allows(user, PmpAccess::Read, 0xffe, RvSize::Word));
+        assert!(csrs.pmp_allows(user, PmpAccess::Write, 0x5000_0ffc, RvSize::Word));
+        assert!(!csrs.pmp_allows(user, PmpAccess::Exec, 0x5000_0000, RvSize::HalfWord));
+        assert!(!csrs.pmp_allows(user, PmpAccess::Read, 0x5000_1000, RvSize::Byte));
+        assert!(csrs.pmp_allows(user, PmpAccess::Read, 0x2003, RvSize::Byte));
+        assert!(!csrs.pmp_allows(user, PmpAccess::Read, 0x2004, RvSize::Byte));
+
+        // Unlocked entries do not apply to machine mode
+        assert!(csrs.pmp_allows(machine, PmpAccess::Write, 0x0, RvSize::Word));
+    }
+
+    #[test]
+    fn test_pmp_lock() {
+        let clock = Clock::new();
+        let mut csrs = CsrFile::new(&clock);
+        let machine = RvPrivMode::Machine;
+
+        // Entry 1 is a locked read-only TOR region
+        csrs.write(Csr::PMPADDR0, 0x1000 >> 2).unwrap();
+        csrs.write(Csr::PMPADDR0 + 1, 0x2000 >> 2).unwrap();
+        csrs.write(Csr::PMPCFG0, (TOR | R | L) << 8).unwrap();
+        assert!(csrs.pmp_allows(machine, PmpAccess::Read, 0x1000, RvSize::Word));
+        assert!(!csrs.pmp_allows(machine, PmpAccess::Write, 0x1000, RvSize::Word));
+        assert!(csrs.pmp_allows(machine, PmpAccess::Write, 0x2000, RvSize::Word));
+
+        // Neither the entry nor the address below it can be changed
+        csrs.write(Csr::PMPCFG0, 0).unwrap();
+        assert_eq!(csrs.read(Csr::PMPCFG0).unwrap(), 0x0000_8900);
+        csrs.write(Csr::PMPADDR0, 0).unwrap();
+        csrs.write(Csr::PMPADDR0 + 1, 0).unwrap();
+        assert_eq!(csrs.read(Csr::PMPADDR0).unwrap(), 0x1000 >> 2);
+        assert_eq!(csrs.read(Csr::PMPADDR0 + 1).unwrap(), 0x2000 >> 2);
+        csrs.write(Csr::PMPADDR0 + 2, 0x3000 >> 2).unwrap();
+        assert_eq!(csrs.read(Csr::PMPADDR0 + 2).unwrap(), 0x3000 >> 2);
+    }
+}
@@ -0,0 +1,256 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    recorder.rs
+
+Abstract:
+
+    File contains the instruction-level execution recorder used to step the
+    CPU backwards and replay recorded execution.
+
+--*/
+
+use std::collections::VecDeque;
+use std::ops::Range;
+
+use crate::types::RvPrivMode;
+use crate::xreg_file::XReg;
+use caliptra_emu_types::{RvAddr, RvData, RvSize};
+
+/// CPU state that is not held in a register file
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub(crate) struct CoreState {
+    pub(crate) pc: RvData,
+    pub(crate) next_pc: RvData,
+    pub(crate) nmivec: u32,
+    pub(crate) ext_int_vec: u32,
+    pub(crate) global_int_en: bool,
+    pub(crate) ext_int_en: bool,
+    pub(crate) halted: bool,
+    pub(crate) priv_mode: RvPrivMode,
+}
+
+/// General purpose register write performed by a recorded step
+#[derive(Clone, Copy, Eq, PartialEq)]
+pub struct XRegWrite {
+    pub reg: XReg,
+    pub old_val: RvData,
+    pub new_val: RvData,
+}
+
+/// Configuration status register write performed by a recorded step
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct CsrWrite {
+    pub csr: RvAddr,
+    pub old_val: RvData,
+    pub new_val: RvData,
+}
+
+/// Bus write performed by a recorded step
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct MemWrite {
+    pub addr: RvAddr,
+    pub size: RvSize,
+
+    /// Previous value at `addr`. `None` for MMIO writes, as reading a
+    /// peripheral register may have side effects.
+    pub old_val: Option<RvData>,
+
+    pub new_val: RvData,
+}
+
+impl MemWrite {
+    /// Returns true if the write targeted a peripheral rather than memory
+    pub fn is_mmio(&self) -> bool {
+        self.old_val.is_none()
+    }
+}
+
+/// Changes made by a single call to [`Cpu::step`](crate::Cpu::step)
+#[derive(Clone, Default)]
+pub struct StepRecord {
+    pub(crate) before: CoreState,
+    pub(crate) after: CoreState,
+    xreg_writes: Vec<XRegWrite>,
+    csr_writes: Vec<CsrWrite>,
+    mem_writes: Vec<MemWrite>,
+}
+
+impl StepRecord {
+    /// Program counter before the step
+    pub fn pc(&self) -> RvData {
+        self.before.pc
+    }
+
+    /// Program counter after the step
+    pub fn next_pc(&self) -> RvData {
+        self.after.pc
+    }
+
+    /// General purpose register writes, in execution order
+    pub fn xreg_writes(&self) -> &[XRegWrite] {
+        &self.xreg_writes
+    }
+
+    /// Configuration status register writes, in execution order
+    pub fn csr_writes(&self) -> &[CsrWrite] {
+        &self.csr_writes
+    }
+
+    /// Memory and MMIO writes, in execution order
+    pub fn mem_writes(&self) -> &[MemWrite] {
+        &self.mem_writes
+    }
+}
+
+/// Records the state changes made by each executed instruction so the CPU
+/// can be stepped backwards.
+///
+/// Register and memory changes are undone exactly. Writes to peripherals are
+/// logged but cannot be undone, and the clock and performance counters are
+/// never rewound, so peripherals keep the state of the most recently executed
+/// instruction.
+/// Stepping forward after stepping backwards replays the log rather than
+/// re-executing instructions until the most recent instruction is reached
+/// again, so execution stays consistent with the peripherals.
+pub struct ExecRecorder {
+    /// Maximum number of steps retained
+    capacity: usize,
+
+    /// Address ranges that can be read without side effects
+    mem_ranges: Vec<Range<RvAddr>>,
+
+    /// Recorded steps, oldest first
+    steps: VecDeque<StepRecord>,
+
+    /// Index of the next step to replay; equal to `steps.len()` when the CPU
+    /// is at the most recently executed instruction.
+    cursor: usize,
+
+    /// Step currently being executed
+    active: Option<StepRecord>,
+}
+
+impl ExecRecorder {
+    /// Create a new execution recorder
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - Maximum number of steps to retain; the oldest steps
+    ///                are discarded once it is reached
+    /// * `mem_ranges` - Address ranges backed by plain memory. Writes outside
+    ///                  these ranges are treated as MMIO.
+    pub fn new(capacity: usize, mem_ranges: Vec<Range<RvAddr>>) -> Self {
+        Self {
+            capacity,
+            mem_ranges,
+            steps: VecDeque::new(),
+            cursor: 0,
+            active: None,
+        }
+    }
+
+    /// Recorded steps, oldest first
+    pub fn steps(&self) -> impl Iterator<Item = &StepRecord> {
+        self.steps.iter()
+    }
+
+    /// Number of steps that can currently be undone
+    pub fn reversible_steps(&self) -> usize {
+        self.cursor
+    }
+
+    /// Returns true if the CPU has been stepped backwards and forward steps
+    /// are replayed from the log
+    pub fn is_replaying(&self) -> bool {
+        self.cursor < self.steps.len()
+    }
+
+    /// Discard all recorded steps
+    pub fn clear(&mut self) {
+        self.steps.clear();
+        self.cursor = 0;
+        self.active = None;
+    }
+
+    /// Returns true if `addr` is backed by plain memory
+    pub(crate) fn is_mem(&self, size: RvSize, addr: RvAddr) -> bool {
+        let Some(end) = addr.checked_add(usize::from(size) as RvAddr) else {
+            return false;
+        };
+        self.mem_ranges
+            .iter()
+            .any(|range| range.start <= addr && end <= range.end)
+    }
+
+    pub(crate) fn begin_step(&mut self, before: CoreState) {
+        self.active = Some(StepRecord {
+            before,
+            ..Default::default()
+        });
+    }
+
+    pub(crate) fn end_step(&mut self, after: CoreState) {
+        let Some(mut record) = self.active.take() else {
+            return;
+        };
+        if self.capacity == 0 {
+            return;
+        }
+        record.after = after;
+        if self.steps.len() == self.capacity {
+            self.steps.pop_front();
+        }
+        self.steps.push_back(record);
+        self.cursor = self.steps.len();
+    }
+
+    pub(crate) fn record_xreg(&mut self, reg: XReg, old_val: RvData, new_val: RvData) {
+        if let Some(record) = &mut self.active {
+            record.xreg_writes.push(XRegWrite {
+                reg,
+                old_val,
+                new_val,
+            });
+        }
+    }
+
+    pub(crate) fn record_csr(&mut self, csr: RvAddr, old_val: RvData, new_val: RvData) {
+        if let Some(record) = &mut self.active {
+            record.csr_writes.push(CsrWrite {
+                csr,
+                old_val,
+                new_val,
+            });
+        }
+    }
+
+    pub(crate) fn record_mem(&mut self, write: MemWrite) {
+        if let Some(record) = &mut self.active {
+            record.mem_writes.push(write);
+        }
+    }
+
+    pub(crate) fn is_active(&self) -> bool {
+        self.active.is_some()
+    }
+
+    /// Move the cursor back one step, returning the step to undo
+    pub(crate) fn rewind(&mut self) -> Option<StepRecord> {
+        if self.cursor == 0 {
+            return None;
+        }
+        self.cursor -= 1;
+        Some(self.steps[self.cursor].clone())
+    }
+
+    /// Move the cursor forward one step, returning the step to replay
+    pub(crate) fn replay(&mut self) -> Option<StepRecord> {
+        let record = self.steps.get(self.cursor)?.clone();
+        self.cursor += 1;
+        Some(record)
+    }
+}
@@ -0,0 +1,42 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    retired.rs
+
+Abstract:
+
+    File contains the trace of the side effects of a retired instruction,
+    used to compare execution against other models.
+
+--*/
+
+use caliptra_emu_types::{RvAddr, RvData, RvSize};
+
+/// Data access made by an instruction
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct BusAccess {
+    pub write: bool,
+    pub size: RvSize,
+    pub addr: RvAddr,
+
+    /// Value read or written, truncated to `size`
+    pub val: RvData,
+}
+
+/// Instruction retired by a step and its architectural side effects
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RetiredInstr {
+    pub pc: RvAddr,
+
+    /// Instruction as fetched; compressed instructions are in the low 16 bits
+    pub instr: u32,
+
+    /// General purpose register writes as (register number, value), in order
+    pub xreg_writes: Vec<(u32, RvData)>,
+
+    /// Data accesses, in order
+    pub bus_accesses: Vec<BusAccess>,
+}
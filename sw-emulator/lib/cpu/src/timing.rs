@@ -0,0 +1,563 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    timing.rs
+
+Abstract:
+
+    File contains the instruction timing model, the hardware performance
+    counter events and the per-instruction and call-stack cycle profiles.
+
+--*/
+
+use std::collections::HashMap;
+use std::ops::Range;
+
+use caliptra_emu_types::RvAddr;
+
+/// Instruction class used to look up instruction costs and count
+/// performance events
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum InstrClass {
+    /// Integer, bit manipulation, LUI and AUIPC instructions
+    Alu,
+
+    /// Multiply instructions
+    Mul,
+
+    /// Divide and remainder instructions
+    Div,
+
+    /// Load instructions
+    Load,
+
+    /// Store instructions
+    Store,
+
+    /// Conditional branches
+    Branch,
+
+    /// JAL and JALR
+    Jump,
+
+    /// CSR access instructions
+    Csr,
+
+    /// FENCE and FENCE.I
+    Fence,
+
+    /// MRET
+    Mret,
+
+    /// Other system instructions
+    System,
+}
+
+impl InstrClass {
+    /// Classify a 32-bit (or decompressed 16-bit) instruction
+    pub fn decode(instr: u32) -> Self {
+        let funct3 = (instr >> 12) & 0b111;
+        let funct7 = instr >> 25;
+        match instr & 0x7f {
+            0b000_0011 => Self::Load,
+            0b010_0011 => Self::Store,
+            0b110_0011 => Self::Branch,
+            0b110_1111 | 0b110_0111 => Self::Jump,
+            0b000_1111 => Self::Fence,
+            0b011_0011 if funct7 == 0b000_0001 && funct3 < 0b100 => Self::Mul,
+            0b011_0011 if funct7 == 0b000_0001 => Self::Div,
+            0b111_0011 if funct3 != 0 => Self::Csr,
+            0b111_0011 if instr >> 20 == 0x302 => Self::Mret,
+            0b111_0011 => Self::System,
+            _ => Self::Alu,
+        }
+    }
+}
+
+/// Extra cycles spent accessing an address range
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct WaitStates {
+    /// Address range the wait states apply to
+    pub range: Range<RvAddr>,
+
+    /// Extra cycles for each instruction fetch and load
+    pub read: u64,
+
+    /// Extra cycles for each store
+    pub write: u64,
+}
+
+/// Cost, in clock cycles, of each retired instruction.
+///
+/// The default model charges one cycle for every instruction and has no
+/// memory wait states, matching the emulator's historic behavior.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TimingModel {
+    pub alu: u64,
+    pub mul: u64,
+    pub div: u64,
+    pub load: u64,
+    pub store: u64,
+    pub branch: u64,
+
+    /// Extra cycles for a taken conditional branch
+    pub branch_taken: u64,
+
+    pub jump: u64,
+    pub csr: u64,
+    pub fence: u64,
+    pub mret: u64,
+    pub system: u64,
+
+    /// Cycles spent entering a trap or interrupt handler
+    pub trap: u64,
+
+    /// Memory wait states; the first matching range applies
+    pub wait_states: Vec<WaitStates>,
+}
+
+impl Default for TimingModel {
+    fn default() -> Self {
+        Self {
+            alu: 1,
+            mul: 1,
+            div: 1,
+            load: 1,
+            store: 1,
+            branch: 1,
+            branch_taken: 0,
+            jump: 1,
+            csr: 1,
+            fence: 1,
+            mret: 1,
+            system: 1,
+            trap: 1,
+            wait_states: Vec::new(),
+        }
+    }
+}
+
+impl TimingModel {
+    /// Cycles taken to retire an instruction of class `class`, excluding
+    /// memory wait states
+    pub fn instr_cycles(&self, class: InstrClass, branch_taken: bool) -> u64 {
+        match class {
+            InstrClass::Alu => self.alu,
+            InstrClass::Mul => self.mul,
+            InstrClass::Div => self.div,
+            InstrClass::Load => self.load,
+            InstrClass::Store => self.store,
+            InstrClass::Branch if branch_taken => self.branch + self.branch_taken,
+            InstrClass::Branch => self.branch,
+            InstrClass::Jump => self.jump,
+            InstrClass::Csr => self.csr,
+            InstrClass::Fence => self.fence,
+            InstrClass::Mret => self.mret,
+            InstrClass::System => self.system,
+        }
+    }
+
+    /// Extra cycles taken to read from `addr`
+    pub fn read_wait_states(&self, addr: RvAddr) -> u64 {
+        self.find_wait_states(addr).map_or(0, |w| w.read)
+    }
+
+    /// Extra cycles taken to write to `addr`
+    pub fn write_wait_states(&self, addr: RvAddr) -> u64 {
+        self.find_wait_states(addr).map_or(0, |w| w.write)
+    }
+
+    fn find_wait_states(&self, addr: RvAddr) -> Option<&WaitStates> {
+        self.wait_states.iter().find(|w| w.range.contains(&addr))
+    }
+}
+
+/// Events that can be counted by the `mhpmcounter3`-`mhpmcounter6` CSRs,
+/// numbered as in the VeeR EL2 Programmer's Reference Manual. Events not
+/// listed here are not modeled and never increment.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub(crate) enum HpmEvent {
+    CyclesActive = 1,
+    InstrCommitted = 4,
+    InstrCommitted16 = 5,
+    InstrCommitted32 = 6,
+    MulCommitted = 9,
+    DivCommitted = 10,
+    LoadCommitted = 11,
+    StoreCommitted = 12,
+    AluCommitted = 15,
+    Fence = 21,
+    Mret = 23,
+    BranchCommitted = 24,
+    BranchTaken = 26,
+    Exception = 36,
+    Interrupt = 37,
+}
+
+impl HpmEvent {
+    /// Event counted when an instruction of class `class` retires
+    pub(crate) fn for_class(class: InstrClass) -> Option<Self> {
+        match class {
+            InstrClass::Alu => Some(Self::AluCommitted),
+            InstrClass::Mul => Some(Self::MulCommitted),
+            InstrClass::Div => Some(Self::DivCommitted),
+            InstrClass::Load => Some(Self::LoadCommitted),
+            InstrClass::Store => Some(Self::StoreCommitted),
+            InstrClass::Branch => Some(Self::BranchCommitted),
+            InstrClass::Fence => Some(Self::Fence),
+            InstrClass::Mret => Some(Self::Mret),
+            InstrClass::Jump | InstrClass::Csr | InstrClass::System => None,
+        }
+    }
+}
+
+/// Cycles spent at a single program counter
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct PcCycles {
+    /// Number of times an instruction at the program counter was executed
+    pub count: u64,
+
+    /// Total cycles charged to those executions, including wait states and
+    /// trap entry
+    pub cycles: u64,
+}
+
+/// Cycles attributed to a single function
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct FunctionCycles<'a> {
+    pub name: &'a str,
+    pub count: u64,
+    pub cycles: u64,
+}
+
+/// Deepest call stack tracked; deeper calls drop the outermost frame
+const MAX_CALL_DEPTH: usize = 256;
+
+/// Call stack tracked from calls, returns and traps
+#[derive(Clone, Debug, Default)]
+struct CallStacks {
+    /// Program counter of each active call site, outermost first
+    frames: Vec<RvAddr>,
+
+    /// Cycles charged to each call stack; the last entry of each key is the
+    /// program counter the cycles were charged to
+    cycles: HashMap<Vec<RvAddr>, u64>,
+}
+
+impl CallStacks {
+    fn record(&mut self, pc: RvAddr, cycles: u64) {
+        self.frames.push(pc);
+        match self.cycles.get_mut(self.frames.as_slice()) {
+            Some(total) => *total += cycles,
+            None => {
+                self.cycles.insert(self.frames.clone(), cycles);
+            }
+        }
+        self.frames.pop();
+    }
+
+    fn push(&mut self, pc: RvAddr) {
+        if self.frames.len() == MAX_CALL_DEPTH {
+            self.frames.remove(0);
+        }
+        self.frames.push(pc);
+    }
+
+    fn pop(&mut self) {
+        self.frames.pop();
+    }
+}
+
+/// Returns true if `reg` holds a return address by the RISC-V calling
+/// convention (`ra` or the alternate link register `t0`)
+fn is_link_reg(reg: u32) -> bool {
+    reg == 1 || reg == 5
+}
+
+/// Function ranges sorted by start address
+struct FunctionLookup<'a> {
+    sorted: Vec<&'a (String, Range<RvAddr>)>,
+}
+
+impl<'a> FunctionLookup<'a> {
+    fn new(functions: &'a [(String, Range<RvAddr>)]) -> Self {
+        let mut sorted: Vec<_> = functions.iter().collect();
+        sorted.sort_by_key(|(_, range)| range.start);
+        Self { sorted }
+    }
+
+    /// Name of the function containing `pc`
+    fn find(&self, pc: RvAddr) -> Option<&'a str> {
+        // Find the last function starting at or before pc
+        let idx = self.sorted.partition_point(|(_, range)| range.start <= pc);
+        let (name, range) = idx.checked_sub(1).map(|i| self.sorted[i])?;
+        range.contains(&pc).then_some(name.as_str())
+    }
+}
+
+/// Cycles charged to each executed instruction and, optionally, to each
+/// call stack
+#[derive(Clone, Debug, Default)]
+pub struct CycleProfile {
+    pcs: HashMap<RvAddr, PcCycles>,
+
+    /// Cycles spent halted, waiting for an interrupt
+    idle_cycles: u64,
+
+    stacks: Option<CallStacks>,
+}
+
+impl CycleProfile {
+    /// Create an empty cycle profile
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Create an empty cycle profile that also tracks call stacks.
+    ///
+    /// Calls are `jal`/`jalr` instructions that write a link register and
+    /// returns are `jalr` instructions that jump through one. Traps push the
+    /// interrupted program counter and `mret` pops it. Tail calls replace the
+    /// caller's frame, as they do in the real call stack.
+    pub fn with_call_stacks() -> Self {
+        Self {
+            stacks: Some(CallStacks::default()),
+            ..Default::default()
+        }
+    }
+
+    pub(crate) fn record(&mut self, pc: RvAddr, cycles: u64) {
+        let entry = self.pcs.entry(pc).or_default();
+        entry.count += 1;
+        entry.cycles += cycles;
+        if let Some(stacks) = &mut self.stacks {
+            stacks.record(pc, cycles);
+        }
+    }
+
+    pub(crate) fn record_idle(&mut self, cycles: u64) {
+        self.idle_cycles += cycles;
+    }
+
+    /// Track calls and returns made by the instruction `instr` retired at `pc`
+    pub(crate) fn record_control_flow(&mut self, pc: RvAddr, instr: u32, class: InstrClass) {
+        let Some(stacks) = &mut self.stacks else {
+            return;
+        };
+        let rd = (instr >> 7) & 0x1f;
+        let rs1 = (instr >> 15) & 0x1f;
+        let is_jalr = instr & 0x7f == 0b110_0111;
+        match class {
+            InstrClass::Jump if is_link_reg(rd) => stacks.push(pc),
+            InstrClass::Jump if is_jalr && rd == 0 && is_link_reg(rs1) => stacks.pop(),
+            InstrClass::Mret => stacks.pop(),
+            _ => {}
+        }
+    }
+
+    /// Track entry into a trap handler that interrupted `pc`
+    pub(crate) fn record_trap(&mut self, pc: RvAddr) {
+        if let Some(stacks) = &mut self.stacks {
+            stacks.push(pc);
+        }
+    }
+
+    /// Forget the current call stack, for example on reset
+    pub(crate) fn reset_call_stack(&mut self) {
+        if let Some(stacks) = &mut self.stacks {
+            stacks.frames.clear();
+        }
+    }
+
+    /// Cycles charged to each program counter
+    pub fn pcs(&self) -> &HashMap<RvAddr, PcCycles> {
+        &self.pcs
+    }
+
+    /// Cycles spent halted, waiting for an interrupt
+    pub fn idle_cycles(&self) -> u64 {
+        self.idle_cycles
+    }
+
+    /// Total cycles, including idle cycles
+    pub fn total_cycles(&self) -> u64 {
+        self.pcs.values().map(|p| p.cycles).sum::<u64>() + self.idle_cycles
+    }
+
+    /// Cycles charged to each call stack, or `None` if the profile was not
+    /// created with [`CycleProfile::with_call_stacks`]. Each key holds the
+    /// program counters of the active call sites, outermost first, followed
+    /// by the program counter the cycles were charged to.
+    pub fn call_stacks(&self) -> Option<&HashMap<Vec<RvAddr>, u64>> {
+        self.stacks.as_ref().map(|s| &s.cycles)
+    }
+
+    /// Sum the profile by function, most expensive first
+    ///
+    /// # Arguments
+    ///
+    /// * `functions` - Name and address range of each function. Cycles at
+    ///                 program counters outside every range are not included.
+    pub fn by_function<'a>(
+        &self,
+        functions: &'a [(String, Range<RvAddr>)],
+    ) -> Vec<FunctionCycles<'a>> {
+        let lookup = FunctionLookup::new(functions);
+        let mut totals: HashMap<&'a str, FunctionCycles<'a>> = HashMap::new();
+        for (pc, pc_cycles) in self.pcs.iter() {
+            let Some(name) = lookup.find(*pc) else {
+                continue;
+            };
+            let total = totals.entry(name).or_insert(FunctionCycles {
+                name,
+                count: 0,
+                cycles: 0,
+            });
+            total.count += pc_cycles.count;
+            total.cycles += pc_cycles.cycles;
+        }
+        let mut result: Vec<_> = totals.into_values().collect();
+        result.sort_by(|a, b| b.cycles.cmp(&a.cycles).then(a.name.cmp(b.name)));
+        result
+    }
+
+    /// Sum the call stacks by function, in the folded format read by
+    /// flamegraph tools: frames are joined with `;`, outermost first. Idle
+    /// cycles are reported as the `[idle]` stack. Returns an empty list if
+    /// call stacks were not tracked.
+    ///
+    /// # Arguments
+    ///
+    /// * `functions` - Name and address range of each function. Frames
+    ///                 outside every range are shown as their address.
+    pub fn folded_stacks(&self, functions: &[(String, Range<RvAddr>)]) -> Vec<(String, u64)> {
+        let Some(stacks) = &self.stacks else {
+            return vec![];
+        };
+        let lookup = FunctionLookup::new(functions);
+        let mut totals: HashMap<String, u64> = HashMap::new();
+        for (frames, cycles) in stacks.cycles.iter() {
+            let folded = frames
+                .iter()
+                .map(|pc| match lookup.find(*pc) {
+                    Some(name) => name.to_string(),
+                    None => format!("0x{pc:08x}"),
+                })
+                .collect::<Vec<_>>()
+                .join(";");
+            *totals.entry(folded).or_default() += cycles;
+        }
+        if self.idle_cycles > 0 {
+            totals.insert("[idle]".into(), self.idle_cycles);
+        }
+        let mut result: Vec<_> = totals.into_iter().collect();
+        result.sort();
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_decode() {
+        assert_eq!(InstrClass::decode(0x0000_0013), InstrClass::Alu); // nop
+        assert_eq!(InstrClass::decode(0x0220_80b3), InstrClass::Mul); // mul x1, x1, x2
+        assert_eq!(InstrClass::decode(0x0220_c0b3), InstrClass::Div); // div x1, x1, x2
+        assert_eq!(InstrClass::decode(0x0001_2083), InstrClass::Load); // lw x1, 0(x2)
+        assert_eq!(InstrClass::decode(0x0011_2023), InstrClass::Store); // sw x1, 0(x2)
+        assert_eq!(InstrClass::decode(0x0000_0463), InstrClass::Branch); // beq x0, x0, 8
+        assert_eq!(InstrClass::decode(0x0000_006f), InstrClass::Jump); // jal x0, 0
+        assert_eq!(InstrClass::decode(0xb000_20f3), InstrClass::Csr); // csrr x1, mcycle
+        assert_eq!(InstrClass::decode(0x3020_0073), InstrClass::Mret);
+        assert_eq!(InstrClass::decode(0x0000_0073), InstrClass::System); // ecall
+    }
+
+    #[test]
+    fn test_wait_states() {
+        let model = TimingModel {
+            wait_states: vec![WaitStates {
+                range: 0x1000..0x2000,
+                read: 2,
+                write: 3,
+            }],
+            ..Default::default()
+        };
+        assert_eq!(model.read_wait_states(0x1000), 2);
+        assert_eq!(model.write_wait_states(0x1ffc), 3);
+        assert_eq!(model.read_wait_states(0x2000), 0);
+    }
+
+    #[test]
+    fn test_profile_by_function() {
+        let mut profile = CycleProfile::new();
+        profile.record(0x10, 1);
+        profile.record(0x14, 5);
+        profile.record(0x10, 1);
+        profile.record(0x24, 3);
+        profile.record(0x40, 9);
+        profile.record_idle(4);
+        assert_eq!(profile.total_cycles(), 23);
+
+        let functions = vec![
+            ("main".to_string(), 0x10..0x20),
+            ("helper".to_string(), 0x20..0x30),
+        ];
+        assert_eq!(
+            profile.by_function(&functions),
+            vec![
+                FunctionCycles {
+                    name: "main",
+                    count: 3,
+                    cycles: 7
+                },
+                FunctionCycles {
+                    name: "helper",
+                    count: 1,
+                    cycles: 3
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_profile_call_stacks() {
+        const CALL: u32 = 0x0100_00ef; // jal ra, 16
+        const RET: u32 = 0x0000_8067; // jalr x0, 0(ra)
+
+        let mut profile = CycleProfile::with_call_stacks();
+        profile.record(0x10, 1);
+        profile.record(0x14, 1);
+        profile.record_control_flow(0x14, CALL, InstrClass::Jump);
+        profile.record(0x24, 2);
+        profile.record_trap(0x28);
+        profile.record(0x80, 3);
+        profile.record(0x84, 1);
+        profile.record_control_flow(0x84, 0x3020_0073, InstrClass::Mret);
+        profile.record(0x28, 1);
+        profile.record_control_flow(0x28, RET, InstrClass::Jump);
+        profile.record(0x18, 4);
+        profile.record_idle(5);
+
+        let functions = vec![
+            ("main".to_string(), 0x10..0x20),
+            ("helper".to_string(), 0x20..0x30),
+            ("trap_handler".to_string(), 0x80..0x90),
+        ];
+        assert_eq!(
+            profile.folded_stacks(&functions),
+            vec![
+                ("[idle]".to_string(), 5),
+                ("main".to_string(), 6),
+                ("main;helper".to_string(), 3),
+                ("main;helper;trap_handler".to_string(), 4),
+            ]
+        );
+        assert_eq!(profile.call_stacks().unwrap()[&vec![0x14, 0x28, 0x80]], 3);
+        assert!(CycleProfile::new().call_stacks().is_none());
+    }
+}
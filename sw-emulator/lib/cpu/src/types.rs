@@ -628,6 +628,55 @@ bitfield! {
 
     /// Machine Mode Previous Interrupt Enable
     pub u32, mpie, set_mpie: 7, 7;
+
+    /// Machine Mode Previous Privilege Mode
+    pub u32, mpp, set_mpp: 12, 11;
+
+    /// Modify Privilege: loads and stores use the privilege mode in MPP
+    pub u32, mprv, set_mprv: 17, 17;
+}
+
+bitfield! {
+    #[derive(Debug, PartialEq, Eq, Clone, Copy)]
+    /// RISCV Physical Memory Protection entry configuration
+    pub struct RvPmpCfg(u8);
+
+    /// Read permission
+    pub u8, r, set_r: 0, 0;
+
+    /// Write permission
+    pub u8, w, set_w: 1, 1;
+
+    /// Execute permission
+    pub u8, x, set_x: 2, 2;
+
+    /// Address matching mode
+    pub u8, a, set_a: 4, 3;
+
+    /// Locked; the entry also applies to machine mode
+    pub u8, l, set_l: 7, 7;
+}
+
+/// RISCV Privilege Mode
+#[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
+pub enum RvPrivMode {
+    /// User Mode
+    User = 0b00,
+
+    /// Machine Mode
+    #[default]
+    Machine = 0b11,
+}
+
+impl From<u32> for RvPrivMode {
+    /// Decode the privilege mode held in `mstatus.MPP`. Only user and machine
+    /// modes are implemented, so any other encoding reads as machine mode.
+    fn from(val: u32) -> Self {
+        match val {
+            0b00 => RvPrivMode::User,
+            _ => RvPrivMode::Machine,
+        }
+    }
 }
 
 bitfield! {
@@ -12,6 +12,7 @@ Abstract:
 
 --*/
 
+use caliptra_emu_bus::impl_snapshot;
 use caliptra_emu_types::{emu_enum, RvAddr, RvData, RvException};
 
 emu_enum!(
@@ -140,6 +141,8 @@ impl Default for XRegFile {
     }
 }
 
+impl_snapshot!(XRegFile { reg });
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,8 +13,11 @@ Abstract:
 --*/
 
 use crate::helpers::{bytes_from_words_le, words_from_bytes_le};
+use crate::PeriphLatencies;
 use crate::{HashSha512, KeyUsage, KeyVault};
-use caliptra_emu_bus::{ActionHandle, BusError, Clock, ReadOnlyRegister, ReadWriteRegister, Timer};
+use caliptra_emu_bus::{
+    impl_snapshot, ActionHandle, BusError, Clock, ReadOnlyRegister, ReadWriteRegister, Timer,
+};
 use caliptra_emu_crypto::{Ecc384, Ecc384PubKey, Ecc384Signature};
 use caliptra_emu_derive::Bus;
 use caliptra_emu_types::{RvData, RvSize};
@@ -34,12 +37,6 @@ const ECC384_IV_SIZE: usize = 48;
 /// ECC384 Nonce size
 const ECC384_NONCE_SIZE: usize = 48;
 
-/// The number of CPU clock cycles it takes to perform ECC operation
-const ECC384_OP_TICKS: u64 = 1000;
-
-/// The number of CPU clock cycles read and write keys from key vault
-const KEY_RW_TICKS: u64 = 100;
-
 register_bitfields! [
     u32,
 
@@ -220,6 +217,9 @@ pub struct AsymEcc384 {
     /// Timer
     timer: Timer,
 
+    /// Operation latencies
+    latencies: PeriphLatencies,
+
     /// Operation complete callback
     op_complete_action: Option<ActionHandle>,
 
@@ -233,6 +233,35 @@ pub struct AsymEcc384 {
     op_key_write_complete_action: Option<ActionHandle>,
 }
 
+impl_snapshot!(AsymEcc384 {
+    control,
+    status,
+    sca_cfg,
+    seed,
+    hash,
+    priv_key_out,
+    priv_key_in,
+    pub_key_x,
+    pub_key_y,
+    sig_r,
+    sig_s,
+    verify_r,
+    iv,
+    nonce,
+    key_read_ctrl,
+    key_read_status,
+    seed_read_ctrl,
+    seed_read_status,
+    key_write_ctrl,
+    key_write_status,
+    error_global_intr,
+    error_internal_intr,
+    op_complete_action,
+    op_key_read_complete_action,
+    op_seed_read_complete_action,
+    op_key_write_complete_action,
+});
+
 impl AsymEcc384 {
     /// NAME0 Register Value
     const NAME0_VAL: RvData = 0x73656370; //0x63737065; // secp
@@ -276,6 +305,7 @@ impl AsymEcc384 {
             key_vault,
             hash_sha512,
             timer: Timer::new(clock),
+            latencies: PeriphLatencies::default(),
             op_complete_action: None,
             op_key_read_complete_action: None,
             op_seed_read_complete_action: None,
@@ -285,6 +315,11 @@ impl AsymEcc384 {
         }
     }
 
+    /// Set the time taken by each operation
+    pub fn set_latencies(&mut self, latencies: PeriphLatencies) {
+        self.latencies = latencies;
+    }
+
     /// On Write callback for `control` register
     ///
     /// # Arguments
@@ -313,7 +348,8 @@ impl AsymEcc384 {
                     .reg
                     .modify(Status::READY::CLEAR + Status::VALID::CLEAR);
 
-                self.op_complete_action = Some(self.timer.schedule_poll_in(ECC384_OP_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.ecc384_op));
             }
             _ => {}
         }
@@ -356,7 +392,8 @@ impl AsymEcc384 {
                     + KeyReadStatus::ERROR::CLEAR,
             );
 
-            self.op_key_read_complete_action = Some(self.timer.schedule_poll_in(KEY_RW_TICKS));
+            self.op_key_read_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
         }
 
         Ok(())
@@ -397,7 +434,8 @@ impl AsymEcc384 {
                     + KeyReadStatus::ERROR::CLEAR,
             );
 
-            self.op_seed_read_complete_action = Some(self.timer.schedule_poll_in(KEY_RW_TICKS));
+            self.op_seed_read_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
         }
 
         Ok(())
@@ -605,7 +643,8 @@ impl AsymEcc384 {
                     + KeyWriteStatus::ERROR::CLEAR,
             );
 
-            self.op_key_write_complete_action = Some(self.timer.schedule_poll_in(KEY_RW_TICKS));
+            self.op_key_write_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
         } else {
             // Make the private key available to the uC
             self.priv_key_out = self.priv_key_in;
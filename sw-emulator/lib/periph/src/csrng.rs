@@ -1,6 +1,9 @@
 // Licensed under the Apache-2.0 license
 
-use caliptra_emu_bus::{BusError, ReadOnlyRegister, WriteOnlyRegister};
+use caliptra_emu_bus::{
+    impl_snapshot, BusError, ReadOnlyRegister, Snapshot, SnapshotError, SnapshotReader,
+    SnapshotWriter, WriteOnlyRegister,
+};
 use caliptra_emu_derive::Bus;
 use caliptra_emu_types::{RvData, RvSize};
 use caliptra_registers::entropy_src::regs::{
@@ -303,6 +306,29 @@ impl Csrng {
     }
 }
 
+// The health tester only runs on the boot-time entropy and is not saved.
+impl_snapshot!(Csrng {
+    ctrl,
+    cmd_req,
+    sw_cmd_sts,
+    genbits_vld,
+    genbits,
+    err_code,
+    module_enable,
+    conf,
+    health_test_windows,
+    repcnt_thresholds,
+    adaptp_hi_thresholds,
+    adaptp_lo_thresholds,
+    alert_summary_fail_counts,
+    alert_fail_counts,
+    main_sm_state,
+    cmd_req_state,
+    seed,
+    ctr_drbg,
+    words,
+});
+
 #[derive(Default)]
 struct Words {
     block: Block,
@@ -355,6 +381,31 @@ enum CmdReqState {
     ExpectSeedWords { num_words: usize },
 }
 
+impl_snapshot!(Words { block, cursor });
+
+impl Snapshot for CmdReqState {
+    fn save(&self, w: &mut SnapshotWriter) {
+        match *self {
+            CmdReqState::ExpectNewCommand => false.save(w),
+            CmdReqState::ExpectSeedWords { num_words } => {
+                true.save(w);
+                num_words.save(w);
+            }
+        }
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        *self = if r.read::<bool>()? {
+            CmdReqState::ExpectSeedWords {
+                num_words: r.read()?,
+            }
+        } else {
+            CmdReqState::ExpectNewCommand
+        };
+        Ok(())
+    }
+}
+
 #[repr(u32)]
 enum MultiBitBool {
     False = 9,
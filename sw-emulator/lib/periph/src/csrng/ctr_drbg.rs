@@ -5,6 +5,8 @@
 
 use std::iter;
 
+use caliptra_emu_bus::impl_snapshot;
+
 use super::WORD_SIZE_BYTES;
 
 // Table 3 of Section 10.2.1 (page 49).
@@ -34,6 +36,12 @@ pub struct CtrDrbg {
     generated_bytes: Vec<Block>,
 }
 
+impl_snapshot!(CtrDrbg {
+    v,
+    key,
+    generated_bytes,
+});
+
 impl CtrDrbg {
     pub fn new() -> Self {
         Self {
@@ -13,9 +13,11 @@ Abstract:
 --*/
 
 use crate::helpers::bytes_swap_word_endian;
+use crate::PeriphLatencies;
 use crate::{KeyVault, SocRegistersInternal};
 use caliptra_emu_bus::{
-    ActionHandle, BusError, Clock, ReadOnlyRegister, ReadWriteMemory, ReadWriteRegister, Timer,
+    impl_snapshot, ActionHandle, BusError, Clock, ReadOnlyRegister, ReadWriteMemory,
+    ReadWriteRegister, Timer,
 };
 use caliptra_emu_crypto::Aes256Cbc;
 use caliptra_emu_derive::Bus;
@@ -26,9 +28,6 @@ use tock_registers::register_bitfields;
 /// Initialization vector size
 const DOE_IV_SIZE: usize = 16;
 
-/// The number of CPU clock cycles it takes to perform the hash update action.
-const DOE_OP_TICKS: u64 = 1000;
-
 // hmac_key_dest_valid | hmac_block_dest_valid
 const DOE_KEY_USAGE: u32 = 0x3;
 
@@ -76,6 +75,9 @@ pub struct Doe {
     /// Timer
     timer: Timer,
 
+    /// Operation latencies
+    latencies: PeriphLatencies,
+
     /// Key Vault
     key_vault: KeyVault,
 
@@ -86,6 +88,13 @@ pub struct Doe {
     op_complete_action: Option<ActionHandle>,
 }
 
+impl_snapshot!(Doe {
+    iv,
+    control,
+    status,
+    op_complete_action,
+});
+
 impl Doe {
     /// Create new instance of deobfuscation engine
     ///
@@ -104,12 +113,18 @@ impl Doe {
             control: ReadWriteRegister::new(0),
             status: ReadOnlyRegister::new(Status::READY::SET.value),
             timer: Timer::new(clock),
+            latencies: PeriphLatencies::default(),
             key_vault,
             soc_reg,
             op_complete_action: None,
         }
     }
 
+    /// Set the time taken by each operation
+    pub fn set_latencies(&mut self, latencies: PeriphLatencies) {
+        self.latencies = latencies;
+    }
+
     /// On Write callback for `control` register
     ///
     /// # Arguments
@@ -133,7 +148,7 @@ impl Doe {
             self.status
                 .reg
                 .modify(Status::READY::CLEAR + Status::VALID::CLEAR);
-            self.op_complete_action = Some(self.timer.schedule_poll_in(DOE_OP_TICKS));
+            self.op_complete_action = Some(self.timer.schedule_poll_in(self.latencies.doe_op));
         }
 
         Ok(())
@@ -12,9 +12,10 @@ Abstract:
 
 --*/
 
+use crate::PeriphLatencies;
 use caliptra_emu_bus::{
-    ActionHandle, BusError, Clock, ReadOnlyMemory, ReadOnlyRegister, ReadWriteMemory,
-    ReadWriteRegister, Timer,
+    impl_snapshot, ActionHandle, BusError, Clock, ReadOnlyMemory, ReadOnlyRegister,
+    ReadWriteMemory, ReadWriteRegister, Timer,
 };
 use caliptra_emu_crypto::{EndianessTransform, Sha256, Sha256Mode};
 use caliptra_emu_derive::Bus;
@@ -53,12 +54,6 @@ const SHA256_BLOCK_SIZE: usize = 64;
 
 const SHA256_HASH_SIZE: usize = 32;
 
-/// The number of CPU clock cycles it takes to perform initialization action.
-const INIT_TICKS: u64 = 1000;
-
-/// The number of CPU clock cycles it takes to perform the hash update action.
-const UPDATE_TICKS: u64 = 1000;
-
 /// SHA-256 Peripheral
 #[derive(Bus)]
 #[poll_fn(poll)]
@@ -102,6 +97,9 @@ pub struct HashSha256 {
 
     timer: Timer,
 
+    /// Operation latencies
+    latencies: PeriphLatencies,
+
     op_complete_action: Option<ActionHandle>,
 }
 #[derive(Debug)]
@@ -120,6 +118,14 @@ pub enum WntzError {
     WntzParamInvalid,
 }
 
+impl_snapshot!(HashSha256 {
+    control,
+    status,
+    block,
+    hash,
+    op_complete_action,
+});
+
 impl HashSha256 {
     /// NAME0 Register Value
     const NAME0_VAL: RvData = 0x363532; // 256
@@ -148,10 +154,16 @@ impl HashSha256 {
             block: ReadWriteMemory::new(),
             hash: ReadOnlyMemory::new(),
             timer: Timer::new(clock),
+            latencies: PeriphLatencies::default(),
             op_complete_action: None,
         }
     }
 
+    /// Set the time taken by each operation
+    pub fn set_latencies(&mut self, latencies: PeriphLatencies) {
+        self.latencies = latencies;
+    }
+
     pub fn hash_block(&mut self, block: &[u8; 64]) -> Result<(), BusError> {
         if self.control.reg.is_set(Control::INIT) || self.control.reg.is_set(Control::NEXT) {
             // Reset the Ready and Valid status bits
@@ -180,13 +192,15 @@ impl HashSha256 {
                 self.sha256.update(block);
 
                 // Schedule a future call to poll() complete the operation.
-                self.op_complete_action = Some(self.timer.schedule_poll_in(INIT_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.sha256_init));
             } else if self.control.reg.is_set(Control::NEXT) {
                 // Update the SHA512 engine with a new block
                 self.sha256.update(self.block.data());
 
                 // Schedule a future call to poll() complete the operation.
-                self.op_complete_action = Some(self.timer.schedule_poll_in(UPDATE_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.sha256_update));
             }
         }
         Ok(())
@@ -279,7 +293,8 @@ impl HashSha256 {
                 }
 
                 // Schedule a future call to poll() complete the operation.
-                self.op_complete_action = Some(self.timer.schedule_poll_in(INIT_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.sha256_init));
             }
         } else {
             let mut block = [0; 64];
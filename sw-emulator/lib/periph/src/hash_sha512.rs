@@ -15,8 +15,10 @@ Abstract:
 use crate::helpers::words_from_bytes_le;
 use crate::key_vault::KeyUsage;
 use crate::KeyVault;
+use crate::PeriphLatencies;
 use caliptra_emu_bus::{
-    ActionHandle, Bus, BusError, Clock, ReadOnlyMemory, ReadOnlyRegister, ReadWriteRegister, Timer,
+    impl_snapshot, ActionHandle, Bus, BusError, Clock, ReadOnlyMemory, ReadOnlyRegister,
+    ReadWriteRegister, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter, Timer,
     WriteOnlyRegister,
 };
 use caliptra_emu_crypto::EndianessTransform;
@@ -107,15 +109,6 @@ const SHA512_BLOCK_SIZE_WORDS: usize = 128 >> 2;
 const SHA512_HASH_SIZE: usize = 64;
 const SHA384_HASH_SIZE: usize = 48;
 
-/// The number of CPU clock cycles it takes to perform initialization action.
-const INIT_TICKS: u64 = 1000;
-
-/// The number of CPU clock cycles it takes to perform the hash update action.
-const UPDATE_TICKS: u64 = 1000;
-
-/// The number of CPU clock cycles read and write keys from key vault
-const KEY_RW_TICKS: u64 = 100;
-
 fn sha512_block_words_from_bytes_le(
     arr: &[u8; SHA512_BLOCK_SIZE],
 ) -> [u32; SHA512_BLOCK_SIZE_WORDS] {
@@ -213,6 +206,9 @@ pub struct HashSha512Regs {
 
     timer: Timer,
 
+    /// Operation latencies
+    latencies: PeriphLatencies,
+
     /// Operation complete action
     op_complete_action: Option<ActionHandle>,
 
@@ -228,6 +224,27 @@ pub struct HashSha512Regs {
     pcr_present: bool,
 }
 
+// The digest of a hash still in progress lives in the SHA engine and is not
+// saved; firmware restarts hashes with INIT after a restore.
+impl_snapshot!(HashSha512Regs {
+    control,
+    status,
+    block,
+    hash,
+    block_read_ctrl,
+    block_read_status,
+    hash_write_ctrl,
+    hash_write_status,
+    pcr_gen_hash_nonce,
+    pcr_hash_control,
+    pcr_hash_status,
+    pcr_hash_digest,
+    op_complete_action,
+    op_block_read_complete_action,
+    op_hash_write_complete_action,
+    op_pcr_gen_hash_complete_action,
+});
+
 impl HashSha512Regs {
     /// NAME0 Register Value
     const NAME0_VAL: RvData = 0x323135; // 512
@@ -263,6 +280,7 @@ impl HashSha512Regs {
             hash: ReadOnlyMemory::new(),
             key_vault,
             timer: Timer::new(clock),
+            latencies: PeriphLatencies::default(),
             op_complete_action: None,
             op_block_read_complete_action: None,
             op_hash_write_complete_action: None,
@@ -327,14 +345,16 @@ impl HashSha512Regs {
                     .update(&sha512_block_bytes_from_words_le(&self.block));
 
                 // Schedule a future call to poll() complete the operation.
-                self.op_complete_action = Some(self.timer.schedule_poll_in(INIT_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.sha512_init));
             } else if self.control.reg.is_set(Control::NEXT) {
                 // Update the SHA512 engine with a new block
                 self.sha512
                     .update(&sha512_block_bytes_from_words_le(&self.block));
 
                 // Schedule a future call to poll() complete the operation.
-                self.op_complete_action = Some(self.timer.schedule_poll_in(UPDATE_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.sha512_update));
             }
         }
 
@@ -401,7 +421,8 @@ impl HashSha512Regs {
                     + BlockReadStatus::ERROR::CLEAR,
             );
 
-            self.op_block_read_complete_action = Some(self.timer.schedule_poll_in(KEY_RW_TICKS));
+            self.op_block_read_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
         }
 
         Ok(())
@@ -469,7 +490,8 @@ impl HashSha512Regs {
             .reg
             .modify(PcrHashStatus::VALID::CLEAR + PcrHashStatus::READY::CLEAR);
 
-        self.op_pcr_gen_hash_complete_action = Some(self.timer.schedule_poll_in(UPDATE_TICKS));
+        self.op_pcr_gen_hash_complete_action =
+            Some(self.timer.schedule_poll_in(self.latencies.sha512_update));
 
         Ok(())
     }
@@ -526,7 +548,8 @@ impl HashSha512Regs {
                     + HashWriteStatus::ERROR::CLEAR,
             );
 
-            self.op_hash_write_complete_action = Some(self.timer.schedule_poll_in(KEY_RW_TICKS));
+            self.op_hash_write_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
         } else if self.control.reg.is_set(Control::LAST)
             && self
                 .block_read_ctrl
@@ -700,6 +723,11 @@ impl HashSha512 {
         }
     }
 
+    /// Set the time taken by each operation
+    pub fn set_latencies(&mut self, latencies: PeriphLatencies) {
+        self.regs.borrow_mut().latencies = latencies;
+    }
+
     /// Export the PCR hash digest
     pub fn pcr_hash_digest(&self) -> [u8; 48] {
         self.regs
@@ -727,6 +755,16 @@ impl Bus for HashSha512 {
     }
 }
 
+impl Snapshot for HashSha512 {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.regs.borrow().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.regs.borrow_mut().restore(r)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -13,8 +13,11 @@ Abstract:
 --*/
 
 use crate::helpers::bytes_from_words_le;
+use crate::PeriphLatencies;
 use crate::{KeyUsage, KeyVault};
-use caliptra_emu_bus::{ActionHandle, BusError, Clock, ReadOnlyRegister, ReadWriteRegister, Timer};
+use caliptra_emu_bus::{
+    impl_snapshot, ActionHandle, BusError, Clock, ReadOnlyRegister, ReadWriteRegister, Timer,
+};
 use caliptra_emu_crypto::EndianessTransform;
 use caliptra_emu_crypto::{Hmac512, Hmac512Mode};
 use caliptra_emu_derive::Bus;
@@ -93,15 +96,6 @@ const HMAC_BLOCK_SIZE: usize = 128;
 /// HMAC Tag Size
 const HMAC_TAG_SIZE: usize = 48;
 
-/// The number of CPU clock cycles it takes to perform initialization action.
-const INIT_TICKS: u64 = 1000;
-
-/// The number of CPU clock cycles it takes to perform the hash update action.
-const UPDATE_TICKS: u64 = 1000;
-
-/// The number of CPU clock cycles read and write keys from key vault
-const KEY_RW_TICKS: u64 = 100;
-
 /// LSFR Seed Size.
 const HMAC_LFSR_SEED_SIZE: usize = 48;
 
@@ -193,6 +187,9 @@ pub struct HmacSha384 {
     /// Timer
     timer: Timer,
 
+    /// Operation latencies
+    latencies: PeriphLatencies,
+
     /// Operation complete action
     op_complete_action: Option<ActionHandle>,
 
@@ -206,6 +203,30 @@ pub struct HmacSha384 {
     op_tag_write_complete_action: Option<ActionHandle>,
 }
 
+// The HMAC engine's intermediate state is not saved; snapshots must not be
+// taken in the middle of a multi-block operation.
+impl_snapshot!(HmacSha384 {
+    control,
+    status,
+    key,
+    block,
+    tag,
+    lfsr_seed,
+    key_read_ctrl,
+    key_read_status,
+    block_read_ctrl,
+    block_read_status,
+    tag_write_ctrl,
+    tag_write_status,
+    key_from_kv,
+    block_from_kv,
+    hide_tag_from_cpu,
+    op_complete_action,
+    op_key_read_complete_action,
+    op_block_read_complete_action,
+    op_tag_write_complete_action,
+});
+
 impl HmacSha384 {
     /// NAME0 Register Value
     const NAME0_VAL: RvData = 0x63616d68; // hmac
@@ -250,6 +271,7 @@ impl HmacSha384 {
             tag_write_status: ReadOnlyRegister::new(TagWriteStatus::READY::SET.value),
             key_vault,
             timer: Timer::new(clock),
+            latencies: PeriphLatencies::default(),
             key_from_kv: false,
             block_from_kv: false,
             hide_tag_from_cpu: false,
@@ -260,6 +282,11 @@ impl HmacSha384 {
         }
     }
 
+    /// Set the time taken by each operation
+    pub fn set_latencies(&mut self, latencies: PeriphLatencies) {
+        self.latencies = latencies;
+    }
+
     fn read_access_fault(&mut self, _size: RvSize, _index: usize) -> Result<u32, BusError> {
         Err(BusError::LoadAccessFault)
     }
@@ -331,13 +358,15 @@ impl HmacSha384 {
                 );
 
                 // Schedule a future call to poll() complete the operation.
-                self.op_complete_action = Some(self.timer.schedule_poll_in(INIT_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.hmac_init));
             } else if self.control.reg.is_set(Control::NEXT) {
                 // Update a HMAC engine with a new block
                 self.hmac.update(&bytes_from_words_le(&self.block));
 
                 // Schedule a future call to poll() complete the operation.
-                self.op_complete_action = Some(self.timer.schedule_poll_in(UPDATE_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.hmac_update));
             }
         }
 
@@ -380,7 +409,8 @@ impl HmacSha384 {
                     + KeyReadStatus::ERROR::CLEAR,
             );
 
-            self.op_key_read_complete_action = Some(self.timer.schedule_poll_in(KEY_RW_TICKS));
+            self.op_key_read_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
         }
 
         Ok(())
@@ -421,7 +451,8 @@ impl HmacSha384 {
                     + KeyReadStatus::ERROR::CLEAR,
             );
 
-            self.op_block_read_complete_action = Some(self.timer.schedule_poll_in(KEY_RW_TICKS));
+            self.op_block_read_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
         }
 
         Ok(())
@@ -501,7 +532,8 @@ impl HmacSha384 {
                     + TagWriteStatus::ERROR::CLEAR,
             );
 
-            self.op_tag_write_complete_action = Some(self.timer.schedule_poll_in(KEY_RW_TICKS));
+            self.op_tag_write_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
         }
 
         // Update Ready and Valid status bits
@@ -15,6 +15,10 @@ use caliptra_emu_bus::Bus;
 use caliptra_emu_bus::BusError;
 use caliptra_emu_bus::Clock;
 use caliptra_emu_bus::Ram;
+use caliptra_emu_bus::Snapshot;
+use caliptra_emu_bus::SnapshotError;
+use caliptra_emu_bus::SnapshotReader;
+use caliptra_emu_bus::SnapshotWriter;
 use caliptra_emu_bus::Timer;
 use caliptra_emu_bus::TimerAction;
 use caliptra_emu_types::RvAddr;
@@ -49,6 +53,19 @@ impl Iccm {
     }
 }
 
+impl Snapshot for Iccm {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.iccm.ram.borrow().save(w);
+        self.iccm.locked.get().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.iccm.ram.borrow_mut().restore(r)?;
+        self.iccm.locked.set(r.read()?);
+        Ok(())
+    }
+}
+
 struct IccmImpl {
     ram: RefCell<Ram>,
     locked: Cell<bool>,
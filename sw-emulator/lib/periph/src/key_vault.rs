@@ -13,7 +13,10 @@ Abstract:
 --*/
 
 use bitfield::bitfield;
-use caliptra_emu_bus::{Bus, BusError, ReadWriteMemory, ReadWriteRegisterArray};
+use caliptra_emu_bus::{
+    impl_snapshot, Bus, BusError, EventTrace, ReadWriteMemory, ReadWriteRegisterArray, Snapshot,
+    SnapshotError, SnapshotReader, SnapshotWriter, TraceEventKind,
+};
 use caliptra_emu_derive::Bus;
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
 use std::cell::RefCell;
@@ -207,6 +210,12 @@ impl KeyVault {
             .borrow_mut()
             .clear_with_debug_values(sel_debug_value);
     }
+
+    /// Record the keys written by the cryptographic peripherals to
+    /// `event_trace`. `None` stops recording.
+    pub fn set_event_trace(&mut self, event_trace: Option<EventTrace>) {
+        self.regs.borrow_mut().event_trace = event_trace;
+    }
 }
 impl Default for KeyVault {
     fn default() -> Self {
@@ -234,6 +243,16 @@ impl Bus for KeyVault {
     }
 }
 
+impl Snapshot for KeyVault {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.regs.borrow().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.regs.borrow_mut().restore(r)
+    }
+}
+
 bitfield! {
     /// Key Usage
     #[derive(Debug, Default, PartialEq, Eq, Clone, Copy)]
@@ -375,8 +394,27 @@ pub struct KeyVaultRegs {
     #[register_array(offset = 0x0000_44a0, write_fn = write_sticky_lockable_scratch)]
     sticky_lockable_scratch:
         ReadWriteRegisterArray<u32, { STICKY_LOCKABLE_SCRATCH_REG_COUNT as usize }>,
+
+    /// Key writes are recorded here, if set
+    event_trace: Option<EventTrace>,
 }
 
+impl_snapshot!(KeyVaultRegs {
+    key_control,
+    keys,
+    pcr_control,
+    pcrs,
+    sticky_datavault_control,
+    sticky_datavault_entry,
+    datavault_control,
+    datavault_entry,
+    lockable_scratch_control,
+    lockable_scratch,
+    nonsticky_generic_scratch,
+    sticky_lockable_scratch_control,
+    sticky_lockable_scratch,
+});
+
 impl KeyVaultRegs {
     /// Create a new instance of KeyVault registers
     pub fn new() -> Self {
@@ -400,6 +438,7 @@ impl KeyVaultRegs {
                 STICKY_LOCKABLE_SCRATCH_CTRL_REG_RESET_VAL,
             ),
             sticky_lockable_scratch: ReadWriteRegisterArray::new(0),
+            event_trace: None,
         }
     }
 
@@ -541,6 +580,12 @@ impl KeyVaultRegs {
         // Update the last dword in the key
         key_ctrl_reg.modify(KV_CONTROL::LAST_DWORD.val(key_wordlen as u32 - 1));
 
+        if let Some(event_trace) = &self.event_trace {
+            event_trace.record(TraceEventKind::KeyVaultWrite {
+                key_id,
+                usage: key_usage,
+            });
+        }
         Ok(())
     }
 
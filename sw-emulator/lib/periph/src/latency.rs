@@ -0,0 +1,71 @@
+/*++
+
+Licensed under the Apache-2.0 license.
+
+File Name:
+
+    latency.rs
+
+Abstract:
+
+    File contains the operation latencies of the cryptographic peripherals.
+
+--*/
+
+/// Number of CPU clock cycles each peripheral operation takes to complete.
+///
+/// The defaults are placeholders rather than measurements of the RTL; replace
+/// them with calibrated values when cycle counts matter.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct PeriphLatencies {
+    /// Deobfuscation engine operation
+    pub doe_op: u64,
+
+    /// ECC-384 key generation, signing or verification
+    pub ecc384_op: u64,
+
+    /// HMAC-384 initialization
+    pub hmac_init: u64,
+
+    /// HMAC-384 update
+    pub hmac_update: u64,
+
+    /// SHA-512 initialization
+    pub sha512_init: u64,
+
+    /// SHA-512 update
+    pub sha512_update: u64,
+
+    /// SHA-256 initialization
+    pub sha256_init: u64,
+
+    /// SHA-256 update
+    pub sha256_update: u64,
+
+    /// SHA-512 accelerator digest of the mailbox contents
+    pub sha512_acc_op: u64,
+
+    /// ML-DSA-87 key generation, signing or verification
+    pub ml_dsa87_op: u64,
+
+    /// Key vault read or write
+    pub key_rw: u64,
+}
+
+impl Default for PeriphLatencies {
+    fn default() -> Self {
+        Self {
+            doe_op: 1000,
+            ecc384_op: 1000,
+            hmac_init: 1000,
+            hmac_update: 1000,
+            sha512_init: 1000,
+            sha512_update: 1000,
+            sha256_init: 1000,
+            sha256_update: 1000,
+            sha512_acc_op: 1000,
+            ml_dsa87_op: 1000,
+            key_rw: 100,
+        }
+    }
+}
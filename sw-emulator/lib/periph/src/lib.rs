@@ -24,6 +24,7 @@ mod helpers;
 mod hmac_sha384;
 mod iccm;
 mod key_vault;
+mod latency;
 mod mailbox;
 #[cfg(feature = "hw-2.x")]
 mod ml_dsa87;
@@ -42,6 +43,7 @@ pub use hmac_sha384::HmacSha384;
 pub use iccm::Iccm;
 pub use key_vault::KeyUsage;
 pub use key_vault::KeyVault;
+pub use latency::PeriphLatencies;
 pub use mailbox::{MailboxExternal, MailboxInternal, MailboxRam, MailboxRequester};
 pub use root_bus::{
     ActionCb, CaliptraRootBus, CaliptraRootBusArgs, DownloadIdevidCsrCb, ReadyForFwCb,
@@ -13,7 +13,10 @@ Abstract:
 --*/
 use smlang::statemachine;
 
-use caliptra_emu_bus::{Bus, BusMmio, Clock, Ram, Timer};
+use caliptra_emu_bus::{
+    impl_snapshot, Bus, BusMmio, Clock, EventTrace, Ram, Snapshot, SnapshotError, SnapshotReader,
+    SnapshotWriter, Timer, TraceEventKind,
+};
 use caliptra_emu_bus::{BusError, ReadOnlyRegister, ReadWriteRegister, WriteOnlyRegister};
 use caliptra_emu_derive::Bus;
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
@@ -154,6 +157,17 @@ impl MailboxInternal {
         }
     }
 
+    /// Record the mailbox state transitions to `event_trace`, starting with
+    /// the current state. `None` stops recording.
+    pub fn set_event_trace(&mut self, event_trace: Option<EventTrace>) {
+        let mut regs = self.regs.borrow_mut();
+        if let Some(event_trace) = &event_trace {
+            let (state, name) = fsm_state(regs.state_machine.state());
+            event_trace.record(TraceEventKind::MailboxState { state, name });
+        }
+        regs.event_trace = event_trace;
+    }
+
     pub fn get_notif_irq(&mut self) -> bool {
         let mut regs = self.regs.borrow_mut();
         if regs.irq {
@@ -255,6 +269,9 @@ pub struct MailboxRegs {
 
     ///
     timer: Timer,
+
+    /// State transitions are recorded here, if set
+    event_trace: Option<EventTrace>,
 }
 
 impl MailboxRegs {
@@ -285,12 +302,25 @@ impl MailboxRegs {
             requester: MailboxRequester::Caliptra,
             irq: false,
             timer: Timer::new(clock),
+            event_trace: None,
         }
     }
     pub fn set_request(&mut self, requester: MailboxRequester) {
         self.requester = requester;
     }
 
+    /// Deliver an event to the state machine, tracing the state it moves to
+    fn process_event(&mut self, event: Events) {
+        let prev = fsm_state(self.state_machine.state());
+        let _ = self.state_machine.process_event(event);
+        let (state, name) = fsm_state(self.state_machine.state());
+        if let Some(event_trace) = &self.event_trace {
+            if state != prev.0 {
+                event_trace.record(TraceEventKind::MailboxState { state, name });
+            }
+        }
+    }
+
     // Todo: Implement read_lock callback fn
     pub fn read_lock(&mut self, _size: RvSize) -> Result<u32, BusError> {
         // If state is not idle mailbox is locked.
@@ -299,9 +329,7 @@ impl MailboxRegs {
             _ => Ok(1),
         };
         // Deliver event to the state machine.
-        let _ = self
-            .state_machine
-            .process_event(Events::RdLock(self.requester));
+        self.process_event(Events::RdLock(self.requester));
 
         result
     }
@@ -313,7 +341,7 @@ impl MailboxRegs {
 
     // Todo: Implement write cmd callback fn
     pub fn write_cmd(&mut self, _size: RvSize, val: RvData) -> Result<(), BusError> {
-        let _ = self.state_machine.process_event(Events::CmdWrite(Cmd(val)));
+        self.process_event(Events::CmdWrite(Cmd(val)));
         Ok(())
     }
 
@@ -324,9 +352,7 @@ impl MailboxRegs {
 
     // Todo: Implement write dlen callback fn
     pub fn write_dlen(&mut self, _size: RvSize, val: RvData) -> Result<(), BusError> {
-        let _ = self
-            .state_machine
-            .process_event(Events::DlenWrite(DataLength(val)));
+        self.process_event(Events::DlenWrite(DataLength(val)));
         Ok(())
     }
 
@@ -337,24 +363,21 @@ impl MailboxRegs {
 
     // Todo: Implement write din callback fn
     pub fn write_din(&mut self, _size: RvSize, val: RvData) -> Result<(), BusError> {
-        let _ = self
-            .state_machine
-            .process_event(Events::DataWrite(DataIn(val)));
+        self.process_event(Events::DataWrite(DataIn(val)));
         Ok(())
     }
 
     // Todo: Implement read dout callback fn
     pub fn read_dout(&mut self, _size: RvSize) -> Result<u32, BusError> {
-        let mb = &mut self.state_machine;
-        let _ = mb.process_event(Events::DataRead);
-        Ok(mb.context.data_out)
+        self.process_event(Events::DataRead);
+        Ok(self.state_machine.context.data_out)
     }
 
     /// Write to execute register
     pub fn write_ex(&mut self, _size: RvSize, val: RvData) -> Result<(), BusError> {
         // Only the lock owner can clear the execute bit.
         if self.requester != self.state_machine.context.user {
-            let _ = self.state_machine.process_event(Events::Error);
+            self.process_event(Events::Error);
             return Ok(());
         }
 
@@ -381,7 +404,7 @@ impl MailboxRegs {
         self.irq = true;
         self.timer.schedule_poll_in(1);
 
-        let _ = self.state_machine.process_event(event);
+        self.process_event(event);
         self.execute.reg.set(val);
         Ok(())
     }
@@ -389,7 +412,7 @@ impl MailboxRegs {
     // Todo: Implement write status callback fn
     pub fn write_status(&mut self, _size: RvSize, val: RvData) -> Result<(), BusError> {
         // Send event to state machine.
-        let _ = self.state_machine.process_event(Events::SetStatus);
+        self.process_event(Events::SetStatus);
 
         let val = LocalRegisterCopy::<u32, Status::Register>::new(val);
         self.state_machine
@@ -415,7 +438,7 @@ impl MailboxRegs {
     }
 
     pub fn write_unlock(&mut self, _size: RvSize, _val: RvData) -> Result<(), BusError> {
-        let _ = self.state_machine.process_event(Events::WrUnlock);
+        self.process_event(Events::WrUnlock);
         Ok(())
     }
 
@@ -424,6 +447,19 @@ impl MailboxRegs {
     }
 }
 
+/// MBOX_STATUS.mbox_fsm_ps value and name of a state
+fn fsm_state(state: &States) -> (u32, &'static str) {
+    match state {
+        States::Idle => (0, "idle"),
+        States::RdyForCmd => (1, "rdy_for_cmd"),
+        States::RdyForData => (2, "rdy_for_data"),
+        States::RdyForDlen => (3, "rdy_for_dlen"),
+        States::ExecSoc => (4, "execute_soc"),
+        States::ExecUc => (6, "execute_uc"),
+        States::Error => (7, "error"),
+    }
+}
+
 #[derive(PartialEq)]
 /// Data length
 pub struct DataLength(pub u32);
@@ -643,6 +679,116 @@ impl Fifo {
     }
 }
 
+impl Snapshot for MailboxRam {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.ram.borrow().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.ram.borrow_mut().restore(r)
+    }
+}
+
+impl Snapshot for MailboxInternal {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.regs.borrow().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.regs.borrow_mut().restore(r)
+    }
+}
+
+impl Snapshot for MailboxRequester {
+    fn save(&self, w: &mut SnapshotWriter) {
+        u32::from(*self).save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        *self = r.read::<u32>()?.into();
+        Ok(())
+    }
+}
+
+/// The mailbox SRAM backing the FIFO is saved by its owner, not here.
+impl Snapshot for MailboxRegs {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.lock.save(w);
+        self.user.save(w);
+        self._cmd.save(w);
+        self._dlen.save(w);
+        self.data_in.save(w);
+        self.data_out.save(w);
+        self.execute.save(w);
+        self._status.save(w);
+        self._unlock.save(w);
+        let state: u8 = match self.state_machine.state() {
+            States::Idle => 0,
+            States::RdyForCmd => 1,
+            States::RdyForDlen => 2,
+            States::RdyForData => 3,
+            States::ExecUc => 4,
+            States::ExecSoc => 5,
+            States::Error => 6,
+        };
+        state.save(w);
+        self.state_machine.context.save(w);
+        self.requester.save(w);
+        self.irq.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.lock.restore(r)?;
+        self.user.restore(r)?;
+        self._cmd.restore(r)?;
+        self._dlen.restore(r)?;
+        self.data_in.restore(r)?;
+        self.data_out.restore(r)?;
+        self.execute.restore(r)?;
+        self._status.restore(r)?;
+        self._unlock.restore(r)?;
+        let state = match r.read::<u8>()? {
+            0 => States::Idle,
+            1 => States::RdyForCmd,
+            2 => States::RdyForDlen,
+            3 => States::RdyForData,
+            4 => States::ExecUc,
+            5 => States::ExecSoc,
+            6 => States::Error,
+            _ => return Err(SnapshotError::InvalidValue),
+        };
+        self.state_machine.context.restore(r)?;
+        // The state machine only exposes its state through transitions, so
+        // rebuild it around the restored context.
+        let ram = self.state_machine.context.fifo.mailbox_ram.clone();
+        let state_machine = std::mem::replace(
+            &mut self.state_machine,
+            StateMachine::new(Context::new(ram)),
+        );
+        self.state_machine = StateMachine::new_with_state(state_machine.context, state);
+        self.requester.restore(r)?;
+        self.irq.restore(r)
+    }
+}
+
+impl_snapshot!(Context {
+    locked,
+    user,
+    exec,
+    dlen,
+    fifo,
+    status,
+    cmd,
+    data_out,
+    unlock,
+});
+
+impl_snapshot!(Fifo {
+    latched_dlen,
+    read_index,
+    write_index,
+});
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -682,6 +828,43 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn test_event_trace() {
+        let clock = Clock::new();
+        let event_trace = EventTrace::new(&clock);
+        let mut mb = MailboxInternal::new(&clock, MailboxRam::new());
+        mb.set_event_trace(Some(event_trace.clone()));
+        let uc_regs = mb.regs();
+        assert!(!uc_regs.lock().read().lock());
+        // Reading the lock again does not change the state
+        assert!(uc_regs.lock().read().lock());
+        uc_regs.cmd().write(|_| 0x55);
+        uc_regs.unlock().write(|w| w.unlock(true));
+
+        let states: Vec<_> = event_trace.events().into_iter().map(|e| e.kind).collect();
+        assert_eq!(
+            states,
+            vec![
+                TraceEventKind::MailboxState {
+                    state: 0,
+                    name: "idle"
+                },
+                TraceEventKind::MailboxState {
+                    state: 1,
+                    name: "rdy_for_cmd"
+                },
+                TraceEventKind::MailboxState {
+                    state: 3,
+                    name: "rdy_for_dlen"
+                },
+                TraceEventKind::MailboxState {
+                    state: 0,
+                    name: "idle"
+                },
+            ]
+        );
+    }
+
     #[test]
     fn test_sm_arc_rdyforcmd_unlock() {
         let mb = get_mailbox();
@@ -12,7 +12,9 @@ File contains Ml_Dsa87 peripheral implementation.
 
 --*/
 
-use caliptra_emu_bus::{ActionHandle, BusError, Clock, ReadOnlyRegister, ReadWriteRegister, Timer};
+use caliptra_emu_bus::{
+    impl_snapshot, ActionHandle, BusError, Clock, ReadOnlyRegister, ReadWriteRegister, Timer,
+};
 use caliptra_emu_derive::Bus;
 use caliptra_emu_types::{RvData, RvSize};
 use fips204::ml_dsa_87::{try_keygen_with_rng, PrivateKey, PublicKey, PK_LEN, SIG_LEN, SK_LEN};
@@ -21,8 +23,11 @@ use rand::rngs::StdRng;
 use rand::SeedableRng;
 use tock_registers::interfaces::{ReadWriteable, Readable, Writeable};
 use tock_registers::register_bitfields;
+use tock_registers::registers::InMemoryRegister;
 
 use crate::helpers::{bytes_from_words_le, words_from_bytes_le};
+use crate::PeriphLatencies;
+use crate::{KeyUsage, KeyVault};
 
 /// ML_DSA87 Initialization Vector size
 const ML_DSA87_IV_SIZE: usize = 64;
@@ -39,9 +44,6 @@ const ML_DSA87_MSG_SIZE: usize = 64;
 /// ML_DSA87 VERIFICATION size
 const ML_DSA87_VERIFICATION_SIZE: usize = 64;
 
-/// The number of CPU clock cycles it takes to perform Ml_Dsa87 operation
-const ML_DSA87_OP_TICKS: u64 = 1000;
-
 register_bitfields! [
     u32,
 
@@ -55,13 +57,35 @@ register_bitfields! [
         ],
         ZEROIZE OFFSET(2) NUMBITS(1) [],
 //        PCR_SIGN OFFSET(3) NUMBITS(1) [] TODO remove right??
+        STREAM_MSG OFFSET(6) NUMBITS(1) [],
     ],
 
     /// Status Register Fields
     Status[
         READY OFFSET(0) NUMBITS(1) [],
         VALID OFFSET(1) NUMBITS(1) [],
-        RSVD OFFSET(2) NUMBITS(30) [],
+        MSG_STREAM_READY OFFSET(2) NUMBITS(1) [],
+        RSVD OFFSET(3) NUMBITS(29) [],
+    ],
+
+    /// Key Control Register Fields
+    KeyReadControl[
+        KEY_READ_EN OFFSET(0) NUMBITS(1) [],
+        KEY_ID OFFSET(1) NUMBITS(5) [],
+        PCR_HASH_EXTEND OFFSET(6) NUMBITS(1) [],
+        RSVD OFFSET(7) NUMBITS(25) [],
+    ],
+
+    /// Key Status Register Fields
+    KeyReadStatus[
+        READY OFFSET(0) NUMBITS(1) [],
+        VALID OFFSET(1) NUMBITS(1) [],
+        ERROR OFFSET(2) NUMBITS(8) [
+            KV_SUCCESS = 0,
+            KV_READ_FAIL = 1,
+            KV_WRITE_FAIL= 2,
+        ],
+        RSVD OFFSET(10) NUMBITS(22) [],
     ],
 ];
 
@@ -99,9 +123,13 @@ pub struct MlDsa87 {
     sign_rnd: [u32; ML_DSA87_SIGN_RND_SIZE / 4],
 
     /// Message
-    #[register_array(offset = 0x0000_0200)]
+    #[register_array(offset = 0x0000_0200, write_fn = on_write_message)]
     message: [u32; ML_DSA87_MSG_SIZE / 4],
 
+    /// Byte enables of the last word of a streamed message
+    #[register(offset = 0x0000_02C0)]
+    msg_strobe: ReadWriteRegister<u32>,
+
     /// Verification result
     #[register_array(offset = 0x0000_0280, write_fn = write_access_fault)]
     verification_result: [u32; ML_DSA87_VERIFICATION_SIZE / 4],
@@ -122,13 +150,53 @@ pub struct MlDsa87 {
     #[register_array(offset = 0x0000_3400)]
     signature: [u32; SIG_LEN / 4 + 1], // Signature len is unaligned
 
+    /// Seed Read Control Register
+    #[register(offset = 0x0000_5000, write_fn = on_write_seed_read_control)]
+    seed_read_ctrl: ReadWriteRegister<u32, KeyReadControl::Register>,
+
+    /// Seed Read Status Register
+    #[register(offset = 0x0000_5004)]
+    seed_read_status: ReadOnlyRegister<u32, KeyReadStatus::Register>,
+
     /// Timer
     timer: Timer,
 
+    /// Operation latencies
+    latencies: PeriphLatencies,
+
+    /// Key Vault
+    key_vault: KeyVault,
+
     /// Operation complete callback
     op_complete_action: Option<ActionHandle>,
+
+    /// Seed read complete action
+    op_seed_read_complete_action: Option<ActionHandle>,
+
+    /// Message streamed through `message[0]`
+    msg_stream: Vec<u8>,
 }
 
+impl_snapshot!(MlDsa87 {
+    control,
+    status,
+    iv,
+    seed,
+    sign_rnd,
+    message,
+    msg_strobe,
+    verification_result,
+    sk_out,
+    sk_in,
+    pk,
+    signature,
+    seed_read_ctrl,
+    seed_read_status,
+    op_complete_action,
+    op_seed_read_complete_action,
+    msg_stream,
+});
+
 impl MlDsa87 {
     /// NAME0 Register Value TODO update when known
     const NAME0_VAL: RvData = 0x73656370; //0x63737065; // secp
@@ -142,7 +210,10 @@ impl MlDsa87 {
     /// VERSION1 Register Value TODO update when known
     const VERSION1_VAL: RvData = 0x00000000;
 
-    pub fn new(clock: &Clock) -> Self {
+    /// MSG_STROBE value enabling every byte of a word
+    const MSG_STROBE_ALL: RvData = 0xF;
+
+    pub fn new(clock: &Clock, key_vault: KeyVault) -> Self {
         Self {
             name: [Self::NAME0_VAL, Self::NAME1_VAL],
             version: [Self::VERSION0_VAL, Self::VERSION1_VAL],
@@ -152,16 +223,28 @@ impl MlDsa87 {
             seed: Default::default(),
             sign_rnd: Default::default(),
             message: Default::default(),
+            msg_strobe: ReadWriteRegister::new(Self::MSG_STROBE_ALL),
             verification_result: Default::default(),
             sk_out: [0; 1224],
             sk_in: [0; 1224],
             pk: [0; 648],
             signature: [0; 1157],
+            seed_read_ctrl: ReadWriteRegister::new(0),
+            seed_read_status: ReadOnlyRegister::new(KeyReadStatus::READY::SET.value),
             timer: Timer::new(clock),
+            latencies: PeriphLatencies::default(),
+            key_vault,
             op_complete_action: None,
+            op_seed_read_complete_action: None,
+            msg_stream: Vec::new(),
         }
     }
 
+    /// Set the time taken by each operation
+    pub fn set_latencies(&mut self, latencies: PeriphLatencies) {
+        self.latencies = latencies;
+    }
+
     fn read_access_fault(&self, _size: RvSize, _index: usize) -> Result<RvData, BusError> {
         Err(BusError::LoadAccessFault)
     }
@@ -175,8 +258,20 @@ impl MlDsa87 {
         Err(BusError::StoreAccessFault)
     }
 
-    // TODO Clear registers
-    fn zeroize(&mut self) {}
+    // Clear registers
+    fn zeroize(&mut self) {
+        self.iv.as_mut().fill(0);
+        self.seed.as_mut().fill(0);
+        self.sign_rnd.as_mut().fill(0);
+        self.message.as_mut().fill(0);
+        self.msg_strobe.reg.set(Self::MSG_STROBE_ALL);
+        self.msg_stream.clear();
+        self.verification_result.as_mut().fill(0);
+        self.sk_out.as_mut().fill(0);
+        self.sk_in.as_mut().fill(0);
+        self.pk.as_mut().fill(0);
+        self.signature.as_mut().fill(0);
+    }
 
     /// On Write callback for `control` register
     ///
@@ -198,6 +293,17 @@ impl MlDsa87 {
         self.control.reg.set(val);
 
         match self.control.reg.read_as_enum(Control::CTRL) {
+            Some(Control::CTRL::Value::SIGN) | Some(Control::CTRL::Value::VERIFY)
+                if self.control.reg.is_set(Control::STREAM_MSG) =>
+            {
+                // Reset the Ready and Valid status bits and wait for the
+                // message; the operation starts once it ends.
+                self.status.reg.modify(
+                    Status::READY::CLEAR + Status::VALID::CLEAR + Status::MSG_STREAM_READY::SET,
+                );
+                self.msg_strobe.reg.set(Self::MSG_STROBE_ALL);
+                self.msg_stream.clear();
+            }
             Some(Control::CTRL::Value::GEN_KEY)
             | Some(Control::CTRL::Value::SIGN)
             | Some(Control::CTRL::Value::VERIFY) => {
@@ -206,7 +312,8 @@ impl MlDsa87 {
                     .reg
                     .modify(Status::READY::CLEAR + Status::VALID::CLEAR);
 
-                self.op_complete_action = Some(self.timer.schedule_poll_in(ML_DSA87_OP_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.ml_dsa87_op));
             }
             _ => {}
         }
@@ -218,6 +325,141 @@ impl MlDsa87 {
         Ok(())
     }
 
+    /// On Write callback for `message` registers
+    ///
+    /// While a message is streamed, every write to the first word appends
+    /// it to the message. The write following a `msg_strobe` write appends
+    /// only the enabled bytes and starts the operation.
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Size of the write
+    /// * `index` - Index of the word
+    /// * `val` - Data to write
+    ///
+    /// # Error
+    ///
+    /// * `BusError` - Exception with cause `BusError::StoreAccessFault`
+    fn on_write_message(
+        &mut self,
+        size: RvSize,
+        index: usize,
+        val: RvData,
+    ) -> Result<(), BusError> {
+        if !self.status.reg.is_set(Status::MSG_STREAM_READY) {
+            self.message[index] = val;
+            return Ok(());
+        }
+
+        if size != RvSize::Word || index != 0 {
+            Err(BusError::StoreAccessFault)?
+        }
+
+        let strobe = self.msg_strobe.reg.get();
+        let bytes = val.to_le_bytes();
+        if strobe == Self::MSG_STROBE_ALL {
+            self.msg_stream.extend_from_slice(&bytes);
+            return Ok(());
+        }
+
+        // Only the leading bytes of the last word may be enabled
+        let len = strobe.trailing_ones() as usize;
+        if strobe >> len != 0 {
+            Err(BusError::StoreAccessFault)?
+        }
+        self.msg_stream.extend_from_slice(&bytes[..len]);
+        self.msg_strobe.reg.set(Self::MSG_STROBE_ALL);
+        self.status.reg.modify(Status::MSG_STREAM_READY::CLEAR);
+        self.op_complete_action = Some(self.timer.schedule_poll_in(self.latencies.ml_dsa87_op));
+        Ok(())
+    }
+
+    /// Message to sign or verify
+    fn op_message(&self) -> Vec<u8> {
+        if self.control.reg.is_set(Control::STREAM_MSG) {
+            self.msg_stream.clone()
+        } else {
+            bytes_from_words_le(&self.message).to_vec()
+        }
+    }
+
+    /// On Write callback for `seed_read_control` register
+    ///
+    /// # Arguments
+    ///
+    /// * `size` - Size of the write
+    /// * `val` - Data to write
+    ///
+    /// # Error
+    ///
+    /// * `BusError` - Exception with cause `BusError::StoreAccessFault` or `BusError::StoreAddrMisaligned`
+    pub fn on_write_seed_read_control(
+        &mut self,
+        size: RvSize,
+        val: RvData,
+    ) -> Result<(), BusError> {
+        // Writes have to be Word aligned
+        if size != RvSize::Word {
+            Err(BusError::StoreAccessFault)?
+        }
+
+        // Set the key control register
+        let seed_ctrl = InMemoryRegister::<u32, KeyReadControl::Register>::new(val);
+
+        self.seed_read_ctrl.reg.modify(
+            KeyReadControl::KEY_READ_EN.val(seed_ctrl.read(KeyReadControl::KEY_READ_EN))
+                + KeyReadControl::KEY_ID.val(seed_ctrl.read(KeyReadControl::KEY_ID)),
+        );
+
+        if seed_ctrl.is_set(KeyReadControl::KEY_READ_EN) {
+            self.seed_read_status.reg.modify(
+                KeyReadStatus::READY::CLEAR
+                    + KeyReadStatus::VALID::CLEAR
+                    + KeyReadStatus::ERROR::CLEAR,
+            );
+
+            self.op_seed_read_complete_action =
+                Some(self.timer.schedule_poll_in(self.latencies.key_rw));
+        }
+
+        Ok(())
+    }
+
+    fn seed_read_complete(&mut self) {
+        let key_id = self.seed_read_ctrl.reg.read(KeyReadControl::KEY_ID);
+
+        // There is no dedicated ML-DSA destination in the key vault yet; the
+        // block accepts entries that are valid ECC key generation seeds.
+        let mut key_usage = KeyUsage::default();
+        key_usage.set_ecc_key_gen_seed(true);
+
+        let result = self.key_vault.read_key(key_id, key_usage);
+        let (seed_read_result, seed) = match result.err() {
+            Some(BusError::LoadAccessFault)
+            | Some(BusError::LoadAddrMisaligned)
+            | Some(BusError::InstrAccessFault) => (KeyReadStatus::ERROR::KV_READ_FAIL.value, None),
+            Some(BusError::StoreAccessFault) | Some(BusError::StoreAddrMisaligned) => {
+                (KeyReadStatus::ERROR::KV_WRITE_FAIL.value, None)
+            }
+            None => (
+                KeyReadStatus::ERROR::KV_SUCCESS.value,
+                Some(result.unwrap()),
+            ),
+        };
+
+        if let Some(seed) = seed {
+            self.seed = words_from_bytes_le(
+                &<[u8; ML_DSA87_SEED_SIZE]>::try_from(&seed[..ML_DSA87_SEED_SIZE]).unwrap(),
+            );
+        }
+
+        self.seed_read_status.reg.modify(
+            KeyReadStatus::READY::SET
+                + KeyReadStatus::VALID::SET
+                + KeyReadStatus::ERROR.val(seed_read_result),
+        );
+    }
+
     fn gen_key(&mut self) {
         let seed_bytes = &bytes_from_words_le(&self.seed);
         let mut rng = StdRng::from_seed(*seed_bytes);
@@ -234,10 +476,10 @@ impl MlDsa87 {
         let secret_key_bytes = &bytes_from_words_le(&self.sk_in);
         let secret_key = PrivateKey::try_from_bytes(*secret_key_bytes).unwrap();
 
-        let message = &bytes_from_words_le(&self.message);
+        let message = self.op_message();
 
         // The Ml_Dsa87 signature is 4595 len but the reg is one byte longer
-        let signature = secret_key.try_sign_with_rng(&mut rng, message).unwrap();
+        let signature = secret_key.try_sign_with_rng(&mut rng, &message).unwrap();
         let signature_extended = {
             let mut sig = [0; SIG_LEN + 1];
             sig[..SIG_LEN].copy_from_slice(&signature);
@@ -248,7 +490,7 @@ impl MlDsa87 {
     }
 
     fn verify(&mut self) {
-        let message = &bytes_from_words_le(&self.message);
+        let message = self.op_message();
 
         let public_key = {
             let key_bytes = &bytes_from_words_le(&self.pk);
@@ -257,7 +499,7 @@ impl MlDsa87 {
 
         let signature = &bytes_from_words_le(&self.signature);
 
-        let result = public_key.verify(message, &signature[..SIG_LEN].try_into().unwrap());
+        let result = public_key.verify(&message, &signature[..SIG_LEN].try_into().unwrap());
 
         self.verification_result
             .iter_mut()
@@ -281,6 +523,8 @@ impl MlDsa87 {
     fn poll(&mut self) {
         if self.timer.fired(&mut self.op_complete_action) {
             self.op_complete();
+        } else if self.timer.fired(&mut self.op_seed_read_complete_action) {
+            self.seed_read_complete();
         }
     }
 
@@ -313,9 +557,12 @@ mod tests {
     const OFFSET_SEED: RvAddr = 0x100;
     const OFFSET_SIGN_RND: RvAddr = 0x180;
     const OFFSET_MSG: RvAddr = 0x200;
+    const OFFSET_MSG_STROBE: RvAddr = 0x2C0;
     const OFFSET_SK_IN: RvAddr = 0x1620;
     const OFFSET_PK: RvAddr = 0x2940;
     const OFFSET_SIGNATURE: RvAddr = 0x3400;
+    const OFFSET_SEED_CONTROL: RvAddr = 0x5000;
+    const OFFSET_SEED_STATUS: RvAddr = 0x5004;
 
     include!("./test_data/ml_dsa87_test_data.rs");
 
@@ -337,7 +584,7 @@ mod tests {
     fn test_name() {
         let clock = Clock::new();
 
-        let mut ml_dsa87 = MlDsa87::new(&clock);
+        let mut ml_dsa87 = MlDsa87::new(&clock, KeyVault::new());
 
         let name0 = ml_dsa87.read(RvSize::Word, OFFSET_NAME0).unwrap();
         let name0 = String::from_utf8_lossy(&name0.to_be_bytes()).to_string();
@@ -352,7 +599,7 @@ mod tests {
     fn test_version() {
         let clock = Clock::new();
 
-        let mut ml_dsa87 = MlDsa87::new(&clock);
+        let mut ml_dsa87 = MlDsa87::new(&clock, KeyVault::new());
 
         let version0 = ml_dsa87.read(RvSize::Word, OFFSET_VERSION0).unwrap();
         let version0 = String::from_utf8_lossy(&version0.to_le_bytes()).to_string();
@@ -367,7 +614,7 @@ mod tests {
     fn test_control() {
         let clock = Clock::new();
 
-        let mut ml_dsa87 = MlDsa87::new(&clock);
+        let mut ml_dsa87 = MlDsa87::new(&clock, KeyVault::new());
         assert_eq!(ml_dsa87.read(RvSize::Word, OFFSET_CONTROL).unwrap(), 0);
     }
 
@@ -375,7 +622,7 @@ mod tests {
     fn test_status() {
         let clock = Clock::new();
 
-        let mut ml_dsa87 = MlDsa87::new(&clock);
+        let mut ml_dsa87 = MlDsa87::new(&clock, KeyVault::new());
         assert_eq!(ml_dsa87.read(RvSize::Word, OFFSET_STATUS).unwrap(), 1);
     }
 
@@ -383,7 +630,7 @@ mod tests {
     fn test_gen_key() {
         let clock = Clock::new();
 
-        let mut ml_dsa87 = MlDsa87::new(&clock);
+        let mut ml_dsa87 = MlDsa87::new(&clock, KeyVault::new());
 
         let mut seed = [0u8; 32];
         seed.to_big_endian(); // Change DWORDs to big-endian. TODO is this needed?
@@ -425,11 +672,117 @@ mod tests {
         assert_eq!(&public_key, &PUB_KEY);
     }
 
+    #[test]
+    fn test_gen_key_kv_seed() {
+        // Test for getting the seed from the key-vault.
+        for key_id in 0..KeyVault::KEY_COUNT {
+            let clock = Clock::new();
+            let seed = [0u8; 48];
+
+            let mut key_vault = KeyVault::new();
+            let mut key_usage = KeyUsage::default();
+            key_usage.set_ecc_key_gen_seed(true);
+
+            key_vault
+                .write_key(key_id, &seed, u32::from(key_usage))
+                .unwrap();
+
+            let mut ml_dsa87 = MlDsa87::new(&clock, key_vault);
+
+            // Instruct seed to be read from key-vault.
+            let seed_ctrl = InMemoryRegister::<u32, KeyReadControl::Register>::new(0);
+            seed_ctrl
+                .modify(KeyReadControl::KEY_ID.val(key_id) + KeyReadControl::KEY_READ_EN.val(1));
+
+            assert_eq!(
+                ml_dsa87
+                    .write(RvSize::Word, OFFSET_SEED_CONTROL, seed_ctrl.get())
+                    .ok(),
+                Some(())
+            );
+
+            // Wait for the periph to retrieve the seed from key-vault.
+            loop {
+                let seed_read_status = InMemoryRegister::<u32, KeyReadStatus::Register>::new(
+                    ml_dsa87.read(RvSize::Word, OFFSET_SEED_STATUS).unwrap(),
+                );
+
+                if seed_read_status.is_set(KeyReadStatus::VALID) {
+                    assert_eq!(
+                        seed_read_status.read(KeyReadStatus::ERROR),
+                        KeyReadStatus::ERROR::KV_SUCCESS.value
+                    );
+                    break;
+                }
+                clock.increment_and_process_timer_actions(1, &mut ml_dsa87);
+            }
+
+            assert_eq!(
+                ml_dsa87
+                    .write(RvSize::Word, OFFSET_CONTROL, Control::CTRL::GEN_KEY.into())
+                    .ok(),
+                Some(())
+            );
+
+            loop {
+                let status = InMemoryRegister::<u32, Status::Register>::new(
+                    ml_dsa87.read(RvSize::Word, OFFSET_STATUS).unwrap(),
+                );
+                if status.is_set(Status::VALID) && status.is_set(Status::READY) {
+                    break;
+                }
+                clock.increment_and_process_timer_actions(1, &mut ml_dsa87);
+            }
+
+            let mut secret_key = bytes_from_words_le(&ml_dsa87.sk_out);
+            secret_key.to_little_endian(); // Change DWORDs to little-endian.
+
+            let mut public_key = bytes_from_words_le(&ml_dsa87.pk);
+            public_key.to_little_endian(); // Change DWORDs to little-endian.
+
+            assert_eq!(&secret_key, &SECRET_KEY);
+            assert_eq!(&public_key, &PUB_KEY);
+        }
+    }
+
+    #[test]
+    fn test_kv_seed_no_usage() {
+        let clock = Clock::new();
+        let mut key_vault = KeyVault::new();
+        key_vault.write_key(0, &[0u8; 48], 0).unwrap();
+
+        let mut ml_dsa87 = MlDsa87::new(&clock, key_vault);
+
+        let seed_ctrl = InMemoryRegister::<u32, KeyReadControl::Register>::new(0);
+        seed_ctrl.modify(KeyReadControl::KEY_ID.val(0) + KeyReadControl::KEY_READ_EN.val(1));
+        assert_eq!(
+            ml_dsa87
+                .write(RvSize::Word, OFFSET_SEED_CONTROL, seed_ctrl.get())
+                .ok(),
+            Some(())
+        );
+
+        loop {
+            let seed_read_status = InMemoryRegister::<u32, KeyReadStatus::Register>::new(
+                ml_dsa87.read(RvSize::Word, OFFSET_SEED_STATUS).unwrap(),
+            );
+
+            if seed_read_status.is_set(KeyReadStatus::VALID) {
+                assert_eq!(
+                    seed_read_status.read(KeyReadStatus::ERROR),
+                    KeyReadStatus::ERROR::KV_READ_FAIL.value
+                );
+                break;
+            }
+            clock.increment_and_process_timer_actions(1, &mut ml_dsa87);
+        }
+    }
+
     #[test]
     fn test_sign() {
         let clock = Clock::new();
 
-        let mut ml_dsa87 = MlDsa87::new(&clock);
+        let mut ml_dsa87 = MlDsa87::new(&clock, KeyVault::new());
 
         let mut seed = [0u8; 32];
         seed.to_big_endian(); // Change DWORDs to big-endian. TODO is this needed?
@@ -515,7 +868,7 @@ mod tests {
     fn test_verify() {
         let clock = Clock::new();
 
-        let mut ml_dsa87 = MlDsa87::new(&clock);
+        let mut ml_dsa87 = MlDsa87::new(&clock, KeyVault::new());
 
         let msg = [0u8; 64];
         for i in (0..msg.len()).step_by(4) {
@@ -625,4 +978,105 @@ mod tests {
 
         assert_eq!(&result, &[0; 64]);
     }
+
+    #[test]
+    fn test_stream_msg() {
+        let clock = Clock::new();
+
+        let mut ml_dsa87 = MlDsa87::new(&clock, KeyVault::new());
+
+        let mut rng = StdRng::from_seed([0x5a; 32]);
+        let (pub_key, secret_key) = try_keygen_with_rng(&mut rng).unwrap();
+        let secret_key = secret_key.into_bytes();
+        let msg = b"message of an unaligned length";
+
+        let stream = |ml_dsa87: &mut MlDsa87, ctrl: u32| {
+            assert_eq!(
+                ml_dsa87
+                    .write(
+                        RvSize::Word,
+                        OFFSET_CONTROL,
+                        ctrl | u32::from(Control::STREAM_MSG::SET)
+                    )
+                    .ok(),
+                Some(())
+            );
+            let status = InMemoryRegister::<u32, Status::Register>::new(
+                ml_dsa87.read(RvSize::Word, OFFSET_STATUS).unwrap(),
+            );
+            assert!(status.is_set(Status::MSG_STREAM_READY));
+
+            let mut chunks = msg.chunks(4).peekable();
+            while let Some(chunk) = chunks.next() {
+                let mut word = [0u8; 4];
+                word[..chunk.len()].copy_from_slice(chunk);
+                if chunks.peek().is_none() {
+                    let strobe = (1 << chunk.len()) - 1;
+                    assert_eq!(
+                        ml_dsa87.write(RvSize::Word, OFFSET_MSG_STROBE, strobe).ok(),
+                        Some(())
+                    );
+                }
+                assert_eq!(
+                    ml_dsa87
+                        .write(RvSize::Word, OFFSET_MSG, u32::from_le_bytes(word))
+                        .ok(),
+                    Some(())
+                );
+            }
+
+            loop {
+                let status = InMemoryRegister::<u32, Status::Register>::new(
+                    ml_dsa87.read(RvSize::Word, OFFSET_STATUS).unwrap(),
+                );
+
+                if status.is_set(Status::VALID) && status.is_set(Status::READY) {
+                    assert!(!status.is_set(Status::MSG_STREAM_READY));
+                    break;
+                }
+
+                clock.increment_and_process_timer_actions(1, ml_dsa87);
+            }
+        };
+
+        // Sign the streamed message
+        for i in (0..secret_key.len()).step_by(4) {
+            assert_eq!(
+                ml_dsa87
+                    .write(
+                        RvSize::Word,
+                        OFFSET_SK_IN + i as RvAddr,
+                        make_word(i, &secret_key)
+                    )
+                    .ok(),
+                Some(())
+            );
+        }
+        stream(&mut ml_dsa87, Control::CTRL::SIGN.into());
+
+        let signature: [u8; SIG_LEN] = bytes_from_words_le(&ml_dsa87.signature)[..SIG_LEN]
+            .try_into()
+            .unwrap();
+        assert!(pub_key.verify(msg, &signature));
+
+        // Verify the streamed message
+        let pub_key = pub_key.into_bytes();
+        for i in (0..pub_key.len()).step_by(4) {
+            assert_eq!(
+                ml_dsa87
+                    .write(
+                        RvSize::Word,
+                        OFFSET_PK + i as RvAddr,
+                        make_word(i, &pub_key)
+                    )
+                    .ok(),
+                Some(())
+            );
+        }
+        stream(&mut ml_dsa87, Control::CTRL::VERIFY.into());
+
+        let mut result = bytes_from_words_le(&ml_dsa87.verification_result);
+        result.to_little_endian();
+        assert_eq!(&result, &VERIFICATION_SUCCES);
+    }
 }
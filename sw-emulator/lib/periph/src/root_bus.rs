@@ -20,10 +20,12 @@ use crate::{
     iccm::Iccm,
     soc_reg::{DebugManufService, SocRegistersExternal},
     AsymEcc384, Csrng, Doe, EmuCtrl, HashSha256, HashSha512, HmacSha384, KeyVault, MailboxExternal,
-    MailboxInternal, MailboxRam, Sha512Accelerator, SocRegistersInternal, Uart,
+    MailboxInternal, MailboxRam, PeriphLatencies, Sha512Accelerator, SocRegistersInternal, Uart,
 };
 use caliptra_api_types::SecurityState;
-use caliptra_emu_bus::{Clock, Ram, Rom};
+use caliptra_emu_bus::{
+    Clock, EventTrace, Ram, Rom, Snapshot, SnapshotError, SnapshotReader, SnapshotWriter,
+};
 use caliptra_emu_cpu::{Pic, PicMmioRegisters};
 use caliptra_emu_derive::Bus;
 use caliptra_hw_model_types::{EtrngResponse, RandomEtrngResponses, RandomNibbles};
@@ -227,6 +229,9 @@ pub struct CaliptraRootBusArgs {
 
     pub itrng_nibbles: Option<Box<dyn Iterator<Item = u8>>>,
     pub etrng_responses: Box<dyn Iterator<Item = EtrngResponse>>,
+
+    /// Time taken by cryptographic peripheral operations
+    pub latencies: PeriphLatencies,
 }
 impl Default for CaliptraRootBusArgs {
     fn default() -> Self {
@@ -242,6 +247,7 @@ impl Default for CaliptraRootBusArgs {
             cptra_obf_key: words_from_bytes_be(&DEFAULT_DOE_KEY),
             itrng_nibbles: Some(Box::new(RandomNibbles::new_from_thread_rng())),
             etrng_responses: Box::new(RandomEtrngResponses::new_from_stdrng()),
+            latencies: Default::default(),
         }
     }
 }
@@ -307,7 +313,11 @@ pub struct CaliptraRootBus {
 impl CaliptraRootBus {
     pub const ROM_SIZE: usize = 48 * 1024;
     pub const ICCM_SIZE: usize = 128 * 1024;
+    #[cfg(not(feature = "hw-2.x"))]
     pub const DCCM_SIZE: usize = 128 * 1024;
+    // Caliptra 2.x doubles the DCCM
+    #[cfg(feature = "hw-2.x")]
+    pub const DCCM_SIZE: usize = 256 * 1024;
 
     pub fn new(clock: &Clock, mut args: CaliptraRootBusArgs) -> Self {
         let mut key_vault = KeyVault::new();
@@ -317,6 +327,7 @@ impl CaliptraRootBus {
         let iccm = Iccm::new(clock);
         let pic = Pic::new();
         let itrng_nibbles = args.itrng_nibbles.take();
+        let latencies = args.latencies;
         let soc_reg = SocRegistersInternal::new(clock, mailbox.clone(), iccm.clone(), &pic, args);
         if !soc_reg.is_debug_locked() {
             // When debug is possible, the key-vault is initialized with a debug value...
@@ -324,9 +335,10 @@ impl CaliptraRootBus {
             key_vault.clear_keys_with_debug_values(false);
         }
 
-        let sha512 = HashSha512::new(clock, key_vault.clone());
+        let mut sha512 = HashSha512::new(clock, key_vault.clone());
+        sha512.set_latencies(latencies);
 
-        Self {
+        let mut result = Self {
             rom,
             doe: Doe::new(clock, key_vault.clone(), soc_reg.clone()),
             ecc384: AsymEcc384::new(clock, key_vault.clone(), sha512.clone()),
@@ -335,7 +347,7 @@ impl CaliptraRootBus {
             sha512,
             sha256: HashSha256::new(clock),
             #[cfg(feature = "hw-2.x")]
-            ml_dsa87: MlDsa87::new(clock),
+            ml_dsa87: MlDsa87::new(clock, key_vault.clone()),
             iccm,
             dccm: Ram::new(vec![0; Self::DCCM_SIZE]),
             uart: Uart::new(),
@@ -346,7 +358,15 @@ impl CaliptraRootBus {
             sha512_acc: Sha512Accelerator::new(clock, mailbox_ram),
             csrng: Csrng::new(itrng_nibbles.unwrap()),
             pic_regs: pic.mmio_regs(clock),
-        }
+        };
+        result.doe.set_latencies(latencies);
+        result.ecc384.set_latencies(latencies);
+        result.hmac.set_latencies(latencies);
+        result.sha256.set_latencies(latencies);
+        #[cfg(feature = "hw-2.x")]
+        result.ml_dsa87.set_latencies(latencies);
+        result.sha512_acc.set_latencies(latencies);
+        result
     }
 
     pub fn soc_to_caliptra_bus(&self, soc_user: MailboxRequester) -> SocToCaliptraBus {
@@ -356,6 +376,59 @@ impl CaliptraRootBus {
             soc_ifc: self.soc_reg.external_regs(),
         }
     }
+
+    /// Record mailbox state transitions and key vault writes to
+    /// `event_trace`. `None` stops recording.
+    pub fn set_event_trace(&mut self, event_trace: Option<EventTrace>) {
+        self.key_vault.set_event_trace(event_trace.clone());
+        self.mailbox.set_event_trace(event_trace);
+    }
+}
+
+/// Saves the state of every peripheral except the ROM, which is fixed when the
+/// bus is constructed. Hash and crypto engines only have their registers
+/// saved, so a snapshot should be taken while firmware is idle (for example
+/// waiting for a mailbox command) and not in the middle of an operation.
+impl Snapshot for CaliptraRootBus {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.doe.save(w);
+        self.ecc384.save(w);
+        self.hmac.save(w);
+        self.key_vault.save(w);
+        self.sha512.save(w);
+        self.sha256.save(w);
+        #[cfg(feature = "hw-2.x")]
+        self.ml_dsa87.save(w);
+        self.iccm.save(w);
+        self.uart.save(w);
+        self.csrng.save(w);
+        self.mailbox_sram.save(w);
+        self.mailbox.save(w);
+        self.sha512_acc.save(w);
+        self.soc_reg.save(w);
+        self.dccm.save(w);
+        self.pic_regs.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.doe.restore(r)?;
+        self.ecc384.restore(r)?;
+        self.hmac.restore(r)?;
+        self.key_vault.restore(r)?;
+        self.sha512.restore(r)?;
+        self.sha256.restore(r)?;
+        #[cfg(feature = "hw-2.x")]
+        self.ml_dsa87.restore(r)?;
+        self.iccm.restore(r)?;
+        self.uart.restore(r)?;
+        self.csrng.restore(r)?;
+        self.mailbox_sram.restore(r)?;
+        self.mailbox.restore(r)?;
+        self.sha512_acc.restore(r)?;
+        self.soc_reg.restore(r)?;
+        self.dccm.restore(r)?;
+        self.pic_regs.restore(r)
+    }
 }
 
 #[derive(Bus)]
@@ -373,6 +446,8 @@ pub struct SocToCaliptraBus {
 #[cfg(test)]
 mod tests {
     use crate::KeyUsage;
+    use caliptra_emu_bus::Bus;
+    use caliptra_emu_types::RvSize;
 
     use super::*;
 
@@ -437,4 +512,40 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn test_snapshot_restore() {
+        let clock = Clock::new();
+        let mut root_bus = CaliptraRootBus::new(&clock, CaliptraRootBusArgs::default());
+        let mut key_usage = KeyUsage::default();
+        key_usage.set_hmac_key(true);
+        root_bus
+            .key_vault
+            .write_key(1, &[0x5a; 48], key_usage.into())
+            .unwrap();
+        root_bus.dccm.data_mut()[0x100] = 0xa5;
+        root_bus
+            .mailbox_sram
+            .write(RvSize::Word, 0, 0x1234_5678)
+            .unwrap();
+
+        let mut w = SnapshotWriter::new();
+        root_bus.save(&mut w);
+        let snapshot = w.into_bytes();
+
+        let mut restored = CaliptraRootBus::new(&Clock::new(), CaliptraRootBusArgs::default());
+        let mut r = SnapshotReader::new(&snapshot);
+        restored.restore(&mut r).unwrap();
+        r.finish().unwrap();
+
+        assert_eq!(
+            restored.key_vault.read_key(1, key_usage).unwrap(),
+            [0x5a; 48]
+        );
+        assert_eq!(restored.dccm.data()[0x100], 0xa5);
+        assert_eq!(
+            restored.mailbox_sram.read(RvSize::Word, 0).unwrap(),
+            0x1234_5678
+        );
+    }
 }
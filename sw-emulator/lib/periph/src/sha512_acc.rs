@@ -12,8 +12,10 @@ Abstract:
 
 --*/
 use crate::MailboxRam;
+use crate::PeriphLatencies;
 use caliptra_emu_bus::{
-    ActionHandle, Bus, BusError, Clock, ReadOnlyMemory, ReadOnlyRegister, ReadWriteRegister, Timer,
+    ActionHandle, Bus, BusError, Clock, ReadOnlyMemory, ReadOnlyRegister, ReadWriteRegister,
+    Snapshot, SnapshotError, SnapshotReader, SnapshotWriter, Timer,
 };
 use caliptra_emu_crypto::{EndianessTransform, Sha512, Sha512Mode};
 use caliptra_emu_derive::Bus;
@@ -31,9 +33,6 @@ const MAX_MAILBOX_CAPACITY_WORDS: usize = (128 << 10) >> 2;
 /// Maximum mailbox capacity in bytes.
 const MAX_MAILBOX_CAPACITY_BYTES: usize = MAX_MAILBOX_CAPACITY_WORDS * RvSize::Word as usize;
 
-/// The number of CPU clock cycles it takes to perform sha operation.
-const SHA_ACC_OP_TICKS: u64 = 1000;
-
 const SHA512_BLOCK_SIZE: usize = 128;
 const SHA512_HASH_SIZE: usize = 64;
 
@@ -135,6 +134,9 @@ pub struct Sha512AcceleratorRegs {
     /// Timer
     timer: Timer,
 
+    /// Operation latencies
+    latencies: PeriphLatencies,
+
     /// State Machine
     state_machine: StateMachine<Context>,
 
@@ -153,6 +155,7 @@ impl Sha512AcceleratorRegs {
             hash_upper: ReadOnlyMemory::new(),
             mailbox_ram,
             timer: Timer::new(clock),
+            latencies: PeriphLatencies::default(),
             _lock: ReadWriteRegister::new(0),
             user: ReadOnlyRegister::new(0),
             dlen: ReadWriteRegister::new(0),
@@ -357,7 +360,8 @@ impl Sha512AcceleratorRegs {
                 self.compute_mbox_hash();
 
                 // Schedule a future call to poll() complete the operation.
-                self.op_complete_action = Some(self.timer.schedule_poll_in(SHA_ACC_OP_TICKS));
+                self.op_complete_action =
+                    Some(self.timer.schedule_poll_in(self.latencies.sha512_acc_op));
             } else if mode == ShaMode::MODE::SHA512_ACC_MODE_SHA_STREAM_384.value
                 || mode == ShaMode::MODE::SHA512_ACC_MODE_SHA_STREAM_512.value
             {
@@ -556,6 +560,11 @@ impl Sha512Accelerator {
             regs: Rc::new(RefCell::new(Sha512AcceleratorRegs::new(clock, mailbox_ram))),
         }
     }
+
+    /// Set the time taken by each operation
+    pub fn set_latencies(&mut self, latencies: PeriphLatencies) {
+        self.regs.borrow_mut().latencies = latencies;
+    }
 }
 
 impl Bus for Sha512Accelerator {
@@ -582,6 +591,63 @@ impl Bus for Sha512Accelerator {
     }
 }
 
+impl Snapshot for Sha512Accelerator {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.regs.borrow().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.regs.borrow_mut().restore(r)
+    }
+}
+
+/// The mailbox SRAM is saved by its owner. The state of a hash that is still
+/// being streamed is not saved, so snapshots must be taken between operations.
+impl Snapshot for Sha512AcceleratorRegs {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self._lock.save(w);
+        self.user.save(w);
+        self.mode.save(w);
+        self.start_address.save(w);
+        self.dlen.save(w);
+        self.data_in.save(w);
+        self.execute.save(w);
+        self.status.save(w);
+        self.hash_lower.save(w);
+        self.hash_upper.save(w);
+        self.control.save(w);
+        matches!(self.state_machine.state(), States::RdyForExc).save(w);
+        self.state_machine.context.locked.save(w);
+        self.state_machine.context.user.save(w);
+        self.op_complete_action.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self._lock.restore(r)?;
+        self.user.restore(r)?;
+        self.mode.restore(r)?;
+        self.start_address.restore(r)?;
+        self.dlen.restore(r)?;
+        self.data_in.restore(r)?;
+        self.execute.restore(r)?;
+        self.status.restore(r)?;
+        self.hash_lower.restore(r)?;
+        self.hash_upper.restore(r)?;
+        self.control.restore(r)?;
+        let state = if r.read::<bool>()? {
+            States::RdyForExc
+        } else {
+            States::Idle
+        };
+        let context = Context {
+            locked: r.read()?,
+            user: r.read()?,
+        };
+        self.state_machine = StateMachine::new_with_state(context, state);
+        self.op_complete_action.restore(r)
+    }
+}
+
 pub struct Owner(pub u32);
 
 statemachine! {
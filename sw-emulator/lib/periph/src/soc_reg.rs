@@ -18,8 +18,8 @@ use crate::root_bus::ReadyForFwCbArgs;
 use crate::{CaliptraRootBusArgs, Iccm, MailboxInternal};
 use caliptra_emu_bus::BusError::{LoadAccessFault, StoreAccessFault};
 use caliptra_emu_bus::{
-    ActionHandle, Bus, BusError, Clock, ReadOnlyRegister, ReadWriteRegister, Register, Timer,
-    TimerAction,
+    ActionHandle, Bus, BusError, Clock, ReadOnlyRegister, ReadWriteRegister, Register, Snapshot,
+    SnapshotError, SnapshotReader, SnapshotWriter, Timer, TimerAction,
 };
 use caliptra_emu_cpu::{IntSource, Irq, Pic};
 use caliptra_emu_derive::Bus;
@@ -440,6 +440,16 @@ impl Bus for SocRegistersInternal {
     }
 }
 
+impl Snapshot for SocRegistersInternal {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.regs.borrow().save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.regs.borrow_mut().restore(r)
+    }
+}
+
 pub struct SocRegistersExternal {
     regs: Rc<RefCell<SocRegistersImpl>>,
 }
@@ -762,6 +772,184 @@ struct SocRegistersImpl {
     op_pending_etrng_response_action: Option<ActionHandle>,
 }
 
+/// The mailbox and ICCM are saved by the root bus, and IRQ levels by the PIC.
+/// Callbacks and the queue of future eTRNG responses come from the machine
+/// configuration and are not saved.
+impl Snapshot for SocRegistersImpl {
+    fn save(&self, w: &mut SnapshotWriter) {
+        self.cptra_hw_error_fatal.save(w);
+        self.cptra_hw_error_non_fatal.save(w);
+        self.cptra_fw_error_fatal.save(w);
+        self.cptra_fw_error_non_fatal.save(w);
+        self.cptra_hw_error_enc.save(w);
+        self.cptra_fw_error_enc.save(w);
+        self.cptra_fw_extended_error_info.save(w);
+        self.cptra_boot_status.save(w);
+        self.cptra_flow_status.save(w);
+        self.cptra_reset_reason.save(w);
+        self.cptra_security_state.save(w);
+        self.cptra_mbox_valid_pauser.save(w);
+        self.cptra_mbox_pauser_lock.save(w);
+        self.cptra_trng_valid_pauser.save(w);
+        self.cptra_trng_pauser_lock.save(w);
+        self.cptra_trng_data.save(w);
+        self.cptra_trng_ctrl.save(w);
+        self.cptra_trng_status.save(w);
+        self.cptra_fuse_wr_done.save(w);
+        self.cptra_timer_config.save(w);
+        self.cptra_bootfsm_go.save(w);
+        self.cptra_dbg_manuf_service_reg.save(w);
+        self.cptra_clk_gating_en.save(w);
+        self.cptra_generic_input_wires.save(w);
+        self.cptra_generic_output_wires.save(w);
+        self.cptra_hw_rev_id.save(w);
+        self.cptra_fw_rev_id.save(w);
+        self.cptra_hw_config.save(w);
+        self.cptra_wdt_timer1_en.save(w);
+        self.cptra_wdt_timer1_ctrl.save(w);
+        self.cptra_wdt_timer1_timeout_period.save(w);
+        self.cptra_wdt_timer2_en.save(w);
+        self.cptra_wdt_timer2_ctrl.save(w);
+        self.cptra_wdt_timer2_timeout_period.save(w);
+        self.cptra_wdt_status.save(w);
+        self.cptra_fuse_valid_pauser.save(w);
+        self.cptra_fuse_pauser_lock.save(w);
+        self.cptra_i_trng_entropy_config_0.save(w);
+        self.cptra_i_trng_entropy_config_1.save(w);
+        self.cptra_rsvd_reg.save(w);
+        self.fuse_uds_seed.save(w);
+        self.cptra_wdt_cfg.save(w);
+        self.fuse_field_entropy.save(w);
+        self.fuse_vendor_pk_hash.save(w);
+        self.fuse_vendor_pk_hash_mask.save(w);
+        self.fuse_owner_pk_hash.save(w);
+        self.fuse_fmc_svn.save(w);
+        self.fuse_runtime_svn.save(w);
+        self.fuse_anti_rollback_disable.save(w);
+        self.fuse_idevid_cert_attr.save(w);
+        self.fuse_idevid_manuf_hsm_id.save(w);
+        self.fuse_life_cycle.save(w);
+        self.fuse_lms_verify.save(w);
+        self.fuse_lms_revocation.save(w);
+        self.fuse_soc_stepping_id.save(w);
+        self.internal_obf_key.save(w);
+        self.internal_iccm_lock.save(w);
+        self.internal_fw_update_reset.save(w);
+        self.internal_fw_update_reset_wait_cycles.save(w);
+        self.internal_nmi_vector.save(w);
+        self.global_intr_en_r.save(w);
+        self.error_intr_en_r.save(w);
+        self.notif_intr_en_r.save(w);
+        self.error_global_intr_r.save(w);
+        self.notif_global_intr_r.save(w);
+        self.error_internal_intr_r.save(w);
+        self.notif_internal_intr_r.save(w);
+        self.error_intr_trig_r.save(w);
+        self.notif_intr_trig_r.save(w);
+        self.op_fw_write_complete_action.save(w);
+        self.op_fw_read_complete_action.save(w);
+        self.op_idevid_csr_read_complete_action.save(w);
+        self.op_reset_trigger_action.save(w);
+        self.fuses_can_be_written.save(w);
+        self.op_wdt_timer1_expired_action.save(w);
+        self.op_wdt_timer2_expired_action.save(w);
+        self.pending_etrng_response.is_some().save(w);
+        if let Some(response) = &self.pending_etrng_response {
+            response.delay.save(w);
+            response.data.save(w);
+        }
+        self.op_pending_etrng_response_action.save(w);
+    }
+
+    fn restore(&mut self, r: &mut SnapshotReader) -> Result<(), SnapshotError> {
+        self.cptra_hw_error_fatal.restore(r)?;
+        self.cptra_hw_error_non_fatal.restore(r)?;
+        self.cptra_fw_error_fatal.restore(r)?;
+        self.cptra_fw_error_non_fatal.restore(r)?;
+        self.cptra_hw_error_enc.restore(r)?;
+        self.cptra_fw_error_enc.restore(r)?;
+        self.cptra_fw_extended_error_info.restore(r)?;
+        self.cptra_boot_status.restore(r)?;
+        self.cptra_flow_status.restore(r)?;
+        self.cptra_reset_reason.restore(r)?;
+        self.cptra_security_state.restore(r)?;
+        self.cptra_mbox_valid_pauser.restore(r)?;
+        self.cptra_mbox_pauser_lock.restore(r)?;
+        self.cptra_trng_valid_pauser.restore(r)?;
+        self.cptra_trng_pauser_lock.restore(r)?;
+        self.cptra_trng_data.restore(r)?;
+        self.cptra_trng_ctrl.restore(r)?;
+        self.cptra_trng_status.restore(r)?;
+        self.cptra_fuse_wr_done.restore(r)?;
+        self.cptra_timer_config.restore(r)?;
+        self.cptra_bootfsm_go.restore(r)?;
+        self.cptra_dbg_manuf_service_reg.restore(r)?;
+        self.cptra_clk_gating_en.restore(r)?;
+        self.cptra_generic_input_wires.restore(r)?;
+        self.cptra_generic_output_wires.restore(r)?;
+        self.cptra_hw_rev_id.restore(r)?;
+        self.cptra_fw_rev_id.restore(r)?;
+        self.cptra_hw_config.restore(r)?;
+        self.cptra_wdt_timer1_en.restore(r)?;
+        self.cptra_wdt_timer1_ctrl.restore(r)?;
+        self.cptra_wdt_timer1_timeout_period.restore(r)?;
+        self.cptra_wdt_timer2_en.restore(r)?;
+        self.cptra_wdt_timer2_ctrl.restore(r)?;
+        self.cptra_wdt_timer2_timeout_period.restore(r)?;
+        self.cptra_wdt_status.restore(r)?;
+        self.cptra_fuse_valid_pauser.restore(r)?;
+        self.cptra_fuse_pauser_lock.restore(r)?;
+        self.cptra_i_trng_entropy_config_0.restore(r)?;
+        self.cptra_i_trng_entropy_config_1.restore(r)?;
+        self.cptra_rsvd_reg.restore(r)?;
+        self.fuse_uds_seed.restore(r)?;
+        self.cptra_wdt_cfg.restore(r)?;
+        self.fuse_field_entropy.restore(r)?;
+        self.fuse_vendor_pk_hash.restore(r)?;
+        self.fuse_vendor_pk_hash_mask.restore(r)?;
+        self.fuse_owner_pk_hash.restore(r)?;
+        self.fuse_fmc_svn.restore(r)?;
+        self.fuse_runtime_svn.restore(r)?;
+        self.fuse_anti_rollback_disable.restore(r)?;
+        self.fuse_idevid_cert_attr.restore(r)?;
+        self.fuse_idevid_manuf_hsm_id.restore(r)?;
+        self.fuse_life_cycle.restore(r)?;
+        self.fuse_lms_verify.restore(r)?;
+        self.fuse_lms_revocation.restore(r)?;
+        self.fuse_soc_stepping_id.restore(r)?;
+        self.internal_obf_key.restore(r)?;
+        self.internal_iccm_lock.restore(r)?;
+        self.internal_fw_update_reset.restore(r)?;
+        self.internal_fw_update_reset_wait_cycles.restore(r)?;
+        self.internal_nmi_vector.restore(r)?;
+        self.global_intr_en_r.restore(r)?;
+        self.error_intr_en_r.restore(r)?;
+        self.notif_intr_en_r.restore(r)?;
+        self.error_global_intr_r.restore(r)?;
+        self.notif_global_intr_r.restore(r)?;
+        self.error_internal_intr_r.restore(r)?;
+        self.notif_internal_intr_r.restore(r)?;
+        self.error_intr_trig_r.restore(r)?;
+        self.notif_intr_trig_r.restore(r)?;
+        self.op_fw_write_complete_action.restore(r)?;
+        self.op_fw_read_complete_action.restore(r)?;
+        self.op_idevid_csr_read_complete_action.restore(r)?;
+        self.op_reset_trigger_action.restore(r)?;
+        self.fuses_can_be_written.restore(r)?;
+        self.op_wdt_timer1_expired_action.restore(r)?;
+        self.op_wdt_timer2_expired_action.restore(r)?;
+        self.pending_etrng_response = if r.read::<bool>()? {
+            Some(EtrngResponse {
+                delay: r.read()?,
+                data: r.read()?,
+            })
+        } else {
+            None
+        };
+        self.op_pending_etrng_response_action.restore(r)
+    }
+}
+
 impl SocRegistersImpl {
     /// Default unique device secret
     const UDS: [u8; FUSE_UDS_SEED_SIZE] = [
@@ -12,7 +12,7 @@ Abstract:
 
 --*/
 
-use caliptra_emu_bus::{Bus, BusError};
+use caliptra_emu_bus::{impl_snapshot, Bus, BusError};
 use caliptra_emu_types::{RvAddr, RvData, RvSize};
 
 pub struct Uart {
@@ -50,6 +50,13 @@ impl Uart {
         256
     }
 }
+
+impl_snapshot!(Uart {
+    bit_rate,
+    data_bits,
+    stop_bits,
+});
+
 impl Default for Uart {
     fn default() -> Self {
         Self::new()
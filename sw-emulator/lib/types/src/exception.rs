@@ -43,6 +43,9 @@ emu_enum! {
         /// Store access fault exception
         StoreAccessFault = 7,
 
+        /// Environment Call from User mode
+        UserEnvironmentCall = 8,
+
         /// Environment Call
         EnvironmentCall = 11,
 
@@ -113,6 +116,11 @@ impl RvException {
         RvException::new(RvExceptionCause::EnvironmentCall, 0)
     }
 
+    /// Create a new environment call from user mode exception
+    pub fn user_environment_call() -> Self {
+        RvException::new(RvExceptionCause::UserEnvironmentCall, 0)
+    }
+
     /// Returns the exception cause
     pub fn cause(&self) -> RvExceptionCause {
         self.cause
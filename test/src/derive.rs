@@ -305,6 +305,8 @@ pub struct Pcr0Input {
     pub fmc_fuse_svn: u32,
     pub lms_vendor_pub_key_index: u32,
     pub rom_verify_config: u32,
+    pub pqc_key_type: u32,
+    pub mldsa_vendor_pub_key_index: u32,
 }
 impl Pcr0Input {}
 
@@ -329,6 +331,8 @@ impl Pcr0 {
                 input.lms_vendor_pub_key_index as u8,
                 input.rom_verify_config as u8,
                 input.owner_pub_key_hash_from_fuses as u8,
+                input.pqc_key_type as u8,
+                input.mldsa_vendor_pub_key_index as u8,
             ],
         );
         extend(
@@ -372,12 +376,14 @@ fn test_derive_pcr0() {
         fmc_fuse_svn: 2,
         lms_vendor_pub_key_index: u32::MAX,
         rom_verify_config: 1, // RomVerifyConfig::EcdsaAndLms
+        pqc_key_type: 0,      // ImagePqcKeyType::Lms
+        mldsa_vendor_pub_key_index: u32::MAX,
     });
     assert_eq!(
         pcr0,
         Pcr0([
-            132444429, 987394663, 2275449643, 2729083116, 322701188, 1268620383, 68534608,
-            1944581982, 1207702945, 1427901733, 3489811836, 435454516
+            123619744, 3965127125, 1524578981, 4169320422, 1807447379, 2163982761, 360516372,
+            773178029, 1657689238, 2563424230, 482486554, 2196999481
         ])
     )
 }
@@ -0,0 +1,251 @@
+// Licensed under the Apache-2.0 license
+
+//! Fault-injection campaigns against the emulator.
+//!
+//! A campaign boots the same ROM and firmware image once per fault, with a
+//! single fault injected into each boot. The image is expected to be
+//! rejected; any fault that gets it accepted is a successful attack.
+
+use std::fmt;
+use std::ops::Range;
+
+use caliptra_api::soc_mgr::SocManager;
+use caliptra_hw_model::{
+    BootParams, Fault, FaultEffect, FaultInjector, FaultTrigger, Fuses, HwModel, InitParams,
+    ModelEmulated, ModelError,
+};
+use caliptra_hw_model_types::{RandomEtrngResponses, RandomNibbles};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// Result of booting with a single injected fault
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum FaultOutcome {
+    /// The ROM rejected the image, reporting this error code
+    Rejected(u32),
+
+    /// The ROM accepted the image but the runtime did not become ready
+    Accepted,
+
+    /// The ROM accepted the image and the runtime became ready
+    Booted,
+
+    /// Neither the ROM nor the runtime finished within the cycle limit
+    Hang,
+
+    /// Execution never reached the fault
+    NotTriggered,
+}
+
+impl FaultOutcome {
+    /// Returns true if the fault got the image past verification
+    pub fn is_bypass(&self) -> bool {
+        matches!(self, Self::Accepted | Self::Booted)
+    }
+}
+
+/// Boots a firmware image under a series of single-fault scenarios
+pub struct FaultCampaign<'a> {
+    pub rom: &'a [u8],
+
+    /// Image the ROM is expected to reject
+    pub fw_image: &'a [u8],
+
+    pub fuses: Fuses,
+
+    /// Seed for the TRNG, so every boot follows the same path until the
+    /// fault is injected
+    pub trng_seed: u64,
+
+    /// Cycles to wait for each boot stage before giving up
+    pub max_cycles: u64,
+}
+
+/// Instructions the reference boot executed before and during firmware
+/// upload
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReferenceBoot {
+    pub outcome: FaultOutcome,
+
+    /// Instruction counts, as used by `FaultTrigger::InstrCount`, spent
+    /// handling the firmware upload
+    pub upload_instrs: Range<u64>,
+}
+
+impl<'a> FaultCampaign<'a> {
+    /// Boot without injecting a fault
+    pub fn reference_boot(&self) -> ReferenceBoot {
+        let mut model = self.new_model(FaultInjector::new(vec![]));
+        let mut upload_start = 0;
+        let outcome = self.boot(&mut model, |model| {
+            upload_start = instr_count(model);
+        });
+        ReferenceBoot {
+            outcome,
+            upload_instrs: upload_start..instr_count(&model),
+        }
+    }
+
+    /// Boot with `fault` injected
+    pub fn run(&self, fault: Fault) -> FaultOutcome {
+        let mut model = self.new_model(FaultInjector::new(vec![fault]));
+        let outcome = self.boot(&mut model, |_| {});
+        match model.fault_injector() {
+            Some(injector) if injector.injected().is_empty() => FaultOutcome::NotTriggered,
+            _ => outcome,
+        }
+    }
+
+    /// Boot once for each of `faults`
+    pub fn run_all(&self, faults: impl IntoIterator<Item = Fault>) -> CampaignReport {
+        CampaignReport {
+            results: faults
+                .into_iter()
+                .map(|fault| (fault, self.run(fault)))
+                .collect(),
+        }
+    }
+
+    fn new_model(&self, injector: FaultInjector) -> ModelEmulated {
+        let rng = || StdRng::seed_from_u64(self.trng_seed);
+        let mut model = ModelEmulated::new_unbooted(InitParams {
+            rom: self.rom,
+            log_writer: Box::new(std::io::sink()),
+            itrng_nibbles: Box::new(RandomNibbles(rng())),
+            etrng_responses: Box::new(RandomEtrngResponses(rng())),
+            random_sram_puf: false,
+            ..Default::default()
+        })
+        .unwrap();
+        model.install_fault_injector(injector);
+        model
+    }
+
+    fn boot(
+        &self,
+        model: &mut ModelEmulated,
+        before_upload: impl FnOnce(&mut ModelEmulated),
+    ) -> FaultOutcome {
+        model
+            .boot(BootParams {
+                fuses: self.fuses.clone(),
+                ..Default::default()
+            })
+            .unwrap();
+        if !step_until(model, self.max_cycles, |m| m.ready_for_fw()) {
+            return fatal_error(model).map_or(FaultOutcome::Hang, FaultOutcome::Rejected);
+        }
+
+        before_upload(model);
+        match model.upload_firmware(self.fw_image) {
+            Ok(()) => (),
+            Err(ModelError::MailboxCmdFailed(code)) => return FaultOutcome::Rejected(code),
+            Err(ModelError::MailboxTimeout) => return FaultOutcome::Hang,
+            Err(_) => return fatal_error(model).map_or(FaultOutcome::Hang, FaultOutcome::Rejected),
+        }
+
+        if step_until(model, self.max_cycles, |m| {
+            m.soc_ifc().cptra_flow_status().read().ready_for_runtime()
+        }) {
+            FaultOutcome::Booted
+        } else {
+            FaultOutcome::Accepted
+        }
+    }
+}
+
+/// Faults chosen at random
+///
+/// # Arguments
+///
+/// * `rng` - Random number generator
+/// * `count` - Number of faults
+/// * `instrs` - Instruction counts to inject faults at, typically
+///              `ReferenceBoot::upload_instrs`
+pub fn random_faults(rng: &mut impl Rng, count: usize, instrs: Range<u64>) -> Vec<Fault> {
+    (0..count)
+        .map(|_| Fault {
+            trigger: FaultTrigger::InstrCount(rng.gen_range(instrs.clone())),
+            effect: match rng.gen_range(0..4) {
+                0 => FaultEffect::SkipInstr,
+                1 => FaultEffect::FlipRegBits {
+                    // Registers x1-x31; x0 cannot be corrupted
+                    reg: rng.gen_range(1u32..32).into(),
+                    mask: 1 << rng.gen_range(0..32),
+                },
+                2 => FaultEffect::CorruptLoad {
+                    mask: 1 << rng.gen_range(0..32),
+                },
+                _ => FaultEffect::InvertBranch,
+            },
+        })
+        .collect()
+}
+
+/// Outcome of every fault in a campaign
+pub struct CampaignReport {
+    pub results: Vec<(Fault, FaultOutcome)>,
+}
+
+impl CampaignReport {
+    /// Faults that got the image past verification
+    pub fn bypasses(&self) -> impl Iterator<Item = &(Fault, FaultOutcome)> {
+        self.results
+            .iter()
+            .filter(|(_, outcome)| outcome.is_bypass())
+    }
+}
+
+impl fmt::Display for CampaignReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let count = |pred: fn(&FaultOutcome) -> bool| {
+            self.results
+                .iter()
+                .filter(|(_, outcome)| pred(outcome))
+                .count()
+        };
+        writeln!(
+            f,
+            "{} faults: {} rejected, {} hung, {} not triggered, {} bypassed verification",
+            self.results.len(),
+            count(|o| matches!(o, FaultOutcome::Rejected(_))),
+            count(|o| *o == FaultOutcome::Hang),
+            count(|o| *o == FaultOutcome::NotTriggered),
+            count(FaultOutcome::is_bypass),
+        )?;
+        for (fault, outcome) in self.bypasses() {
+            writeln!(f, "  {fault}: {outcome:?}")?;
+        }
+        Ok(())
+    }
+}
+
+fn instr_count(model: &ModelEmulated) -> u64 {
+    model.fault_injector().map_or(0, FaultInjector::instr_count)
+}
+
+fn fatal_error(model: &mut ModelEmulated) -> Option<u32> {
+    match model.soc_ifc().cptra_fw_error_fatal().read() {
+        0 => None,
+        code => Some(code),
+    }
+}
+
+/// Step until `predicate` returns true or the firmware reports a fatal error,
+/// for at most `max_cycles` cycles. Returns true if `predicate` returned true.
+fn step_until(
+    model: &mut ModelEmulated,
+    max_cycles: u64,
+    mut predicate: impl FnMut(&mut ModelEmulated) -> bool,
+) -> bool {
+    for _ in 0..max_cycles {
+        if predicate(model) {
+            return true;
+        }
+        if fatal_error(model).is_some() {
+            return false;
+        }
+        model.step();
+    }
+    false
+}
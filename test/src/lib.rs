@@ -9,6 +9,7 @@ use caliptra_hw_model::{BootParams, DefaultHwModel, HwModel, InitParams};
 
 pub mod crypto;
 pub mod derive;
+pub mod fault_campaign;
 mod redact;
 mod unwrap_single;
 pub mod x509;
@@ -251,6 +251,8 @@ fn fake_boot_test() {
             fmc_fuse_svn: 7,
             lms_vendor_pub_key_index: u32::MAX,
             rom_verify_config: 0, // RomVerifyConfig::EcdsaOnly
+            pqc_key_type: image.manifest.preamble.pqc_key_type,
+            mldsa_vendor_pub_key_index: u32::MAX,
         }),
         &expected_ldevid_key,
     );
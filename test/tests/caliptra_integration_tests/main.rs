@@ -4,4 +4,6 @@ mod fake_collateral_boot_test;
 mod jtag_test;
 mod smoke_test;
 mod test_code_coverage;
+mod test_fault_injection;
+mod test_profile;
 mod warm_reset;
@@ -372,6 +372,8 @@ fn smoke_test() {
             fmc_fuse_svn: 7,
             lms_vendor_pub_key_index: image.manifest.header.vendor_lms_pub_key_idx,
             rom_verify_config: 1, // RomVerifyConfig::EcdsaAndLms
+            pqc_key_type: image.manifest.preamble.pqc_key_type,
+            mldsa_vendor_pub_key_index: u32::MAX,
         }),
         &expected_ldevid_key,
     );
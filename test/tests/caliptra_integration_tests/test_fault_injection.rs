@@ -0,0 +1,47 @@
+// Licensed under the Apache-2.0 license
+#[cfg(all(not(feature = "verilator"), not(feature = "fpga_realtime")))]
+#[test]
+fn test_fault_campaign() {
+    use caliptra_builder::{firmware, ImageOptions};
+    use caliptra_drivers::CaliptraError;
+    use caliptra_hw_model::Fuses;
+    use caliptra_test::fault_campaign::{random_faults, FaultCampaign, FaultOutcome};
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    // CPTRA_FAULT_CAMPAIGN_SIZE sets the number of faults; each one costs a
+    // full ROM boot
+    let size = std::env::var("CPTRA_FAULT_CAMPAIGN_SIZE")
+        .ok()
+        .and_then(|s| s.parse().ok())
+        .unwrap_or(4);
+
+    let rom = caliptra_builder::rom_for_fw_integration_tests().unwrap();
+    let mut image = caliptra_builder::build_and_sign_image(
+        &firmware::FMC_WITH_UART,
+        &firmware::APP_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+    // Corrupt the FMC after signing
+    image.fmc[0..4].copy_from_slice(&0xdeadbeef_u32.to_le_bytes());
+    let image = image.to_bytes().unwrap();
+
+    let campaign = FaultCampaign {
+        rom: &rom,
+        fw_image: &image,
+        fuses: Fuses::default(),
+        trng_seed: 0,
+        max_cycles: 20_000_000,
+    };
+    let reference = campaign.reference_boot();
+    assert_eq!(
+        reference.outcome,
+        FaultOutcome::Rejected(CaliptraError::IMAGE_VERIFIER_ERR_FMC_DIGEST_MISMATCH.into())
+    );
+
+    let faults = random_faults(&mut StdRng::seed_from_u64(0), size, reference.upload_instrs);
+    let report = campaign.run_all(faults);
+    println!("{report}");
+    assert_eq!(report.results.len(), size);
+}
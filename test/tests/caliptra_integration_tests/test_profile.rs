@@ -0,0 +1,53 @@
+// Licensed under the Apache-2.0 license
+#[cfg(all(not(feature = "verilator"), not(feature = "fpga_realtime")))]
+#[test]
+fn test_emu_call_stack_profile() {
+    use std::path::PathBuf;
+
+    use caliptra_api::SocManager;
+    use caliptra_builder::{firmware, ImageOptions};
+    use caliptra_common::mailbox_api::GetLdevCertReq;
+    use caliptra_coverage::{firmware_functions, write_folded_stacks, CPTRA_PROFILE_PATH};
+    use caliptra_hw_model::{BootParams, HwModel, InitParams};
+
+    let rom = caliptra_builder::build_firmware_rom(firmware::rom_from_env()).unwrap();
+    let image = caliptra_builder::build_and_sign_image(
+        &firmware::FMC_WITH_UART,
+        &firmware::APP_WITH_UART,
+        ImageOptions::default(),
+    )
+    .unwrap();
+
+    let mut hw = caliptra_hw_model::new_unbooted(InitParams {
+        rom: &rom,
+        ..Default::default()
+    })
+    .unwrap();
+    hw.start_call_stack_profile();
+    hw.boot(BootParams {
+        fw_image: Some(&image.to_bytes().unwrap()),
+        ..Default::default()
+    })
+    .unwrap();
+    hw.step_until(|m| m.soc_ifc().cptra_flow_status().read().ready_for_runtime());
+    hw.mailbox_execute_req(GetLdevCertReq::default()).unwrap();
+    let profile = hw.stop_cycle_profile().unwrap();
+
+    let functions = firmware_functions(&[
+        firmware::rom_from_env(),
+        &firmware::FMC_WITH_UART,
+        &firmware::APP_WITH_UART,
+    ])
+    .unwrap();
+    let folded = profile.folded_stacks(&functions);
+    assert_eq!(
+        folded.iter().map(|(_, cycles)| cycles).sum::<u64>(),
+        profile.total_cycles()
+    );
+    assert!(folded.iter().any(|(stack, _)| stack.contains(';')));
+
+    if let Ok(profile_path) = std::env::var(CPTRA_PROFILE_PATH) {
+        let path = PathBuf::from(profile_path).join("caliptra_boot_profile.folded");
+        write_folded_stacks(&path, &profile, &functions).unwrap();
+    }
+}
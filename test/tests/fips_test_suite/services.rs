@@ -9,6 +9,7 @@ use caliptra_drivers::CaliptraError;
 use caliptra_drivers::FipsTestHook;
 use caliptra_hw_model::{BootParams, HwModel, InitParams, ModelError, ShaAccMode};
 use caliptra_image_types::ImageManifest;
+use caliptra_runtime::RtBootStatus;
 use common::*;
 use dpe::{commands::*, context::ContextHandle, response::Response, DPE_PROFILE};
 use openssl::sha::{sha384, sha512};
@@ -813,3 +814,46 @@ pub fn fips_self_test_rt() {
     // SELF TEST GET RESULTS
     exec_cmd_self_test_get_results(&mut hw);
 }
+
+#[test]
+pub fn fips_self_test_rt_restart() {
+    let mut hw = fips_test_init_to_rt(None, None);
+    let payload = MailboxReqHeader {
+        chksum: caliptra_common::checksum::calc_checksum(
+            u32::from(CommandId::SELF_TEST_START),
+            &[],
+        ),
+    };
+
+    // SELF TEST START
+    exec_cmd_self_test_start(&mut hw);
+
+    // The self test cannot restart while it runs
+    match mbx_send_and_check_resp_hdr::<_, MailboxRespHeader>(
+        &mut hw,
+        u32::from(CommandId::SELF_TEST_START),
+        payload.as_bytes(),
+    ) {
+        Err(ModelError::MailboxCmdFailed(code)) => {
+            assert_eq!(
+                code,
+                u32::from(CaliptraError::RUNTIME_SELF_TEST_IN_PROGRESS)
+            );
+        }
+        other => panic!("Unexpected result {:?}", other.map(|_| ())),
+    }
+
+    // Wait for the self test to finish
+    hw.step_until(|m| {
+        m.soc_ifc().cptra_boot_status().read() == u32::from(RtBootStatus::RtFipSelfTestComplete)
+    });
+    for _ in 0..1000 {
+        hw.step();
+    }
+
+    // A finished self test is replaced without reading its result
+    exec_cmd_self_test_start(&mut hw);
+
+    // SELF TEST GET RESULTS
+    exec_cmd_self_test_get_results(&mut hw);
+}
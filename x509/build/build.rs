@@ -28,7 +28,7 @@ mod x509;
 use {
     code_gen::CodeGen,
     std::env,
-    x509::{EcdsaSha384Algo, Fwid, FwidParam, KeyUsage},
+    x509::{EcdsaSha384Algo, Fwid, FwidParam, KeyUsage, MlDsa87Algo, SigningAlgorithm},
 };
 
 // Main Entry point
@@ -38,58 +38,64 @@ fn main() {
         let out_dir_os_str = env::var_os("OUT_DIR").unwrap();
         let out_dir = out_dir_os_str.to_str().unwrap();
 
-        gen_init_devid_csr(out_dir);
-        gen_fmc_alias_csr(out_dir);
-        gen_local_devid_cert(out_dir);
-        gen_fmc_alias_cert(out_dir);
-        gen_rt_alias_cert(out_dir);
+        gen_init_devid_csr::<EcdsaSha384Algo>(out_dir, "InitDevIdCsrTbs");
+        gen_fmc_alias_csr::<EcdsaSha384Algo>(out_dir, "FmcAliasCsrTbs");
+        gen_local_devid_cert::<EcdsaSha384Algo>(out_dir, "LocalDevIdCertTbs");
+        gen_fmc_alias_cert::<EcdsaSha384Algo>(out_dir, "FmcAliasCertTbs");
+        gen_rt_alias_cert::<EcdsaSha384Algo>(out_dir, "RtAliasCertTbs");
+
+        gen_init_devid_csr::<MlDsa87Algo>(out_dir, "InitDevIdCsrTbsMlDsa87");
+        gen_fmc_alias_csr::<MlDsa87Algo>(out_dir, "FmcAliasCsrTbsMlDsa87");
+        gen_local_devid_cert::<MlDsa87Algo>(out_dir, "LocalDevIdCertTbsMlDsa87");
+        gen_fmc_alias_cert::<MlDsa87Algo>(out_dir, "FmcAliasCertTbsMlDsa87");
+        gen_rt_alias_cert::<MlDsa87Algo>(out_dir, "RtAliasCertTbsMlDsa87");
     }
 }
 
 /// Generated Initial DeviceId Cert Signing request Template
 #[cfg(feature = "generate_templates")]
-fn gen_init_devid_csr(out_dir: &str) {
+fn gen_init_devid_csr<Algo: SigningAlgorithm>(out_dir: &str, type_name: &str) {
     let mut usage = KeyUsage::default();
     usage.set_key_cert_sign(true);
-    let bldr = csr::CsrTemplateBuilder::<EcdsaSha384Algo>::new()
+    let bldr = csr::CsrTemplateBuilder::<Algo>::new()
         .add_basic_constraints_ext(true, 5)
         .add_key_usage_ext(usage)
         .add_ueid_ext(&[0xFF; 17]);
     let template = bldr.tbs_template("Caliptra 1.0 IDevID");
-    CodeGen::gen_code("InitDevIdCsrTbs", template, out_dir);
+    CodeGen::gen_code(type_name, template, out_dir);
 }
 
 #[cfg(feature = "generate_templates")]
-fn gen_fmc_alias_csr(out_dir: &str) {
+fn gen_fmc_alias_csr<Algo: SigningAlgorithm>(out_dir: &str, type_name: &str) {
     let mut usage = KeyUsage::default();
     usage.set_key_cert_sign(true);
-    let bldr = csr::CsrTemplateBuilder::<EcdsaSha384Algo>::new()
+    let bldr = csr::CsrTemplateBuilder::<Algo>::new()
         .add_basic_constraints_ext(true, 5)
         .add_key_usage_ext(usage)
         .add_ueid_ext(&[0xFF; 17]);
     let template = bldr.tbs_template("Caliptra 1.0 FMC Alias");
-    CodeGen::gen_code("FmcAliasCsrTbs", template, out_dir);
+    CodeGen::gen_code(type_name, template, out_dir);
 }
 
 /// Generate Local DeviceId Certificate Template
 #[cfg(feature = "generate_templates")]
-fn gen_local_devid_cert(out_dir: &str) {
+fn gen_local_devid_cert<Algo: SigningAlgorithm>(out_dir: &str, type_name: &str) {
     let mut usage = KeyUsage::default();
     usage.set_key_cert_sign(true);
-    let bldr = cert::CertTemplateBuilder::<EcdsaSha384Algo>::new()
+    let bldr = cert::CertTemplateBuilder::<Algo>::new()
         .add_basic_constraints_ext(true, 4)
         .add_key_usage_ext(usage)
         .add_ueid_ext(&[0xFF; 17]);
     let template = bldr.tbs_template("Caliptra 1.0 LDevID", "Caliptra 1.0 IDevID");
-    CodeGen::gen_code("LocalDevIdCertTbs", template, out_dir);
+    CodeGen::gen_code(type_name, template, out_dir);
 }
 
 /// Generate FMC Alias Certificate Template
 #[cfg(feature = "generate_templates")]
-fn gen_fmc_alias_cert(out_dir: &str) {
+fn gen_fmc_alias_cert<Algo: SigningAlgorithm>(out_dir: &str, type_name: &str) {
     let mut usage = KeyUsage::default();
     usage.set_key_cert_sign(true);
-    let bldr = cert::CertTemplateBuilder::<EcdsaSha384Algo>::new()
+    let bldr = cert::CertTemplateBuilder::<Algo>::new()
         .add_basic_constraints_ext(true, 3)
         .add_key_usage_ext(usage)
         .add_ueid_ext(&[0xFF; 17])
@@ -112,18 +118,18 @@ fn gen_fmc_alias_cert(out_dir: &str) {
             }],
         );
     let template = bldr.tbs_template("Caliptra 1.0 FMC Alias", "Caliptra 1.0 LDevID");
-    CodeGen::gen_code("FmcAliasCertTbs", template, out_dir);
+    CodeGen::gen_code(type_name, template, out_dir);
 }
 
 /// Generate FMC Alias Certificate Template
 #[cfg(feature = "generate_templates")]
-fn gen_rt_alias_cert(out_dir: &str) {
+fn gen_rt_alias_cert<Algo: SigningAlgorithm>(out_dir: &str, type_name: &str) {
     let mut usage = KeyUsage::default();
     // Add KeyCertSign to allow signing of other certs
     usage.set_key_cert_sign(true);
     // Add DigitalSignature to allow signing of firmware
     usage.set_digital_signature(true);
-    let bldr = cert::CertTemplateBuilder::<EcdsaSha384Algo>::new()
+    let bldr = cert::CertTemplateBuilder::<Algo>::new()
         // Basic Constraints : CA = true, PathLen = 2
         .add_basic_constraints_ext(true, 2)
         .add_key_usage_ext(usage)
@@ -136,5 +142,5 @@ fn gen_rt_alias_cert(out_dir: &str) {
             },
         }]);
     let template = bldr.tbs_template("Caliptra 1.0 Rt Alias", "Caliptra 1.0 FMC Alias");
-    CodeGen::gen_code("RtAliasCertTbs", template, out_dir);
+    CodeGen::gen_code(type_name, template, out_dir);
 }
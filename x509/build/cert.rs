@@ -35,6 +35,12 @@ pub struct CertTemplateBuilder<Algo: SigningAlgorithm> {
 }
 
 impl<Algo: SigningAlgorithm> CertTemplateBuilder<Algo> {
+    /// Index of the signature AlgorithmIdentifier in the TBSCertificate
+    const SIG_ALG_IDX: usize = 2;
+
+    /// Index of the SubjectPublicKeyInfo in the TBSCertificate
+    const SPKI_IDX: usize = 6;
+
     // Create an instance of `CertificateTemplateBuilder`
     pub fn new() -> Self {
         Self {
@@ -163,11 +169,7 @@ impl<Algo: SigningAlgorithm> CertTemplateBuilder<Algo> {
     ///
     /// * `key_id` - Key Id
     fn add_subj_key_id_ext(&mut self, key_id: &[u8]) {
-        self.exts
-            .push(x509::make_subj_key_id_ext(
-                &self.builder.x509v3_context(None, None),
-            ))
-            .unwrap();
+        self.exts.push(x509::make_subj_key_id_ext(key_id)).unwrap();
 
         let param = CertTemplateParam {
             tbs_param: TbsParam::new("SUBJECT_KEY_ID", 0, key_id.len()),
@@ -293,6 +295,17 @@ impl<Algo: SigningAlgorithm> CertTemplateBuilder<Algo> {
         // Retrieve the To be signed portion from the Certificate
         let mut tbs = x509::get_tbs(der);
 
+        // Substitute the algorithm identifiers OpenSSL could not produce
+        if let Some(alg_id) = self.algo.alg_id_override() {
+            tbs = x509::replace_alg_ids(
+                &tbs,
+                Some(Self::SIG_ALG_IDX),
+                Self::SPKI_IDX,
+                &alg_id,
+                subject_key.pub_key(),
+            );
+        }
+
         // Match long params first to ensure a subset is not sanitized by a short param.
         self.params
             .sort_by(|a, b| a.needle.len().cmp(&b.needle.len()).reverse());
@@ -33,6 +33,9 @@ pub struct CsrTemplateBuilder<Algo: SigningAlgorithm> {
 }
 
 impl<Algo: SigningAlgorithm> CsrTemplateBuilder<Algo> {
+    /// Index of the SubjectPublicKeyInfo in the CertificationRequestInfo
+    const SPKI_IDX: usize = 2;
+
     // Create an instance of `CertificateTemplateBuilder`
     pub fn new() -> Self {
         Self {
@@ -130,6 +133,11 @@ impl<Algo: SigningAlgorithm> CsrTemplateBuilder<Algo> {
         // Retrieve the To be signed portion from the CSR
         let mut tbs = x509::get_tbs(der);
 
+        // Substitute the algorithm identifiers OpenSSL could not produce
+        if let Some(alg_id) = self.algo.alg_id_override() {
+            tbs = x509::replace_alg_ids(&tbs, None, Self::SPKI_IDX, &alg_id, key.pub_key());
+        }
+
         // Calculate the offset of parameters and sanitize the TBS section
         let params = self
             .params
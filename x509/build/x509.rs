@@ -25,13 +25,12 @@ use openssl::hash::MessageDigest;
 use openssl::nid::Nid;
 use openssl::pkey::PKey;
 use openssl::pkey::Private;
+use openssl::rand::rand_bytes;
 use openssl::sha::Sha1;
 use openssl::sha::Sha256;
 use openssl::x509::extension::BasicConstraints;
 use openssl::x509::extension::KeyUsage as Usage;
-use openssl::x509::extension::SubjectKeyIdentifier;
 use openssl::x509::X509Extension;
-use openssl::x509::X509v3Context;
 
 use crate::tbs::TbsParam;
 
@@ -40,6 +39,7 @@ const FLAG_MASK: u32 = dice::FLAG_BIT_NOT_CONFIGURED
     | dice::FLAG_BIT_DEBUG
     | dice::FLAG_BIT_FIXED_WIDTH;
 
+const SUBJ_KEY_ID_OID: &str = "2.5.29.14";
 const AUTH_KEY_ID_OID: &str = "2.5.29.35";
 const TCG_UEID_OID: &str = "2.23.133.5.4.4";
 const TCG_TCB_INFO_OID: &str = "2.23.133.5.4.1";
@@ -117,6 +117,13 @@ pub trait SigningAlgorithm: Default {
     fn digest(&self) -> MessageDigest {
         Self::Digest::algo()
     }
+
+    /// DER encoded AlgorithmIdentifier to substitute into the TBS for
+    /// algorithms OpenSSL cannot sign with. `None` keeps the signature and
+    /// public key algorithms emitted by OpenSSL.
+    fn alg_id_override(&self) -> Option<Vec<u8>> {
+        None
+    }
 }
 
 /// ECC-348 Asymmetric Key Pair
@@ -176,6 +183,64 @@ impl SigningAlgorithm for EcdsaSha384Algo {
     }
 }
 
+/// ML-DSA-87 Asymmetric Key Pair
+///
+/// The OpenSSL bindings cannot generate or sign with ML-DSA keys. The public
+/// key is a random placeholder of the correct size and an ECC-384 key is used
+/// to let OpenSSL produce the TBS; the algorithm identifiers are replaced
+/// afterwards (see `SigningAlgorithm::alg_id_override`).
+pub struct MlDsa87AsymKey {
+    placeholder_key: Ecc384AsymKey,
+    pub_key: Vec<u8>,
+}
+
+impl MlDsa87AsymKey {
+    /// ML-DSA-87 public key size in bytes
+    pub const PUB_KEY_SIZE: usize = 2592;
+}
+
+impl AsymKey for MlDsa87AsymKey {
+    /// Retrieve Private Key
+    fn priv_key(&self) -> &PKey<Private> {
+        self.placeholder_key.priv_key()
+    }
+
+    /// Retrieve Public Key
+    fn pub_key(&self) -> &[u8] {
+        &self.pub_key
+    }
+}
+
+impl Default for MlDsa87AsymKey {
+    /// Returns the "default value" for a type.
+    fn default() -> Self {
+        let mut pub_key = vec![0u8; Self::PUB_KEY_SIZE];
+        rand_bytes(&mut pub_key).unwrap();
+        Self {
+            placeholder_key: Ecc384AsymKey::default(),
+            pub_key,
+        }
+    }
+}
+
+#[derive(Default)]
+pub struct MlDsa87Algo {}
+
+impl SigningAlgorithm for MlDsa87Algo {
+    type AsymKey = MlDsa87AsymKey;
+    type Digest = Sha384;
+
+    fn gen_key(&self) -> Self::AsymKey {
+        Self::AsymKey::default()
+    }
+
+    fn alg_id_override(&self) -> Option<Vec<u8>> {
+        // AlgorithmIdentifier with id-ml-dsa-87 and absent parameters
+        let oid = asn1::oid!(2, 16, 840, 1, 101, 3, 4, 3, 19);
+        Some(der_encode(0x30, &asn1::write_single(&oid).unwrap()))
+    }
+}
+
 bitfield::bitfield! {
     #[derive(Default, Debug, PartialEq, Eq, Clone, Copy)]
     /// Key Usage
@@ -267,8 +332,11 @@ pub fn make_tcg_ueid_ext(ueid: &[u8]) -> X509Extension {
 }
 
 /// Make Subject Key ID extension
-pub fn make_subj_key_id_ext(ctx: &X509v3Context) -> X509Extension {
-    SubjectKeyIdentifier::new().build(ctx).unwrap()
+pub fn make_subj_key_id_ext(key_id: &[u8]) -> X509Extension {
+    let der = asn1::write_single(&key_id).unwrap();
+    let der = Asn1OctetString::new_from_bytes(&der).unwrap();
+    let oid = Asn1Object::from_str(SUBJ_KEY_ID_OID).unwrap();
+    X509Extension::new_from_der(&oid, false, &der).unwrap()
 }
 
 /// Make Authority Key ID extension
@@ -417,6 +485,78 @@ pub fn get_tbs(der: Vec<u8>) -> Vec<u8> {
     der[tbs_offset..tbs_offset + tbs_len].to_vec()
 }
 
+/// Encode a DER tag-length-value
+fn der_encode(tag: u8, value: &[u8]) -> Vec<u8> {
+    let mut der = vec![tag];
+    match value.len() {
+        0..=0x7F => der.push(value.len() as u8),
+        0x80..=0xFF => der.extend_from_slice(&[0x81, value.len() as u8]),
+        0x100..=0xFFFF => {
+            der.push(0x82);
+            der.extend_from_slice(&(value.len() as u16).to_be_bytes());
+        }
+        _ => panic!("Unsupported DER Length"),
+    }
+    der.extend_from_slice(value);
+    der
+}
+
+/// Split the contents of a DER sequence into its encoded elements
+fn der_elements(der: &[u8]) -> Vec<&[u8]> {
+    let header_len = |buf: &[u8]| match buf[1] {
+        0..=0x7F => (2, buf[1] as usize),
+        0x81 => (3, buf[2] as usize),
+        0x82 => (4, u16::from_be_bytes([buf[2], buf[3]]) as usize),
+        _ => panic!("Unsupported DER Length"),
+    };
+
+    let (hdr, len) = header_len(der);
+    let mut contents = &der[hdr..hdr + len];
+    let mut elements = vec![];
+    while !contents.is_empty() {
+        let (hdr, len) = header_len(contents);
+        let (element, rest) = contents.split_at(hdr + len);
+        elements.push(element);
+        contents = rest;
+    }
+    elements
+}
+
+/// Replace the signature algorithm and subject public key info of a TBS
+///
+/// # Arguments
+///
+/// * `tbs`         - DER encoded TBS sequence
+/// * `sig_alg_idx` - Index of the signature AlgorithmIdentifier, if present
+/// * `spki_idx`    - Index of the SubjectPublicKeyInfo
+/// * `alg_id`      - DER encoded AlgorithmIdentifier to substitute
+/// * `pub_key`     - Raw subject public key
+pub fn replace_alg_ids(
+    tbs: &[u8],
+    sig_alg_idx: Option<usize>,
+    spki_idx: usize,
+    alg_id: &[u8],
+    pub_key: &[u8],
+) -> Vec<u8> {
+    let mut bit_str = vec![0u8];
+    bit_str.extend_from_slice(pub_key);
+    let mut spki = alg_id.to_vec();
+    spki.extend(der_encode(0x03, &bit_str));
+    let spki = der_encode(0x30, &spki);
+
+    let mut contents = vec![];
+    for (idx, element) in der_elements(tbs).into_iter().enumerate() {
+        if Some(idx) == sig_alg_idx {
+            contents.extend_from_slice(alg_id);
+        } else if idx == spki_idx {
+            contents.extend_from_slice(&spki);
+        } else {
+            contents.extend_from_slice(element);
+        }
+    }
+    der_encode(0x30, &contents)
+}
+
 /// Initialize template parameter with its offset
 pub fn init_param(needle: &[u8], haystack: &[u8], param: TbsParam) -> TbsParam {
     assert_eq!(needle.len(), param.len);
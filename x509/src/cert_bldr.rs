@@ -9,7 +9,7 @@ File Name:
 Abstract:
 
     X509 API to construct Certificate or Certificate Signing Request
-    from "To Be Signed" blob and ECDSA-384 Signature.
+    from "To Be Signed" blob and ECDSA-384 or ML-DSA-87 Signature.
 
 --*/
 
@@ -283,3 +283,117 @@ impl<'a> Ecdsa384CertBuilder<'a> {
         Some(1 + len_bytes + len)
     }
 }
+
+pub type MlDsa87CsrBuilder<'a> = MlDsa87CertBuilder<'a>;
+
+/// ML-DSA-87 Signature length
+pub const MLDSA87_SIG_LEN: usize = 4627;
+
+/// ML-DSA-87 Certificate Builder
+#[derive(Debug)]
+pub struct MlDsa87CertBuilder<'a> {
+    /// DER encoded To be signed portion
+    tbs: &'a [u8],
+
+    /// Raw ML-DSA-87 Signature
+    sig: &'a [u8; MLDSA87_SIG_LEN],
+
+    /// Length of the signed Cert/CSR
+    len: usize,
+}
+
+impl<'a> MlDsa87CertBuilder<'a> {
+    // DER Encoded Sequence with id-ml-dsa-87 OID
+    const OID_DER: [u8; 13] = [
+        0x30, 0x0B, 0x06, 0x09, 0x60, 0x86, 0x48, 0x01, 0x65, 0x03, 0x04, 0x03, 0x13,
+    ];
+
+    // DER Bit String header of the signature: TAG, LEN (2 bytes), unused bits
+    const SIG_HDR_DER: [u8; 5] = [
+        DER_BIT_STR_TAG,
+        0x82,
+        ((MLDSA87_SIG_LEN + 1) >> u8::BITS) as u8,
+        (MLDSA87_SIG_LEN + 1) as u8,
+        0x00,
+    ];
+
+    /// Create an instance of `MlDsa87CertBuilder`
+    ///
+    /// # Arguments
+    ///
+    /// * `tbs` - DER encoded To be signed portion
+    /// * `sig` - Signature of the To be signed portion
+    pub fn new(tbs: &'a [u8], sig: &'a [u8; MLDSA87_SIG_LEN]) -> Option<Self> {
+        let len = Self::compute_len(tbs.len())?;
+        Some(Self { tbs, sig, len })
+    }
+
+    /// Build the Certificate or Certificate Signing Request
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - Buffer to construct the certificate in
+    pub fn build(&self, buf: &mut [u8]) -> Option<usize> {
+        if buf.len() < self.len {
+            None?;
+        }
+
+        let mut pos = 0;
+
+        // Copy Tag
+        *buf.get_mut(pos)? = DER_SEQ_TAG;
+        pos += 1;
+
+        // Copy Length
+        let len = self.tbs.len() + Self::OID_DER.len() + Self::SIG_HDR_DER.len() + self.sig.len();
+        *buf.get_mut(pos)? = 0x82;
+        *buf.get_mut(pos + 1)? = (len >> u8::BITS) as u8;
+        *buf.get_mut(pos + 2)? = (len as u8) & u8::MAX;
+        pos += 3;
+
+        // Copy Value
+
+        // Copy TBS DER
+        buf.get_mut(pos..pos + self.tbs.len())?
+            .copy_from_slice(self.tbs);
+        pos += self.tbs.len();
+
+        // Copy OID DER
+        buf.get_mut(pos..pos + Self::OID_DER.len())?
+            .copy_from_slice(&Self::OID_DER);
+        pos += Self::OID_DER.len();
+
+        // Copy Signature DER
+        buf.get_mut(pos..pos + Self::SIG_HDR_DER.len())?
+            .copy_from_slice(&Self::SIG_HDR_DER);
+        pos += Self::SIG_HDR_DER.len();
+        buf.get_mut(pos..pos + self.sig.len())?
+            .copy_from_slice(self.sig);
+        pos += self.sig.len();
+
+        Some(pos)
+    }
+
+    /// Return the length of Certificate or Certificate Signing Request
+    #[allow(clippy::len_without_is_empty)]
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    // Compute length of the X509 certificate or cert signing request
+    fn compute_len(tbs_len: usize) -> Option<usize> {
+        let len = tbs_len + Self::OID_DER.len() + Self::SIG_HDR_DER.len() + MLDSA87_SIG_LEN;
+
+        // The signature alone requires a two byte length
+        if len > u16::MAX as usize {
+            None?;
+        }
+
+        //
+        // Length of the CSR or Certificate
+        //
+        // Certificate is encoded as DER Sequence
+        // TAG (1 byte) + LEN (3 bytes) + len (Length of the sequence contents)
+        Some(1 + 3 + len)
+    }
+}
@@ -17,6 +17,13 @@ Abstract:
 include!(concat!(env!("OUT_DIR"), "/init_dev_id_csr_tbs.rs"));
 #[cfg(not(feature = "generate_templates"))]
 include! {"../build/init_dev_id_csr_tbs.rs"}
+#[cfg(feature = "generate_templates")]
+include!(concat!(
+    env!("OUT_DIR"),
+    "/init_dev_id_csr_tbs_ml_dsa_87.rs"
+));
+#[cfg(not(feature = "generate_templates"))]
+include! {"../build/init_dev_id_csr_tbs_ml_dsa_87.rs"}
 
 #[cfg(all(test, target_family = "unix"))]
 mod tests {
@@ -17,6 +17,13 @@ Abstract:
 include!(concat!(env!("OUT_DIR"), "/local_dev_id_cert_tbs.rs"));
 #[cfg(not(feature = "generate_templates"))]
 include! {"../build/local_dev_id_cert_tbs.rs"}
+#[cfg(feature = "generate_templates")]
+include!(concat!(
+    env!("OUT_DIR"),
+    "/local_dev_id_cert_tbs_ml_dsa_87.rs"
+));
+#[cfg(not(feature = "generate_templates"))]
+include! {"../build/local_dev_id_cert_tbs_ml_dsa_87.rs"}
 
 #[cfg(all(test, target_family = "unix"))]
 mod tests {
@@ -167,6 +174,50 @@ mod tests {
         assert!(!ext_map[&UEID_OID].critical);
     }
 
+    #[test]
+    fn test_mldsa87_cert() {
+        let public_key = [0x5Au8; LocalDevIdCertTbsMlDsa87Params::PUBLIC_KEY_LEN];
+        let params = LocalDevIdCertTbsMlDsa87Params {
+            serial_number: &[0xABu8; LocalDevIdCertTbsMlDsa87Params::SERIAL_NUMBER_LEN],
+            public_key: &public_key,
+            subject_sn: &[b'A'; LocalDevIdCertTbsMlDsa87Params::SUBJECT_SN_LEN],
+            issuer_sn: &[b'B'; LocalDevIdCertTbsMlDsa87Params::ISSUER_SN_LEN],
+            ueid: &TEST_UEID.try_into().unwrap(),
+            subject_key_id: &[0xCDu8; LocalDevIdCertTbsMlDsa87Params::SUBJECT_KEY_ID_LEN],
+            authority_key_id: &[0xEFu8; LocalDevIdCertTbsMlDsa87Params::AUTHORITY_KEY_ID_LEN],
+            not_before: &NotBefore::default().value,
+            not_after: &NotAfter::default().value,
+        };
+        let cert = LocalDevIdCertTbsMlDsa87::new(&params);
+
+        let sig = [0x11u8; crate::MLDSA87_SIG_LEN];
+        let builder = crate::MlDsa87CertBuilder::new(cert.tbs(), &sig).unwrap();
+        let mut buf = vec![0u8; builder.len()];
+        assert_eq!(builder.build(&mut buf), Some(buf.len()));
+
+        let mut parser = X509CertificateParser::new().with_deep_parse_extensions(true);
+        let parsed_cert = match parser.parse(&buf) {
+            Ok((_, parsed_cert)) => parsed_cert,
+            Err(e) => panic!("x509 parsing failed: {:?}", e),
+        };
+
+        const ML_DSA_87_OID: Oid = oid!(2.16.840 .1 .101 .3 .4 .3 .19);
+        assert_eq!(parsed_cert.signature_algorithm.algorithm, ML_DSA_87_OID);
+        assert_eq!(
+            parsed_cert.tbs_certificate.signature.algorithm,
+            ML_DSA_87_OID
+        );
+        assert_eq!(parsed_cert.public_key().algorithm.algorithm, ML_DSA_87_OID);
+        assert_eq!(
+            parsed_cert.public_key().subject_public_key.data,
+            &public_key[..]
+        );
+        assert_eq!(parsed_cert.signature_value.data, &sig[..]);
+
+        let basic_constraints = parsed_cert.basic_constraints().unwrap().unwrap();
+        assert!(basic_constraints.value.ca);
+    }
+
     #[test]
     #[cfg(feature = "generate_templates")]
     fn test_ldevid_template() {
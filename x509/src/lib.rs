@@ -22,12 +22,26 @@ mod ldevid_cert;
 mod rt_alias_cert;
 mod test_util;
 
-pub use cert_bldr::{Ecdsa384CertBuilder, Ecdsa384CsrBuilder, Ecdsa384Signature};
-pub use fmc_alias_cert::{FmcAliasCertTbs, FmcAliasCertTbsParams};
-pub use fmc_alias_csr::{FmcAliasCsrTbs, FmcAliasCsrTbsParams};
-pub use idevid_csr::{InitDevIdCsrTbs, InitDevIdCsrTbsParams};
-pub use ldevid_cert::{LocalDevIdCertTbs, LocalDevIdCertTbsParams};
-pub use rt_alias_cert::{RtAliasCertTbs, RtAliasCertTbsParams};
+pub use cert_bldr::{
+    Ecdsa384CertBuilder, Ecdsa384CsrBuilder, Ecdsa384Signature, MlDsa87CertBuilder,
+    MlDsa87CsrBuilder, MLDSA87_SIG_LEN,
+};
+pub use fmc_alias_cert::{
+    FmcAliasCertTbs, FmcAliasCertTbsMlDsa87, FmcAliasCertTbsMlDsa87Params, FmcAliasCertTbsParams,
+};
+pub use fmc_alias_csr::{
+    FmcAliasCsrTbs, FmcAliasCsrTbsMlDsa87, FmcAliasCsrTbsMlDsa87Params, FmcAliasCsrTbsParams,
+};
+pub use idevid_csr::{
+    InitDevIdCsrTbs, InitDevIdCsrTbsMlDsa87, InitDevIdCsrTbsMlDsa87Params, InitDevIdCsrTbsParams,
+};
+pub use ldevid_cert::{
+    LocalDevIdCertTbs, LocalDevIdCertTbsMlDsa87, LocalDevIdCertTbsMlDsa87Params,
+    LocalDevIdCertTbsParams,
+};
+pub use rt_alias_cert::{
+    RtAliasCertTbs, RtAliasCertTbsMlDsa87, RtAliasCertTbsMlDsa87Params, RtAliasCertTbsParams,
+};
 use zeroize::Zeroize;
 
 pub const NOT_BEFORE: &str = "20230101000000Z";
@@ -17,6 +17,10 @@ Abstract:
 include!(concat!(env!("OUT_DIR"), "/rt_alias_cert_tbs.rs"));
 #[cfg(not(feature = "generate_templates"))]
 include! {"../build/rt_alias_cert_tbs.rs"}
+#[cfg(feature = "generate_templates")]
+include!(concat!(env!("OUT_DIR"), "/rt_alias_cert_tbs_ml_dsa_87.rs"));
+#[cfg(not(feature = "generate_templates"))]
+include! {"../build/rt_alias_cert_tbs_ml_dsa_87.rs"}
 
 #[cfg(all(test, target_family = "unix"))]
 mod tests {